@@ -0,0 +1,161 @@
+package risor
+
+import (
+	"crypto/sha256"
+	"encoding/hex"
+	"fmt"
+	"os"
+	"path/filepath"
+	"runtime/debug"
+	"strings"
+	"sync"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/bytecode"
+)
+
+// compileCacheFormat is part of every cache key. Change it when the format
+// of cache entries changes.
+const compileCacheFormat = "risor-compile-cache-1"
+
+// CompileCache stores compiled bytecode on disk so that a script that has not
+// changed is not parsed and compiled again. Enable it with WithCompileCache.
+//
+// Entries are keyed by a SHA-256 hash of the source code, the filename, the
+// names of the globals in the environment, how undefined names are handled,
+// the syntax configuration, and the build of the running program, so any
+// change to these compiles the script afresh. Old entries are never used
+// again, but are not deleted; call Clear to remove them. Compilations that
+// use custom validators or transformers are not cached, since their behavior
+// can't be part of the key.
+//
+// Failures to read or write the cache are ignored, and corrupt entries are
+// treated as missing, so the cache never causes compilation to fail. A
+// CompileCache is safe for concurrent use, including by multiple processes
+// sharing a directory.
+type CompileCache struct {
+	dir string
+}
+
+// NewCompileCache returns a CompileCache that stores entries in dir. The
+// directory is created when the first entry is written.
+func NewCompileCache(dir string) *CompileCache {
+	return &CompileCache{dir: dir}
+}
+
+// DefaultCompileCacheDir returns the default cache directory, "risor" within
+// the user's cache directory (for example ~/.cache/risor on Linux).
+func DefaultCompileCacheDir() (string, error) {
+	dir, err := os.UserCacheDir()
+	if err != nil {
+		return "", err
+	}
+	return filepath.Join(dir, "risor"), nil
+}
+
+// Dir returns the directory holding the cache entries.
+func (c *CompileCache) Dir() string {
+	return c.dir
+}
+
+// Clear removes all entries from the cache.
+func (c *CompileCache) Clear() error {
+	entries, err := filepath.Glob(filepath.Join(c.dir, "*.json"))
+	if err != nil {
+		return err
+	}
+	for _, entry := range entries {
+		if err := os.Remove(entry); err != nil && !os.IsNotExist(err) {
+			return err
+		}
+	}
+	return nil
+}
+
+// key returns the cache key for compiling source with the given options.
+func (c *CompileCache) key(source string, o *options) string {
+	h := sha256.New()
+	cfg := o.compilerConfig()
+	for _, part := range []string{
+		compileCacheFormat,
+		buildID(),
+		cfg.Filename,
+		strings.Join(cfg.GlobalNames, ","),
+		fmt.Sprint(cfg.UndefinedNames),
+		cfg.LanguageVersion,
+		fmt.Sprintf("%+v", o.syntaxConfig),
+		source,
+	} {
+		fmt.Fprintf(h, "%d:%s\n", len(part), part)
+	}
+	return hex.EncodeToString(h.Sum(nil))
+}
+
+// path returns the path of the entry with the given key.
+func (c *CompileCache) path(key string) string {
+	return filepath.Join(c.dir, key+".json")
+}
+
+// get returns the cached code for a key, if there is a usable entry.
+func (c *CompileCache) get(key string) (*bytecode.Code, bool) {
+	data, err := os.ReadFile(c.path(key))
+	if err != nil {
+		return nil, false
+	}
+	code, err := bytecode.Unmarshal(data)
+	if err != nil {
+		return nil, false
+	}
+	return code, true
+}
+
+// put stores code under a key. The entry is written to a temporary file and
+// renamed into place, so readers never see a partial entry.
+func (c *CompileCache) put(key string, code *bytecode.Code) error {
+	data, err := bytecode.Marshal(code)
+	if err != nil {
+		return err
+	}
+	if err := os.MkdirAll(c.dir, 0o755); err != nil {
+		return err
+	}
+	tmp, err := os.CreateTemp(c.dir, "tmp-*")
+	if err != nil {
+		return err
+	}
+	defer os.Remove(tmp.Name())
+	if _, err := tmp.Write(data); err != nil {
+		tmp.Close()
+		return err
+	}
+	if err := tmp.Close(); err != nil {
+		return err
+	}
+	return os.Rename(tmp.Name(), c.path(key))
+}
+
+// buildID identifies the build of the running program, so that cache entries
+// written by a different version of the compiler are not used.
+var buildID = sync.OnceValue(func() string {
+	var parts []string
+	if info, ok := debug.ReadBuildInfo(); ok {
+		parts = append(parts, info.GoVersion, info.Main.Path, info.Main.Version)
+		for _, dep := range info.Deps {
+			if dep.Path == "github.com/deepnoodle-ai/risor/v2" {
+				parts = append(parts, dep.Version, dep.Sum)
+			}
+		}
+		for _, setting := range info.Settings {
+			if strings.HasPrefix(setting.Key, "vcs.") {
+				parts = append(parts, setting.Value)
+			}
+		}
+	}
+	// Development builds share a version, so the executable itself
+	// distinguishes one build from the next
+	if exe, err := os.Executable(); err == nil {
+		if stat, err := os.Stat(exe); err == nil {
+			parts = append(parts, fmt.Sprint(stat.Size(), stat.ModTime().UnixNano()))
+		}
+	}
+	return strings.Join(parts, " ")
+})
@@ -0,0 +1,98 @@
+package risor
+
+import (
+	"context"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/ast"
+	"github.com/deepnoodle-ai/risor/v2/pkg/bytecode"
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+// cacheEntries returns the entry files in a compile cache directory.
+func cacheEntries(t *testing.T, dir string) []string {
+	t.Helper()
+	entries, err := filepath.Glob(filepath.Join(dir, "*.json"))
+	assert.Nil(t, err)
+	return entries
+}
+
+func TestCompileCache(t *testing.T) {
+	ctx := context.Background()
+	dir := t.TempDir()
+	cache := NewCompileCache(dir)
+
+	result, err := Eval(ctx, `1 + 1`, WithCompileCache(cache))
+	assert.Nil(t, err)
+	assert.Equal(t, result, int64(2))
+	entries := cacheEntries(t, dir)
+	assert.Len(t, entries, 1)
+
+	// Replace the entry with different bytecode to show that it is used
+	other, err := Compile(ctx, `40 + 2`)
+	assert.Nil(t, err)
+	data, err := bytecode.Marshal(other)
+	assert.Nil(t, err)
+	assert.Nil(t, os.WriteFile(entries[0], data, 0o644))
+	result, err = Eval(ctx, `1 + 1`, WithCompileCache(cache))
+	assert.Nil(t, err)
+	assert.Equal(t, result, int64(42))
+
+	// A corrupt entry is ignored and replaced
+	assert.Nil(t, os.WriteFile(entries[0], []byte("{not json"), 0o644))
+	result, err = Eval(ctx, `1 + 1`, WithCompileCache(cache))
+	assert.Nil(t, err)
+	assert.Equal(t, result, int64(2))
+	assert.Len(t, cacheEntries(t, dir), 1)
+
+	assert.Nil(t, cache.Clear())
+	assert.Len(t, cacheEntries(t, dir), 0)
+}
+
+func TestCompileCacheKey(t *testing.T) {
+	ctx := context.Background()
+	dir := t.TempDir()
+	cache := NewCompileCache(dir)
+
+	compile := func(source string, opts ...Option) {
+		t.Helper()
+		_, err := Compile(ctx, source, append(opts, WithCompileCache(cache))...)
+		assert.Nil(t, err)
+	}
+	compile(`x + 1`, WithEnv(map[string]any{"x": 1}))
+	compile(`x + 1`, WithEnv(map[string]any{"x": 2}))
+	assert.Len(t, cacheEntries(t, dir), 1)
+
+	// Changes to the source, globals, filename, or language version
+	// invalidate the entry
+	compile(`x + 2`, WithEnv(map[string]any{"x": 1}))
+	compile(`x + 1`, WithEnv(map[string]any{"x": 1, "y": 2}))
+	compile(`x + 1`, WithEnv(map[string]any{"x": 1}), WithFilename("main.risor"))
+	compile(`x + 1`, WithEnv(map[string]any{"x": 1}), WithLanguageVersion("2.1"))
+	assert.Len(t, cacheEntries(t, dir), 5)
+
+	// Compilations with custom validators are not cached
+	validator := ValidatorFunc(func(p *ast.Program) []ValidationError { return nil })
+	compile(`x + 3`, WithEnv(map[string]any{"x": 1}), WithValidator(validator))
+	assert.Len(t, cacheEntries(t, dir), 5)
+
+	// Failed compilations are not cached
+	_, err := Compile(ctx, `let`, WithCompileCache(cache))
+	assert.NotNil(t, err)
+	assert.Len(t, cacheEntries(t, dir), 5)
+}
+
+func TestCompileCacheErrors(t *testing.T) {
+	// Errors from cached code still point at the source
+	ctx := context.Background()
+	cache := NewCompileCache(t.TempDir())
+	source := "let x = 1\nthrow error(\"boom\")"
+	for i := 0; i < 2; i++ {
+		_, err := Eval(ctx, source, WithEnv(Builtins()), WithFilename("main.risor"),
+			WithCompileCache(cache))
+		assert.NotNil(t, err)
+		assert.Contains(t, err.Error(), "boom")
+	}
+}
@@ -10,22 +10,23 @@ import (
 
 // Risor keywords for completion
 var risorKeywords = []string{
-	"catch", "const", "else", "false", "finally",
-	"function", "if", "in", "let", "match", "nil", "not", "null", "return", "struct",
-	"throw", "true", "try",
+	"assert", "catch", "const", "del", "else", "export", "false", "finally",
+	"function", "if", "import", "in", "let", "match", "nil", "not", "null", "quote", "return", "struct",
+	"throw", "true", "try", "with",
 }
 
 // Common built-in functions
 var risorBuiltins = []string{
-	"all", "any", "assert", "bool", "byte", "call", "chunk", "coalesce",
-	"decode", "encode", "filter", "float", "getattr",
-	"int", "keys", "len", "list", "reversed",
-	"sorted", "sprintf", "string", "type",
+	"all", "any", "avg", "bool", "byte", "call", "chunk", "coalesce", "compile",
+	"copy", "count_by", "decimal", "decode", "dir", "encode", "enumerate", "equal", "eval",
+	"filter", "float", "freeze", "getattr", "group_by", "help", "int", "keys", "len", "list",
+	"max", "memo", "min", "on", "repr", "reversed", "run", "skip", "sorted", "sprintf",
+	"string", "stringbuilder", "sum", "take", "to_source", "type", "window", "zip",
 }
 
 // Common modules
 var risorModules = []string{
-	"math", "rand", "regexp", "strings", "time",
+	"cron", "log", "math", "net", "rand", "regexp", "schema", "strings", "time",
 }
 
 func (s *Server) Completion(ctx context.Context, params *protocol.CompletionParams) (*protocol.CompletionList, error) {
@@ -103,10 +103,22 @@ func nodeToJSON(node ast.Node) *ASTNode {
 		if n.Name != nil {
 			result.Children = append(result.Children, nodeToJSON(n.Name))
 		}
+		if n.Index != nil {
+			result.Children = append(result.Children, nodeToJSON(n.Index))
+		}
+		if n.Slice != nil {
+			result.Children = append(result.Children, nodeToJSON(n.Slice))
+		}
 		if n.Value != nil {
 			result.Children = append(result.Children, nodeToJSON(n.Value))
 		}
 
+	case *ast.MultiAssign:
+		for _, name := range n.Names {
+			result.Children = append(result.Children, nodeToJSON(name))
+		}
+		result.Children = append(result.Children, nodeToJSON(n.Value))
+
 	case *ast.Infix:
 		result.Value = string(n.Op)
 		if n.X != nil {
@@ -173,6 +185,11 @@ func nodeToJSON(node ast.Node) *ASTNode {
 			result.Children = append(result.Children, nodeToJSON(item))
 		}
 
+	case *ast.Tuple:
+		for _, item := range n.Items {
+			result.Children = append(result.Children, nodeToJSON(item))
+		}
+
 	case *ast.Map:
 		for _, pair := range n.Items {
 			pairNode := &ASTNode{Type: "MapPair"}
@@ -391,10 +408,26 @@ func printNode(node ast.Node, indent string, isLast bool) {
 		if n.Name != nil {
 			printNode(n.Name, childIndent, n.Value == nil)
 		}
+		if n.Index != nil {
+			printNode(n.Index, childIndent, n.Value == nil)
+		}
+		if n.Slice != nil {
+			printNode(n.Slice, childIndent, n.Value == nil)
+		}
 		if n.Value != nil {
 			printNode(n.Value, childIndent, true)
 		}
 
+	case *ast.MultiAssign:
+		printLine(tui.Group(
+			tui.Text("%s%s", indent, connector).Style(mutedStyle),
+			tui.Text("%s", typeName).Style(nodeStyle),
+		))
+		for _, name := range n.Names {
+			printNode(name, childIndent, false)
+		}
+		printNode(n.Value, childIndent, true)
+
 	case *ast.Infix:
 		printLine(tui.Group(
 			tui.Text("%s%s", indent, connector).Style(mutedStyle),
@@ -544,6 +577,16 @@ func printNode(node ast.Node, indent string, isLast bool) {
 			printNode(item, childIndent, i == len(n.Items)-1)
 		}
 
+	case *ast.Tuple:
+		printLine(tui.Group(
+			tui.Text("%s%s", indent, connector).Style(mutedStyle),
+			tui.Text("%s", typeName).Style(nodeStyle),
+			tui.Text(" (%d items)", len(n.Items)).Style(mutedStyle),
+		))
+		for i, item := range n.Items {
+			printNode(item, childIndent, i == len(n.Items)-1)
+		}
+
 	case *ast.Map:
 		printLine(tui.Group(
 			tui.Text("%s%s", indent, connector).Style(mutedStyle),
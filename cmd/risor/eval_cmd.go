@@ -29,6 +29,7 @@ func evalHandler(ctx *cli.Context) error {
 
 	// Evaluate
 	result, err := risor.Eval(ctx.Context(), expr, opts...)
+	err = stdout.flushAfter(err)
 	if err != nil {
 		if outputFormat == "json" {
 			out := map[string]any{
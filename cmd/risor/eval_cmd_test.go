@@ -583,6 +583,7 @@ func TestNewPrintBuiltin(t *testing.T) {
 		object.True,
 		object.Nil,
 	)
+	assert.Nil(t, stdout.Flush())
 
 	w.Close()
 	os.Stdout = old
@@ -609,6 +610,7 @@ func TestNewPrintBuiltin_NoArgs(t *testing.T) {
 	os.Stdout = w
 
 	result, err := fn.Call(context.Background())
+	assert.Nil(t, stdout.Flush())
 
 	w.Close()
 	os.Stdout = old
@@ -621,3 +623,143 @@ func TestNewPrintBuiltin_NoArgs(t *testing.T) {
 	// Should just be a newline
 	assert.Equal(t, buf.String(), "\n")
 }
+
+func TestPrintLinesAndFlush(t *testing.T) {
+	old := os.Stdout
+	r, w, _ := os.Pipe()
+	os.Stdout = w
+
+	ctx := context.Background()
+	env := outputBuiltins()
+	_, err := env["print_lines"].(*object.Builtin).Call(ctx, object.NewList([]object.Object{
+		object.NewString("a"),
+		object.NewInt(1),
+		object.Nil,
+	}))
+	assert.Nil(t, err)
+	_, printErr := env["print"].(*object.Builtin).Call(ctx, object.NewString("b"))
+	_, flushErr := env["flush"].(*object.Builtin).Call(ctx)
+	_, typeErr := env["print_lines"].(*object.Builtin).Call(ctx, object.NewInt(1))
+
+	w.Close()
+	os.Stdout = old
+
+	assert.Nil(t, printErr)
+	assert.Nil(t, flushErr)
+	assert.NotNil(t, typeErr)
+	assert.Equal(t, typeErr.Error(), "type error: print_lines() expected an enumerable (int given)")
+
+	var buf bytes.Buffer
+	_, _ = buf.ReadFrom(r)
+	assert.Equal(t, buf.String(), "a\n1\nnull\nb\n")
+}
+
+func TestEvalHandler_OutputFormats(t *testing.T) {
+	oldEnabled := color.Enabled
+	color.Enabled = false
+	defer func() { color.Enabled = oldEnabled }()
+
+	tests := []struct {
+		format   string
+		expr     string
+		expected string
+	}{
+		{"json", `"a\tb"`, "\"a\\tb\"\n"},
+		{"json", `[1, {x: nil}]`, "[\n  1,\n  {\n    \"x\": null\n  }\n]\n"},
+		{"pretty", `{a: [true]}`, "{\n  \"a\": [\n    true\n  ]\n}\n"},
+		{"raw", `"a\tb"`, "a\tb\n"},
+		{"raw", `{a: [1, 2]}`, "{\"a\":[1,2]}\n"},
+		{"raw", `nil`, "null\n"},
+		{"text", `"hi"`, "hi\n"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.format+" "+tt.expr, func(t *testing.T) {
+			app := cli.New("risor").SetColorEnabled(false)
+			app.Command("eval").
+				Args("expr?").
+				Flags(
+					cli.String("code", "c"),
+					cli.Bool("stdin", ""),
+					cli.String("output", "o").Enum("json", "pretty", "raw", "text"),
+					cli.Bool("quiet", "q"),
+				).
+				Run(evalHandler)
+
+			old := os.Stdout
+			r, w, _ := os.Pipe()
+			os.Stdout = w
+
+			err := app.ExecuteArgs([]string{"eval", "-o", tt.format, "-c", tt.expr})
+
+			w.Close()
+			os.Stdout = old
+
+			assert.Nil(t, err)
+
+			var buf bytes.Buffer
+			_, _ = buf.ReadFrom(r)
+			assert.Equal(t, buf.String(), tt.expected)
+		})
+	}
+}
+
+func TestEvalHandler_InputJSON(t *testing.T) {
+	oldEnabled := color.Enabled
+	color.Enabled = false
+	defer func() { color.Enabled = oldEnabled }()
+
+	stdinR, stdinW, _ := os.Pipe()
+	stdinW.WriteString(`{"items": [1, 2, 3], "name": "Alice"}`)
+	stdinW.Close()
+
+	oldStdin := os.Stdin
+	os.Stdin = stdinR
+	defer func() { os.Stdin = oldStdin }()
+
+	app := cli.New("risor").SetColorEnabled(false)
+	app.GlobalFlags(
+		cli.Bool("stdin", ""),
+		cli.String("input", "").Enum("json"),
+	)
+	app.Command("eval").
+		Args("expr?").
+		Flags(
+			cli.String("code", "c"),
+			cli.Bool("stdin", ""),
+			cli.String("output", "o").Enum("json", "text"),
+			cli.Bool("quiet", "q"),
+		).
+		Run(evalHandler)
+
+	old := os.Stdout
+	r, w, _ := os.Pipe()
+	os.Stdout = w
+
+	err := app.ExecuteArgs([]string{"eval", "--input", "json", "-c", `[data.items.len(), data.name]`})
+
+	w.Close()
+	os.Stdout = old
+
+	assert.Nil(t, err)
+
+	var buf bytes.Buffer
+	_, _ = buf.ReadFrom(r)
+	assert.Equal(t, buf.String(), "[\n  3,\n  \"Alice\"\n]\n")
+}
+
+func TestParseInput(t *testing.T) {
+	value, err := parseInput("json", []byte(`{"a": [1, "b", null]}`))
+	assert.Nil(t, err)
+	assert.Equal(t, value, map[string]any{"a": []any{float64(1), "b", nil}})
+
+	value, err = parseInput("json", []byte(`42`))
+	assert.Nil(t, err)
+	assert.Equal(t, value, float64(42))
+
+	_, err = parseInput("json", []byte(`{"a": `))
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "--input json")
+
+	_, err = parseInput("xml", []byte(`<a/>`))
+	assert.NotNil(t, err)
+}
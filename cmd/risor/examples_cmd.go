@@ -4,6 +4,7 @@ import (
 	"context"
 	"encoding/json"
 	"fmt"
+	"maps"
 	"os"
 	"sort"
 	"strings"
@@ -315,16 +316,18 @@ print(math.round(3.5))`,
 		Description: "Time module",
 		Category:    "modules",
 		Code: `let now = time.now()
-print(now)
+print(now.iso())
 print(now.format("2006-01-02 15:04:05"))
-print(now.year())
-print(now.month())
-print(now.day())
-
-// Measure duration
-let start = time.now()
-time.sleep(0.1)
-print("Elapsed:", time.since(start), "seconds")`,
+
+// Timezones and arithmetic
+let meeting = time.parse("2024-03-10T09:00", null, "America/New_York")
+print(meeting.in("Europe/London").iso())  // 2024-03-10T13:00:00Z
+print(meeting.add("90m").iso())           // 2024-03-10T10:30:00-04:00
+print(meeting.truncate("week").iso())     // 2024-03-04T00:00:00-05:00
+
+// Differences are in seconds
+let start = time.parse("2024-03-10T08:00:00Z")
+print(meeting.diff(start) / 3600, "hours")  // 5 hours`,
 	},
 	{
 		Name:        "regexp",
@@ -459,8 +462,9 @@ func examplesHandler(ctx *cli.Context) error {
 
 		// Execute the code with print function
 		env := risor.Builtins()
-		env["print"] = newPrintBuiltin()
+		maps.Copy(env, outputBuiltins())
 		result, err := risor.Eval(context.Background(), found.Code, risor.WithEnv(env))
+		err = stdout.flushAfter(err)
 		if err != nil {
 			fmt.Println(tui.Sprint(tui.Text("Error: %v", err).Style(tui.NewStyle().WithFgRGB(tui.RGB{R: 255, G: 100, B: 100}))))
 		} else if result != nil {
@@ -7,6 +7,7 @@ import (
 	"fmt"
 	"io"
 	"os"
+	"strconv"
 	"strings"
 
 	"github.com/deepnoodle-ai/risor/v2/pkg/ast"
@@ -141,11 +142,14 @@ func (f *Formatter) formatNode(node ast.Node) {
 			f.formatNode(n.Name)
 		}
 		if n.Index != nil {
-			f.buf.WriteString("[")
 			f.formatNode(n.Index)
-			f.buf.WriteString("]")
 		}
-		f.buf.WriteString(" = ")
+		if n.Slice != nil {
+			f.formatNode(n.Slice)
+		}
+		f.buf.WriteString(" ")
+		f.buf.WriteString(n.Op)
+		f.buf.WriteString(" ")
 		if n.Value != nil {
 			f.formatNode(n.Value)
 		}
@@ -194,7 +198,7 @@ func (f *Formatter) formatNode(node ast.Node) {
 		f.formatNode(n.Body)
 
 	case *ast.Call:
-		f.formatNode(n.Fun)
+		f.formatOperand(n.Fun)
 		f.buf.WriteString("(")
 		for i, arg := range n.Args {
 			if i > 0 {
@@ -205,14 +209,17 @@ func (f *Formatter) formatNode(node ast.Node) {
 		f.buf.WriteString(")")
 
 	case *ast.ObjectCall:
-		f.formatNode(n.X)
+		f.formatOperand(n.X)
+		if n.Optional {
+			f.buf.WriteString("?")
+		}
 		f.buf.WriteString(".")
 		if n.Call != nil {
 			f.formatNode(n.Call)
 		}
 
 	case *ast.GetAttr:
-		f.formatNode(n.X)
+		f.formatOperand(n.X)
 		if n.Optional {
 			f.buf.WriteString("?")
 		}
@@ -220,20 +227,25 @@ func (f *Formatter) formatNode(node ast.Node) {
 		f.buf.WriteString(n.Attr.Name)
 
 	case *ast.SetAttr:
-		f.formatNode(n.X)
+		f.formatOperand(n.X)
 		f.buf.WriteString(".")
 		f.buf.WriteString(n.Attr.Name)
-		f.buf.WriteString(" = ")
+		f.buf.WriteString(" ")
+		f.buf.WriteString(n.Op)
+		f.buf.WriteString(" ")
 		f.formatNode(n.Value)
 
 	case *ast.Index:
-		f.formatNode(n.X)
+		f.formatOperand(n.X)
+		if n.Optional {
+			f.buf.WriteString("?.")
+		}
 		f.buf.WriteString("[")
 		f.formatNode(n.Index)
 		f.buf.WriteString("]")
 
 	case *ast.Slice:
-		f.formatNode(n.X)
+		f.formatOperand(n.X)
 		f.buf.WriteString("[")
 		if n.Low != nil {
 			f.formatNode(n.Low)
@@ -242,32 +254,34 @@ func (f *Formatter) formatNode(node ast.Node) {
 		if n.High != nil {
 			f.formatNode(n.High)
 		}
+		if n.Step != nil {
+			f.buf.WriteString(":")
+			f.formatNode(n.Step)
+		}
 		f.buf.WriteString("]")
 
 	case *ast.Infix:
-		f.formatNode(n.X)
-		f.buf.WriteString(" ")
-		f.buf.WriteString(string(n.Op))
-		f.buf.WriteString(" ")
-		f.formatNode(n.Y)
+		f.formatBinary(n.Op, n.X, n.Y)
 
 	case *ast.Prefix:
 		f.buf.WriteString(string(n.Op))
-		f.formatNode(n.X)
+		if prefixOperandNeedsParens(n.Op, n.X) {
+			f.buf.WriteString("(")
+			f.formatNode(n.X)
+			f.buf.WriteString(")")
+		} else {
+			f.formatNode(n.X)
+		}
 
 	case *ast.Postfix:
-		f.formatNode(n.X)
+		f.formatOperand(n.X)
 		f.buf.WriteString(string(n.Op))
 
 	case *ast.In:
-		f.formatNode(n.X)
-		f.buf.WriteString(" in ")
-		f.formatNode(n.Y)
+		f.formatBinary("in", n.X, n.Y)
 
 	case *ast.NotIn:
-		f.formatNode(n.X)
-		f.buf.WriteString(" not in ")
-		f.formatNode(n.Y)
+		f.formatBinary("not in", n.X, n.Y)
 
 	case *ast.Try:
 		f.buf.WriteString("try ")
@@ -286,10 +300,46 @@ func (f *Formatter) formatNode(node ast.Node) {
 			f.formatNode(n.FinallyBlock)
 		}
 
+	case *ast.With:
+		f.buf.WriteString("with ")
+		f.formatNode(n.Value)
+		f.buf.WriteString(" as ")
+		f.buf.WriteString(n.Name.Name)
+		f.buf.WriteString(" ")
+		f.formatNode(n.Body)
+
+	case *ast.Quote:
+		f.buf.WriteString("quote ")
+		f.formatNode(n.Body)
+
 	case *ast.Throw:
 		f.buf.WriteString("throw ")
 		f.formatNode(n.Value)
 
+	case *ast.Export:
+		f.buf.WriteString("export ")
+		f.formatNode(n.Decl)
+
+	case *ast.Import:
+		f.buf.WriteString("import ")
+		f.formatNode(n.Path)
+		if n.Alias != nil {
+			f.buf.WriteString(" as ")
+			f.buf.WriteString(n.Alias.Name)
+		}
+
+	case *ast.Assert:
+		f.buf.WriteString("assert ")
+		f.formatNode(n.Cond)
+		if n.Message != nil {
+			f.buf.WriteString(", ")
+			f.formatNode(n.Message)
+		}
+
+	case *ast.Del:
+		f.buf.WriteString("del ")
+		f.formatNode(n.Index)
+
 	case *ast.Pipe:
 		for i, expr := range n.Exprs {
 			if i > 0 {
@@ -310,7 +360,12 @@ func (f *Formatter) formatNode(node ast.Node) {
 		fmt.Fprintf(&f.buf, "%d", n.Value)
 
 	case *ast.Float:
-		fmt.Fprintf(&f.buf, "%g", n.Value)
+		// Keep a decimal point so that whole numbers stay floats
+		s := strconv.FormatFloat(n.Value, 'f', -1, 64)
+		if !strings.Contains(s, ".") {
+			s += ".0"
+		}
+		f.buf.WriteString(s)
 
 	case *ast.Bool:
 		if n.Value {
@@ -341,6 +396,14 @@ func (f *Formatter) formatNode(node ast.Node) {
 		}
 		f.buf.WriteString("]")
 
+	case *ast.Tuple:
+		for i, item := range n.Items {
+			if i > 0 {
+				f.buf.WriteString(", ")
+			}
+			f.formatNode(item)
+		}
+
 	case *ast.Map:
 		if len(n.Items) == 0 {
 			f.buf.WriteString("{}")
@@ -354,14 +417,18 @@ func (f *Formatter) formatNode(node ast.Node) {
 			if item.Key == nil {
 				// Spread
 				f.formatNode(item.Value)
+			} else if item.Computed {
+				f.buf.WriteString("[")
+				f.formatNode(item.Key)
+				f.buf.WriteString("]: ")
+				f.formatNode(item.Value)
 			} else if ident, ok := item.Key.(*ast.Ident); ok {
 				f.buf.WriteString(ident.Name)
 				f.buf.WriteString(": ")
 				f.formatNode(item.Value)
 			} else {
-				f.buf.WriteString("[")
 				f.formatNode(item.Key)
-				f.buf.WriteString("]: ")
+				f.buf.WriteString(": ")
 				f.formatNode(item.Value)
 			}
 		}
@@ -423,6 +490,20 @@ func (f *Formatter) formatNode(node ast.Node) {
 			f.formatNode(n.Value)
 		}
 
+	case *ast.MultiAssign:
+		separator := " = "
+		if n.Unpack {
+			separator = ", "
+		}
+		for i, name := range n.Names {
+			if i > 0 {
+				f.buf.WriteString(separator)
+			}
+			f.buf.WriteString(name.Name)
+		}
+		f.buf.WriteString(" = ")
+		f.formatNode(n.Value)
+
 	case *ast.Match:
 		f.buf.WriteString("match ")
 		f.formatNode(n.Subject)
@@ -457,6 +538,101 @@ func (f *Formatter) formatNode(node ast.Node) {
 	}
 }
 
+// binaryPrecedence gives the binding strength of each binary operator, from
+// loosest to tightest, following the parser's precedence levels.
+var binaryPrecedence = map[string]int{
+	"??":     1,
+	"&&":     2,
+	"||":     2,
+	"==":     3,
+	"!=":     3,
+	"<":      4,
+	"<=":     4,
+	">":      4,
+	">=":     4,
+	"in":     4,
+	"not in": 4,
+	"+":      5,
+	"-":      5,
+	"*":      6,
+	"/":      6,
+	"%":      6,
+	"&":      6,
+	"|":      6,
+	"^":      6,
+	"<<":     6,
+	">>":     6,
+	"**":     7,
+}
+
+// formatBinary formats a binary operator expression, adding parentheses
+// around operands that would otherwise parse differently.
+func (f *Formatter) formatBinary(op string, x, y ast.Expr) {
+	f.formatBinaryOperand(op, x, true)
+	f.buf.WriteString(" ")
+	f.buf.WriteString(op)
+	f.buf.WriteString(" ")
+	f.formatBinaryOperand(op, y, false)
+}
+
+func (f *Formatter) formatBinaryOperand(op string, operand ast.Expr, left bool) {
+	if binaryOperandNeedsParens(op, operand, left) {
+		f.buf.WriteString("(")
+		f.formatNode(operand)
+		f.buf.WriteString(")")
+		return
+	}
+	f.formatNode(operand)
+}
+
+func binaryOperandNeedsParens(op string, operand ast.Expr, left bool) bool {
+	var operandOp string
+	switch operand := operand.(type) {
+	case *ast.Infix:
+		operandOp = operand.Op
+	case *ast.In:
+		operandOp = "in"
+	case *ast.NotIn:
+		operandOp = "not in"
+	case *ast.Prefix:
+		// A prefix operator before ** applies to the whole power, since
+		// -a ** b parses as -(a ** b)
+		return left && op == "**"
+	default:
+		return false
+	}
+	precedence, operandPrecedence := binaryPrecedence[op], binaryPrecedence[operandOp]
+	if operandPrecedence != precedence {
+		return operandPrecedence < precedence
+	}
+	// Operators group to the left, except for ** which groups to the right
+	return left == (op == "**")
+}
+
+func prefixOperandNeedsParens(op string, operand ast.Expr) bool {
+	switch operand := operand.(type) {
+	case *ast.Infix, *ast.In, *ast.NotIn:
+		return true
+	case *ast.Prefix:
+		// "--" and "++" would lex as postfix operators
+		return (op == "-" || op == "+") && operand.Op == op
+	}
+	return false
+}
+
+// formatOperand formats the operand of a call, index, attribute access, or
+// postfix operator, adding parentheses around operator expressions.
+func (f *Formatter) formatOperand(node ast.Expr) {
+	switch node.(type) {
+	case *ast.Infix, *ast.Prefix, *ast.In, *ast.NotIn:
+		f.buf.WriteString("(")
+		f.formatNode(node)
+		f.buf.WriteString(")")
+	default:
+		f.formatNode(node)
+	}
+}
+
 func (f *Formatter) formatParams(params []ast.FuncParam, defaults map[string]ast.Expr, rest *ast.Ident) {
 	for i, p := range params {
 		if i > 0 {
@@ -0,0 +1,374 @@
+package main
+
+import (
+	"context"
+	"math/rand/v2"
+	"reflect"
+	"strconv"
+	"testing"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/ast"
+	"github.com/deepnoodle-ai/risor/v2/pkg/parser"
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+// TestFormatRoundTrip generates random programs, formats them, and parses the
+// result. The parsed program must match the generated one, so the formatter
+// can't write code that the parser reads differently, such as an operator
+// expression that lost its parentheses. Formatting the parsed program must
+// also give the same source.
+func TestFormatRoundTrip(t *testing.T) {
+	count := 2000
+	if testing.Short() {
+		count = 200
+	}
+	for seed := range uint64(count) {
+		g := &astGen{r: rand.New(rand.NewPCG(seed, 0))}
+		program := g.program()
+		source := formatProgram(program)
+		parsed, err := parser.Parse(context.Background(), source, nil)
+		if err != nil {
+			t.Fatalf("seed %d: formatted program doesn't parse: %v\n%s", seed, err, source)
+		}
+		if !equalAST(reflect.ValueOf(program), reflect.ValueOf(parsed)) {
+			t.Fatalf("seed %d: program changed in a round trip:\n%s\nparsed as:\n%s",
+				seed, source, formatProgram(parsed))
+		}
+		assert.Equal(t, formatProgram(parsed), source)
+	}
+}
+
+var positionType = reflect.TypeOf((&ast.Program{}).Pos())
+
+// equalAST reports whether two nodes have the same structure and values. It
+// ignores positions and the raw text of literals, and treats nil and empty
+// slices and maps as equal.
+func equalAST(a, b reflect.Value) bool {
+	if a.Kind() != b.Kind() {
+		return false
+	}
+	switch a.Kind() {
+	case reflect.Interface, reflect.Pointer:
+		if a.IsNil() || b.IsNil() {
+			return a.IsNil() && b.IsNil()
+		}
+		if a.Elem().Type() != b.Elem().Type() {
+			return false
+		}
+		return equalAST(a.Elem(), b.Elem())
+	case reflect.Struct:
+		if a.Type() == positionType {
+			return true
+		}
+		for i := range a.NumField() {
+			if a.Type().Field(i).Name == "Literal" {
+				continue
+			}
+			if !equalAST(a.Field(i), b.Field(i)) {
+				return false
+			}
+		}
+		return true
+	case reflect.Slice:
+		if a.Len() != b.Len() {
+			return false
+		}
+		for i := range a.Len() {
+			if !equalAST(a.Index(i), b.Index(i)) {
+				return false
+			}
+		}
+		return true
+	case reflect.Map:
+		if a.Len() != b.Len() {
+			return false
+		}
+		for _, key := range a.MapKeys() {
+			value := b.MapIndex(key)
+			if !value.IsValid() || !equalAST(a.MapIndex(key), value) {
+				return false
+			}
+		}
+		return true
+	default:
+		return a.Equal(b)
+	}
+}
+
+var (
+	genNames       = []string{"a", "b", "c", "x", "y", "items", "total"}
+	genAttrs       = []string{"size", "name", "first", "upper"}
+	genPrefixOps   = []string{"-", "!"}
+	genBinaryOps   = []string{"??", "&&", "||", "==", "!=", "<", "<=", ">", ">=", "+", "-", "*", "/", "%", "**"}
+	genAssignOps   = []string{"=", "+=", "-=", "*=", "/="}
+	genStringRunes = []rune("ab z\"\\\n\t")
+)
+
+// astGen generates random programs from the part of the grammar that the
+// formatter supports. Nodes are generated without positions.
+type astGen struct {
+	r     *rand.Rand
+	depth int
+}
+
+func pick[T any](r *rand.Rand, items []T) T {
+	return items[r.IntN(len(items))]
+}
+
+func (g *astGen) program() *ast.Program {
+	return &ast.Program{Stmts: g.stmts(1+g.r.IntN(5), false)}
+}
+
+func (g *astGen) stmts(n int, inFunc bool) []ast.Node {
+	stmts := make([]ast.Node, 0, n)
+	for range n {
+		stmts = append(stmts, g.stmt(inFunc))
+	}
+	return stmts
+}
+
+func (g *astGen) block(inFunc bool) *ast.Block {
+	g.depth++
+	defer func() { g.depth-- }()
+	return &ast.Block{Stmts: g.stmts(g.r.IntN(3), inFunc)}
+}
+
+func (g *astGen) ident() *ast.Ident {
+	return &ast.Ident{Name: pick(g.r, genNames)}
+}
+
+func (g *astGen) attr() *ast.Ident {
+	return &ast.Ident{Name: pick(g.r, genAttrs)}
+}
+
+func (g *astGen) stmt(inFunc bool) ast.Node {
+	// Statements with blocks are only generated near the top of the tree
+	n := 8
+	if g.depth < 2 {
+		n = 11
+	}
+	switch g.r.IntN(n) {
+	case 0:
+		var value ast.Expr
+		if g.depth < 2 && g.r.IntN(4) == 0 {
+			value = g.function(nil)
+		} else {
+			value = g.expr()
+		}
+		return &ast.Var{Name: g.ident(), Value: value}
+	case 1:
+		return &ast.Const{Name: g.ident(), Value: g.expr()}
+	case 2:
+		if g.r.IntN(4) == 0 {
+			return g.multiAssign()
+		}
+		return &ast.Assign{Name: g.ident(), Op: pick(g.r, genAssignOps), Value: g.expr()}
+	case 3:
+		index := &ast.Index{X: g.ident(), Index: g.expr()}
+		switch g.r.IntN(4) {
+		case 0:
+			return &ast.Del{Index: index}
+		case 1:
+			slice := &ast.Slice{X: index.X, Low: index.Index}
+			return &ast.Assign{Slice: slice, Op: "=", Value: g.expr()}
+		}
+		return &ast.Assign{Index: index, Op: "=", Value: g.expr()}
+	case 4:
+		return &ast.SetAttr{X: g.ident(), Attr: g.attr(), Op: pick(g.r, genAssignOps), Value: g.expr()}
+	case 5:
+		return &ast.Postfix{X: g.ident(), Op: pick(g.r, []string{"++", "--"})}
+	case 6:
+		if g.r.IntN(2) == 0 {
+			return g.call()
+		}
+		return &ast.ObjectCall{X: g.ident(), Call: g.methodCall(), Optional: g.r.IntN(4) == 0}
+	case 7:
+		if !inFunc {
+			return &ast.Throw{Value: g.expr()}
+		}
+		if g.r.IntN(4) == 0 {
+			return &ast.Return{Value: &ast.Tuple{Items: []ast.Expr{g.expr(), g.expr()}}}
+		}
+		return &ast.Return{Value: g.expr()}
+	case 8:
+		node := &ast.If{Cond: g.expr(), Consequence: g.block(inFunc)}
+		if g.r.IntN(2) == 0 {
+			node.Alternative = g.block(inFunc)
+		}
+		return node
+	case 9:
+		node := &ast.Try{Body: g.block(inFunc)}
+		hasCatch := g.r.IntN(3) != 0
+		if hasCatch {
+			if g.r.IntN(2) == 0 {
+				node.CatchIdent = g.ident()
+			}
+			node.CatchBlock = g.block(inFunc)
+		}
+		if !hasCatch || g.r.IntN(2) == 0 {
+			node.FinallyBlock = g.block(inFunc)
+		}
+		return node
+	default:
+		return g.function(g.ident())
+	}
+}
+
+func (g *astGen) multiAssign() *ast.MultiAssign {
+	names := []*ast.Ident{g.ident(), g.ident()}
+	if g.r.IntN(2) == 0 {
+		return &ast.MultiAssign{Names: names, Value: g.expr()}
+	}
+	value := &ast.Tuple{Items: []ast.Expr{g.expr(), g.expr()}}
+	return &ast.MultiAssign{Names: names, Value: value, Unpack: true}
+}
+
+func (g *astGen) function(name *ast.Ident) *ast.Func {
+	fn := &ast.Func{Name: name, Defaults: map[string]ast.Expr{}}
+	// Parameter names are distinct, and only the last one has a default
+	for _, i := range g.r.Perm(len(genNames))[:g.r.IntN(3)] {
+		fn.Params = append(fn.Params, &ast.Ident{Name: genNames[i]})
+	}
+	if len(fn.Params) > 0 && g.r.IntN(2) == 0 {
+		last := fn.Params[len(fn.Params)-1].(*ast.Ident)
+		fn.Defaults[last.Name] = g.intLit()
+	}
+	if g.r.IntN(4) == 0 {
+		fn.RestParam = &ast.Ident{Name: "rest"}
+	}
+	fn.Body = g.block(true)
+	return fn
+}
+
+func (g *astGen) expr() ast.Expr {
+	g.depth++
+	defer func() { g.depth-- }()
+	if g.depth > 4 {
+		return g.leaf()
+	}
+	switch g.r.IntN(10) {
+	case 0, 1:
+		return g.leaf()
+	case 2:
+		return &ast.Prefix{Op: pick(g.r, genPrefixOps), X: g.expr()}
+	case 3, 4:
+		return &ast.Infix{X: g.expr(), Op: pick(g.r, genBinaryOps), Y: g.expr()}
+	case 5:
+		return g.list()
+	case 6:
+		return g.mapLit()
+	case 7:
+		return g.call()
+	default:
+		return g.access()
+	}
+}
+
+func (g *astGen) leaf() ast.Expr {
+	switch g.r.IntN(6) {
+	case 0:
+		return g.ident()
+	case 1:
+		return g.intLit()
+	case 2:
+		value := float64(g.r.IntN(100)) + pick(g.r, []float64{0, 0.25, 0.5})
+		return &ast.Float{Literal: strconv.FormatFloat(value, 'f', -1, 64), Value: value}
+	case 3:
+		runes := make([]rune, g.r.IntN(5))
+		for i := range runes {
+			runes[i] = pick(g.r, genStringRunes)
+		}
+		value := string(runes)
+		return &ast.String{Literal: strconv.Quote(value), Value: value}
+	case 4:
+		value := g.r.IntN(2) == 0
+		return &ast.Bool{Literal: strconv.FormatBool(value), Value: value}
+	default:
+		return &ast.Nil{}
+	}
+}
+
+func (g *astGen) intLit() *ast.Int {
+	value := g.r.Int64N(1000)
+	return &ast.Int{Literal: strconv.FormatInt(value, 10), Value: value}
+}
+
+func (g *astGen) list() *ast.List {
+	items := make([]ast.Expr, g.r.IntN(4))
+	for i := range items {
+		if g.r.IntN(5) == 0 {
+			items[i] = &ast.Spread{X: g.ident()}
+		} else {
+			items[i] = g.expr()
+		}
+	}
+	return &ast.List{Items: items}
+}
+
+func (g *astGen) mapLit() *ast.Map {
+	items := make([]ast.MapItem, g.r.IntN(4))
+	for i := range items {
+		switch g.r.IntN(4) {
+		case 0:
+			items[i] = ast.MapItem{Key: g.ident(), Value: g.expr()}
+		case 1:
+			key := pick(g.r, []string{"", "a b", "key"})
+			items[i] = ast.MapItem{Key: &ast.String{Literal: strconv.Quote(key), Value: key}, Value: g.expr()}
+		case 2:
+			items[i] = ast.MapItem{Key: g.expr(), Value: g.expr(), Computed: true}
+		default:
+			items[i] = ast.MapItem{Value: &ast.Spread{X: g.ident()}}
+		}
+	}
+	return &ast.Map{Items: items}
+}
+
+func (g *astGen) args() []ast.Node {
+	args := make([]ast.Node, g.r.IntN(3))
+	for i := range args {
+		if g.r.IntN(5) == 0 {
+			args[i] = &ast.Spread{X: g.ident()}
+		} else {
+			args[i] = g.expr()
+		}
+	}
+	return args
+}
+
+func (g *astGen) call() *ast.Call {
+	return &ast.Call{Fun: g.ident(), Args: g.args()}
+}
+
+func (g *astGen) methodCall() *ast.Call {
+	return &ast.Call{Fun: g.attr(), Args: g.args()}
+}
+
+// access generates an index, slice, attribute, or method call expression.
+func (g *astGen) access() ast.Expr {
+	x := g.expr()
+	switch x.(type) {
+	case *ast.Int, *ast.Float:
+		// Avoid "1.size", where the period lexes as part of a number
+		x = g.ident()
+	}
+	switch g.r.IntN(4) {
+	case 0:
+		return &ast.Index{X: x, Index: g.expr(), Optional: g.r.IntN(4) == 0}
+	case 1:
+		node := &ast.Slice{X: x}
+		if g.r.IntN(2) == 0 {
+			node.Low = g.expr()
+		}
+		if g.r.IntN(2) == 0 {
+			node.High = g.expr()
+		}
+		if g.r.IntN(4) == 0 {
+			node.Step = g.expr()
+		}
+		return node
+	case 2:
+		return &ast.GetAttr{X: x, Attr: g.attr(), Optional: g.r.IntN(4) == 0}
+	default:
+		return &ast.ObjectCall{X: x, Call: g.methodCall(), Optional: g.r.IntN(4) == 0}
+	}
+}
@@ -0,0 +1,124 @@
+package main
+
+import (
+	"context"
+	goerrors "errors"
+	"fmt"
+	"runtime/debug"
+	"runtime/metrics"
+	"strconv"
+	"strings"
+	"time"
+
+	"github.com/deepnoodle-ai/wonton/cli"
+)
+
+// errMemoryLimitExceeded is the cause of the context cancellation when a
+// script's memory use exceeds the --max-memory limit.
+var errMemoryLimitExceeded = goerrors.New("memory limit exceeded")
+
+// memoryCheckInterval is how often the heap size is compared to the limit.
+const memoryCheckInterval = 10 * time.Millisecond
+
+// liveHeapMetric reports the size of the heap that survived the last GC.
+const liveHeapMetric = "/gc/heap/live:bytes"
+
+// resourceLimits holds the limits set by the --timeout and --max-memory flags.
+// Zero values mean no limit.
+type resourceLimits struct {
+	timeout   time.Duration
+	maxMemory int64
+}
+
+func getResourceLimits(ctx *cli.Context) (resourceLimits, error) {
+	var limits resourceLimits
+	if s := ctx.String("timeout"); s != "" {
+		d, err := time.ParseDuration(s)
+		if err != nil || d < 0 {
+			return limits, fmt.Errorf("invalid --timeout %q (expected a duration such as 30s or 5m)", s)
+		}
+		limits.timeout = d
+	}
+	if s := ctx.String("max-memory"); s != "" {
+		n, err := parseByteSize(s)
+		if err != nil {
+			return limits, fmt.Errorf("invalid --max-memory %q (expected a size such as 512MB or 2GB)", s)
+		}
+		limits.maxMemory = n
+	}
+	return limits, nil
+}
+
+// byteSizeUnits maps the size suffixes accepted by parseByteSize to their
+// multipliers. Sizes are binary, so 1KB is 1024 bytes.
+var byteSizeUnits = []struct {
+	suffix string
+	size   int64
+}{
+	{"KIB", 1 << 10},
+	{"MIB", 1 << 20},
+	{"GIB", 1 << 30},
+	{"KB", 1 << 10},
+	{"MB", 1 << 20},
+	{"GB", 1 << 30},
+	{"K", 1 << 10},
+	{"M", 1 << 20},
+	{"G", 1 << 30},
+	{"B", 1},
+}
+
+// parseByteSize parses a size such as "512MB", "2G", or "1048576".
+func parseByteSize(s string) (int64, error) {
+	number := strings.ToUpper(strings.TrimSpace(s))
+	multiplier := int64(1)
+	for _, unit := range byteSizeUnits {
+		if strings.HasSuffix(number, unit.suffix) {
+			number = strings.TrimSpace(strings.TrimSuffix(number, unit.suffix))
+			multiplier = unit.size
+			break
+		}
+	}
+	n, err := strconv.ParseInt(number, 10, 64)
+	if err != nil {
+		return 0, err
+	}
+	if n < 0 || n > (1<<63-1)/multiplier {
+		return 0, fmt.Errorf("size out of range: %s", s)
+	}
+	return n * multiplier, nil
+}
+
+// memoryLimitContext returns a context that is cancelled, with
+// errMemoryLimitExceeded as its cause, once the live heap grows beyond limit
+// bytes. The limit also becomes the Go runtime's soft memory limit, so that
+// garbage is collected before it counts against the script. Call the returned
+// function to stop monitoring and restore the previous soft limit.
+func memoryLimitContext(parent context.Context, limit int64) (context.Context, func()) {
+	ctx, cancel := context.WithCancelCause(parent)
+	previous := debug.SetMemoryLimit(limit)
+	done := make(chan struct{})
+	go func() {
+		ticker := time.NewTicker(memoryCheckInterval)
+		defer ticker.Stop()
+		sample := []metrics.Sample{{Name: liveHeapMetric}}
+		for {
+			select {
+			case <-done:
+				return
+			case <-ctx.Done():
+				return
+			case <-ticker.C:
+			}
+			metrics.Read(sample)
+			if sample[0].Value.Kind() == metrics.KindUint64 && sample[0].Value.Uint64() > uint64(limit) {
+				cancel(errMemoryLimitExceeded)
+				return
+			}
+		}
+	}()
+	return ctx, func() {
+		close(done)
+		cancel(nil)
+		debug.SetMemoryLimit(previous)
+	}
+}
@@ -0,0 +1,55 @@
+package main
+
+import (
+	"context"
+	"runtime"
+	"testing"
+	"time"
+
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+func TestParseByteSize(t *testing.T) {
+	tests := []struct {
+		input    string
+		expected int64
+	}{
+		{"1024", 1024},
+		{"100B", 100},
+		{"64k", 64 << 10},
+		{"512MB", 512 << 20},
+		{"512 MiB", 512 << 20},
+		{"2G", 2 << 30},
+		{"1gib", 1 << 30},
+	}
+	for _, tt := range tests {
+		n, err := parseByteSize(tt.input)
+		assert.Nil(t, err, tt.input)
+		assert.Equal(t, n, tt.expected, tt.input)
+	}
+
+	for _, input := range []string{"", "MB", "-1MB", "1.5GB", "1TB", "99999999999999GB"} {
+		_, err := parseByteSize(input)
+		assert.NotNil(t, err, input)
+	}
+}
+
+func TestMemoryLimitContext(t *testing.T) {
+	ctx, stop := memoryLimitContext(context.Background(), 1)
+	defer stop()
+	runtime.GC()
+
+	select {
+	case <-ctx.Done():
+	case <-time.After(5 * time.Second):
+		t.Fatal("memory limit was not enforced")
+	}
+	assert.Equal(t, context.Cause(ctx), errMemoryLimitExceeded)
+}
+
+func TestMemoryLimitContextStop(t *testing.T) {
+	ctx, stop := memoryLimitContext(context.Background(), 1<<40)
+	stop()
+	<-ctx.Done()
+	assert.Equal(t, context.Cause(ctx), context.Canceled)
+}
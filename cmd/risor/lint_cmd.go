@@ -7,9 +7,9 @@ import (
 	"fmt"
 	"io"
 	"os"
-	"strings"
 
 	"github.com/deepnoodle-ai/risor/v2/pkg/ast"
+	"github.com/deepnoodle-ai/risor/v2/pkg/lint"
 	"github.com/deepnoodle-ai/risor/v2/pkg/parser"
 	"github.com/deepnoodle-ai/wonton/cli"
 	"github.com/deepnoodle-ai/wonton/tui"
@@ -53,7 +53,11 @@ func lintHandler(ctx *cli.Context) error {
 	}
 
 	// Run linting checks
-	issues := lintProgram(program, code)
+	var extra []lint.Rule
+	if ctx.Bool("security") {
+		extra = lint.SecurityRules()
+	}
+	issues := lintProgram(program, code, extra...)
 
 	// Print results
 	printLintResults(filename, issues, outputFormat)
@@ -61,7 +65,7 @@ func lintHandler(ctx *cli.Context) error {
 	if len(issues) > 0 {
 		for _, issue := range issues {
 			if issue.Level == "error" {
-				os.Exit(1)
+				exit(1)
 			}
 		}
 	}
@@ -111,173 +115,21 @@ func getLintCode(ctx *cli.Context) (string, string, error) {
 	return ctx.String("code"), "", nil
 }
 
-func lintProgram(program *ast.Program, source string) []LintIssue {
+// lintProgram runs the default lint rules, along with any extra rules, over
+// the program.
+func lintProgram(program *ast.Program, source string, extra ...lint.Rule) []LintIssue {
+	linter := lint.New()
+	linter.Register(extra...)
 	var issues []LintIssue
-	lines := strings.Split(source, "\n")
-
-	// Track declared variables for unused detection
-	declared := make(map[string]int)        // name -> line
-	used := make(map[string]bool)           // name -> was used
-	constants := make(map[string]bool)      // track constants for reassignment check
-	shadowWarnings := make(map[string]bool) // prevent duplicate shadow warnings
-
-	// Visit all nodes
-	ast.Inspect(program, func(node ast.Node) bool {
-		if node == nil {
-			return true
-		}
-
-		switch n := node.(type) {
-		case *ast.Var:
-			name := n.Name.Name
-			line := n.Name.Pos().Line
-
-			// Check for shadowing
-			if prevLine, exists := declared[name]; exists && !shadowWarnings[name] {
-				issues = append(issues, LintIssue{
-					Line:    line,
-					Column:  n.Name.Pos().Column,
-					Rule:    "variable-shadow",
-					Message: fmt.Sprintf("variable %q shadows declaration on line %d", name, prevLine),
-					Level:   "warning",
-				})
-				shadowWarnings[name] = true
-			}
-			declared[name] = line
-			used[name] = false
-
-		case *ast.Const:
-			name := n.Name.Name
-			line := n.Name.Pos().Line
-			declared[name] = line
-			used[name] = false
-			constants[name] = true
-
-		case *ast.Assign:
-			if n.Name != nil {
-				name := n.Name.Name
-				// Check for constant reassignment
-				if constants[name] {
-					issues = append(issues, LintIssue{
-						Line:    n.Name.Pos().Line,
-						Column:  n.Name.Pos().Column,
-						Rule:    "const-reassign",
-						Message: fmt.Sprintf("cannot reassign constant %q", name),
-						Level:   "error",
-					})
-				}
-				used[name] = true
-			}
-
-		case *ast.Ident:
-			used[n.Name] = true
-
-		case *ast.Func:
-			// Check for functions without return
-			if n.Name != nil && n.Body != nil {
-				hasReturn := false
-				ast.Inspect(n.Body, func(inner ast.Node) bool {
-					if _, ok := inner.(*ast.Return); ok {
-						hasReturn = true
-						return false
-					}
-					return true
-				})
-				// This is just informational, not a warning
-				_ = hasReturn
-			}
-
-		case *ast.If:
-			// Check for empty blocks
-			if n.Consequence != nil && len(n.Consequence.Stmts) == 0 {
-				issues = append(issues, LintIssue{
-					Line:    n.Consequence.Pos().Line,
-					Column:  n.Consequence.Pos().Column,
-					Rule:    "empty-block",
-					Message: "empty if block",
-					Level:   "warning",
-				})
-			}
-			if n.Alternative != nil && len(n.Alternative.Stmts) == 0 {
-				issues = append(issues, LintIssue{
-					Line:    n.Alternative.Pos().Line,
-					Column:  n.Alternative.Pos().Column,
-					Rule:    "empty-block",
-					Message: "empty else block",
-					Level:   "warning",
-				})
-			}
-
-		case *ast.Infix:
-			// Check for self-comparison
-			if leftIdent, ok := n.X.(*ast.Ident); ok {
-				if rightIdent, ok := n.Y.(*ast.Ident); ok {
-					if leftIdent.Name == rightIdent.Name {
-						issues = append(issues, LintIssue{
-							Line:    n.Pos().Line,
-							Column:  n.Pos().Column,
-							Rule:    "self-compare",
-							Message: fmt.Sprintf("comparing %q to itself", leftIdent.Name),
-							Level:   "warning",
-						})
-					}
-				}
-			}
-
-		case *ast.String:
-			// Check for very long strings
-			if len(n.Value) > 1000 {
-				issues = append(issues, LintIssue{
-					Line:    n.Pos().Line,
-					Column:  n.Pos().Column,
-					Rule:    "long-string",
-					Message: fmt.Sprintf("string literal is very long (%d characters)", len(n.Value)),
-					Level:   "warning",
-				})
-			}
-		}
-
-		return true
-	})
-
-	// Check line-level issues
-	for i, line := range lines {
-		lineNum := i + 1
-
-		// Check for trailing whitespace
-		if strings.HasSuffix(line, " ") || strings.HasSuffix(line, "\t") {
-			issues = append(issues, LintIssue{
-				Line:    lineNum,
-				Column:  len(line),
-				Rule:    "trailing-whitespace",
-				Message: "trailing whitespace",
-				Level:   "warning",
-			})
-		}
-
-		// Check for very long lines
-		if len(line) > 120 {
-			issues = append(issues, LintIssue{
-				Line:    lineNum,
-				Column:  121,
-				Rule:    "line-too-long",
-				Message: fmt.Sprintf("line exceeds 120 characters (%d)", len(line)),
-				Level:   "warning",
-			})
-		}
-
-		// Check for TODO/FIXME comments
-		if strings.Contains(line, "TODO") || strings.Contains(line, "FIXME") {
-			issues = append(issues, LintIssue{
-				Line:    lineNum,
-				Column:  1,
-				Rule:    "todo-comment",
-				Message: "TODO/FIXME comment found",
-				Level:   "warning",
-			})
-		}
+	for _, issue := range linter.Lint(program, source) {
+		issues = append(issues, LintIssue{
+			Line:    issue.Position.LineNumber(),
+			Column:  issue.Position.ColumnNumber(),
+			Rule:    issue.Rule,
+			Message: issue.Message,
+			Level:   issue.Severity.String(),
+		})
 	}
-
 	return issues
 }
 
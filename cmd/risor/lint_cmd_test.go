@@ -4,6 +4,7 @@ import (
 	"context"
 	"testing"
 
+	"github.com/deepnoodle-ai/risor/v2/pkg/lint"
 	"github.com/deepnoodle-ai/risor/v2/pkg/parser"
 	"github.com/deepnoodle-ai/wonton/assert"
 )
@@ -241,3 +242,22 @@ func TestGetLintCode_NoInput(t *testing.T) {
 		assert.Equal(t, issue.Column, 5)
 	})
 }
+
+func TestLintProgram_SecurityRules(t *testing.T) {
+	code := `let f = os.open("data.txt")`
+	program, err := parser.Parse(context.Background(), code, nil)
+	assert.Nil(t, err)
+
+	for _, issue := range lintProgram(program, code) {
+		assert.True(t, issue.Rule != "uses-capability")
+	}
+
+	found := false
+	for _, issue := range lintProgram(program, code, lint.SecurityRules()...) {
+		if issue.Rule == "uses-capability" {
+			found = true
+			assert.Equal(t, issue.Level, "error")
+		}
+	}
+	assert.True(t, found, "expected uses-capability error")
+}
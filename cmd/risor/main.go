@@ -13,6 +13,19 @@ var (
 	date    = "unknown"
 )
 
+// The flags of the root command that take a value, declared with valueFlag
+// so that splitScriptArgs skips their values when it looks for the script.
+var (
+	codeFlag       = valueFlag(cli.String, "code", "c")
+	varFlag        = valueFlag(cli.Strings, "var", "")
+	varJSONFlag    = valueFlag(cli.String, "var-json", "")
+	inputFlag      = valueFlag(cli.String, "input", "")
+	cpuProfileFlag = valueFlag(cli.String, "cpu-profile", "")
+	outputFlag     = valueFlag(cli.String, "output", "o")
+	timeoutFlag    = valueFlag(cli.String, "timeout", "")
+	maxMemoryFlag  = valueFlag(cli.String, "max-memory", "")
+)
+
 func main() {
 	app := cli.New("risor").
 		Description("Fast and flexible scripting for Go developers").
@@ -21,11 +34,12 @@ func main() {
 
 	// Global flags
 	app.GlobalFlags(
-		cli.String("code", "c").Help("Code to evaluate"),
+		codeFlag.Help("Code to evaluate"),
 		cli.Bool("stdin", "").Help("Read code from stdin"),
-		cli.Strings("var", "").Help("Set a variable (key=value)"),
-		cli.String("var-json", "").Help("Set variables from a JSON object"),
-		cli.String("cpu-profile", "").Help("Capture CPU profile"),
+		varFlag.Help("Set a variable (key=value)"),
+		varJSONFlag.Help("Set variables from a JSON object"),
+		inputFlag.Enum("json").Help("Parse piped stdin into the data variable"),
+		cpuProfileFlag.Help("Capture CPU profile"),
 		cli.Bool("no-color", "").Env("NO_COLOR").Help("Disable colored output"),
 		cli.Bool("no-default-globals", "").Help("Disable the standard library"),
 	)
@@ -35,8 +49,11 @@ func main() {
 		Args("file?").
 		Flags(
 			cli.Bool("timing", "").Help("Show execution time"),
-			cli.String("output", "o").Enum("json", "text").Help("Output format"),
+			outputFlag.Enum("json", "pretty", "raw", "text").Help("Output format"),
 			cli.Bool("no-repl", "").Help("Disable the REPL"),
+			cli.Bool("no-cache", "").Env("RISOR_NO_CACHE").Help("Disable the compile cache"),
+			timeoutFlag.Env("RISOR_TIMEOUT").Help("Stop the script after a duration (e.g. 30s)"),
+			maxMemoryFlag.Env("RISOR_MAX_MEMORY").Help("Stop the script if its heap exceeds a size (e.g. 512MB)"),
 		).
 		Run(runHandler)
 
@@ -123,11 +140,33 @@ func main() {
 		Flags(
 			cli.String("code", "c").Help("Expression to evaluate"),
 			cli.Bool("stdin", "").Help("Read from stdin"),
-			cli.String("output", "o").Enum("json", "text").Help("Output format"),
+			cli.String("output", "o").Enum("json", "pretty", "raw", "text").Help("Output format"),
 			cli.Bool("quiet", "q").Help("Suppress output"),
 		).
 		Run(evalHandler)
 
+	// Test-expr command
+	app.Command("test-expr").
+		Description("Exit 0 if an expression is truthy, 1 if falsy, 2 on error").
+		Args("expr?").
+		Flags(
+			cli.String("code", "c").Help("Expression to evaluate"),
+			cli.Bool("stdin", "").Help("Read from stdin"),
+			cli.Bool("quiet", "q").Help("Suppress error messages"),
+		).
+		Run(testExprHandler)
+
+	// Render command
+	app.Command("render").
+		Description("Render a text template with ${expr} holes").
+		Args("file?").
+		Flags(
+			cli.String("code", "c").Help("Template to render"),
+			cli.Bool("stdin", "").Help("Read the template from stdin"),
+			cli.Bool("lenient", "").Help("Treat undefined names as null"),
+		).
+		Run(renderHandler)
+
 	// Lint command
 	app.Command("lint").
 		Description("Check code for issues").
@@ -136,9 +175,21 @@ func main() {
 			cli.String("code", "c").Help("Code to check"),
 			cli.Bool("stdin", "").Help("Read code from stdin"),
 			cli.String("output", "o").Enum("json", "text").Help("Output format"),
+			cli.Bool("security", "").Help("Flag capabilities, dynamic code, and unbounded execution"),
 		).
 		Run(lintHandler)
 
+	// Migrate command
+	app.Command("migrate").
+		Description("Report Risor v1 constructs that need changes for v2").
+		Args("file?").
+		Flags(
+			cli.String("code", "c").Help("Code to check"),
+			cli.Bool("stdin", "").Help("Read code from stdin"),
+			cli.String("output", "o").Enum("json", "text").Help("Output format"),
+		).
+		Run(migrateHandler)
+
 	// Benchmark command
 	app.Command("bench").
 		Description("Benchmark code execution").
@@ -152,12 +203,15 @@ func main() {
 		).
 		Run(benchHandler)
 
-	if err := app.Execute(); err != nil {
+	// Arguments after the script path are passed to the script as os.args
+	var cliArgs []string
+	cliArgs, scriptArgs = splitScriptArgs(os.Args[1:])
+	if err := app.ExecuteArgs(cliArgs); err != nil {
 		if cli.IsHelpRequested(err) {
 			return
 		}
 		printError(err.Error())
-		os.Exit(cli.GetExitCode(err))
+		exit(cli.GetExitCode(err))
 	}
 }
 
@@ -0,0 +1,193 @@
+package main
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"strconv"
+	"strings"
+
+	"github.com/deepnoodle-ai/risor/v2"
+)
+
+// manifestName is the name of the project manifest file.
+const manifestName = "risor.toml"
+
+// manifest holds the settings read from a risor.toml file. Only a small
+// subset of TOML is supported:
+//
+//	# Directories searched for non-relative imports, in order
+//	[modules]
+//	paths = ["lib", "vendor"]
+type manifest struct {
+	// dir is the directory containing the manifest.
+	dir string
+	// modulePaths are the module search paths, relative to dir.
+	modulePaths []string
+}
+
+// searchPaths returns the module search paths resolved against the
+// directory containing the manifest.
+func (m *manifest) searchPaths() []string {
+	paths := make([]string, len(m.modulePaths))
+	for i, p := range m.modulePaths {
+		if filepath.IsAbs(p) {
+			paths[i] = p
+		} else {
+			paths[i] = filepath.Join(m.dir, filepath.FromSlash(p))
+		}
+	}
+	return paths
+}
+
+// findManifest looks for a risor.toml file in dir and each of its parents.
+// It returns nil if there is none.
+func findManifest(dir string) (*manifest, error) {
+	dir, err := filepath.Abs(dir)
+	if err != nil {
+		return nil, err
+	}
+	for {
+		path := filepath.Join(dir, manifestName)
+		data, err := os.ReadFile(path)
+		if err == nil {
+			m, err := parseManifest(string(data))
+			if err != nil {
+				return nil, fmt.Errorf("%s: %w", path, err)
+			}
+			m.dir = dir
+			return m, nil
+		}
+		if !os.IsNotExist(err) {
+			return nil, err
+		}
+		parent := filepath.Dir(dir)
+		if parent == dir {
+			return nil, nil
+		}
+		dir = parent
+	}
+}
+
+// parseManifest parses the contents of a risor.toml file.
+func parseManifest(data string) (*manifest, error) {
+	m := &manifest{}
+	section := ""
+	lines := strings.Split(data, "\n")
+	for i := 0; i < len(lines); i++ {
+		lineNum := i + 1
+		line := strings.TrimSpace(stripComment(lines[i]))
+		if line == "" {
+			continue
+		}
+		if strings.HasPrefix(line, "[") {
+			if !strings.HasSuffix(line, "]") {
+				return nil, fmt.Errorf("line %d: malformed section header", lineNum)
+			}
+			section = strings.TrimSpace(line[1 : len(line)-1])
+			if section != "modules" {
+				return nil, fmt.Errorf("line %d: unknown section [%s]", lineNum, section)
+			}
+			continue
+		}
+		key, value, ok := strings.Cut(line, "=")
+		if !ok {
+			return nil, fmt.Errorf("line %d: expected key = value", lineNum)
+		}
+		key = strings.TrimSpace(key)
+		value = strings.TrimSpace(value)
+		if section != "modules" || key != "paths" {
+			return nil, fmt.Errorf("line %d: unknown key %q", lineNum, key)
+		}
+		// Arrays may span several lines
+		for strings.HasPrefix(value, "[") && !strings.HasSuffix(value, "]") && i+1 < len(lines) {
+			i++
+			value += " " + strings.TrimSpace(stripComment(lines[i]))
+		}
+		paths, err := parseStringArray(value)
+		if err != nil {
+			return nil, fmt.Errorf("line %d: %s: %w", lineNum, key, err)
+		}
+		m.modulePaths = paths
+	}
+	return m, nil
+}
+
+// stripComment removes a trailing # comment from a line, ignoring any #
+// inside a quoted string.
+func stripComment(line string) string {
+	inString := false
+	for i := 0; i < len(line); i++ {
+		switch line[i] {
+		case '\\':
+			if inString {
+				i++
+			}
+		case '"':
+			inString = !inString
+		case '#':
+			if !inString {
+				return line[:i]
+			}
+		}
+	}
+	return line
+}
+
+// parseStringArray parses a TOML array of basic strings, such as
+// ["lib", "vendor"]. A trailing comma is allowed.
+func parseStringArray(value string) ([]string, error) {
+	if !strings.HasPrefix(value, "[") || !strings.HasSuffix(value, "]") {
+		return nil, fmt.Errorf("expected an array of strings")
+	}
+	rest := strings.TrimSpace(value[1 : len(value)-1])
+	result := []string{}
+	for rest != "" {
+		if rest[0] != '"' {
+			return nil, fmt.Errorf("expected a string, got %q", rest)
+		}
+		end := 1
+		for end < len(rest) && rest[end] != '"' {
+			if rest[end] == '\\' {
+				end++
+			}
+			end++
+		}
+		if end >= len(rest) {
+			return nil, fmt.Errorf("unterminated string")
+		}
+		s, err := strconv.Unquote(rest[:end+1])
+		if err != nil {
+			return nil, fmt.Errorf("invalid string %s", rest[:end+1])
+		}
+		result = append(result, s)
+		rest = strings.TrimSpace(rest[end+1:])
+		if rest == "" {
+			break
+		}
+		if rest[0] != ',' {
+			return nil, fmt.Errorf("expected a comma between strings")
+		}
+		rest = strings.TrimSpace(rest[1:])
+	}
+	return result, nil
+}
+
+// newImporter returns the importer for a script. Module search paths come
+// from the nearest risor.toml, found by searching upward from the script's
+// directory, or from the working directory when no script file is given.
+func newImporter(file string, opts []risor.Option) (*risor.Importer, error) {
+	dir := "."
+	if file != "" {
+		dir = filepath.Dir(file)
+	}
+	m, err := findManifest(dir)
+	if err != nil {
+		return nil, err
+	}
+	var searchPaths []string
+	if m != nil {
+		searchPaths = m.searchPaths()
+	}
+	return risor.NewFileImporter(searchPaths, opts...), nil
+}
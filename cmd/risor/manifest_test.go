@@ -0,0 +1,82 @@
+package main
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/deepnoodle-ai/risor/v2"
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+func TestParseManifest(t *testing.T) {
+	m, err := parseManifest(`
+# Project settings
+[modules]
+paths = ["lib", "vendor"] # searched in order
+`)
+	assert.Nil(t, err)
+	assert.Equal(t, m.modulePaths, []string{"lib", "vendor"})
+
+	m, err = parseManifest(`
+[modules]
+paths = [
+    "lib",
+    "third #party",
+]
+`)
+	assert.Nil(t, err)
+	assert.Equal(t, m.modulePaths, []string{"lib", "third #party"})
+
+	m, err = parseManifest("")
+	assert.Nil(t, err)
+	assert.Len(t, m.modulePaths, 0)
+}
+
+func TestParseManifestErrors(t *testing.T) {
+	tests := []struct {
+		input string
+		err   string
+	}{
+		{"[package]\nname = \"x\"", "line 1: unknown section [package]"},
+		{"paths = [\"lib\"]", `line 1: unknown key "paths"`},
+		{"[modules]\npath = [\"lib\"]", `line 2: unknown key "path"`},
+		{"[modules]\npaths = \"lib\"", "line 2: paths: expected an array of strings"},
+		{"[modules]\npaths = [\"lib\" \"vendor\"]", "expected a comma between strings"},
+		{"[modules]\npaths = [\"lib\"", "expected an array of strings"},
+		{"[modules\n", "line 1: malformed section header"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.input, func(t *testing.T) {
+			_, err := parseManifest(tt.input)
+			assert.NotNil(t, err)
+			assert.Contains(t, err.Error(), tt.err)
+		})
+	}
+}
+
+func TestFindManifest(t *testing.T) {
+	dir := t.TempDir()
+	scripts := filepath.Join(dir, "scripts", "jobs")
+	assert.Nil(t, os.MkdirAll(scripts, 0o755))
+	assert.Nil(t, os.WriteFile(filepath.Join(dir, manifestName),
+		[]byte("[modules]\npaths = [\"lib\", \"vendor\"]\n"), 0o644))
+
+	// The manifest is found from a nested directory, and its search paths
+	// are relative to the directory containing it
+	m, err := findManifest(scripts)
+	assert.Nil(t, err)
+	assert.NotNil(t, m)
+	assert.Equal(t, m.searchPaths(), []string{
+		filepath.Join(dir, "lib"),
+		filepath.Join(dir, "vendor"),
+	})
+
+	imp, err := newImporter(filepath.Join(scripts, "main.risor"), nil)
+	assert.Nil(t, err)
+	loader, ok := imp.Loader().(*risor.MultiLoader)
+	assert.True(t, ok)
+	files, ok := loader.Loaders()[0].(*risor.FileLoader)
+	assert.True(t, ok)
+	assert.Equal(t, files.SearchPaths(), m.searchPaths())
+}
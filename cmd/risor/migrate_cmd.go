@@ -0,0 +1,297 @@
+package main
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"os"
+	"regexp"
+	"strings"
+	"unicode/utf8"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/parser"
+	"github.com/deepnoodle-ai/wonton/cli"
+	"github.com/deepnoodle-ai/wonton/tui"
+)
+
+// MigrationIssue is a construct of a Risor v1 script that v2 doesn't support
+// or runs differently, along with a suggested rewrite.
+type MigrationIssue struct {
+	Line       int
+	Column     int
+	Rule       string
+	Message    string
+	Suggestion string
+}
+
+// migrationRule matches a v1 construct in a line of code. The message may
+// refer to the pattern's submatches, as in "$1".
+type migrationRule struct {
+	name       string
+	pattern    *regexp.Regexp
+	message    string
+	suggestion string
+}
+
+// migrationRules describe the changes listed in the v1 to v2 migration guide.
+// Since v1 scripts usually don't parse as v2, the rules match source text
+// rather than the AST.
+var migrationRules = []migrationRule{
+	{
+		name:       "hash-comment",
+		pattern:    regexp.MustCompile(`#`),
+		message:    "# comments were removed",
+		suggestion: "use // comments",
+	},
+	{
+		name:       "for-loop",
+		pattern:    regexp.MustCompile(`^\s*for\b`),
+		message:    "for loops were removed",
+		suggestion: "iterate with items.each(x => ...), range(n).each(i => ...), map, filter, or reduce",
+	},
+	{
+		name:       "loop-control",
+		pattern:    regexp.MustCompile(`\b(break|continue)\b`),
+		message:    "$1 was removed along with loops",
+		suggestion: "return early from the callback, or filter items before iterating",
+	},
+	{
+		name:       "defer",
+		pattern:    regexp.MustCompile(`\bdefer\b`),
+		message:    "defer was removed",
+		suggestion: "run cleanup in a finally block: try { ... } finally { cleanup() }",
+	},
+	{
+		name:       "goroutine",
+		pattern:    regexp.MustCompile(`(?:^|[^.\w])go\s+(?:func\b|\w+\s*\()`),
+		message:    "goroutines were removed, as each VM is single-threaded",
+		suggestion: "run work in parallel with separate VMs from Go",
+	},
+	{
+		name:       "spawn",
+		pattern:    regexp.MustCompile(`\bspawn\s*\(`),
+		message:    "spawn() was removed, as each VM is single-threaded",
+		suggestion: "run work in parallel with separate VMs from Go",
+	},
+	{
+		name:       "channel",
+		pattern:    regexp.MustCompile(`\bchan\b|<-`),
+		message:    "channels were removed",
+		suggestion: "pass values as function arguments and return values",
+	},
+	{
+		name:       "switch",
+		pattern:    regexp.MustCompile(`\bswitch\b`),
+		message:    "switch statements were removed",
+		suggestion: "use a match expression or if/else",
+	},
+	{
+		name:       "module-import",
+		pattern:    regexp.MustCompile(`^\s*(?:import\s+[A-Za-z_]|from\s+\w+\s+import\b)`),
+		message:    "module imports were removed, as modules come from the environment",
+		suggestion: `have the host provide the module, or import a Risor file with import "./path"`,
+	},
+	{
+		name:       "if-parens",
+		pattern:    regexp.MustCompile(`(?:^|[^.\w])if\s+[^\s(]`),
+		message:    "if conditions require parentheses",
+		suggestion: "write if (condition) { ... }",
+	},
+	{
+		name:       "short-var",
+		pattern:    regexp.MustCompile(`:=`),
+		message:    ":= declarations were removed",
+		suggestion: "declare variables with let or const",
+	},
+	{
+		name:       "func-keyword",
+		pattern:    regexp.MustCompile(`\bfunc\b`),
+		message:    "func was renamed to function",
+		suggestion: "write function name(args) { ... } or an arrow function such as x => x * 2",
+	},
+	{
+		name:       "try-builtin",
+		pattern:    regexp.MustCompile(`\btry\s*\(`),
+		message:    "the try() builtin was removed",
+		suggestion: "use try { ... } catch (e) { ... }",
+	},
+	{
+		name:       "removed-builtin",
+		pattern:    regexp.MustCompile(`(?:^|[^.\w])(delete|make|iter|is_hashable)\s*\(`),
+		message:    "the $1() builtin was removed",
+		suggestion: "see Removed Builtins in docs/guides/migration-v2.md",
+	},
+	{
+		name:       "removed-type",
+		pattern:    regexp.MustCompile(`(?:^|[^.\w])(buffer|set|float_slice)\s*\(`),
+		message:    "the $1 type was removed",
+		suggestion: "use bytes for binary data and list for collections",
+	},
+	{
+		name:       "byte-slice",
+		pattern:    regexp.MustCompile(`\bbyte_slice\s*\(`),
+		message:    "byte_slice was renamed to bytes",
+		suggestion: "call bytes(...)",
+	},
+	{
+		name:       "removed-module",
+		pattern:    regexp.MustCompile(`(?:^|[^.\w])(http|exec|ssh|dns|bcrypt|filepath|errors|fmt|strings|json)\.[A-Za-z_]`),
+		message:    "the $1 module is no longer built in",
+		suggestion: "have the host provide it, or use the builtins and type methods that replace it",
+	},
+}
+
+func migrateHandler(ctx *cli.Context) error {
+	code, filename, err := getLintCode(ctx)
+	if err != nil {
+		return err
+	}
+	if filename == "" {
+		filename = "<stdin>"
+	}
+	issues := checkMigration(code)
+	printMigrationResults(filename, issues, ctx.String("output"))
+	if len(issues) > 0 {
+		exit(1)
+	}
+	return nil
+}
+
+// checkMigration reports the constructs of a v1 script that need changes to
+// run on v2. If no rule matches but the script still doesn't parse, the parse
+// error is reported.
+func checkMigration(source string) []MigrationIssue {
+	var issues []MigrationIssue
+	for i, line := range codeLines(source) {
+		for _, rule := range migrationRules {
+			for _, loc := range rule.pattern.FindAllStringSubmatchIndex(line, -1) {
+				start := loc[0]
+				for start < loc[1] && strings.ContainsRune(" \t(){};,=", rune(line[start])) {
+					start++
+				}
+				issues = append(issues, MigrationIssue{
+					Line:       i + 1,
+					Column:     utf8.RuneCountInString(line[:start]) + 1,
+					Rule:       rule.name,
+					Message:    string(rule.pattern.ExpandString(nil, rule.message, line, loc)),
+					Suggestion: rule.suggestion,
+				})
+			}
+		}
+	}
+	if len(issues) == 0 {
+		if _, err := parser.Parse(context.Background(), source, nil); err != nil {
+			issues = append(issues, MigrationIssue{
+				Line:       1,
+				Column:     1,
+				Rule:       "parse-error",
+				Message:    err.Error(),
+				Suggestion: "see docs/guides/migration-v2.md",
+			})
+		}
+	}
+	return issues
+}
+
+// codeLines splits source into lines with the contents of strings and
+// comments replaced by spaces, so that rules only match code. The "#" of a
+// hash comment is kept so that it can be reported. A shebang line is blank.
+func codeLines(source string) []string {
+	lines := strings.Split(source, "\n")
+	inBlockComment := false
+	inRawString := false
+	for n, line := range lines {
+		if n == 0 && strings.HasPrefix(line, "#!") {
+			lines[n] = ""
+			continue
+		}
+		b := []byte(line)
+		var quote byte
+		for i := 0; i < len(b); i++ {
+			c := b[i]
+			switch {
+			case inBlockComment:
+				if c == '*' && i+1 < len(b) && b[i+1] == '/' {
+					inBlockComment = false
+					b[i+1] = ' '
+				}
+				b[i] = ' '
+			case inRawString:
+				inRawString = c != '`'
+				b[i] = ' '
+			case quote != 0:
+				if c == '\\' && i+1 < len(b) {
+					b[i+1] = ' '
+				} else if c == quote {
+					quote = 0
+				}
+				b[i] = ' '
+			case c == '"' || c == '\'':
+				quote = c
+				b[i] = ' '
+			case c == '`':
+				inRawString = true
+				b[i] = ' '
+			case c == '/' && i+1 < len(b) && b[i+1] == '*':
+				inBlockComment = true
+				b[i] = ' '
+			case c == '/' && i+1 < len(b) && b[i+1] == '/':
+				blank(b[i:])
+				i = len(b)
+			case c == '#':
+				blank(b[i+1:])
+				i = len(b)
+			}
+		}
+		lines[n] = string(b)
+	}
+	return lines
+}
+
+func blank(b []byte) {
+	for i := range b {
+		b[i] = ' '
+	}
+}
+
+func printMigrationResults(filename string, issues []MigrationIssue, outputFormat string) {
+	if outputFormat == "json" {
+		result := struct {
+			File   string           `json:"file"`
+			Issues []MigrationIssue `json:"issues"`
+		}{
+			File:   filename,
+			Issues: issues,
+		}
+		enc := json.NewEncoder(os.Stdout)
+		enc.SetIndent("", "  ")
+		enc.Encode(result)
+		return
+	}
+
+	fileStyle := tui.NewStyle().WithFgRGB(tui.RGB{R: 100, G: 200, B: 255})
+	ruleStyle := tui.NewStyle().WithFgRGB(tui.RGB{R: 180, G: 140, B: 220})
+	hintStyle := tui.NewStyle().WithFgRGB(tui.RGB{R: 140, G: 140, B: 140})
+	okStyle := tui.NewStyle().WithFgRGB(tui.RGB{R: 100, G: 220, B: 100})
+	warnStyle := tui.NewStyle().WithFgRGB(tui.RGB{R: 255, G: 200, B: 80})
+
+	if len(issues) == 0 {
+		fmt.Println(tui.Sprint(tui.Group(
+			tui.Text("%s: ", filename).Style(fileStyle),
+			tui.Text("no changes needed").Style(okStyle),
+		)))
+		return
+	}
+
+	for _, issue := range issues {
+		fmt.Println(tui.Sprint(tui.Group(
+			tui.Text("%s:%d:%d:", filename, issue.Line, issue.Column).Style(fileStyle),
+			tui.Text(" [%s]", issue.Rule).Style(ruleStyle),
+			tui.Text(" %s", issue.Message),
+		)))
+		fmt.Println(tui.Sprint(tui.Text("    suggestion: %s", issue.Suggestion).Style(hintStyle)))
+	}
+
+	fmt.Println()
+	fmt.Println(tui.Sprint(tui.Text("%d issue(s) to migrate", len(issues)).Style(warnStyle)))
+}
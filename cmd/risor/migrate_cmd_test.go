@@ -0,0 +1,72 @@
+package main
+
+import (
+	"testing"
+
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+func TestCheckMigration(t *testing.T) {
+	source := `#!/usr/bin/env risor
+import strings
+# count the items
+total := 0
+for i, item := range items {
+    defer cleanup()
+    if item > 0 { total += item }
+}
+go func() { ch <- total }()
+switch x { }
+let f = func(x) { return x }
+let s = set(1, 2)
+let r = try(f)
+let b = byte_slice("hi")
+delete(m, "k")
+strings.split("a,b", ",")
+`
+	issues := checkMigration(source)
+	found := map[string]MigrationIssue{}
+	for _, issue := range issues {
+		if _, ok := found[issue.Rule]; !ok {
+			found[issue.Rule] = issue
+		}
+	}
+	for _, rule := range []string{
+		"module-import", "hash-comment", "short-var", "for-loop", "defer",
+		"if-parens", "goroutine", "channel", "switch", "func-keyword",
+		"removed-type", "try-builtin", "byte-slice", "removed-builtin",
+		"removed-module",
+	} {
+		_, ok := found[rule]
+		assert.True(t, ok, rule)
+	}
+
+	issue := found["for-loop"]
+	assert.Equal(t, issue.Line, 5)
+	assert.Equal(t, issue.Column, 1)
+	issue = found["defer"]
+	assert.Equal(t, issue.Line, 6)
+	assert.Equal(t, issue.Column, 5)
+	issue = found["removed-builtin"]
+	assert.Equal(t, issue.Message, "the delete() builtin was removed")
+	assert.Equal(t, issue.Column, 1)
+	issue = found["removed-module"]
+	assert.Equal(t, issue.Message, "the strings module is no longer built in")
+	assert.Equal(t, issue.Line, 16)
+}
+
+func TestCheckMigrationIgnoresStringsAndComments(t *testing.T) {
+	source := "#!/usr/bin/env risor\n" +
+		"// for each item, defer nothing\n" +
+		"/* switch x {\n   go func() */\n" +
+		"let msg = \"# not a comment: x := 1\"\n" +
+		"let tpl = `for ${msg}\nswitch`\n" +
+		"if (len(msg) > 0) { print(msg.split(\",\")) }\n"
+	assert.Len(t, checkMigration(source), 0)
+}
+
+func TestCheckMigrationParseError(t *testing.T) {
+	issues := checkMigration("let x = [1, 2")
+	assert.Len(t, issues, 1)
+	assert.Equal(t, issues[0].Rule, "parse-error")
+}
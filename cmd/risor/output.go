@@ -10,6 +10,15 @@ import (
 	"github.com/deepnoodle-ai/wonton/color"
 )
 
+// formatOutput converts the result of a script to text according to the
+// --output flag:
+//
+//   - json: indented JSON without colors
+//   - pretty: indented JSON, colorized when writing to a terminal
+//   - raw: strings as is, other values as compact JSON
+//   - text: the value as it prints in Go
+//
+// With no format set, results are shown as pretty JSON when possible.
 func formatOutput(ctx *cli.Context, result any) (string, error) {
 	format := strings.ToLower(ctx.String("output"))
 	noColor := ctx.Bool("no-color")
@@ -26,11 +35,33 @@ func formatOutput(ctx *cli.Context, result any) (string, error) {
 		}
 		return string(output), nil
 	case "json":
+		// Machine-readable output is never colorized, so it can be piped
+		// into tools like jq even when stdout is a terminal.
+		output, err := formatJSON(result, true)
+		if err != nil {
+			return "", err
+		}
+		return string(output), nil
+	case "pretty":
 		output, err := formatJSON(result, noColor)
 		if err != nil {
 			return "", err
 		}
 		return string(output), nil
+	case "raw":
+		// Like jq -r: strings are printed without quotes or escapes and
+		// other values as compact JSON.
+		switch v := result.(type) {
+		case string:
+			return v, nil
+		case []byte:
+			return string(v), nil
+		}
+		output, err := json.Marshal(result)
+		if err != nil {
+			return "", err
+		}
+		return string(output), nil
 	case "text":
 		return fmt.Sprintf("%v", result), nil
 	default:
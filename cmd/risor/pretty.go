@@ -0,0 +1,250 @@
+package main
+
+import (
+	"fmt"
+	"strconv"
+	"strings"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+)
+
+// Default limits for printing values in the REPL. Both can be changed with
+// the :set command.
+const (
+	defaultPrintDepth  = 4
+	defaultPrintLength = 20
+)
+
+const (
+	// prettyWidth is the width within which containers are printed on one line.
+	prettyWidth = 80
+
+	// prettyStringLength is the number of characters of a string that are
+	// printed before the rest is elided.
+	prettyStringLength = 500
+)
+
+// prettyKind classifies the spans of pretty-printed output so that they can
+// be colored.
+type prettyKind int
+
+const (
+	prettyPlain prettyKind = iota
+	prettyNumber
+	prettyString
+	prettyKey
+	prettyKeyword
+	prettyError
+	prettyMuted
+)
+
+type prettySpan struct {
+	text string
+	kind prettyKind
+}
+
+type prettyLine []prettySpan
+
+func (l prettyLine) width() int {
+	n := 0
+	for _, span := range l {
+		n += len([]rune(span.text))
+	}
+	return n
+}
+
+func (l prettyLine) String() string {
+	var sb strings.Builder
+	for _, span := range l {
+		sb.WriteString(span.text)
+	}
+	return sb.String()
+}
+
+// prettyPrinter formats objects for display, with one item per line for
+// containers that don't fit on a single line. Containers nested more than
+// maxDepth levels deep are summarized, and only the first maxLength items of
+// each container are shown.
+type prettyPrinter struct {
+	maxDepth  int
+	maxLength int
+	lines     []prettyLine
+	active    map[object.Object]bool // Containers being printed, to detect cycles
+}
+
+func newPrettyPrinter(maxDepth, maxLength int) *prettyPrinter {
+	return &prettyPrinter{maxDepth: maxDepth, maxLength: maxLength}
+}
+
+// Format returns the lines of output for obj.
+func (p *prettyPrinter) Format(obj object.Object) []prettyLine {
+	p.lines = []prettyLine{nil}
+	p.active = map[object.Object]bool{}
+	p.value(obj, 0, 0)
+	return p.lines
+}
+
+func (p *prettyPrinter) add(text string, kind prettyKind) {
+	last := len(p.lines) - 1
+	p.lines[last] = append(p.lines[last], prettySpan{text, kind})
+}
+
+func (p *prettyPrinter) newline(indent int) {
+	p.lines = append(p.lines, prettyLine{{strings.Repeat("  ", indent), prettyPlain}})
+}
+
+// value prints obj at the end of the current line, breaking it over several
+// lines if it's a container that doesn't fit in the remaining width.
+func (p *prettyPrinter) value(obj object.Object, depth, indent int) {
+	budget := prettyWidth - p.lines[len(p.lines)-1].width()
+	if line, ok := p.inline(obj, depth, budget); ok {
+		last := len(p.lines) - 1
+		p.lines[last] = append(p.lines[last], line...)
+		return
+	}
+	items, open, close := containerItems(obj)
+	p.active[obj] = true
+	defer delete(p.active, obj)
+	p.add(open, prettyPlain)
+	for _, item := range items[:min(len(items), p.maxLength)] {
+		p.newline(indent + 1)
+		if item.keyed {
+			p.add(strconv.Quote(item.key), prettyKey)
+			p.add(": ", prettyPlain)
+		}
+		p.value(item.value, depth+1, indent+1)
+		p.add(",", prettyPlain)
+	}
+	if hidden := len(items) - p.maxLength; hidden > 0 {
+		p.newline(indent + 1)
+		p.add(fmt.Sprintf("… +%d more", hidden), prettyMuted)
+	}
+	p.newline(indent)
+	p.add(close, prettyPlain)
+}
+
+// inline returns obj formatted on a single line, or false if that would be
+// wider than budget. Values other than containers can't be broken up, so they
+// are always returned.
+func (p *prettyPrinter) inline(obj object.Object, depth, budget int) (prettyLine, bool) {
+	items, open, close := containerItems(obj)
+	if open == "" {
+		return p.scalar(obj), true
+	}
+	if p.active[obj] {
+		return prettyLine{{open + "..." + close, prettyMuted}}, true
+	}
+	if len(items) == 0 {
+		return prettyLine{{open + close, prettyPlain}}, true
+	}
+	if depth >= p.maxDepth {
+		return prettyLine{{fmt.Sprintf("%s… %d %s%s", open, len(items), itemNoun(obj, len(items)), close), prettyMuted}}, true
+	}
+	p.active[obj] = true
+	defer delete(p.active, obj)
+	line := prettyLine{{open, prettyPlain}}
+	for i, item := range items[:min(len(items), p.maxLength)] {
+		if i > 0 {
+			line = append(line, prettySpan{", ", prettyPlain})
+		}
+		if item.keyed {
+			line = append(line, prettySpan{strconv.Quote(item.key), prettyKey}, prettySpan{": ", prettyPlain})
+		}
+		child, ok := p.inline(item.value, depth+1, budget-line.width()-len(close))
+		if !ok {
+			return nil, false
+		}
+		line = append(line, child...)
+		if line.width()+len(close) > budget {
+			return nil, false
+		}
+	}
+	if hidden := len(items) - p.maxLength; hidden > 0 {
+		line = append(line, prettySpan{", ", prettyPlain}, prettySpan{fmt.Sprintf("… +%d more", hidden), prettyMuted})
+	}
+	if _, ok := obj.(*object.Tuple); ok && len(items) == 1 {
+		line = append(line, prettySpan{",", prettyPlain})
+	}
+	line = append(line, prettySpan{close, prettyPlain})
+	if line.width() > budget {
+		return nil, false
+	}
+	return line, true
+}
+
+func (p *prettyPrinter) scalar(obj object.Object) prettyLine {
+	switch obj := obj.(type) {
+	case *object.Int, *object.Float, *object.Byte, *object.Decimal:
+		return prettyLine{{object.Repr(obj), prettyNumber}}
+	case *object.Bool, *object.NilType:
+		return prettyLine{{obj.Inspect(), prettyKeyword}}
+	case *object.Error:
+		return prettyLine{{obj.Inspect(), prettyError}}
+	case *object.String:
+		value := obj.Value()
+		runes := []rune(value)
+		if len(runes) <= prettyStringLength {
+			return prettyLine{{strconv.Quote(value), prettyString}}
+		}
+		return prettyLine{
+			{strconv.Quote(string(runes[:prettyStringLength])), prettyString},
+			{fmt.Sprintf(" … +%d chars", len(runes)-prettyStringLength), prettyMuted},
+		}
+	}
+	return prettyLine{{object.Repr(obj), prettyPlain}}
+}
+
+type prettyItem struct {
+	key   string
+	keyed bool // False for list and tuple items
+	value object.Object
+}
+
+// containerItems returns the items of a list, tuple, or map, along with its
+// opening and closing brackets. The brackets are empty for other objects.
+func containerItems(obj object.Object) ([]prettyItem, string, string) {
+	switch obj := obj.(type) {
+	case *object.List:
+		values := obj.Value()
+		items := make([]prettyItem, len(values))
+		for i, v := range values {
+			items[i] = prettyItem{value: v}
+		}
+		return items, "[", "]"
+	case *object.Tuple:
+		values := obj.Value()
+		items := make([]prettyItem, len(values))
+		for i, v := range values {
+			items[i] = prettyItem{value: v}
+		}
+		return items, "(", ")"
+	case *object.Map:
+		keys := obj.SortedKeys()
+		items := make([]prettyItem, len(keys))
+		for i, k := range keys {
+			items[i] = prettyItem{key: k, keyed: true, value: obj.Get(k)}
+		}
+		return items, "{", "}"
+	}
+	return nil, "", ""
+}
+
+func itemNoun(obj object.Object, n int) string {
+	noun := "item"
+	if _, ok := obj.(*object.Map); ok {
+		noun = "key"
+	}
+	if n != 1 {
+		noun += "s"
+	}
+	return noun
+}
+
+// prettyTypeName describes obj's type for the REPL, including the length of
+// containers, as in "list(3)".
+func prettyTypeName(obj object.Object) string {
+	if items, open, _ := containerItems(obj); open != "" {
+		return fmt.Sprintf("%s(%d)", obj.Type(), len(items))
+	}
+	return string(obj.Type())
+}
@@ -0,0 +1,71 @@
+package main
+
+import (
+	"strings"
+	"testing"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+func formatPretty(obj object.Object, depth, length int) string {
+	lines := newPrettyPrinter(depth, length).Format(obj)
+	text := make([]string, len(lines))
+	for i, line := range lines {
+		text[i] = line.String()
+	}
+	return strings.Join(text, "\n")
+}
+
+func TestPrettyPrintInline(t *testing.T) {
+	obj := object.NewMap(map[string]object.Object{
+		"b": object.NewList([]object.Object{object.NewInt(1), object.NewString("x")}),
+		"a": object.True,
+		"c": object.NewTuple([]object.Object{object.Nil}),
+	})
+	assert.Equal(t, formatPretty(obj, 4, 20), `{"a": true, "b": [1, "x"], "c": (null,)}`)
+	assert.Equal(t, formatPretty(object.NewList(nil), 4, 20), "[]")
+	assert.Equal(t, formatPretty(object.NewInt(42), 4, 20), "42")
+}
+
+func TestPrettyPrintMultiline(t *testing.T) {
+	long := strings.Repeat("x", 40)
+	obj := object.NewMap(map[string]object.Object{
+		"first":  object.NewString(long),
+		"second": object.NewList([]object.Object{object.NewString(long), object.NewString(long)}),
+	})
+	expected := `{
+  "first": "` + long + `",
+  "second": [
+    "` + long + `",
+    "` + long + `",
+  ],
+}`
+	assert.Equal(t, formatPretty(obj, 4, 20), expected)
+}
+
+func TestPrettyPrintLimits(t *testing.T) {
+	items := make([]object.Object, 30)
+	for i := range items {
+		items[i] = object.NewInt(int64(i))
+	}
+	list := object.NewList(items)
+	assert.Equal(t, formatPretty(list, 4, 3), "[0, 1, 2, … +27 more]")
+
+	nested := object.NewList([]object.Object{
+		object.NewList([]object.Object{object.NewMap(map[string]object.Object{"a": object.NewInt(1)})}),
+	})
+	assert.Equal(t, formatPretty(nested, 2, 20), "[[{… 1 key}]]")
+	assert.Equal(t, formatPretty(nested, 0, 20), "[… 1 item]")
+}
+
+func TestPrettyPrintCycle(t *testing.T) {
+	list := object.NewList([]object.Object{object.NewInt(1)})
+	list.Append(list)
+	assert.Equal(t, formatPretty(list, 4, 20), "[1, [...]]")
+}
+
+func TestPrettyTypeName(t *testing.T) {
+	assert.Equal(t, prettyTypeName(object.NewList([]object.Object{object.Nil})), "list(1)")
+	assert.Equal(t, prettyTypeName(object.NewString("a")), "string")
+}
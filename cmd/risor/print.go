@@ -0,0 +1,172 @@
+package main
+
+import (
+	"bufio"
+	"context"
+	"fmt"
+	"os"
+	"sync"
+	"time"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+)
+
+// stdout is where print and the other output builtins write. Output is
+// buffered so that a script printing many lines doesn't make a write call for
+// each one. When stdout is a terminal, each print is flushed right away so
+// that output shows up as it happens. Commands that run scripts flush it
+// before writing anything else to stdout.
+var stdout = newScriptOutput()
+
+// scriptOutput is a buffered writer for what scripts print.
+type scriptOutput struct {
+	mu       sync.Mutex
+	w        *bufio.Writer
+	terminal bool
+}
+
+func newScriptOutput() *scriptOutput {
+	info, err := os.Stdout.Stat()
+	return &scriptOutput{
+		w:        bufio.NewWriterSize(stdoutWriter{}, 64*1024),
+		terminal: err == nil && info.Mode()&os.ModeCharDevice != 0,
+	}
+}
+
+// stdoutWriter writes to whatever os.Stdout is at the time of the write.
+type stdoutWriter struct{}
+
+func (stdoutWriter) Write(p []byte) (int, error) {
+	return os.Stdout.Write(p)
+}
+
+// println writes values separated by spaces and followed by a newline, as
+// fmt.Println does.
+func (o *scriptOutput) println(values ...any) error {
+	o.mu.Lock()
+	defer o.mu.Unlock()
+	if _, err := fmt.Fprintln(o.w, values...); err != nil {
+		return err
+	}
+	if o.terminal {
+		return o.w.Flush()
+	}
+	return nil
+}
+
+// Flush writes any buffered output to stdout.
+func (o *scriptOutput) Flush() error {
+	o.mu.Lock()
+	defer o.mu.Unlock()
+	return o.w.Flush()
+}
+
+// flushAfter flushes the output of a script that finished with err. It
+// returns err, or the error from flushing if the script succeeded.
+func (o *scriptOutput) flushAfter(err error) error {
+	if flushErr := o.Flush(); flushErr != nil && err == nil {
+		return flushErr
+	}
+	return err
+}
+
+// exitFlushTimeout bounds how long exit waits to write buffered output. A
+// script that is still running may hold the output, or stdout may be a pipe
+// that nobody reads.
+const exitFlushTimeout = time.Second
+
+// exit writes any buffered output and then exits with the given status.
+// Commands call it instead of os.Exit, which would drop the output.
+func exit(code int) {
+	flushed := make(chan struct{})
+	go func() {
+		_ = stdout.Flush()
+		close(flushed)
+	}()
+	select {
+	case <-flushed:
+	case <-time.After(exitFlushTimeout):
+	}
+	os.Exit(code)
+}
+
+// outputBuiltins returns the builtins that the CLI provides for writing to
+// stdout.
+func outputBuiltins() map[string]any {
+	return map[string]any{
+		"print":       newPrintBuiltin(),
+		"print_lines": newPrintLinesBuiltin(),
+		"flush":       newFlushBuiltin(),
+		"help":        newHelpBuiltin(),
+	}
+}
+
+func newPrintBuiltin() *object.Builtin {
+	return object.NewBuiltin("print", func(ctx context.Context, args ...object.Object) (object.Object, error) {
+		values := make([]any, len(args))
+		for i, arg := range args {
+			value, err := printValue(ctx, arg)
+			if err != nil {
+				return nil, err
+			}
+			values[i] = value
+		}
+		if err := stdout.println(values...); err != nil {
+			return nil, err
+		}
+		return object.Nil, nil
+	})
+}
+
+// newPrintLinesBuiltin returns a builtin that prints each item of a list, or
+// any other iterable, on its own line, as a print call for each item would.
+func newPrintLinesBuiltin() *object.Builtin {
+	return object.NewBuiltin("print_lines", func(ctx context.Context, args ...object.Object) (object.Object, error) {
+		if len(args) != 1 {
+			return nil, fmt.Errorf("print_lines: expected 1 argument, got %d", len(args))
+		}
+		items, ok := args[0].(object.Enumerable)
+		if !ok {
+			return nil, object.TypeErrorf("print_lines() expected an enumerable (%s given)", args[0].Type())
+		}
+		var err error
+		items.Enumerate(ctx, func(_, item object.Object) bool {
+			var value any
+			if value, err = printValue(ctx, item); err != nil {
+				return false
+			}
+			err = stdout.println(value)
+			return err == nil
+		})
+		if err != nil {
+			return nil, err
+		}
+		return object.Nil, nil
+	})
+}
+
+// newFlushBuiltin returns a builtin that writes any output that print has
+// buffered.
+func newFlushBuiltin() *object.Builtin {
+	return object.NewBuiltin("flush", func(ctx context.Context, args ...object.Object) (object.Object, error) {
+		if len(args) != 0 {
+			return nil, fmt.Errorf("flush: expected 0 arguments, got %d", len(args))
+		}
+		if err := stdout.Flush(); err != nil {
+			return nil, err
+		}
+		return object.Nil, nil
+	})
+}
+
+// printValue returns the value that print writes for an object: the result
+// of its __str__ method if it has one, and otherwise its printable value.
+func printValue(ctx context.Context, obj object.Object) (any, error) {
+	if str, ok, err := object.CallStr(ctx, obj); ok {
+		if err != nil {
+			return nil, err
+		}
+		return str, nil
+	}
+	return object.PrintableValue(obj), nil
+}
@@ -0,0 +1,38 @@
+package main
+
+import (
+	"errors"
+	"fmt"
+
+	"github.com/deepnoodle-ai/risor/v2"
+	"github.com/deepnoodle-ai/wonton/cli"
+)
+
+func renderHandler(ctx *cli.Context) error {
+	if !ctx.IsSet("code") && !ctx.Bool("stdin") && ctx.Arg(0) == "" {
+		return errors.New("no template provided")
+	}
+	text, err := getRisorCode(ctx)
+	if err != nil {
+		return err
+	}
+
+	opts, err := getRisorOptions(ctx, true)
+	if err != nil {
+		return err
+	}
+	if file := ctx.Arg(0); file != "" {
+		opts = append(opts, risor.WithFilename(file))
+	}
+	if ctx.Bool("lenient") {
+		opts = append(opts, risor.WithUndefinedNames(risor.UndefinedNil))
+	}
+
+	out, err := risor.Render(ctx.Context(), text, opts...)
+	err = stdout.flushAfter(err)
+	if err != nil {
+		return err
+	}
+	fmt.Print(out)
+	return nil
+}
@@ -0,0 +1,66 @@
+package main
+
+import (
+	"bytes"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/deepnoodle-ai/wonton/assert"
+	"github.com/deepnoodle-ai/wonton/cli"
+)
+
+func runRender(t *testing.T, args ...string) (string, error) {
+	t.Helper()
+	app := cli.New("risor").SetColorEnabled(false)
+	app.GlobalFlags(
+		cli.Strings("var", ""),
+	)
+	app.Command("render").
+		Args("file?").
+		Flags(
+			cli.String("code", "c"),
+			cli.Bool("stdin", ""),
+			cli.Bool("lenient", ""),
+		).
+		Run(renderHandler)
+
+	old := os.Stdout
+	r, w, _ := os.Pipe()
+	os.Stdout = w
+
+	err := app.ExecuteArgs(append([]string{"render"}, args...))
+
+	w.Close()
+	os.Stdout = old
+
+	var buf bytes.Buffer
+	_, _ = buf.ReadFrom(r)
+	return buf.String(), err
+}
+
+func TestRenderHandler_File(t *testing.T) {
+	path := filepath.Join(t.TempDir(), "app.conf.tmpl")
+	err := os.WriteFile(path, []byte("name = ${name}\nreplicas = ${2 * 3}\n"), 0o644)
+	assert.Nil(t, err)
+
+	output, err := runRender(t, "--var", "name=api", path)
+	assert.Nil(t, err)
+	assert.Equal(t, output, "name = api\nreplicas = 6\n")
+}
+
+func TestRenderHandler_Lenient(t *testing.T) {
+	_, err := runRender(t, "-c", "hello ${who}")
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), `undefined variable "who"`)
+
+	output, err := runRender(t, "--lenient", "-c", `hello ${who ?? "world"}`)
+	assert.Nil(t, err)
+	assert.Equal(t, output, "hello world")
+}
+
+func TestRenderHandler_NoInput(t *testing.T) {
+	_, err := runRender(t)
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "no template provided")
+}
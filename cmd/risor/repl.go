@@ -5,6 +5,7 @@ import (
 	"fmt"
 	"os"
 	"path/filepath"
+	"strconv"
 	"strings"
 	"time"
 	"unicode"
@@ -25,9 +26,12 @@ type replApp struct {
 	historyPath string
 	showTiming  bool
 	multiLine   bool // true when input contains newlines
+	useColor    bool
+	printDepth  int // Nesting levels of results to print
+	printLength int // Items of each container in results to print
 }
 
-func runRepl(ctx context.Context, env map[string]any) error {
+func runRepl(ctx context.Context, env map[string]any, useColor bool) error {
 	// Load history
 	history, historyPath := loadHistory()
 
@@ -43,6 +47,9 @@ func runRepl(ctx context.Context, env map[string]any) error {
 		history:     history,
 		historyIdx:  -1,
 		historyPath: historyPath,
+		useColor:    useColor,
+		printDepth:  defaultPrintDepth,
+		printLength: defaultPrintLength,
 	}
 
 	app.runner = tui.NewInlineApp(tui.InlineAppConfig{
@@ -382,8 +389,9 @@ func (app *replApp) submit() []tui.Cmd {
 
 	// Try to evaluate - check if input is incomplete
 	start := time.Now()
-	result, err := app.vm.Eval(app.ctx, input)
+	result, err := app.vm.EvalObject(app.ctx, input)
 	elapsed := time.Since(start)
+	err = stdout.flushAfter(err)
 
 	// Check if the error indicates incomplete input
 	if err != nil && isIncompleteInput(err) {
@@ -416,7 +424,7 @@ func (app *replApp) submit() []tui.Cmd {
 	// Print result
 	if err != nil {
 		app.runner.Print(tui.Text("%s", err.Error()).Fg(tui.ColorRed).Wrap())
-	} else if result != nil {
+	} else if result != object.Nil {
 		app.printResult(result)
 	}
 
@@ -553,6 +561,10 @@ func (app *replApp) handleCommand(input string) []tui.Cmd {
 				tui.Text("  :timing         ").Style(accentStyle),
 				tui.Text("  Toggle execution timing").Style(mutedStyle),
 			),
+			tui.Group(
+				tui.Text("  :set <opt> <n>  ").Style(accentStyle),
+				tui.Text("  Set printdepth or printlength").Style(mutedStyle),
+			),
 			tui.Group(
 				tui.Text("  :clear, :cls    ").Style(accentStyle),
 				tui.Text("  Clear the screen").Style(mutedStyle),
@@ -598,6 +610,28 @@ func (app *replApp) handleCommand(input string) []tui.Cmd {
 			app.runner.Print(tui.Text("  Timing disabled").Style(mutedStyle))
 		}
 
+	case ":set":
+		if len(parts) != 3 {
+			app.runner.Print(tui.Text("  Usage: :set printdepth|printlength <n>").Style(mutedStyle))
+			app.runner.Print(tui.Text("  printdepth %d, printlength %d", app.printDepth, app.printLength).Style(mutedStyle))
+			return nil
+		}
+		n, err := strconv.Atoi(parts[2])
+		if err != nil || n < 0 {
+			app.runner.Print(tui.Text("  Expected a non-negative number: %s", parts[2]).Fg(tui.ColorRed))
+			return nil
+		}
+		switch setting := strings.ToLower(parts[1]); setting {
+		case "printdepth":
+			app.printDepth = n
+		case "printlength":
+			app.printLength = n
+		default:
+			app.runner.Print(tui.Text("  Unknown setting: %s", setting).Fg(tui.ColorRed))
+			return nil
+		}
+		app.runner.Print(tui.Text("  %s set to %d", strings.ToLower(parts[1]), n).Style(mutedStyle))
+
 	case ":exit", ":quit", ":q":
 		return []tui.Cmd{tui.Quit()}
 
@@ -608,181 +642,50 @@ func (app *replApp) handleCommand(input string) []tui.Cmd {
 	return nil
 }
 
-const maxResultLines = 50
-
-func (app *replApp) printResult(result any) {
-	// Color scheme
-	numberStyle := tui.NewStyle().WithFgRGB(tui.RGB{R: 255, G: 200, B: 100}) // yellow-gold
-	stringStyle := tui.NewStyle().WithFgRGB(tui.RGB{R: 150, G: 220, B: 150}) // soft green
-	boolStyle := tui.NewStyle().WithFgRGB(tui.RGB{R: 180, G: 140, B: 220})   // soft purple
-	typeStyle := tui.NewStyle().WithFgRGB(tui.RGB{R: 100, G: 100, B: 110})   // muted gray
-
-	switch v := result.(type) {
-	case error:
-		app.runner.Print(tui.Text("%s", v.Error()).Fg(tui.ColorRed))
-
-	case nil:
-		app.runner.Print(tui.Group(
-			tui.Text("null").Style(boolStyle),
-		))
-
-	case int64:
-		app.runner.Print(tui.Group(
-			tui.Text("%d", v).Style(numberStyle),
-			tui.Text(" int").Style(typeStyle),
-		))
-
-	case float64:
-		app.runner.Print(tui.Group(
-			tui.Text("%g", v).Style(numberStyle),
-			tui.Text(" float").Style(typeStyle),
-		))
-
-	case bool:
-		app.runner.Print(tui.Group(
-			tui.Text("%v", v).Style(boolStyle),
-			tui.Text(" bool").Style(typeStyle),
-		))
-
-	case string:
-		// Truncate long strings
-		display := v
-		truncated := false
-		if len(v) > 500 {
-			display = v[:500]
-			truncated = true
+// maxResultLines is the number of lines of a result that are printed before
+// the rest is elided.
+const maxResultLines = 100
+
+func (app *replApp) printResult(obj object.Object) {
+	lines := newPrettyPrinter(app.printDepth, app.printLength).Format(obj)
+	shown := lines[:min(len(lines), maxResultLines)]
+	for i, line := range shown {
+		views := make([]tui.View, 0, len(line)+1)
+		for _, span := range line {
+			views = append(views, tui.Text("%s", span.text).Style(app.prettyStyle(span.kind)))
 		}
-		if truncated {
-			app.runner.Print(tui.Group(
-				tui.Text("%q", display).Style(stringStyle).Wrap(),
-				tui.Text("… +%d chars", len(v)-500).Style(typeStyle),
-			))
-		} else {
-			app.runner.Print(tui.Group(
-				tui.Text("%q", display).Style(stringStyle).Wrap(),
-				tui.Text(" string").Style(typeStyle),
-			))
+		if i == len(lines)-1 {
+			views = append(views, tui.Text(" %s", prettyTypeName(obj)).Style(app.prettyStyle(prettyMuted)))
 		}
-
-	case byte:
-		app.runner.Print(tui.Group(
-			tui.Text("%d", v).Style(numberStyle),
-			tui.Text(" byte").Style(typeStyle),
-		))
-
-	case []byte:
-		app.runner.Print(tui.Group(
-			tui.Text("%q", string(v)).Style(stringStyle).Wrap(),
-			tui.Text(" bytes(%d)", len(v)).Style(typeStyle),
-		))
-
-	case []any:
-		app.printList(v, typeStyle)
-
-	case map[string]any:
-		app.printMap(v, typeStyle)
-
-	default:
-		// For other types, format as string
-		formatted := fmt.Sprintf("%v", v)
-		lines := strings.Split(formatted, "\n")
-		if len(lines) > maxResultLines {
-			truncated := strings.Join(lines[:maxResultLines], "\n")
-			app.runner.Print(tui.Text("%s", truncated).Wrap())
-			app.runner.Print(tui.Text("… +%d lines", len(lines)-maxResultLines).Style(typeStyle))
-		} else {
-			app.runner.Print(tui.Text("%s", formatted).Wrap())
-		}
-		app.runner.Print(tui.Text(" %T", v).Style(typeStyle))
-	}
-}
-
-func (app *replApp) printList(list []any, typeStyle tui.Style) {
-	if len(list) == 0 {
-		app.runner.Print(tui.Group(
-			tui.Text("[]"),
-			tui.Text(" list(0)").Style(typeStyle),
-		))
-		return
-	}
-
-	// Format as JSON-like output
-	formatted := formatValue(list)
-	lines := strings.Split(formatted, "\n")
-
-	if len(lines) > maxResultLines {
-		truncated := strings.Join(lines[:maxResultLines], "\n")
-		app.runner.Print(tui.Text("%s", truncated).Wrap())
-		app.runner.Print(tui.Text("… +%d lines", len(lines)-maxResultLines).Style(typeStyle))
-	} else {
-		app.runner.Print(tui.Text("%s", formatted).Wrap())
-	}
-	app.runner.Print(tui.Text(" list(%d)", len(list)).Style(typeStyle))
-}
-
-func (app *replApp) printMap(m map[string]any, typeStyle tui.Style) {
-	if len(m) == 0 {
-		app.runner.Print(tui.Group(
-			tui.Text("{}"),
-			tui.Text(" map(0)").Style(typeStyle),
-		))
-		return
+		app.runner.Print(tui.Group(views...))
 	}
-
-	// Format as JSON-like output
-	formatted := formatValue(m)
-	lines := strings.Split(formatted, "\n")
-
-	if len(lines) > maxResultLines {
-		truncated := strings.Join(lines[:maxResultLines], "\n")
-		app.runner.Print(tui.Text("%s", truncated).Wrap())
-		app.runner.Print(tui.Text("… +%d lines", len(lines)-maxResultLines).Style(typeStyle))
-	} else {
-		app.runner.Print(tui.Text("%s", formatted).Wrap())
-	}
-	app.runner.Print(tui.Text(" map(%d)", len(m)).Style(typeStyle))
-}
-
-func formatValue(v any) string {
-	// Use JSON marshaling for nice formatting
-	data, err := jsonMarshalIndent(v)
-	if err != nil {
-		return fmt.Sprintf("%v", v)
+	if hidden := len(lines) - len(shown); hidden > 0 {
+		app.runner.Print(tui.Text("… +%d lines", hidden).Style(app.prettyStyle(prettyMuted)))
 	}
-	return string(data)
 }
 
-func jsonMarshalIndent(v any) ([]byte, error) {
-	// Simple JSON formatting
-	switch val := v.(type) {
-	case []any:
-		if len(val) == 0 {
-			return []byte("[]"), nil
-		}
-		var parts []string
-		for _, item := range val {
-			itemBytes, _ := jsonMarshalIndent(item)
-			parts = append(parts, "  "+string(itemBytes))
-		}
-		return []byte("[\n" + strings.Join(parts, ",\n") + "\n]"), nil
-
-	case map[string]any:
-		if len(val) == 0 {
-			return []byte("{}"), nil
-		}
-		var parts []string
-		for k, item := range val {
-			itemBytes, _ := jsonMarshalIndent(item)
-			parts = append(parts, fmt.Sprintf("  %q: %s", k, string(itemBytes)))
-		}
-		return []byte("{\n" + strings.Join(parts, ",\n") + "\n}"), nil
-
-	case string:
-		return []byte(fmt.Sprintf("%q", val)), nil
-
-	default:
-		return []byte(fmt.Sprintf("%v", val)), nil
-	}
+// prettyStyle returns the style for a kind of pretty-printed span. Everything
+// is unstyled when color is disabled.
+func (app *replApp) prettyStyle(kind prettyKind) tui.Style {
+	style := tui.NewStyle()
+	if !app.useColor {
+		return style
+	}
+	switch kind {
+	case prettyNumber:
+		return style.WithFgRGB(tui.RGB{R: 255, G: 200, B: 100}) // yellow-gold
+	case prettyString:
+		return style.WithFgRGB(tui.RGB{R: 150, G: 220, B: 150}) // soft green
+	case prettyKey:
+		return style.WithFgRGB(tui.RGB{R: 130, G: 180, B: 230}) // soft blue
+	case prettyKeyword:
+		return style.WithFgRGB(tui.RGB{R: 180, G: 140, B: 220}) // soft purple
+	case prettyError:
+		return style.WithFgRGB(tui.RGB{R: 230, G: 90, B: 90}) // red
+	case prettyMuted:
+		return style.WithFgRGB(tui.RGB{R: 100, G: 100, B: 110}) // muted gray
+	}
+	return style
 }
 
 func (app *replApp) insertRune(r rune) {
@@ -6,15 +6,20 @@ import (
 	goerrors "errors"
 	"fmt"
 	"io"
+	"log/slog"
+	"maps"
 	"os"
 	"os/signal"
 	"runtime/pprof"
+	"slices"
 	"strings"
 	"syscall"
 	"time"
 
 	"github.com/deepnoodle-ai/risor/v2"
+	"github.com/deepnoodle-ai/risor/v2/pkg/builtins"
 	"github.com/deepnoodle-ai/risor/v2/pkg/errors"
+	modos "github.com/deepnoodle-ai/risor/v2/pkg/modules/os"
 	"github.com/deepnoodle-ai/risor/v2/pkg/object"
 	"github.com/deepnoodle-ai/wonton/cli"
 	"github.com/deepnoodle-ai/wonton/color"
@@ -29,7 +34,6 @@ func runHandler(ctx *cli.Context) error {
 		}
 		pprof.StartCPUProfile(f)
 		defer pprof.StopCPUProfile()
-		handleSigForProfiler()
 	}
 
 	// Get Risor options
@@ -44,7 +48,8 @@ func runHandler(ctx *cli.Context) error {
 		if err != nil {
 			return err
 		}
-		return runRepl(ctx.Context(), replEnv)
+		useColor := !ctx.Bool("no-color") && color.ShouldColorize(os.Stdout)
+		return runRepl(ctx.Context(), replEnv, useColor)
 	}
 
 	// Get the code to execute
@@ -52,6 +57,23 @@ func runHandler(ctx *cli.Context) error {
 	if err != nil {
 		return err
 	}
+	opts = append(opts, risor.WithEnv(map[string]any{
+		"os": modos.Module(modos.Policy{Args: osArgs(ctx.Arg(0), scriptArgs)}),
+	}))
+
+	// Reuse compiled bytecode for script files that haven't changed
+	if ctx.Arg(0) != "" && !ctx.Bool("no-cache") {
+		if dir, err := risor.DefaultCompileCacheDir(); err == nil {
+			opts = append(opts, risor.WithCompileCache(risor.NewCompileCache(dir)))
+		}
+	}
+
+	// Enable imports, using the search paths from the nearest risor.toml
+	importer, err := newImporter(ctx.Arg(0), opts)
+	if err != nil {
+		return err
+	}
+	opts = append(opts, risor.WithImporter(importer))
 
 	// Execute the code
 	start := time.Now()
@@ -59,8 +81,41 @@ func runHandler(ctx *cli.Context) error {
 		opts = append(opts, risor.WithFilename(file))
 	}
 
-	result, err := risor.Eval(ctx.Context(), code, opts...)
+	evalCtx, stop := interruptContext(ctx.Context())
+	defer stop()
+
+	// Enforce the --timeout and --max-memory limits
+	limits, err := getResourceLimits(ctx)
+	if err != nil {
+		return err
+	}
+
+	// Let finally blocks run if the script is interrupted. Finally blocks
+	// run for any cancellation, and could keep running past the limits, so
+	// scripts run with limits stop at once instead.
+	if limits.timeout == 0 && limits.maxMemory == 0 {
+		opts = append(opts, risor.WithFinallyOnCancel())
+	}
+	if limits.timeout > 0 {
+		opts = append(opts, risor.WithTimeout(limits.timeout))
+	}
+	if limits.maxMemory > 0 {
+		var stopMonitor func()
+		evalCtx, stopMonitor = memoryLimitContext(evalCtx, limits.maxMemory)
+		defer stopMonitor()
+	}
+
+	result, err := risor.Eval(evalCtx, code, opts...)
+	err = stdout.flushAfter(err)
 	if err != nil {
+		switch {
+		case goerrors.Is(err, context.DeadlineExceeded) && limits.timeout > 0:
+			return fmt.Errorf("script timed out after %v", limits.timeout)
+		case goerrors.Is(context.Cause(evalCtx), errMemoryLimitExceeded):
+			return fmt.Errorf("%w (--max-memory %s)", errMemoryLimitExceeded, ctx.String("max-memory"))
+		case goerrors.Is(err, context.Canceled) && ctx.Context().Err() == nil:
+			return goerrors.New("interrupted")
+		}
 		return formatRisorError(ctx, err)
 	}
 	dt := time.Since(start)
@@ -105,13 +160,18 @@ func getRisorOptions(ctx *cli.Context, injectStdin bool) ([]risor.Option, error)
 	if !ctx.Bool("no-default-globals") {
 		opts = append(opts, risor.WithEnv(risor.Builtins()))
 	}
-	// Provide print in CLI mode (not available in library mode by design)
-	opts = append(opts, risor.WithEnv(map[string]any{
-		"print": newPrintBuiltin(),
-	}))
+	// Provide print and the other output builtins in CLI mode (not available in
+	// library mode by design)
+	opts = append(opts, risor.WithEnv(outputBuiltins()))
+	opts = append(opts, risor.WithLogHandler(newStderrLogHandler()))
 	// Auto-inject stdin as a variable when data is piped and stdin isn't
 	// being used to read code (via --stdin flag).
-	if injectStdin && !ctx.Bool("stdin") && cli.IsPiped() {
+	input := ctx.String("input")
+	piped := injectStdin && !ctx.Bool("stdin") && cli.IsPiped()
+	if input != "" && !piped {
+		return nil, fmt.Errorf("--input %s: no data piped to stdin", input)
+	}
+	if piped {
 		data, err := io.ReadAll(os.Stdin)
 		if err != nil {
 			return nil, fmt.Errorf("reading stdin: %w", err)
@@ -121,6 +181,16 @@ func getRisorOptions(ctx *cli.Context, injectStdin bool) ([]risor.Option, error)
 				"stdin": string(data),
 			}))
 		}
+		// With --input, the piped data is also parsed into the data variable
+		if input != "" {
+			value, err := parseInput(input, data)
+			if err != nil {
+				return nil, err
+			}
+			opts = append(opts, risor.WithEnv(map[string]any{
+				"data": value,
+			}))
+		}
 	}
 	// --var and --var-json flags come last so they can override auto-detected stdin
 	if vars, err := parseVarFlags(ctx.Strings("var")); err != nil {
@@ -136,6 +206,21 @@ func getRisorOptions(ctx *cli.Context, injectStdin bool) ([]risor.Option, error)
 	return opts, nil
 }
 
+// parseInput parses data piped to stdin in the format named by the --input
+// flag.
+func parseInput(format string, data []byte) (any, error) {
+	switch format {
+	case "json":
+		var value any
+		if err := json.Unmarshal(data, &value); err != nil {
+			return nil, fmt.Errorf("--input json: invalid JSON on stdin: %w", err)
+		}
+		return value, nil
+	default:
+		return nil, fmt.Errorf("unknown input format: %s", format)
+	}
+}
+
 // parseJSONVarFlag parses a --var-json flag value as a JSON object.
 func parseJSONVarFlag(value string) (map[string]any, error) {
 	if value == "" {
@@ -202,7 +287,7 @@ func getReplEnv(ctx *cli.Context) (map[string]any, error) {
 			env[k] = v
 		}
 	}
-	mergeInto(map[string]any{"print": newPrintBuiltin()})
+	mergeInto(outputBuiltins())
 	if vars, err := parseVarFlags(ctx.Strings("var")); err != nil {
 		return nil, err
 	} else if len(vars) > 0 {
@@ -276,14 +361,35 @@ func getRisorCode(ctx *cli.Context) (string, error) {
 	return ctx.String("code"), nil
 }
 
-func handleSigForProfiler() {
-	c := make(chan os.Signal, 1)
-	signal.Notify(c, syscall.SIGINT, syscall.SIGTERM)
+// interruptContext returns a context that is cancelled when the process
+// receives SIGINT or SIGTERM, so that the script's finally blocks run before
+// it stops. A second signal exits immediately, in case cleanup hangs. Call
+// the returned function to stop listening for signals.
+func interruptContext(parent context.Context) (context.Context, func()) {
+	ctx, cancel := context.WithCancel(parent)
+	signals := make(chan os.Signal, 2)
+	done := make(chan struct{})
+	signal.Notify(signals, os.Interrupt, syscall.SIGTERM)
 	go func() {
-		<-c
-		pprof.StopCPUProfile()
-		os.Exit(1)
+		select {
+		case <-signals:
+		case <-done:
+			return
+		}
+		fmt.Fprintln(os.Stderr, "interrupted, cleaning up (interrupt again to exit now)")
+		cancel()
+		select {
+		case <-signals:
+			pprof.StopCPUProfile()
+			exit(130)
+		case <-done:
+		}
 	}()
+	return ctx, func() {
+		signal.Stop(signals)
+		close(done)
+		cancel()
+	}
 }
 
 // formatRisorError formats a Risor error with colors and professional styling.
@@ -308,13 +414,31 @@ func formatRisorError(ctx *cli.Context, err error) error {
 	return err
 }
 
-func newPrintBuiltin() *object.Builtin {
-	return object.NewBuiltin("print", func(ctx context.Context, args ...object.Object) (object.Object, error) {
-		values := make([]any, len(args))
-		for i, arg := range args {
-			values[i] = object.PrintableValue(arg)
+// newStderrLogHandler returns a handler that writes records from the log
+// module to stderr in slog's text format.
+func newStderrLogHandler() object.LogHandler {
+	logger := slog.New(slog.NewTextHandler(os.Stderr, nil))
+	return func(ctx context.Context, r object.LogRecord) error {
+		attrs := make([]any, 0, len(r.Fields)*2)
+		for _, k := range slices.Sorted(maps.Keys(r.Fields)) {
+			attrs = append(attrs, k, r.Fields[k])
+		}
+		logger.Log(ctx, r.Level, r.Message, attrs...)
+		return nil
+	}
+}
+
+// newHelpBuiltin returns a help builtin that prints the documentation that
+// the standard help builtin returns, so that help(len) reads well in the REPL.
+func newHelpBuiltin() *object.Builtin {
+	return object.NewBuiltin("help", func(ctx context.Context, args ...object.Object) (object.Object, error) {
+		text, err := builtins.Help(ctx, args...)
+		if err != nil {
+			return nil, err
+		}
+		if err := stdout.println(text.(*object.String).Value()); err != nil {
+			return nil, err
 		}
-		fmt.Println(values...)
 		return object.Nil, nil
 	})
 }
@@ -0,0 +1,73 @@
+package main
+
+import (
+	"os"
+	"strings"
+)
+
+// scriptArgs holds the command line arguments that follow the script path.
+// They are passed to the script as os.args instead of being parsed as flags.
+var scriptArgs []string
+
+// valueFlags holds the flags of the root command that take a value, such as
+// "--code" and "-c", so that the value isn't mistaken for the script path.
+var valueFlags = map[string]bool{}
+
+// valueFlag returns the flag made by newFlag, such as cli.String, and adds it
+// to valueFlags. The root command declares its flags that take a value with
+// it.
+func valueFlag[F any](newFlag func(name, short string) F, name, short string) F {
+	valueFlags["--"+name] = true
+	if short != "" {
+		valueFlags["-"+short] = true
+	}
+	return newFlag(name, short)
+}
+
+// splitScriptArgs splits command line arguments into those for the CLI and
+// those for the script. The first positional argument that names a file is
+// the script path, and everything after it belongs to the script. This is
+// what a shebang line such as "#!/usr/bin/env -S risor --no-cache" produces:
+//
+//	risor --no-cache ./deploy.risor --dry-run prod
+//
+// With -c or --stdin, all positional arguments belong to the script, and a
+// "--" separator ends the CLI's flags. Arguments for subcommands such as
+// "risor eval" are returned unchanged.
+func splitScriptArgs(args []string) (cliArgs, rest []string) {
+	inlineCode := false
+	for i := 0; i < len(args); i++ {
+		arg := args[i]
+		if arg == "--" && inlineCode {
+			return args[:i], args[i+1:]
+		}
+		if strings.HasPrefix(arg, "-") && arg != "-" {
+			name, _, hasValue := strings.Cut(arg, "=")
+			if name == "-c" || name == "--code" || name == "--stdin" {
+				inlineCode = true
+			}
+			if valueFlags[name] && !hasValue {
+				i++
+			}
+			continue
+		}
+		if inlineCode {
+			return args[:i], args[i:]
+		}
+		if info, err := os.Stat(arg); err == nil && info.Mode().IsRegular() {
+			return args[:i+1], args[i+1:]
+		}
+		return args, nil
+	}
+	return args, nil
+}
+
+// osArgs returns the arguments scripts see as os.args. Like os.Args in Go,
+// they are the script path followed by the script's arguments, with "risor"
+// in place of the path for code given with -c or --stdin.
+func osArgs(script string, args []string) []string {
+	if script == "" {
+		script = "risor"
+	}
+	return append([]string{script}, args...)
+}
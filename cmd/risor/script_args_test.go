@@ -0,0 +1,91 @@
+package main
+
+import (
+	"bytes"
+	"encoding/json"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/deepnoodle-ai/wonton/assert"
+	"github.com/deepnoodle-ai/wonton/cli"
+	"github.com/deepnoodle-ai/wonton/color"
+)
+
+func TestSplitScriptArgs(t *testing.T) {
+	script := filepath.Join(t.TempDir(), "deploy.risor")
+	assert.Nil(t, os.WriteFile(script, []byte("1"), 0o644))
+
+	tests := []struct {
+		args    []string
+		cliArgs []string
+		rest    []string
+	}{
+		{[]string{script}, []string{script}, []string{}},
+		{[]string{script, "-v", "--dry-run", "prod"}, []string{script}, []string{"-v", "--dry-run", "prod"}},
+		{[]string{"--no-cache", script, "--no-cache"}, []string{"--no-cache", script}, []string{"--no-cache"}},
+		{[]string{"--var", "x=1", "-o", "json", script, "a"}, []string{"--var", "x=1", "-o", "json", script}, []string{"a"}},
+		{[]string{"--timeout=5s", script, "a"}, []string{"--timeout=5s", script}, []string{"a"}},
+		{[]string{"-c", "os.args", "a", "b"}, []string{"-c", "os.args"}, []string{"a", "b"}},
+		{[]string{"-c", "os.args", "--", "-v"}, []string{"-c", "os.args"}, []string{"-v"}},
+		{[]string{"eval", "1 + 1"}, []string{"eval", "1 + 1"}, nil},
+		{[]string{"fmt", script, "-w"}, []string{"fmt", script, "-w"}, nil},
+		{[]string{"--no-color"}, []string{"--no-color"}, nil},
+		{[]string{}, []string{}, nil},
+	}
+	for _, tt := range tests {
+		cliArgs, rest := splitScriptArgs(tt.args)
+		assert.Equal(t, cliArgs, tt.cliArgs)
+		assert.Equal(t, len(rest), len(tt.rest))
+		for i := range rest {
+			assert.Equal(t, rest[i], tt.rest[i])
+		}
+	}
+}
+
+func TestRunHandler_ShebangScriptArgs(t *testing.T) {
+	oldEnabled := color.Enabled
+	color.Enabled = false
+	defer func() { color.Enabled = oldEnabled }()
+
+	script := filepath.Join(t.TempDir(), "args.risor")
+	source := "#!/usr/bin/env -S risor --no-cache\nos.args\n"
+	assert.Nil(t, os.WriteFile(script, []byte(source), 0o755))
+
+	app := cli.New("risor").SetColorEnabled(false)
+	app.GlobalFlags(
+		cli.String("code", "c"),
+		cli.Bool("stdin", ""),
+		cli.Strings("var", ""),
+	)
+	app.Main().
+		Args("file?").
+		Flags(
+			cli.String("output", "o").Enum("json", "text"),
+			cli.Bool("no-repl", ""),
+			cli.Bool("no-cache", ""),
+		).
+		Run(runHandler)
+
+	// As invoked by the kernel for "./args.risor --dry-run -n 3 prod"
+	var cliArgs []string
+	cliArgs, scriptArgs = splitScriptArgs([]string{"--no-cache", script, "--dry-run", "-n", "3", "prod"})
+	defer func() { scriptArgs = nil }()
+
+	old := os.Stdout
+	r, w, _ := os.Pipe()
+	os.Stdout = w
+
+	err := app.ExecuteArgs(cliArgs)
+
+	w.Close()
+	os.Stdout = old
+
+	assert.Nil(t, err)
+
+	var buf bytes.Buffer
+	_, _ = buf.ReadFrom(r)
+	var args []string
+	assert.Nil(t, json.Unmarshal(buf.Bytes(), &args))
+	assert.Equal(t, args, []string{script, "--dry-run", "-n", "3", "prod"})
+}
@@ -38,7 +38,7 @@ func testHandler(ctx *cli.Context) error {
 
 	// Exit with non-zero status on failure
 	if !summary.Success() {
-		os.Exit(1)
+		exit(1)
 	}
 
 	return nil
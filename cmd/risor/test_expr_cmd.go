@@ -0,0 +1,54 @@
+package main
+
+import (
+	"github.com/deepnoodle-ai/risor/v2"
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+	"github.com/deepnoodle-ai/wonton/cli"
+)
+
+// Exit statuses of the test-expr command when the expression isn't truthy.
+// As with test(1), errors use a status distinct from a false result so
+// scripts can tell them apart.
+const (
+	exitFalse = 1
+	exitError = 2
+)
+
+// testExprHandler evaluates an expression and exits 0 if its result is
+// truthy, 1 if it is falsy, and 2 if it fails, for use in shell conditions:
+//
+//	if risor test-expr 'len(stdin) > 0' < file; then ...
+func testExprHandler(ctx *cli.Context) error {
+	ok, err := evalCondition(ctx)
+	if err != nil {
+		if !ctx.Bool("quiet") {
+			printError(err.Error())
+		}
+		exit(exitError)
+	}
+	if !ok {
+		exit(exitFalse)
+	}
+	return nil
+}
+
+// evalCondition evaluates the expression given to test-expr and reports
+// whether its result is truthy.
+func evalCondition(ctx *cli.Context) (bool, error) {
+	expr, err := getEvalExpr(ctx)
+	if err != nil {
+		return false, err
+	}
+	opts, err := getRisorOptions(ctx, true)
+	if err != nil {
+		return false, err
+	}
+	opts = append(opts, risor.WithRawResult())
+	result, err := risor.Eval(ctx.Context(), expr, opts...)
+	err = stdout.flushAfter(err)
+	if err != nil {
+		return false, err
+	}
+	obj, ok := result.(object.Object)
+	return ok && obj.IsTruthy(), nil
+}
@@ -0,0 +1,69 @@
+package main
+
+import (
+	"testing"
+
+	"github.com/deepnoodle-ai/wonton/assert"
+	"github.com/deepnoodle-ai/wonton/cli"
+)
+
+func runCondition(t *testing.T, args ...string) (bool, error) {
+	t.Helper()
+	var ok bool
+	var condErr error
+	app := cli.New("risor").SetColorEnabled(false)
+	app.GlobalFlags(
+		cli.Strings("var", ""),
+		cli.Bool("no-default-globals", ""),
+	)
+	app.Command("test-expr").
+		Args("expr?").
+		Flags(
+			cli.String("code", "c"),
+			cli.Bool("stdin", ""),
+			cli.Bool("quiet", "q"),
+		).
+		Run(func(ctx *cli.Context) error {
+			ok, condErr = evalCondition(ctx)
+			return nil
+		})
+	err := app.ExecuteArgs(append([]string{"test-expr"}, args...))
+	assert.Nil(t, err)
+	return ok, condErr
+}
+
+func TestEvalCondition(t *testing.T) {
+	tests := []struct {
+		args     []string
+		expected bool
+	}{
+		{[]string{"true"}, true},
+		{[]string{"false"}, false},
+		{[]string{"nil"}, false},
+		{[]string{"1 + 1 == 2"}, true},
+		{[]string{"0"}, false},
+		{[]string{`""`}, false},
+		{[]string{"[]"}, false},
+		{[]string{"[0]"}, true},
+		{[]string{"-c", "len([1, 2, 3]) > 2"}, true},
+		{[]string{"--var", "env=prod", `env == "prod"`}, true},
+		{[]string{"--var", "env=dev", `env == "prod"`}, false},
+	}
+	for _, tt := range tests {
+		ok, err := runCondition(t, tt.args...)
+		assert.Nil(t, err)
+		assert.Equal(t, ok, tt.expected)
+	}
+}
+
+func TestEvalConditionErrors(t *testing.T) {
+	_, err := runCondition(t, "undefined_name")
+	assert.NotNil(t, err)
+
+	_, err = runCondition(t, "1 +")
+	assert.NotNil(t, err)
+
+	_, err = runCondition(t)
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "no expression provided")
+}
@@ -61,6 +61,7 @@ func (v *replVM) vmOpts() []vm.Option {
 	if v.nextIP > 0 {
 		opts = append(opts, vm.WithInstructionOffset(v.nextIP))
 	}
+	opts = append(opts, vm.WithLogHandler(newStderrLogHandler()))
 	return opts
 }
 
@@ -0,0 +1,221 @@
+package risor
+
+import (
+	"context"
+	"fmt"
+	"path"
+	"slices"
+	"strings"
+	"sync"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+	"github.com/deepnoodle-ai/risor/v2/pkg/vm"
+)
+
+// SourceExt is the file extension of Risor source files.
+const SourceExt = ".risor"
+
+// ModuleLoader supplies the source code of modules to an Importer. Hosts
+// implement it to serve modules from a database, embedded assets, or a remote
+// service instead of the filesystem.
+type ModuleLoader interface {
+	// Resolve returns the canonical name of the module an import path refers
+	// to. The from argument is the name of the importing module, or the
+	// filename of the importing script, if known. Imports that resolve to the
+	// same name share one module.
+	Resolve(importPath, from string) (string, error)
+
+	// Load returns the source code of a module, given its canonical name.
+	Load(ctx context.Context, name string) (string, error)
+}
+
+// Importer is a vm.Importer that compiles and runs modules whose source code
+// is supplied by a ModuleLoader.
+//
+// A module is compiled and its top level is run once, the first time it is
+// imported successfully. Later imports of the same module, from any script
+// that uses this importer, share the same module. The module contains the
+// names the source declares with export; everything else is private to it.
+// Exported functions keep reading and writing the module's own globals when
+// called from the importing script.
+// Modules may import other modules, but import cycles are reported as errors.
+//
+// An Importer is safe for concurrent use. Scripts running at the same time
+// may call the same exported functions; each read or write of a module global
+// is atomic, but a function that updates a global in several steps, such as
+// a counter, may interleave with another call.
+type Importer struct {
+	loader ModuleLoader
+	opts   []Option
+
+	mu      sync.Mutex
+	modules map[string]*moduleEntry
+}
+
+// moduleEntry is a module that has been loaded, or is being loaded, by an
+// Importer. The done channel is closed once loading finishes.
+type moduleEntry struct {
+	done   chan struct{}
+	module *object.Module
+	err    error
+
+	// waitingOn is the module whose load, started by another caller, this
+	// module's load is waiting for. Guarded by the Importer's mutex.
+	waitingOn string
+}
+
+// importChainKey is the context key for the modules currently being
+// imported, which is used to detect import cycles.
+type importChainKey struct{}
+
+// NewImporter returns an Importer that loads module source code with the
+// given loader. The options are used to compile and run each module; in
+// particular, modules see the environment given by WithEnv.
+func NewImporter(loader ModuleLoader, opts ...Option) *Importer {
+	return &Importer{
+		loader:  loader,
+		opts:    slices.Clone(opts),
+		modules: map[string]*moduleEntry{},
+	}
+}
+
+// NewFileImporter returns an Importer that loads Risor source files, using a
+// FileLoader with the given search paths. Import paths that are not found in
+// the search paths fall back to the standard library modules, so a project
+// can provide its own version of a standard module.
+func NewFileImporter(searchPaths []string, opts ...Option) *Importer {
+	return NewImporter(NewMultiLoader(NewFileLoader(searchPaths), StdlibLoader()), opts...)
+}
+
+// Loader returns the loader that supplies module source code.
+func (imp *Importer) Loader() ModuleLoader {
+	return imp.loader
+}
+
+// Import returns the module for an import path, loading it on first use.
+func (imp *Importer) Import(ctx context.Context, importPath, from string) (*object.Module, error) {
+	name, err := imp.loader.Resolve(importPath, from)
+	if err != nil {
+		return nil, err
+	}
+	// Check for a cycle before waiting on an in-progress load, since the
+	// load in progress may be the one waiting on this import.
+	chain, _ := ctx.Value(importChainKey{}).([]string)
+	if slices.Contains(chain, name) {
+		return nil, fmt.Errorf("import cycle: %s", strings.Join(append(chain, name), " -> "))
+	}
+
+	imp.mu.Lock()
+	entry, found := imp.modules[name]
+	if !found {
+		entry = &moduleEntry{done: make(chan struct{})}
+		imp.modules[name] = entry
+	}
+	var waiter *moduleEntry
+	if found && len(chain) > 0 {
+		// Loads running concurrently can also wait on each other, as when
+		// one caller imports a, which imports b, while another imports b,
+		// which imports a.
+		if cycle := imp.waitCycle(name, chain); cycle != nil {
+			imp.mu.Unlock()
+			return nil, fmt.Errorf("import cycle: %s", strings.Join(cycle, " -> "))
+		}
+		if waiter = imp.modules[chain[len(chain)-1]]; waiter != nil {
+			waiter.waitingOn = name
+		}
+	}
+	imp.mu.Unlock()
+
+	if found {
+		defer func() {
+			if waiter != nil {
+				imp.mu.Lock()
+				waiter.waitingOn = ""
+				imp.mu.Unlock()
+			}
+		}()
+		select {
+		case <-entry.done:
+			return entry.module, entry.err
+		case <-ctx.Done():
+			return nil, ctx.Err()
+		}
+	}
+	ctx = context.WithValue(ctx, importChainKey{}, append(slices.Clip(chain), name))
+	entry.module, entry.err = imp.load(ctx, name)
+	if entry.err != nil {
+		// Forget failed loads so that a later import tries again
+		imp.mu.Lock()
+		delete(imp.modules, name)
+		imp.mu.Unlock()
+	}
+	close(entry.done)
+	return entry.module, entry.err
+}
+
+// waitCycle returns the modules that would wait on each other forever if the
+// load of the last module in chain waited for the named module, or nil if
+// there is no such cycle. It must be called with imp.mu held.
+func (imp *Importer) waitCycle(name string, chain []string) []string {
+	path := slices.Clone(chain)
+	for n := name; n != "" && len(path) <= len(chain)+len(imp.modules); {
+		path = append(path, n)
+		if slices.Contains(chain, n) {
+			return path
+		}
+		entry, ok := imp.modules[n]
+		if !ok {
+			return nil
+		}
+		n = entry.waitingOn
+	}
+	return nil
+}
+
+// load compiles and runs a module and collects its exports.
+func (imp *Importer) load(ctx context.Context, name string) (*object.Module, error) {
+	source, err := imp.loader.Load(ctx, name)
+	if err != nil {
+		return nil, err
+	}
+	opts := append(slices.Clone(imp.opts), WithFilename(name))
+	code, err := Compile(ctx, source, opts...)
+	if err != nil {
+		return nil, err
+	}
+	vmOpts := append(collectOptions(opts...).vmOpts(), vm.WithImporter(imp))
+	machine, err := vm.New(code, vmOpts...)
+	if err != nil {
+		return nil, err
+	}
+	if err := machine.Run(ctx); err != nil {
+		return nil, err
+	}
+	exports, err := machine.Exports()
+	if err != nil {
+		return nil, err
+	}
+	return object.NewBuiltinsModule(moduleName(name), exports), nil
+}
+
+// moduleName returns the name of a module object: the last element of its
+// canonical name, without the source file extension.
+func moduleName(name string) string {
+	name = strings.ReplaceAll(name, "\\", "/")
+	return strings.TrimSuffix(path.Base(name), SourceExt)
+}
+
+// isRelativeImport reports whether an import path is relative to the
+// importing module.
+func isRelativeImport(importPath string) bool {
+	return strings.HasPrefix(importPath, "./") || strings.HasPrefix(importPath, "../")
+}
+
+// withSourceExt appends the source file extension to a path that doesn't
+// already have it.
+func withSourceExt(p string) string {
+	if path.Ext(p) != SourceExt {
+		return p + SourceExt
+	}
+	return p
+}
@@ -0,0 +1,264 @@
+package risor
+
+import (
+	"context"
+	"os"
+	"path/filepath"
+	"sync"
+	"testing"
+	"time"
+
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+// writeFiles creates the given files, relative to dir.
+func writeFiles(t *testing.T, dir string, files map[string]string) {
+	t.Helper()
+	for name, content := range files {
+		path := filepath.Join(dir, filepath.FromSlash(name))
+		assert.Nil(t, os.MkdirAll(filepath.Dir(path), 0o755))
+		assert.Nil(t, os.WriteFile(path, []byte(content), 0o644))
+	}
+}
+
+// runFile evaluates a script file with the given importer.
+func runFile(t *testing.T, file string, imp *Importer) (any, error) {
+	t.Helper()
+	source, err := os.ReadFile(file)
+	assert.Nil(t, err)
+	return Eval(context.Background(), string(source),
+		WithEnv(Builtins()), WithFilename(file), WithImporter(imp))
+}
+
+func TestImportRelative(t *testing.T) {
+	dir := t.TempDir()
+	writeFiles(t, dir, map[string]string{
+		"main.risor": `
+		import "./lib/helpers"
+		helpers.shout("hi")
+		`,
+		"lib/helpers.risor": `
+		import "./strings.risor" as s
+		export function shout(x) { s.upper(x) + "!" }
+		`,
+		"lib/strings.risor": `
+		export function upper(x) { x.to_upper() }
+		`,
+	})
+	result, err := runFile(t, filepath.Join(dir, "main.risor"), NewFileImporter(nil, WithEnv(Builtins())))
+	assert.Nil(t, err)
+	assert.Equal(t, result, "HI!")
+}
+
+func TestImportSearchPathOrder(t *testing.T) {
+	dir := t.TempDir()
+	writeFiles(t, dir, map[string]string{
+		"main.risor":         `import "greet"; greet.name`,
+		"lib/greet.risor":    `export let name = "lib"`,
+		"vendor/greet.risor": `export let name = "vendor"`,
+		"vendor/only.risor":  `export let name = "only"`,
+	})
+	lib := filepath.Join(dir, "lib")
+	vendor := filepath.Join(dir, "vendor")
+	main := filepath.Join(dir, "main.risor")
+
+	result, err := runFile(t, main, NewFileImporter([]string{lib, vendor}))
+	assert.Nil(t, err)
+	assert.Equal(t, result, "lib")
+
+	result, err = runFile(t, main, NewFileImporter([]string{vendor, lib}))
+	assert.Nil(t, err)
+	assert.Equal(t, result, "vendor")
+
+	// Relative paths never consult the search paths
+	loader := NewFileLoader([]string{vendor})
+	_, err = loader.Resolve("./only", main)
+	assert.NotNil(t, err)
+	file, err := loader.Resolve("only", main)
+	assert.Nil(t, err)
+	assert.Equal(t, file, filepath.Join(vendor, "only.risor"))
+}
+
+func TestImportRunsOnce(t *testing.T) {
+	dir := t.TempDir()
+	writeFiles(t, dir, map[string]string{
+		"main.risor": `
+		import "./a"
+		import "./counter"
+		a.bump()
+		counter.value()
+		`,
+		"a.risor": `
+		import "./counter"
+		export function bump() { counter.inc() }
+		`,
+		"counter.risor": `
+		let n = 0
+		export function inc() { n++ }
+		export function value() { n }
+		`,
+	})
+	imp := NewFileImporter(nil)
+	result, err := runFile(t, filepath.Join(dir, "main.risor"), imp)
+	assert.Nil(t, err)
+	assert.Equal(t, result, int64(1))
+
+	// Later scripts using the same importer share the module
+	result, err = runFile(t, filepath.Join(dir, "main.risor"), imp)
+	assert.Nil(t, err)
+	assert.Equal(t, result, int64(2))
+}
+
+func TestImportModuleGlobals(t *testing.T) {
+	dir := t.TempDir()
+	writeFiles(t, dir, map[string]string{
+		"main.risor": `
+		import "./lib"
+		let n = 1
+		lib.set(5)
+		let add = lib.adder()
+		[n, lib.get(), add(1), lib.apply(x => x + n)]
+		`,
+		"lib.risor": `
+		let n = 10
+		export function set(v) { n = v }
+		export function get() { n }
+		export function adder() { return x => x + n }
+		export function apply(fn) { fn(n) }
+		`,
+	})
+	// The module and the script each see their own n
+	result, err := runFile(t, filepath.Join(dir, "main.risor"), NewFileImporter(nil))
+	assert.Nil(t, err)
+	assert.Equal(t, result, []any{int64(1), int64(5), int64(6), int64(6)})
+}
+
+func TestImportModuleGlobalsConcurrent(t *testing.T) {
+	imp := NewImporter(MapLoader{
+		"counter.risor": `
+		let count = 0
+		export function bump() { count = count + 1 }
+		export function get() { count }
+		`,
+	})
+
+	// Scripts on separate VMs call the same module function, which reads and
+	// writes the module's globals. Run with -race to check the access.
+	const scripts, calls = 2, 100
+	errs := make(chan error, scripts)
+	for range scripts {
+		go func() {
+			_, err := Eval(context.Background(), `
+			import "counter"
+			list(range(100)).each(i => counter.bump())
+			counter.get()
+			`, WithImporter(imp))
+			errs <- err
+		}()
+	}
+	for range scripts {
+		assert.Nil(t, <-errs)
+	}
+	result, err := Eval(context.Background(), `import "counter"; counter.get()`,
+		WithImporter(imp))
+	assert.Nil(t, err)
+	count, ok := result.(int64)
+	assert.True(t, ok)
+	assert.True(t, count > 0 && count <= scripts*calls)
+}
+
+func TestImportExportsOnly(t *testing.T) {
+	dir := t.TempDir()
+	writeFiles(t, dir, map[string]string{
+		"main.risor": `import "./lib"; lib.hidden`,
+		"lib.risor": `
+		let hidden = 1
+		export let shown = 2
+		`,
+	})
+	_, err := runFile(t, filepath.Join(dir, "main.risor"), NewFileImporter(nil))
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "hidden")
+}
+
+func TestImportCycle(t *testing.T) {
+	dir := t.TempDir()
+	writeFiles(t, dir, map[string]string{
+		"main.risor": `import "./a"`,
+		"a.risor":    `import "./b"`,
+		"b.risor":    `import "./a"`,
+	})
+	_, err := runFile(t, filepath.Join(dir, "main.risor"), NewFileImporter(nil))
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "import cycle")
+}
+
+// barrierLoader serves modules from memory, holding back each load until
+// every module in wait has started loading.
+type barrierLoader struct {
+	MapLoader
+	wait    sync.WaitGroup
+	started sync.Map
+}
+
+func (l *barrierLoader) Load(ctx context.Context, name string) (string, error) {
+	if _, loaded := l.started.LoadOrStore(name, true); !loaded {
+		l.wait.Done()
+	}
+	l.wait.Wait()
+	return l.MapLoader.Load(ctx, name)
+}
+
+func TestImportCycleConcurrent(t *testing.T) {
+	loader := &barrierLoader{MapLoader: MapLoader{
+		"a.risor": `import "b"; export let name = "a"`,
+		"b.risor": `import "a"; export let name = "b"`,
+	}}
+	loader.wait.Add(2)
+	imp := NewImporter(loader)
+
+	// One script imports a while another imports b, so each load waits for
+	// the other unless the cycle is detected.
+	errs := make(chan error, 2)
+	for _, name := range []string{"a", "b"} {
+		go func() {
+			_, err := Eval(context.Background(), `import "`+name+`"`, WithImporter(imp))
+			errs <- err
+		}()
+	}
+	for range 2 {
+		select {
+		case err := <-errs:
+			assert.NotNil(t, err)
+			assert.Contains(t, err.Error(), "import cycle")
+		case <-time.After(5 * time.Second):
+			t.Fatal("concurrent imports of a cycle deadlocked")
+		}
+	}
+}
+
+func TestImportNotFound(t *testing.T) {
+	dir := t.TempDir()
+	writeFiles(t, dir, map[string]string{
+		"main.risor": `import "missing"`,
+	})
+	main := filepath.Join(dir, "main.risor")
+
+	_, err := runFile(t, main, NewFileImporter(nil))
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), `cannot import "missing": module not found (no search paths configured)`)
+
+	_, err = runFile(t, main, NewFileImporter([]string{filepath.Join(dir, "lib")}))
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), filepath.Join(dir, "lib", "missing.risor"))
+
+	_, err = NewFileLoader(nil).Resolve("/etc/passwd", main)
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "absolute import paths are not allowed")
+}
+
+func TestImportDisabled(t *testing.T) {
+	_, err := Eval(context.Background(), `import "./lib"`)
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "imports are not enabled")
+}
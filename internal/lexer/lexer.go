@@ -22,6 +22,19 @@ const (
 	NumberTypeBinary  NumberType = "binary"
 )
 
+// attributeKeywords are words that are lexed as identifiers when they follow a
+// period or come before a colon, so they remain usable as attribute and
+// method names and as map keys, as in m.assert and {assert: true}.
+var attributeKeywords = map[string]bool{
+	"as":     true,
+	"assert": true,
+	"del":    true,
+	"export": true,
+	"import": true,
+	"quote":  true,
+	"with":   true,
+}
+
 // Lexer holds our object-state.
 type Lexer struct {
 	// The index of the current character
@@ -33,9 +46,19 @@ type Lexer struct {
 	// The current character
 	ch rune
 
+	// The input string
+	input string
+
 	// A rune slice of our input string
 	characters []rune
 
+	// Whether the input is entirely ASCII, in which case character indices
+	// are also byte offsets into the input
+	ascii bool
+
+	// The byte offset of each character, if the input isn't ASCII
+	offsets []int
+
 	// Previous token
 	prevToken token.Token
 
@@ -53,6 +76,9 @@ type Lexer struct {
 
 	// Name of the file be read
 	file string
+
+	// Keywords to read as identifiers
+	identifiers map[string]bool
 }
 
 // Option is a configuration function for a Lexer.
@@ -65,14 +91,34 @@ func WithFile(file string) Option {
 	}
 }
 
+// WithIdentifiers makes the Lexer read the given keywords as identifiers, for
+// code written before the language reserved them.
+func WithIdentifiers(names ...string) Option {
+	return func(l *Lexer) {
+		l.identifiers = map[string]bool{}
+		for _, name := range names {
+			l.identifiers[name] = true
+		}
+	}
+}
+
 // New returns a Lexer instance for the given string input.
 func New(input string, options ...Option) *Lexer {
+	characters := []rune(input)
 	l := &Lexer{
-		characters:   []rune(input),
+		input:        input,
+		characters:   characters,
+		ascii:        len(characters) == len(input),
 		column:       -1, // -1 = before the first column
 		position:     -1, // -1 = before the first character
 		nextPosition: 0,  //  0 = read the first character next
 	}
+	if !l.ascii {
+		l.offsets = make([]int, 0, len(characters))
+		for offset := range input {
+			l.offsets = append(l.offsets, offset)
+		}
+	}
 	for _, option := range options {
 		option(l)
 	}
@@ -132,6 +178,7 @@ func (l *Lexer) SetFilename(file string) {
 func (l *Lexer) Position() token.Position {
 	return token.Position{
 		Char:      l.position,
+		Offset:    l.byteOffset(l.position),
 		LineStart: l.lineStart,
 		Line:      l.line,
 		Column:    l.column,
@@ -160,6 +207,7 @@ func (l *Lexer) Next() (token.Token, error) {
 	// multi-line comments
 	if l.ch == rune('/') && l.peekChar() == rune('*') {
 		l.skipMultiLineComment()
+		return l.Next()
 	}
 
 	if l.prevToken.Type == token.EOF {
@@ -170,149 +218,130 @@ func (l *Lexer) Next() (token.Token, error) {
 	switch l.ch {
 	case rune('&'):
 		if l.peekChar() == rune('&') {
-			ch := l.ch
 			l.readChar()
-			tok = l.newToken(token.AND, string(ch)+string(l.ch))
+			tok = l.newToken(token.AND, l.literal(2))
 		} else {
-			tok = l.newToken(token.AMPERSAND, string(l.ch))
+			tok = l.newToken(token.AMPERSAND, l.literal(1))
 		}
 	case rune('|'):
 		if l.peekChar() == rune('|') {
-			ch := l.ch
 			l.readChar()
-			tok = l.newToken(token.OR, string(ch)+string(l.ch))
+			tok = l.newToken(token.OR, l.literal(2))
 		} else if l.peekChar() == rune('>') {
-			ch := l.ch
 			l.readChar()
-			tok = l.newToken(token.PIPE, string(ch)+string(l.ch))
+			tok = l.newToken(token.PIPE, l.literal(2))
 		} else {
-			tok = l.newToken(token.BITOR, string(l.ch))
+			tok = l.newToken(token.BITOR, l.literal(1))
 		}
 	case rune('^'):
-		tok = l.newToken(token.CARET, string(l.ch))
+		tok = l.newToken(token.CARET, l.literal(1))
 	case rune('='):
 		if l.peekChar() == rune('=') {
-			ch := l.ch
 			l.readChar()
-			tok = l.newToken(token.EQ, string(ch)+string(l.ch))
+			tok = l.newToken(token.EQ, l.literal(2))
 		} else if l.peekChar() == rune('>') {
-			ch := l.ch
 			l.readChar()
-			tok = l.newToken(token.ARROW, string(ch)+string(l.ch))
+			tok = l.newToken(token.ARROW, l.literal(2))
 		} else {
-			tok = l.newToken(token.ASSIGN, string(l.ch))
+			tok = l.newToken(token.ASSIGN, l.literal(1))
 		}
 	case rune(';'):
-		tok = l.newToken(token.SEMICOLON, string(l.ch))
+		tok = l.newToken(token.SEMICOLON, l.literal(1))
 	case rune('?'):
 		if l.peekChar() == rune('.') {
-			ch := l.ch
 			l.readChar()
-			tok = l.newToken(token.QUESTION_DOT, string(ch)+string(l.ch))
+			tok = l.newToken(token.QUESTION_DOT, l.literal(2))
 		} else if l.peekChar() == rune('?') {
-			ch := l.ch
 			l.readChar()
-			tok = l.newToken(token.NULLISH, string(ch)+string(l.ch))
+			tok = l.newToken(token.NULLISH, l.literal(2))
 		} else {
-			tok = l.newToken(token.QUESTION, string(l.ch))
+			tok = l.newToken(token.QUESTION, l.literal(1))
 		}
 	case rune('('):
-		tok = l.newToken(token.LPAREN, string(l.ch))
+		tok = l.newToken(token.LPAREN, l.literal(1))
 	case rune(')'):
-		tok = l.newToken(token.RPAREN, string(l.ch))
+		tok = l.newToken(token.RPAREN, l.literal(1))
 	case rune(','):
-		tok = l.newToken(token.COMMA, string(l.ch))
+		tok = l.newToken(token.COMMA, l.literal(1))
 	case rune('.'):
 		if l.peekChar() == rune('.') && l.peekCharN(2) == rune('.') {
 			l.readChar() // consume second '.'
 			l.readChar() // consume third '.'
 			tok = l.newToken(token.SPREAD, "...")
 		} else {
-			tok = l.newToken(token.PERIOD, string(l.ch))
+			tok = l.newToken(token.PERIOD, l.literal(1))
 		}
 	case rune('+'):
 		if l.peekChar() == rune('+') {
-			ch := l.ch
 			l.readChar()
-			tok = l.newToken(token.PLUS_PLUS, string(ch)+string(l.ch))
+			tok = l.newToken(token.PLUS_PLUS, l.literal(2))
 		} else if l.peekChar() == rune('=') {
-			ch := l.ch
 			l.readChar()
-			tok = l.newToken(token.PLUS_EQUALS, string(ch)+string(l.ch))
+			tok = l.newToken(token.PLUS_EQUALS, l.literal(2))
 		} else {
-			tok = l.newToken(token.PLUS, string(l.ch))
+			tok = l.newToken(token.PLUS, l.literal(1))
 		}
 	case rune('%'):
-		tok = l.newToken(token.MOD, string(l.ch))
+		tok = l.newToken(token.MOD, l.literal(1))
 	case rune('{'):
-		tok = l.newToken(token.LBRACE, string(l.ch))
+		tok = l.newToken(token.LBRACE, l.literal(1))
 	case rune('}'):
-		tok = l.newToken(token.RBRACE, string(l.ch))
+		tok = l.newToken(token.RBRACE, l.literal(1))
 	case rune('-'):
 		if l.peekChar() == rune('-') {
-			ch := l.ch
 			l.readChar()
-			tok = l.newToken(token.MINUS_MINUS, string(ch)+string(l.ch))
+			tok = l.newToken(token.MINUS_MINUS, l.literal(2))
 		} else if l.peekChar() == rune('=') {
-			ch := l.ch
 			l.readChar()
-			tok = l.newToken(token.MINUS_EQUALS, string(ch)+string(l.ch))
+			tok = l.newToken(token.MINUS_EQUALS, l.literal(2))
 		} else {
-			tok = l.newToken(token.MINUS, string(l.ch))
+			tok = l.newToken(token.MINUS, l.literal(1))
 		}
 	case rune('/'):
 		if l.peekChar() == rune('=') {
-			ch := l.ch
 			l.readChar()
-			tok = l.newToken(token.SLASH_EQUALS, string(ch)+string(l.ch))
+			tok = l.newToken(token.SLASH_EQUALS, l.literal(2))
 		} else {
-			tok = l.newToken(token.SLASH, string(l.ch))
+			tok = l.newToken(token.SLASH, l.literal(1))
 		}
 	case rune('*'):
 		if l.peekChar() == rune('*') {
-			ch := l.ch
 			l.readChar()
-			tok = l.newToken(token.POW, string(ch)+string(l.ch))
+			tok = l.newToken(token.POW, l.literal(2))
 		} else if l.peekChar() == rune('=') {
-			ch := l.ch
 			l.readChar()
-			tok = l.newToken(token.ASTERISK_EQUALS, string(ch)+string(l.ch))
+			tok = l.newToken(token.ASTERISK_EQUALS, l.literal(2))
 		} else {
-			tok = l.newToken(token.ASTERISK, string(l.ch))
+			tok = l.newToken(token.ASTERISK, l.literal(1))
 		}
 	case rune('<'):
 		if l.peekChar() == rune('<') {
-			ch := l.ch
 			l.readChar()
-			tok = l.newToken(token.LT_LT, string(ch)+string(l.ch))
+			tok = l.newToken(token.LT_LT, l.literal(2))
 		} else if l.peekChar() == rune('=') {
-			ch := l.ch
 			l.readChar()
-			tok = l.newToken(token.LT_EQUALS, string(ch)+string(l.ch))
+			tok = l.newToken(token.LT_EQUALS, l.literal(2))
 		} else {
-			tok = l.newToken(token.LT, string(l.ch))
+			tok = l.newToken(token.LT, l.literal(1))
 		}
 	case rune('>'):
 		if l.peekChar() == rune('>') {
-			ch := l.ch
 			l.readChar()
-			tok = l.newToken(token.GT_GT, string(ch)+string(l.ch))
+			tok = l.newToken(token.GT_GT, l.literal(2))
 		} else if l.peekChar() == rune('=') {
-			ch := l.ch
 			l.readChar()
-			tok = l.newToken(token.GT_EQUALS, string(ch)+string(l.ch))
+			tok = l.newToken(token.GT_EQUALS, l.literal(2))
 		} else {
-			tok = l.newToken(token.GT, string(l.ch))
+			tok = l.newToken(token.GT, l.literal(1))
 		}
 	case rune('~'):
-		return token.Token{}, fmt.Errorf("unexpected character: %q", l.ch)
+		tok = l.newToken(token.TILDE, l.literal(1))
 	case rune('!'):
 		if l.peekChar() == rune('=') {
-			ch := l.ch
 			l.readChar()
-			tok = l.newToken(token.NOT_EQ, string(ch)+string(l.ch))
+			tok = l.newToken(token.NOT_EQ, l.literal(2))
 		} else {
-			tok = l.newToken(token.BANG, string(l.ch))
+			tok = l.newToken(token.BANG, l.literal(1))
 		}
 	case rune('\''):
 		s, err := l.readString('\'')
@@ -342,21 +371,20 @@ func (l *Lexer) Next() (token.Token, error) {
 		}
 		tok = l.newToken(token.TEMPLATE, s)
 	case rune('['):
-		tok = l.newToken(token.LBRACKET, string(l.ch))
+		tok = l.newToken(token.LBRACKET, l.literal(1))
 	case rune(']'):
-		tok = l.newToken(token.RBRACKET, string(l.ch))
+		tok = l.newToken(token.RBRACKET, l.literal(1))
 	case rune(':'):
-		tok = l.newToken(token.COLON, string(l.ch))
+		tok = l.newToken(token.COLON, l.literal(1))
 	case rune('\r'):
 		if l.peekChar() == rune('\n') {
-			ch := l.ch
 			l.readChar()
-			tok = l.newToken(token.NEWLINE, string(ch)+string(l.ch))
+			tok = l.newToken(token.NEWLINE, l.literal(2))
 		} else {
-			tok = l.newToken(token.NEWLINE, string(l.ch))
+			tok = l.newToken(token.NEWLINE, l.literal(1))
 		}
 	case rune('\n'):
-		tok = l.newToken(token.NEWLINE, string(l.ch))
+		tok = l.newToken(token.NEWLINE, l.literal(1))
 	case rune(0):
 		tok = l.newToken(token.EOF, "")
 	default:
@@ -374,7 +402,12 @@ func (l *Lexer) Next() (token.Token, error) {
 		if err != nil {
 			return token.Token{}, err
 		}
-		if ident == "as" && l.prevToken.Type == token.PERIOD {
+		// Keywords used as attribute names or map keys, e.g. t.assert(...) or
+		// {with: 1}, stay identifiers
+		if attributeKeywords[ident] && (l.prevToken.Type == token.PERIOD ||
+			l.prevToken.Type == token.QUESTION_DOT || l.colonNext()) {
+			tok = l.newToken(token.IDENT, ident)
+		} else if l.identifiers[ident] {
 			tok = l.newToken(token.IDENT, ident)
 		} else {
 			tok = l.newToken(token.LookupIdentifier(ident), ident)
@@ -433,25 +466,54 @@ func (l *Lexer) newToken(typ token.Type, literal string) token.Token {
 		Literal:       literal,
 		StartPosition: l.tokenStartPosition,
 		EndPosition:   l.Position(),
+		Span: token.Span{
+			Start: l.tokenStartPosition.Offset,
+			End:   l.byteOffset(l.position + 1),
+		},
 	}
 }
 
+// byteOffset returns the byte offset in the input of the character at the
+// given index. Indices at or past the end give the length of the input.
+func (l *Lexer) byteOffset(index int) int {
+	switch {
+	case index < 0:
+		return 0
+	case index >= len(l.characters):
+		return len(l.input)
+	case l.ascii:
+		return index
+	}
+	return l.offsets[index]
+}
+
+// text returns the input between two character indices. For ASCII input this
+// is a substring of the input and doesn't allocate.
+func (l *Lexer) text(start, end int) string {
+	if l.ascii {
+		return l.input[start:end]
+	}
+	return string(l.characters[start:end])
+}
+
+// literal returns the last n characters read, ending with the current one.
+func (l *Lexer) literal(n int) string {
+	return l.text(l.position-n+1, l.position+1)
+}
+
 // Read a single identifier
 func (l *Lexer) readIdentifier() (string, error) {
-	var runes []rune
-	if isIdentifier(l.ch) {
-		runes = append(runes, l.ch)
-	} else {
+	start := l.position
+	if !isIdentifier(l.ch) {
 		return "", fmt.Errorf("invalid identifier: %s", string(l.ch))
 	}
 	for isIdentifier(l.peekChar()) {
 		l.readChar()
-		runes = append(runes, l.ch)
 	}
 	if l.peekChar() > unicode.MaxASCII {
-		return "", fmt.Errorf("invalid identifier: %s", string(runes)+string(l.peekChar()))
+		return "", fmt.Errorf("invalid identifier: %s", l.text(start, l.position+1)+string(l.peekChar()))
 	}
-	return string(runes), nil
+	return l.text(start, l.position+1), nil
 }
 
 // Skip over any tabs or spaces. The parser is sensitive to newlines, so we
@@ -491,7 +553,7 @@ func (l *Lexer) skipMultiLineComment() {
 
 // Read a decimal, hex, octal, or binary number
 func (l *Lexer) readNumber(onlyDecimal bool) (NumberType, string, error) {
-	str := string(l.ch)
+	start := l.position
 	// We usually just accept digits
 	accept := "0123456789"
 	numberType := NumberTypeDecimal
@@ -504,15 +566,18 @@ func (l *Lexer) readNumber(onlyDecimal bool) (NumberType, string, error) {
 			// 0b prefix => binary
 			accept = "0b01"
 			numberType = NumberTypeBinary
-		} else if l.ch == '0' && l.peekChar() != '.' {
+		} else if l.ch == '0' && l.peekChar() != '.' && !l.exponentNext() {
 			// 0 prefix => octal
 			accept = "01234567"
 			numberType = NumberTypeOctal
 		}
 	}
-	for strings.Contains(accept, string(l.peekChar())) {
+	for strings.ContainsRune(accept, l.peekChar()) {
 		l.readChar()
-		str += string(l.ch)
+	}
+	str := l.text(start, l.position+1)
+	if numberType == NumberTypeDecimal && l.exponentNext() {
+		return numberType, str, nil
 	}
 	trailing := l.peekChar()
 	if unicode.IsLetter(trailing) || unicode.IsNumber(trailing) {
@@ -521,23 +586,26 @@ func (l *Lexer) readNumber(onlyDecimal bool) (NumberType, string, error) {
 	return numberType, str, nil
 }
 
-// Read an integer or floating point number
+// Read an integer or floating point number. Floats may have a fraction, an
+// exponent, or both, as in 1.5, 1e9, and 2.5e-3.
 func (l *Lexer) readDecimal() (token.Token, error) {
 	// Read an integer
+	start := l.position
 	numberType, integer, err := l.readNumber(false)
 	if err != nil {
 		return token.Token{}, err
 	}
-	hasDot := l.peekChar() == rune('.')
-	if !hasDot {
-		return l.newToken(token.INT, integer), nil
-	}
-	if numberType != NumberTypeDecimal {
-		return token.Token{}, fmt.Errorf("invalid decimal literal: %s%s", integer, ".")
-	}
-	// Read the "."
-	l.readChar()
-	if isDigit(l.peekChar()) {
+	isFloat := false
+	if l.peekChar() == rune('.') {
+		if numberType != NumberTypeDecimal {
+			return token.Token{}, fmt.Errorf("invalid decimal literal: %s%s", integer, ".")
+		}
+		// Read the "."
+		l.readChar()
+		if !isDigit(l.peekChar()) {
+			// We reach this point with something like "42.foo"
+			return token.Token{}, fmt.Errorf("invalid decimal literal: %s.%c", integer, l.peekChar())
+		}
 		l.readChar()
 		numberType, fraction, err := l.readNumber(true)
 		if err != nil {
@@ -546,10 +614,41 @@ func (l *Lexer) readDecimal() (token.Token, error) {
 		if numberType != NumberTypeDecimal {
 			return token.Token{}, fmt.Errorf("invalid decimal literal: %s.%s", integer, fraction)
 		}
-		return l.newToken(token.FLOAT, integer+"."+fraction), nil
+		isFloat = true
 	}
-	// We reach this point with something like "42.foo"
-	return token.Token{}, fmt.Errorf("invalid decimal literal: %s.%c", integer, l.peekChar())
+	if numberType == NumberTypeDecimal && l.exponentNext() {
+		// Read the "e" and the exponent's sign, if any
+		l.readChar()
+		if sign := l.peekChar(); sign == '+' || sign == '-' {
+			l.readChar()
+		}
+		l.readChar()
+		for isDigit(l.peekChar()) {
+			l.readChar()
+		}
+		if trailing := l.peekChar(); unicode.IsLetter(trailing) || unicode.IsNumber(trailing) {
+			return token.Token{}, fmt.Errorf("invalid decimal literal: %s%c",
+				l.text(start, l.position+1), trailing)
+		}
+		isFloat = true
+	}
+	if isFloat {
+		return l.newToken(token.FLOAT, l.text(start, l.position+1)), nil
+	}
+	return l.newToken(token.INT, integer), nil
+}
+
+// exponentNext reports whether the next characters are the exponent of a
+// number in scientific notation, such as "e10", "E+3", or "e-7".
+func (l *Lexer) exponentNext() bool {
+	if ch := l.peekChar(); ch != 'e' && ch != 'E' {
+		return false
+	}
+	next := l.peekCharN(2)
+	if next == '+' || next == '-' {
+		next = l.peekCharN(3)
+	}
+	return isDigit(next)
 }
 
 func (l *Lexer) readString(end rune) (string, error) {
@@ -680,7 +779,7 @@ func (l *Lexer) readBacktick() (string, error) {
 			break
 		}
 	}
-	return string(l.characters[position:l.position]), err
+	return l.text(position, l.position), err
 }
 
 func (l *Lexer) peekChar() rune {
@@ -690,6 +789,21 @@ func (l *Lexer) peekChar() rune {
 	return l.characters[l.nextPosition]
 }
 
+// colonNext reports whether the next character after any spaces or tabs is a
+// colon.
+func (l *Lexer) colonNext() bool {
+	for _, ch := range l.characters[min(l.nextPosition, len(l.characters)):] {
+		switch ch {
+		case ' ', '\t':
+			continue
+		case ':':
+			return true
+		}
+		return false
+	}
+	return false
+}
+
 // peekCharN returns the character at the given offset from the current position.
 // peekCharN(1) is equivalent to peekChar().
 func (l *Lexer) peekCharN(n int) rune {
@@ -737,7 +851,7 @@ func (l *Lexer) GetLineText(t token.Token) string {
 		end++
 	}
 	// Return the line, excluding the newline character
-	return string(l.characters[start:end])
+	return l.text(start, end)
 }
 
 func isIdentifier(ch rune) bool {
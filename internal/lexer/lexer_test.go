@@ -419,6 +419,11 @@ func TestInvalidIntegers(t *testing.T) {
 		{"12ab", "invalid decimal literal: 12a"},
 		{"0x1aZ", "invalid decimal literal: 0x1aZ"},
 		{"078", "invalid decimal literal: 078"},
+		{"1e", "invalid decimal literal: 1e"},
+		{"1e+", "invalid decimal literal: 1e"},
+		{"1e3e4", "invalid decimal literal: 1e3e"},
+		{"2.5e3x", "invalid decimal literal: 2.5e3x"},
+		{"0x1p3", "invalid decimal literal: 0x1p"},
 	}
 	for _, tt := range tests {
 		l := New(tt.input)
@@ -788,7 +793,6 @@ func TestInvalids(t *testing.T) {
 		{`"foo`, "unterminated string literal"},
 		{"`foo", "unterminated string literal"},
 		{"'foo", "unterminated string literal"},
-		{"~", "unexpected character: '~'"},
 	}
 	for i, tt := range tests {
 		t.Run(fmt.Sprintf("%d-%s", i, tt.input), func(t *testing.T) {
@@ -1133,6 +1137,19 @@ func TestBitShiftOperators(t *testing.T) {
 				{token.EOF, ""},
 			},
 		},
+		{
+			input: "x & ~0xF",
+			expected: []struct {
+				typ     token.Type
+				literal string
+			}{
+				{token.IDENT, "x"},
+				{token.AMPERSAND, "&"},
+				{token.TILDE, "~"},
+				{token.INT, "0xF"},
+				{token.EOF, ""},
+			},
+		},
 	}
 	for _, tt := range tests {
 		t.Run(tt.input, func(t *testing.T) {
@@ -1326,6 +1343,140 @@ func TestAsAfterPeriod(t *testing.T) {
 				{token.EOF, ""},
 			},
 		},
+		{
+			name:  "assert standalone is keyword",
+			input: "assert x",
+			expected: []struct {
+				typ     token.Type
+				literal string
+			}{
+				{token.ASSERT, "assert"},
+				{token.IDENT, "x"},
+				{token.EOF, ""},
+			},
+		},
+		{
+			name:  "assert after period is ident",
+			input: "t.assert(x)",
+			expected: []struct {
+				typ     token.Type
+				literal string
+			}{
+				{token.IDENT, "t"},
+				{token.PERIOD, "."},
+				{token.IDENT, "assert"},
+				{token.LPAREN, "("},
+				{token.IDENT, "x"},
+				{token.RPAREN, ")"},
+				{token.EOF, ""},
+			},
+		},
+		{
+			name:  "del standalone is keyword",
+			input: "del x[0]",
+			expected: []struct {
+				typ     token.Type
+				literal string
+			}{
+				{token.DEL, "del"},
+				{token.IDENT, "x"},
+				{token.LBRACKET, "["},
+				{token.INT, "0"},
+				{token.RBRACKET, "]"},
+				{token.EOF, ""},
+			},
+		},
+		{
+			name:  "del after period is ident",
+			input: "m.del",
+			expected: []struct {
+				typ     token.Type
+				literal string
+			}{
+				{token.IDENT, "m"},
+				{token.PERIOD, "."},
+				{token.IDENT, "del"},
+				{token.EOF, ""},
+			},
+		},
+		{
+			name:  "export after period is ident",
+			input: "m.export",
+			expected: []struct {
+				typ     token.Type
+				literal string
+			}{
+				{token.IDENT, "m"},
+				{token.PERIOD, "."},
+				{token.IDENT, "export"},
+				{token.EOF, ""},
+			},
+		},
+		{
+			name:  "import after period is ident",
+			input: "m.import",
+			expected: []struct {
+				typ     token.Type
+				literal string
+			}{
+				{token.IDENT, "m"},
+				{token.PERIOD, "."},
+				{token.IDENT, "import"},
+				{token.EOF, ""},
+			},
+		},
+		{
+			name:  "with after optional chaining is ident",
+			input: "m?.with",
+			expected: []struct {
+				typ     token.Type
+				literal string
+			}{
+				{token.IDENT, "m"},
+				{token.QUESTION_DOT, "?."},
+				{token.IDENT, "with"},
+				{token.EOF, ""},
+			},
+		},
+		{
+			name:  "keywords before a colon are idents",
+			input: "{assert: 1, quote : 2, with:3}",
+			expected: []struct {
+				typ     token.Type
+				literal string
+			}{
+				{token.LBRACE, "{"},
+				{token.IDENT, "assert"},
+				{token.COLON, ":"},
+				{token.INT, "1"},
+				{token.COMMA, ","},
+				{token.IDENT, "quote"},
+				{token.COLON, ":"},
+				{token.INT, "2"},
+				{token.COMMA, ","},
+				{token.IDENT, "with"},
+				{token.COLON, ":"},
+				{token.INT, "3"},
+				{token.RBRACE, "}"},
+				{token.EOF, ""},
+			},
+		},
+		{
+			name:  "with standalone is keyword",
+			input: "with f as x {}",
+			expected: []struct {
+				typ     token.Type
+				literal string
+			}{
+				{token.WITH, "with"},
+				{token.IDENT, "f"},
+				{token.IDENT, "as"},
+				{token.IDENT, "x"},
+				{token.LBRACE, "{"},
+				{token.RBRACE, "}"},
+				{token.EOF, ""},
+			},
+		},
 	}
 	for _, tt := range tests {
 		t.Run(tt.name, func(t *testing.T) {
@@ -1549,6 +1700,18 @@ func TestFilenameOption(t *testing.T) {
 	})
 }
 
+func TestIdentifiersOption(t *testing.T) {
+	l := New("with import", WithIdentifiers("with"))
+	tok, err := l.Next()
+	assert.Nil(t, err)
+	assert.Equal(t, tok.Type, token.IDENT)
+	assert.Equal(t, tok.Literal, "with")
+
+	tok, err = l.Next()
+	assert.Nil(t, err)
+	assert.Equal(t, tok.Type, token.IMPORT)
+}
+
 func TestTemplateStringWithNewlines(t *testing.T) {
 	tests := []struct {
 		name            string
@@ -1604,6 +1767,11 @@ func TestFloatEdgeCases(t *testing.T) {
 		{"123.0", token.FLOAT, "123.0"},
 		{"0", token.INT, "0"},
 		{"00", token.INT, "00"}, // octal zero
+		{"1e3", token.FLOAT, "1e3"},
+		{"2.5e-3", token.FLOAT, "2.5e-3"},
+		{"1E+10", token.FLOAT, "1E+10"},
+		{"0e0", token.FLOAT, "0e0"},
+		{"10e2", token.FLOAT, "10e2"},
 	}
 	for _, tt := range tests {
 		t.Run(tt.input, func(t *testing.T) {
@@ -1673,3 +1841,15 @@ func TestSlashEquals(t *testing.T) {
 		assert.Equal(t, tok.Literal, exp.literal, "token %d literal", i)
 	}
 }
+
+func TestTokenSpans(t *testing.T) {
+	input := "s = \"世界\\n\" + x /* note */ y // done\nz"
+	expected := []string{"s", "=", `"世界\n"`, "+", "x", "y", "\n", "z", ""}
+	l := New(input)
+	for i, text := range expected {
+		tok, err := l.Next()
+		assert.Nil(t, err)
+		assert.Equal(t, tok.Span.Text(input), text, "token %d", i)
+		assert.Equal(t, tok.StartPosition.Offset, tok.Span.Start, "token %d", i)
+	}
+}
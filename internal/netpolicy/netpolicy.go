@@ -0,0 +1,83 @@
+// Package netpolicy holds what the modules that open network connections
+// share: the dialer the host provides, the check made before a script
+// connects, and the timeout that bounds each operation on a connection.
+package netpolicy
+
+import (
+	"context"
+	"fmt"
+	"net"
+	"sync/atomic"
+	"time"
+)
+
+// Dialer opens network connections. *net.Dialer implements it.
+type Dialer interface {
+	DialContext(ctx context.Context, network, address string) (net.Conn, error)
+}
+
+// Check returns an error unless a script may connect to target, which needs
+// a dialer and the host's approval. fn names the function connecting, such
+// as "ws.connect", and target is shown in the error, so it must not hold
+// secrets.
+func Check(fn string, dialer Dialer, allowed bool, target string) error {
+	if dialer == nil {
+		return fmt.Errorf("%s: network access is not allowed", fn)
+	}
+	if !allowed {
+		return fmt.Errorf("%s: connecting to %s is not allowed", fn, target)
+	}
+	return nil
+}
+
+// Deadliner is a connection whose operations can be given a deadline.
+type Deadliner interface {
+	SetDeadline(t time.Time) error
+}
+
+// Timeout is the time limit for each operation on a connection. Scripts may
+// change it while another goroutine uses the connection. Zero means no
+// limit.
+type Timeout struct {
+	d atomic.Int64
+}
+
+// NewTimeout returns a Timeout of d.
+func NewTimeout(d time.Duration) *Timeout {
+	t := &Timeout{}
+	t.Set(d)
+	return t
+}
+
+// Set changes the timeout for operations that start after it returns.
+func (t *Timeout) Set(d time.Duration) {
+	t.d.Store(int64(d))
+}
+
+// Do runs fn, an operation on conn, with a deadline from the timeout and
+// ctx, and interrupts it if ctx is canceled. If fn fails after ctx is done,
+// Do returns the context's error.
+func (t *Timeout) Do(ctx context.Context, conn Deadliner, fn func() error) error {
+	var deadline time.Time
+	if timeout := time.Duration(t.d.Load()); timeout > 0 {
+		deadline = time.Now().Add(timeout)
+	}
+	if d, ok := ctx.Deadline(); ok && (deadline.IsZero() || d.Before(deadline)) {
+		deadline = d
+	}
+	if err := conn.SetDeadline(deadline); err != nil {
+		return err
+	}
+	stop := context.AfterFunc(ctx, func() {
+		// A deadline in the past unblocks the operation
+		_ = conn.SetDeadline(time.Unix(1, 0))
+	})
+	defer stop()
+	if err := fn(); err != nil {
+		if ctx.Err() != nil {
+			return ctx.Err()
+		}
+		return err
+	}
+	return nil
+}
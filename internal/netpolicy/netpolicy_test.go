@@ -0,0 +1,50 @@
+package netpolicy
+
+import (
+	"context"
+	"errors"
+	"net"
+	"os"
+	"testing"
+	"time"
+
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+func TestCheck(t *testing.T) {
+	err := Check("net.dial", nil, true, "db.internal:5432")
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), "net.dial: network access is not allowed")
+
+	err = Check("net.dial", &net.Dialer{}, false, "db.internal:5432")
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), "net.dial: connecting to db.internal:5432 is not allowed")
+
+	assert.Nil(t, Check("net.dial", &net.Dialer{}, true, "db.internal:5432"))
+}
+
+func TestTimeoutDo(t *testing.T) {
+	client, server := net.Pipe()
+	defer client.Close()
+	defer server.Close()
+	read := func() error {
+		_, err := client.Read(make([]byte, 1))
+		return err
+	}
+
+	// The timeout bounds each operation
+	timeout := NewTimeout(20 * time.Millisecond)
+	err := timeout.Do(context.Background(), client, read)
+	assert.True(t, errors.Is(err, os.ErrDeadlineExceeded))
+
+	// Canceling the context interrupts the operation
+	timeout.Set(0)
+	ctx, cancel := context.WithCancel(context.Background())
+	time.AfterFunc(20*time.Millisecond, cancel)
+	err = timeout.Do(ctx, client, read)
+	assert.True(t, errors.Is(err, context.Canceled))
+
+	// Without a timeout or a done context, the operation runs to completion
+	go func() { _, _ = server.Write([]byte{1}) }()
+	assert.Nil(t, timeout.Do(context.Background(), client, read))
+}
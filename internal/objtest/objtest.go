@@ -0,0 +1,21 @@
+// Package objtest holds helpers for testing the objects that Go packages,
+// such as the modules, give to scripts.
+package objtest
+
+import (
+	"context"
+	"testing"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+// Call calls the builtin that obj has as the named attribute, such as a
+// module function or a resource method, as a script would. The test fails if
+// obj has no such attribute.
+func Call(t *testing.T, obj object.Object, name string, args ...object.Object) (object.Object, error) {
+	t.Helper()
+	fn, ok := obj.GetAttr(name)
+	assert.True(t, ok)
+	return fn.(*object.Builtin).Call(context.Background(), args...)
+}
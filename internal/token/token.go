@@ -6,8 +6,9 @@ type Type string
 
 // Position points to a particular location in an input string.
 type Position struct {
-	Char      int    // byte offset within the file
-	LineStart int    // byte offset of the start of the current line
+	Char      int    // character offset within the file
+	Offset    int    // byte offset within the file
+	LineStart int    // character offset of the start of the current line
 	Line      int    // 0-indexed line number
 	Column    int    // 0-indexed column number
 	File      string // filename
@@ -29,6 +30,7 @@ func (p Position) ColumnNumber() int {
 func (p Position) Advance(n int) Position {
 	return Position{
 		Char:      p.Char + n,
+		Offset:    p.Offset + n,
 		LineStart: p.LineStart,
 		Line:      p.Line,
 		Column:    p.Column + n,
@@ -38,18 +40,46 @@ func (p Position) Advance(n int) Position {
 
 // IsValid returns true if this position has been set.
 func (p Position) IsValid() bool {
-	return p.File != "" || p.Line > 0 || p.Column > 0 || p.Char > 0
+	return p.File != "" || p.Line > 0 || p.Column > 0 || p.Char > 0 || p.Offset > 0
 }
 
 // NoPos is the zero value Position, representing an invalid/unset position.
 var NoPos = Position{}
 
+// Span is the range of source text covered by a token or node, in byte
+// offsets. Start is the offset of the first byte and End is the offset just
+// past the last byte, so the text is source[Start:End].
+type Span struct {
+	Start int
+	End   int
+}
+
+// Len returns the length of the span in bytes.
+func (s Span) Len() int {
+	return s.End - s.Start
+}
+
+// Contains returns true if the byte at offset is within the span.
+func (s Span) Contains(offset int) bool {
+	return offset >= s.Start && offset < s.End
+}
+
+// Text returns the text of the span within source, or an empty string if the
+// span is out of range.
+func (s Span) Text(source string) string {
+	if s.Start < 0 || s.Start > s.End || s.End > len(source) {
+		return ""
+	}
+	return source[s.Start:s.End]
+}
+
 // Token represents one token lexed from the input source code.
 type Token struct {
 	Type          Type
 	Literal       string
 	StartPosition Position
-	EndPosition   Position
+	EndPosition   Position // position of the last character of the token
+	Span          Span     // byte range of the token's source text
 }
 
 // Token types
@@ -123,21 +153,33 @@ const (
 	CATCH           Type = "CATCH"
 	FINALLY         Type = "FINALLY"
 	THROW           Type = "THROW"
+	TILDE           Type = "~"
+	WITH            Type = "WITH"
+	ASSERT          Type = "ASSERT"
+	EXPORT          Type = "EXPORT"
+	IMPORT          Type = "IMPORT"
+	QUOTE           Type = "QUOTE"
+	DEL             Type = "DEL"
 )
 
 // Reserved keywords
 var keywords = map[string]Type{
+	"assert":   ASSERT,
 	"const":    CONST,
+	"del":      DEL,
 	"else":     ELSE,
+	"export":   EXPORT,
 	"false":    FALSE,
 	"function": FUNCTION,
 	"if":       IF,
+	"import":   IMPORT,
 	"in":       IN,
 	"let":      LET,
 	"match":    MATCH,
 	"nil":      NIL,
 	"null":     NIL,
 	"not":      NOT,
+	"quote":    QUOTE,
 	"return":   RETURN,
 	"struct":   STRUCT,
 	"throw":    THROW,
@@ -145,6 +187,7 @@ var keywords = map[string]Type{
 	"try":      TRY,
 	"catch":    CATCH,
 	"finally":  FINALLY,
+	"with":     WITH,
 }
 
 // LookupIdentifier used to determinate whether identifier is keyword nor not
@@ -50,9 +50,10 @@ func TestPositionAdvance(t *testing.T) {
 	// Advance by 3 bytes
 	advanced := pos.Advance(3)
 
-	// Check that Char and Column are advanced
+	// Check that Char, Column, and Offset are advanced
 	assert.Equal(t, advanced.Char, 13)
 	assert.Equal(t, advanced.Column, 8)
+	assert.Equal(t, advanced.Offset, 3)
 
 	// Check that other fields are preserved
 	assert.Equal(t, advanced.LineStart, 5)
@@ -125,3 +126,14 @@ func TestPositionFields(t *testing.T) {
 	assert.Equal(t, pos.LineNumber(), 6)    // Line + 1
 	assert.Equal(t, pos.ColumnNumber(), 11) // Column + 1
 }
+
+func TestSpan(t *testing.T) {
+	source := "let é = 1"
+	span := Span{Start: 4, End: 6}
+	assert.Equal(t, span.Len(), 2)
+	assert.Equal(t, span.Text(source), "é")
+	assert.True(t, span.Contains(4))
+	assert.True(t, span.Contains(5))
+	assert.False(t, span.Contains(6))
+	assert.Equal(t, Span{Start: 4, End: 20}.Text(source), "")
+}
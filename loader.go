@@ -0,0 +1,268 @@
+package risor
+
+import (
+	"context"
+	"fmt"
+	"io/fs"
+	"os"
+	"path"
+	"path/filepath"
+	"slices"
+	"strings"
+	"sync"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/stdlib"
+)
+
+// FileLoader is a ModuleLoader that reads Risor source files from disk.
+//
+// Import paths are resolved in a fixed order:
+//
+//  1. Paths beginning with "./" or "../" are relative to the directory of the
+//     importing file, or to the working directory if the importing code has
+//     no filename. Search paths are not consulted.
+//  2. Other paths are looked up in each search path, in the order given, and
+//     the first match wins.
+//
+// The ".risor" extension is added to paths that don't already have it.
+// Absolute import paths are rejected. Modules are named by their absolute
+// file path.
+type FileLoader struct {
+	searchPaths []string
+}
+
+// NewFileLoader returns a FileLoader that searches the given directories for
+// non-relative import paths.
+func NewFileLoader(searchPaths []string) *FileLoader {
+	return &FileLoader{searchPaths: slices.Clone(searchPaths)}
+}
+
+// SearchPaths returns the directories searched for non-relative import paths.
+func (l *FileLoader) SearchPaths() []string {
+	return slices.Clone(l.searchPaths)
+}
+
+// Resolve returns the absolute path of the file an import path refers to.
+func (l *FileLoader) Resolve(importPath, from string) (string, error) {
+	if filepath.IsAbs(importPath) || strings.HasPrefix(importPath, "/") {
+		return "", fmt.Errorf("absolute import paths are not allowed")
+	}
+	var candidates []string
+	if isRelativeImport(importPath) {
+		dir := "."
+		if from != "" {
+			dir = filepath.Dir(from)
+		}
+		candidates = append(candidates, filepath.Join(dir, filepath.FromSlash(importPath)))
+	} else {
+		for _, searchPath := range l.searchPaths {
+			candidates = append(candidates, filepath.Join(searchPath, filepath.FromSlash(importPath)))
+		}
+	}
+	for i, candidate := range candidates {
+		if filepath.Ext(candidate) != SourceExt {
+			candidate += SourceExt
+		}
+		candidates[i] = candidate
+		if info, err := os.Stat(candidate); err == nil && info.Mode().IsRegular() {
+			return filepath.Abs(candidate)
+		}
+	}
+	if len(candidates) == 0 {
+		return "", fmt.Errorf("module not found (no search paths configured)")
+	}
+	return "", fmt.Errorf("module not found (searched %s)", strings.Join(candidates, ", "))
+}
+
+// Load reads the source file with the given path.
+func (l *FileLoader) Load(ctx context.Context, name string) (string, error) {
+	data, err := os.ReadFile(name)
+	if err != nil {
+		return "", err
+	}
+	return string(data), nil
+}
+
+// FSLoader is a ModuleLoader that reads Risor source files from an fs.FS,
+// such as an embed.FS compiled into the host program.
+//
+// Import paths are resolved as by FileLoader, except that paths are slash
+// separated and interpreted within the file system, and modules are named by
+// their path within it. Relative imports cannot reach outside the file
+// system.
+type FSLoader struct {
+	fsys        fs.FS
+	searchPaths []string
+}
+
+// NewFSLoader returns an FSLoader that searches the given directories of fsys
+// for non-relative import paths. If no search paths are given, the root of
+// fsys is searched.
+func NewFSLoader(fsys fs.FS, searchPaths []string) *FSLoader {
+	if len(searchPaths) == 0 {
+		searchPaths = []string{"."}
+	}
+	return &FSLoader{fsys: fsys, searchPaths: slices.Clone(searchPaths)}
+}
+
+// Resolve returns the path within the file system of the file an import path
+// refers to.
+func (l *FSLoader) Resolve(importPath, from string) (string, error) {
+	if path.IsAbs(importPath) {
+		return "", fmt.Errorf("absolute import paths are not allowed")
+	}
+	var candidates []string
+	if isRelativeImport(importPath) {
+		dir := "."
+		if from != "" {
+			dir = path.Dir(from)
+		}
+		candidates = append(candidates, path.Join(dir, importPath))
+	} else {
+		for _, searchPath := range l.searchPaths {
+			candidates = append(candidates, path.Join(searchPath, importPath))
+		}
+	}
+	for i, candidate := range candidates {
+		candidate = withSourceExt(candidate)
+		candidates[i] = candidate
+		if !fs.ValidPath(candidate) {
+			return "", fmt.Errorf("import path %q is outside the module file system", importPath)
+		}
+		if info, err := fs.Stat(l.fsys, candidate); err == nil && info.Mode().IsRegular() {
+			return candidate, nil
+		}
+	}
+	return "", fmt.Errorf("module not found (searched %s)", strings.Join(candidates, ", "))
+}
+
+// Load reads the source file with the given path within the file system.
+func (l *FSLoader) Load(ctx context.Context, name string) (string, error) {
+	data, err := fs.ReadFile(l.fsys, name)
+	if err != nil {
+		return "", err
+	}
+	return string(data), nil
+}
+
+// MapLoader is a ModuleLoader that serves module source code from memory. It
+// maps module names, which are slash-separated paths such as "helpers" or
+// "lib/strings.risor", to source code.
+//
+// An import path names the module with that path, or with that path plus the
+// ".risor" extension. Relative import paths are resolved against the name of
+// the importing module.
+type MapLoader map[string]string
+
+// Resolve returns the name of the module an import path refers to.
+func (l MapLoader) Resolve(importPath, from string) (string, error) {
+	if path.IsAbs(importPath) {
+		return "", fmt.Errorf("absolute import paths are not allowed")
+	}
+	name := importPath
+	if isRelativeImport(importPath) {
+		dir := "."
+		if from != "" {
+			dir = path.Dir(from)
+		}
+		name = path.Join(dir, importPath)
+	}
+	if _, ok := l[name]; ok {
+		return name, nil
+	}
+	if _, ok := l[withSourceExt(name)]; ok {
+		return withSourceExt(name), nil
+	}
+	return "", fmt.Errorf("module not found")
+}
+
+// Load returns the source code of the named module.
+func (l MapLoader) Load(ctx context.Context, name string) (string, error) {
+	source, ok := l[name]
+	if !ok {
+		return "", fmt.Errorf("module not found")
+	}
+	return source, nil
+}
+
+// MultiLoader is a ModuleLoader that combines several loaders. An import path
+// is resolved by each loader in turn, and the first one that resolves it
+// supplies the module. Relative import paths are resolved only by the loader
+// that supplied the importing module, or by the first loader when the import
+// is in code that did not come from this MultiLoader, such as the main script.
+//
+// Modules are cached by name, so the loaders should give their modules
+// distinct names, as FileLoader and FSLoader do.
+type MultiLoader struct {
+	loaders []ModuleLoader
+
+	mu     sync.Mutex
+	owners map[string]ModuleLoader
+}
+
+// NewMultiLoader returns a MultiLoader that consults the given loaders in
+// order.
+func NewMultiLoader(loaders ...ModuleLoader) *MultiLoader {
+	return &MultiLoader{
+		loaders: slices.Clone(loaders),
+		owners:  map[string]ModuleLoader{},
+	}
+}
+
+// Loaders returns the loaders, in the order they are consulted.
+func (l *MultiLoader) Loaders() []ModuleLoader {
+	return slices.Clone(l.loaders)
+}
+
+// Resolve resolves an import path with the first loader that can. If none
+// can, the error from the first loader is returned.
+func (l *MultiLoader) Resolve(importPath, from string) (string, error) {
+	l.mu.Lock()
+	owner, found := l.owners[from]
+	l.mu.Unlock()
+	loaders := l.loaders
+	if isRelativeImport(importPath) && len(loaders) > 0 {
+		if !found {
+			owner = loaders[0]
+		}
+		loaders = []ModuleLoader{owner}
+	}
+	var firstErr error
+	for _, loader := range loaders {
+		name, err := loader.Resolve(importPath, from)
+		if err != nil {
+			if firstErr == nil {
+				firstErr = err
+			}
+			continue
+		}
+		l.mu.Lock()
+		if _, taken := l.owners[name]; !taken {
+			l.owners[name] = loader
+		}
+		l.mu.Unlock()
+		return name, nil
+	}
+	if firstErr == nil {
+		firstErr = fmt.Errorf("module not found")
+	}
+	return "", firstErr
+}
+
+// Load loads a module with the loader that resolved its name.
+func (l *MultiLoader) Load(ctx context.Context, name string) (string, error) {
+	l.mu.Lock()
+	owner, found := l.owners[name]
+	l.mu.Unlock()
+	if !found {
+		return "", fmt.Errorf("module not found")
+	}
+	return owner.Load(ctx, name)
+}
+
+// StdlibLoader returns a loader for the standard library modules written in
+// Risor, such as itertools and functools. The modules are embedded in the
+// binary and are compiled only when first imported.
+func StdlibLoader() *FSLoader {
+	return NewFSLoader(stdlib.FS(), nil)
+}
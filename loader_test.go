@@ -0,0 +1,136 @@
+package risor
+
+import (
+	"context"
+	"fmt"
+	"path/filepath"
+	"testing"
+	"testing/fstest"
+
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+func TestMapLoader(t *testing.T) {
+	loader := MapLoader{
+		"greet": `
+		import "./lib/strings" as s
+		export function greet(name) { "hello " + s.upper(name) }
+		`,
+		"lib/strings.risor": `export function upper(x) { x.to_upper() }`,
+	}
+	imp := NewImporter(loader, WithEnv(Builtins()))
+	result, err := Eval(context.Background(), `import "greet"; greet.greet("ann")`,
+		WithEnv(Builtins()), WithImporter(imp))
+	assert.Nil(t, err)
+	assert.Equal(t, result, "hello ANN")
+
+	_, err = loader.Resolve("missing", "")
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "module not found")
+}
+
+func TestFSLoader(t *testing.T) {
+	fsys := fstest.MapFS{
+		"lib/helpers.risor": {Data: []byte(`
+		import "./strings"
+		export function shout(x) { strings.upper(x) + "!" }
+		`)},
+		"lib/strings.risor": {Data: []byte(`export function upper(x) { x.to_upper() }`)},
+	}
+	loader := NewFSLoader(fsys, []string{"lib"})
+	imp := NewImporter(loader, WithEnv(Builtins()))
+	result, err := Eval(context.Background(), `import "helpers"; helpers.shout("hi")`,
+		WithEnv(Builtins()), WithImporter(imp))
+	assert.Nil(t, err)
+	assert.Equal(t, result, "HI!")
+
+	name, err := loader.Resolve("./strings", "lib/helpers.risor")
+	assert.Nil(t, err)
+	assert.Equal(t, name, "lib/strings.risor")
+
+	_, err = loader.Resolve("../../secret", "lib/helpers.risor")
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "outside the module file system")
+
+	_, err = loader.Resolve("missing", "")
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "module not found (searched lib/missing.risor)")
+}
+
+// countingLoader serves modules from memory and counts how often each one is
+// loaded, standing in for a remote or database-backed loader.
+type countingLoader struct {
+	sources map[string]string
+	loads   map[string]int
+}
+
+func (l *countingLoader) Resolve(importPath, from string) (string, error) {
+	if _, ok := l.sources[importPath]; !ok {
+		return "", fmt.Errorf("no module named %q", importPath)
+	}
+	return "db://" + importPath, nil
+}
+
+func (l *countingLoader) Load(ctx context.Context, name string) (string, error) {
+	l.loads[name]++
+	return l.sources[name[len("db://"):]], nil
+}
+
+func TestCustomLoader(t *testing.T) {
+	loader := &countingLoader{
+		sources: map[string]string{
+			"config": `export let region = "us-east"`,
+			"app":    `import "config"; export function region() { config.region }`,
+		},
+		loads: map[string]int{},
+	}
+	imp := NewImporter(loader)
+	result, err := Eval(context.Background(), `
+	import "app"
+	import "config"
+	app.region() + "/" + config.region
+	`, WithImporter(imp))
+	assert.Nil(t, err)
+	assert.Equal(t, result, "us-east/us-east")
+	assert.Equal(t, loader.loads, map[string]int{"db://app": 1, "db://config": 1})
+
+	_, err = Eval(context.Background(), `import "other"`, WithImporter(imp))
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), `no module named "other"`)
+}
+
+func TestMultiLoader(t *testing.T) {
+	first := MapLoader{
+		"app":    `import "./util"; import "shared"; export let value = util.name + "," + shared.name`,
+		"util":   `export let name = "first-util"`,
+		"shared": `export let name = "first-shared"`,
+	}
+	second := MapLoader{
+		"ext/util":  `export let name = "second-util"`,
+		"ext/extra": `import "./util"; export let name = util.name`,
+		"shared":    `export let name = "second-shared"`,
+	}
+	imp := NewImporter(NewMultiLoader(first, second))
+	result, err := Eval(context.Background(), `
+	import "app"
+	import "ext/extra"
+	app.value + "," + extra.name
+	`, WithImporter(imp))
+	assert.Nil(t, err)
+	// Earlier loaders win, and relative imports stay within their loader
+	assert.Equal(t, result, "first-util,first-shared,second-util")
+}
+
+func TestFileImporterStdlib(t *testing.T) {
+	dir := t.TempDir()
+	writeFiles(t, dir, map[string]string{
+		"main.risor":          `import "itertools"; import "functools"; [itertools.take([1, 2, 3], 1), functools.name]`,
+		"lib/functools.risor": `export let name = "project"`,
+	})
+	imp := NewFileImporter([]string{filepath.Join(dir, "lib")}, WithEnv(Builtins()))
+	result, err := runFile(t, filepath.Join(dir, "main.risor"), imp)
+	assert.Nil(t, err)
+	// The standard library is available, and the project's own module
+	// shadows the standard one
+	assert.Equal(t, result, []any{[]any{int64(1)}, "project"})
+}
@@ -0,0 +1,242 @@
+package risor
+
+import (
+	"errors"
+	"fmt"
+	"slices"
+	"strconv"
+	"strings"
+	"time"
+)
+
+// ErrRequirementNotMet is returned when a script's metadata requires a global
+// that the environment doesn't provide.
+var ErrRequirementNotMet = errors.New("script requirement not met")
+
+// Metadata holds the declarations in a script's metadata block. The block is
+// a comment at the start of the script, after any shebang line, whose "/*---"
+// and "---*/" delimiters are on lines of their own. It lists entries as
+// key: value pairs separated by commas or newlines:
+//
+//	/*---
+//	requires: ["http", "db"]
+//	timeout: 30s
+//	max_steps: 1000000
+//	---*/
+//
+// Values are lists in brackets or single values. Strings may be written bare
+// or in double quotes. Run and Eval honor the known entries:
+//
+//   - requires: globals the script needs. Run fails with
+//     ErrRequirementNotMet before running the script if any are missing.
+//   - timeout: a duration such as 30s. It applies if it is shorter than the
+//     timeout given with WithTimeout, so scripts can't raise host limits.
+//   - max_steps: an instruction limit, applied like timeout.
+//
+// Other entries, such as a description, are kept in Fields. A comment that
+// only looks like a block, such as a "/*--- utils ---*/" banner or one with
+// no key: value entries, isn't treated as metadata.
+type Metadata struct {
+	Requires []string
+	Timeout  time.Duration
+	MaxSteps int64
+	// Fields holds every entry by key. Values are strings, or []string for
+	// lists.
+	Fields map[string]any
+}
+
+// ParseMetadata returns the metadata declared by source, or nil if it has no
+// metadata block.
+func ParseMetadata(source string) (*Metadata, error) {
+	body, ok := findMetadataBlock(source)
+	if !ok {
+		return nil, nil
+	}
+	entries := splitMetadata(body)
+	if !slices.ContainsFunc(entries, isMetadataEntry) {
+		return nil, nil
+	}
+	fields := map[string]any{}
+	for _, entry := range entries {
+		key, value, found := strings.Cut(entry, ":")
+		key = strings.TrimSpace(key)
+		if !found || !isMetadataKey(key) {
+			return nil, fmt.Errorf("invalid script metadata: expected key: value (got %q)", entry)
+		}
+		if _, dup := fields[key]; dup {
+			return nil, fmt.Errorf("invalid script metadata: duplicate key %q", key)
+		}
+		v, err := parseMetadataValue(strings.TrimSpace(value))
+		if err != nil {
+			return nil, fmt.Errorf("invalid script metadata: %s: %w", key, err)
+		}
+		fields[key] = v
+	}
+
+	m := &Metadata{Fields: fields}
+	for key, value := range fields {
+		switch key {
+		case "requires":
+			if s, ok := value.(string); ok {
+				m.Requires = []string{s}
+			} else {
+				m.Requires = value.([]string)
+			}
+		case "timeout":
+			s, ok := value.(string)
+			d, err := time.ParseDuration(s)
+			if !ok || err != nil || d <= 0 {
+				return nil, fmt.Errorf("invalid script metadata: timeout: expected a duration such as 30s (got %v)", value)
+			}
+			m.Timeout = d
+		case "max_steps":
+			s, ok := value.(string)
+			n, err := strconv.ParseInt(s, 10, 64)
+			if !ok || err != nil || n <= 0 {
+				return nil, fmt.Errorf("invalid script metadata: max_steps: expected a positive integer (got %v)", value)
+			}
+			m.MaxSteps = n
+		}
+	}
+	return m, nil
+}
+
+// apply checks the metadata's requirements against the environment and
+// tightens the resource limits to those it declares.
+func (m *Metadata) apply(o *options) error {
+	if m == nil {
+		return nil
+	}
+	var missing []string
+	for _, name := range m.Requires {
+		if _, ok := o.env[name]; ok || o.prelude.defines(name) || slices.Contains(o.globalNames, name) {
+			continue
+		}
+		missing = append(missing, strconv.Quote(name))
+	}
+	if len(missing) > 0 {
+		return fmt.Errorf("%w: %s not provided", ErrRequirementNotMet, strings.Join(missing, ", "))
+	}
+	if m.Timeout > 0 && (o.timeout == 0 || m.Timeout < o.timeout) {
+		o.timeout = m.Timeout
+	}
+	if m.MaxSteps > 0 && (o.maxSteps == 0 || m.MaxSteps < o.maxSteps) {
+		o.maxSteps = m.MaxSteps
+	}
+	return nil
+}
+
+// findMetadataBlock returns the text between the "/*---" and "---*/" lines
+// at the start of source.
+func findMetadataBlock(source string) (string, bool) {
+	if strings.HasPrefix(source, "#!") {
+		_, source, _ = strings.Cut(source, "\n")
+	}
+	source = strings.TrimLeft(source, " \t\r\n")
+	rest, ok := strings.CutPrefix(source, "/*---")
+	if !ok {
+		return "", false
+	}
+	first, rest, ok := strings.Cut(rest, "\n")
+	if !ok || strings.TrimSpace(first) != "" {
+		return "", false
+	}
+	end := 0
+	for line := range strings.Lines(rest) {
+		if strings.TrimSpace(line) == "---*/" {
+			return rest[:end], true
+		}
+		if strings.Contains(line, "*/") {
+			// The comment ends before a closing delimiter
+			return "", false
+		}
+		end += len(line)
+	}
+	return "", false
+}
+
+// splitMetadata splits s at commas and newlines, ignoring those within quotes
+// and brackets. Empty parts are dropped.
+func splitMetadata(s string) []string {
+	var parts []string
+	inString := false
+	depth, start := 0, 0
+	flush := func(end int) {
+		if part := strings.TrimSpace(s[start:end]); part != "" {
+			parts = append(parts, part)
+		}
+		start = end + 1
+	}
+	for i, r := range s {
+		switch {
+		case inString:
+			inString = r != '"' || s[i-1] == '\\'
+		case r == '"':
+			inString = true
+		case r == '[':
+			depth++
+		case r == ']':
+			depth--
+		case depth == 0 && (r == ',' || r == '\n'):
+			flush(i)
+		}
+	}
+	flush(len(s))
+	return parts
+}
+
+// isMetadataEntry reports whether entry has the form key: value.
+func isMetadataEntry(entry string) bool {
+	key, _, found := strings.Cut(entry, ":")
+	return found && isMetadataKey(strings.TrimSpace(key))
+}
+
+func isMetadataKey(key string) bool {
+	if key == "" {
+		return false
+	}
+	for _, r := range key {
+		if r != '_' && r != '-' && (r < 'a' || r > 'z') && (r < 'A' || r > 'Z') && (r < '0' || r > '9') {
+			return false
+		}
+	}
+	return true
+}
+
+// parseMetadataValue parses a list in brackets as a []string and anything
+// else as a string.
+func parseMetadataValue(value string) (any, error) {
+	if inner, ok := strings.CutPrefix(value, "["); ok {
+		inner, ok = strings.CutSuffix(inner, "]")
+		if !ok {
+			return nil, errors.New("missing closing ]")
+		}
+		items := []string{}
+		for _, item := range splitMetadata(inner) {
+			s, err := parseMetadataScalar(item)
+			if err != nil {
+				return nil, err
+			}
+			items = append(items, s)
+		}
+		return items, nil
+	}
+	return parseMetadataScalar(value)
+}
+
+func parseMetadataScalar(value string) (string, error) {
+	if value == "" {
+		return "", errors.New("missing value")
+	}
+	if value[0] == '"' {
+		s, err := strconv.Unquote(value)
+		if err != nil {
+			return "", fmt.Errorf("invalid string %s", value)
+		}
+		return s, nil
+	}
+	if strings.ContainsAny(value, "[]\"") {
+		return "", fmt.Errorf("unexpected value %s", value)
+	}
+	return value, nil
+}
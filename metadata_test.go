@@ -0,0 +1,119 @@
+package risor
+
+import (
+	"context"
+	"testing"
+	"time"
+
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+func TestParseMetadata(t *testing.T) {
+	meta, err := ParseMetadata(`#!/usr/bin/env risor
+
+/*---
+requires: ["http", db]
+timeout: 30s, max_steps: 1000
+description: "Deploys the app, then checks it"
+owner: ops team
+---*/
+http.get(url)
+`)
+	assert.Nil(t, err)
+	assert.Equal(t, meta.Requires, []string{"http", "db"})
+	assert.Equal(t, meta.Timeout, 30*time.Second)
+	assert.Equal(t, meta.MaxSteps, int64(1000))
+	assert.Equal(t, meta.Fields["description"], "Deploys the app, then checks it")
+	assert.Equal(t, meta.Fields["owner"], "ops team")
+
+	meta, err = ParseMetadata("/*---\nrequires: http\n---*/\n1")
+	assert.Nil(t, err)
+	assert.Equal(t, meta.Requires, []string{"http"})
+
+	meta, err = ParseMetadata("/*---\nrequires: [\n  \"http\",\n  \"db\",\n]\n---*/")
+	assert.Nil(t, err)
+	assert.Equal(t, meta.Requires, []string{"http", "db"})
+
+	// Comments that only look like a block aren't metadata
+	for _, source := range []string{
+		"1 + 1",
+		"/* just a comment */ 1",
+		"let x = 1\n/*---\ntimeout: 1s\n---*/",
+		"/*----------*/\n1",
+		"/*--- utils ---*/\n1",
+		"/*--- timeout: 1s ---*/\n1",
+		"/*---\nHelpers for the deploy scripts\n---*/\n1",
+		"/*---\ntimeout: 30s\n*/ 1",
+		"/*---\ntimeout: 30s",
+	} {
+		meta, err = ParseMetadata(source)
+		assert.Nil(t, err)
+		assert.Nil(t, meta)
+	}
+}
+
+func TestParseMetadataErrors(t *testing.T) {
+	tests := []struct {
+		source string
+		errMsg string
+	}{
+		{"requires: http\ntimeout", "expected key: value"},
+		{"timeout: soon", "timeout: expected a duration"},
+		{"max_steps: -1", "max_steps: expected a positive integer"},
+		{"requires: [http", "missing closing ]"},
+		{"timeout: [1s]", "timeout: expected a duration"},
+		{"a: 1, a: 2", `duplicate key "a"`},
+		{"a:", "missing value"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.source, func(t *testing.T) {
+			_, err := ParseMetadata("/*---\n" + tt.source + "\n---*/")
+			assert.NotNil(t, err)
+			assert.Contains(t, err.Error(), tt.errMsg)
+		})
+	}
+}
+
+func TestMetadataRequires(t *testing.T) {
+	ctx := context.Background()
+	source := "/*---\nrequires: [api, token]\n---*/\napi + token"
+
+	result, err := Eval(ctx, source, WithEnv(map[string]any{"api": "a", "token": "b"}))
+	assert.Nil(t, err)
+	assert.Equal(t, result, "ab")
+
+	_, err = Eval(ctx, "/*---\nrequires: [api, token]\n---*/\n1", WithEnv(map[string]any{"api": "a"}))
+	assert.ErrorIs(t, err, ErrRequirementNotMet)
+	assert.Contains(t, err.Error(), `"token" not provided`)
+
+	// Malformed metadata is a compile error
+	_, err = Compile(ctx, "/*---\ntimeout: soon\n---*/\n1")
+	assert.NotNil(t, err)
+
+	// Banners that start like a block still compile
+	result, err = Eval(ctx, "/*--- utils ---*/\n/*----------*/\n1")
+	assert.Nil(t, err)
+	assert.Equal(t, result, int64(1))
+}
+
+func TestMetadataLimits(t *testing.T) {
+	ctx := context.Background()
+	loop := `let sum = 0; list(range(100000)).each(function(i) { sum = sum + i }); sum`
+
+	_, err := Eval(ctx, "/*---\nmax_steps: 5000\n---*/\n"+loop, WithEnv(Builtins()))
+	assert.ErrorIs(t, err, ErrStepLimitExceeded)
+
+	// Scripts can lower the host's limits but not raise them
+	_, err = Eval(ctx, "/*---\nmax_steps: 100000000\n---*/\n"+loop,
+		WithEnv(Builtins()), WithMaxSteps(5000))
+	assert.ErrorIs(t, err, ErrStepLimitExceeded)
+
+	_, err = Eval(ctx, "/*---\ntimeout: 1h\n---*/\n"+loop, WithEnv(Builtins()))
+	assert.Nil(t, err)
+
+	o := collectOptions(WithTimeout(time.Second))
+	assert.Nil(t, (&Metadata{Timeout: time.Minute}).apply(o))
+	assert.Equal(t, o.timeout, time.Second)
+	assert.Nil(t, (&Metadata{Timeout: time.Millisecond}).apply(o))
+	assert.Equal(t, o.timeout, time.Millisecond)
+}
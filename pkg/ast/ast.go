@@ -17,6 +17,11 @@ type Node interface {
 	String() string
 }
 
+// SpanOf returns the byte range of the source text covered by a node.
+func SpanOf(node Node) token.Span {
+	return token.Span{Start: node.Pos().Offset, End: node.End().Offset}
+}
+
 // Stmt represents a statement node. Statements cause side effects but
 // do not evaluate to a value.
 type Stmt interface {
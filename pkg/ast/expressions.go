@@ -24,7 +24,7 @@ func (x *Ident) String() string { return x.Name }
 // Examples include "!false" and "-x".
 type Prefix struct {
 	OpPos token.Position // position of operator
-	Op    string         // operator: "!", "-", "not"
+	Op    string         // operator: "!", "-", "~", "not"
 	X     Expr           // operand
 }
 
@@ -273,10 +273,11 @@ func (x *ObjectCall) String() string {
 
 // Index is an expression node that describes indexing on an object.
 type Index struct {
-	X      Expr           // object expression
-	Lbrack token.Position // position of "["
-	Index  Expr           // index expression
-	Rbrack token.Position // position of "]"
+	X        Expr           // object expression
+	Lbrack   token.Position // position of "["
+	Index    Expr           // index expression
+	Rbrack   token.Position // position of "]"
+	Optional bool           // true if optional chaining (?.[)
 }
 
 func (x *Index) exprNode() {}
@@ -292,6 +293,9 @@ func (x *Index) End() token.Position { return x.Rbrack.Advance(1) }
 func (x *Index) String() string {
 	var out bytes.Buffer
 	out.WriteString(x.X.String())
+	if x.Optional {
+		out.WriteString("?.")
+	}
 	out.WriteString("[")
 	out.WriteString(x.Index.String())
 	out.WriteString("]")
@@ -304,6 +308,7 @@ type Slice struct {
 	Lbrack token.Position // position of "["
 	Low    Expr           // begin of slice range; nil if omitted
 	High   Expr           // end of slice range; nil if omitted
+	Step   Expr           // step between items; nil if omitted
 	Rbrack token.Position // position of "]"
 }
 
@@ -328,6 +333,10 @@ func (x *Slice) String() string {
 	if x.High != nil {
 		out.WriteString(x.High.String())
 	}
+	if x.Step != nil {
+		out.WriteString(":")
+		out.WriteString(x.Step.String())
+	}
 	out.WriteString("]")
 	return out.String()
 }
@@ -484,3 +493,51 @@ func (x *Match) String() string {
 	out.WriteString(" }")
 	return out.String()
 }
+
+// Quote is an expression node that holds a block of code as a value, rather
+// than running it. Calls to unquote within the block are evaluated when the
+// quote is, and their results are spliced into the code.
+type Quote struct {
+	Quote token.Position // position of "quote" keyword
+	Body  *Block         // quoted code
+}
+
+func (x *Quote) exprNode() {}
+
+func (x *Quote) Pos() token.Position { return x.Quote }
+func (x *Quote) End() token.Position {
+	if x.Body != nil {
+		return x.Body.End()
+	}
+	return x.Quote.Advance(5) // len("quote")
+}
+
+func (x *Quote) String() string {
+	var out bytes.Buffer
+	out.WriteString("quote { ")
+	out.WriteString(x.Body.String())
+	out.WriteString(" }")
+	return out.String()
+}
+
+// Unquotes returns the unquote calls in the quoted code, in source order.
+// Calls within nested quotes belong to those quotes and aren't included.
+func (x *Quote) Unquotes() []*Call {
+	var calls []*Call
+	if x.Body == nil {
+		return nil
+	}
+	Inspect(x.Body, func(node Node) bool {
+		switch n := node.(type) {
+		case *Quote:
+			return false
+		case *Call:
+			if ident, ok := n.Fun.(*Ident); ok && ident.Name == "unquote" {
+				calls = append(calls, n)
+				return false
+			}
+		}
+		return true
+	})
+	return calls
+}
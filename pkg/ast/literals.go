@@ -39,13 +39,19 @@ func (x *Float) String() string { return x.Literal }
 
 // Nil is an expression node that holds a nil literal.
 type Nil struct {
-	NilPos token.Position // position of "nil" keyword
+	NilPos  token.Position // position of "nil" or "null" keyword
+	Literal string         // "nil" or "null"
 }
 
 func (x *Nil) exprNode() {}
 
 func (x *Nil) Pos() token.Position { return x.NilPos }
-func (x *Nil) End() token.Position { return x.NilPos.Advance(4) } // len("null")
+func (x *Nil) End() token.Position {
+	if x.Literal == "" {
+		return x.NilPos.Advance(4) // len("null")
+	}
+	return x.NilPos.Advance(len(x.Literal))
+}
 
 func (x *Nil) String() string { return "null" }
 
@@ -87,7 +93,8 @@ func (x *DefaultValue) String() string {
 // String is an expression node that holds a string literal.
 type String struct {
 	ValuePos token.Position // position of opening quote
-	Literal  string         // the raw literal including quotes
+	ValueEnd token.Position // position just after the closing quote; zero if unknown
+	Literal  string         // the literal text
 	Value    string         // the unquoted string value
 	Template *tmpl.Template // template if this is a template string
 	Exprs    []Expr         // embedded expressions for templates
@@ -96,7 +103,12 @@ type String struct {
 func (x *String) exprNode() {}
 
 func (x *String) Pos() token.Position { return x.ValuePos }
-func (x *String) End() token.Position { return x.ValuePos.Advance(len(x.Literal)) }
+func (x *String) End() token.Position {
+	if x.ValueEnd.IsValid() {
+		return x.ValueEnd
+	}
+	return x.ValuePos.Advance(len(x.Literal))
+}
 
 func (x *String) String() string { return fmt.Sprintf("%q", x.Value) }
 
@@ -128,14 +140,18 @@ func (x *ObjectDestructureParam) funcParam() {}
 func (x *ObjectDestructureParam) Pos() token.Position { return x.Lbrace }
 func (x *ObjectDestructureParam) End() token.Position { return x.Rbrace.Advance(1) }
 
-// ParamNames returns all variable names introduced by this destructuring parameter.
+// ParamNames returns all variable names introduced by this destructuring
+// parameter, including names bound by nested patterns.
 func (x *ObjectDestructureParam) ParamNames() []string {
-	names := make([]string, len(x.Bindings))
-	for i, b := range x.Bindings {
-		if b.Alias != "" {
-			names[i] = b.Alias
-		} else {
-			names[i] = b.Key
+	names := make([]string, 0, len(x.Bindings))
+	for _, b := range x.Bindings {
+		switch {
+		case b.Pattern != nil:
+			names = append(names, b.Pattern.ParamNames()...)
+		case b.Alias != "":
+			names = append(names, b.Alias)
+		default:
+			names = append(names, b.Key)
 		}
 	}
 	return names
@@ -149,7 +165,10 @@ func (x *ObjectDestructureParam) String() string {
 			out.WriteString(", ")
 		}
 		out.WriteString(b.Key)
-		if b.Alias != "" && b.Alias != b.Key {
+		if b.Pattern != nil {
+			out.WriteString(": ")
+			out.WriteString(b.Pattern.String())
+		} else if b.Alias != "" && b.Alias != b.Key {
 			out.WriteString(": ")
 			out.WriteString(b.Alias)
 		}
@@ -175,11 +194,16 @@ func (x *ArrayDestructureParam) funcParam() {}
 func (x *ArrayDestructureParam) Pos() token.Position { return x.Lbrack }
 func (x *ArrayDestructureParam) End() token.Position { return x.Rbrack.Advance(1) }
 
-// ParamNames returns all variable names introduced by this destructuring parameter.
+// ParamNames returns all variable names introduced by this destructuring
+// parameter, including names bound by nested patterns.
 func (x *ArrayDestructureParam) ParamNames() []string {
-	names := make([]string, len(x.Elements))
-	for i, e := range x.Elements {
-		names[i] = e.Name.Name
+	names := make([]string, 0, len(x.Elements))
+	for _, e := range x.Elements {
+		if e.Pattern != nil {
+			names = append(names, e.Pattern.ParamNames()...)
+		} else {
+			names = append(names, e.Name.Name)
+		}
 	}
 	return names
 }
@@ -191,7 +215,11 @@ func (x *ArrayDestructureParam) String() string {
 		if i > 0 {
 			out.WriteString(", ")
 		}
-		out.WriteString(e.Name.String())
+		if e.Pattern != nil {
+			out.WriteString(e.Pattern.String())
+		} else {
+			out.WriteString(e.Name.String())
+		}
 		if e.Default != nil {
 			out.WriteString(" = ")
 			out.WriteString(e.Default.String())
@@ -268,11 +296,34 @@ func (x *List) String() string {
 	return out.String()
 }
 
+// Tuple is an expression node that builds a tuple from a comma-separated
+// list of expressions. Tuples are only produced by multi-value return
+// statements: return a, b
+type Tuple struct {
+	Items []Expr // tuple elements; always at least two
+}
+
+func (x *Tuple) exprNode() {}
+
+func (x *Tuple) Pos() token.Position { return x.Items[0].Pos() }
+func (x *Tuple) End() token.Position { return x.Items[len(x.Items)-1].End() }
+
+func (x *Tuple) String() string {
+	elements := make([]string, 0, len(x.Items))
+	for _, el := range x.Items {
+		elements = append(elements, el.String())
+	}
+	return strings.Join(elements, ", ")
+}
+
 // MapItem represents a single key-value pair in a map literal.
 // For spread expressions (...obj), Key is nil and Value is the spread expression.
+// Computed is true when the key was written as [expr], in which case the key
+// expression is evaluated at runtime and must produce a string.
 type MapItem struct {
-	Key   Expr // nil for spread expressions
-	Value Expr
+	Key      Expr // nil for spread expressions
+	Value    Expr
+	Computed bool // key written as [expr]
 }
 
 // Map is an expression node that builds a map data structure.
@@ -303,6 +354,8 @@ func (x *Map) String() string {
 	for _, item := range x.Items {
 		if item.Key == nil {
 			pairs = append(pairs, "..."+item.Value.String())
+		} else if item.Computed {
+			pairs = append(pairs, "["+item.Key.String()+"]:"+item.Value.String())
 		} else {
 			pairs = append(pairs, item.Key.String()+":"+item.Value.String())
 		}
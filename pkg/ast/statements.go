@@ -2,6 +2,7 @@ package ast
 
 import (
 	"bytes"
+	"path"
 	"strings"
 
 	"github.com/deepnoodle-ai/risor/v2/internal/token"
@@ -75,9 +76,10 @@ func (x *MultiVar) String() string {
 // It has a key (property name to extract), an optional alias (local variable name),
 // and an optional default value.
 type DestructureBinding struct {
-	Key     string // property name to extract from object
-	Alias   string // local variable name (empty means use Key as name)
-	Default Expr   // default value if property is nil (optional)
+	Key     string    // property name to extract from object
+	Alias   string    // local variable name (empty means use Key as name)
+	Default Expr      // default value if property is nil (optional)
+	Pattern FuncParam // nested pattern, e.g. {a: {b}} (parameters only; optional)
 }
 
 // ObjectDestructure is a statement that extracts properties from an object.
@@ -124,8 +126,9 @@ func (x *ObjectDestructure) String() string {
 
 // ArrayDestructureElement represents a single element binding in array destructuring.
 type ArrayDestructureElement struct {
-	Name    *Ident // variable name to bind
-	Default Expr   // default value if element is nil (optional)
+	Name    *Ident    // variable name to bind; nil when Pattern is set
+	Default Expr      // default value if element is nil (optional)
+	Pattern FuncParam // nested pattern, e.g. [a, [b, c]] (parameters only; optional)
 }
 
 // ArrayDestructure is a statement that extracts elements from an array.
@@ -255,8 +258,9 @@ func (x *Block) String() string {
 
 // Assign is a statement node used to describe a variable assignment.
 type Assign struct {
-	Name  *Ident         // variable name; nil for index assignment
-	Index *Index         // index expression; nil for simple assignment
+	Name  *Ident         // variable name; nil for index or slice assignment
+	Index *Index         // index expression; nil unless assigning to an index
+	Slice *Slice         // slice expression; nil unless assigning to a slice
 	OpPos token.Position // position of operator
 	Op    string         // assignment operator: "=", "+=", "-=", etc.
 	Value Expr           // value to assign
@@ -271,15 +275,21 @@ func (x *Assign) Pos() token.Position {
 	if x.Index != nil {
 		return x.Index.Pos()
 	}
+	if x.Slice != nil {
+		return x.Slice.Pos()
+	}
 	return x.OpPos
 }
 func (x *Assign) End() token.Position { return x.Value.End() }
 
 func (x *Assign) String() string {
 	var out bytes.Buffer
-	if x.Index != nil {
+	switch {
+	case x.Index != nil:
 		out.WriteString(x.Index.String())
-	} else {
+	case x.Slice != nil:
+		out.WriteString(x.Slice.String())
+	default:
 		out.WriteString(x.Name.Name)
 	}
 	out.WriteString(" ")
@@ -289,6 +299,39 @@ func (x *Assign) String() string {
 	return out.String()
 }
 
+// MultiAssign is a statement node that assigns to several variables at once.
+// With Unpack set, as in "a, b = b, a", the value is unpacked into the
+// variables like MultiVar. Otherwise, as in "x = y = 0", every variable is
+// assigned the same value. Either way the value is evaluated before any
+// variable is assigned.
+type MultiAssign struct {
+	Names  []*Ident       // variable names
+	OpPos  token.Position // position of the first "="
+	Value  Expr           // value to assign
+	Unpack bool           // true for "a, b = value"; false for "a = b = value"
+}
+
+func (x *MultiAssign) stmtNode() {}
+
+func (x *MultiAssign) Pos() token.Position { return x.Names[0].Pos() }
+func (x *MultiAssign) End() token.Position { return x.Value.End() }
+
+func (x *MultiAssign) String() string {
+	var out bytes.Buffer
+	names := make([]string, 0, len(x.Names))
+	for _, name := range x.Names {
+		names = append(names, name.Name)
+	}
+	if x.Unpack {
+		out.WriteString(strings.Join(names, ", "))
+	} else {
+		out.WriteString(strings.Join(names, " = "))
+	}
+	out.WriteString(" = ")
+	out.WriteString(x.Value.String())
+	return out.String()
+}
+
 // Postfix is a statement node that describes a postfix expression like "x++".
 // The operand X can be an Ident, Index, or GetAttr expression.
 type Postfix struct {
@@ -386,6 +429,39 @@ func (x *Try) String() string {
 	return out.String()
 }
 
+// With represents a with statement, which binds a resource to a variable for
+// the duration of a block and closes it when the block exits.
+type With struct {
+	With  token.Position // position of "with" keyword
+	Value Expr           // resource expression
+	Name  *Ident         // variable given with "as"
+	Body  *Block         // with block
+}
+
+func (x *With) stmtNode() {}
+func (x *With) exprNode() {} // with is also an expression, like try
+
+func (x *With) Pos() token.Position { return x.With }
+
+func (x *With) End() token.Position {
+	if x.Body != nil {
+		return x.Body.End()
+	}
+	return x.Name.End()
+}
+
+func (x *With) String() string {
+	var out bytes.Buffer
+	out.WriteString("with ")
+	out.WriteString(x.Value.String())
+	out.WriteString(" as ")
+	out.WriteString(x.Name.String())
+	out.WriteString(" { ")
+	out.WriteString(x.Body.String())
+	out.WriteString(" }")
+	return out.String()
+}
+
 // Throw represents a throw statement.
 type Throw struct {
 	Throw token.Position // position of "throw" keyword
@@ -410,3 +486,128 @@ func (x *Throw) String() string {
 	}
 	return out.String()
 }
+
+// Export marks a top-level declaration as part of a script's public surface.
+// Decl is a *Var, *MultiVar, *Const, or named *Func.
+type Export struct {
+	Export token.Position // position of "export" keyword
+	Decl   Stmt           // exported declaration
+}
+
+func (x *Export) stmtNode() {}
+
+func (x *Export) Pos() token.Position { return x.Export }
+func (x *Export) End() token.Position { return x.Decl.End() }
+
+func (x *Export) String() string {
+	return "export " + x.Decl.String()
+}
+
+// Names returns the names of the variables, constants, or function declared
+// by the exported declaration.
+func (x *Export) Names() []string {
+	switch decl := x.Decl.(type) {
+	case *Var:
+		return []string{decl.Name.Name}
+	case *MultiVar:
+		names := make([]string, 0, len(decl.Names))
+		for _, name := range decl.Names {
+			names = append(names, name.Name)
+		}
+		return names
+	case *Const:
+		return []string{decl.Name.Name}
+	case *Func:
+		if decl.Name != nil {
+			return []string{decl.Name.Name}
+		}
+	}
+	return nil
+}
+
+// Import binds a module provided by the host's importer to a variable. The
+// variable is named by the alias, if present, and otherwise by the last
+// element of the path.
+type Import struct {
+	Import token.Position // position of "import" keyword
+	Path   *String        // module path
+	Alias  *Ident         // name given with "as"; nil if absent
+}
+
+func (x *Import) stmtNode() {}
+
+func (x *Import) Pos() token.Position { return x.Import }
+func (x *Import) End() token.Position {
+	if x.Alias != nil {
+		return x.Alias.End()
+	}
+	return x.Path.End()
+}
+
+// Name returns the name of the variable the module is bound to.
+func (x *Import) Name() string {
+	if x.Alias != nil {
+		return x.Alias.Name
+	}
+	return ImportName(x.Path.Value)
+}
+
+func (x *Import) String() string {
+	var out bytes.Buffer
+	out.WriteString("import ")
+	out.WriteString(x.Path.String())
+	if x.Alias != nil {
+		out.WriteString(" as ")
+		out.WriteString(x.Alias.Name)
+	}
+	return out.String()
+}
+
+// ImportName returns the variable name an import path binds to when no alias
+// is given: the last element of the path without its ".risor" extension.
+// For example, "./lib/helpers" binds to "helpers".
+func ImportName(importPath string) string {
+	return strings.TrimSuffix(path.Base(importPath), ".risor")
+}
+
+// Assert represents an assert statement.
+type Assert struct {
+	Assert  token.Position // position of "assert" keyword
+	Cond    Expr           // condition that must be truthy
+	Message Expr           // optional message; nil if absent
+}
+
+func (x *Assert) stmtNode() {}
+
+func (x *Assert) Pos() token.Position { return x.Assert }
+func (x *Assert) End() token.Position {
+	if x.Message != nil {
+		return x.Message.End()
+	}
+	return x.Cond.End()
+}
+
+func (x *Assert) String() string {
+	var out bytes.Buffer
+	out.WriteString("assert ")
+	out.WriteString(x.Cond.String())
+	if x.Message != nil {
+		out.WriteString(", ")
+		out.WriteString(x.Message.String())
+	}
+	return out.String()
+}
+
+// Del represents a del statement, which removes an item from a container, as
+// in "del m["key"]".
+type Del struct {
+	Del   token.Position // position of "del" keyword
+	Index *Index         // item to remove
+}
+
+func (x *Del) stmtNode() {}
+
+func (x *Del) Pos() token.Position { return x.Del }
+func (x *Del) End() token.Position { return x.Index.End() }
+
+func (x *Del) String() string { return "del " + x.Index.String() }
@@ -73,6 +73,16 @@ func Walk(v Visitor, node Node) {
 		if n.Index != nil {
 			Walk(v, n.Index)
 		}
+		if n.Slice != nil {
+			Walk(v, n.Slice)
+		}
+		if n.Value != nil {
+			Walk(v, n.Value)
+		}
+	case *MultiAssign:
+		for _, name := range n.Names {
+			Walk(v, name)
+		}
 		if n.Value != nil {
 			Walk(v, n.Value)
 		}
@@ -96,10 +106,34 @@ func Walk(v Visitor, node Node) {
 		if n.FinallyBlock != nil {
 			Walk(v, n.FinallyBlock)
 		}
+	case *With:
+		if n.Value != nil {
+			Walk(v, n.Value)
+		}
+		if n.Name != nil {
+			Walk(v, n.Name)
+		}
+		if n.Body != nil {
+			Walk(v, n.Body)
+		}
 	case *Throw:
 		if n.Value != nil {
 			Walk(v, n.Value)
 		}
+	case *Export:
+		Walk(v, n.Decl)
+	case *Import:
+		Walk(v, n.Path)
+		if n.Alias != nil {
+			Walk(v, n.Alias)
+		}
+	case *Assert:
+		Walk(v, n.Cond)
+		if n.Message != nil {
+			Walk(v, n.Message)
+		}
+	case *Del:
+		Walk(v, n.Index)
 	case *Postfix:
 		if n.X != nil {
 			Walk(v, n.X)
@@ -191,6 +225,9 @@ func Walk(v Visitor, node Node) {
 		if n.High != nil {
 			Walk(v, n.High)
 		}
+		if n.Step != nil {
+			Walk(v, n.Step)
+		}
 	case *In:
 		if n.X != nil {
 			Walk(v, n.X)
@@ -220,6 +257,10 @@ func Walk(v Visitor, node Node) {
 			Walk(v, n.Default.Pattern)
 			Walk(v, n.Default.Result)
 		}
+	case *Quote:
+		if n.Body != nil {
+			Walk(v, n.Body)
+		}
 	case *LiteralPattern:
 		Walk(v, n.Value)
 	case *WildcardPattern:
@@ -228,6 +269,10 @@ func Walk(v Visitor, node Node) {
 		for _, item := range n.Items {
 			Walk(v, item)
 		}
+	case *Tuple:
+		for _, item := range n.Items {
+			Walk(v, item)
+		}
 	case *Map:
 		for _, pair := range n.Items {
 			if pair.Key != nil {
@@ -257,6 +302,9 @@ func Walk(v Visitor, node Node) {
 	// Destructuring parameter types
 	case *ObjectDestructureParam:
 		for _, b := range n.Bindings {
+			if b.Pattern != nil {
+				Walk(v, b.Pattern)
+			}
 			if b.Default != nil {
 				Walk(v, b.Default)
 			}
@@ -266,6 +314,9 @@ func Walk(v Visitor, node Node) {
 			if e.Name != nil {
 				Walk(v, e.Name)
 			}
+			if e.Pattern != nil {
+				Walk(v, e.Pattern)
+			}
 			if e.Default != nil {
 				Walk(v, e.Default)
 			}
@@ -363,6 +414,18 @@ func Preorder(root Node) iter.Seq[Node] {
 				if node.Index != nil && !visit(node.Index) {
 					return false
 				}
+				if node.Slice != nil && !visit(node.Slice) {
+					return false
+				}
+				if node.Value != nil && !visit(node.Value) {
+					return false
+				}
+			case *MultiAssign:
+				for _, name := range node.Names {
+					if !visit(name) {
+						return false
+					}
+				}
 				if node.Value != nil && !visit(node.Value) {
 					return false
 				}
@@ -386,10 +449,42 @@ func Preorder(root Node) iter.Seq[Node] {
 				if node.FinallyBlock != nil && !visit(node.FinallyBlock) {
 					return false
 				}
+			case *With:
+				if node.Value != nil && !visit(node.Value) {
+					return false
+				}
+				if node.Name != nil && !visit(node.Name) {
+					return false
+				}
+				if node.Body != nil && !visit(node.Body) {
+					return false
+				}
 			case *Throw:
 				if node.Value != nil && !visit(node.Value) {
 					return false
 				}
+			case *Export:
+				if !visit(node.Decl) {
+					return false
+				}
+			case *Import:
+				if !visit(node.Path) {
+					return false
+				}
+				if node.Alias != nil && !visit(node.Alias) {
+					return false
+				}
+			case *Assert:
+				if !visit(node.Cond) {
+					return false
+				}
+				if node.Message != nil && !visit(node.Message) {
+					return false
+				}
+			case *Del:
+				if !visit(node.Index) {
+					return false
+				}
 			case *Postfix:
 				if node.X != nil && !visit(node.X) {
 					return false
@@ -466,6 +561,9 @@ func Preorder(root Node) iter.Seq[Node] {
 				if node.High != nil && !visit(node.High) {
 					return false
 				}
+				if node.Step != nil && !visit(node.Step) {
+					return false
+				}
 			case *In:
 				if node.X != nil && !visit(node.X) {
 					return false
@@ -503,6 +601,10 @@ func Preorder(root Node) iter.Seq[Node] {
 						return false
 					}
 				}
+			case *Quote:
+				if node.Body != nil && !visit(node.Body) {
+					return false
+				}
 			case *LiteralPattern:
 				if node.Value != nil && !visit(node.Value) {
 					return false
@@ -515,6 +617,12 @@ func Preorder(root Node) iter.Seq[Node] {
 						return false
 					}
 				}
+			case *Tuple:
+				for _, item := range node.Items {
+					if !visit(item) {
+						return false
+					}
+				}
 			case *Map:
 				for _, pair := range node.Items {
 					if pair.Key != nil && !visit(pair.Key) {
@@ -554,6 +662,9 @@ func Preorder(root Node) iter.Seq[Node] {
 			// Destructuring parameter types
 			case *ObjectDestructureParam:
 				for _, b := range node.Bindings {
+					if b.Pattern != nil && !visit(b.Pattern) {
+						return false
+					}
 					if b.Default != nil && !visit(b.Default) {
 						return false
 					}
@@ -563,6 +674,9 @@ func Preorder(root Node) iter.Seq[Node] {
 					if e.Name != nil && !visit(e.Name) {
 						return false
 					}
+					if e.Pattern != nil && !visit(e.Pattern) {
+						return false
+					}
 					if e.Default != nil && !visit(e.Default) {
 						return false
 					}
@@ -0,0 +1,200 @@
+package builtins
+
+import (
+	"context"
+	"fmt"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+	"github.com/deepnoodle-ai/risor/v2/pkg/op"
+)
+
+// The aggregation builtins summarize the values of a list, iterator, or other
+// enumerable. Most accept an optional key function, which is called with each
+// value and whose result is used in place of the value.
+
+// Sum returns the sum of the values, or 0 if there are none. Ints stay ints
+// and adding a float or decimal gives a float or decimal, as with +.
+// Example: sum(orders, o => o.total)
+func Sum(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) < 1 || len(args) > 2 {
+		return nil, fmt.Errorf("sum: expected 1 or 2 arguments, got %d", len(args))
+	}
+	result, _, err := total(ctx, "sum", args)
+	return result, err
+}
+
+// Avg returns the mean of the values as a float, or as a decimal when summing
+// them gives a decimal.
+// Example: avg([1, 2, 4])
+func Avg(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) < 1 || len(args) > 2 {
+		return nil, fmt.Errorf("avg: expected 1 or 2 arguments, got %d", len(args))
+	}
+	result, count, err := total(ctx, "avg", args)
+	if err != nil {
+		return nil, err
+	}
+	if count == 0 {
+		return nil, object.ValueErrorf("avg() of an empty sequence")
+	}
+	if i, ok := result.(*object.Int); ok {
+		return object.NewFloat(float64(i.Value()) / float64(count)), nil
+	}
+	return object.BinaryOp(op.Divide, result, object.NewInt(count))
+}
+
+// Min returns the smallest value, ordered as by sorted. With a key function,
+// it returns the value with the smallest key.
+// Example: min(users, u => u.age)
+func Min(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) < 1 || len(args) > 2 {
+		return nil, fmt.Errorf("min: expected 1 or 2 arguments, got %d", len(args))
+	}
+	return extreme(ctx, "min", args, -1)
+}
+
+// Max returns the largest value, ordered as by sorted. With a key function,
+// it returns the value with the largest key.
+// Example: max(users, u => u.age)
+func Max(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) < 1 || len(args) > 2 {
+		return nil, fmt.Errorf("max: expected 1 or 2 arguments, got %d", len(args))
+	}
+	return extreme(ctx, "max", args, 1)
+}
+
+// CountBy returns a map from each key to the number of values with that key.
+// Without a key function the values themselves must be strings.
+// Example: count_by(orders, o => o.status)
+func CountBy(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) < 1 || len(args) > 2 {
+		return nil, fmt.Errorf("count_by: expected 1 or 2 arguments, got %d", len(args))
+	}
+	counts := map[string]int64{}
+	err := eachKey(ctx, "count_by", args, func(key string, value object.Object) {
+		counts[key]++
+	})
+	if err != nil {
+		return nil, err
+	}
+	result := make(map[string]object.Object, len(counts))
+	for key, count := range counts {
+		result[key] = object.NewInt(count)
+	}
+	return object.NewMap(result), nil
+}
+
+// GroupBy returns a map from each key to a list of the values with that key,
+// in the order they were produced.
+// Example: group_by(orders, o => o.status)
+func GroupBy(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) != 2 {
+		return nil, fmt.Errorf("group_by: expected 2 arguments, got %d", len(args))
+	}
+	groups := map[string][]object.Object{}
+	err := eachKey(ctx, "group_by", args, func(key string, value object.Object) {
+		groups[key] = append(groups[key], value)
+	})
+	if err != nil {
+		return nil, err
+	}
+	result := make(map[string]object.Object, len(groups))
+	for key, items := range groups {
+		result[key] = object.NewList(items)
+	}
+	return object.NewMap(result), nil
+}
+
+// total adds up the values, or their keys, and counts them.
+func total(ctx context.Context, name string, args []object.Object) (object.Object, int64, error) {
+	var result object.Object = object.NewInt(0)
+	var count int64
+	err := eachValue(ctx, name, args, func(_, key object.Object) error {
+		sum, err := object.BinaryOp(op.Add, result, key)
+		if err != nil {
+			return err
+		}
+		result = sum
+		count++
+		return nil
+	})
+	if err != nil {
+		return nil, 0, err
+	}
+	return result, count, nil
+}
+
+// extreme returns the value whose key compares as want, -1 or 1, against the
+// keys of all other values. The first such value wins ties.
+func extreme(ctx context.Context, name string, args []object.Object, want int) (object.Object, error) {
+	var best, bestKey object.Object
+	err := eachValue(ctx, name, args, func(value, key object.Object) error {
+		cmp, ok := key.(object.Comparable)
+		if !ok {
+			return object.TypeErrorf("%s() encountered a non-comparable item (%s)", name, key.Type())
+		}
+		if best != nil {
+			result, err := cmp.Compare(bestKey)
+			if err != nil {
+				return err
+			}
+			if result != want {
+				return nil
+			}
+		}
+		best, bestKey = value, key
+		return nil
+	})
+	if err != nil {
+		return nil, err
+	}
+	if best == nil {
+		return nil, object.ValueErrorf("%s() of an empty sequence", name)
+	}
+	return best, nil
+}
+
+// eachKey calls fn with the string key of each value, which is the value
+// itself when no key function is given.
+func eachKey(ctx context.Context, name string, args []object.Object, fn func(key string, value object.Object)) error {
+	return eachValue(ctx, name, args, func(value, key object.Object) error {
+		s, ok := key.(*object.String)
+		if !ok {
+			if len(args) == 2 {
+				return object.TypeErrorf("%s() key function must return a string (%s given)", name, key.Type())
+			}
+			return object.TypeErrorf("%s() values must be strings without a key function (%s given)",
+				name, key.Type())
+		}
+		fn(s.Value(), value)
+		return nil
+	})
+}
+
+// eachValue calls fn with each value of args[0] and its key, which is the
+// result of the key function args[1] if there is one, and otherwise the value.
+func eachValue(ctx context.Context, name string, args []object.Object, fn func(value, key object.Object) error) error {
+	items, err := enumerableArg(name, args[0])
+	if err != nil {
+		return err
+	}
+	var keyFn object.Callable
+	if len(args) == 2 {
+		var ok bool
+		if keyFn, ok = args[1].(object.Callable); !ok {
+			return object.TypeErrorf("%s() expected a key function (%s given)", name, args[1].Type())
+		}
+	}
+	for value := range values(ctx, items) {
+		key := value
+		if keyFn != nil {
+			if key, err = keyFn.Call(ctx, value); err != nil {
+				return err
+			}
+		}
+		if err := fn(value, key); err != nil {
+			return err
+		}
+	}
+	return ctx.Err()
+}
@@ -0,0 +1,75 @@
+package builtins
+
+import (
+	"context"
+	"testing"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+// lenFn is a key function that returns the length of a string.
+var lenFn = object.NewBuiltin("len", Len)
+
+// typeFn is a key function that returns the type name of a value.
+var typeFn = object.NewBuiltin("type", Type)
+
+func TestAggregates(t *testing.T) {
+	ctx := context.Background()
+	ints := object.NewList([]object.Object{object.NewInt(3), object.NewInt(1), object.NewInt(4)})
+	words := object.NewStringList([]string{"kiwi", "fig", "banana", "fig"})
+	tests := []struct {
+		fn       object.BuiltinFunction
+		args     []object.Object
+		expected string
+	}{
+		{Sum, []object.Object{ints}, "8"},
+		{Sum, []object.Object{object.NewList(nil)}, "0"},
+		{Sum, []object.Object{object.NewList([]object.Object{object.NewInt(1), object.NewFloat(0.5)})}, "1.5"},
+		{Sum, []object.Object{words, lenFn}, "16"},
+		{Sum, []object.Object{object.NewRange(0, 5, 1)}, "10"},
+		{Avg, []object.Object{ints}, "2.6666666666666665"},
+		{Avg, []object.Object{words, lenFn}, "4"},
+		{Min, []object.Object{ints}, "1"},
+		{Max, []object.Object{ints}, "4"},
+		{Min, []object.Object{words}, `"banana"`},
+		{Min, []object.Object{words, lenFn}, `"fig"`},
+		{Max, []object.Object{words, lenFn}, `"banana"`},
+		{CountBy, []object.Object{words}, `{"banana": 1, "fig": 2, "kiwi": 1}`},
+		{CountBy, []object.Object{ints, typeFn}, `{"int": 3}`},
+		{GroupBy, []object.Object{words, object.NewBuiltin("first", func(ctx context.Context, args ...object.Object) (object.Object, error) {
+			return args[0].(*object.String).GetItem(object.NewInt(0))
+		})}, `{"b": ["banana"], "f": ["fig", "fig"], "k": ["kiwi"]}`},
+	}
+	for _, tt := range tests {
+		result, err := tt.fn(ctx, tt.args...)
+		assert.Nil(t, err)
+		assert.Equal(t, result.Inspect(), tt.expected)
+	}
+}
+
+func TestAggregateErrors(t *testing.T) {
+	ctx := context.Background()
+	empty := object.NewList(nil)
+	ints := object.NewList([]object.Object{object.NewInt(1), object.NewInt(2)})
+	tests := []struct {
+		fn       object.BuiltinFunction
+		args     []object.Object
+		expected string
+	}{
+		{Avg, []object.Object{empty}, "value error: avg() of an empty sequence"},
+		{Min, []object.Object{empty}, "value error: min() of an empty sequence"},
+		{Max, []object.Object{object.NewInt(1)}, "type error: max() expected an enumerable (int given)"},
+		{Max, []object.Object{object.NewList([]object.Object{object.NewInt(1), object.NewString("a")})},
+			"type error: unable to compare string and int"},
+		{Sum, []object.Object{ints, object.NewInt(1)}, "type error: sum() expected a key function (int given)"},
+		{CountBy, []object.Object{ints}, "type error: count_by() values must be strings without a key function (int given)"},
+		{GroupBy, []object.Object{ints, lenFn}, "type error: len() unsupported argument (int given)"},
+		{GroupBy, []object.Object{ints, typeFn, typeFn}, "group_by: expected 2 arguments, got 3"},
+	}
+	for _, tt := range tests {
+		_, err := tt.fn(ctx, tt.args...)
+		assert.NotNil(t, err)
+		assert.Equal(t, err.Error(), tt.expected)
+	}
+}
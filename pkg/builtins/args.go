@@ -0,0 +1,104 @@
+package builtins
+
+import (
+	"context"
+	"fmt"
+	"strings"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+)
+
+// CheckArgs returns an error if args don't match the arguments the entry
+// declares. Args gives the number of arguments: names ending in "?" are
+// optional and a name ending in "..." accepts any number of arguments.
+// Types gives the accepted types of each argument, such as "int" or
+// "list|string", where "callable" accepts anything that can be called and an
+// empty string accepts any value. The last type applies to all variadic
+// arguments.
+func (e Entry) CheckArgs(args []object.Object) error {
+	minArgs, maxArgs := e.arity()
+	if len(args) < minArgs || (maxArgs >= 0 && len(args) > maxArgs) {
+		return fmt.Errorf("%s: expected %s, got %d", e.Name, argCount(minArgs, maxArgs), len(args))
+	}
+	for i, arg := range args {
+		index := min(i, len(e.Args)-1)
+		if index >= len(e.Types) || e.Types[index] == "" {
+			continue
+		}
+		if !matchesType(arg, e.Types[index]) {
+			name := strings.TrimSuffix(strings.TrimSuffix(e.Args[index], "?"), "...")
+			return object.TypeErrorf("%s() argument %q must be %s (%s given)",
+				e.Name, name, describeType(e.Types[index]), arg.Type())
+		}
+	}
+	return nil
+}
+
+// arity returns the minimum and maximum number of arguments, where a maximum
+// of -1 means there is no limit.
+func (e Entry) arity() (int, int) {
+	minArgs, maxArgs := 0, len(e.Args)
+	for _, name := range e.Args {
+		switch {
+		case strings.HasSuffix(name, "..."):
+			maxArgs = -1
+		case !strings.HasSuffix(name, "?"):
+			minArgs++
+		}
+	}
+	return minArgs, maxArgs
+}
+
+// checked returns the entry's function, wrapped to check its arguments first.
+func (e Entry) checked() object.BuiltinFunction {
+	return func(ctx context.Context, args ...object.Object) (object.Object, error) {
+		if err := e.CheckArgs(args); err != nil {
+			return nil, err
+		}
+		return e.Fn(ctx, args...)
+	}
+}
+
+// argCount describes a number of arguments, such as "1 argument" or
+// "1 to 3 arguments".
+func argCount(minArgs, maxArgs int) string {
+	switch {
+	case maxArgs < 0:
+		return fmt.Sprintf("at least %d %s", minArgs, plural("argument", minArgs))
+	case minArgs == maxArgs:
+		return fmt.Sprintf("%d %s", minArgs, plural("argument", minArgs))
+	default:
+		return fmt.Sprintf("%d to %d arguments", minArgs, maxArgs)
+	}
+}
+
+func plural(word string, n int) string {
+	if n == 1 {
+		return word
+	}
+	return word + "s"
+}
+
+// matchesType returns true if obj has one of the types in a list such as
+// "list|string".
+func matchesType(obj object.Object, types string) bool {
+	for typ := range strings.SplitSeq(types, "|") {
+		if typ == "callable" {
+			if _, ok := obj.(object.Callable); ok {
+				return true
+			}
+		} else if object.Type(typ) == obj.Type() {
+			return true
+		}
+	}
+	return false
+}
+
+// describeType describes a list of types, such as "a list or string".
+func describeType(types string) string {
+	article := "a"
+	if strings.ContainsRune("aeiou", rune(types[0])) {
+		article = "an"
+	}
+	return article + " " + strings.ReplaceAll(types, "|", " or ")
+}
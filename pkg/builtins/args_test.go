@@ -0,0 +1,47 @@
+package builtins
+
+import (
+	"context"
+	"testing"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+func TestCheckArgs(t *testing.T) {
+	ctx := context.Background()
+	b := Builtins()
+	tests := []struct {
+		name     string
+		args     []object.Object
+		expected string
+	}{
+		{"len", nil, "len: expected 1 argument, got 0"},
+		{"chunk", []object.Object{object.NewList(nil)}, "chunk: expected 2 arguments, got 1"},
+		{"range", nil, "range: expected 1 to 3 arguments, got 0"},
+		{"sprintf", nil, "sprintf: expected at least 1 argument, got 0"},
+		{"chunk", []object.Object{object.NewList(nil), object.NewString("2")},
+			`type error: chunk() argument "size" must be an int (string given)`},
+		{"range", []object.Object{object.NewInt(1), object.NewFloat(2)},
+			`type error: range() argument "stop" must be an int (float given)`},
+		{"reversed", []object.Object{object.NewInt(1)},
+			`type error: reversed() argument "sequence" must be a list or string (int given)`},
+		{"filter", []object.Object{object.NewList(nil), object.NewInt(1)},
+			`type error: filter() argument "fn" must be a callable (int given)`},
+	}
+	for _, tt := range tests {
+		_, err := b[tt.name].(*object.Builtin).Call(ctx, tt.args...)
+		assert.NotNil(t, err)
+		assert.Equal(t, err.Error(), tt.expected)
+	}
+
+	// Optional and variadic arguments
+	result, err := b["sprintf"].(*object.Builtin).Call(ctx,
+		object.NewString("%d-%d"), object.NewInt(1), object.NewInt(2))
+	assert.Nil(t, err)
+	assert.Equal(t, result.Inspect(), `"1-2"`)
+	result, err = b["filter"].(*object.Builtin).Call(ctx,
+		object.NewList([]object.Object{object.NewInt(1)}), b["type"])
+	assert.Nil(t, err)
+	assert.Equal(t, result.Inspect(), `[1]`)
+}
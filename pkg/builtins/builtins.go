@@ -5,10 +5,13 @@ import (
 	"context"
 	"fmt"
 	"io"
-	"sort"
+	"maps"
+	"slices"
 	"strconv"
+	"sync"
 
 	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+	"github.com/deepnoodle-ai/risor/v2/pkg/parser"
 )
 
 func Len(ctx context.Context, args ...object.Object) (object.Object, error) {
@@ -87,6 +90,12 @@ func String(ctx context.Context, args ...object.Object) (object.Object, error) {
 		return object.NewString(""), nil
 	}
 	arg := args[0]
+	if str, ok, err := object.CallStr(ctx, arg); ok {
+		if err != nil {
+			return nil, err
+		}
+		return object.NewString(str), nil
+	}
 	switch arg := arg.(type) {
 	case *object.String:
 		return object.NewString(arg.Value()), nil
@@ -108,29 +117,58 @@ func String(ctx context.Context, args ...object.Object) (object.Object, error) {
 	}
 }
 
-func Type(ctx context.Context, args ...object.Object) (object.Object, error) {
+// StringBuilder returns a builder for building a string with write, which
+// unlike repeated + doesn't copy the whole string on every step.
+// Example: let sb = stringbuilder(); sb.write("a"); sb.to_string()
+func StringBuilder(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) > 1 {
+		return nil, fmt.Errorf("stringbuilder: expected 0-1 arguments, got %d", len(args))
+	}
+	if len(args) == 0 {
+		return object.NewStringBuilder(""), nil
+	}
+	s, err := object.AsString(args[0])
+	if err != nil {
+		return nil, err
+	}
+	return object.NewStringBuilder(s), nil
+}
+
+// Repr returns a debugging representation of a value, which unlike string()
+// quotes strings and shows the types of numbers.
+func Repr(ctx context.Context, args ...object.Object) (object.Object, error) {
 	if len(args) != 1 {
-		return nil, fmt.Errorf("type: expected 1 argument, got %d", len(args))
+		return nil, fmt.Errorf("repr: expected 1 argument, got %d", len(args))
 	}
-	return object.NewString(string(args[0].Type())), nil
+	return object.NewString(object.Repr(args[0])), nil
 }
 
-func Assert(ctx context.Context, args ...object.Object) (object.Object, error) {
+// ToSource returns a value written as Risor code that evaluates to an equal
+// value, on one line or, if an indent string is given, across several.
+// Example: to_source({name: "risor", tags: ["a"]}, "  ")
+func ToSource(ctx context.Context, args ...object.Object) (object.Object, error) {
 	if len(args) < 1 || len(args) > 2 {
-		return nil, fmt.Errorf("assert: expected 1-2 arguments, got %d", len(args))
-	}
-	if !args[0].IsTruthy() {
-		if len(args) == 2 {
-			switch arg := args[1].(type) {
-			case *object.String:
-				return nil, fmt.Errorf("%s", arg.Value())
-			default:
-				return nil, fmt.Errorf("%s", args[1].Inspect())
-			}
+		return nil, fmt.Errorf("to_source: expected 1 or 2 arguments, got %d", len(args))
+	}
+	var indent string
+	if len(args) == 2 {
+		var err error
+		if indent, err = object.AsString(args[1]); err != nil {
+			return nil, err
 		}
-		return nil, fmt.Errorf("assertion failed")
 	}
-	return object.Nil, nil
+	source, err := object.ToSource(args[0], indent)
+	if err != nil {
+		return nil, err
+	}
+	return object.NewString(source), nil
+}
+
+func Type(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) != 1 {
+		return nil, fmt.Errorf("type: expected 1 argument, got %d", len(args))
+	}
+	return object.NewString(string(args[0].Type())), nil
 }
 
 func Any(ctx context.Context, args ...object.Object) (object.Object, error) {
@@ -246,49 +284,103 @@ func Bool(ctx context.Context, args ...object.Object) (object.Object, error) {
 	return object.False, nil
 }
 
+// Sorted returns a sorted list of the values of items, which may be any
+// enumerable. Maps give their keys and strings give their characters. The
+// optional second argument is either a less-than function or a map of
+// options: key, a function whose result is sorted on in place of each value,
+// and reverse, which sorts in descending order. Sorting is stable, including
+// in reverse.
+// Example: sorted(users, {key: u => u.age, reverse: true})
 func Sorted(ctx context.Context, args ...object.Object) (object.Object, error) {
 	if len(args) < 1 || len(args) > 2 {
 		return nil, fmt.Errorf("sorted: expected 1-2 arguments, got %d", len(args))
 	}
-	arg := args[0]
-	var items []object.Object
-	switch arg := arg.(type) {
+	var resultItems []object.Object
+	switch arg := args[0].(type) {
 	case *object.List:
-		items = arg.Value()
+		resultItems = slices.Clone(arg.Value())
 	case *object.Map:
-		items = arg.Keys().Value()
+		resultItems = arg.Keys().Value()
 	case *object.String:
-		items = arg.Runes()
+		resultItems = arg.Runes()
+	case object.Enumerable:
+		for value := range values(ctx, arg) {
+			resultItems = append(resultItems, value)
+		}
+		if err := ctx.Err(); err != nil {
+			return nil, err
+		}
 	default:
 		return nil, object.TypeErrorf("sorted() unsupported argument (%s given)", arg.Type())
 	}
-	resultItems := make([]object.Object, len(items))
-	copy(resultItems, items)
+	var less, key object.Callable
+	var reverse bool
 	if len(args) == 2 {
-		callable, ok := args[1].(object.Callable)
-		if !ok {
-			return nil, object.TypeErrorf("sorted() expected a function as the second argument (%s given)", args[1].Type())
-		}
-		var sortErr error
-		sort.SliceStable(resultItems, func(i, j int) bool {
-			result, err := callable.Call(ctx, resultItems[i], resultItems[j])
-			if err != nil {
-				sortErr = err
-				return false
+		switch opt := args[1].(type) {
+		case *object.Map:
+			var err error
+			if key, reverse, err = sortOptions(opt); err != nil {
+				return nil, err
 			}
-			return result.IsTruthy()
-		})
-		if sortErr != nil {
-			return nil, sortErr
+		case object.Callable:
+			less = opt
+		default:
+			return nil, object.TypeErrorf("sorted() expected a function or options map as the second argument (%s given)", opt.Type())
+		}
+	}
+	// Sorting the reversed values and reversing the result keeps equal
+	// values in their original order.
+	if reverse {
+		slices.Reverse(resultItems)
+	}
+	switch {
+	case less != nil:
+		if err := object.SortFunc(ctx, resultItems, less); err != nil {
+			return nil, err
 		}
-	} else {
+	case key != nil:
+		if err := object.SortByKey(ctx, resultItems, key); err != nil {
+			return nil, err
+		}
+	default:
 		if err := object.Sort(resultItems); err != nil {
 			return nil, err
 		}
 	}
+	if reverse {
+		slices.Reverse(resultItems)
+	}
 	return object.NewList(resultItems), nil
 }
 
+// sortOptions reads the key and reverse options given to sorted.
+func sortOptions(opts *object.Map) (object.Callable, bool, error) {
+	var key object.Callable
+	var reverse bool
+	for name, value := range opts.Value() {
+		switch name {
+		case "key":
+			if value == object.Nil {
+				continue
+			}
+			fn, ok := value.(object.Callable)
+			if !ok {
+				return nil, false, object.TypeErrorf("sorted() key option must be a function (%s given)", value.Type())
+			}
+			key = fn
+		case "reverse":
+			b, ok := value.(*object.Bool)
+			if !ok {
+				return nil, false, object.TypeErrorf("sorted() reverse option must be a bool (%s given)", value.Type())
+			}
+			reverse = b.Value()
+		default:
+			return nil, false, object.ValueErrorf("sorted() unknown option %q", name)
+		}
+	}
+	return key, reverse, nil
+}
+
 func Reversed(ctx context.Context, args ...object.Object) (object.Object, error) {
 	if len(args) != 1 {
 		return nil, fmt.Errorf("reversed: expected 1 argument, got %d", len(args))
@@ -322,6 +414,17 @@ func GetAttr(ctx context.Context, args ...object.Object) (object.Object, error)
 		args[0].Type(), attrName)
 }
 
+// Dir returns the sorted names of the attributes and methods of a value,
+// such as the methods of a string or the members of a module.
+func Dir(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) != 1 {
+		return nil, fmt.Errorf("dir: expected 1 argument, got %d", len(args))
+	}
+	names := object.AttrNames(args[0].Attrs())
+	slices.Sort(names)
+	return object.NewStringList(slices.Compact(names)), nil
+}
+
 func Call(ctx context.Context, args ...object.Object) (object.Object, error) {
 	if len(args) < 1 || len(args) > 64 {
 		return nil, fmt.Errorf("call: expected 1-64 arguments, got %d", len(args))
@@ -333,6 +436,130 @@ func Call(ctx context.Context, args ...object.Object) (object.Object, error) {
 	return callable.Call(ctx, args[1:]...)
 }
 
+// On registers fn to be called with the event data whenever the host emits
+// the named event. Handlers run when the host dispatches queued events, in
+// the order they were registered.
+// Example: on("order.created", order => log.info("new order", order))
+func On(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) != 2 {
+		return nil, fmt.Errorf("on: expected 2 arguments, got %d", len(args))
+	}
+	event, err := object.AsString(args[0])
+	if err != nil {
+		return nil, err
+	}
+	handler, ok := args[1].(object.Callable)
+	if !ok {
+		return nil, object.TypeErrorf("on() expected a callable (%s given)", args[1].Type())
+	}
+	register, ok := object.GetEventRegisterFunc(ctx)
+	if !ok {
+		return nil, fmt.Errorf("on: events are not supported in this context")
+	}
+	register(event, handler)
+	return object.Nil, nil
+}
+
+// Eval compiles and runs a string of code in a child VM. The code sees the
+// builtins and the entries of the optional globals map, but not the caller's
+// globals. It returns the value of the code, or an error value if the code
+// fails to compile or raises an error.
+// Example: eval("price * qty", {price: 2, qty: 3})
+func Eval(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) < 1 || len(args) > 2 {
+		return nil, fmt.Errorf("eval: expected 1 or 2 arguments, got %d", len(args))
+	}
+	source, err := object.AsString(args[0])
+	if err != nil {
+		return nil, err
+	}
+	return evalCode(ctx, "eval", source, args[1:])
+}
+
+// Compile parses a string of code into a quote that can be passed to run,
+// raising an error if the code has a syntax error.
+// Example: compile("price * qty")
+func Compile(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) != 1 {
+		return nil, fmt.Errorf("compile: expected 1 argument, got %d", len(args))
+	}
+	source, err := object.AsString(args[0])
+	if err != nil {
+		return nil, err
+	}
+	if _, err := parser.Parse(ctx, source, nil); err != nil {
+		return nil, err
+	}
+	return object.NewQuote(source), nil
+}
+
+// Run runs a quote in a child VM, the same way eval runs a string of code.
+// Example: run(quote { price * qty }, {price: 2, qty: 3})
+func Run(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) < 1 || len(args) > 2 {
+		return nil, fmt.Errorf("run: expected 1 or 2 arguments, got %d", len(args))
+	}
+	quote, ok := args[0].(*object.Quote)
+	if !ok {
+		return nil, object.TypeErrorf("run() expected a quote (%s given)", args[0].Type())
+	}
+	return evalCode(ctx, "run", quote.Source(), args[1:])
+}
+
+// evalCode runs source in a child VM with the globals map in args, if given.
+func evalCode(ctx context.Context, name, source string, args []object.Object) (object.Object, error) {
+	globals := map[string]object.Object{}
+	if len(args) == 1 {
+		m, err := object.AsMap(args[0])
+		if err != nil {
+			return nil, err
+		}
+		maps.Copy(globals, m.Value())
+	}
+	eval, ok := object.GetEvalFunc(ctx)
+	if !ok {
+		return nil, fmt.Errorf("%s: not supported in this context", name)
+	}
+	return eval(ctx, source, globals)
+}
+
+// Memo wraps a callable with a cache keyed on its arguments. Arguments must be
+// hashable; calling the wrapper with an unhashable argument raises a type error.
+// Example: let lookup = memo(name => fetch_user(name))
+func Memo(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) != 1 {
+		return nil, fmt.Errorf("memo: expected 1 argument, got %d", len(args))
+	}
+	fn, ok := args[0].(object.Callable)
+	if !ok {
+		return nil, object.TypeErrorf("memo() expected a callable (%s given)", args[0].Type())
+	}
+	var mu sync.Mutex
+	cache := map[object.HashKey]object.Object{}
+	return object.NewBuiltin("memo", func(ctx context.Context, args ...object.Object) (object.Object, error) {
+		key, err := object.Hash(object.NewTuple(args))
+		if err != nil {
+			return nil, err
+		}
+		mu.Lock()
+		result, found := cache[key]
+		mu.Unlock()
+		if found {
+			return result, nil
+		}
+		// The lock is not held during the call, since the wrapped function
+		// typically calls back into this wrapper recursively.
+		result, err = fn.Call(ctx, args...)
+		if err != nil {
+			return nil, err
+		}
+		mu.Lock()
+		cache[key] = result
+		mu.Unlock()
+		return result, nil
+	}), nil
+}
+
 func Keys(ctx context.Context, args ...object.Object) (object.Object, error) {
 	if len(args) != 1 {
 		return nil, fmt.Errorf("keys: expected 1 argument, got %d", len(args))
@@ -419,6 +646,11 @@ func Int(ctx context.Context, args ...object.Object) (object.Object, error) {
 		return object.NewInt(int64(obj.Value())), nil
 	case *object.Float:
 		return object.NewInt(int64(obj.Value())), nil
+	case *object.Decimal:
+		if i, ok := obj.Int64(); ok {
+			return object.NewInt(i), nil
+		}
+		return nil, object.ValueErrorf("int() argument out of range: %s", obj)
 	case *object.String:
 		if i, err := strconv.ParseInt(obj.Value(), 0, 64); err == nil {
 			return object.NewInt(i), nil
@@ -443,6 +675,8 @@ func Float(ctx context.Context, args ...object.Object) (object.Object, error) {
 		return object.NewFloat(float64(obj.Value())), nil
 	case *object.Float:
 		return obj, nil
+	case *object.Decimal:
+		return object.NewFloat(obj.Float64()), nil
 	case *object.String:
 		if f, err := strconv.ParseFloat(obj.Value(), 64); err == nil {
 			return object.NewFloat(f), nil
@@ -453,6 +687,77 @@ func Float(ctx context.Context, args ...object.Object) (object.Object, error) {
 	}
 }
 
+func Copy(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) < 1 || len(args) > 2 {
+		return nil, fmt.Errorf("copy: expected 1-2 arguments, got %d", len(args))
+	}
+	deep := true
+	if len(args) == 2 {
+		b, ok := args[1].(*object.Bool)
+		if !ok {
+			return nil, object.TypeErrorf("copy() expected a bool as the second argument (%s given)", args[1].Type())
+		}
+		deep = b.Value()
+	}
+	if deep {
+		return object.DeepCopy(args[0]), nil
+	}
+	switch arg := args[0].(type) {
+	case *object.List:
+		return arg.Copy(), nil
+	case *object.Map:
+		return arg.Copy(), nil
+	case *object.Bytes:
+		return arg.Clone(), nil
+	default:
+		return arg, nil
+	}
+}
+
+func Equal(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) != 2 {
+		return nil, fmt.Errorf("equal: expected 2 arguments, got %d", len(args))
+	}
+	return object.NewBool(object.StrictEqual(args[0], args[1])), nil
+}
+
+func Freeze(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) != 1 {
+		return nil, fmt.Errorf("freeze: expected 1 argument, got %d", len(args))
+	}
+	return object.Freeze(args[0]), nil
+}
+
+func Decimal(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) > 1 {
+		return nil, fmt.Errorf("decimal: expected 0-1 arguments, got %d", len(args))
+	}
+	if len(args) == 0 {
+		return object.NewDecimalFromInt(0), nil
+	}
+	switch obj := args[0].(type) {
+	case *object.Decimal:
+		return obj, nil
+	case *object.Int:
+		return object.NewDecimalFromInt(obj.Value()), nil
+	case *object.Byte:
+		return object.NewDecimalFromInt(int64(obj.Value())), nil
+	case *object.Float:
+		d, err := object.NewDecimalFromFloat(obj.Value())
+		if err != nil {
+			return nil, object.ValueErrorf("decimal(): %s", err)
+		}
+		return d, nil
+	case *object.String:
+		if d, err := object.ParseDecimal(obj.Value()); err == nil {
+			return d, nil
+		}
+		return nil, object.ValueErrorf("invalid literal for decimal(): %q", obj.Value())
+	default:
+		return nil, object.TypeErrorf("decimal() unsupported argument (%s given)", args[0].Type())
+	}
+}
+
 func Coalesce(ctx context.Context, args ...object.Object) (object.Object, error) {
 	if len(args) > 64 {
 		return nil, fmt.Errorf("coalesce: expected 0-64 arguments, got %d", len(args))
@@ -469,11 +774,6 @@ func Chunk(ctx context.Context, args ...object.Object) (object.Object, error) {
 	if len(args) != 2 {
 		return nil, fmt.Errorf("chunk: expected 2 arguments, got %d", len(args))
 	}
-	list, ok := args[0].(*object.List)
-	if !ok {
-		return nil, object.TypeErrorf("chunk() expected a list (%s given)", args[0].Type())
-	}
-	listSize := int64(list.Size())
 	chunkSizeObj, ok := args[1].(*object.Int)
 	if !ok {
 		return nil, object.TypeErrorf("chunk() expected an int (%s given)", args[1].Type())
@@ -482,6 +782,17 @@ func Chunk(ctx context.Context, args ...object.Object) (object.Object, error) {
 	if chunkSize <= 0 {
 		return nil, object.ValueErrorf("chunk() size must be > 0 (%d given)", chunkSize)
 	}
+	var list *object.List
+	switch arg := args[0].(type) {
+	case *object.List:
+		list = arg
+	case *object.Iter, *object.Range:
+		// Iterators may be long or unbounded, so they're chunked lazily
+		return chunkIter(arg.(object.Enumerable), chunkSize), nil
+	default:
+		return nil, object.TypeErrorf("chunk() expected a list or iterator (%s given)", args[0].Type())
+	}
+	listSize := int64(list.Size())
 	items := list.Value()
 	nChunks := listSize / chunkSize
 	if listSize%chunkSize != 0 {
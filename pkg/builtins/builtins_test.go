@@ -2,6 +2,7 @@ package builtins
 
 import (
 	"context"
+	"math"
 	"testing"
 
 	"github.com/deepnoodle-ai/risor/v2/pkg/object"
@@ -176,6 +177,47 @@ func TestSortedWithBuiltin(t *testing.T) {
 	assertObjectEqual(t, result, expected)
 }
 
+func TestSortedWithOptions(t *testing.T) {
+	ctx := context.Background()
+	words := object.NewStringList([]string{"kiwi", "fig", "banana", "pear"})
+	reverse := object.NewMap(map[string]object.Object{"reverse": object.True})
+	byLen := object.NewMap(map[string]object.Object{"key": lenFn})
+	byLenDesc := object.NewMap(map[string]object.Object{"key": lenFn, "reverse": object.True})
+	tests := []struct {
+		args     []object.Object
+		expected string
+	}{
+		{[]object.Object{words, reverse}, `["pear", "kiwi", "fig", "banana"]`},
+		{[]object.Object{words, byLen}, `["fig", "kiwi", "pear", "banana"]`},
+		// Equal keys keep their original order when reversed too
+		{[]object.Object{words, byLenDesc}, `["banana", "kiwi", "pear", "fig"]`},
+		{[]object.Object{object.NewRange(0, 4, 1), reverse}, `[3, 2, 1, 0]`},
+		{[]object.Object{object.NewMap(map[string]object.Object{
+			"b": object.NewInt(1),
+			"a": object.NewInt(2),
+		}), reverse}, `["b", "a"]`},
+		{[]object.Object{object.NewString("cab")}, `["a", "b", "c"]`},
+	}
+	for _, tt := range tests {
+		t.Run(tt.expected, func(t *testing.T) {
+			result, err := Sorted(ctx, tt.args...)
+			assert.Nil(t, err)
+			assert.Equal(t, result.Inspect(), tt.expected)
+		})
+	}
+
+	_, err := Sorted(ctx, words, object.NewMap(map[string]object.Object{"reversed": object.True}))
+	assert.Error(t, err)
+	assert.Equal(t, err.Error(), `value error: sorted() unknown option "reversed"`)
+
+	_, err = Sorted(ctx, words, object.NewMap(map[string]object.Object{"reverse": object.NewInt(1)}))
+	assert.Error(t, err)
+	assert.Equal(t, err.Error(), "type error: sorted() reverse option must be a bool (int given)")
+
+	_, err = Sorted(ctx, object.NewInt(1))
+	assert.Error(t, err)
+}
+
 func TestCoalesce(t *testing.T) {
 	ctx := context.Background()
 	tests := []testCase{
@@ -346,6 +388,33 @@ func TestList(t *testing.T) {
 	assert.Len(t, list.Value(), 0)
 }
 
+func TestDir(t *testing.T) {
+	ctx := context.Background()
+
+	result, err := Dir(ctx, object.NewString("hi"))
+	assert.Nil(t, err)
+	names := result.(*object.List).Value()
+	assert.Greater(t, len(names), 1)
+	assert.Contains(t, result.Inspect(), `"split"`)
+	for i := 1; i < len(names); i++ {
+		prev := names[i-1].(*object.String).Value()
+		assert.True(t, prev < names[i].(*object.String).Value())
+	}
+
+	mod := object.NewBuiltinsModule("tools", map[string]object.Object{
+		"noop":    object.NewNoopBuiltin("noop"),
+		"version": object.NewString("1.0"),
+	})
+	result, err = Dir(ctx, mod)
+	assert.Nil(t, err)
+	assert.Equal(t, result.Inspect(), `["__name__", "noop", "version"]`)
+
+	_, err = Dir(ctx)
+	assert.NotNil(t, err)
+	_, err = Dir(ctx, mod, mod)
+	assert.NotNil(t, err)
+}
+
 func TestListErrors(t *testing.T) {
 	ctx := context.Background()
 
@@ -381,6 +450,85 @@ func TestString(t *testing.T) {
 	assertObjectEqual(t, result, object.NewString("42"))
 }
 
+func TestStringBuilder(t *testing.T) {
+	ctx := context.Background()
+
+	result, err := StringBuilder(ctx)
+	assert.Nil(t, err)
+	assert.Equal(t, result.(*object.StringBuilder).String(), "")
+
+	result, err = StringBuilder(ctx, object.NewString("hello"))
+	assert.Nil(t, err)
+	assert.Equal(t, result.(*object.StringBuilder).String(), "hello")
+
+	result, err = String(ctx, result)
+	assert.Nil(t, err)
+	assertObjectEqual(t, result, object.NewString("hello"))
+
+	_, err = StringBuilder(ctx, object.NewInt(1))
+	assert.NotNil(t, err)
+}
+
+func TestRepr(t *testing.T) {
+	ctx := context.Background()
+	tests := []struct {
+		input    object.Object
+		expected string
+	}{
+		{object.NewString("a\tb"), `"a\tb"`},
+		{object.NewFloat(2), "2.0"},
+		{object.NewFloat(2.5), "2.5"},
+		{object.NewByte(65), "byte(65)"},
+		{object.NewList([]object.Object{object.NewInt(1), object.NewString("a")}), `[1, "a"]`},
+		{object.NewTuple([]object.Object{object.NewString("x")}), `("x",)`},
+		{object.NewMap(map[string]object.Object{"k": object.NewFloat(1)}), `{"k": 1.0}`},
+	}
+	for _, tt := range tests {
+		result, err := Repr(ctx, tt.input)
+		assert.Nil(t, err)
+		assertObjectEqual(t, result, object.NewString(tt.expected))
+	}
+
+	// A list that contains itself
+	list := object.NewList([]object.Object{object.NewInt(1)})
+	list.Append(list)
+	result, err := Repr(ctx, list)
+	assert.Nil(t, err)
+	assertObjectEqual(t, result, object.NewString("[1, [...]]"))
+
+	_, err = Repr(ctx)
+	assert.NotNil(t, err)
+}
+
+func TestToSource(t *testing.T) {
+	ctx := context.Background()
+	data := object.NewMap(map[string]object.Object{
+		"name":  object.NewString("a\"b"),
+		"if":    object.NewFloat(2),
+		"a-b":   object.NewList([]object.Object{object.NewInt(-1), object.Nil}),
+		"empty": object.NewList(nil),
+		"raw":   object.NewBytes([]byte{0xff, 'x'}),
+	})
+	result, err := ToSource(ctx, data)
+	assert.Nil(t, err)
+	assertObjectEqual(t, result, object.NewString(
+		`{"a-b": [-1, null], empty: [], "if": 2.0, name: "a\"b", raw: bytes("\377x")}`))
+
+	result, err = ToSource(ctx, object.NewMap(map[string]object.Object{
+		"items": object.NewList([]object.Object{object.NewInt(1), object.True}),
+	}), object.NewString("  "))
+	assert.Nil(t, err)
+	assertObjectEqual(t, result, object.NewString("{\n  items: [\n    1,\n    true,\n  ],\n}"))
+
+	// Values that can't be written as code
+	_, err = ToSource(ctx, object.NewBuiltin("f", Len))
+	assert.NotNil(t, err)
+	list := object.NewList(nil)
+	list.Append(list)
+	_, err = ToSource(ctx, list)
+	assert.NotNil(t, err)
+}
+
 func TestStringByte(t *testing.T) {
 	ctx := context.Background()
 
@@ -441,34 +589,6 @@ func TestTypeErrors(t *testing.T) {
 	assert.NotNil(t, err)
 }
 
-func TestAssert(t *testing.T) {
-	ctx := context.Background()
-
-	// Truthy assertion
-	result, err := Assert(ctx, object.True)
-	assert.Nil(t, err)
-	assert.Equal(t, result, object.Nil)
-
-	// With message
-	result, err = Assert(ctx, object.True, object.NewString("should pass"))
-	assert.Nil(t, err)
-	assert.Equal(t, result, object.Nil)
-
-	// Failed assertion
-	_, err = Assert(ctx, object.False)
-	assert.NotNil(t, err)
-
-	// Failed assertion with message
-	_, err = Assert(ctx, object.False, object.NewString("custom error"))
-	assert.NotNil(t, err)
-}
-
-func TestAssertErrors(t *testing.T) {
-	ctx := context.Background()
-	_, err := Assert(ctx)
-	assert.NotNil(t, err)
-}
-
 func TestAny(t *testing.T) {
 	ctx := context.Background()
 
@@ -727,6 +847,18 @@ func TestFloat(t *testing.T) {
 	f, ok = result.(*object.Float)
 	assert.True(t, ok)
 	assert.Equal(t, f.Value(), float64(3.14))
+
+	// From strings in scientific notation
+	for input, expected := range map[string]float64{
+		"1e3":     1000,
+		"-2.5E-3": -0.0025,
+		"+1e+21":  1e21,
+		"1e21":    1e21,
+	} {
+		result, err = Float(ctx, object.NewString(input))
+		assert.Nil(t, err)
+		assert.Equal(t, result.(*object.Float).Value(), expected)
+	}
 }
 
 func TestFloatErrors(t *testing.T) {
@@ -737,10 +869,131 @@ func TestFloatErrors(t *testing.T) {
 	_, err = Float(ctx, object.NewString("invalid"))
 	assert.NotNil(t, err)
 
+	// Parsing doesn't depend on the locale
+	_, err = Float(ctx, object.NewString("1,5"))
+	assert.NotNil(t, err)
+
 	_, err = Float(ctx, object.NewList([]object.Object{}))
 	assert.NotNil(t, err)
 }
 
+func TestCopy(t *testing.T) {
+	ctx := context.Background()
+	inner := object.NewList([]object.Object{object.NewInt(1)})
+	original := object.NewList([]object.Object{inner})
+
+	deep, err := Copy(ctx, original)
+	assert.Nil(t, err)
+	shallow, err := Copy(ctx, original, object.False)
+	assert.Nil(t, err)
+	inner.Append(object.NewInt(2))
+
+	assert.Equal(t, deep.(*object.List).Value()[0].(*object.List).Len().Value(), int64(1))
+	assert.True(t, shallow.(*object.List).Value()[0] == inner)
+	assert.True(t, shallow != original)
+
+	// Immutable values are returned as they are
+	str := object.NewString("a")
+	result, err := Copy(ctx, str)
+	assert.Nil(t, err)
+	assert.True(t, result == str)
+
+	_, err = Copy(ctx, original, object.NewInt(1))
+	assert.NotNil(t, err)
+	_, err = Copy(ctx)
+	assert.NotNil(t, err)
+}
+
+func TestEqual(t *testing.T) {
+	ctx := context.Background()
+	a := object.NewList([]object.Object{object.NewInt(1), object.NewMap(map[string]object.Object{"b": object.NewInt(2)})})
+	b := object.NewList([]object.Object{object.NewInt(1), object.NewMap(map[string]object.Object{"b": object.NewInt(2)})})
+	c := object.NewList([]object.Object{object.NewInt(1), object.NewMap(map[string]object.Object{"b": object.NewFloat(2)})})
+
+	result, err := Equal(ctx, a, b)
+	assert.Nil(t, err)
+	assert.Equal(t, result, object.True)
+	result, err = Equal(ctx, a, c)
+	assert.Nil(t, err)
+	assert.Equal(t, result, object.False)
+
+	_, err = Equal(ctx, a)
+	assert.NotNil(t, err)
+}
+
+func TestFreeze(t *testing.T) {
+	ctx := context.Background()
+	inner := object.NewList([]object.Object{object.NewInt(1)})
+	config := object.NewMap(map[string]object.Object{"items": inner})
+
+	result, err := Freeze(ctx, config)
+	assert.Nil(t, err)
+	assert.True(t, result == config)
+	assert.True(t, config.IsFrozen())
+	assert.True(t, inner.IsFrozen())
+
+	// Strings and other immutable values are returned as they are
+	str := object.NewString("a")
+	result, err = Freeze(ctx, str)
+	assert.Nil(t, err)
+	assert.True(t, result == str)
+
+	_, err = Freeze(ctx)
+	assert.NotNil(t, err)
+}
+
+func TestDecimal(t *testing.T) {
+	ctx := context.Background()
+	tests := []struct {
+		input    object.Object
+		expected string
+	}{
+		{object.NewString("19.99"), "19.99"},
+		{object.NewString("-1.5e-2"), "-0.015"},
+		{object.NewInt(42), "42"},
+		{object.NewByte(7), "7"},
+		{object.NewFloat(0.1), "0.1"},
+	}
+	for _, tt := range tests {
+		result, err := Decimal(ctx, tt.input)
+		assert.Nil(t, err)
+		assert.Equal(t, result.(*object.Decimal).String(), tt.expected)
+	}
+
+	result, err := Decimal(ctx)
+	assert.Nil(t, err)
+	assert.Equal(t, result.(*object.Decimal).String(), "0")
+
+	// Conversions back to int and float
+	d, _ := Decimal(ctx, object.NewString("-2.75"))
+	result, err = Int(ctx, d)
+	assert.Nil(t, err)
+	assert.Equal(t, result, object.NewInt(-2))
+	result, err = Float(ctx, d)
+	assert.Nil(t, err)
+	assert.Equal(t, result, object.NewFloat(-2.75))
+}
+
+func TestDecimalErrors(t *testing.T) {
+	ctx := context.Background()
+	_, err := Decimal(ctx, object.NewString("1,5"))
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), "value error: invalid literal for decimal(): \"1,5\"")
+
+	_, err = Decimal(ctx, object.NewFloat(math.NaN()))
+	assert.NotNil(t, err)
+
+	_, err = Decimal(ctx, object.NewList(nil))
+	assert.NotNil(t, err)
+
+	_, err = Decimal(ctx, object.NewInt(1), object.NewInt(2))
+	assert.NotNil(t, err)
+
+	large, _ := Decimal(ctx, object.NewString("1e30"))
+	_, err = Int(ctx, large)
+	assert.NotNil(t, err)
+}
+
 func TestGetAttr(t *testing.T) {
 	ctx := context.Background()
 
@@ -960,3 +1213,89 @@ func TestRangeWithList(t *testing.T) {
 		assert.Equal(t, v.(*object.Int).Value(), expected[i])
 	}
 }
+
+func TestMemo(t *testing.T) {
+	ctx := context.Background()
+	calls := 0
+	square := object.NewBuiltin("square", func(ctx context.Context, args ...object.Object) (object.Object, error) {
+		calls++
+		i := args[0].(*object.Int).Value()
+		return object.NewInt(i * i), nil
+	})
+	result, err := Memo(ctx, square)
+	assert.Nil(t, err)
+	memoized := result.(object.Callable)
+
+	for _, arg := range []object.Object{object.NewInt(4), object.NewInt(4), object.NewFloat(4.0)} {
+		value, err := memoized.Call(ctx, arg)
+		assert.Nil(t, err)
+		assertObjectEqual(t, value, object.NewInt(16))
+	}
+	assert.Equal(t, calls, 1)
+
+	value, err := memoized.Call(ctx, object.NewInt(5))
+	assert.Nil(t, err)
+	assertObjectEqual(t, value, object.NewInt(25))
+	assert.Equal(t, calls, 2)
+}
+
+func TestMemoErrors(t *testing.T) {
+	ctx := context.Background()
+	_, err := Memo(ctx)
+	assert.NotNil(t, err)
+
+	_, err = Memo(ctx, object.NewInt(1))
+	assert.NotNil(t, err)
+
+	calls := 0
+	fail := object.NewBuiltin("fail", func(ctx context.Context, args ...object.Object) (object.Object, error) {
+		calls++
+		return nil, object.Errorf("failed").Value()
+	})
+	result, err := Memo(ctx, fail)
+	assert.Nil(t, err)
+	memoized := result.(object.Callable)
+
+	// Errors are not cached
+	_, err = memoized.Call(ctx, object.NewInt(1))
+	assert.NotNil(t, err)
+	_, err = memoized.Call(ctx, object.NewInt(1))
+	assert.NotNil(t, err)
+	assert.Equal(t, calls, 2)
+
+	// Unhashable arguments are rejected before calling the function
+	_, err = memoized.Call(ctx, object.NewList(nil))
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "unhashable type: list")
+	assert.Equal(t, calls, 2)
+}
+
+func TestOn(t *testing.T) {
+	handler := object.NewBuiltin("handler", func(ctx context.Context, args ...object.Object) (object.Object, error) {
+		return object.Nil, nil
+	})
+	registered := map[string][]object.Callable{}
+	ctx := object.WithEventRegisterFunc(context.Background(), func(event string, fn object.Callable) {
+		registered[event] = append(registered[event], fn)
+	})
+
+	result, err := On(ctx, object.NewString("tick"), handler)
+	assert.Nil(t, err)
+	assert.Equal(t, result, object.Nil)
+	assert.Len(t, registered["tick"], 1)
+
+	_, err = On(ctx, object.NewString("tick"))
+	assert.NotNil(t, err)
+
+	_, err = On(ctx, object.NewInt(1), handler)
+	assert.NotNil(t, err)
+
+	_, err = On(ctx, object.NewString("tick"), object.NewInt(1))
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "on() expected a callable (int given)")
+
+	// Without a VM there is nowhere to deliver events
+	_, err = On(context.Background(), object.NewString("tick"), handler)
+	assert.NotNil(t, err)
+	assert.Len(t, registered["tick"], 1)
+}
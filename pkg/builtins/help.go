@@ -0,0 +1,133 @@
+package builtins
+
+import (
+	"context"
+	"fmt"
+	"strings"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+)
+
+const helpUsage = "Use help(value) to show the documentation of a function, module, or type.\n" +
+	"For example: help(len), help(math), help(math.sqrt), help(\"\")"
+
+// Help returns documentation for a value as a string: the signature and
+// docstring of a builtin or function, the members of a module, or the
+// methods of other types. Functions are documented by a string literal at
+// the start of their body:
+//
+//	function greet(name) {
+//	    "Return a greeting for name"
+//	    return "hello " + name
+//	}
+func Help(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) > 1 {
+		return nil, fmt.Errorf("help: expected 0-1 arguments, got %d", len(args))
+	}
+	if len(args) == 0 {
+		return object.NewString(helpUsage), nil
+	}
+	return object.NewString(HelpText(args[0])), nil
+}
+
+// HelpText returns the documentation that help() shows for obj.
+func HelpText(obj object.Object) string {
+	var b strings.Builder
+	switch obj := obj.(type) {
+	case *object.Builtin:
+		spec, ok := obj.Spec()
+		if !ok {
+			spec = object.FuncSpec{Args: []string{"..."}}
+		}
+		spec.Name = obj.Key()
+		writeSpec(&b, spec, true, "")
+		if !ok {
+			b.WriteString("\n\nNo documentation available.")
+		}
+	case *object.Closure:
+		b.WriteString(closureSignature(obj))
+		if code := obj.Code(); code != nil && code.Doc() != "" {
+			b.WriteString("\n\n" + code.Doc())
+		}
+	case *object.Module:
+		b.WriteString("module " + obj.Name().Value())
+		if doc := obj.Doc(); doc != "" {
+			b.WriteString("\n\n" + doc)
+		}
+		if specs := obj.Specs(); len(specs) > 0 {
+			b.WriteString("\n")
+			for _, spec := range specs {
+				member, _ := obj.GetAttr(spec.Name)
+				_, callable := member.(object.Callable)
+				b.WriteString("\n")
+				writeSpec(&b, spec, callable, "    ")
+			}
+		}
+	default:
+		typeName := string(obj.Type())
+		b.WriteString(typeName)
+		spec, ok := object.TypeDocsMap()[typeName]
+		if ok && spec.Doc != "" {
+			b.WriteString("\n\n" + spec.Doc)
+		}
+		if ok && len(spec.Attrs) > 0 {
+			b.WriteString("\n")
+			for _, attr := range spec.Attrs {
+				b.WriteString("\n")
+				writeSpec(&b, object.FuncSpec{
+					Name:    "." + attr.Name,
+					Doc:     attr.Doc,
+					Args:    attr.Args,
+					Returns: attr.Returns,
+				}, true, "    ")
+			}
+		}
+	}
+	return b.String()
+}
+
+// writeSpec writes a signature line followed by the documentation of a
+// function, or of a value if it isn't callable. The documentation is indented
+// by indent when it is non-empty.
+func writeSpec(b *strings.Builder, spec object.FuncSpec, callable bool, indent string) {
+	b.WriteString(spec.Name)
+	if callable {
+		b.WriteString("(" + strings.Join(spec.Args, ", ") + ")")
+		if spec.Returns != "" {
+			b.WriteString(" -> " + spec.Returns)
+		}
+	} else if spec.Returns != "" {
+		b.WriteString(": " + spec.Returns)
+	}
+	sep := "\n\n"
+	if indent != "" {
+		sep = "\n" + indent
+	}
+	if spec.Doc != "" {
+		b.WriteString(sep + spec.Doc)
+	}
+	if spec.Example != "" {
+		b.WriteString(sep + "Example: " + spec.Example)
+	}
+}
+
+// closureSignature returns the signature of a function, such as
+// greet(name, greeting="hello", ...rest).
+func closureSignature(fn *object.Closure) string {
+	params := make([]string, 0, fn.ParameterCount()+1)
+	for i := 0; i < fn.ParameterCount(); i++ {
+		param := fn.Parameter(i)
+		if def := fn.Default(i); def != nil {
+			param += "=" + def.Inspect()
+		}
+		params = append(params, param)
+	}
+	if fn.HasRestParam() {
+		params = append(params, "..."+fn.RestParam())
+	}
+	name := fn.Name()
+	if name == "" {
+		name = "function"
+	}
+	return name + "(" + strings.Join(params, ", ") + ")"
+}
@@ -0,0 +1,51 @@
+package builtins
+
+import (
+	"context"
+	"testing"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+func TestHelp(t *testing.T) {
+	ctx := context.Background()
+	b := Builtins()
+
+	result, err := Help(ctx)
+	assert.Nil(t, err)
+	assert.Contains(t, result.(*object.String).Value(), "help(value)")
+
+	result, err = Help(ctx, b["len"])
+	assert.Nil(t, err)
+	assert.Equal(t, result.(*object.String).Value(),
+		"len(container) -> int\n\nReturn length of container\n\nExample: len([1, 2, 3])")
+
+	_, err = Help(ctx, b["len"], b["len"])
+	assert.NotNil(t, err)
+}
+
+func TestHelpText(t *testing.T) {
+	noop := object.NewNoopBuiltin("noop").InModule("tools")
+	assert.Equal(t, HelpText(noop), "tools.noop(...)\n\nNo documentation available.")
+
+	mod := object.NewBuiltinsModule("tools", map[string]object.Object{
+		"noop":    object.NewNoopBuiltin("noop"),
+		"version": object.NewString("1.0"),
+	}).WithDocs("Handy tools", []object.FuncSpec{
+		{Name: "noop", Doc: "Do nothing", Returns: "nil"},
+		{Name: "version", Doc: "Version of the tools", Returns: "string"},
+	})
+	assert.Equal(t, HelpText(mod), "module tools\n\nHandy tools\n\n"+
+		"noop() -> nil\n    Do nothing\n"+
+		"version: string\n    Version of the tools")
+
+	// Builtins in a documented module get their spec
+	noopAttr, ok := mod.GetAttr("noop")
+	assert.True(t, ok)
+	assert.Equal(t, HelpText(noopAttr), "tools.noop() -> nil\n\nDo nothing")
+
+	text := HelpText(object.NewString("hi"))
+	assert.Contains(t, text, "string")
+	assert.Contains(t, text, ".split(")
+}
@@ -0,0 +1,225 @@
+package builtins
+
+import (
+	"context"
+	"fmt"
+	"iter"
+	"slices"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+)
+
+// The iterator builtins return lazy iterators that produce their values as
+// they are consumed, so a pipeline such as take(enumerate(rows), 10) doesn't
+// build intermediate lists. Like other iterators, they run again from the
+// start each time they are enumerated.
+
+// Enumerate returns an iterator of [index, value] pairs, counting from start.
+// Example: list(enumerate(["a", "b"], 1)) is [[1, "a"], [2, "b"]]
+func Enumerate(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) < 1 || len(args) > 2 {
+		return nil, fmt.Errorf("enumerate: expected 1 or 2 arguments, got %d", len(args))
+	}
+	items, err := enumerableArg("enumerate", args[0])
+	if err != nil {
+		return nil, err
+	}
+	var start int64
+	if len(args) == 2 {
+		if start, err = object.AsInt(args[1]); err != nil {
+			return nil, err
+		}
+	}
+	return object.NewIter("enumerate", func(ctx context.Context, fn func(key, value object.Object) bool) {
+		var i int64
+		for value := range values(ctx, items) {
+			pair := object.NewList([]object.Object{object.NewInt(start + i), value})
+			if !fn(object.NewInt(i), pair) {
+				return
+			}
+			i++
+		}
+	}), nil
+}
+
+// Zip returns an iterator of lists holding one value from each argument,
+// stopping when the shortest argument runs out.
+// Example: list(zip([1, 2], ["a", "b", "c"])) is [[1, "a"], [2, "b"]]
+func Zip(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) < 1 {
+		return nil, fmt.Errorf("zip: expected at least 1 argument, got %d", len(args))
+	}
+	sources := make([]object.Enumerable, len(args))
+	for i, arg := range args {
+		items, err := enumerableArg("zip", arg)
+		if err != nil {
+			return nil, err
+		}
+		sources[i] = items
+	}
+	return object.NewIter("zip", func(ctx context.Context, fn func(key, value object.Object) bool) {
+		nexts := make([]func() (object.Object, bool), len(sources))
+		for i, items := range sources {
+			next, stop := iter.Pull(values(ctx, items))
+			defer stop()
+			nexts[i] = next
+		}
+		for i := int64(0); ctx.Err() == nil; i++ {
+			row := make([]object.Object, len(nexts))
+			for j, next := range nexts {
+				value, ok := next()
+				if !ok {
+					return
+				}
+				row[j] = value
+			}
+			if !fn(object.NewInt(i), object.NewList(row)) {
+				return
+			}
+		}
+	}), nil
+}
+
+// Window returns an iterator of lists holding each run of size consecutive
+// values.
+// Example: list(window([1, 2, 3, 4], 3)) is [[1, 2, 3], [2, 3, 4]]
+func Window(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) != 2 {
+		return nil, fmt.Errorf("window: expected 2 arguments, got %d", len(args))
+	}
+	items, err := enumerableArg("window", args[0])
+	if err != nil {
+		return nil, err
+	}
+	size, err := object.AsInt(args[1])
+	if err != nil {
+		return nil, err
+	}
+	if size <= 0 {
+		return nil, object.ValueErrorf("window() size must be > 0 (%d given)", size)
+	}
+	return object.NewIter("window", func(ctx context.Context, fn func(key, value object.Object) bool) {
+		var i int64
+		var buf []object.Object
+		for value := range values(ctx, items) {
+			buf = append(buf, value)
+			if int64(len(buf)) < size {
+				continue
+			}
+			if int64(len(buf)) > size {
+				buf = buf[1:]
+			}
+			if !fn(object.NewInt(i), object.NewList(slices.Clone(buf))) {
+				return
+			}
+			i++
+		}
+	}), nil
+}
+
+// Take returns an iterator of the first n values.
+// Example: list(take(range(1000000), 3)) is [0, 1, 2]
+func Take(ctx context.Context, args ...object.Object) (object.Object, error) {
+	items, n, err := countArgs("take", args)
+	if err != nil {
+		return nil, err
+	}
+	return object.NewIter("take", func(ctx context.Context, fn func(key, value object.Object) bool) {
+		if n == 0 {
+			return
+		}
+		var i int64
+		for value := range values(ctx, items) {
+			if !fn(object.NewInt(i), value) {
+				return
+			}
+			i++
+			if i == n {
+				return
+			}
+		}
+	}), nil
+}
+
+// Skip returns an iterator of the values after the first n.
+// Example: list(skip([1, 2, 3], 2)) is [3]
+func Skip(ctx context.Context, args ...object.Object) (object.Object, error) {
+	items, n, err := countArgs("skip", args)
+	if err != nil {
+		return nil, err
+	}
+	return object.NewIter("skip", func(ctx context.Context, fn func(key, value object.Object) bool) {
+		var skipped, i int64
+		for value := range values(ctx, items) {
+			if skipped < n {
+				skipped++
+				continue
+			}
+			if !fn(object.NewInt(i), value) {
+				return
+			}
+			i++
+		}
+	}), nil
+}
+
+// chunkIter returns an iterator of lists of size values, where the last list
+// holds the values left over.
+func chunkIter(items object.Enumerable, size int64) *object.Iter {
+	return object.NewIter("chunk", func(ctx context.Context, fn func(key, value object.Object) bool) {
+		var i int64
+		var chunk []object.Object
+		for value := range values(ctx, items) {
+			chunk = append(chunk, value)
+			if int64(len(chunk)) < size {
+				continue
+			}
+			if !fn(object.NewInt(i), object.NewList(chunk)) {
+				return
+			}
+			chunk = nil
+			i++
+		}
+		if len(chunk) > 0 && ctx.Err() == nil {
+			fn(object.NewInt(i), object.NewList(chunk))
+		}
+	})
+}
+
+// countArgs checks the arguments of take and skip, which are an enumerable
+// and a count that isn't negative.
+func countArgs(name string, args []object.Object) (object.Enumerable, int64, error) {
+	if len(args) != 2 {
+		return nil, 0, fmt.Errorf("%s: expected 2 arguments, got %d", name, len(args))
+	}
+	items, err := enumerableArg(name, args[0])
+	if err != nil {
+		return nil, 0, err
+	}
+	n, err := object.AsInt(args[1])
+	if err != nil {
+		return nil, 0, err
+	}
+	if n < 0 {
+		return nil, 0, object.ValueErrorf("%s() count must not be negative (%d given)", name, n)
+	}
+	return items, n, nil
+}
+
+func enumerableArg(name string, arg object.Object) (object.Enumerable, error) {
+	items, ok := arg.(object.Enumerable)
+	if !ok {
+		return nil, object.TypeErrorf("%s() expected an enumerable (%s given)", name, arg.Type())
+	}
+	return items, nil
+}
+
+// values returns the values of an enumerable as a sequence, which ends early
+// if ctx is done.
+func values(ctx context.Context, items object.Enumerable) iter.Seq[object.Object] {
+	return func(yield func(object.Object) bool) {
+		items.Enumerate(ctx, func(_, value object.Object) bool {
+			return ctx.Err() == nil && yield(value)
+		})
+	}
+}
@@ -0,0 +1,62 @@
+package builtins
+
+import (
+	"context"
+	"testing"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+// collect calls a builtin that returns an iterator and returns the Inspect
+// of the values it produces.
+func collect(t *testing.T, fn object.BuiltinFunction, args ...object.Object) string {
+	t.Helper()
+	ctx := context.Background()
+	result, err := fn(ctx, args...)
+	assert.Nil(t, err)
+	assert.Equal(t, result.Type(), object.ITER)
+	items, err := List(ctx, result)
+	assert.Nil(t, err)
+	return items.Inspect()
+}
+
+func TestIterBuiltins(t *testing.T) {
+	abc := object.NewStringList([]string{"a", "b", "c"})
+	ints := object.NewRange(0, 5, 1)
+
+	assert.Equal(t, collect(t, Enumerate, abc), `[[0, "a"], [1, "b"], [2, "c"]]`)
+	assert.Equal(t, collect(t, Enumerate, abc, object.NewInt(1)), `[[1, "a"], [2, "b"], [3, "c"]]`)
+	assert.Equal(t, collect(t, Zip, abc, ints), `[["a", 0], ["b", 1], ["c", 2]]`)
+	assert.Equal(t, collect(t, Zip, ints, abc, abc), `[[0, "a", "a"], [1, "b", "b"], [2, "c", "c"]]`)
+	assert.Equal(t, collect(t, Window, ints, object.NewInt(3)), `[[0, 1, 2], [1, 2, 3], [2, 3, 4]]`)
+	assert.Equal(t, collect(t, Window, abc, object.NewInt(4)), `[]`)
+	assert.Equal(t, collect(t, Take, ints, object.NewInt(2)), `[0, 1]`)
+	assert.Equal(t, collect(t, Take, ints, object.NewInt(0)), `[]`)
+	assert.Equal(t, collect(t, Take, abc, object.NewInt(10)), `["a", "b", "c"]`)
+	assert.Equal(t, collect(t, Skip, ints, object.NewInt(3)), `[3, 4]`)
+	assert.Equal(t, collect(t, Skip, abc, object.NewInt(5)), `[]`)
+	assert.Equal(t, collect(t, Chunk, ints, object.NewInt(2)), `[[0, 1], [2, 3], [4]]`)
+
+	// Adaptors compose without building lists in between
+	taken, err := Take(context.Background(), object.NewRange(0, 1<<40, 1), object.NewInt(4))
+	assert.Nil(t, err)
+	assert.Equal(t, collect(t, Window, taken, object.NewInt(2)), `[[0, 1], [1, 2], [2, 3]]`)
+	assert.Equal(t, collect(t, Zip, taken, taken), `[[0, 0], [1, 1], [2, 2], [3, 3]]`)
+}
+
+func TestIterBuiltinErrors(t *testing.T) {
+	ctx := context.Background()
+	_, err := Enumerate(ctx, object.NewInt(1))
+	assert.Equal(t, err.Error(), "type error: enumerate() expected an enumerable (int given)")
+	_, err = Zip(ctx)
+	assert.Equal(t, err.Error(), "zip: expected at least 1 argument, got 0")
+	_, err = Window(ctx, object.NewList(nil), object.NewInt(0))
+	assert.Equal(t, err.Error(), "value error: window() size must be > 0 (0 given)")
+	_, err = Take(ctx, object.NewList(nil), object.NewInt(-1))
+	assert.Equal(t, err.Error(), "value error: take() count must not be negative (-1 given)")
+	_, err = Skip(ctx, object.NewList(nil), object.NewString("1"))
+	assert.NotNil(t, err)
+	_, err = Chunk(ctx, object.NewString("abc"), object.NewInt(1))
+	assert.NotNil(t, err)
+}
@@ -9,6 +9,7 @@ type Entry struct {
 	Fn      object.BuiltinFunction
 	Doc     string
 	Args    []string
+	Types   []string // accepted types of each argument; see CheckArgs
 	Returns string
 	Example string
 }
@@ -33,12 +34,13 @@ var registry = []Entry{
 		Example: "any([false, 0, \"yes\"])",
 	},
 	{
-		Name:    "assert",
-		Fn:      Assert,
-		Doc:     "Raise an error if condition is false",
-		Args:    []string{"condition", "message?"},
-		Returns: "nil",
-		Example: "assert(x > 0, \"x must be positive\")",
+		Name:    "avg",
+		Fn:      Avg,
+		Doc:     "Return the mean of the values, or of their keys",
+		Args:    []string{"items", "key?"},
+		Types:   []string{"", "callable"},
+		Returns: "float",
+		Example: "avg(orders, o => o.total)",
 	},
 	{
 		Name:    "bool",
@@ -75,9 +77,10 @@ var registry = []Entry{
 	{
 		Name:    "chunk",
 		Fn:      Chunk,
-		Doc:     "Split list into chunks of size n",
+		Doc:     "Split list into chunks of size n; iterators are chunked lazily",
 		Args:    []string{"list", "size"},
-		Returns: "list",
+		Types:   []string{"list|iter|range", "int"},
+		Returns: "list|iter",
 		Example: "chunk([1, 2, 3, 4, 5], 2)",
 	},
 	{
@@ -88,35 +91,108 @@ var registry = []Entry{
 		Returns: "any",
 		Example: "coalesce(nil, nil, \"default\")",
 	},
+	{
+		Name:    "compile",
+		Fn:      Compile,
+		Doc:     "Parse a string of code into a quote that can be run",
+		Args:    []string{"code"},
+		Types:   []string{"string"},
+		Returns: "quote",
+		Example: "compile(\"price * qty\")",
+	},
+	{
+		Name:    "copy",
+		Fn:      Copy,
+		Doc:     "Copy a value, deeply unless deep is false",
+		Args:    []string{"value", "deep?"},
+		Types:   []string{"", "bool"},
+		Returns: "any",
+		Example: "copy({a: [1, 2]})",
+	},
+	{
+		Name:    "count_by",
+		Fn:      CountBy,
+		Doc:     "Count the values with each key, returning a map of counts",
+		Args:    []string{"items", "key?"},
+		Types:   []string{"", "callable"},
+		Returns: "map",
+		Example: "count_by(orders, o => o.status)",
+	},
+	{
+		Name:    "decimal",
+		Fn:      Decimal,
+		Doc:     "Convert value to an exact decimal number",
+		Args:    []string{"value?"},
+		Returns: "decimal",
+		Example: "decimal(\"19.99\")",
+	},
 	{
 		Name:    "decode",
 		Fn:      Decode,
 		Doc:     "Decode data from a format (json, base64, hex, etc.)",
-		Args:    []string{"format", "data"},
+		Args:    []string{"data", "format"},
+		Types:   []string{"", "string"},
 		Returns: "any",
-		Example: "decode(\"json\", '{\"a\": 1}')",
+		Example: "decode('{\"a\": 1}', \"json\")",
+	},
+	{
+		Name:    "dir",
+		Fn:      Dir,
+		Doc:     "List the attributes and methods of a value",
+		Args:    []string{"value"},
+		Returns: "list",
+		Example: "dir(\"hello\")",
 	},
 	{
 		Name:    "encode",
 		Fn:      Encode,
 		Doc:     "Encode data to a format (json, base64, hex, etc.)",
-		Args:    []string{"format", "value"},
+		Args:    []string{"value", "format"},
+		Types:   []string{"", "string"},
 		Returns: "string",
-		Example: "encode(\"json\", {a: 1})",
+		Example: "encode({a: 1}, \"json\")",
+	},
+	{
+		Name:    "enumerate",
+		Fn:      Enumerate,
+		Doc:     "Iterate over [index, value] pairs, counting from start",
+		Args:    []string{"items", "start?"},
+		Types:   []string{"", "int"},
+		Returns: "iter",
+		Example: "list(enumerate([\"a\", \"b\"], 1))",
+	},
+	{
+		Name:    "equal",
+		Fn:      Equal,
+		Doc:     "Return true if values are equal in structure and type",
+		Args:    []string{"a", "b"},
+		Returns: "bool",
+		Example: "equal([1, {a: 2}], [1, {a: 2}])",
 	},
 	{
 		Name:    "error",
 		Fn:      Error,
 		Doc:     "Create an error value (does not throw)",
 		Args:    []string{"message", "args..."},
+		Types:   []string{"string"},
 		Returns: "error",
 		Example: "error(\"file %s not found\", name)",
 	},
+	{
+		Name:    "eval",
+		Fn:      Eval,
+		Doc:     "Run code in a sandboxed VM, returning its result or an error value",
+		Args:    []string{"code", "globals?"},
+		Types:   []string{"string", "map"},
+		Returns: "any",
+		Example: "eval(\"price * qty\", {price: 2, qty: 3})",
+	},
 	{
 		Name:    "filter",
 		Fn:      Filter,
 		Doc:     "Keep elements where fn returns true",
 		Args:    []string{"items", "fn"},
+		Types:   []string{"", "callable"},
 		Returns: "list",
 		Example: "filter([1, 2, 3, 4], x => x > 2)",
 	},
@@ -128,14 +204,40 @@ var registry = []Entry{
 		Returns: "float",
 		Example: "float(\"3.14\")",
 	},
+	{
+		Name:    "freeze",
+		Fn:      Freeze,
+		Doc:     "Make lists and maps read-only, including nested ones",
+		Args:    []string{"value"},
+		Returns: "any",
+		Example: "let config = freeze({debug: false})",
+	},
 	{
 		Name:    "getattr",
 		Fn:      GetAttr,
 		Doc:     "Get attribute from object with optional default",
 		Args:    []string{"obj", "name", "default?"},
+		Types:   []string{"", "string"},
 		Returns: "any",
 		Example: "getattr(obj, \"name\", \"unknown\")",
 	},
+	{
+		Name:    "group_by",
+		Fn:      GroupBy,
+		Doc:     "Group values by key, returning a map of lists",
+		Args:    []string{"items", "key"},
+		Types:   []string{"", "callable"},
+		Returns: "map",
+		Example: "group_by(orders, o => o.status)",
+	},
+	{
+		Name:    "help",
+		Fn:      Help,
+		Doc:     "Show the documentation of a function, module, or type",
+		Args:    []string{"value?"},
+		Returns: "string",
+		Example: "help(len)",
+	},
 	{
 		Name:    "int",
 		Fn:      Int,
@@ -168,35 +270,101 @@ var registry = []Entry{
 		Returns: "list",
 		Example: "list(range(5))",
 	},
+	{
+		Name:    "max",
+		Fn:      Max,
+		Doc:     "Return the largest value, or the value with the largest key",
+		Args:    []string{"items", "key?"},
+		Types:   []string{"", "callable"},
+		Returns: "any",
+		Example: "max(users, u => u.age)",
+	},
+	{
+		Name:    "memo",
+		Fn:      Memo,
+		Doc:     "Wrap a function with a cache keyed on its arguments",
+		Args:    []string{"fn"},
+		Types:   []string{"callable"},
+		Returns: "builtin",
+		Example: "let square = memo(x => x * x)",
+	},
+	{
+		Name:    "min",
+		Fn:      Min,
+		Doc:     "Return the smallest value, or the value with the smallest key",
+		Args:    []string{"items", "key?"},
+		Types:   []string{"", "callable"},
+		Returns: "any",
+		Example: "min([3, 1, 2])",
+	},
+	{
+		Name:    "on",
+		Fn:      On,
+		Doc:     "Register a handler for events emitted by the host",
+		Args:    []string{"event", "fn"},
+		Types:   []string{"string", "callable"},
+		Returns: "nil",
+		Example: "on(\"order.created\", order => process(order))",
+	},
 	{
 		Name:    "range",
 		Fn:      Range,
 		Doc:     "Generate a sequence of integers",
 		Args:    []string{"start_or_stop", "stop?", "step?"},
+		Types:   []string{"int", "int", "int"},
 		Returns: "range",
 		Example: "range(1, 10, 2)",
 	},
+	{
+		Name:    "repr",
+		Fn:      Repr,
+		Doc:     "Return debugging representation of value, with strings quoted",
+		Args:    []string{"value"},
+		Returns: "string",
+		Example: "repr([1, \"a\"])",
+	},
 	{
 		Name:    "reversed",
 		Fn:      Reversed,
 		Doc:     "Return reversed copy of list or string",
 		Args:    []string{"sequence"},
+		Types:   []string{"list|string"},
 		Returns: "list|string",
 		Example: "reversed([1, 2, 3])",
 	},
+	{
+		Name:    "run",
+		Fn:      Run,
+		Doc:     "Run a quote in a sandboxed VM, returning its result or an error value",
+		Args:    []string{"code", "globals?"},
+		Types:   []string{"quote", "map"},
+		Returns: "any",
+		Example: "run(quote { price * qty }, {price: 2, qty: 3})",
+	},
+	{
+		Name:    "skip",
+		Fn:      Skip,
+		Doc:     "Iterate over the values after the first n",
+		Args:    []string{"items", "n"},
+		Types:   []string{"", "int"},
+		Returns: "iter",
+		Example: "list(skip([1, 2, 3], 2))",
+	},
 	{
 		Name:    "sorted",
 		Fn:      Sorted,
-		Doc:     "Return sorted copy of list",
-		Args:    []string{"items", "key?"},
+		Doc:     "Return a sorted list of the values, with a less function or {key, reverse} options",
+		Args:    []string{"items", "options?"},
+		Types:   []string{"", "callable|map"},
 		Returns: "list",
-		Example: "sorted([3, 1, 2])",
+		Example: "sorted([3, 1, 2], {reverse: true})",
 	},
 	{
 		Name:    "sprintf",
 		Fn:      Sprintf,
 		Doc:     "Format string with arguments",
 		Args:    []string{"format", "args..."},
+		Types:   []string{"string"},
 		Returns: "string",
 		Example: "sprintf(\"%s: %d\", \"count\", 42)",
 	},
@@ -208,6 +376,42 @@ var registry = []Entry{
 		Returns: "string",
 		Example: "string(123)",
 	},
+	{
+		Name:    "stringbuilder",
+		Fn:      StringBuilder,
+		Doc:     "Create a buffer for building a string piece by piece",
+		Args:    []string{"initial?"},
+		Types:   []string{"string"},
+		Returns: "stringbuilder",
+		Example: "let sb = stringbuilder(); sb.write(\"a\"); sb.to_string()",
+	},
+	{
+		Name:    "sum",
+		Fn:      Sum,
+		Doc:     "Return the sum of the values, or of their keys",
+		Args:    []string{"items", "key?"},
+		Types:   []string{"", "callable"},
+		Returns: "int|float",
+		Example: "sum([1, 2, 3])",
+	},
+	{
+		Name:    "take",
+		Fn:      Take,
+		Doc:     "Iterate over the first n values",
+		Args:    []string{"items", "n"},
+		Types:   []string{"", "int"},
+		Returns: "iter",
+		Example: "list(take(range(1000000), 3))",
+	},
+	{
+		Name:    "to_source",
+		Fn:      ToSource,
+		Doc:     "Write a value as Risor code that evaluates to an equal value",
+		Args:    []string{"value", "indent?"},
+		Types:   []string{"", "string"},
+		Returns: "string",
+		Example: "to_source({name: \"risor\", tags: [\"a\"]}, \"  \")",
+	},
 	{
 		Name:    "type",
 		Fn:      Type,
@@ -216,13 +420,30 @@ var registry = []Entry{
 		Returns: "string",
 		Example: "type([1, 2, 3])",
 	},
+	{
+		Name:    "window",
+		Fn:      Window,
+		Doc:     "Iterate over lists of each size consecutive values",
+		Args:    []string{"items", "size"},
+		Types:   []string{"", "int"},
+		Returns: "iter",
+		Example: "list(window([1, 2, 3, 4], 3))",
+	},
+	{
+		Name:    "zip",
+		Fn:      Zip,
+		Doc:     "Iterate over lists of one value from each argument, until one runs out",
+		Args:    []string{"items", "more..."},
+		Returns: "iter",
+		Example: "list(zip([1, 2], [\"a\", \"b\"]))",
+	},
 }
 
 // Builtins returns all builtin functions as a map for use by the VM.
 func Builtins() map[string]object.Object {
 	result := make(map[string]object.Object, len(registry))
 	for _, entry := range registry {
-		result[entry.Name] = object.NewBuiltin(entry.Name, entry.Fn)
+		result[entry.Name] = object.NewBuiltin(entry.Name, entry.checked()).WithSpec(entry.spec())
 	}
 	return result
 }
@@ -231,13 +452,17 @@ func Builtins() map[string]object.Object {
 func Docs() []object.FuncSpec {
 	specs := make([]object.FuncSpec, len(registry))
 	for i, entry := range registry {
-		specs[i] = object.FuncSpec{
-			Name:    entry.Name,
-			Doc:     entry.Doc,
-			Args:    entry.Args,
-			Returns: entry.Returns,
-			Example: entry.Example,
-		}
+		specs[i] = entry.spec()
 	}
 	return specs
 }
+
+func (e Entry) spec() object.FuncSpec {
+	return object.FuncSpec{
+		Name:    e.Name,
+		Doc:     e.Doc,
+		Args:    e.Args,
+		Returns: e.Returns,
+		Example: e.Example,
+	}
+}
@@ -21,6 +21,7 @@ type Code struct {
 	source       string
 	filename     string
 	functionID   string
+	doc          string // Docstring of a function body
 
 	// Source map: one location per instruction for error reporting
 	locations []SourceLocation
@@ -43,6 +44,10 @@ type Code struct {
 	// environment at compile time (as opposed to globals defined in the
 	// script itself). Used for validation at run time.
 	envKeys []string
+
+	// exports stores the names of globals declared with the export
+	// statement (only set on root code).
+	exports []string
 }
 
 // CodeParams contains parameters for creating a new Code.
@@ -57,6 +62,7 @@ type CodeParams struct {
 	Source       string
 	Filename     string
 	FunctionID   string
+	Doc          string
 	Locations    []SourceLocation
 	MaxCallArgs  int
 	LocalCount   int
@@ -64,6 +70,7 @@ type CodeParams struct {
 	GlobalNames  []string
 	LocalNames   []string
 	EnvKeys      []string // Names of globals from compile-time env (for validation)
+	Exports      []string // Names of globals declared with export
 
 	ExceptionHandlers []ExceptionHandler
 }
@@ -90,6 +97,7 @@ func NewCode(params CodeParams) *Code {
 		source:            params.Source,
 		filename:          params.Filename,
 		functionID:        params.FunctionID,
+		doc:               params.Doc,
 		locations:         copyLocations(params.Locations),
 		maxCallArgs:       params.MaxCallArgs,
 		localCount:        params.LocalCount,
@@ -97,6 +105,7 @@ func NewCode(params CodeParams) *Code {
 		globalNames:       copyStrings(params.GlobalNames),
 		localNames:        copyStrings(params.LocalNames),
 		envKeys:           copyStrings(params.EnvKeys),
+		exports:           copyStrings(params.Exports),
 		exceptionHandlers: copyHandlers(params.ExceptionHandlers),
 	}
 
@@ -173,6 +182,12 @@ func (c *Code) Source() string {
 	return c.source
 }
 
+// Doc returns the docstring of a function body: the string literal that
+// begins it, if any.
+func (c *Code) Doc() string {
+	return c.doc
+}
+
 // Filename returns the source filename.
 func (c *Code) Filename() string {
 	return c.filename
@@ -277,14 +292,20 @@ func (c *Code) GetSourceLine(lineNum int) string {
 	return lines[lineNum-1]
 }
 
-// getRootSource returns the source from the root code for accurate line lookups.
-func (c *Code) getRootSource() string {
-	// Walk up to root to get the full source
+// Root returns the top-level code that this code is nested within, or the
+// code itself if it has no parent. Global variable indices in all nested code
+// refer to the root's global names.
+func (c *Code) Root() *Code {
 	root := c
 	for root.parent != nil {
 		root = root.parent
 	}
-	return root.source
+	return root
+}
+
+// getRootSource returns the source from the root code for accurate line lookups.
+func (c *Code) getRootSource() string {
+	return c.Root().source
 }
 
 // Stats returns statistics about this code block.
@@ -328,6 +349,18 @@ func (c *Code) EnvKeys() []string {
 	return keys
 }
 
+// Exports returns a copy of the names of the globals the script declared with
+// the export statement, in declaration order. A script without any export
+// statements returns nil.
+func (c *Code) Exports() []string {
+	if len(c.exports) == 0 {
+		return nil
+	}
+	names := make([]string, len(c.exports))
+	copy(names, c.exports)
+	return names
+}
+
 // FunctionNames returns the names of all named functions in this code.
 // Anonymous functions are not included.
 func (c *Code) FunctionNames() []string {
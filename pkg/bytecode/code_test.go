@@ -135,6 +135,12 @@ func TestCodeWithChildren(t *testing.T) {
 	if parent.ChildAt(1).ID() != "child2" {
 		t.Errorf("expected child 1 ID 'child2', got %v", parent.ChildAt(1).ID())
 	}
+	if child2.Root() != parent {
+		t.Errorf("expected child 2 root to be the parent")
+	}
+	if parent.Root() != parent {
+		t.Errorf("expected parent to be its own root")
+	}
 }
 
 func TestCodeGetSourceLine(t *testing.T) {
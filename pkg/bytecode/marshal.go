@@ -87,6 +87,7 @@ type codeDef struct {
 	IsNamed           bool                  `json:"is_named,omitempty"`
 	ChildIndices      []int                 `json:"child_indices,omitempty"` // Indices of children in codes array
 	FunctionID        string                `json:"function_id,omitempty"`
+	Doc               string                `json:"doc,omitempty"`
 	Instructions      []op.Code             `json:"instructions"`
 	Constants         []json.RawMessage     `json:"constants"`
 	Names             []string              `json:"names"`
@@ -98,6 +99,7 @@ type codeDef struct {
 	GlobalCount       int                   `json:"global_count"`
 	GlobalNames       []string              `json:"global_names,omitempty"`
 	LocalNames        []string              `json:"local_names,omitempty"`
+	Exports           []string              `json:"exports,omitempty"`
 	ExceptionHandlers []exceptionHandlerDef `json:"exception_handlers,omitempty"`
 }
 
@@ -180,6 +182,7 @@ func stateFromCode(code *Code) (*codeState, error) {
 			IsNamed:           c.IsNamed(),
 			ChildIndices:      childIndices,
 			FunctionID:        c.FunctionID(),
+			Doc:               c.Doc(),
 			Instructions:      instructions,
 			Constants:         constants,
 			Names:             names,
@@ -191,6 +194,7 @@ func stateFromCode(code *Code) (*codeState, error) {
 			GlobalCount:       c.GlobalCount(),
 			GlobalNames:       globalNames,
 			LocalNames:        localNames,
+			Exports:           c.Exports(),
 			ExceptionHandlers: handlers,
 		}
 	}
@@ -250,6 +254,7 @@ func codeFromState(state *codeState) (*Code, error) {
 			IsNamed:           def.IsNamed,
 			Children:          children,
 			FunctionID:        def.FunctionID,
+			Doc:               def.Doc,
 			Instructions:      def.Instructions,
 			Constants:         constants,
 			Names:             def.Names,
@@ -261,6 +266,7 @@ func codeFromState(state *codeState) (*Code, error) {
 			GlobalCount:       def.GlobalCount,
 			GlobalNames:       def.GlobalNames,
 			LocalNames:        def.LocalNames,
+			Exports:           def.Exports,
 			ExceptionHandlers: handlers,
 		})
 	}
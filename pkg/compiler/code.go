@@ -32,6 +32,7 @@ type Code struct {
 	source       string
 	functionID   string
 	filename     string // The source file this code came from
+	doc          string // Docstring of a function body
 
 	// rootSource points to the full original source from the root Code.
 	// Used for accurate line lookups in function bodies. Child codes set
@@ -51,6 +52,10 @@ type Code struct {
 	// Only set on root code. Used for validation at run time.
 	envKeys []string
 
+	// exports stores the names of globals declared with the export
+	// statement, in declaration order. Only set on root code.
+	exports []string
+
 	// Used during compilation only
 	pipeActive bool
 }
@@ -63,6 +68,7 @@ type codeSnapshot struct {
 	locationLen       int
 	childLen          int
 	exceptionHandlers int
+	exportLen         int
 	source            string
 	maxCallArgs       uint16
 }
@@ -77,6 +83,7 @@ func (c *Code) snapshot() codeSnapshot {
 		locationLen:       len(c.locations),
 		childLen:          len(c.children),
 		exceptionHandlers: len(c.exceptionHandlers),
+		exportLen:         len(c.exports),
 		source:            c.source,
 		maxCallArgs:       c.maxCallArgs,
 	}
@@ -92,6 +99,7 @@ func (c *Code) restore(s codeSnapshot) {
 	c.locations = c.locations[:s.locationLen]
 	c.children = c.children[:s.childLen]
 	c.exceptionHandlers = c.exceptionHandlers[:s.exceptionHandlers]
+	c.exports = c.exports[:s.exportLen]
 	c.source = s.source
 	c.maxCallArgs = s.maxCallArgs
 }
@@ -381,6 +389,7 @@ func (c *Code) toBytecodeWithMap(codeMap map[*Code]*bytecode.Code) *bytecode.Cod
 		Source:            c.source,
 		Filename:          c.filename,
 		FunctionID:        c.functionID,
+		Doc:               c.doc,
 		Locations:         locations,
 		MaxCallArgs:       int(c.maxCallArgs),
 		LocalCount:        c.LocalsCount(),
@@ -388,6 +397,7 @@ func (c *Code) toBytecodeWithMap(codeMap map[*Code]*bytecode.Code) *bytecode.Cod
 		GlobalNames:       c.GlobalNames(),
 		LocalNames:        c.LocalNames(),
 		EnvKeys:           c.envKeys,
+		Exports:           c.exports,
 		ExceptionHandlers: handlers,
 	})
 
@@ -44,14 +44,17 @@
 // The symbol table handles scope resolution and tracks which local variables
 // are captured by nested functions (free variables). When a function references
 // a variable from an enclosing scope, the compiler emits MakeCell instructions
-// to capture the variable into the closure.
+// to capture the variable into the closure. Variables that are already free in
+// the enclosing function are passed through with LoadCell instead.
 package compiler
 
 import (
 	"fmt"
 	"math"
+	"slices"
 	"sort"
 	"strings"
+	"unicode"
 
 	"github.com/deepnoodle-ai/risor/v2/internal/token"
 	"github.com/deepnoodle-ai/risor/v2/pkg/ast"
@@ -89,6 +92,12 @@ type Compiler struct {
 	// Names of globals to be available during compilation
 	globalNames []string
 
+	// How reads of names that are undefined at compile time are handled
+	undefinedNames UndefinedMode
+
+	// The language version the code is written for
+	languageVersion languageVersion
+
 	// Increments with each function compiled
 	funcIndex int
 
@@ -98,8 +107,17 @@ type Compiler struct {
 	// Original source code (for better error messages)
 	source string
 
+	// The source as runes, since token positions are rune offsets. Built
+	// on first use by nodeSource.
+	sourceRunes []rune
+
 	// Current AST node being compiled (used for source map tracking)
 	currentNode ast.Node
+
+	// Number of values returned by top-level named functions, for those
+	// functions where every return statement returns the same number of
+	// values. Used to check multi-variable assignments at compile time.
+	returnArity map[string]int
 }
 
 // Config holds compiler configuration options.
@@ -118,8 +136,39 @@ type Config struct {
 	// REPL-style incremental compilation where state must be preserved.
 	// If nil, a new code object is created.
 	Code *Code
+
+	// UndefinedNames selects how reads of names that aren't defined at
+	// compile time are handled. By default they are compile errors.
+	// Assignments always require the variable to be defined.
+	UndefinedNames UndefinedMode
+
+	// LanguageVersion pins the language version, such as "2.1", that the code
+	// is written for. Syntax added in a later version is a compile error, so
+	// hosts can keep stored scripts on a stable dialect. By default, all
+	// syntax up to LatestLanguageVersion is available.
+	LanguageVersion string
 }
 
+// UndefinedMode selects how the compiler handles reads of names that aren't
+// defined at compile time.
+type UndefinedMode int
+
+const (
+	// UndefinedError makes reading an undefined name a compile error.
+	UndefinedError UndefinedMode = iota
+
+	// UndefinedLateBound compiles reads of undefined names to a lookup by
+	// name, resolved against the VM's globals when the code runs. This lets
+	// hosts compile code without listing every global in GlobalNames.
+	// Reading a name that is still undefined at runtime raises a name error.
+	UndefinedLateBound
+
+	// UndefinedNil resolves names at runtime like UndefinedLateBound, but a
+	// name that is still undefined evaluates to nil. This suits templates,
+	// where a missing value should render as empty rather than fail.
+	UndefinedNil
+)
+
 // Compile compiles the given AST node and returns immutable bytecode.
 // This is the standard entry point for compiling code that will be executed.
 // Pass nil for cfg to use default settings.
@@ -137,13 +186,19 @@ func Compile(node ast.Node, cfg *Config) (*bytecode.Code, error) {
 
 // New creates and returns a new Compiler. Pass nil for cfg to use defaults.
 func New(cfg *Config) (*Compiler, error) {
-	c := &Compiler{}
+	c := &Compiler{languageVersion: latestVersion}
 	if cfg != nil {
 		c.globalNames = make([]string, len(cfg.GlobalNames))
 		copy(c.globalNames, cfg.GlobalNames) // isolate from caller
 		c.filename = cfg.Filename
 		c.source = cfg.Source
 		c.main = cfg.Code
+		c.undefinedNames = cfg.UndefinedNames
+		version, err := parseLanguageVersion(cfg.LanguageVersion)
+		if err != nil {
+			return nil, err
+		}
+		c.languageVersion = version
 	}
 	// Create a default, empty code object to compile into if the caller didn't
 	// supply one. If the caller did supply one, it may be a situation like the
@@ -256,6 +311,8 @@ func (c *Compiler) collectFunctionDeclarations(node ast.Node) error {
 				return err
 			}
 		}
+	case *ast.Export:
+		return c.collectFunctionDeclarations(node.Decl)
 	case *ast.Func:
 		// Only collect named functions at the top level
 		if node.Name != nil && c.current.parent == nil {
@@ -266,15 +323,55 @@ func (c *Compiler) collectFunctionDeclarations(node ast.Node) error {
 			if _, err := c.current.symbols.InsertConstant(functionName); err != nil {
 				return err
 			}
+			if arity, ok := staticReturnArity(node); ok {
+				if c.returnArity == nil {
+					c.returnArity = map[string]int{}
+				}
+				c.returnArity[functionName] = arity
+			}
 		}
 	}
 	return nil
 }
 
+// staticReturnArity reports the number of values a function returns, if it
+// can be determined from the function body alone. This is only the case when
+// the body ends with a return statement and every return statement in the
+// body (excluding nested functions) returns a tuple of the same length.
+func staticReturnArity(fn *ast.Func) (int, bool) {
+	if fn.Body == nil || len(fn.Body.Stmts) == 0 {
+		return 0, false
+	}
+	if _, ok := fn.Body.Stmts[len(fn.Body.Stmts)-1].(*ast.Return); !ok {
+		return 0, false
+	}
+	arity := -1
+	ast.Inspect(fn.Body, func(node ast.Node) bool {
+		switch node := node.(type) {
+		case *ast.Func, *ast.Quote:
+			return false
+		case *ast.Return:
+			tuple, ok := node.Value.(*ast.Tuple)
+			if !ok || (arity != -1 && arity != len(tuple.Items)) {
+				arity = 0
+				return false
+			}
+			if arity == -1 {
+				arity = len(tuple.Items)
+			}
+		}
+		return arity != 0
+	})
+	return arity, arity > 0
+}
+
 // compile the given AST node and all its children.
 func (c *Compiler) compile(node ast.Node) error {
 	// Track the current node for source location mapping
 	c.currentNode = node
+	if err := c.checkLanguageVersion(node); err != nil {
+		return err
+	}
 	switch node := node.(type) {
 	case *ast.Nil:
 		if err := c.compileNil(); err != nil {
@@ -340,6 +437,10 @@ func (c *Compiler) compile(node ast.Node) error {
 		if err := c.compileList(node); err != nil {
 			return err
 		}
+	case *ast.Tuple:
+		if err := c.compileTuple(node); err != nil {
+			return err
+		}
 	case *ast.Map:
 		if err := c.compileMap(node); err != nil {
 			return err
@@ -392,6 +493,10 @@ func (c *Compiler) compile(node ast.Node) error {
 		if err := c.compileMultiVar(node); err != nil {
 			return err
 		}
+	case *ast.MultiAssign:
+		if err := c.compileMultiAssign(node); err != nil {
+			return err
+		}
 	case *ast.ObjectDestructure:
 		if err := c.compileObjectDestructure(node); err != nil {
 			return err
@@ -408,10 +513,36 @@ func (c *Compiler) compile(node ast.Node) error {
 		if err := c.compileTry(node); err != nil {
 			return err
 		}
+	case *ast.With:
+		if err := c.compileWith(node); err != nil {
+			return err
+		}
 	case *ast.Throw:
 		if err := c.compileThrow(node); err != nil {
 			return err
 		}
+	case *ast.Assert:
+		if err := c.compileAssert(node); err != nil {
+			return err
+		}
+	case *ast.Del:
+		if err := c.compileDel(node); err != nil {
+			return err
+		}
+	case *ast.Export:
+		if err := c.compileExport(node); err != nil {
+			return err
+		}
+	case *ast.Import:
+		if err := c.compileImport(node); err != nil {
+			return err
+		}
+	case *ast.Quote:
+		if err := c.compileQuote(node); err != nil {
+			return err
+		}
+	case *ast.Spread:
+		return c.formatError("spread is only allowed in list literals, map literals, and call arguments", node.Pos())
 	case *ast.BadExpr:
 		return c.formatError("syntax error in expression", node.Pos())
 	case *ast.BadStmt:
@@ -464,7 +595,7 @@ func (c *Compiler) compileProgram(node *ast.Program) error {
 		c.emit(op.Nil)
 	} else {
 		for i, stmt := range statements {
-			if err := c.compile(stmt); err != nil {
+			if err := c.compileStmt(stmt); err != nil {
 				return err
 			}
 			if i < count-1 {
@@ -495,7 +626,7 @@ func (c *Compiler) compileBlock(node *ast.Block) error {
 		c.emit(op.Nil)
 	} else {
 		for i, stmt := range statements {
-			if err := c.compile(stmt); err != nil {
+			if err := c.compileStmt(stmt); err != nil {
 				return err
 			}
 			if i < count-1 {
@@ -522,7 +653,7 @@ func (c *Compiler) compileFunctionBlock(node *ast.Block) error {
 	statements := normalizeFunctionBlock(node)
 	count := len(statements)
 	for i, stmt := range statements {
-		if err := c.compile(stmt); err != nil {
+		if err := c.compileStmt(stmt); err != nil {
 			return err
 		}
 		if i < count-1 {
@@ -565,6 +696,14 @@ func (c *Compiler) compileIdent(node *ast.Ident) error {
 	}
 	resolution, found := c.current.symbols.Resolve(name)
 	if !found {
+		switch c.undefinedNames {
+		case UndefinedLateBound:
+			c.emit(op.LoadName, c.current.addName(name))
+			return nil
+		case UndefinedNil:
+			c.emit(op.LoadNameOrNil, c.current.addName(name))
+			return nil
+		}
 		return c.formatUndefinedVariableError(name, node.Pos())
 	}
 	c.emitLoad(resolution)
@@ -577,6 +716,9 @@ func (c *Compiler) compileMultiVar(node *ast.MultiVar) error {
 	if len(names) > math.MaxUint16 {
 		return c.formatError("too many variables in multi-variable assignment", node.Pos())
 	}
+	if err := c.checkReturnArity(expr, len(names), node.Pos()); err != nil {
+		return err
+	}
 	// Compile the RHS value
 	if err := c.compile(expr); err != nil {
 		return err
@@ -604,6 +746,70 @@ func (c *Compiler) compileMultiVar(node *ast.MultiVar) error {
 	return nil
 }
 
+// checkReturnArity returns an error if a value unpacked into count variables
+// calls a function whose return count is known and doesn't match.
+func (c *Compiler) checkReturnArity(expr ast.Expr, count int, pos token.Position) error {
+	call, ok := expr.(*ast.Call)
+	if !ok {
+		return nil
+	}
+	ident, ok := call.Fun.(*ast.Ident)
+	if !ok {
+		return nil
+	}
+	if arity, ok := c.returnArity[ident.Name]; ok && arity != count {
+		if res, found := c.current.symbols.Resolve(ident.Name); found && res.Scope() == Global {
+			return c.formatError(fmt.Sprintf(
+				"function %q returns %d values but %d variables were given",
+				ident.Name, arity, count), pos)
+		}
+	}
+	return nil
+}
+
+func (c *Compiler) compileMultiAssign(node *ast.MultiAssign) error {
+	names := node.Names
+	if len(names) > math.MaxUint16 {
+		return c.formatError("too many variables in multi-variable assignment", node.Pos())
+	}
+	if node.Unpack {
+		if err := c.checkReturnArity(node.Value, len(names), node.Pos()); err != nil {
+			return err
+		}
+	}
+	// Compile the RHS value. It's evaluated in full before any variable is
+	// assigned, so "a, b = b, a" swaps a and b.
+	if err := c.compile(node.Value); err != nil {
+		return err
+	}
+	if node.Unpack {
+		c.emit(op.Unpack, uint16(len(names)))
+	}
+	// Assign the variables in reverse order, since the last unpacked value is
+	// on top of the stack. A chain such as "x = y = 0" copies the value for
+	// each variable but the first.
+	for i := len(names) - 1; i >= 0; i-- {
+		name := names[i].Name
+		if !node.Unpack && i > 0 {
+			c.emit(op.Copy, 0)
+		}
+		// The blank identifier discards the value
+		if IsBlankIdentifier(name) {
+			c.emit(op.PopTop)
+			continue
+		}
+		resolution, found := c.current.symbols.Resolve(name)
+		if !found {
+			return c.formatUndefinedVariableError(name, names[i].Pos())
+		}
+		if resolution.symbol.IsConstant() {
+			return c.formatError(fmt.Sprintf("cannot assign to constant %q", name), names[i].Pos())
+		}
+		c.emitStore(resolution)
+	}
+	return nil
+}
+
 func (c *Compiler) compileObjectDestructure(node *ast.ObjectDestructure) error {
 	bindings := node.Bindings
 	if len(bindings) > math.MaxUint16 {
@@ -742,20 +948,26 @@ func (c *Compiler) compileArrayDestructure(node *ast.ArrayDestructure) error {
 // The parameter at paramIdx contains the value to destructure, and the
 // destructured values are stored into local variables.
 func (c *Compiler) emitDestructurePreamble(param ast.FuncParam, paramIdx int) error {
+	// Load the parameter value onto the stack
+	c.emit(op.LoadFast, uint16(paramIdx))
+	return c.emitDestructurePattern(param)
+}
+
+// emitDestructurePattern emits bytecode that destructures the value at the top
+// of the stack according to the given pattern, consuming the value. Nested
+// patterns are handled recursively.
+func (c *Compiler) emitDestructurePattern(param ast.FuncParam) error {
 	switch p := param.(type) {
 	case *ast.ObjectDestructureParam:
-		return c.emitObjectDestructurePreamble(p, paramIdx)
+		return c.emitObjectDestructurePattern(p)
 	case *ast.ArrayDestructureParam:
-		return c.emitArrayDestructurePreamble(p, paramIdx)
+		return c.emitArrayDestructurePattern(p)
 	}
-	return nil
+	return c.formatError(fmt.Sprintf("unexpected destructuring pattern: %T", param), param.Pos())
 }
 
-// emitObjectDestructurePreamble emits bytecode to destructure an object parameter.
-func (c *Compiler) emitObjectDestructurePreamble(param *ast.ObjectDestructureParam, paramIdx int) error {
-	// Load the parameter value onto the stack
-	c.emit(op.LoadFast, uint16(paramIdx))
-
+// emitObjectDestructurePattern emits bytecode to destructure an object pattern.
+func (c *Compiler) emitObjectDestructurePattern(param *ast.ObjectDestructureParam) error {
 	// For each binding, load the property and store it in a variable
 	for _, binding := range param.Bindings {
 		// Duplicate the object on the stack (we need it for each property access)
@@ -770,20 +982,17 @@ func (c *Compiler) emitObjectDestructurePreamble(param *ast.ObjectDestructurePar
 
 		// Handle default value if present
 		if binding.Default != nil {
-			// Stack has the value at TOS. Check if it's nil.
-			c.emit(op.Copy, 0) // Duplicate the value
-			jumpPos := c.emit(op.PopJumpForwardIfNotNil, Placeholder)
-			// If nil, pop the nil value and use the default
-			c.emit(op.PopTop)
-			if err := c.compile(binding.Default); err != nil {
+			if err := c.emitDestructureDefault(binding.Default); err != nil {
 				return err
 			}
-			c.emit(op.Nop)
-			delta, err := c.calculateDelta(jumpPos)
-			if err != nil {
+		}
+
+		// Nested pattern: destructure the property value further
+		if binding.Pattern != nil {
+			if err := c.emitDestructurePattern(binding.Pattern); err != nil {
 				return err
 			}
-			c.changeOperand(jumpPos, delta)
+			continue
 		}
 
 		// Determine the variable name (alias if provided, otherwise key)
@@ -791,19 +1000,9 @@ func (c *Compiler) emitObjectDestructurePreamble(param *ast.ObjectDestructurePar
 		if varName == "" {
 			varName = binding.Key
 		}
-
-		// Blank identifier "_" - discard the value
-		if IsBlankIdentifier(varName) {
-			c.emit(op.PopTop)
-			continue
-		}
-
-		// Find the variable (already inserted in symbol table during setup)
-		resolution, found := c.current.symbols.Resolve(varName)
-		if !found {
-			return c.formatError(fmt.Sprintf("undefined variable %q in destructuring", varName), param.Pos())
+		if err := c.emitDestructureStore(varName, param.Pos()); err != nil {
+			return err
 		}
-		c.emit(op.StoreFast, resolution.symbol.Index())
 	}
 
 	// Pop the remaining object from the stack
@@ -811,55 +1010,75 @@ func (c *Compiler) emitObjectDestructurePreamble(param *ast.ObjectDestructurePar
 	return nil
 }
 
-// emitArrayDestructurePreamble emits bytecode to destructure an array parameter.
-func (c *Compiler) emitArrayDestructurePreamble(param *ast.ArrayDestructureParam, paramIdx int) error {
+// emitArrayDestructurePattern emits bytecode to destructure an array pattern.
+func (c *Compiler) emitArrayDestructurePattern(param *ast.ArrayDestructureParam) error {
 	elements := param.Elements
 
-	// Load the parameter value onto the stack
-	c.emit(op.LoadFast, uint16(paramIdx))
-
 	// Emit the Unpack opcode to unpack the array onto the stack
 	c.emit(op.Unpack, uint16(len(elements)))
 
 	// Store each value in reverse order (like MultiVar)
 	for i := len(elements) - 1; i >= 0; i-- {
 		element := elements[i]
-		varName := element.Name.Name
 
 		// Handle default value if present
 		if element.Default != nil {
-			// Stack has the value at TOS. Check if it's nil.
-			c.emit(op.Copy, 0) // Duplicate the value
-			jumpPos := c.emit(op.PopJumpForwardIfNotNil, Placeholder)
-			// If nil, pop the nil value and use the default
-			c.emit(op.PopTop)
-			if err := c.compile(element.Default); err != nil {
-				return err
-			}
-			c.emit(op.Nop)
-			delta, err := c.calculateDelta(jumpPos)
-			if err != nil {
+			if err := c.emitDestructureDefault(element.Default); err != nil {
 				return err
 			}
-			c.changeOperand(jumpPos, delta)
 		}
 
-		// Blank identifier "_" - discard the value
-		if IsBlankIdentifier(varName) {
-			c.emit(op.PopTop)
+		// Nested pattern: destructure the element further
+		if element.Pattern != nil {
+			if err := c.emitDestructurePattern(element.Pattern); err != nil {
+				return err
+			}
 			continue
 		}
 
-		// Find the variable (already inserted in symbol table during setup)
-		resolution, found := c.current.symbols.Resolve(varName)
-		if !found {
-			return c.formatError(fmt.Sprintf("undefined variable %q in destructuring", varName), param.Pos())
+		if err := c.emitDestructureStore(element.Name.Name, param.Pos()); err != nil {
+			return err
 		}
-		c.emit(op.StoreFast, resolution.symbol.Index())
 	}
 	return nil
 }
 
+// emitDestructureDefault replaces a nil value at the top of the stack with
+// the given default expression.
+func (c *Compiler) emitDestructureDefault(def ast.Expr) error {
+	// Stack has the value at TOS. Check if it's nil.
+	c.emit(op.Copy, 0) // Duplicate the value
+	jumpPos := c.emit(op.PopJumpForwardIfNotNil, Placeholder)
+	// If nil, pop the nil value and use the default
+	c.emit(op.PopTop)
+	if err := c.compile(def); err != nil {
+		return err
+	}
+	c.emit(op.Nop)
+	delta, err := c.calculateDelta(jumpPos)
+	if err != nil {
+		return err
+	}
+	c.changeOperand(jumpPos, delta)
+	return nil
+}
+
+// emitDestructureStore pops the top of the stack into the named local
+// variable, which was inserted in the symbol table during function setup.
+func (c *Compiler) emitDestructureStore(varName string, pos token.Position) error {
+	// Blank identifier "_" - discard the value
+	if IsBlankIdentifier(varName) {
+		c.emit(op.PopTop)
+		return nil
+	}
+	resolution, found := c.current.symbols.Resolve(varName)
+	if !found {
+		return c.formatError(fmt.Sprintf("undefined variable %q in destructuring", varName), pos)
+	}
+	c.emit(op.StoreFast, resolution.symbol.Index())
+	return nil
+}
+
 func (c *Compiler) compileMatch(node *ast.Match) error {
 	// Compile the subject expression (remains on stack for comparisons)
 	if err := c.compile(node.Subject); err != nil {
@@ -980,16 +1199,40 @@ func (c *Compiler) compilePatternMatch(pattern ast.Pattern) error {
 }
 
 func (c *Compiler) compileSlice(node *ast.Slice) error {
-	if err := c.compile(node.X); err != nil {
+	count, err := c.compileSliceArgs(node)
+	if err != nil {
 		return err
 	}
+	c.emit(op.Slice, count)
+	return nil
+}
+
+// compileSliceArgs compiles the container and bounds of a slice, and the step
+// if there is one, and returns the number of slice arguments pushed after the
+// container.
+func (c *Compiler) compileSliceArgs(node *ast.Slice) (uint16, error) {
+	if err := c.compile(node.X); err != nil {
+		return 0, err
+	}
+	if node.Step != nil {
+		// The default bounds depend on the sign of the step, so omitted
+		// bounds are passed as nil and resolved by the container
+		for _, expr := range []ast.Expr{node.High, node.Low, node.Step} {
+			if expr == nil {
+				c.emit(op.Nil)
+			} else if err := c.compile(expr); err != nil {
+				return 0, err
+			}
+		}
+		return 3, nil
+	}
 	high := node.High
 	if high == nil {
 		c.emit(op.Copy, 0)
 		c.emit(op.Length)
 	} else {
 		if err := c.compile(high); err != nil {
-			return err
+			return 0, err
 		}
 	}
 	low := node.Low
@@ -997,11 +1240,10 @@ func (c *Compiler) compileSlice(node *ast.Slice) error {
 		c.emit(op.LoadConst, c.constant(int64(0)))
 	} else {
 		if err := c.compile(low); err != nil {
-			return err
+			return 0, err
 		}
 	}
-	c.emit(op.Slice)
-	return nil
+	return 2, nil
 }
 
 func (c *Compiler) compileString(node *ast.String) error {
@@ -1046,6 +1288,59 @@ func (c *Compiler) compileString(node *ast.String) error {
 	return nil
 }
 
+// compileQuote compiles a quote expression. The quoted code itself isn't
+// compiled. Its source is split around any unquote calls, and the argument
+// of each call is compiled in place so it's evaluated when the quote is.
+func (c *Compiler) compileQuote(node *ast.Quote) error {
+	unquotes := node.Unquotes()
+	if len(unquotes) > math.MaxUint16 {
+		return c.formatError("quote exceeded max unquote count", node.Pos())
+	}
+	for _, call := range unquotes {
+		if len(call.Args) != 1 {
+			return c.formatError("unquote takes exactly one argument", call.Pos())
+		}
+	}
+
+	if c.sourceRunes == nil {
+		c.sourceRunes = []rune(c.source)
+	}
+	start, end := node.Body.Lbrace.Char+1, node.Body.Rbrace.Char
+	if c.source == "" || start < 0 || end > len(c.sourceRunes) || end < start {
+		// Without the original source, the quote can only be rebuilt from
+		// the AST, which leaves nowhere to splice unquoted values.
+		if len(unquotes) > 0 {
+			return c.formatError("unquote requires the original source code", unquotes[0].Pos())
+		}
+		c.emit(op.LoadConst, c.constant(node.Body.String()))
+		c.emit(op.BuildQuote, 0)
+		return nil
+	}
+
+	// Push the source fragments alternating with the unquoted values
+	for i, call := range unquotes {
+		fragment := string(c.sourceRunes[start:call.Pos().Char])
+		if i == 0 {
+			fragment = strings.TrimLeftFunc(fragment, unicode.IsSpace)
+		}
+		c.emit(op.LoadConst, c.constant(fragment))
+		if err := c.compile(call.Args[0]); err != nil {
+			return err
+		}
+		start = call.End().Char
+	}
+	fragment := string(c.sourceRunes[start:end])
+	if len(unquotes) == 0 {
+		fragment = strings.TrimSpace(fragment)
+	} else {
+		fragment = strings.TrimRightFunc(fragment, unicode.IsSpace)
+	}
+	c.emit(op.LoadConst, c.constant(fragment))
+	c.currentNode = node
+	c.emit(op.BuildQuote, uint16(len(unquotes)))
+	return nil
+}
+
 func (c *Compiler) compilePipe(node *ast.Pipe) error {
 	if c.current.pipeActive {
 		return c.formatError("invalid nested pipe", node.Pos())
@@ -1109,41 +1404,30 @@ func (c *Compiler) compilePostfix(node *ast.Postfix) error {
 
 	case *ast.Index:
 		// Index expression: arr[i]++
-		// 1. Load the current value
-		if err := c.compile(x.X); err != nil {
+		// 1. Load the current value, keeping the container and index
+		if err := c.compileSubscrTarget(x); err != nil {
 			return err
 		}
-		if err := c.compile(x.Index); err != nil {
-			return err
-		}
-		c.emit(op.BinarySubscr)
 		// 2. Add the increment amount
 		c.emit(op.LoadConst, c.constant(amount))
 		c.emit(op.BinaryOp, uint16(op.Add))
 		// 3. Store back
-		if err := c.compile(x.X); err != nil {
-			return err
-		}
-		if err := c.compile(x.Index); err != nil {
-			return err
-		}
-		c.emit(op.StoreSubscr)
+		c.emitStoreSubscrTarget()
 
 	case *ast.GetAttr:
 		// Attribute expression: obj.x++
 		idx := c.current.addName(x.Attr.Name)
-		// 1. Load the current attribute value
+		// 1. Load the current attribute value, keeping the object
 		if err := c.compile(x.X); err != nil {
 			return err
 		}
+		c.emit(op.Copy, 0)
 		c.emit(op.LoadAttr, idx)
 		// 2. Add the increment amount
 		c.emit(op.LoadConst, c.constant(amount))
 		c.emit(op.BinaryOp, uint16(op.Add))
 		// 3. Store back
-		if err := c.compile(x.X); err != nil {
-			return err
-		}
+		c.emit(op.Swap, 1)
 		c.emit(op.StoreAttr, idx)
 
 	default:
@@ -1207,31 +1491,30 @@ func (c *Compiler) compilePrefix(node *ast.Prefix) error {
 		c.emit(op.UnaryNot)
 	case "-":
 		c.emit(op.UnaryNegative)
+	case "~":
+		c.emit(op.UnaryInvert)
 	}
 	return nil
 }
 
 func (c *Compiler) compileCall(node *ast.Call) error {
-	args := node.Args
-	argc := len(args)
-	if argc > MaxArgs {
-		return c.formatError(fmt.Sprintf("max args limit of %d exceeded (got %d)", MaxArgs, argc), node.Pos())
-	}
-
-	// Check if any arguments are spread expressions
-	hasSpread := false
-	for _, arg := range args {
-		if _, ok := arg.(*ast.Spread); ok {
-			hasSpread = true
-			break
-		}
-	}
-
 	if err := c.compile(node.Fun); err != nil {
 		return err
 	}
+	return c.compileCallArgs(node.Args, node.Pos())
+}
 
-	if !hasSpread {
+// compileCallArgs compiles the arguments of a call whose callee is already on
+// the stack, then emits the call instruction. Only arguments written with
+// "..." are spread; all other arguments are passed through as-is, even when
+// they evaluate to lists.
+func (c *Compiler) compileCallArgs(args []ast.Node, pos token.Position) error {
+	argc := len(args)
+	if argc > MaxArgs {
+		return c.formatError(fmt.Sprintf("max args limit of %d exceeded (got %d)", MaxArgs, argc), pos)
+	}
+
+	if !hasSpread(args) {
 		// Fast path: no spread, use regular Call
 		for _, arg := range args {
 			if err := c.compile(arg); err != nil {
@@ -1246,6 +1529,11 @@ func (c *Compiler) compileCall(node *ast.Call) error {
 		return nil
 	}
 
+	if c.current.pipeActive {
+		// For pipe, we can't easily support spread (would need PartialSpread)
+		return c.formatError("spread arguments not supported in pipe expressions", pos)
+	}
+
 	// Slow path: has spread, build args list then use CallSpread
 	c.emit(op.BuildList, 0) // Start with empty list
 	for _, arg := range args {
@@ -1256,21 +1544,27 @@ func (c *Compiler) compileCall(node *ast.Call) error {
 			}
 			c.emit(op.ListExtend)
 		} else {
-			// Normal arg: append to the args list
+			// Normal arg: append to the args list as a single value
 			if err := c.compile(arg); err != nil {
 				return err
 			}
 			c.emit(op.ListAppend)
 		}
 	}
-	if c.current.pipeActive {
-		// For pipe, we can't easily support spread (would need PartialSpread)
-		return c.formatError("spread arguments not supported in pipe expressions", node.Pos())
-	}
 	c.emit(op.CallSpread)
 	return nil
 }
 
+// hasSpread reports whether any of the arguments are spread expressions.
+func hasSpread(args []ast.Node) bool {
+	for _, arg := range args {
+		if _, ok := arg.(*ast.Spread); ok {
+			return true
+		}
+	}
+	return false
+}
+
 func (c *Compiler) compileObjectCall(node *ast.ObjectCall) error {
 	if err := c.compile(node.X); err != nil {
 		return err
@@ -1285,21 +1579,25 @@ func (c *Compiler) compileObjectCall(node *ast.ObjectCall) error {
 	name := method.Fun.String()
 	// Restore currentNode so LoadAttr gets the method name position
 	c.currentNode = method.Fun
-	c.emit(op.LoadAttr, c.current.addName(name))
-	args := method.Args
-	argc := len(args)
-	if argc > MaxArgs {
-		return c.formatError(fmt.Sprintf("max args limit of %d exceeded (got %d)", MaxArgs, argc), node.Pos())
-	}
-	for _, arg := range args {
-		if err := c.compile(arg); err != nil {
+	if c.current.pipeActive || hasSpread(method.Args) {
+		c.emit(op.LoadAttr, c.current.addName(name))
+		if err := c.compileCallArgs(method.Args, node.Pos()); err != nil {
 			return err
 		}
-	}
-	if c.current.pipeActive {
-		c.emit(op.Partial, uint16(len(args)))
 	} else {
-		c.emit(op.Call, uint16(len(args)))
+		// Common case: call the method in one instruction, which avoids
+		// creating a bound method object
+		argc := len(method.Args)
+		if argc > MaxArgs {
+			return c.formatError(fmt.Sprintf("max args limit of %d exceeded (got %d)", MaxArgs, argc), node.Pos())
+		}
+		for _, arg := range method.Args {
+			if err := c.compile(arg); err != nil {
+				return err
+			}
+		}
+		c.currentNode = method.Fun
+		c.emit(op.CallMethod, c.current.addName(name), uint16(argc))
 	}
 	if node.Optional {
 		c.emit(op.Nop)
@@ -1339,10 +1637,38 @@ func (c *Compiler) compileIndex(node *ast.Index) error {
 	if err := c.compile(node.X); err != nil {
 		return err
 	}
+	// Handle optional chaining (?.[)
+	var jumpPos int
+	if node.Optional {
+		c.emit(op.Copy, 0)
+		jumpPos = c.emit(op.PopJumpForwardIfNil, Placeholder)
+	}
 	if err := c.compile(node.Index); err != nil {
 		return err
 	}
 	c.emit(op.BinarySubscr)
+	if node.Optional {
+		c.emit(op.Nop)
+		delta, _ := c.calculateDelta(jumpPos)
+		c.changeOperand(jumpPos, delta)
+	}
+	return nil
+}
+
+func (c *Compiler) compileTuple(node *ast.Tuple) error {
+	count := len(node.Items)
+	if count > math.MaxUint16 {
+		return c.formatError("too many return values", node.Pos())
+	}
+	for _, expr := range node.Items {
+		if _, ok := expr.(*ast.Spread); ok {
+			return c.formatError("spread is not allowed in return values", expr.Pos())
+		}
+		if err := c.compile(expr); err != nil {
+			return err
+		}
+	}
+	c.emit(op.BuildTuple, uint16(count))
 	return nil
 }
 
@@ -1404,15 +1730,8 @@ func (c *Compiler) compileMap(node *ast.Map) error {
 	if !hasSpread {
 		// Fast path: no spread, use simple BuildMap
 		for _, item := range items {
-			switch k := item.Key.(type) {
-			case *ast.String:
-				if err := c.compile(k); err != nil {
-					return err
-				}
-			case *ast.Ident:
-				c.emit(op.LoadConst, c.constant(k.String()))
-			default:
-				return c.formatError(fmt.Sprintf("invalid map key type: %T", item.Key), item.Key.Pos())
+			if err := c.compileMapKey(item); err != nil {
+				return err
 			}
 			if err := c.compile(item.Value); err != nil {
 				return err
@@ -1439,15 +1758,8 @@ func (c *Compiler) compileMap(node *ast.Map) error {
 			c.emit(op.MapMerge)
 		} else {
 			// Normal key-value: set in the map
-			switch k := item.Key.(type) {
-			case *ast.String:
-				if err := c.compile(k); err != nil {
-					return err
-				}
-			case *ast.Ident:
-				c.emit(op.LoadConst, c.constant(k.String()))
-			default:
-				return c.formatError(fmt.Sprintf("invalid map key type: %T", item.Key), item.Key.Pos())
+			if err := c.compileMapKey(item); err != nil {
+				return err
 			}
 			if err := c.compile(item.Value); err != nil {
 				return err
@@ -1458,6 +1770,30 @@ func (c *Compiler) compileMap(node *ast.Map) error {
 	return nil
 }
 
+// compileMapKey pushes the key of a map item onto the stack. Map keys are
+// always strings: bare identifiers and string literals are used as-is, while
+// computed keys ([expr]) are evaluated at runtime and type checked by the VM.
+func (c *Compiler) compileMapKey(item ast.MapItem) error {
+	if item.Computed {
+		return c.compile(item.Key)
+	}
+	switch k := item.Key.(type) {
+	case *ast.String:
+		return c.compile(k)
+	case *ast.Ident:
+		c.emit(op.LoadConst, c.constant(k.String()))
+		return nil
+	case *ast.Int, *ast.Float, *ast.Bool, *ast.Nil:
+		return c.formatError(fmt.Sprintf(
+			"map key must be a string (got %s); use a string key like \"%s\" or a computed key [%s]",
+			k.String(), k.String(), k.String()), item.Key.Pos())
+	default:
+		return c.formatError(fmt.Sprintf(
+			"invalid map key %s; use a computed key [%s] to evaluate an expression",
+			k.String(), k.String()), item.Key.Pos())
+	}
+}
+
 func (c *Compiler) compileFunc(node *ast.Func) error {
 	// Python cell variables:
 	// https://stackoverflow.com/questions/23757143/what-is-a-cell-in-the-context-of-an-interpreter-or-compiler
@@ -1479,6 +1815,7 @@ func (c *Compiler) compileFunc(node *ast.Func) error {
 	functionID := fmt.Sprintf("%d", c.funcIndex)
 	bodySource := c.extractFunctionBodySource(node)
 	code := c.current.newChild(functionName, bodySource, functionID)
+	code.doc = functionDoc(node.Body)
 
 	// Setting current here means subsequent calls to compile will add to this
 	// code object instead of the parent.
@@ -1592,9 +1929,21 @@ func (c *Compiler) compileFunc(node *ast.Func) error {
 		}
 	}
 
+	// Add the function's own name to its symbol table. This supports recursive
+	// calls to the function, including from named function expressions that
+	// are assigned to a different variable. The VM stores the function object
+	// in the slot directly after the parameters and rest parameter, so this
+	// must be inserted before any destructured variable names.
+	if code.isNamed {
+		if _, err := code.symbols.InsertConstant(functionName); err != nil {
+			return err
+		}
+	}
+
 	// Add all destructured variable names to the symbol table.
-	// These come after rest param since the destructuring preamble will
-	// store extracted values into these local variables.
+	// These come after the rest param and function name since the
+	// destructuring preamble will store extracted values into these local
+	// variables.
 	for _, di := range destructureParams {
 		for _, name := range di.param.ParamNames() {
 			if _, err := code.symbols.InsertVariable(name); err != nil {
@@ -1603,15 +1952,6 @@ func (c *Compiler) compileFunc(node *ast.Func) error {
 		}
 	}
 
-	// Add the function's own name to its symbol table. This supports recursive
-	// calls to the function. Later when we create the function object, we'll
-	// add the object value to the table.
-	if code.isNamed {
-		if _, err := code.symbols.InsertConstant(functionName); err != nil {
-			return err
-		}
-	}
-
 	// Emit destructuring preamble for any destructured parameters
 	// This runs at the start of the function to extract values into local vars
 	for _, di := range destructureParams {
@@ -1643,36 +1983,79 @@ func (c *Compiler) compileFunc(node *ast.Func) error {
 	freeCount := code.symbols.FreeCount()
 	if freeCount > 0 {
 		for i := uint16(0); i < freeCount; i++ {
-			resolution := code.symbols.Free(i)
-			c.emit(op.MakeCell, resolution.symbol.Index(), uint16(resolution.depth-1))
+			if err := c.emitCaptureCell(code.symbols.Free(i), node.Pos()); err != nil {
+				return err
+			}
 		}
 		c.emit(op.LoadClosure, c.constant(fn), freeCount)
 	} else {
 		c.emit(op.LoadConst, c.constant(fn))
 	}
 
-	// If the function was named, we store it as a named variable in the current
-	// code. Otherwise, we just leave it on the stack.
-	if code.isNamed {
-		// Check if the function name already exists in the symbol table
-		// (it would have been added in the first pass for forward references)
-		funcSymbol, found := c.current.symbols.Get(functionName)
-		if !found {
-			var err error
-			funcSymbol, err = c.current.symbols.InsertConstant(functionName)
-			if err != nil {
-				return err
-			}
-		}
-		// Duplicate function on the stack, so that we ensure the function
-		// evaluates to a value even when it's named.
-		c.emit(op.Copy, 0)
-		if c.current.parent == nil {
-			c.emit(op.StoreGlobal, funcSymbol.Index())
-		} else {
-			c.emit(op.StoreFast, funcSymbol.Index())
+	return nil
+}
+
+// compileFuncDecl compiles a named function statement such as
+// "function foo() { ... }". Unlike a named function expression, whose name is
+// only visible inside the function itself, a declaration also binds the name
+// in the enclosing scope.
+func (c *Compiler) compileFuncDecl(node *ast.Func) error {
+	if err := c.compileFunc(node); err != nil {
+		return err
+	}
+	functionName := node.Name.Name
+	// Check if the function name already exists in the symbol table
+	// (it would have been added in the first pass for forward references)
+	funcSymbol, found := c.current.symbols.Get(functionName)
+	if !found {
+		var err error
+		funcSymbol, err = c.current.symbols.InsertConstant(functionName)
+		if err != nil {
+			return err
 		}
 	}
+	// Duplicate function on the stack, so that we ensure the function
+	// evaluates to a value even when it's named.
+	c.emit(op.Copy, 0)
+	if c.current.parent == nil {
+		c.emit(op.StoreGlobal, funcSymbol.Index())
+	} else {
+		c.emit(op.StoreFast, funcSymbol.Index())
+	}
+	return nil
+}
+
+// compileStmt compiles a statement within a program or block. Named functions
+// in statement position are declarations; everywhere else a function literal
+// is an expression.
+func (c *Compiler) compileStmt(stmt ast.Node) error {
+	if fn, ok := stmt.(*ast.Func); ok && fn.Name != nil {
+		return c.compileFuncDecl(fn)
+	}
+	return c.compile(stmt)
+}
+
+// emitCaptureCell pushes the cell for one free variable of a closure being
+// created in the current scope. The variable is resolved again from the
+// scope where the closure is created: a local of the enclosing function gets
+// a new cell pointing at its slot, while a variable that is itself free in
+// the enclosing function reuses that function's cell. Passing cells along
+// this way keeps every level of nesting bound to the same storage, no matter
+// which frames happen to be on the call stack when the closure is created.
+func (c *Compiler) emitCaptureCell(free *Resolution, pos token.Position) error {
+	name := free.symbol.Name()
+	resolution, found := c.current.symbols.Resolve(name)
+	if !found {
+		return c.formatError(fmt.Sprintf("undefined variable %q", name), pos)
+	}
+	switch resolution.scope {
+	case Local:
+		c.emit(op.MakeCell, resolution.symbol.Index())
+	case Free:
+		c.emit(op.LoadCell, uint16(resolution.freeIndex))
+	default:
+		return c.formatError(fmt.Sprintf("cannot capture global variable %q", name), pos)
+	}
 	return nil
 }
 
@@ -1695,57 +2078,111 @@ func (c *Compiler) compileReturn(node *ast.Return) error {
 func (c *Compiler) compileSetItem(node *ast.Assign) error {
 	index := node.Index
 
-	// Handle compound operators (*=, +=, etc.)
-	if node.Op != "=" {
-		// 1. Load the current value: test[0]
+	// Simple assignment
+	if node.Op == "=" {
+		if err := c.compile(node.Value); err != nil {
+			return err
+		}
 		if err := c.compile(index.X); err != nil {
 			return err
 		}
 		if err := c.compile(index.Index); err != nil {
 			return err
 		}
-		c.emit(op.BinarySubscr)
+		c.emit(op.StoreSubscr)
+		return nil
+	}
 
-		// 2. Load the RHS value
-		if err := c.compile(node.Value); err != nil {
-			return err
-		}
+	// Compound operators (*=, +=, etc.)
+	// 1. Load the current value test[0], keeping the container and index
+	if err := c.compileSubscrTarget(index); err != nil {
+		return err
+	}
 
-		// 3. Apply the compound operation
-		switch node.Op {
-		case "+=":
-			c.emit(op.BinaryOp, uint16(op.Add))
-		case "-=":
-			c.emit(op.BinaryOp, uint16(op.Subtract))
-		case "*=":
-			c.emit(op.BinaryOp, uint16(op.Multiply))
-		case "/=":
-			c.emit(op.BinaryOp, uint16(op.Divide))
-		default:
-			return c.formatError(fmt.Sprintf("unsupported compound assignment operator: %s", node.Op), node.Pos())
-		}
-	} else {
-		// Simple assignment
-		if err := c.compile(node.Value); err != nil {
-			return err
-		}
+	// 2. Load the RHS value
+	if err := c.compile(node.Value); err != nil {
+		return err
+	}
+
+	// 3. Apply the compound operation
+	switch node.Op {
+	case "+=":
+		c.emit(op.BinaryOp, uint16(op.Add))
+	case "-=":
+		c.emit(op.BinaryOp, uint16(op.Subtract))
+	case "*=":
+		c.emit(op.BinaryOp, uint16(op.Multiply))
+	case "/=":
+		c.emit(op.BinaryOp, uint16(op.Divide))
+	default:
+		return c.formatError(fmt.Sprintf("unsupported compound assignment operator: %s", node.Op), node.Pos())
 	}
 
 	// 4. Store the result back
+	c.emitStoreSubscrTarget()
+	return nil
+}
+
+// compileSetSlice compiles an assignment to a slice such as "x[1:3] = y",
+// which replaces the items in the slice.
+func (c *Compiler) compileSetSlice(node *ast.Assign) error {
+	if err := c.compile(node.Value); err != nil {
+		return err
+	}
+	count, err := c.compileSliceArgs(node.Slice)
+	if err != nil {
+		return err
+	}
+	c.emit(op.StoreSlice, count)
+	return nil
+}
+
+// compileDel compiles a del statement, which removes an item from a
+// container.
+func (c *Compiler) compileDel(node *ast.Del) error {
+	if err := c.compile(node.Index.X); err != nil {
+		return err
+	}
+	if err := c.compile(node.Index.Index); err != nil {
+		return err
+	}
+	c.emit(op.DeleteSubscr)
+	return nil
+}
+
+// compileSubscrTarget compiles the container and index of an update such as
+// "x[i] += 1" and loads the current item, leaving the container, index, and
+// item on the stack. The container and index are evaluated only once, so an
+// update through a call such as "f()[g()] += 1" calls each function once.
+func (c *Compiler) compileSubscrTarget(index *ast.Index) error {
 	if err := c.compile(index.X); err != nil {
 		return err
 	}
 	if err := c.compile(index.Index); err != nil {
 		return err
 	}
-	c.emit(op.StoreSubscr)
+	c.emit(op.Copy, 1)
+	c.emit(op.Copy, 1)
+	c.emit(op.BinarySubscr)
 	return nil
 }
 
+// emitStoreSubscrTarget stores the new item left on the stack by an update
+// started with compileSubscrTarget.
+func (c *Compiler) emitStoreSubscrTarget() {
+	// Reorder container, index, item to item, container, index
+	c.emit(op.Swap, 2)
+	c.emit(op.Swap, 1)
+	c.emit(op.StoreSubscr)
+}
+
 func (c *Compiler) compileAssign(node *ast.Assign) error {
 	if node.Index != nil {
 		return c.compileSetItem(node)
 	}
+	if node.Slice != nil {
+		return c.compileSetSlice(node)
+	}
 	name := node.Name.Name
 	// The blank identifier discards the value
 	if IsBlankIdentifier(name) {
@@ -1811,10 +2248,12 @@ func (c *Compiler) compileSetAttr(node *ast.SetAttr) error {
 	}
 
 	// Compound assignment: load current value, apply operation, store result
-	// First, load current attribute value
+	// First, load current attribute value, keeping a copy of the object so
+	// that it's only evaluated once
 	if err := c.compile(node.X); err != nil {
 		return err
 	}
+	c.emit(op.Copy, 0)
 	c.emit(op.LoadAttr, idx)
 
 	// Compile the RHS value
@@ -1834,10 +2273,8 @@ func (c *Compiler) compileSetAttr(node *ast.SetAttr) error {
 		c.emit(op.BinaryOp, uint16(op.Divide))
 	}
 
-	// Compile the object again and store the attribute
-	if err := c.compile(node.X); err != nil {
-		return err
-	}
+	// Move the object back to the top of the stack and store the attribute
+	c.emit(op.Swap, 1)
 	c.emit(op.StoreAttr, idx)
 	return nil
 }
@@ -2080,8 +2517,8 @@ func (c *Compiler) emit(opcode op.Code, operands ...uint16) int {
 	code.instructions = append(code.instructions, inst...)
 
 	// Track maximum call arguments for VM optimization
-	if opcode == op.Call && len(operands) > 0 {
-		argc := operands[0]
+	if (opcode == op.Call || opcode == op.CallMethod) && len(operands) > 0 {
+		argc := operands[len(operands)-1]
 		if argc > code.maxCallArgs {
 			code.maxCallArgs = argc
 		}
@@ -2158,6 +2595,18 @@ func makeInstruction(opcode op.Code, operands ...uint16) []op.Code {
 	return instruction
 }
 
+// functionDoc returns the docstring of a function body: a plain string
+// literal that begins the body and is followed by other statements.
+func functionDoc(body *ast.Block) string {
+	if body == nil || len(body.Stmts) < 2 {
+		return ""
+	}
+	if str, ok := body.Stmts[0].(*ast.String); ok && str.Template == nil {
+		return str.Value
+	}
+	return ""
+}
+
 func normalizeFunctionBlock(node *ast.Block) []ast.Node {
 	// Return a new slice of ast.Node that has some guarantees:
 	// 1. The slice ends with the first return statement
@@ -2258,6 +2707,7 @@ func (c *Compiler) getSourceLine(lineNum int) string {
 // This should be called before CompileAST when the original source is available.
 func (c *Compiler) SetSource(source string) {
 	c.source = source
+	c.sourceRunes = nil
 }
 
 // getRootSource returns the best available source for line lookups.
@@ -2304,6 +2754,45 @@ func (c *Compiler) extractFunctionBodySource(node *ast.Func) string {
 	return content
 }
 
+// nodeSource returns the original source text of the given node, falling
+// back to the node's string form when the source isn't available. Parentheses
+// around a node aren't part of its position range, so text with unbalanced
+// brackets is also rejected in favor of the fallback.
+func (c *Compiler) nodeSource(node ast.Node) string {
+	fallback := node.String()
+	if _, ok := node.(*ast.Infix); ok {
+		// Infix nodes wrap themselves in parentheses
+		fallback = fallback[1 : len(fallback)-1]
+	}
+	if c.source == "" {
+		return fallback
+	}
+	if c.sourceRunes == nil {
+		c.sourceRunes = []rune(c.source)
+	}
+	start, end := node.Pos().Char, node.End().Char
+	if start < 0 || end > len(c.sourceRunes) || end <= start {
+		return fallback
+	}
+	text := string(c.sourceRunes[start:end])
+	depth := 0
+	for _, ch := range text {
+		switch ch {
+		case '(', '[', '{':
+			depth++
+		case ')', ']', '}':
+			depth--
+		}
+		if depth < 0 {
+			return fallback
+		}
+	}
+	if depth != 0 {
+		return fallback
+	}
+	return text
+}
+
 func (c *Compiler) compileTry(node *ast.Try) error {
 	// Record the start of the try block
 	tryStart := c.currentPosition()
@@ -2442,6 +2931,41 @@ func (c *Compiler) compileTry(node *ast.Try) error {
 	return nil
 }
 
+// compileWith compiles a with statement as the equivalent of:
+//
+//	{ let name = value; try { body } finally { name.close() } }
+//
+// so the resource is closed however the block exits, including by throwing or
+// returning. Like try, the statement evaluates to the value of its block.
+func (c *Compiler) compileWith(node *ast.With) error {
+	if IsBlankIdentifier(node.Name.Name) {
+		return c.formatError("with statement requires a variable name other than _", node.Name.Pos())
+	}
+	code := c.current
+	code.symbols = code.symbols.NewBlock()
+	defer func() {
+		code.symbols = code.symbols.parent
+	}()
+	if err := c.compileVar(&ast.Var{Let: node.With, Name: node.Name, Value: node.Value}); err != nil {
+		return err
+	}
+	closeCall := &ast.ObjectCall{
+		X:      node.Name,
+		Period: node.Name.End(),
+		Call: &ast.Call{
+			Fun:    &ast.Ident{NamePos: node.Name.End(), Name: "close"},
+			Lparen: node.Name.End(),
+			Rparen: node.Name.End(),
+		},
+	}
+	return c.compileTry(&ast.Try{
+		Try:          node.With,
+		Body:         node.Body,
+		Finally:      node.With,
+		FinallyBlock: &ast.Block{Lbrace: node.With, Stmts: []ast.Node{closeCall}, Rbrace: node.With},
+	})
+}
+
 func (c *Compiler) compileThrow(node *ast.Throw) error {
 	// Compile the expression to throw
 	if err := c.compile(node.Value); err != nil {
@@ -2452,3 +2976,122 @@ func (c *Compiler) compileThrow(node *ast.Throw) error {
 	c.emit(op.Throw)
 	return nil
 }
+
+// assertComparisons maps the comparison operators whose operands are
+// reported when an assertion fails.
+var assertComparisons = map[string]op.CompareOpType{
+	"==": op.Equal,
+	"!=": op.NotEqual,
+	"<":  op.LessThan,
+	"<=": op.LessThanOrEqual,
+	">":  op.GreaterThan,
+	">=": op.GreaterThanOrEqual,
+}
+
+// compileExport compiles an exported declaration and records the names it
+// declares on the main code.
+func (c *Compiler) compileExport(node *ast.Export) error {
+	if c.current.parent != nil || c.current.symbols.Parent() != nil {
+		return c.formatError("export is only allowed at the top level", node.Pos())
+	}
+	if err := c.compileStmt(node.Decl); err != nil {
+		return err
+	}
+	// A function declaration leaves the function on the stack, but export
+	// is a statement and so does not produce a value.
+	if isExpr(node.Decl) {
+		c.emit(op.PopTop)
+	}
+	for _, name := range node.Names() {
+		if !slices.Contains(c.main.exports, name) {
+			c.main.exports = append(c.main.exports, name)
+		}
+	}
+	return nil
+}
+
+// compileImport compiles an import statement, which loads the module through
+// the VM's importer and stores it in a new global variable.
+func (c *Compiler) compileImport(node *ast.Import) error {
+	if c.current.parent != nil || c.current.symbols.Parent() != nil {
+		return c.formatError("import is only allowed at the top level", node.Pos())
+	}
+	name := node.Name()
+	if c.current.symbols.IsDefined(name) {
+		return c.formatError(fmt.Sprintf("import of %q conflicts with existing variable %q",
+			node.Path.Value, name), node.Pos())
+	}
+	c.emit(op.Import, c.constant(node.Path.Value))
+	sym, err := c.current.symbols.InsertVariable(name)
+	if err != nil {
+		return err
+	}
+	c.emit(op.StoreGlobal, sym.Index())
+	return nil
+}
+
+// assertComparison returns the comparison at the root of an assert condition,
+// if there is one.
+func assertComparison(cond ast.Expr) (*ast.Infix, op.CompareOpType, bool) {
+	infix, ok := cond.(*ast.Infix)
+	if !ok {
+		return nil, 0, false
+	}
+	cmp, ok := assertComparisons[infix.Op]
+	return infix, cmp, ok
+}
+
+func (c *Compiler) compileAssert(node *ast.Assert) error {
+	// The source of the condition is kept so the failure message can show it
+	source := c.constant(c.nodeSource(node.Cond))
+
+	// When the condition is a comparison, keep both operands on the stack so
+	// their values can be included in the failure message.
+	infix, cmp, isComparison := assertComparison(node.Cond)
+	var passJumpPos int
+	if isComparison {
+		if err := c.compile(infix.X); err != nil {
+			return err
+		}
+		if err := c.compile(infix.Y); err != nil {
+			return err
+		}
+		c.currentNode = infix
+		c.emit(op.Copy, 1)
+		c.emit(op.Copy, 1)
+		c.emit(op.CompareOp, uint16(cmp))
+		failJumpPos := c.emit(op.PopJumpForwardIfFalse, Placeholder)
+		c.emit(op.PopTop)
+		c.emit(op.PopTop)
+		passJumpPos = c.emit(op.JumpForward, Placeholder)
+		delta, err := c.calculateDelta(failJumpPos)
+		if err != nil {
+			return err
+		}
+		c.changeOperand(failJumpPos, delta)
+	} else {
+		if err := c.compile(node.Cond); err != nil {
+			return err
+		}
+		passJumpPos = c.emit(op.PopJumpForwardIfTrue, Placeholder)
+	}
+
+	// Failure path: push the message, or nil if there isn't one
+	if node.Message != nil {
+		if err := c.compile(node.Message); err != nil {
+			return err
+		}
+	} else {
+		c.emit(op.Nil)
+	}
+	c.currentNode = node
+	c.emit(op.AssertFail, source, uint16(cmp))
+
+	c.emit(op.Nop)
+	delta, err := c.calculateDelta(passJumpPos)
+	if err != nil {
+		return err
+	}
+	c.changeOperand(passJumpPos, delta)
+	return nil
+}
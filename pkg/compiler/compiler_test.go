@@ -34,6 +34,41 @@ func TestUndefinedVariable(t *testing.T) {
 	assert.Equal(t, err.Error(), "compile error: undefined variable \"foo\"\n\nlocation: unknown:2:2")
 }
 
+func TestUndefinedNames(t *testing.T) {
+	program, err := parser.Parse(context.Background(), "let x = 1; len(items) + x", nil)
+	assert.Nil(t, err)
+
+	c, err := New(&Config{UndefinedNames: UndefinedLateBound})
+	assert.Nil(t, err)
+	code, err := c.CompileAST(program)
+	assert.Nil(t, err)
+	instructions := NewInstructionIter(code).All()
+	assert.Equal(t, instructions[2], []op.Code{op.LoadName, 0})
+	assert.Equal(t, instructions[3], []op.Code{op.LoadName, 1})
+	assert.Equal(t, code.Name(0), "len")
+	assert.Equal(t, code.Name(1), "items")
+	// Names defined at compile time are still loaded directly
+	assert.Equal(t, instructions[5], []op.Code{op.LoadGlobal, 0})
+
+	// Assignments still require the variable to be defined
+	program, err = parser.Parse(context.Background(), "items = []", nil)
+	assert.Nil(t, err)
+	c, err = New(&Config{UndefinedNames: UndefinedLateBound})
+	assert.Nil(t, err)
+	_, err = c.CompileAST(program)
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), `undefined variable "items"`)
+
+	program, err = parser.Parse(context.Background(), "items", nil)
+	assert.Nil(t, err)
+	c, err = New(&Config{UndefinedNames: UndefinedNil})
+	assert.Nil(t, err)
+	code, err = c.CompileAST(program)
+	assert.Nil(t, err)
+	instructions = NewInstructionIter(code).All()
+	assert.Equal(t, instructions[0], []op.Code{op.LoadNameOrNil, 0})
+}
+
 func TestCompileErrors(t *testing.T) {
 	testCase := []struct {
 		name   string
@@ -191,13 +226,15 @@ func TestCompoundAssignmentWithIndex(t *testing.T) {
 		{op.StoreGlobal, 0}, // store into 'test'
 		{op.LoadGlobal, 0},  // load 'test'
 		{op.LoadConst, 2},   // load index 0
+		{op.Copy, 1},        // copy 'test'
+		{op.Copy, 1},        // copy index 0
 		{op.BinarySubscr},   // get test[0]
 		{op.LoadConst, 3},   // load 3
 		{op.BinaryOp, op.Code(op.Multiply)},
-		{op.LoadGlobal, 0}, // load 'test' again
-		{op.LoadConst, 4},  // load index 0
-		{op.StoreSubscr},   // store result back in test[0]
-		{op.Nil},           // implicit return value
+		{op.Swap, 2},     // move the result below 'test'
+		{op.Swap, 1},     // and below index 0
+		{op.StoreSubscr}, // store result back in test[0]
+		{op.Nil},         // implicit return value
 	}
 
 	c, err := New(nil)
@@ -1217,3 +1254,165 @@ func TestCompileASTRollbackIncrementalREPL(t *testing.T) {
 	_, found = c.main.symbols.Get("bar")
 	assert.False(t, found)
 }
+
+func TestExports(t *testing.T) {
+	source := `
+	export let count = 0
+	export const LIMIT = 10
+	function helper() { 1 }
+	export function handle() { helper() }
+	`
+	program, err := parser.Parse(context.Background(), source, nil)
+	assert.Nil(t, err)
+	code, err := Compile(program, nil)
+	assert.Nil(t, err)
+	assert.Equal(t, code.Exports(), []string{"count", "LIMIT", "handle"})
+
+	// Scripts without exports have none
+	program, err = parser.Parse(context.Background(), `let x = 1`, nil)
+	assert.Nil(t, err)
+	code, err = Compile(program, nil)
+	assert.Nil(t, err)
+	assert.Nil(t, code.Exports())
+}
+
+func TestExportNotTopLevel(t *testing.T) {
+	tests := []string{
+		`function f() { export let x = 1 }`,
+		`if (true) { export let x = 1 }`,
+	}
+	for _, source := range tests {
+		t.Run(source, func(t *testing.T) {
+			program, err := parser.Parse(context.Background(), source, nil)
+			assert.Nil(t, err)
+			_, err = Compile(program, nil)
+			assert.NotNil(t, err)
+			assert.Contains(t, err.Error(), "export is only allowed at the top level")
+		})
+	}
+}
+
+func TestImportErrors(t *testing.T) {
+	tests := []struct {
+		source string
+		err    string
+	}{
+		{`function f() { import "./lib/helpers" }`, "import is only allowed at the top level"},
+		{`let helpers = 1; import "./lib/helpers"`, `import of "./lib/helpers" conflicts with existing variable "helpers"`},
+	}
+	for _, tt := range tests {
+		t.Run(tt.source, func(t *testing.T) {
+			program, err := parser.Parse(context.Background(), tt.source, nil)
+			assert.Nil(t, err)
+			_, err = Compile(program, nil)
+			assert.NotNil(t, err)
+			assert.Contains(t, err.Error(), tt.err)
+		})
+	}
+}
+
+func TestAssertSourceSnippet(t *testing.T) {
+	tests := []struct {
+		source   string
+		expected string
+	}{
+		{`assert x  ==  1`, "x  ==  1"},
+		{`assert len([x]) > 0, "empty"`, "len([x]) > 0"},
+		{`assert(x == 1, "bad x")`, "x == 1"},
+		// The parentheses aren't part of the condition's range, so the
+		// snippet falls back to the AST string form
+		{`assert (x) == 1`, "x == 1"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.source, func(t *testing.T) {
+			program, err := parser.Parse(context.Background(), tt.source, nil)
+			assert.Nil(t, err)
+			c, err := New(&Config{GlobalNames: []string{"x", "len"}, Source: tt.source})
+			assert.Nil(t, err)
+			code, err := c.CompileAST(program)
+			assert.Nil(t, err)
+			assert.Equal(t, code.Constant(0), tt.expected)
+		})
+	}
+}
+
+func TestFunctionDoc(t *testing.T) {
+	program, err := parser.Parse(context.Background(), `
+function f() {
+    "Doc for f"
+    return 1
+}
+function g() { "not a doc" }
+function h() { let s = "x"; return s }
+function i() {
+    `+"`a ${1} template`"+`
+    return 1
+}`, nil)
+	assert.Nil(t, err)
+	code, err := Compile(program, nil)
+	assert.Nil(t, err)
+	assert.Equal(t, code.ChildCount(), 4)
+	assert.Equal(t, code.ChildAt(0).Doc(), "Doc for f")
+	assert.Equal(t, code.ChildAt(1).Doc(), "")
+	assert.Equal(t, code.ChildAt(2).Doc(), "")
+	assert.Equal(t, code.ChildAt(3).Doc(), "")
+	assert.Equal(t, code.Doc(), "")
+}
+
+func TestLanguageVersion(t *testing.T) {
+	compile := func(source, version string) error {
+		program, err := parser.Parse(context.Background(), source, nil)
+		assert.Nil(t, err)
+		_, err = Compile(program, &Config{LanguageVersion: version})
+		return err
+	}
+	tests := []struct {
+		source  string
+		feature string
+	}{
+		{`assert 1 == 1`, "assert statements"},
+		{`export let x = 1`, "export statements"},
+		{`import "lib"`, "import statements"},
+		{`quote { 1 + 2 }`, "quote expressions"},
+		{`function f() { return 1, 2 }`, "multiple return values"},
+		{`let a = 1; let b = 2; a, b = b, a`, "multiple assignments"},
+		{`[1, 2, 3][::-1]`, "slice steps"},
+		{`let x = [1, 2]; x[:1] = [3]`, "slice assignments"},
+		{`let m = {a: 1}; del m["a"]`, "del statements"},
+		{`let r = 1; with r as x { x }`, "with statements"},
+		{`let x = ~1`, "bitwise not operators"},
+		{`let k = "a"; let m = {[k]: 1}`, "computed map keys"},
+		{`let m = {a: 1}; m?.["a"]`, "optional indexing"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.feature, func(t *testing.T) {
+			err := compile(tt.source, "2.1")
+			assert.NotNil(t, err)
+			assert.Contains(t, err.Error(), tt.feature+" require language version >= 2.2")
+			assert.Nil(t, compile(tt.source, "2.2"))
+			assert.Nil(t, compile(tt.source, ""))
+		})
+	}
+
+	// Older syntax is available in every version
+	assert.Nil(t, compile(`let m = {a: 1, ...{b: 2}}; let x = -m.a + m["a"]`, "2.0"))
+
+	for _, version := range []string{"1.8", "2", "2.x", "9.0"} {
+		err := compile(`1`, version)
+		assert.NotNil(t, err)
+		assert.Contains(t, err.Error(), "invalid language version")
+	}
+}
+
+func TestUnreservedKeywords(t *testing.T) {
+	names, err := UnreservedKeywords("2.1")
+	assert.Nil(t, err)
+	assert.Equal(t, names, []string{"assert", "del", "export", "import", "quote", "with"})
+
+	names, err = UnreservedKeywords("2.2")
+	assert.Nil(t, err)
+	assert.Len(t, names, 0)
+
+	_, err = UnreservedKeywords("9.0")
+	assert.NotNil(t, err)
+}
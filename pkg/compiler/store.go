@@ -112,6 +112,7 @@ type codeDef struct {
 	Locations     []locationDef     `json:"locations,omitempty"`
 	MaxCallArgs   uint16            `json:"max_call_args,omitempty"`
 	EnvKeys       []string          `json:"env_keys,omitempty"`
+	Exports       []string          `json:"exports,omitempty"`
 }
 
 // A representation of a Code object that can be marshalled more easily.
@@ -168,6 +169,7 @@ func codeFromState(state *state) (*Code, error) {
 			locations:    locationsFromDefs(c.Locations),
 			maxCallArgs:  c.MaxCallArgs,
 			envKeys:      copyStrings(c.EnvKeys),
+			exports:      copyStrings(c.Exports),
 		}
 		codesByID[code.id] = code
 		codes = append(codes, code)
@@ -375,6 +377,7 @@ func stateFromCode(code *Code) (*state, error) {
 			Locations:     locationsToDefs(code.locations),
 			MaxCallArgs:   code.maxCallArgs,
 			EnvKeys:       copyStrings(code.envKeys),
+			Exports:       copyStrings(code.exports),
 		}
 		if code.parent != nil {
 			cdef.ParentID = code.parent.id
@@ -0,0 +1,139 @@
+package compiler
+
+import (
+	"fmt"
+	"slices"
+	"strconv"
+	"strings"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/ast"
+)
+
+// LatestLanguageVersion is the newest language version. Code compiled without
+// a Config.LanguageVersion may use all syntax up to this version.
+const LatestLanguageVersion = "2.2"
+
+// languageVersion is a language version, such as 2.1, as its major and minor
+// numbers.
+type languageVersion struct {
+	major, minor int
+}
+
+var (
+	version2_0    = languageVersion{2, 0}
+	version2_2    = languageVersion{2, 2}
+	latestVersion = version2_2
+)
+
+func (v languageVersion) String() string {
+	return fmt.Sprintf("%d.%d", v.major, v.minor)
+}
+
+func (v languageVersion) less(other languageVersion) bool {
+	return v.major < other.major || (v.major == other.major && v.minor < other.minor)
+}
+
+// parseLanguageVersion parses a version of the form "major.minor". The empty
+// string selects the latest version.
+func parseLanguageVersion(s string) (languageVersion, error) {
+	if s == "" {
+		return latestVersion, nil
+	}
+	major, minor, ok := strings.Cut(s, ".")
+	v := languageVersion{}
+	var errMajor, errMinor error
+	v.major, errMajor = strconv.Atoi(major)
+	v.minor, errMinor = strconv.Atoi(minor)
+	if !ok || errMajor != nil || errMinor != nil || v.less(version2_0) || latestVersion.less(v) {
+		return languageVersion{}, fmt.Errorf("invalid language version %q (expected %s to %s)",
+			s, version2_0, latestVersion)
+	}
+	return v, nil
+}
+
+// keywordVersions holds the language version that reserved each keyword added
+// after 2.0. Code pinned to an earlier version may use these words as names.
+var keywordVersions = map[string]languageVersion{
+	"assert": version2_2,
+	"del":    version2_2,
+	"export": version2_2,
+	"import": version2_2,
+	"quote":  version2_2,
+	"with":   version2_2,
+}
+
+// UnreservedKeywords returns the keywords that code pinned to the given
+// language version may use as names, because a later version reserved them.
+// Parse such code with these words in parser.Config.Identifiers.
+func UnreservedKeywords(version string) ([]string, error) {
+	v, err := parseLanguageVersion(version)
+	if err != nil {
+		return nil, err
+	}
+	var names []string
+	for name, reserved := range keywordVersions {
+		if v.less(reserved) {
+			names = append(names, name)
+		}
+	}
+	slices.Sort(names)
+	return names, nil
+}
+
+// languageFeature returns the name of the syntax that node uses and the
+// language version that introduced it, if that syntax is versioned.
+func languageFeature(node ast.Node) (string, languageVersion, bool) {
+	switch node := node.(type) {
+	case *ast.Assert:
+		return "assert statements", version2_2, true
+	case *ast.Export:
+		return "export statements", version2_2, true
+	case *ast.Import:
+		return "import statements", version2_2, true
+	case *ast.Quote:
+		return "quote expressions", version2_2, true
+	case *ast.MultiAssign:
+		return "multiple assignments", version2_2, true
+	case *ast.Del:
+		return "del statements", version2_2, true
+	case *ast.With:
+		return "with statements", version2_2, true
+	case *ast.Assign:
+		if node.Slice != nil {
+			return "slice assignments", version2_2, true
+		}
+	case *ast.Tuple:
+		return "multiple return values", version2_2, true
+	case *ast.Prefix:
+		if node.Op == "~" {
+			return "bitwise not operators", version2_2, true
+		}
+	case *ast.Map:
+		if slices.ContainsFunc(node.Items, func(item ast.MapItem) bool { return item.Computed }) {
+			return "computed map keys", version2_2, true
+		}
+	case *ast.Slice:
+		if node.Step != nil {
+			return "slice steps", version2_2, true
+		}
+	case *ast.Index:
+		if node.Optional {
+			return "optional indexing", version2_2, true
+		}
+	}
+	return "", languageVersion{}, false
+}
+
+// checkLanguageVersion returns an error if node uses syntax that is newer than
+// the language version being compiled for.
+func (c *Compiler) checkLanguageVersion(node ast.Node) error {
+	if c.languageVersion == latestVersion {
+		return nil
+	}
+	feature, since, ok := languageFeature(node)
+	if !ok || !c.languageVersion.less(since) {
+		return nil
+	}
+	return c.formatError(fmt.Sprintf("%s require language version >= %s (compiling for %s)",
+		feature, since, c.languageVersion), node.Pos())
+}
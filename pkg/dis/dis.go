@@ -49,7 +49,7 @@ func Disassemble(code *bytecode.Code) ([]Instruction, error) {
 			if err != nil {
 				return nil, err
 			}
-		case "LOAD_ATTR", "STORE_ATTR":
+		case "LOAD_ATTR", "STORE_ATTR", "CALL_METHOD", "LOAD_NAME", "LOAD_NAME_OR_NIL":
 			nameIndex := int(val[1])
 			name, err := getName(code, nameIndex)
 			if err != nil {
@@ -5,9 +5,14 @@ import (
 	"sort"
 
 	"github.com/deepnoodle-ai/risor/v2/pkg/builtins"
+	"github.com/deepnoodle-ai/risor/v2/pkg/modules/cron"
+	"github.com/deepnoodle-ai/risor/v2/pkg/modules/log"
 	"github.com/deepnoodle-ai/risor/v2/pkg/modules/math"
+	"github.com/deepnoodle-ai/risor/v2/pkg/modules/net"
 	"github.com/deepnoodle-ai/risor/v2/pkg/modules/rand"
 	"github.com/deepnoodle-ai/risor/v2/pkg/modules/regexp"
+	"github.com/deepnoodle-ai/risor/v2/pkg/modules/schema"
+	"github.com/deepnoodle-ai/risor/v2/pkg/modules/time"
 	"github.com/deepnoodle-ai/risor/v2/pkg/object"
 )
 
@@ -368,9 +373,14 @@ var docsModuleDocs = map[string]struct {
 	Doc   string
 	Funcs []object.FuncSpec
 }{
+	"cron":   {Doc: cron.ModuleDoc(), Funcs: cron.Docs()},
+	"log":    {Doc: log.ModuleDoc(), Funcs: log.Docs()},
 	"math":   {Doc: math.ModuleDoc(), Funcs: math.Docs()},
+	"net":    {Doc: net.ModuleDoc(), Funcs: net.Docs()},
 	"rand":   {Doc: rand.ModuleDoc(), Funcs: rand.Docs()},
 	"regexp": {Doc: regexp.ModuleDoc(), Funcs: regexp.Docs()},
+	"schema": {Doc: schema.ModuleDoc(), Funcs: schema.Docs()},
+	"time":   {Doc: time.ModuleDoc(), Funcs: time.Docs()},
 }
 
 // Syntax quick reference
@@ -168,6 +168,7 @@ func TestErrorKind_String(t *testing.T) {
 		{ErrValue, "value error"},
 		{ErrRuntime, "runtime error"},
 		{ErrImport, "import error"},
+		{ErrAssertion, "assertion error"},
 		{ErrorKind(999), "error"},
 	}
 
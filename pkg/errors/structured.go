@@ -22,6 +22,8 @@ const (
 	ErrRuntime
 	// ErrImport indicates an error importing a module.
 	ErrImport
+	// ErrAssertion indicates a failed assert statement.
+	ErrAssertion
 )
 
 // String returns the string representation of the error kind.
@@ -39,6 +41,8 @@ func (k ErrorKind) String() string {
 		return "runtime error"
 	case ErrImport:
 		return "import error"
+	case ErrAssertion:
+		return "assertion error"
 	default:
 		return "error"
 	}
@@ -52,6 +56,7 @@ type StructuredError struct {
 	Location SourceLocation
 	Stack    []StackFrame
 	Cause    error
+	Hint     string // "Did you mean?" suggestion
 }
 
 // Error implements the error interface.
@@ -98,6 +103,12 @@ func (e *StructuredError) FriendlyErrorMessage() string {
 		}
 	}
 
+	if e.Hint != "" {
+		msg.WriteString("hint: ")
+		msg.WriteString(e.Hint)
+		msg.WriteString("\n")
+	}
+
 	// Stack trace
 	if len(e.Stack) > 0 {
 		msg.WriteString("\n")
@@ -152,6 +163,7 @@ func (e *StructuredError) ToFormatted() *FormattedError {
 		Line:      e.Location.Line,
 		Column:    e.Location.Column,
 		EndColumn: e.Location.EndColumn,
+		Hint:      e.Hint,
 	}
 
 	if e.Location.Source != "" {
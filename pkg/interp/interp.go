@@ -0,0 +1,996 @@
+// Package interp is a tree-walking interpreter for Risor. It evaluates the
+// AST directly and shares the object model with the compiler and VM, so
+// builtins, modules, and values work the same way in both.
+//
+// The interpreter is slower than the VM but much simpler, with no code
+// generation, stack, or specialized instructions. It serves as a reference
+// for differential testing, where a script that gives different results in
+// the two points to a bug in the compiler or VM, and as a fallback for hosts
+// that can't use the VM.
+//
+// It covers literals, templates, variables, operators, if, match, functions
+// and closures, try/catch/finally, and calls to builtins and methods. Eval
+// returns ErrUnsupported for syntax outside of this, such as destructuring,
+// pipes, imports, and quotes.
+package interp
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"maps"
+	"strings"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/ast"
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+	"github.com/deepnoodle-ai/risor/v2/pkg/op"
+)
+
+// ErrUnsupported is returned by Eval for syntax that the interpreter doesn't
+// implement.
+var ErrUnsupported = errors.New("unsupported by the interpreter")
+
+var binaryOps = map[string]op.BinaryOpType{
+	"+":  op.Add,
+	"-":  op.Subtract,
+	"*":  op.Multiply,
+	"/":  op.Divide,
+	"%":  op.Modulo,
+	"**": op.Power,
+	"<<": op.LShift,
+	">>": op.RShift,
+	"&":  op.BitwiseAnd,
+	"|":  op.BitwiseOr,
+	"^":  op.Xor,
+}
+
+var compareOps = map[string]op.CompareOpType{
+	">":  op.GreaterThan,
+	">=": op.GreaterThanOrEqual,
+	"<":  op.LessThan,
+	"<=": op.LessThanOrEqual,
+	"==": op.Equal,
+	"!=": op.NotEqual,
+}
+
+var assignOps = map[string]op.BinaryOpType{
+	"+=": op.Add,
+	"-=": op.Subtract,
+	"*=": op.Multiply,
+	"/=": op.Divide,
+}
+
+// scope holds the variables of a program, function, or block.
+type scope struct {
+	vars   map[string]object.Object
+	consts map[string]bool
+	parent *scope
+}
+
+func newScope(parent *scope) *scope {
+	return &scope{
+		vars:   map[string]object.Object{},
+		consts: map[string]bool{},
+		parent: parent,
+	}
+}
+
+// find returns the innermost scope that defines name.
+func (s *scope) find(name string) (*scope, bool) {
+	for ; s != nil; s = s.parent {
+		if _, ok := s.vars[name]; ok {
+			return s, true
+		}
+	}
+	return nil, false
+}
+
+// returnSignal unwinds a function call to the function that returns.
+type returnSignal struct {
+	value object.Object
+}
+
+func (r *returnSignal) Error() string {
+	return "return outside of a function"
+}
+
+// Eval evaluates a program with the given globals. Like the VM, it returns
+// the value of the last statement if it is an expression, and nil otherwise.
+// Evaluation stops with the context's error if the context is canceled.
+func Eval(ctx context.Context, program *ast.Program, globals map[string]object.Object) (object.Object, error) {
+	global := newScope(nil)
+	maps.Copy(global.vars, globals)
+	in := &interpreter{ctx: ctx}
+	// Top-level functions may be called before they are defined
+	for _, stmt := range program.Stmts {
+		if fn, ok := stmt.(*ast.Func); ok && fn.Name != nil {
+			if _, err := in.eval(fn, global); err != nil {
+				return nil, err
+			}
+		}
+	}
+	result, err := in.block(program.Stmts, global)
+	var ret *returnSignal
+	if errors.As(err, &ret) {
+		return nil, ret
+	}
+	return result, err
+}
+
+type interpreter struct {
+	ctx context.Context
+}
+
+// block evaluates statements in order and returns the value of the last one
+// if it is an expression.
+func (in *interpreter) block(stmts []ast.Node, s *scope) (object.Object, error) {
+	var result object.Object = object.Nil
+	for _, stmt := range stmts {
+		value, err := in.eval(stmt, s)
+		if err != nil {
+			return nil, err
+		}
+		result = object.Nil
+		if _, ok := stmt.(ast.Expr); ok {
+			result = value
+		}
+	}
+	return result, nil
+}
+
+func (in *interpreter) eval(node ast.Node, s *scope) (object.Object, error) {
+	switch node := node.(type) {
+	case *ast.Int:
+		return object.NewInt(node.Value), nil
+	case *ast.Float:
+		return object.NewFloat(node.Value), nil
+	case *ast.Bool:
+		return object.NewBool(node.Value), nil
+	case *ast.Nil:
+		return object.Nil, nil
+	case *ast.String:
+		return in.str(node, s)
+	case *ast.Ident:
+		if owner, ok := s.find(node.Name); ok {
+			return owner.vars[node.Name], nil
+		}
+		return nil, fmt.Errorf("undefined variable %q", node.Name)
+	case *ast.Var:
+		return in.declare(node.Name.Name, node.Value, false, s)
+	case *ast.Const:
+		return in.declare(node.Name.Name, node.Value, true, s)
+	case *ast.MultiVar:
+		return in.unpack(node, s)
+	case *ast.MultiAssign:
+		return in.multiAssign(node, s)
+	case *ast.Assign:
+		return in.assign(node, s)
+	case *ast.SetAttr:
+		return in.setAttr(node, s)
+	case *ast.Postfix:
+		return in.postfix(node, s)
+	case *ast.Prefix:
+		return in.prefix(node, s)
+	case *ast.Infix:
+		return in.infix(node, s)
+	case *ast.In:
+		return in.contains(node.X, node.Y, false, s)
+	case *ast.NotIn:
+		return in.contains(node.X, node.Y, true, s)
+	case *ast.If:
+		cond, err := in.eval(node.Cond, s)
+		if err != nil {
+			return nil, err
+		}
+		if cond.IsTruthy() {
+			return in.block(node.Consequence.Stmts, newScope(s))
+		}
+		if node.Alternative != nil {
+			return in.block(node.Alternative.Stmts, newScope(s))
+		}
+		return object.Nil, nil
+	case *ast.Match:
+		return in.match(node, s)
+	case *ast.Block:
+		return in.block(node.Stmts, newScope(s))
+	case *ast.List:
+		items, err := in.evalItems(node.Items, s)
+		if err != nil {
+			return nil, err
+		}
+		return object.NewList(items), nil
+	case *ast.Tuple:
+		items, err := in.evalItems(node.Items, s)
+		if err != nil {
+			return nil, err
+		}
+		return object.NewTuple(items), nil
+	case *ast.Map:
+		return in.mapLiteral(node, s)
+	case *ast.Index:
+		return in.index(node, s)
+	case *ast.Slice:
+		return in.slice(node, s)
+	case *ast.GetAttr:
+		x, err := in.eval(node.X, s)
+		if err != nil {
+			return nil, err
+		}
+		if node.Optional {
+			// A missing attribute is nil rather than an error, as in the VM
+			value, err := in.getAttr(x, node.Attr.Name)
+			if err != nil {
+				return object.Nil, nil
+			}
+			return value, nil
+		}
+		return in.getAttr(x, node.Attr.Name)
+	case *ast.ObjectCall:
+		x, err := in.eval(node.X, s)
+		if err != nil {
+			return nil, err
+		}
+		if node.Optional && x == object.Nil {
+			return object.Nil, nil
+		}
+		method, err := in.getAttr(x, node.Call.Fun.String())
+		if err != nil {
+			return nil, err
+		}
+		return in.call(method, node.Call.Args, s)
+	case *ast.Call:
+		fn, err := in.eval(node.Fun, s)
+		if err != nil {
+			return nil, err
+		}
+		return in.call(fn, node.Args, s)
+	case *ast.Func:
+		return in.function(node, s)
+	case *ast.Return:
+		var value object.Object = object.Nil
+		if node.Value != nil {
+			var err error
+			if value, err = in.eval(node.Value, s); err != nil {
+				return nil, err
+			}
+		}
+		return nil, &returnSignal{value: value}
+	case *ast.Try:
+		return in.try(node, s)
+	case *ast.Throw:
+		value, err := in.eval(node.Value, s)
+		if err != nil {
+			return nil, err
+		}
+		switch value := value.(type) {
+		case *object.Error:
+			return nil, value
+		case *object.String:
+			return nil, object.NewError(errors.New(value.Value()))
+		default:
+			return nil, object.NewError(errors.New(value.Inspect()))
+		}
+	case *ast.Assert:
+		cond, err := in.eval(node.Cond, s)
+		if err != nil {
+			return nil, err
+		}
+		if cond.IsTruthy() {
+			return object.Nil, nil
+		}
+		msg := "assertion failed"
+		if node.Message != nil {
+			value, err := in.eval(node.Message, s)
+			if err != nil {
+				return nil, err
+			}
+			if str, ok := value.(*object.String); ok {
+				msg = str.Value()
+			} else {
+				msg = value.Inspect()
+			}
+		}
+		return nil, object.NewError(errors.New(msg))
+	case *ast.Del:
+		x, err := in.eval(node.Index.X, s)
+		if err != nil {
+			return nil, err
+		}
+		key, err := in.eval(node.Index.Index, s)
+		if err != nil {
+			return nil, err
+		}
+		container, ok := x.(object.Container)
+		if !ok {
+			return nil, fmt.Errorf("object is not a container (got %s)", x.Type())
+		}
+		if err := container.DelItem(key); err != nil {
+			return nil, err
+		}
+		return object.Nil, nil
+	}
+	return nil, fmt.Errorf("%w: %T", ErrUnsupported, node)
+}
+
+// str evaluates a string literal, filling in the holes of a template.
+func (in *interpreter) str(node *ast.String, s *scope) (object.Object, error) {
+	if node.Template == nil {
+		return object.NewString(node.Value), nil
+	}
+	var b strings.Builder
+	exprs := node.Exprs
+	for _, fragment := range node.Template.Fragments() {
+		if !fragment.IsVariable() {
+			b.WriteString(fragment.Value())
+			continue
+		}
+		expr := exprs[0]
+		exprs = exprs[1:]
+		if expr == nil {
+			continue
+		}
+		value, err := in.eval(expr, s)
+		if err != nil {
+			return nil, err
+		}
+		text, ok, err := object.CallStr(in.ctx, value)
+		if err != nil {
+			return nil, err
+		}
+		if !ok {
+			switch value := value.(type) {
+			case *object.Error:
+				text = value.String()
+			case *object.String:
+				text = value.Value()
+			default:
+				text = value.Inspect()
+			}
+		}
+		b.WriteString(text)
+	}
+	return object.NewString(b.String()), nil
+}
+
+// evalItems evaluates the items of a list, tuple, or argument list,
+// expanding any spread items.
+func (in *interpreter) evalItems(exprs []ast.Expr, s *scope) ([]object.Object, error) {
+	values := make([]object.Object, 0, len(exprs))
+	for _, expr := range exprs {
+		if spread, ok := expr.(*ast.Spread); ok {
+			value, err := in.eval(spread.X, s)
+			if err != nil {
+				return nil, err
+			}
+			if values, err = in.spread(values, value); err != nil {
+				return nil, err
+			}
+			continue
+		}
+		value, err := in.eval(expr, s)
+		if err != nil {
+			return nil, err
+		}
+		values = append(values, value)
+	}
+	return values, nil
+}
+
+// spread appends the items of value to values. Maps give their keys and
+// other enumerables their values.
+func (in *interpreter) spread(values []object.Object, value object.Object) ([]object.Object, error) {
+	enumerable, ok := value.(object.Enumerable)
+	if !ok {
+		return nil, fmt.Errorf("spread requires an enumerable (got %s)", value.Type())
+	}
+	_, isMap := value.(*object.Map)
+	enumerable.Enumerate(in.ctx, func(key, item object.Object) bool {
+		if isMap {
+			item = key
+		}
+		values = append(values, item)
+		return true
+	})
+	return values, nil
+}
+
+func (in *interpreter) declare(name string, expr ast.Expr, isConst bool, s *scope) (object.Object, error) {
+	value, err := in.eval(expr, s)
+	if err != nil {
+		return nil, err
+	}
+	s.vars[name] = value
+	s.consts[name] = isConst
+	return object.Nil, nil
+}
+
+// unpack declares the variables of "let a, b = value". A tuple must have
+// exactly as many items as there are names, while other containers may have
+// fewer, leaving the remaining names nil.
+func (in *interpreter) unpack(node *ast.MultiVar, s *scope) (object.Object, error) {
+	value, err := in.eval(node.Value, s)
+	if err != nil {
+		return nil, err
+	}
+	items, err := in.unpackItems(value, len(node.Names))
+	if err != nil {
+		return nil, err
+	}
+	for i, name := range node.Names {
+		s.vars[name.Name] = items[i]
+	}
+	return object.Nil, nil
+}
+
+// unpackItems returns the items of a value unpacked into count variables,
+// padded with nil.
+func (in *interpreter) unpackItems(value object.Object, count int) ([]object.Object, error) {
+	container, ok := value.(object.Container)
+	if !ok {
+		return nil, fmt.Errorf("object is not a container (got %s)", value.Type())
+	}
+	size := int(container.Len().Value())
+	if _, ok := value.(*object.Tuple); ok && size != count {
+		return nil, fmt.Errorf("unpack count mismatch: expected %d values, got %d", count, size)
+	}
+	if size > count {
+		return nil, fmt.Errorf("unpack count mismatch: %d > %d", size, count)
+	}
+	items, err := in.spread(nil, value)
+	if err != nil {
+		return nil, err
+	}
+	for len(items) < count {
+		items = append(items, object.Nil)
+	}
+	return items, nil
+}
+
+// multiAssign assigns to the variables of "a, b = value" or "a = b = value".
+// Like the VM, it evaluates the value first and assigns the variables from
+// last to first.
+func (in *interpreter) multiAssign(node *ast.MultiAssign, s *scope) (object.Object, error) {
+	value, err := in.eval(node.Value, s)
+	if err != nil {
+		return nil, err
+	}
+	var items []object.Object
+	if node.Unpack {
+		if items, err = in.unpackItems(value, len(node.Names)); err != nil {
+			return nil, err
+		}
+	}
+	for i := len(node.Names) - 1; i >= 0; i-- {
+		name := node.Names[i].Name
+		if name == "_" {
+			continue
+		}
+		owner, ok := s.find(name)
+		if !ok {
+			return nil, fmt.Errorf("undefined variable %q", name)
+		}
+		if owner.consts[name] {
+			return nil, fmt.Errorf("cannot assign to constant %q", name)
+		}
+		if node.Unpack {
+			owner.vars[name] = items[i]
+		} else {
+			owner.vars[name] = value
+		}
+	}
+	return object.Nil, nil
+}
+
+func (in *interpreter) assign(node *ast.Assign, s *scope) (object.Object, error) {
+	if node.Index != nil {
+		return in.setItem(node, s)
+	}
+	if node.Slice != nil {
+		return in.setSlice(node, s)
+	}
+	value, err := in.eval(node.Value, s)
+	if err != nil {
+		return nil, err
+	}
+	name := node.Name.Name
+	owner, ok := s.find(name)
+	if !ok {
+		return nil, fmt.Errorf("undefined variable %q", name)
+	}
+	if owner.consts[name] {
+		return nil, fmt.Errorf("cannot assign to constant %q", name)
+	}
+	if node.Op != "=" {
+		opType, ok := assignOps[node.Op]
+		if !ok {
+			return nil, fmt.Errorf("%w: %s", ErrUnsupported, node.Op)
+		}
+		if value, err = object.BinaryOp(opType, owner.vars[name], value); err != nil {
+			return nil, err
+		}
+	}
+	owner.vars[name] = value
+	return object.Nil, nil
+}
+
+// setItem assigns to an index. As in the VM, a plain assignment evaluates the
+// value first, and an update such as "+=" evaluates the container and index
+// once and reads the current item before evaluating the value.
+func (in *interpreter) setItem(node *ast.Assign, s *scope) (object.Object, error) {
+	var value object.Object
+	if node.Op == "=" {
+		var err error
+		if value, err = in.eval(node.Value, s); err != nil {
+			return nil, err
+		}
+	}
+	x, err := in.eval(node.Index.X, s)
+	if err != nil {
+		return nil, err
+	}
+	key, err := in.eval(node.Index.Index, s)
+	if err != nil {
+		return nil, err
+	}
+	container, ok := x.(object.Container)
+	if !ok {
+		return nil, fmt.Errorf("object is not a container (got %s)", x.Type())
+	}
+	if node.Op != "=" {
+		opType, ok := assignOps[node.Op]
+		if !ok {
+			return nil, fmt.Errorf("%w: %s", ErrUnsupported, node.Op)
+		}
+		current, getErr := container.GetItem(key)
+		if getErr != nil {
+			return nil, getErr
+		}
+		operand, err := in.eval(node.Value, s)
+		if err != nil {
+			return nil, err
+		}
+		if value, err = object.BinaryOp(opType, current, operand); err != nil {
+			return nil, err
+		}
+	}
+	if err := container.SetItem(key, value); err != nil {
+		return nil, err
+	}
+	return object.Nil, nil
+}
+
+// setSlice replaces the items in a slice, evaluating the value first as in
+// the VM.
+func (in *interpreter) setSlice(node *ast.Assign, s *scope) (object.Object, error) {
+	value, err := in.eval(node.Value, s)
+	if err != nil {
+		return nil, err
+	}
+	container, slice, err := in.sliceArgs(node.Slice, s)
+	if err != nil {
+		return nil, err
+	}
+	if err := container.SetSlice(slice, value); err != nil {
+		return nil, err
+	}
+	return object.Nil, nil
+}
+
+// setAttr assigns to an attribute. An update such as "+=" reads the current
+// value before evaluating the new one, as in the VM.
+func (in *interpreter) setAttr(node *ast.SetAttr, s *scope) (object.Object, error) {
+	x, err := in.eval(node.X, s)
+	if err != nil {
+		return nil, err
+	}
+	var current object.Object
+	var opType op.BinaryOpType
+	if node.Op != "=" {
+		var ok bool
+		if opType, ok = assignOps[node.Op]; !ok {
+			return nil, fmt.Errorf("%w: %s", ErrUnsupported, node.Op)
+		}
+		if current, err = in.getAttr(x, node.Attr.Name); err != nil {
+			return nil, err
+		}
+	}
+	value, err := in.eval(node.Value, s)
+	if err != nil {
+		return nil, err
+	}
+	if current != nil {
+		if value, err = object.BinaryOp(opType, current, value); err != nil {
+			return nil, err
+		}
+	}
+	if err := x.SetAttr(node.Attr.Name, value); err != nil {
+		return nil, err
+	}
+	return object.Nil, nil
+}
+
+func (in *interpreter) postfix(node *ast.Postfix, s *scope) (object.Object, error) {
+	assignOp := "+="
+	if node.Op == "--" {
+		assignOp = "-="
+	}
+	one := &ast.Int{Value: 1}
+	switch x := node.X.(type) {
+	case *ast.Ident:
+		return in.assign(&ast.Assign{Name: x, Op: assignOp, Value: one}, s)
+	case *ast.Index:
+		return in.assign(&ast.Assign{Index: x, Op: assignOp, Value: one}, s)
+	case *ast.GetAttr:
+		return in.setAttr(&ast.SetAttr{X: x.X, Attr: x.Attr, Op: assignOp, Value: one}, s)
+	}
+	return nil, fmt.Errorf("%w: %s on %T", ErrUnsupported, node.Op, node.X)
+}
+
+func (in *interpreter) prefix(node *ast.Prefix, s *scope) (object.Object, error) {
+	x, err := in.eval(node.X, s)
+	if err != nil {
+		return nil, err
+	}
+	switch node.Op {
+	case "!", "not":
+		return object.NewBool(!x.IsTruthy()), nil
+	case "-":
+		switch x := x.(type) {
+		case *object.Int:
+			return object.NewInt(-x.Value()), nil
+		case *object.Float:
+			return object.NewFloat(-x.Value()), nil
+		case *object.Decimal:
+			return x.Neg(), nil
+		}
+		return nil, fmt.Errorf("object is not a number (got %s)", x.Type())
+	case "~":
+		switch x := x.(type) {
+		case *object.Int:
+			return object.NewInt(^x.Value()), nil
+		case *object.Byte:
+			return object.NewByte(^x.Value()), nil
+		}
+		return nil, fmt.Errorf("bitwise not requires an int or byte (got %s)", x.Type())
+	}
+	return nil, fmt.Errorf("%w: prefix %s", ErrUnsupported, node.Op)
+}
+
+func (in *interpreter) infix(node *ast.Infix, s *scope) (object.Object, error) {
+	x, err := in.eval(node.X, s)
+	if err != nil {
+		return nil, err
+	}
+	// Short-circuit operators evaluate the right operand only if needed
+	switch node.Op {
+	case "&&":
+		if !x.IsTruthy() {
+			return x, nil
+		}
+	case "||":
+		if x.IsTruthy() {
+			return x, nil
+		}
+	case "??":
+		if x != object.Nil {
+			return x, nil
+		}
+	}
+	y, err := in.eval(node.Y, s)
+	if err != nil {
+		return nil, err
+	}
+	switch node.Op {
+	case "&&":
+		return object.BinaryOp(op.And, x, y)
+	case "||", "??":
+		return y, nil
+	}
+	if opType, ok := binaryOps[node.Op]; ok {
+		// A map that overloads the operator must not fall back to the
+		// built-in operator, such as merging for "+"
+		if name, ok := object.BinaryOpMethod(opType); ok {
+			if _, ok := object.LookupMethod(x, name); ok {
+				return nil, fmt.Errorf("%w: %s method", ErrUnsupported, name)
+			}
+		}
+		return object.BinaryOp(opType, x, y)
+	}
+	if opType, ok := compareOps[node.Op]; ok {
+		return object.Compare(opType, x, y)
+	}
+	return nil, fmt.Errorf("%w: operator %s", ErrUnsupported, node.Op)
+}
+
+func (in *interpreter) contains(item, container ast.Expr, invert bool, s *scope) (object.Object, error) {
+	x, err := in.eval(item, s)
+	if err != nil {
+		return nil, err
+	}
+	y, err := in.eval(container, s)
+	if err != nil {
+		return nil, err
+	}
+	c, ok := y.(object.Container)
+	if !ok {
+		return nil, fmt.Errorf("object is not a container (got %s)", y.Type())
+	}
+	result := c.Contains(x)
+	if invert {
+		result = object.Not(result)
+	}
+	return result, nil
+}
+
+// match evaluates the result of the first arm whose pattern equals the
+// subject and whose guard, if any, is truthy.
+func (in *interpreter) match(node *ast.Match, s *scope) (object.Object, error) {
+	subject, err := in.eval(node.Subject, s)
+	if err != nil {
+		return nil, err
+	}
+	for _, arm := range node.Arms {
+		switch pattern := arm.Pattern.(type) {
+		case *ast.LiteralPattern:
+			value, err := in.eval(pattern.Value, s)
+			if err != nil {
+				return nil, err
+			}
+			if !subject.Equals(value) {
+				continue
+			}
+		case *ast.WildcardPattern:
+		default:
+			return nil, fmt.Errorf("%w: pattern %T", ErrUnsupported, arm.Pattern)
+		}
+		if arm.Guard != nil {
+			guard, err := in.eval(arm.Guard, s)
+			if err != nil {
+				return nil, err
+			}
+			if !guard.IsTruthy() {
+				continue
+			}
+		}
+		return in.eval(arm.Result, s)
+	}
+	return in.eval(node.Default.Result, s)
+}
+
+// mapLiteral builds a map. As in the VM, the first occurrence of a key wins.
+func (in *interpreter) mapLiteral(node *ast.Map, s *scope) (object.Object, error) {
+	items := map[string]object.Object{}
+	for _, item := range node.Items {
+		if item.Key == nil {
+			return nil, fmt.Errorf("%w: map spread", ErrUnsupported)
+		}
+		var key string
+		switch k := item.Key.(type) {
+		case *ast.Ident:
+			key = k.Name
+		case *ast.String:
+			key = k.Value
+		default:
+			return nil, fmt.Errorf("%w: map key %T", ErrUnsupported, item.Key)
+		}
+		if item.Computed {
+			keyObj, err := in.eval(item.Key, s)
+			if err != nil {
+				return nil, err
+			}
+			str, ok := keyObj.(*object.String)
+			if !ok {
+				return nil, fmt.Errorf("map key must be a string (got %s)", keyObj.Type())
+			}
+			key = str.Value()
+		} else if k, ok := item.Key.(*ast.String); ok && k.Template != nil {
+			return nil, fmt.Errorf("%w: template map key", ErrUnsupported)
+		}
+		value, err := in.eval(item.Value, s)
+		if err != nil {
+			return nil, err
+		}
+		if _, found := items[key]; !found {
+			items[key] = value
+		}
+	}
+	return object.NewMap(items), nil
+}
+
+func (in *interpreter) index(node *ast.Index, s *scope) (object.Object, error) {
+	x, err := in.eval(node.X, s)
+	if err != nil {
+		return nil, err
+	}
+	if node.Optional && x == object.Nil {
+		return object.Nil, nil
+	}
+	key, err := in.eval(node.Index, s)
+	if err != nil {
+		return nil, err
+	}
+	container, ok := x.(object.Container)
+	if !ok {
+		return nil, fmt.Errorf("object is not a container (got %s)", x.Type())
+	}
+	value, getErr := container.GetItem(key)
+	if getErr != nil {
+		return nil, getErr
+	}
+	return value, nil
+}
+
+func (in *interpreter) slice(node *ast.Slice, s *scope) (object.Object, error) {
+	container, slice, err := in.sliceArgs(node, s)
+	if err != nil {
+		return nil, err
+	}
+	value, sliceErr := container.GetSlice(slice)
+	if sliceErr != nil {
+		return nil, sliceErr
+	}
+	return value, nil
+}
+
+// sliceArgs evaluates the container and bounds of a slice, and the step if
+// there is one.
+func (in *interpreter) sliceArgs(node *ast.Slice, s *scope) (object.Container, object.Slice, error) {
+	x, err := in.eval(node.X, s)
+	if err != nil {
+		return nil, object.Slice{}, err
+	}
+	container, ok := x.(object.Container)
+	if !ok {
+		return nil, object.Slice{}, fmt.Errorf("object is not a container (got %s)", x.Type())
+	}
+	slice := object.Slice{Start: object.NewInt(0), Stop: container.Len()}
+	if node.Step != nil {
+		// The default bounds depend on the sign of the step
+		slice.Start, slice.Stop = nil, nil
+	}
+	if node.Low != nil {
+		if slice.Start, err = in.eval(node.Low, s); err != nil {
+			return nil, object.Slice{}, err
+		}
+	}
+	if node.High != nil {
+		if slice.Stop, err = in.eval(node.High, s); err != nil {
+			return nil, object.Slice{}, err
+		}
+	}
+	if node.Step != nil {
+		if slice.Step, err = in.eval(node.Step, s); err != nil {
+			return nil, object.Slice{}, err
+		}
+		if slice.Start == object.Nil {
+			slice.Start = nil
+		}
+		if slice.Stop == object.Nil {
+			slice.Stop = nil
+		}
+	}
+	return container, slice, nil
+}
+
+func (in *interpreter) getAttr(x object.Object, name string) (object.Object, error) {
+	value, ok := x.GetAttr(name)
+	if !ok {
+		return nil, fmt.Errorf("attribute %q not found on %s object", name, x.Type())
+	}
+	if resolver, ok := value.(object.AttrResolver); ok {
+		return resolver.ResolveAttr(in.ctx, name)
+	}
+	return value, nil
+}
+
+func (in *interpreter) call(fn object.Object, argNodes []ast.Node, s *scope) (object.Object, error) {
+	if err := in.ctx.Err(); err != nil {
+		return nil, err
+	}
+	exprs := make([]ast.Expr, 0, len(argNodes))
+	for _, node := range argNodes {
+		expr, ok := node.(ast.Expr)
+		if !ok {
+			return nil, fmt.Errorf("%w: argument %T", ErrUnsupported, node)
+		}
+		exprs = append(exprs, expr)
+	}
+	args, err := in.evalItems(exprs, s)
+	if err != nil {
+		return nil, err
+	}
+	callable, ok := fn.(object.Callable)
+	if !ok {
+		return nil, fmt.Errorf("object is not callable (got %s)", fn.Type())
+	}
+	return callable.Call(in.ctx, args...)
+}
+
+// try evaluates a try expression. Errors other than cancellation are caught,
+// and the finally block runs however the try and catch blocks end.
+func (in *interpreter) try(node *ast.Try, s *scope) (object.Object, error) {
+	result, err := in.block(node.Body.Stmts, newScope(s))
+	if err != nil && node.CatchBlock != nil && catchable(err) {
+		catchScope := newScope(s)
+		if node.CatchIdent != nil {
+			var errObj *object.Error
+			if !errors.As(err, &errObj) {
+				errObj = object.NewError(err)
+			}
+			catchScope.vars[node.CatchIdent.Name] = errObj
+		}
+		result, err = in.block(node.CatchBlock.Stmts, catchScope)
+	}
+	if node.FinallyBlock != nil {
+		if _, finallyErr := in.block(node.FinallyBlock.Stmts, newScope(s)); finallyErr != nil {
+			return nil, finallyErr
+		}
+	}
+	return result, err
+}
+
+func catchable(err error) bool {
+	var ret *returnSignal
+	return !errors.As(err, &ret) &&
+		!errors.Is(err, ErrUnsupported) &&
+		!errors.Is(err, context.Canceled) &&
+		!errors.Is(err, context.DeadlineExceeded)
+}
+
+// function returns a function as a builtin that evaluates its body in a new
+// scope enclosed by s, so that closures share variables with the code that
+// defines them, as they do in the VM.
+func (in *interpreter) function(node *ast.Func, s *scope) (object.Object, error) {
+	params := make([]string, 0, len(node.Params))
+	for _, param := range node.Params {
+		ident, ok := param.(*ast.Ident)
+		if !ok {
+			return nil, fmt.Errorf("%w: destructuring parameters", ErrUnsupported)
+		}
+		params = append(params, ident.Name)
+	}
+	defaults := map[string]object.Object{}
+	for name, expr := range node.Defaults {
+		value, err := in.eval(expr, s)
+		if err != nil {
+			return nil, err
+		}
+		defaults[name] = value
+	}
+	required := len(params) - len(defaults)
+	name := "function"
+	if node.Name != nil {
+		name = node.Name.Name
+	}
+	fn := object.NewBuiltin(name, func(ctx context.Context, args ...object.Object) (object.Object, error) {
+		if len(args) < required || (node.RestParam == nil && len(args) > len(params)) {
+			return nil, fmt.Errorf("args error: function %q takes %d argument(s) (%d given)",
+				name, len(params), len(args))
+		}
+		local := newScope(s)
+		for i, param := range params {
+			if i < len(args) {
+				local.vars[param] = args[i]
+			} else {
+				local.vars[param] = defaults[param]
+			}
+		}
+		if node.RestParam != nil {
+			rest := []object.Object{}
+			if len(args) > len(params) {
+				rest = append(rest, args[len(params):]...)
+			}
+			local.vars[node.RestParam.Name] = object.NewList(rest)
+		}
+		result, err := in.block(node.Body.Stmts, local)
+		var ret *returnSignal
+		if errors.As(err, &ret) {
+			return ret.value, nil
+		}
+		return result, err
+	})
+	if node.Name != nil {
+		s.vars[node.Name.Name] = fn
+	}
+	return fn, nil
+}
@@ -0,0 +1,95 @@
+package interp
+
+import (
+	"context"
+	"errors"
+	"testing"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/builtins"
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+	"github.com/deepnoodle-ai/risor/v2/pkg/parser"
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+func eval(t *testing.T, source string) (object.Object, error) {
+	t.Helper()
+	ctx := context.Background()
+	program, err := parser.Parse(ctx, source, nil)
+	assert.Nil(t, err)
+	return Eval(ctx, program, builtins.Builtins())
+}
+
+func TestEval(t *testing.T) {
+	tests := []struct {
+		source   string
+		expected string
+	}{
+		{`1 + 2 * 3`, `7`},
+		{`let x = 1; x += 2; x++; x`, `4`},
+		{`nil ?? "default"`, `"default"`},
+		{`false || 0`, `0`},
+		{`~5`, `-6`},
+		{`2 in [1, 2, 3]`, `true`},
+		{`"a" not in {a: 1}`, `false`},
+		{`let x = 5; if (x > 3) { "big" } else { "small" }`, `"big"`},
+		{`let name = "x"; {[name]: 1, b: 2, b: 3}`, `{"b": 2, "x": 1}`},
+		{`[1, 2, 3, 4][1:3]`, `[2, 3]`},
+		{`"hello"[:2]`, `"he"`},
+		{`let a = [2, 3]; [1, ...a]`, `[1, 2, 3]`},
+		{`let a, b = [1, 2]; a + b`, `3`},
+		{`let n = 2; "n is ${n * 2}"`, `"n is 4"`},
+		{`function f(a, b = 10) { a + b }; f(1)`, `11`},
+		{`function f(a, ...rest) { rest }; f(1, 2, 3)`, `[2, 3]`},
+		{`let add = (a, b) => a + b; add(...[1, 2])`, `3`},
+		{`f(); function f() { return 42 }`, `42`},
+		{`let count = 0; let inc = () => { count++ }; inc(); inc(); count`, `2`},
+		{`match 2 { 1 => "one", 2 => "two", _ => "many" }`, `"two"`},
+		{`match 5 { 5 if false => "no", _ => "default" }`, `"default"`},
+		{`"a,b".split(",")`, `["a", "b"]`},
+		{`len([1, 2])`, `2`},
+		{`try { throw "oops" } catch (e) { e.message() }`, `"oops"`},
+		{`let x = 0; try { 1 } finally { x = 2 }; x`, `2`},
+	}
+	for _, tt := range tests {
+		t.Run(tt.source, func(t *testing.T) {
+			result, err := eval(t, tt.source)
+			assert.Nil(t, err)
+			assert.Equal(t, result.Inspect(), tt.expected)
+		})
+	}
+}
+
+func TestEvalErrors(t *testing.T) {
+	tests := []string{
+		`undefined_name`,
+		`const x = 1; x = 2`,
+		`[1, 2][5]`,
+		`1 + "a"`,
+		`throw "oops"`,
+		`assert 1 > 2`,
+		`let a, b = [1, 2, 3]`,
+		`function f(a) { a }; f()`,
+		`try { throw "a" } catch { throw "b" }`,
+	}
+	for _, source := range tests {
+		t.Run(source, func(t *testing.T) {
+			_, err := eval(t, source)
+			assert.NotNil(t, err)
+			assert.False(t, errors.Is(err, ErrUnsupported))
+		})
+	}
+}
+
+func TestEvalUnsupported(t *testing.T) {
+	_, err := eval(t, `let {a, b} = {a: 1, b: 2}`)
+	assert.ErrorIs(t, err, ErrUnsupported)
+}
+
+func TestEvalCanceled(t *testing.T) {
+	program, err := parser.Parse(context.Background(), `len([1])`, nil)
+	assert.Nil(t, err)
+	ctx, cancel := context.WithCancel(context.Background())
+	cancel()
+	_, err = Eval(ctx, program, builtins.Builtins())
+	assert.ErrorIs(t, err, context.Canceled)
+}
@@ -0,0 +1,172 @@
+// Package lint checks Risor programs for likely mistakes and style issues.
+//
+// A Linter runs a set of rules over a parsed program. Each rule inspects the
+// program's syntax tree, along with the scopes and variable bindings resolved
+// from it, and reports issues:
+//
+//	program, err := parser.Parse(ctx, source, nil)
+//	if err != nil {
+//	    return err
+//	}
+//	for _, issue := range lint.New().Lint(program, source) {
+//	    fmt.Println(issue)
+//	}
+//
+// New returns a Linter with the rules from DefaultRules. Hosts can add their
+// own rules with Register, such as one that forbids calls to a deprecated
+// function, and turn off built-in rules with Disable.
+package lint
+
+import (
+	"cmp"
+	"fmt"
+	"slices"
+
+	"github.com/deepnoodle-ai/risor/v2/internal/token"
+	"github.com/deepnoodle-ai/risor/v2/pkg/ast"
+)
+
+// Severity indicates how serious an issue is.
+type Severity int
+
+const (
+	// Warning is an issue that may be intentional.
+	Warning Severity = iota
+	// Error is an issue that will cause the program to fail.
+	Error
+)
+
+// String returns "warning" or "error".
+func (s Severity) String() string {
+	if s == Error {
+		return "error"
+	}
+	return "warning"
+}
+
+// Issue is a problem found in a program by a rule.
+type Issue struct {
+	Rule     string         // name of the rule that reported the issue
+	Message  string         // description of the issue
+	Severity Severity       // how serious the issue is
+	Position token.Position // source location
+}
+
+// String formats the issue as "line:column: severity [rule] message".
+func (i Issue) String() string {
+	return fmt.Sprintf("%d:%d: %s [%s] %s", i.Position.LineNumber(), i.Position.ColumnNumber(),
+		i.Severity, i.Rule, i.Message)
+}
+
+// Rule checks a program for one kind of issue.
+type Rule interface {
+	// Name identifies the rule in reported issues, as in "unused-variable".
+	Name() string
+
+	// Check inspects the program and reports any issues to the pass.
+	Check(pass *Pass)
+}
+
+// NewRule returns a Rule with the given name that calls check.
+func NewRule(name string, check func(pass *Pass)) Rule {
+	return &funcRule{name: name, check: check}
+}
+
+type funcRule struct {
+	name  string
+	check func(pass *Pass)
+}
+
+func (r *funcRule) Name() string     { return r.name }
+func (r *funcRule) Check(pass *Pass) { r.check(pass) }
+
+// Pass holds the program being checked by a rule and collects the issues the
+// rule reports.
+type Pass struct {
+	Program *ast.Program // the program being checked
+	Source  string       // the program's source code
+
+	scopes *Scope
+	rule   string
+	issues *[]Issue
+}
+
+// Scope returns the top-level scope of the program. The variables declared
+// in the program, and the identifiers that refer to them, are resolved when
+// the pass is created, so rules can share the result.
+func (p *Pass) Scope() *Scope {
+	return p.scopes
+}
+
+// Report records an issue at the position of node.
+func (p *Pass) Report(node ast.Node, severity Severity, format string, args ...any) {
+	p.ReportAt(node.Pos(), severity, format, args...)
+}
+
+// ReportAt records an issue at the given position.
+func (p *Pass) ReportAt(pos token.Position, severity Severity, format string, args ...any) {
+	*p.issues = append(*p.issues, Issue{
+		Rule:     p.rule,
+		Message:  fmt.Sprintf(format, args...),
+		Severity: severity,
+		Position: pos,
+	})
+}
+
+// Linter runs a set of rules over programs.
+type Linter struct {
+	rules []Rule
+}
+
+// New returns a Linter with the rules from DefaultRules.
+func New() *Linter {
+	return &Linter{rules: DefaultRules()}
+}
+
+// Register adds rules to the linter. A rule with the same name as one that
+// is already registered replaces it.
+func (l *Linter) Register(rules ...Rule) {
+	for _, rule := range rules {
+		l.Disable(rule.Name())
+		l.rules = append(l.rules, rule)
+	}
+}
+
+// Disable removes the rules with the given names from the linter.
+func (l *Linter) Disable(names ...string) {
+	l.rules = slices.DeleteFunc(l.rules, func(rule Rule) bool {
+		return slices.Contains(names, rule.Name())
+	})
+}
+
+// Rules returns the names of the registered rules.
+func (l *Linter) Rules() []string {
+	names := make([]string, len(l.rules))
+	for i, rule := range l.rules {
+		names[i] = rule.Name()
+	}
+	return names
+}
+
+// Lint runs every registered rule over the program and returns the issues
+// they report, ordered by position.
+func (l *Linter) Lint(program *ast.Program, source string) []Issue {
+	var issues []Issue
+	scope := resolve(program)
+	for _, rule := range l.rules {
+		rule.Check(&Pass{
+			Program: program,
+			Source:  source,
+			scopes:  scope,
+			rule:    rule.Name(),
+			issues:  &issues,
+		})
+	}
+	slices.SortStableFunc(issues, func(a, b Issue) int {
+		return cmp.Or(
+			cmp.Compare(a.Position.Line, b.Position.Line),
+			cmp.Compare(a.Position.Column, b.Position.Column),
+		)
+	})
+	return issues
+}
@@ -0,0 +1,190 @@
+package lint
+
+import (
+	"context"
+	"slices"
+	"testing"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/parser"
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+func lint(t *testing.T, linter *Linter, source string) []Issue {
+	t.Helper()
+	program, err := parser.Parse(context.Background(), source, nil)
+	assert.Nil(t, err)
+	return linter.Lint(program, source)
+}
+
+// reported formats the issues reported by the named rules.
+func reported(issues []Issue, rules ...string) []string {
+	var result []string
+	for _, issue := range issues {
+		for _, rule := range rules {
+			if issue.Rule == rule {
+				result = append(result, issue.String())
+			}
+		}
+	}
+	return result
+}
+
+func TestVariableShadow(t *testing.T) {
+	issues := lint(t, New(), `
+let x = 1
+function f(y) {
+	let x = 2
+	let y = 3
+	try {
+		g()
+	} catch (x) {
+		return x + y
+	}
+}
+`)
+	assert.Equal(t, reported(issues, "variable-shadow"), []string{
+		`4:6: warning [variable-shadow] variable "x" shadows declaration on line 2`,
+		`5:6: warning [variable-shadow] variable "y" shadows declaration on line 3`,
+	})
+}
+
+func TestUnusedVariable(t *testing.T) {
+	issues := lint(t, New(), `
+let top = 1
+function f(a, b) {
+	let used = a
+	let unused = 2
+	let _ignored = 3
+	let count = 0
+	count++
+	return used
+}
+`)
+	assert.Equal(t, reported(issues, "unused-variable"), []string{
+		`5:6: warning [unused-variable] variable "unused" is declared but never used`,
+		`7:6: warning [unused-variable] variable "count" is declared but never used`,
+	})
+}
+
+func TestConditions(t *testing.T) {
+	issues := lint(t, New(), `
+if (true) { print(1) }
+if (0) { print(2) }
+if (x == null) { print(3) }
+if (null == null) { print(4) }
+if (x < null) { print(5) }
+x == 1
+x != 2
+x
+`)
+	assert.Equal(t, reported(issues, "constant-condition", "null-compare", "discarded-comparison"), []string{
+		`2:5: warning [constant-condition] condition is always true`,
+		`3:5: warning [constant-condition] condition is always false`,
+		`5:5: warning [null-compare] comparison with null is always true`,
+		`6:5: warning [null-compare] null can't be ordered; "<" raises a type error unless both sides are null`,
+		`7:1: warning [discarded-comparison] result of comparison is unused; did you mean "="?`,
+		`8:1: warning [discarded-comparison] result of comparison is unused`,
+	})
+}
+
+func TestConstantConditions(t *testing.T) {
+	issues := lint(t, New(), `
+if ([]) { print(1) }
+if ([...items]) { print(2) }
+if ({a: 1}) { print(3) }
+if (!"") { print(4) }
+if (function() {}) { print(5) }
+if (items) { print(6) }
+match x { 1 if false => "a", _ => "b" }
+`)
+	assert.Equal(t, reported(issues, "constant-condition"), []string{
+		`2:5: warning [constant-condition] condition is always false`,
+		`4:5: warning [constant-condition] condition is always true`,
+		`5:5: warning [constant-condition] condition is always true`,
+		`6:5: warning [constant-condition] condition is always true`,
+		`8:16: warning [constant-condition] condition is always false`,
+	})
+}
+
+func TestConstReassign(t *testing.T) {
+	issues := lint(t, New(), "const limit = 1\nfunction f() { limit = 2 }")
+	assert.Equal(t, reported(issues, "const-reassign"), []string{
+		`2:16: error [const-reassign] cannot reassign constant "limit"`,
+	})
+}
+
+func TestStringConcat(t *testing.T) {
+	issues := lint(t, New(), `
+let out = ""
+items.each(x => { out += x + "," })
+out += "done"
+function f(rows) {
+	let line = ""
+	line += "a"
+	rows.each(row => { line += row + ";" })
+	return line
+}
+let total = 0
+items.each(x => { total += x })
+`)
+	assert.Equal(t, reported(issues, "string-concat"), []string{
+		`3:19: warning [string-concat] "out" is built with += in a function, copying it on every call; use stringbuilder() instead`,
+		`8:21: warning [string-concat] "line" is built with += in a function, copying it on every call; use stringbuilder() instead`,
+	})
+}
+
+func TestScopeResolution(t *testing.T) {
+	program, err := parser.Parse(context.Background(), `
+let items = [1, 2]
+function total() { return sum(items.map(x => x * factor)) }
+let m = {items: 1, [key]: 2}
+let q = quote { items[0] > unquote(limit) }
+function greet({user: {name = fallback}, tags: [first = items[0]]}) { name + first }
+`, nil)
+	assert.Nil(t, err)
+	scope := resolve(program)
+
+	var unresolved []string
+	for _, ident := range scope.Unresolved {
+		unresolved = append(unresolved, ident.Name)
+	}
+	assert.Equal(t, unresolved, []string{"sum", "factor", "key", "limit", "fallback"})
+
+	items := scope.Lookup("items")
+	assert.NotNil(t, items)
+	assert.Len(t, items.Uses, 2)
+	assert.Equal(t, scope.Refers(items.Uses[0]), items)
+	assert.Equal(t, scope.Lookup("total").Kind, KindFunc)
+	assert.Nil(t, scope.Lookup("x"))
+}
+
+func TestRegisterRule(t *testing.T) {
+	noEval := NewRule("no-eval", func(pass *Pass) {
+		for _, ident := range pass.Scope().Unresolved {
+			if ident.Name == "eval" {
+				pass.Report(ident, Error, "eval is not allowed")
+			}
+		}
+	})
+	linter := New()
+	linter.Register(noEval)
+	linter.Disable("unused-variable", "todo-comment")
+	assert.Contains(t, linter.Rules(), "no-eval")
+	assert.False(t, slices.Contains(linter.Rules(), "todo-comment"))
+
+	issues := lint(t, linter, `
+eval("1") // TODO
+function f(eval) { let unused = eval("2") }
+`)
+	assert.Equal(t, reported(issues, "no-eval", "unused-variable", "todo-comment"), []string{
+		`2:1: error [no-eval] eval is not allowed`,
+	})
+}
+
+func TestLineRules(t *testing.T) {
+	issues := lint(t, New(), "let a = 1  \nlet b = 2 // FIXME\n")
+	assert.Equal(t, reported(issues, "trailing-whitespace", "todo-comment"), []string{
+		`1:10: warning [trailing-whitespace] trailing whitespace`,
+		`2:1: warning [todo-comment] TODO/FIXME comment found`,
+	})
+}
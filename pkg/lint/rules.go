@@ -0,0 +1,344 @@
+package lint
+
+import (
+	"strings"
+
+	"github.com/deepnoodle-ai/risor/v2/internal/token"
+	"github.com/deepnoodle-ai/risor/v2/pkg/ast"
+)
+
+// Limits used by the built-in rules.
+const (
+	MaxLineLength   = 120
+	MaxStringLength = 1000
+)
+
+// DefaultRules returns the built-in rules:
+//
+//   - variable-shadow: a let or const declaration hides another variable
+//     of the same name
+//   - unused-variable: a variable declared inside a block or function is
+//     never read
+//   - const-reassign: a constant is assigned after its declaration
+//   - constant-condition: an if condition or match guard is always truthy
+//     or always falsy, such as a literal, a function, or an empty list
+//   - null-compare: a comparison with null that always has the same result
+//     or raises a type error
+//   - discarded-comparison: a comparison is used as a statement, as when
+//     "x == 1" is written for "x = 1"
+//   - self-compare: a variable is compared to itself
+//   - empty-block: an if or else block has no statements
+//   - string-concat: a function builds an outer string with +=, which
+//     copies the whole string each time, as in items.each(x => { s += x })
+//   - long-string: a string literal is longer than MaxStringLength
+//   - trailing-whitespace: a line ends with spaces or tabs
+//   - line-too-long: a line is longer than MaxLineLength
+//   - todo-comment: a line contains TODO or FIXME
+//
+// Risor doesn't allow an assignment where a condition is expected, so the
+// mistake of writing "=" for "==" in an if condition is reported by the
+// parser. The reverse mistake is reported by discarded-comparison.
+func DefaultRules() []Rule {
+	return []Rule{
+		NewRule("variable-shadow", checkShadow),
+		NewRule("unused-variable", checkUnused),
+		NewRule("const-reassign", checkConstReassign),
+		NewRule("constant-condition", checkConstantCondition),
+		NewRule("null-compare", checkNullCompare),
+		NewRule("discarded-comparison", checkDiscardedComparison),
+		NewRule("self-compare", checkSelfCompare),
+		NewRule("empty-block", checkEmptyBlock),
+		NewRule("string-concat", checkStringConcat),
+		NewRule("long-string", checkLongString),
+		NewRule("trailing-whitespace", checkTrailingWhitespace),
+		NewRule("line-too-long", checkLineLength),
+		NewRule("todo-comment", checkTodoComment),
+	}
+}
+
+func checkShadow(pass *Pass) {
+	for scope := range pass.Scope().All() {
+		for _, v := range scope.Vars {
+			if (v.Kind == KindLet || v.Kind == KindConst) && v.Shadows != nil && v.Name != "_" {
+				pass.ReportAt(v.Pos, Warning, "variable %q shadows declaration on line %d",
+					v.Name, v.Shadows.Pos.LineNumber())
+			}
+		}
+	}
+}
+
+func checkUnused(pass *Pass) {
+	for scope := range pass.Scope().All() {
+		// Top-level variables are globals, which the host may read
+		if scope.Parent == nil {
+			continue
+		}
+		for _, v := range scope.Vars {
+			if (v.Kind == KindLet || v.Kind == KindConst) && len(v.Uses) == 0 &&
+				!strings.HasPrefix(v.Name, "_") {
+				pass.ReportAt(v.Pos, Warning, "variable %q is declared but never used", v.Name)
+			}
+		}
+	}
+}
+
+func checkConstReassign(pass *Pass) {
+	for scope := range pass.Scope().All() {
+		for _, v := range scope.Vars {
+			if v.Kind != KindConst {
+				continue
+			}
+			for _, stmt := range v.Assigns {
+				pass.Report(stmt, Error, "cannot reassign constant %q", v.Name)
+			}
+		}
+	}
+}
+
+func checkConstantCondition(pass *Pass) {
+	check := func(cond ast.Expr) {
+		if truthy, ok := constantTruth(cond); ok {
+			pass.Report(cond, Warning, "condition is always %t", truthy)
+		}
+	}
+	ast.Inspect(pass.Program, func(node ast.Node) bool {
+		switch n := node.(type) {
+		case *ast.If:
+			check(n.Cond)
+		case *ast.Match:
+			for _, arm := range n.Arms {
+				if arm.Guard != nil {
+					check(arm.Guard)
+				}
+			}
+		}
+		return true
+	})
+}
+
+func checkNullCompare(pass *Pass) {
+	ast.Inspect(pass.Program, func(node ast.Node) bool {
+		n, ok := node.(*ast.Infix)
+		if !ok || !isComparison(n.Op) {
+			return true
+		}
+		_, xNull := n.X.(*ast.Nil)
+		_, yNull := n.Y.(*ast.Nil)
+		if !xNull && !yNull {
+			return true
+		}
+		switch {
+		case n.Op != "==" && n.Op != "!=":
+			if !xNull || !yNull {
+				pass.Report(n, Warning, "null can't be ordered; %q raises a type error unless both sides are null",
+					n.Op)
+			}
+		case isLiteral(n.X) && isLiteral(n.Y):
+			result := (xNull && yNull) == (n.Op == "==")
+			pass.Report(n, Warning, "comparison with null is always %t", result)
+		}
+		return true
+	})
+}
+
+func checkDiscardedComparison(pass *Pass) {
+	check := func(stmts []ast.Node) {
+		// The last statement is the value of the block or program
+		for i := 0; i < len(stmts)-1; i++ {
+			n, ok := stmts[i].(*ast.Infix)
+			if !ok || !isComparison(n.Op) {
+				continue
+			}
+			if n.Op == "==" {
+				pass.Report(n, Warning, `result of comparison is unused; did you mean "="?`)
+			} else {
+				pass.Report(n, Warning, "result of comparison is unused")
+			}
+		}
+	}
+	check(pass.Program.Stmts)
+	ast.Inspect(pass.Program, func(node ast.Node) bool {
+		if block, ok := node.(*ast.Block); ok {
+			check(block.Stmts)
+		}
+		return true
+	})
+}
+
+func checkSelfCompare(pass *Pass) {
+	ast.Inspect(pass.Program, func(node ast.Node) bool {
+		n, ok := node.(*ast.Infix)
+		if !ok || !isComparison(n.Op) {
+			return true
+		}
+		x, xOk := n.X.(*ast.Ident)
+		y, yOk := n.Y.(*ast.Ident)
+		if xOk && yOk && x.Name == y.Name {
+			pass.Report(n, Warning, "comparing %q to itself", x.Name)
+		}
+		return true
+	})
+}
+
+func checkEmptyBlock(pass *Pass) {
+	ast.Inspect(pass.Program, func(node ast.Node) bool {
+		n, ok := node.(*ast.If)
+		if !ok {
+			return true
+		}
+		if n.Consequence != nil && len(n.Consequence.Stmts) == 0 {
+			pass.Report(n.Consequence, Warning, "empty if block")
+		}
+		if n.Alternative != nil && len(n.Alternative.Stmts) == 0 {
+			pass.Report(n.Alternative, Warning, "empty else block")
+		}
+		return true
+	})
+}
+
+func checkStringConcat(pass *Pass) {
+	for scope := range pass.Scope().All() {
+		for _, v := range scope.Vars {
+			for _, node := range v.Assigns {
+				n, ok := node.(*ast.Assign)
+				if !ok || n.Op != "+=" || !isStringExpr(n.Value) || !inNestedFunc(scope, n) {
+					continue
+				}
+				pass.Report(n, Warning, "%q is built with += in a function, copying it on every call; "+
+					"use stringbuilder() instead", v.Name)
+			}
+		}
+	}
+}
+
+// inNestedFunc reports whether node is inside a function whose body is
+// nested in scope. Such a function, typically passed to a method like each,
+// may run many times.
+func inNestedFunc(scope *Scope, node ast.Node) bool {
+	for s := range scope.All() {
+		if s != scope && s.IsFunc() &&
+			s.Node.Pos().Offset <= node.Pos().Offset && node.End().Offset <= s.Node.End().Offset {
+			return true
+		}
+	}
+	return false
+}
+
+// isStringExpr reports whether an expression is a string literal, a
+// template string, or a concatenation involving one.
+func isStringExpr(expr ast.Expr) bool {
+	switch x := expr.(type) {
+	case *ast.String:
+		return true
+	case *ast.Infix:
+		return x.Op == "+" && (isStringExpr(x.X) || isStringExpr(x.Y))
+	}
+	return false
+}
+
+func checkLongString(pass *Pass) {
+	ast.Inspect(pass.Program, func(node ast.Node) bool {
+		if n, ok := node.(*ast.String); ok && len(n.Value) > MaxStringLength {
+			pass.Report(n, Warning, "string literal is very long (%d characters)", len(n.Value))
+		}
+		return true
+	})
+}
+
+func checkTrailingWhitespace(pass *Pass) {
+	forEachLine(pass.Source, func(line string, pos token.Position) {
+		if trimmed := strings.TrimRight(line, " \t"); len(trimmed) < len(line) {
+			pass.ReportAt(pos.Advance(len(trimmed)), Warning, "trailing whitespace")
+		}
+	})
+}
+
+func checkLineLength(pass *Pass) {
+	forEachLine(pass.Source, func(line string, pos token.Position) {
+		if len(line) > MaxLineLength {
+			pass.ReportAt(pos.Advance(MaxLineLength), Warning, "line exceeds %d characters (%d)",
+				MaxLineLength, len(line))
+		}
+	})
+}
+
+func checkTodoComment(pass *Pass) {
+	forEachLine(pass.Source, func(line string, pos token.Position) {
+		if strings.Contains(line, "TODO") || strings.Contains(line, "FIXME") {
+			pass.ReportAt(pos, Warning, "TODO/FIXME comment found")
+		}
+	})
+}
+
+// forEachLine calls fn with each line of the source and the position of its
+// start.
+func forEachLine(source string, fn func(line string, pos token.Position)) {
+	var offset int
+	for i, line := range strings.Split(source, "\n") {
+		fn(strings.TrimSuffix(line, "\r"), token.Position{Char: offset, LineStart: offset, Line: i})
+		offset += len(line) + 1
+	}
+}
+
+// isComparison reports whether op is a comparison operator.
+func isComparison(op string) bool {
+	switch op {
+	case "==", "!=", "<", ">", "<=", ">=":
+		return true
+	}
+	return false
+}
+
+// isLiteral reports whether the expression is a literal with a value known
+// before the program runs.
+func isLiteral(expr ast.Expr) bool {
+	switch x := expr.(type) {
+	case *ast.Bool, *ast.Int, *ast.Float, *ast.Nil:
+		return true
+	case *ast.String:
+		return len(x.Exprs) == 0
+	}
+	return false
+}
+
+// constantTruth reports whether the truthiness of an expression is known
+// before the program runs, and if so, whether it is truthy. The rules match
+// object.IsTruthy: null, false, zero, and empty strings and collections are
+// falsy, and functions are always truthy.
+func constantTruth(expr ast.Expr) (truthy, ok bool) {
+	switch x := expr.(type) {
+	case *ast.Bool:
+		return x.Value, true
+	case *ast.Int:
+		return x.Value != 0, true
+	case *ast.Float:
+		return x.Value != 0, true
+	case *ast.Nil:
+		return false, true
+	case *ast.String:
+		return x.Value != "", len(x.Exprs) == 0
+	case *ast.Func, *ast.Tuple:
+		return true, true
+	case *ast.List:
+		// A spread may add no items, but any other item makes the list non-empty
+		for _, item := range x.Items {
+			if _, spread := item.(*ast.Spread); !spread {
+				return true, true
+			}
+		}
+		return false, len(x.Items) == 0
+	case *ast.Map:
+		for _, item := range x.Items {
+			if item.Key != nil {
+				return true, true
+			}
+		}
+		return false, len(x.Items) == 0
+	case *ast.Prefix:
+		if x.Op == "!" || x.Op == "not" {
+			truthy, ok := constantTruth(x.X)
+			return !truthy, ok
+		}
+	}
+	return false, false
+}
@@ -0,0 +1,365 @@
+package lint
+
+import (
+	"iter"
+	"maps"
+	"slices"
+
+	"github.com/deepnoodle-ai/risor/v2/internal/token"
+	"github.com/deepnoodle-ai/risor/v2/pkg/ast"
+)
+
+// VarKind indicates how a variable was declared.
+type VarKind int
+
+const (
+	KindLet    VarKind = iota // let statement, including destructuring
+	KindConst                 // const statement
+	KindFunc                  // named function
+	KindParam                 // function parameter
+	KindCatch                 // catch variable of a try statement
+	KindWith                  // variable given with "as" in a with statement
+	KindImport                // module bound by an import statement
+)
+
+// Var is a variable declared in a program.
+type Var struct {
+	Name  string
+	Kind  VarKind
+	Ident *ast.Ident     // declaring identifier; nil for destructured names
+	Pos   token.Position // position of the declaration
+	Scope *Scope         // scope the variable is declared in
+
+	// Shadows is the variable of the same name that was visible where this
+	// one was declared, if any.
+	Shadows *Var
+
+	// Uses are the identifiers that read the variable.
+	Uses []*ast.Ident
+
+	// Assigns are the statements that assign to the variable after its
+	// declaration: *ast.Assign, *ast.MultiAssign, and *ast.Postfix nodes.
+	// Compound assignments such as "x += 1" and "x++" are not counted as
+	// uses.
+	Assigns []ast.Node
+}
+
+// Scope is a lexical scope of a program. The program itself, blocks,
+// function bodies, catch blocks, and with blocks each have their own scope.
+type Scope struct {
+	Node     ast.Node // *ast.Program, *ast.Block, *ast.Func, *ast.Try, or *ast.With
+	Parent   *Scope   // nil for the top-level scope
+	Children []*Scope // nested scopes in source order
+	Vars     []*Var   // variables declared in the scope in source order
+
+	// Unresolved are the identifiers that don't refer to a variable declared
+	// in the program, such as globals provided by the host and builtins.
+	// They are only recorded on the top-level scope.
+	Unresolved []*ast.Ident
+
+	names map[string]*Var
+	refs  map[*ast.Ident]*Var
+}
+
+// Lookup returns the variable a name refers to in this scope, searching the
+// enclosing scopes if it isn't declared here, or nil if there is none.
+func (s *Scope) Lookup(name string) *Var {
+	for ; s != nil; s = s.Parent {
+		if v, ok := s.names[name]; ok {
+			return v
+		}
+	}
+	return nil
+}
+
+// Refers returns the variable an identifier in the program refers to, or nil
+// if the identifier is unresolved or doesn't refer to a variable, as with
+// attribute names.
+func (s *Scope) Refers(ident *ast.Ident) *Var {
+	for s.Parent != nil {
+		s = s.Parent
+	}
+	return s.refs[ident]
+}
+
+// All returns an iterator over this scope and all the scopes nested in it,
+// in depth-first order.
+func (s *Scope) All() iter.Seq[*Scope] {
+	return func(yield func(*Scope) bool) {
+		s.all(yield)
+	}
+}
+
+func (s *Scope) all(yield func(*Scope) bool) bool {
+	if !yield(s) {
+		return false
+	}
+	for _, child := range s.Children {
+		if !child.all(yield) {
+			return false
+		}
+	}
+	return true
+}
+
+// IsFunc reports whether the scope is the body of a function.
+func (s *Scope) IsFunc() bool {
+	_, ok := s.Node.(*ast.Func)
+	return ok
+}
+
+func newScope(node ast.Node, parent *Scope) *Scope {
+	s := &Scope{Node: node, Parent: parent, names: map[string]*Var{}}
+	if parent != nil {
+		parent.Children = append(parent.Children, s)
+		s.refs = parent.refs
+	} else {
+		s.refs = map[*ast.Ident]*Var{}
+	}
+	return s
+}
+
+// resolve builds the scopes of a program and resolves the identifiers in it
+// to the variables they refer to.
+func resolve(program *ast.Program) *Scope {
+	r := &resolver{scope: newScope(program, nil), hoisted: map[*ast.Func]bool{}}
+	r.root = r.scope
+	r.stmts(program.Stmts)
+	return r.root
+}
+
+// resolver walks a program, declaring variables in the current scope and
+// recording the uses and assignments of each.
+type resolver struct {
+	root    *Scope
+	scope   *Scope
+	hoisted map[*ast.Func]bool // named functions declared by their block
+}
+
+func (r *resolver) walk(node ast.Node) {
+	if node != nil {
+		ast.Walk(r, node)
+	}
+}
+
+// push returns a resolver for a new scope nested in the current one.
+func (r *resolver) push(node ast.Node) *resolver {
+	return &resolver{root: r.root, scope: newScope(node, r.scope), hoisted: r.hoisted}
+}
+
+// stmts resolves a list of statements. Named functions are declared before
+// the statements are walked, so they can be called before they are defined.
+func (r *resolver) stmts(stmts []ast.Node) {
+	for _, stmt := range stmts {
+		if export, ok := stmt.(*ast.Export); ok {
+			stmt = export.Decl
+		}
+		if fn, ok := stmt.(*ast.Func); ok && fn.Name != nil {
+			r.declare(fn.Name.Name, KindFunc, fn.Name, fn.Name.Pos())
+			r.hoisted[fn] = true
+		}
+	}
+	for _, stmt := range stmts {
+		r.walk(stmt)
+	}
+}
+
+func (r *resolver) declare(name string, kind VarKind, ident *ast.Ident, pos token.Position) {
+	v := &Var{Name: name, Kind: kind, Ident: ident, Pos: pos, Scope: r.scope}
+	v.Shadows = r.scope.Lookup(name)
+	r.scope.Vars = append(r.scope.Vars, v)
+	r.scope.names[name] = v
+	if ident != nil {
+		r.scope.refs[ident] = v
+	}
+}
+
+func (r *resolver) use(ident *ast.Ident) {
+	if v := r.scope.Lookup(ident.Name); v != nil {
+		v.Uses = append(v.Uses, ident)
+		r.scope.refs[ident] = v
+	} else {
+		r.root.Unresolved = append(r.root.Unresolved, ident)
+	}
+}
+
+func (r *resolver) assign(ident *ast.Ident, stmt ast.Node) {
+	if v := r.scope.Lookup(ident.Name); v != nil {
+		v.Assigns = append(v.Assigns, stmt)
+		r.scope.refs[ident] = v
+	} else {
+		r.root.Unresolved = append(r.root.Unresolved, ident)
+	}
+}
+
+// Visit implements ast.Visitor. Nodes that declare variables or open a scope
+// are handled here, and the rest are left to ast.Walk.
+func (r *resolver) Visit(node ast.Node) ast.Visitor {
+	switch n := node.(type) {
+	case *ast.Block:
+		r.push(n).stmts(n.Stmts)
+	case *ast.Var:
+		r.walk(n.Value)
+		r.declare(n.Name.Name, KindLet, n.Name, n.Name.Pos())
+	case *ast.Const:
+		r.walk(n.Value)
+		r.declare(n.Name.Name, KindConst, n.Name, n.Name.Pos())
+	case *ast.MultiVar:
+		r.walk(n.Value)
+		for _, name := range n.Names {
+			r.declare(name.Name, KindLet, name, name.Pos())
+		}
+	case *ast.ObjectDestructure:
+		for _, b := range n.Bindings {
+			r.walk(b.Default)
+		}
+		r.walk(n.Value)
+		for _, b := range n.Bindings {
+			name := b.Alias
+			if name == "" {
+				name = b.Key
+			}
+			r.declare(name, KindLet, nil, n.Lbrace)
+		}
+	case *ast.ArrayDestructure:
+		for _, e := range n.Elements {
+			r.walk(e.Default)
+		}
+		r.walk(n.Value)
+		for _, e := range n.Elements {
+			if e.Name != nil {
+				r.declare(e.Name.Name, KindLet, e.Name, e.Name.Pos())
+			}
+		}
+	case *ast.Func:
+		r.fn(n)
+	case *ast.Try:
+		if n.Body != nil {
+			r.walk(n.Body)
+		}
+		if n.CatchBlock != nil {
+			if n.CatchIdent != nil {
+				catch := r.push(n)
+				catch.declare(n.CatchIdent.Name, KindCatch, n.CatchIdent, n.CatchIdent.Pos())
+				catch.stmts(n.CatchBlock.Stmts)
+			} else {
+				r.walk(n.CatchBlock)
+			}
+		}
+		if n.FinallyBlock != nil {
+			r.walk(n.FinallyBlock)
+		}
+	case *ast.With:
+		r.walk(n.Value)
+		with := r.push(n)
+		with.declare(n.Name.Name, KindWith, n.Name, n.Name.Pos())
+		if n.Body != nil {
+			with.stmts(n.Body.Stmts)
+		}
+	case *ast.Import:
+		r.declare(n.Name(), KindImport, n.Alias, n.Pos())
+	case *ast.Assign:
+		if n.Name != nil {
+			r.assign(n.Name, n)
+		}
+		if n.Index != nil {
+			r.walk(n.Index)
+		}
+		if n.Slice != nil {
+			r.walk(n.Slice)
+		}
+		r.walk(n.Value)
+	case *ast.MultiAssign:
+		for _, name := range n.Names {
+			r.assign(name, n)
+		}
+		r.walk(n.Value)
+	case *ast.Postfix:
+		if ident, ok := n.X.(*ast.Ident); ok {
+			r.assign(ident, n)
+		} else {
+			r.walk(n.X)
+		}
+	case *ast.Quote:
+		// Quoted code runs elsewhere, so only unquoted values are evaluated
+		// in this scope
+		for _, call := range n.Unquotes() {
+			for _, arg := range call.Args {
+				r.walk(arg)
+			}
+		}
+	case *ast.Ident:
+		r.use(n)
+	case *ast.ObjectCall:
+		// The method name is not a variable
+		r.walk(n.X)
+		if n.Call != nil {
+			for _, arg := range n.Call.Args {
+				r.walk(arg)
+			}
+		}
+	case *ast.Map:
+		for _, item := range n.Items {
+			// Bare identifier keys are names, not variables
+			if _, ok := item.Key.(*ast.Ident); item.Key != nil && (item.Computed || !ok) {
+				r.walk(item.Key)
+			}
+			r.walk(item.Value)
+		}
+	default:
+		return r
+	}
+	return nil
+}
+
+// fn resolves a function literal. Its parameters and body share one scope.
+func (r *resolver) fn(n *ast.Func) {
+	body := r.push(n)
+	if n.Name != nil && !r.hoisted[n] {
+		// A named function expression can refer to itself
+		body.declare(n.Name.Name, KindFunc, n.Name, n.Name.Pos())
+	}
+	for _, name := range slices.Sorted(maps.Keys(n.Defaults)) {
+		body.walk(n.Defaults[name])
+	}
+	for _, param := range n.Params {
+		switch p := param.(type) {
+		case *ast.Ident:
+			body.declare(p.Name, KindParam, p, p.Pos())
+		case *ast.ObjectDestructureParam:
+			body.paramDefaults(p)
+			for _, name := range p.ParamNames() {
+				body.declare(name, KindParam, nil, p.Pos())
+			}
+		case *ast.ArrayDestructureParam:
+			body.paramDefaults(p)
+			for _, name := range p.ParamNames() {
+				body.declare(name, KindParam, nil, p.Pos())
+			}
+		}
+	}
+	if n.RestParam != nil {
+		body.declare(n.RestParam.Name, KindParam, n.RestParam, n.RestParam.Pos())
+	}
+	if n.Body != nil {
+		body.stmts(n.Body.Stmts)
+	}
+}
+
+// paramDefaults resolves the default values in a destructuring parameter,
+// including those in nested patterns.
+func (r *resolver) paramDefaults(param ast.FuncParam) {
+	switch p := param.(type) {
+	case *ast.ObjectDestructureParam:
+		for _, b := range p.Bindings {
+			r.walk(b.Default)
+			r.paramDefaults(b.Pattern)
+		}
+	case *ast.ArrayDestructureParam:
+		for _, e := range p.Elements {
+			r.walk(e.Default)
+			r.paramDefaults(e.Pattern)
+		}
+	}
+}
+
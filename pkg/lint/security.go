@@ -0,0 +1,145 @@
+package lint
+
+import (
+	"slices"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/ast"
+)
+
+// Capabilities are the globals, modules, and module functions that
+// SecurityRules flags by default. Each gives a script access to something
+// outside the VM when a host provides it.
+var Capabilities = []string{"cloud", "db", "exec", "http", "net.dial", "os", "redis", "rpc", "ws"}
+
+// SecurityRules returns rules for reviewing scripts from untrusted sources
+// before they are run. They report errors for code that should get a closer
+// look, rather than code that is wrong:
+//
+//   - uses-capability: a script refers to one of the Capabilities
+//   - dynamic-code: a script calls eval, compile, or run, or reads an
+//     attribute whose name is computed with getattr, which can't be checked
+//     statically
+//   - recursion: a function calls itself, which can run without bound
+//     unless the host sets step or stack limits
+//   - unbounded-range: range is called with a size known only at runtime
+//
+// Risor has no loop statements, so recursion and iteration over computed
+// ranges are how a script can run for an unbounded time.
+func SecurityRules() []Rule {
+	return []Rule{
+		NewCapabilityRule(Capabilities...),
+		NewRule("dynamic-code", checkDynamicCode),
+		NewRule("recursion", checkRecursion),
+		NewRule("unbounded-range", checkUnboundedRange),
+	}
+}
+
+// NewCapabilityRule returns a rule named "uses-capability" that reports
+// references to the given globals, and imports of modules with those names.
+// A name such as "net.dial" reports uses of that attribute of a global,
+// for modules where only some functions are capabilities. Hosts can use it
+// in place of the default rule to flag the capabilities they provide.
+func NewCapabilityRule(names ...string) Rule {
+	return NewRule("uses-capability", func(pass *Pass) {
+		unresolved := pass.Scope().Unresolved
+		for _, ident := range unresolved {
+			if slices.Contains(names, ident.Name) {
+				pass.Report(ident, Error, "use of %q", ident.Name)
+			}
+		}
+		reportAttr := func(node ast.Node, x ast.Expr, attr string) {
+			ident, ok := x.(*ast.Ident)
+			if !ok || !slices.Contains(unresolved, ident) {
+				return
+			}
+			if name := ident.Name + "." + attr; slices.Contains(names, name) {
+				pass.Report(node, Error, "use of %q", name)
+			}
+		}
+		ast.Inspect(pass.Program, func(node ast.Node) bool {
+			switch n := node.(type) {
+			case *ast.Import:
+				if slices.Contains(names, n.Path.Value) {
+					pass.Report(n, Error, "import of %q", n.Path.Value)
+				}
+			case *ast.GetAttr:
+				reportAttr(n, n.X, n.Attr.Name)
+			case *ast.ObjectCall:
+				if fn, ok := n.Call.Fun.(*ast.Ident); ok {
+					reportAttr(n, n.X, fn.Name)
+				}
+			}
+			return true
+		})
+	})
+}
+
+func checkDynamicCode(pass *Pass) {
+	for _, call := range globalCalls(pass) {
+		switch name := call.Fun.(*ast.Ident).Name; name {
+		case "eval", "compile", "run":
+			pass.Report(call, Error, "call to %s evaluates code at runtime", name)
+		case "getattr":
+			if len(call.Args) < 2 || !isStringLiteral(call.Args[1]) {
+				pass.Report(call, Error, "getattr with a computed attribute name")
+			}
+		}
+	}
+}
+
+func checkRecursion(pass *Pass) {
+	scope := pass.Scope()
+	ast.Inspect(pass.Program, func(node ast.Node) bool {
+		fn, ok := node.(*ast.Func)
+		if !ok || fn.Name == nil || fn.Body == nil {
+			return true
+		}
+		self := scope.Refers(fn.Name)
+		ast.Inspect(fn.Body, func(inner ast.Node) bool {
+			call, ok := inner.(*ast.Call)
+			if !ok {
+				return true
+			}
+			if ident, ok := call.Fun.(*ast.Ident); ok && self != nil && scope.Refers(ident) == self {
+				pass.Report(call, Error, "function %q calls itself", fn.Name.Name)
+			}
+			return true
+		})
+		return true
+	})
+}
+
+func checkUnboundedRange(pass *Pass) {
+	for _, call := range globalCalls(pass) {
+		if call.Fun.(*ast.Ident).Name != "range" {
+			continue
+		}
+		for _, arg := range call.Args {
+			if _, ok := arg.(*ast.Int); !ok {
+				pass.Report(call, Error, "range size is computed at runtime")
+				break
+			}
+		}
+	}
+}
+
+// globalCalls returns the calls in the program to functions named by
+// unresolved identifiers, such as builtins.
+func globalCalls(pass *Pass) []*ast.Call {
+	unresolved := pass.Scope().Unresolved
+	var calls []*ast.Call
+	ast.Inspect(pass.Program, func(node ast.Node) bool {
+		if call, ok := node.(*ast.Call); ok {
+			if ident, ok := call.Fun.(*ast.Ident); ok && slices.Contains(unresolved, ident) {
+				calls = append(calls, call)
+			}
+		}
+		return true
+	})
+	return calls
+}
+
+func isStringLiteral(node ast.Node) bool {
+	s, ok := node.(*ast.String)
+	return ok && len(s.Exprs) == 0
+}
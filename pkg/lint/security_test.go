@@ -0,0 +1,54 @@
+package lint
+
+import (
+	"testing"
+
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+func TestSecurityRules(t *testing.T) {
+	linter := &Linter{}
+	linter.Register(SecurityRules()...)
+	issues := lint(t, linter, `
+import "http"
+let f = os.open(path)
+let attr = getattr(f, "name") + getattr(f, name)
+eval(code)
+function walk(node) {
+	return [node, ...node.children.map(walk)] + walk(node.next)
+}
+range(10).each(i => print(i))
+range(0, n)
+function exec_later(exec) { exec() }
+`)
+	assert.Equal(t, reported(issues, "uses-capability", "dynamic-code", "recursion", "unbounded-range"), []string{
+		`2:1: error [uses-capability] import of "http"`,
+		`3:9: error [uses-capability] use of "os"`,
+		`4:33: error [dynamic-code] getattr with a computed attribute name`,
+		`5:1: error [dynamic-code] call to eval evaluates code at runtime`,
+		`7:46: error [recursion] function "walk" calls itself`,
+		`10:1: error [unbounded-range] range size is computed at runtime`,
+	})
+}
+
+func TestCapabilityRule(t *testing.T) {
+	linter := &Linter{}
+	linter.Register(NewCapabilityRule("db"))
+	issues := lint(t, linter, `db.query("select 1"); os.open("x")`)
+	assert.Equal(t, reported(issues, "uses-capability"), []string{
+		`1:1: error [uses-capability] use of "db"`,
+	})
+}
+
+func TestCapabilityRuleAttr(t *testing.T) {
+	linter := &Linter{}
+	linter.Register(NewCapabilityRule("net.dial"))
+	issues := lint(t, linter, `net.parse_ip("::1")
+let c = net.dial("tcp", "localhost:80")
+let d = net.dial
+`)
+	assert.Equal(t, reported(issues, "uses-capability"), []string{
+		`2:9: error [uses-capability] use of "net.dial"`,
+		`3:9: error [uses-capability] use of "net.dial"`,
+	})
+}
@@ -0,0 +1,169 @@
+// Package cloud lets scripts call cloud SDK clients, such as the AWS SDK's
+// S3 client, that the host creates. Each SDK method of the form
+//
+//	func (c *Client) PutObject(ctx context.Context, params *PutObjectInput, optFns ...func(*Options)) (*PutObjectOutput, error)
+//
+// is exposed to scripts as client.put_object(params), with the params map
+// converted to the input struct and the output struct converted to a map.
+//
+// Clients names the SDK clients that scripts may use, already configured
+// with the host's credentials and region. Only those clients are reachable,
+// and risor.Builtins() provides none:
+//
+//	env := risor.Builtins()
+//	env["aws"] = modcloud.Module(modcloud.Policy{
+//		Clients: modcloud.Clients(map[string]any{"s3": s3.NewFromConfig(cfg)}),
+//	})
+package cloud
+
+import (
+	"context"
+	"fmt"
+	"reflect"
+	"sort"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+)
+
+// DefaultMaxBodySize is the most bytes read from a response body when the
+// Policy doesn't set MaxBodySize.
+const DefaultMaxBodySize = 64 << 20
+
+// Factory returns the SDK client for a service, such as "s3".
+type Factory func(ctx context.Context, service string) (any, error)
+
+// Policy controls the cloud services scripts can call with cloud.client.
+// The zero Policy allows none.
+type Policy struct {
+	// Clients returns the SDK client for a service. If it is nil, scripts
+	// can't use any service.
+	Clients Factory
+
+	// MaxBodySize limits the size of response bodies, such as the contents
+	// of an S3 object, which are read into memory. If it is zero,
+	// DefaultMaxBodySize is used.
+	MaxBodySize int64
+}
+
+// Clients returns a Factory for a fixed set of clients, keyed by service.
+func Clients(clients map[string]any) Factory {
+	return func(ctx context.Context, service string) (any, error) {
+		client, ok := clients[service]
+		if !ok {
+			names := make([]string, 0, len(clients))
+			for name := range clients {
+				names = append(names, name)
+			}
+			sort.Strings(names)
+			return nil, fmt.Errorf("unknown service %q (available: %v)", service, names)
+		}
+		return client, nil
+	}
+}
+
+// Module returns the cloud module, which gets clients according to policy.
+func Module(policy Policy) *object.Module {
+	client := func(ctx context.Context, args ...object.Object) (object.Object, error) {
+		if len(args) != 1 {
+			return nil, fmt.Errorf("cloud.client: expected 1 argument, got %d", len(args))
+		}
+		service, err := object.AsString(args[0])
+		if err != nil {
+			return nil, err
+		}
+		if policy.Clients == nil {
+			return nil, fmt.Errorf("cloud.client: cloud access is not allowed")
+		}
+		sdkClient, err := policy.Clients(ctx, service)
+		if err != nil {
+			return nil, fmt.Errorf("cloud.client: %w", err)
+		}
+		return policy.NewClient(service, sdkClient)
+	}
+	return object.NewBuiltinsModule("cloud", map[string]object.Object{
+		"client": object.NewBuiltin("client", client),
+	})
+}
+
+var (
+	contextType = reflect.TypeOf((*context.Context)(nil)).Elem()
+	errorType   = reflect.TypeOf((*error)(nil)).Elem()
+)
+
+// NewClient returns a module named service whose functions call the SDK
+// methods of client. Methods are named in snake case, so PutObject becomes
+// put_object, and methods that don't have the SDK's signature are left out.
+// Response bodies are limited to the policy's MaxBodySize.
+func (p Policy) NewClient(service string, client any) (*object.Module, error) {
+	maxBody := p.MaxBodySize
+	if maxBody <= 0 {
+		maxBody = DefaultMaxBodySize
+	}
+	v := reflect.ValueOf(client)
+	if !v.IsValid() {
+		return nil, fmt.Errorf("cloud.client: no client for service %q", service)
+	}
+	methods := map[string]object.Object{}
+	for i := 0; i < v.NumMethod(); i++ {
+		method := v.Type().Method(i)
+		fn := v.Method(i)
+		if !isSDKMethod(fn.Type()) {
+			continue
+		}
+		name := snakeCase(method.Name)
+		qualified := service + "." + name
+		methods[name] = object.NewBuiltin(name, func(ctx context.Context, args ...object.Object) (object.Object, error) {
+			return invoke(ctx, qualified, fn, args, maxBody)
+		})
+	}
+	return object.NewBuiltinsModule(service, methods), nil
+}
+
+// isSDKMethod reports whether a method has the form
+// func(context.Context, *Input, ...Option) (*Output, error).
+func isSDKMethod(t reflect.Type) bool {
+	if t.NumIn() < 2 || t.NumIn() > 3 || t.NumOut() != 2 {
+		return false
+	}
+	if t.In(0) != contextType || t.Out(1) != errorType {
+		return false
+	}
+	input := t.In(1)
+	if input.Kind() != reflect.Pointer || input.Elem().Kind() != reflect.Struct {
+		return false
+	}
+	return t.NumIn() == 2 || t.IsVariadic()
+}
+
+// invoke converts the params map, calls the method, and converts its output,
+// reading at most maxBody bytes from each response body.
+func invoke(ctx context.Context, name string, fn reflect.Value, args []object.Object, maxBody int64) (object.Object, error) {
+	if len(args) > 1 {
+		return nil, fmt.Errorf("%s: expected 0-1 arguments, got %d", name, len(args))
+	}
+	input := reflect.New(fn.Type().In(1).Elem())
+	if len(args) == 1 {
+		params, err := object.AsMap(args[0])
+		if err != nil {
+			return nil, err
+		}
+		if err := setStruct(input.Elem(), params); err != nil {
+			return nil, object.TypeErrorf("%s: %s", name, err)
+		}
+	}
+	in := []reflect.Value{reflect.ValueOf(ctx), input}
+	var out []reflect.Value
+	if fn.Type().IsVariadic() {
+		out = fn.CallSlice(append(in, reflect.MakeSlice(fn.Type().In(2), 0, 0)))
+	} else {
+		out = fn.Call(in)
+	}
+	if err, _ := out[1].Interface().(error); err != nil {
+		return nil, fmt.Errorf("%s: %w", name, err)
+	}
+	result, err := toObject(out[0], maxBody)
+	if err != nil {
+		return nil, fmt.Errorf("%s: %w", name, err)
+	}
+	return result, nil
+}
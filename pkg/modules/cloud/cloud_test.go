@@ -0,0 +1,173 @@
+package cloud
+
+import (
+	"context"
+	"errors"
+	"io"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/deepnoodle-ai/risor/v2/internal/objtest"
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+type storageClass string
+
+type PutObjectInput struct {
+	Bucket       *string
+	Key          *string
+	Body         io.Reader
+	Metadata     map[string]string
+	StorageClass storageClass
+	MaxAttempts  int32
+	Expires      *time.Time
+}
+
+type PutObjectOutput struct {
+	ETag      *string
+	VersionId *string
+	Size      int64
+}
+
+type GetObjectInput struct {
+	Bucket *string
+	Key    *string
+}
+
+type GetObjectOutput struct {
+	Body          io.ReadCloser
+	ContentLength *int64
+	Tags          []string
+}
+
+type options struct{}
+
+// fakeStorage has methods shaped like those of an AWS SDK client.
+type fakeStorage struct {
+	put *PutObjectInput
+}
+
+func (s *fakeStorage) PutObject(ctx context.Context, in *PutObjectInput, optFns ...func(*options)) (*PutObjectOutput, error) {
+	s.put = in
+	return &PutObjectOutput{ETag: ptr(`"abc"`), Size: 5}, nil
+}
+
+func (s *fakeStorage) GetObject(ctx context.Context, in *GetObjectInput, optFns ...func(*options)) (*GetObjectOutput, error) {
+	if *in.Key == "missing" {
+		return nil, errors.New("NoSuchKey")
+	}
+	return &GetObjectOutput{Body: io.NopCloser(strings.NewReader("hello")), ContentLength: ptr(int64(5))}, nil
+}
+
+// Region isn't an SDK method, so it isn't exposed
+func (s *fakeStorage) Region() string {
+	return "us-east-1"
+}
+
+func ptr[T any](v T) *T {
+	return &v
+}
+
+func TestClient(t *testing.T) {
+	storage := &fakeStorage{}
+	m := Module(Policy{Clients: Clients(map[string]any{"s3": storage})})
+	client, err := objtest.Call(t, m, "client", object.NewString("s3"))
+	assert.Nil(t, err)
+	s3 := client.(*object.Module)
+	_, ok := s3.GetAttr("region")
+	assert.False(t, ok)
+
+	expires := time.Date(2024, 1, 2, 0, 0, 0, 0, time.UTC)
+	result, err := objtest.Call(t, s3, "put_object", object.NewMap(map[string]object.Object{
+		"bucket":        object.NewString("reports"),
+		"Key":           object.NewString("daily.csv"),
+		"body":          object.NewBytes([]byte("a,b\n")),
+		"metadata":      object.NewMap(map[string]object.Object{"owner": object.NewString("ops")}),
+		"storage_class": object.NewString("STANDARD_IA"),
+		"max_attempts":  object.NewInt(3),
+		"expires":       object.NewTime(expires),
+	}))
+	assert.Nil(t, err)
+	assert.Equal(t, result.Inspect(), `{"e_tag": '"abc"', "size": 5, "version_id": null}`)
+	assert.Equal(t, *storage.put.Bucket, "reports")
+	assert.Equal(t, *storage.put.Key, "daily.csv")
+	body, err := io.ReadAll(storage.put.Body)
+	assert.Nil(t, err)
+	assert.Equal(t, string(body), "a,b\n")
+	assert.Equal(t, storage.put.Metadata, map[string]string{"owner": "ops"})
+	assert.Equal(t, storage.put.StorageClass, storageClass("STANDARD_IA"))
+	assert.Equal(t, storage.put.MaxAttempts, int32(3))
+	assert.Equal(t, *storage.put.Expires, expires)
+
+	result, err = objtest.Call(t, s3, "get_object", object.NewMap(map[string]object.Object{
+		"bucket": object.NewString("reports"),
+		"key":    object.NewString("daily.csv"),
+	}))
+	assert.Nil(t, err)
+	assert.Equal(t, result.Inspect(), `{"body": bytes("hello"), "content_length": 5, "tags": []}`)
+}
+
+func TestClientErrors(t *testing.T) {
+	_, err := objtest.Call(t, Module(Policy{}), "client", object.NewString("s3"))
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), "cloud.client: cloud access is not allowed")
+
+	m := Module(Policy{Clients: Clients(map[string]any{"s3": &fakeStorage{}})})
+	_, err = objtest.Call(t, m, "client", object.NewString("sqs"))
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), `cloud.client: unknown service "sqs" (available: [s3])`)
+
+	client, err := objtest.Call(t, m, "client", object.NewString("s3"))
+	assert.Nil(t, err)
+	s3 := client.(*object.Module)
+
+	_, err = objtest.Call(t, s3, "get_object", object.NewMap(map[string]object.Object{
+		"key": object.NewString("missing"),
+	}))
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), "s3.get_object: NoSuchKey")
+
+	_, err = objtest.Call(t, s3, "put_object", object.NewMap(map[string]object.Object{
+		"bucket_name": object.NewString("reports"),
+	}))
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), `type error: s3.put_object: unknown parameter "bucket_name"`)
+
+	_, err = objtest.Call(t, s3, "put_object", object.NewMap(map[string]object.Object{
+		"max_attempts": object.NewInt(1 << 40),
+	}))
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), "type error: s3.put_object: max_attempts: 1099511627776 is out of range for int32")
+
+	_, err = objtest.Call(t, s3, "put_object", object.NewMap(map[string]object.Object{
+		"body": object.NewInt(1),
+	}))
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), "type error: s3.put_object: body: expected bytes or a string (int given)")
+}
+
+func TestClientMaxBodySize(t *testing.T) {
+	m := Module(Policy{Clients: Clients(map[string]any{"s3": &fakeStorage{}}), MaxBodySize: 4})
+	client, err := objtest.Call(t, m, "client", object.NewString("s3"))
+	assert.Nil(t, err)
+	_, err = objtest.Call(t, client.(*object.Module), "get_object", object.NewMap(map[string]object.Object{
+		"key": object.NewString("daily.csv"),
+	}))
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), "s3.get_object: response body exceeds 4 bytes")
+}
+
+func TestSnakeCase(t *testing.T) {
+	for name, expected := range map[string]string{
+		"PutObject":     "put_object",
+		"ETag":          "e_tag",
+		"ListObjectsV2": "list_objects_v2",
+		"ContentMD5":    "content_md5",
+		"ID":            "id",
+		"SSEKMSKeyId":   "ssekms_key_id",
+	} {
+		assert.Equal(t, snakeCase(name), expected)
+	}
+}
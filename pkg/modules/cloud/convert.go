@@ -0,0 +1,303 @@
+package cloud
+
+import (
+	"bytes"
+	"fmt"
+	"io"
+	"reflect"
+	"strings"
+	"time"
+	"unicode"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+)
+
+var (
+	timeType        = reflect.TypeOf(time.Time{})
+	bytesReaderType = reflect.TypeOf((*bytes.Reader)(nil))
+	readerType      = reflect.TypeOf((*io.Reader)(nil)).Elem()
+)
+
+// setStruct sets the fields of a struct from a params map. Keys match field
+// names ignoring case and underscores, so "max_keys" and "MaxKeys" both set
+// MaxKeys.
+func setStruct(dst reflect.Value, params *object.Map) error {
+	t := dst.Type()
+	for _, key := range params.SortedKeys() {
+		field, ok := findField(t, key)
+		if !ok {
+			return fmt.Errorf("unknown parameter %q", key)
+		}
+		if err := setValue(dst.FieldByIndex(field.Index), params.Value()[key]); err != nil {
+			return fmt.Errorf("%s: %w", key, err)
+		}
+	}
+	return nil
+}
+
+func findField(t reflect.Type, key string) (reflect.StructField, bool) {
+	name := strings.ReplaceAll(key, "_", "")
+	for i := 0; i < t.NumField(); i++ {
+		field := t.Field(i)
+		if field.IsExported() && strings.EqualFold(field.Name, name) {
+			return field, true
+		}
+	}
+	return reflect.StructField{}, false
+}
+
+// setValue sets dst from a script value, allocating pointers as needed.
+// Strings and bytes set io.Reader fields, such as request bodies, to a
+// reader of their contents. Null leaves dst unset.
+func setValue(dst reflect.Value, obj object.Object) error {
+	if obj == object.Nil {
+		return nil
+	}
+	t := dst.Type()
+	switch t.Kind() {
+	case reflect.Pointer:
+		elem := reflect.New(t.Elem())
+		if err := setValue(elem.Elem(), obj); err != nil {
+			return err
+		}
+		dst.Set(elem)
+		return nil
+	case reflect.Interface:
+		if bytesReaderType.AssignableTo(t) {
+			switch obj := obj.(type) {
+			case *object.Bytes:
+				dst.Set(reflect.ValueOf(bytes.NewReader(obj.Value())))
+				return nil
+			case *object.String:
+				dst.Set(reflect.ValueOf(bytes.NewReader([]byte(obj.Value()))))
+				return nil
+			}
+			return mismatch("bytes or a string", obj)
+		}
+		if t.NumMethod() == 0 {
+			if v := obj.Interface(); v != nil {
+				dst.Set(reflect.ValueOf(v))
+				return nil
+			}
+		}
+		return fmt.Errorf("cannot convert a %s to %s", obj.Type(), t)
+	case reflect.Struct:
+		if t == timeType {
+			tm, ok := obj.(*object.Time)
+			if !ok {
+				return mismatch("a time", obj)
+			}
+			dst.Set(reflect.ValueOf(tm.Value()))
+			return nil
+		}
+		m, ok := obj.(*object.Map)
+		if !ok {
+			return mismatch("a map", obj)
+		}
+		return setStruct(dst, m)
+	case reflect.Slice:
+		if t.Elem().Kind() == reflect.Uint8 {
+			switch obj := obj.(type) {
+			case *object.Bytes:
+				dst.SetBytes(bytes.Clone(obj.Value()))
+				return nil
+			case *object.String:
+				dst.SetBytes([]byte(obj.Value()))
+				return nil
+			}
+			return mismatch("bytes or a string", obj)
+		}
+		list, ok := obj.(*object.List)
+		if !ok {
+			return mismatch("a list", obj)
+		}
+		items := list.Value()
+		slice := reflect.MakeSlice(t, len(items), len(items))
+		for i, item := range items {
+			if err := setValue(slice.Index(i), item); err != nil {
+				return fmt.Errorf("[%d]: %w", i, err)
+			}
+		}
+		dst.Set(slice)
+		return nil
+	case reflect.Map:
+		m, ok := obj.(*object.Map)
+		if !ok || t.Key().Kind() != reflect.String {
+			return mismatch("a map", obj)
+		}
+		result := reflect.MakeMapWithSize(t, m.Size())
+		for _, key := range m.SortedKeys() {
+			elem := reflect.New(t.Elem()).Elem()
+			if err := setValue(elem, m.Value()[key]); err != nil {
+				return fmt.Errorf("%s: %w", key, err)
+			}
+			result.SetMapIndex(reflect.ValueOf(key).Convert(t.Key()), elem)
+		}
+		dst.Set(result)
+		return nil
+	case reflect.String:
+		s, ok := obj.(*object.String)
+		if !ok {
+			return mismatch("a string", obj)
+		}
+		dst.SetString(s.Value())
+		return nil
+	case reflect.Bool:
+		b, ok := obj.(*object.Bool)
+		if !ok {
+			return mismatch("a bool", obj)
+		}
+		dst.SetBool(b.Value())
+		return nil
+	case reflect.Int, reflect.Int8, reflect.Int16, reflect.Int32, reflect.Int64:
+		i, ok := obj.(*object.Int)
+		if !ok {
+			return mismatch("an int", obj)
+		}
+		if dst.OverflowInt(i.Value()) {
+			return fmt.Errorf("%d is out of range for %s", i.Value(), t)
+		}
+		dst.SetInt(i.Value())
+		return nil
+	case reflect.Uint, reflect.Uint8, reflect.Uint16, reflect.Uint32, reflect.Uint64:
+		i, ok := obj.(*object.Int)
+		if !ok {
+			return mismatch("an int", obj)
+		}
+		if i.Value() < 0 || dst.OverflowUint(uint64(i.Value())) {
+			return fmt.Errorf("%d is out of range for %s", i.Value(), t)
+		}
+		dst.SetUint(uint64(i.Value()))
+		return nil
+	case reflect.Float32, reflect.Float64:
+		switch obj := obj.(type) {
+		case *object.Float:
+			dst.SetFloat(obj.Value())
+			return nil
+		case *object.Int:
+			dst.SetFloat(float64(obj.Value()))
+			return nil
+		}
+		return mismatch("a number", obj)
+	default:
+		return fmt.Errorf("cannot set a parameter of type %s", t)
+	}
+}
+
+func mismatch(expected string, obj object.Object) error {
+	return fmt.Errorf("expected %s (%s given)", expected, obj.Type())
+}
+
+// toObject converts an SDK output to a script value. Structs become maps
+// with their exported fields in snake case, and readers, such as response
+// bodies, are read to the end, closed, and returned as bytes. A reader with
+// more than maxBody bytes is an error. Functions and channels are left out.
+func toObject(v reflect.Value, maxBody int64) (object.Object, error) {
+	if !v.IsValid() {
+		return object.Nil, nil
+	}
+	if (v.Kind() == reflect.Interface || v.Kind() == reflect.Pointer) && v.IsNil() {
+		return object.Nil, nil
+	}
+	if v.Type() == timeType {
+		return object.NewTime(v.Interface().(time.Time)), nil
+	}
+	if v.Type().Implements(readerType) {
+		r := v.Interface().(io.Reader)
+		data, err := io.ReadAll(io.LimitReader(r, maxBody+1))
+		if c, ok := r.(io.Closer); ok {
+			c.Close()
+		}
+		if err != nil {
+			return nil, err
+		}
+		if int64(len(data)) > maxBody {
+			return nil, fmt.Errorf("response body exceeds %d bytes", maxBody)
+		}
+		return object.NewBytes(data), nil
+	}
+	switch v.Kind() {
+	case reflect.Pointer, reflect.Interface:
+		return toObject(v.Elem(), maxBody)
+	case reflect.Struct:
+		t := v.Type()
+		items := map[string]object.Object{}
+		for i := 0; i < t.NumField(); i++ {
+			field := t.Field(i)
+			if !field.IsExported() || !convertible(field.Type) {
+				continue
+			}
+			item, err := toObject(v.Field(i), maxBody)
+			if err != nil {
+				return nil, err
+			}
+			items[snakeCase(field.Name)] = item
+		}
+		return object.NewMap(items), nil
+	case reflect.Slice, reflect.Array:
+		if v.Type().Elem().Kind() == reflect.Uint8 {
+			data := make([]byte, v.Len())
+			reflect.Copy(reflect.ValueOf(data), v)
+			return object.NewBytes(data), nil
+		}
+		items := make([]object.Object, v.Len())
+		for i := range items {
+			item, err := toObject(v.Index(i), maxBody)
+			if err != nil {
+				return nil, err
+			}
+			items[i] = item
+		}
+		return object.NewList(items), nil
+	case reflect.Map:
+		items := make(map[string]object.Object, v.Len())
+		iter := v.MapRange()
+		for iter.Next() {
+			item, err := toObject(iter.Value(), maxBody)
+			if err != nil {
+				return nil, err
+			}
+			items[fmt.Sprint(iter.Key().Interface())] = item
+		}
+		return object.NewMap(items), nil
+	case reflect.String:
+		return object.NewString(v.String()), nil
+	case reflect.Bool:
+		return object.NewBool(v.Bool()), nil
+	case reflect.Int, reflect.Int8, reflect.Int16, reflect.Int32, reflect.Int64:
+		return object.NewInt(v.Int()), nil
+	case reflect.Uint, reflect.Uint8, reflect.Uint16, reflect.Uint32, reflect.Uint64:
+		return object.NewInt(int64(v.Uint())), nil
+	case reflect.Float32, reflect.Float64:
+		return object.NewFloat(v.Float()), nil
+	default:
+		return object.Nil, nil
+	}
+}
+
+func convertible(t reflect.Type) bool {
+	switch t.Kind() {
+	case reflect.Func, reflect.Chan, reflect.UnsafePointer:
+		return false
+	}
+	return true
+}
+
+// snakeCase converts a Go name to snake case, keeping acronyms together:
+// PutObject becomes put_object, ETag e_tag, and ListObjectsV2
+// list_objects_v2.
+func snakeCase(name string) string {
+	runes := []rune(name)
+	var sb strings.Builder
+	for i, r := range runes {
+		if i > 0 && unicode.IsUpper(r) {
+			prev := runes[i-1]
+			nextLower := i+1 < len(runes) && unicode.IsLower(runes[i+1])
+			if unicode.IsLower(prev) || unicode.IsDigit(prev) || (unicode.IsUpper(prev) && nextLower) {
+				sb.WriteByte('_')
+			}
+		}
+		sb.WriteRune(unicode.ToLower(r))
+	}
+	return sb.String()
+}
@@ -0,0 +1,17 @@
+package cloud
+
+import "github.com/deepnoodle-ai/risor/v2/pkg/object"
+
+// Docs returns documentation for the cloud module.
+func Docs() []object.FuncSpec {
+	return cloudDocs
+}
+
+// ModuleDoc returns the module-level documentation.
+func ModuleDoc() string {
+	return "Cloud SDK clients provided by the host"
+}
+
+var cloudDocs = []object.FuncSpec{
+	{Name: "client", Doc: "Get the client for a service", Args: []string{"service"}, Returns: "module"},
+}
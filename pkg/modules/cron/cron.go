@@ -0,0 +1,73 @@
+// Package cron computes schedules from cron expressions, so scripts can find
+// when a job is next due, or whether it is due now, without shelling out.
+package cron
+
+import (
+	"context"
+	"fmt"
+	"time"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+)
+
+// schedule parses the cron expression given as a module function argument.
+func schedule(name string, arg object.Object) (*Schedule, string, error) {
+	expr, err := object.AsString(arg)
+	if err != nil {
+		return nil, "", err
+	}
+	s, err := Parse(expr)
+	if err != nil {
+		return nil, "", fmt.Errorf("cron.%s: %w", name, err)
+	}
+	return s, expr, nil
+}
+
+// Next returns the first time after the given time, or after now, at which
+// a cron expression fires. Times are computed in the given time's timezone,
+// or in UTC.
+func Next(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) < 1 || len(args) > 2 {
+		return nil, fmt.Errorf("cron.next: expected 1-2 arguments, got %d", len(args))
+	}
+	s, expr, err := schedule("next", args[0])
+	if err != nil {
+		return nil, err
+	}
+	from := time.Now().UTC()
+	if len(args) == 2 {
+		if from, err = object.AsTime(args[1]); err != nil {
+			return nil, err
+		}
+	}
+	next, ok := s.Next(from)
+	if !ok {
+		return nil, fmt.Errorf("cron.next: %q has no time in the next five years", expr)
+	}
+	return object.NewTime(next), nil
+}
+
+// Matches returns true if a cron expression fires at the minute containing
+// the given time.
+func Matches(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) != 2 {
+		return nil, fmt.Errorf("cron.matches: expected 2 arguments, got %d", len(args))
+	}
+	s, _, err := schedule("matches", args[0])
+	if err != nil {
+		return nil, err
+	}
+	t, err := object.AsTime(args[1])
+	if err != nil {
+		return nil, err
+	}
+	return object.NewBool(s.Matches(t)), nil
+}
+
+// Module returns the cron module.
+func Module() *object.Module {
+	return object.NewBuiltinsModule("cron", map[string]object.Object{
+		"next":    object.NewBuiltin("next", Next),
+		"matches": object.NewBuiltin("matches", Matches),
+	})
+}
@@ -0,0 +1,100 @@
+package cron
+
+import (
+	"context"
+	"testing"
+	"time"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+func TestParseErrors(t *testing.T) {
+	tests := []struct {
+		expr string
+		err  string
+	}{
+		{"* * * *", `invalid cron expression "* * * *": expected 5 fields, got 4`},
+		{"60 * * * *", `invalid cron expression "60 * * * *": invalid minute "60"`},
+		{"* * 0 * *", `invalid cron expression "* * 0 * *": invalid day of month "0"`},
+		{"*/0 * * * *", `invalid cron expression "*/0 * * * *": invalid minute step "0"`},
+		{"* 5-1 * * *", `invalid cron expression "* 5-1 * * *": invalid hour range "5-1"`},
+		{"* * * foo *", `invalid cron expression "* * * foo *": invalid month "foo"`},
+	}
+	for _, tt := range tests {
+		_, err := Parse(tt.expr)
+		assert.NotNil(t, err)
+		assert.Equal(t, err.Error(), tt.err)
+	}
+}
+
+func TestNext(t *testing.T) {
+	// A Wednesday
+	from := time.Date(2024, 1, 31, 10, 7, 30, 0, time.UTC)
+	tests := []struct {
+		expr string
+		want time.Time
+	}{
+		{"*/5 * * * *", time.Date(2024, 1, 31, 10, 10, 0, 0, time.UTC)},
+		{"7 10 * * *", time.Date(2024, 2, 1, 10, 7, 0, 0, time.UTC)},
+		{"0 9 * * mon-fri", time.Date(2024, 2, 1, 9, 0, 0, 0, time.UTC)},
+		{"30 8 * * 7", time.Date(2024, 2, 4, 8, 30, 0, 0, time.UTC)},
+		{"0 0 29 2 *", time.Date(2024, 2, 29, 0, 0, 0, 0, time.UTC)},
+		{"0 0 31 * *", time.Date(2024, 3, 31, 0, 0, 0, 0, time.UTC)},
+		// Either day field matches when both are restricted
+		{"0 12 15 * fri", time.Date(2024, 2, 2, 12, 0, 0, 0, time.UTC)},
+		{"@monthly", time.Date(2024, 2, 1, 0, 0, 0, 0, time.UTC)},
+		{"0 22-23/1,3 * * *", time.Date(2024, 1, 31, 22, 0, 0, 0, time.UTC)},
+	}
+	for _, tt := range tests {
+		s, err := Parse(tt.expr)
+		assert.Nil(t, err)
+		got, ok := s.Next(from)
+		assert.True(t, ok)
+		assert.Equal(t, got, tt.want, "next(%q)", tt.expr)
+	}
+
+	s, err := Parse("0 0 30 2 *")
+	assert.Nil(t, err)
+	_, ok := s.Next(from)
+	assert.False(t, ok)
+}
+
+func TestNextTimezone(t *testing.T) {
+	newYork, err := time.LoadLocation("America/New_York")
+	assert.Nil(t, err)
+	// Clocks go forward at 2:00 on March 10th 2024, so there is no 2:30 that day
+	s, err := Parse("30 2 * * *")
+	assert.Nil(t, err)
+	got, ok := s.Next(time.Date(2024, 3, 9, 12, 0, 0, 0, newYork))
+	assert.True(t, ok)
+	assert.Equal(t, got, time.Date(2024, 3, 11, 2, 30, 0, 0, newYork))
+}
+
+func TestModule(t *testing.T) {
+	ctx := context.Background()
+	at := object.NewTime(time.Date(2024, 1, 31, 10, 15, 45, 0, time.UTC))
+
+	result, err := Matches(ctx, object.NewString("*/15 10 * * wed"), at)
+	assert.Nil(t, err)
+	assert.Equal(t, result, object.True)
+	result, err = Matches(ctx, object.NewString("*/15 10 * * thu"), at)
+	assert.Nil(t, err)
+	assert.Equal(t, result, object.False)
+
+	result, err = Next(ctx, object.NewString("0 * * * *"), at)
+	assert.Nil(t, err)
+	assert.Equal(t, result.(*object.Time).Value(), time.Date(2024, 1, 31, 11, 0, 0, 0, time.UTC))
+
+	result, err = Next(ctx, object.NewString("@hourly"))
+	assert.Nil(t, err)
+	assert.True(t, result.(*object.Time).Value().After(time.Now()))
+
+	_, err = Next(ctx, object.NewString("bad"))
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), `cron.next: invalid cron expression "bad": expected 5 fields, got 1`)
+
+	_, err = Next(ctx, object.NewString("0 0 31 4 *"), at)
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), `cron.next: "0 0 31 4 *" has no time in the next five years`)
+}
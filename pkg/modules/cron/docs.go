@@ -0,0 +1,18 @@
+package cron
+
+import "github.com/deepnoodle-ai/risor/v2/pkg/object"
+
+// Docs returns documentation for the cron module.
+func Docs() []object.FuncSpec {
+	return cronDocs
+}
+
+// ModuleDoc returns the module-level documentation.
+func ModuleDoc() string {
+	return "Compute schedules from cron expressions"
+}
+
+var cronDocs = []object.FuncSpec{
+	{Name: "next", Doc: "Next time a cron expression fires", Args: []string{"expr", "from?"}, Returns: "time"},
+	{Name: "matches", Doc: "Check if a cron expression fires at a time", Args: []string{"expr", "time"}, Returns: "bool"},
+}
@@ -0,0 +1,134 @@
+// Package db lets scripts run SQL statements through a Driver that the host
+// provides, so they can look up data without holding a connection string.
+//
+// A host that has opened a *sql.DB can hand it to scripts with FromSQL.
+// risor.Builtins() has no database to offer, so the module is added
+// explicitly:
+//
+//	env := risor.Builtins()
+//	env["db"] = moddb.Module(moddb.FromSQL(sqlDB))
+package db
+
+import (
+	"context"
+	"fmt"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+)
+
+// Driver runs SQL statements for scripts. Arguments are nil, bool, int64,
+// float64, string, []byte, or time.Time values, bound to the statement's
+// placeholders in the driver's own syntax.
+type Driver interface {
+	// Query runs a statement that returns rows, with each row given as a
+	// map of column names to values.
+	Query(ctx context.Context, query string, args []any) ([]map[string]any, error)
+
+	// Exec runs a statement that doesn't return rows.
+	Exec(ctx context.Context, query string, args []any) (Result, error)
+}
+
+// Result describes the effect of a statement run with Exec. Fields the
+// driver doesn't report are zero.
+type Result struct {
+	RowsAffected int64
+	LastInsertID int64
+}
+
+// Module returns the db module, which runs statements with driver.
+func Module(driver Driver) *object.Module {
+	m := &module{driver: driver}
+	return object.NewBuiltinsModule("db", map[string]object.Object{
+		"exec":  object.NewBuiltin("exec", m.Exec),
+		"query": object.NewBuiltin("query", m.Query),
+	})
+}
+
+type module struct {
+	driver Driver
+}
+
+// Query implements db.query(sql, ...args), returning the rows as a list of
+// maps.
+func (m *module) Query(ctx context.Context, args ...object.Object) (object.Object, error) {
+	query, params, err := m.statement("db.query", args)
+	if err != nil {
+		return nil, err
+	}
+	rows, err := m.driver.Query(ctx, query, params)
+	if err != nil {
+		return nil, fmt.Errorf("db.query: %w", err)
+	}
+	items := make([]object.Object, len(rows))
+	for i, row := range rows {
+		values, err := object.AsObjects(row)
+		if err != nil {
+			return nil, fmt.Errorf("db.query: %w", err)
+		}
+		items[i] = object.NewMap(values)
+	}
+	return object.NewList(items), nil
+}
+
+// Exec implements db.exec(sql, ...args), returning a map with the number of
+// rows affected and the last inserted ID.
+func (m *module) Exec(ctx context.Context, args ...object.Object) (object.Object, error) {
+	query, params, err := m.statement("db.exec", args)
+	if err != nil {
+		return nil, err
+	}
+	result, err := m.driver.Exec(ctx, query, params)
+	if err != nil {
+		return nil, fmt.Errorf("db.exec: %w", err)
+	}
+	return object.NewMap(map[string]object.Object{
+		"rows_affected":  object.NewInt(result.RowsAffected),
+		"last_insert_id": object.NewInt(result.LastInsertID),
+	}), nil
+}
+
+// statement returns the SQL and the Go values of the arguments to bind.
+func (m *module) statement(name string, args []object.Object) (string, []any, error) {
+	if len(args) < 1 {
+		return "", nil, fmt.Errorf("%s: expected at least 1 argument, got 0", name)
+	}
+	query, err := object.AsString(args[0])
+	if err != nil {
+		return "", nil, err
+	}
+	if m.driver == nil {
+		return "", nil, fmt.Errorf("%s: database access is not allowed", name)
+	}
+	params := make([]any, len(args)-1)
+	for i, arg := range args[1:] {
+		if params[i], err = param(arg); err != nil {
+			return "", nil, object.TypeErrorf("%s: argument %d: %s", name, i+2, err)
+		}
+	}
+	return query, params, nil
+}
+
+// param converts a statement argument to a Go value. Decimals are passed
+// as strings so that no precision is lost.
+func param(obj object.Object) (any, error) {
+	switch obj := obj.(type) {
+	case *object.NilType:
+		return nil, nil
+	case *object.Bool:
+		return obj.Value(), nil
+	case *object.Int:
+		return obj.Value(), nil
+	case *object.Float:
+		return obj.Value(), nil
+	case *object.Decimal:
+		return obj.String(), nil
+	case *object.String:
+		return obj.Value(), nil
+	case *object.Bytes:
+		return obj.Value(), nil
+	case *object.Time:
+		return obj.Value(), nil
+	default:
+		return nil, fmt.Errorf("cannot bind a %s", obj.Type())
+	}
+}
@@ -0,0 +1,128 @@
+package db
+
+import (
+	"context"
+	"database/sql"
+	"database/sql/driver"
+	"errors"
+	"io"
+	"testing"
+	"time"
+
+	"github.com/deepnoodle-ai/risor/v2/internal/objtest"
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+type testDriver struct {
+	query string
+	args  []any
+	err   error
+}
+
+func (d *testDriver) Query(ctx context.Context, query string, args []any) ([]map[string]any, error) {
+	d.query, d.args = query, args
+	return []map[string]any{
+		{"id": int64(1), "name": "ada", "avatar": []byte{1, 2}, "deleted_at": nil},
+	}, d.err
+}
+
+func (d *testDriver) Exec(ctx context.Context, query string, args []any) (Result, error) {
+	d.query, d.args = query, args
+	return Result{RowsAffected: 2, LastInsertID: 7}, d.err
+}
+
+func TestQueryAndExec(t *testing.T) {
+	d := &testDriver{}
+	m := Module(d)
+	at := time.Date(2024, 1, 2, 3, 4, 5, 0, time.UTC)
+
+	result, err := objtest.Call(t, m, "query", object.NewString("SELECT * FROM users WHERE id = ?"),
+		object.NewInt(1), object.NewString("x"), object.Nil, object.NewTime(at))
+	assert.Nil(t, err)
+	assert.Equal(t, d.query, "SELECT * FROM users WHERE id = ?")
+	assert.Equal(t, d.args, []any{int64(1), "x", nil, at})
+	assert.Equal(t, result.Inspect(), `[{"avatar": bytes("\x01\x02"), "deleted_at": null, "id": 1, "name": "ada"}]`)
+
+	result, err = objtest.Call(t, m, "exec", object.NewString("DELETE FROM users"))
+	assert.Nil(t, err)
+	assert.Equal(t, d.args, []any{})
+	assert.Equal(t, result.Inspect(), `{"last_insert_id": 7, "rows_affected": 2}`)
+}
+
+func TestErrors(t *testing.T) {
+	_, err := objtest.Call(t, Module(nil), "query", object.NewString("SELECT 1"))
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), "db.query: database access is not allowed")
+
+	m := Module(&testDriver{err: errors.New("no such table: users")})
+	_, err = objtest.Call(t, m, "exec")
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), "db.exec: expected at least 1 argument, got 0")
+
+	_, err = objtest.Call(t, m, "exec", object.NewString("DELETE FROM users"))
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), "db.exec: no such table: users")
+
+	_, err = objtest.Call(t, m, "query", object.NewString("SELECT ?"), object.NewList(nil))
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), "type error: db.query: argument 2: cannot bind a list")
+}
+
+// fakeSQL is a database/sql driver whose queries return two fixed rows.
+type fakeSQL struct{}
+
+func (fakeSQL) Open(name string) (driver.Conn, error) { return fakeConn{}, nil }
+
+type fakeConn struct{}
+
+func (fakeConn) Prepare(query string) (driver.Stmt, error) { return fakeStmt{}, nil }
+func (fakeConn) Close() error                              { return nil }
+func (fakeConn) Begin() (driver.Tx, error)                 { return nil, errors.New("not supported") }
+
+type fakeStmt struct{}
+
+func (fakeStmt) Close() error  { return nil }
+func (fakeStmt) NumInput() int { return -1 }
+
+func (fakeStmt) Exec(args []driver.Value) (driver.Result, error) {
+	return driver.RowsAffected(int64(len(args))), nil
+}
+
+func (fakeStmt) Query(args []driver.Value) (driver.Rows, error) {
+	return &fakeRows{}, nil
+}
+
+type fakeRows struct {
+	n int
+}
+
+func (r *fakeRows) Columns() []string { return []string{"id", "name"} }
+func (r *fakeRows) Close() error      { return nil }
+
+func (r *fakeRows) Next(dest []driver.Value) error {
+	if r.n == 2 {
+		return io.EOF
+	}
+	r.n++
+	dest[0] = int64(r.n)
+	dest[1] = []byte("row")
+	return nil
+}
+
+func TestFromSQL(t *testing.T) {
+	sql.Register("risor-db-test", fakeSQL{})
+	sqlDB, err := sql.Open("risor-db-test", "")
+	assert.Nil(t, err)
+	defer sqlDB.Close()
+	m := Module(FromSQL(sqlDB))
+
+	result, err := objtest.Call(t, m, "query", object.NewString("SELECT id, name FROM t"))
+	assert.Nil(t, err)
+	assert.Equal(t, result.Inspect(), `[{"id": 1, "name": bytes("row")}, {"id": 2, "name": bytes("row")}]`)
+
+	result, err = objtest.Call(t, m, "exec", object.NewString("DELETE FROM t WHERE a = ? AND b = ?"),
+		object.NewInt(1), object.NewInt(2))
+	assert.Nil(t, err)
+	assert.Equal(t, result.Inspect(), `{"last_insert_id": 0, "rows_affected": 2}`)
+}
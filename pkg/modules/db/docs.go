@@ -0,0 +1,18 @@
+package db
+
+import "github.com/deepnoodle-ai/risor/v2/pkg/object"
+
+// Docs returns documentation for the db module.
+func Docs() []object.FuncSpec {
+	return dbDocs
+}
+
+// ModuleDoc returns the module-level documentation.
+func ModuleDoc() string {
+	return "SQL queries through a database provided by the host"
+}
+
+var dbDocs = []object.FuncSpec{
+	{Name: "exec", Doc: "Run a statement that doesn't return rows", Args: []string{"sql", "args..."}, Returns: "map"},
+	{Name: "query", Doc: "Run a query and return its rows as maps", Args: []string{"sql", "args..."}, Returns: "list"},
+}
@@ -0,0 +1,63 @@
+package db
+
+import (
+	"context"
+	"database/sql"
+)
+
+// FromSQL returns a Driver that runs statements on a database/sql handle,
+// so any registered driver, such as SQLite, Postgres, or MySQL, can back the
+// module. Values are returned as the driver scans them; some drivers return
+// text columns as bytes.
+func FromSQL(db *sql.DB) Driver {
+	return &sqlDriver{db: db}
+}
+
+type sqlDriver struct {
+	db *sql.DB
+}
+
+func (d *sqlDriver) Query(ctx context.Context, query string, args []any) ([]map[string]any, error) {
+	rows, err := d.db.QueryContext(ctx, query, args...)
+	if err != nil {
+		return nil, err
+	}
+	defer rows.Close()
+	columns, err := rows.Columns()
+	if err != nil {
+		return nil, err
+	}
+	result := []map[string]any{}
+	for rows.Next() {
+		values := make([]any, len(columns))
+		dest := make([]any, len(columns))
+		for i := range values {
+			dest[i] = &values[i]
+		}
+		if err := rows.Scan(dest...); err != nil {
+			return nil, err
+		}
+		row := make(map[string]any, len(columns))
+		for i, column := range columns {
+			row[column] = values[i]
+		}
+		result = append(result, row)
+	}
+	return result, rows.Err()
+}
+
+func (d *sqlDriver) Exec(ctx context.Context, query string, args []any) (Result, error) {
+	res, err := d.db.ExecContext(ctx, query, args...)
+	if err != nil {
+		return Result{}, err
+	}
+	// Not every driver reports these, so errors leave them zero
+	var result Result
+	if n, err := res.RowsAffected(); err == nil {
+		result.RowsAffected = n
+	}
+	if id, err := res.LastInsertId(); err == nil {
+		result.LastInsertID = id
+	}
+	return result, nil
+}
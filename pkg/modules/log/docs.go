@@ -0,0 +1,19 @@
+package log
+
+import "github.com/deepnoodle-ai/risor/v2/pkg/object"
+
+// Docs returns documentation for the log module.
+func Docs() []object.FuncSpec {
+	return logDocs
+}
+
+// ModuleDoc returns the module-level documentation.
+func ModuleDoc() string {
+	return "Structured logging through a host-provided handler"
+}
+
+var logDocs = []object.FuncSpec{
+	{Name: "info", Doc: "Log a message at info level", Args: []string{"msg", "fields?"}, Returns: "nil"},
+	{Name: "warn", Doc: "Log a message at warning level", Args: []string{"msg", "fields?"}, Returns: "nil"},
+	{Name: "error", Doc: "Log a message at error level", Args: []string{"msg", "fields?"}, Returns: "nil"},
+}
@@ -0,0 +1,66 @@
+// Package log provides structured logging for scripts. Records are forwarded
+// to the handler the host registered with vm.WithLogHandler or
+// risor.WithLogHandler, and discarded if there is none. This lets embedded
+// scripts log through the application's own logging and tracing pipeline.
+package log
+
+import (
+	"context"
+	"fmt"
+	"log/slog"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+)
+
+// emit sends a record at the given level to the registered handler. The
+// arguments are a message string and an optional map of fields.
+func emit(ctx context.Context, name string, level slog.Level, args []object.Object) (object.Object, error) {
+	if len(args) < 1 || len(args) > 2 {
+		return nil, fmt.Errorf("log.%s: expected 1-2 arguments, got %d", name, len(args))
+	}
+	msg, err := object.AsString(args[0])
+	if err != nil {
+		return nil, err
+	}
+	var fields map[string]any
+	if len(args) == 2 {
+		m, err := object.AsMap(args[1])
+		if err != nil {
+			return nil, err
+		}
+		fields = m.Interface().(map[string]any)
+	}
+	handler, ok := object.GetLogHandler(ctx)
+	if !ok {
+		return object.Nil, nil
+	}
+	record := object.LogRecord{Level: level, Message: msg, Fields: fields}
+	if err := handler(ctx, record); err != nil {
+		return nil, err
+	}
+	return object.Nil, nil
+}
+
+// Info logs a message at info level.
+func Info(ctx context.Context, args ...object.Object) (object.Object, error) {
+	return emit(ctx, "info", slog.LevelInfo, args)
+}
+
+// Warn logs a message at warning level.
+func Warn(ctx context.Context, args ...object.Object) (object.Object, error) {
+	return emit(ctx, "warn", slog.LevelWarn, args)
+}
+
+// Error logs a message at error level.
+func Error(ctx context.Context, args ...object.Object) (object.Object, error) {
+	return emit(ctx, "error", slog.LevelError, args)
+}
+
+// Module returns the log module.
+func Module() *object.Module {
+	return object.NewBuiltinsModule("log", map[string]object.Object{
+		"info":  object.NewBuiltin("info", Info),
+		"warn":  object.NewBuiltin("warn", Warn),
+		"error": object.NewBuiltin("error", Error),
+	})
+}
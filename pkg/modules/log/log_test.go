@@ -0,0 +1,87 @@
+package log
+
+import (
+	"context"
+	"errors"
+	"log/slog"
+	"testing"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+func recordingContext(records *[]object.LogRecord) context.Context {
+	return object.WithLogHandler(context.Background(), func(ctx context.Context, r object.LogRecord) error {
+		*records = append(*records, r)
+		return nil
+	})
+}
+
+func TestLevels(t *testing.T) {
+	var records []object.LogRecord
+	ctx := recordingContext(&records)
+
+	_, err := Info(ctx, object.NewString("a"))
+	assert.Nil(t, err)
+	_, err = Warn(ctx, object.NewString("b"))
+	assert.Nil(t, err)
+	_, err = Error(ctx, object.NewString("c"))
+	assert.Nil(t, err)
+
+	assert.Len(t, records, 3)
+	assert.Equal(t, records[0].Level, slog.LevelInfo)
+	assert.Equal(t, records[0].Message, "a")
+	assert.Nil(t, records[0].Fields)
+	assert.Equal(t, records[1].Level, slog.LevelWarn)
+	assert.Equal(t, records[1].Message, "b")
+	assert.Equal(t, records[2].Level, slog.LevelError)
+	assert.Equal(t, records[2].Message, "c")
+}
+
+func TestFields(t *testing.T) {
+	var records []object.LogRecord
+	ctx := recordingContext(&records)
+
+	fields := object.NewMap(map[string]object.Object{
+		"id":   object.NewInt(42),
+		"tags": object.NewList([]object.Object{object.NewString("x")}),
+	})
+	result, err := Info(ctx, object.NewString("saved"), fields)
+	assert.Nil(t, err)
+	assert.Equal(t, result, object.Nil)
+
+	assert.Len(t, records, 1)
+	assert.Equal(t, records[0].Fields, map[string]any{
+		"id":   int64(42),
+		"tags": []any{"x"},
+	})
+}
+
+func TestNoHandler(t *testing.T) {
+	result, err := Info(context.Background(), object.NewString("dropped"))
+	assert.Nil(t, err)
+	assert.Equal(t, result, object.Nil)
+}
+
+func TestHandlerError(t *testing.T) {
+	ctx := object.WithLogHandler(context.Background(), func(ctx context.Context, r object.LogRecord) error {
+		return errors.New("sink closed")
+	})
+	_, err := Warn(ctx, object.NewString("x"))
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), "sink closed")
+}
+
+func TestErrors(t *testing.T) {
+	ctx := context.Background()
+
+	_, err := Info(ctx)
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "log.info: expected 1-2 arguments, got 0")
+
+	_, err = Info(ctx, object.NewInt(1))
+	assert.NotNil(t, err)
+
+	_, err = Error(ctx, object.NewString("x"), object.NewString("not a map"))
+	assert.NotNil(t, err)
+}
@@ -0,0 +1,194 @@
+package net
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"io"
+	"net"
+	"slices"
+	"time"
+
+	"github.com/deepnoodle-ai/risor/v2/internal/netpolicy"
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+)
+
+// DefaultReadSize is the most bytes conn.read returns when no size is given.
+const DefaultReadSize = 4096
+
+// DefaultMaxReadSize is the most bytes a script may ask conn.read for when
+// the Policy doesn't set MaxReadSize.
+const DefaultMaxReadSize = 1 << 20
+
+// Dialer opens network connections. *net.Dialer implements it.
+type Dialer = netpolicy.Dialer
+
+// Policy controls the connections scripts can open with net.dial. The zero
+// Policy allows none.
+type Policy struct {
+	// Dialer opens connections. If it is nil, scripts can't open any.
+	Dialer Dialer
+
+	// Allow reports whether scripts may connect to an address, such as
+	// "db.internal:5432", on a network. If it is nil, all addresses are
+	// allowed.
+	Allow func(network, address string) bool
+
+	// MaxReadSize is the most bytes a script may ask conn.read for, which
+	// bounds the buffer each read allocates. If it is zero,
+	// DefaultMaxReadSize applies.
+	MaxReadSize int
+}
+
+var dialNetworks = []string{"tcp", "tcp4", "tcp6", "udp", "udp4", "udp6"}
+
+// Dial opens a connection to an address on a TCP or UDP network, with an
+// optional timeout. The timeout limits the time taken to connect, and then
+// each read and write on the connection.
+func (p Policy) Dial(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) < 2 || len(args) > 3 {
+		return nil, fmt.Errorf("net.dial: expected 2-3 arguments, got %d", len(args))
+	}
+	network, err := object.AsString(args[0])
+	if err != nil {
+		return nil, err
+	}
+	address, err := object.AsString(args[1])
+	if err != nil {
+		return nil, err
+	}
+	var timeout time.Duration
+	if len(args) == 3 {
+		if timeout, err = object.AsDuration(args[2]); err != nil {
+			return nil, err
+		}
+		if timeout < 0 {
+			return nil, object.ValueErrorf("net.dial: timeout must not be negative")
+		}
+	}
+	if !slices.Contains(dialNetworks, network) {
+		return nil, object.ValueErrorf("net.dial: unsupported network %q (expected tcp or udp)", network)
+	}
+	allowed := p.Allow == nil || p.Allow(network, address)
+	if err := netpolicy.Check("net.dial", p.Dialer, allowed, address); err != nil {
+		return nil, err
+	}
+	dialCtx := ctx
+	if timeout > 0 {
+		var cancel context.CancelFunc
+		dialCtx, cancel = context.WithTimeout(ctx, timeout)
+		defer cancel()
+	}
+	conn, err := p.Dialer.DialContext(dialCtx, network, address)
+	if err != nil {
+		return nil, err
+	}
+	maxRead := p.MaxReadSize
+	if maxRead <= 0 {
+		maxRead = DefaultMaxReadSize
+	}
+	return newConn(conn, timeout, maxRead), nil
+}
+
+// conn holds a connection and the timeout for each read and write on it.
+type conn struct {
+	conn    net.Conn
+	timeout *netpolicy.Timeout
+	maxRead int64
+}
+
+// NewConn returns a resource named "conn" that scripts use to read from and
+// write to c. Each read and write fails if it takes longer than the timeout,
+// unless the timeout is zero, or if the context of the call is done first.
+// Reads are limited to DefaultMaxReadSize bytes. Closing the resource closes
+// c.
+func NewConn(c net.Conn, timeout time.Duration) *object.Resource {
+	return newConn(c, timeout, DefaultMaxReadSize)
+}
+
+func newConn(c net.Conn, timeout time.Duration, maxRead int) *object.Resource {
+	cn := &conn{conn: c, timeout: netpolicy.NewTimeout(timeout), maxRead: int64(maxRead)}
+	return object.NewResource("conn", c, c.Close).
+		WithMethod("read", cn.read).
+		WithMethod("write", cn.write).
+		WithMethod("set_timeout", cn.setTimeout).
+		WithMethod("remote_addr", cn.remoteAddr)
+}
+
+// do runs a read or write, bounded by the timeout and the context.
+func (c *conn) do(ctx context.Context, fn func() (int, error)) (int, error) {
+	var n int
+	err := c.timeout.Do(ctx, c.conn, func() error {
+		var err error
+		n, err = fn()
+		return err
+	})
+	return n, err
+}
+
+func (c *conn) read(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) > 1 {
+		return nil, fmt.Errorf("conn.read: expected 0-1 arguments, got %d", len(args))
+	}
+	size := int64(DefaultReadSize)
+	if len(args) == 1 {
+		var err error
+		if size, err = object.AsInt(args[0]); err != nil {
+			return nil, err
+		}
+		if size < 1 {
+			return nil, object.ValueErrorf("conn.read: n must be positive (got %d)", size)
+		}
+		if size > c.maxRead {
+			return nil, object.ValueErrorf("conn.read: n must be at most %d (got %d)", c.maxRead, size)
+		}
+	}
+	buf := make([]byte, min(size, c.maxRead))
+	n, err := c.do(ctx, func() (int, error) { return c.conn.Read(buf) })
+	if err != nil && !errors.Is(err, io.EOF) {
+		return nil, err
+	}
+	return object.NewBytes(buf[:n]), nil
+}
+
+func (c *conn) write(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) != 1 {
+		return nil, fmt.Errorf("conn.write: expected 1 argument, got %d", len(args))
+	}
+	var data []byte
+	switch arg := args[0].(type) {
+	case *object.String:
+		data = []byte(arg.Value())
+	case *object.Bytes:
+		data = arg.Value()
+	default:
+		return nil, object.TypeErrorf("conn.write: expected a string or bytes (%s given)", args[0].Type())
+	}
+	n, err := c.do(ctx, func() (int, error) { return c.conn.Write(data) })
+	if err != nil {
+		return nil, err
+	}
+	return object.NewInt(int64(n)), nil
+}
+
+func (c *conn) setTimeout(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) != 1 {
+		return nil, fmt.Errorf("conn.set_timeout: expected 1 argument, got %d", len(args))
+	}
+	timeout, err := object.AsDuration(args[0])
+	if err != nil {
+		return nil, err
+	}
+	if timeout < 0 {
+		return nil, object.ValueErrorf("conn.set_timeout: timeout must not be negative")
+	}
+	c.timeout.Set(timeout)
+	return object.Nil, nil
+}
+
+func (c *conn) remoteAddr(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) != 0 {
+		return nil, fmt.Errorf("conn.remote_addr: expected 0 arguments, got %d", len(args))
+	}
+	return object.NewString(c.conn.RemoteAddr().String()), nil
+}
@@ -0,0 +1,112 @@
+package net
+
+import (
+	"context"
+	"errors"
+	"net"
+	"os"
+	"testing"
+
+	"github.com/deepnoodle-ai/risor/v2/internal/objtest"
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+// pipeDialer connects to the server end of an in-memory pipe.
+type pipeDialer struct {
+	server  net.Conn
+	address string
+}
+
+func (d *pipeDialer) DialContext(ctx context.Context, network, address string) (net.Conn, error) {
+	client, server := net.Pipe()
+	d.server = server
+	d.address = address
+	return client, nil
+}
+
+func TestDialPolicy(t *testing.T) {
+	ctx := context.Background()
+	addr := object.NewString("db.internal:5432")
+
+	_, err := Policy{}.Dial(ctx, object.NewString("tcp"), addr)
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), "net.dial: network access is not allowed")
+
+	policy := Policy{
+		Dialer: &pipeDialer{},
+		Allow: func(network, address string) bool {
+			return address == "localhost:8080"
+		},
+	}
+	_, err = policy.Dial(ctx, object.NewString("tcp"), addr)
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), "net.dial: connecting to db.internal:5432 is not allowed")
+
+	_, err = policy.Dial(ctx, object.NewString("unix"), addr)
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), `value error: net.dial: unsupported network "unix" (expected tcp or udp)`)
+}
+
+func TestDialReadWrite(t *testing.T) {
+	dialer := &pipeDialer{}
+	conn, err := Policy{Dialer: dialer}.Dial(context.Background(),
+		object.NewString("tcp"), object.NewString("localhost:6379"), object.NewString("1s"))
+	assert.Nil(t, err)
+	assert.Equal(t, dialer.address, "localhost:6379")
+
+	go func() {
+		buf := make([]byte, 6)
+		n, _ := dialer.server.Read(buf)
+		if string(buf[:n]) == "PING\r\n" {
+			_, _ = dialer.server.Write([]byte("+PONG\r\n"))
+		}
+	}()
+	result, err := objtest.Call(t, conn, "write", object.NewString("PING\r\n"))
+	assert.Nil(t, err)
+	assert.Equal(t, result, object.NewInt(6))
+	result, err = objtest.Call(t, conn, "read")
+	assert.Nil(t, err)
+	assert.Equal(t, result, object.NewBytes([]byte("+PONG\r\n")))
+
+	// Nothing more is written, so the next read times out
+	_, err = objtest.Call(t, conn, "set_timeout", object.NewString("20ms"))
+	assert.Nil(t, err)
+	_, err = objtest.Call(t, conn, "read", object.NewInt(10))
+	assert.True(t, errors.Is(err, os.ErrDeadlineExceeded))
+
+	// The server closing the connection reads as empty bytes
+	assert.Nil(t, dialer.server.Close())
+	result, err = objtest.Call(t, conn, "read")
+	assert.Nil(t, err)
+	assert.Equal(t, result, object.NewBytes([]byte{}))
+
+	assert.Nil(t, conn.(*object.Resource).Close())
+	_, err = objtest.Call(t, conn, "write", object.NewString("x"))
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), "value error: conn is closed")
+}
+
+func TestDialMaxReadSize(t *testing.T) {
+	conn, err := Policy{Dialer: &pipeDialer{}, MaxReadSize: 16}.Dial(context.Background(),
+		object.NewString("tcp"), object.NewString("localhost:6379"))
+	assert.Nil(t, err)
+	defer conn.(*object.Resource).Close()
+	_, err = objtest.Call(t, conn, "read", object.NewInt(17))
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), "value error: conn.read: n must be at most 16 (got 17)")
+}
+
+func TestConnContext(t *testing.T) {
+	client, server := net.Pipe()
+	defer server.Close()
+	conn := NewConn(client, 0)
+	defer conn.Close()
+
+	ctx, cancel := context.WithCancel(context.Background())
+	read, ok := conn.GetAttr("read")
+	assert.True(t, ok)
+	go cancel()
+	_, err := read.(*object.Builtin).Call(ctx)
+	assert.True(t, errors.Is(err, context.Canceled))
+}
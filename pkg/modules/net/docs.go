@@ -0,0 +1,20 @@
+package net
+
+import "github.com/deepnoodle-ai/risor/v2/pkg/object"
+
+// Docs returns documentation for the net module.
+func Docs() []object.FuncSpec {
+	return netDocs
+}
+
+// ModuleDoc returns the module-level documentation.
+func ModuleDoc() string {
+	return "IP address and CIDR range helpers, and opt-in TCP and UDP connections"
+}
+
+var netDocs = []object.FuncSpec{
+	{Name: "parse_ip", Doc: "Parse an IP address into a map describing it", Args: []string{"ip"}, Returns: "map"},
+	{Name: "cidr_contains", Doc: "Check if a CIDR range contains an IP address", Args: []string{"cidr", "ip"}, Returns: "bool"},
+	{Name: "ip_range", Doc: "List the addresses in a CIDR range or between two addresses", Args: []string{"cidr_or_first", "last?"}, Returns: "list"},
+	{Name: "dial", Doc: "Open a TCP or UDP connection, if the host allows it", Args: []string{"network", "address", "timeout?"}, Returns: "resource"},
+}
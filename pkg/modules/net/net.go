@@ -0,0 +1,152 @@
+// Package net provides helpers for IP addresses and CIDR ranges, for ops
+// scripts that would otherwise parse and compare addresses as strings, and
+// TCP and UDP connections for health checks and integration scripts.
+//
+// Scripts can't open connections by default. The module in risor.Builtins()
+// has a zero Policy, so net.dial fails. To allow connections, replace it:
+//
+//	env := risor.Builtins()
+//	env["net"] = modnet.Module(modnet.Policy{Dialer: &net.Dialer{}})
+package net
+
+import (
+	"context"
+	"fmt"
+	"net/netip"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+)
+
+// MaxRangeSize is the most addresses ip_range returns.
+const MaxRangeSize = 65536
+
+func parseAddr(name string, arg object.Object) (netip.Addr, error) {
+	s, err := object.AsString(arg)
+	if err != nil {
+		return netip.Addr{}, err
+	}
+	addr, err := netip.ParseAddr(s)
+	if err != nil {
+		return netip.Addr{}, fmt.Errorf("net.%s: invalid IP address %q", name, s)
+	}
+	return addr.Unmap(), nil
+}
+
+func parsePrefix(name string, arg object.Object) (netip.Prefix, error) {
+	s, err := object.AsString(arg)
+	if err != nil {
+		return netip.Prefix{}, err
+	}
+	prefix, err := netip.ParsePrefix(s)
+	if err != nil {
+		return netip.Prefix{}, fmt.Errorf("net.%s: invalid CIDR %q", name, s)
+	}
+	return prefix.Masked(), nil
+}
+
+// ParseIP parses an IPv4 or IPv6 address and returns a map describing it.
+func ParseIP(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) != 1 {
+		return nil, fmt.Errorf("net.parse_ip: expected 1 argument, got %d", len(args))
+	}
+	addr, err := parseAddr("parse_ip", args[0])
+	if err != nil {
+		return nil, err
+	}
+	version := int64(4)
+	if addr.Is6() {
+		version = 6
+	}
+	return object.NewMap(map[string]object.Object{
+		"ip":             object.NewString(addr.String()),
+		"version":        object.NewInt(version),
+		"is_private":     object.NewBool(addr.IsPrivate()),
+		"is_loopback":    object.NewBool(addr.IsLoopback()),
+		"is_multicast":   object.NewBool(addr.IsMulticast()),
+		"is_link_local":  object.NewBool(addr.IsLinkLocalUnicast()),
+		"is_unspecified": object.NewBool(addr.IsUnspecified()),
+	}), nil
+}
+
+// CIDRContains returns true if a CIDR range contains an IP address.
+func CIDRContains(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) != 2 {
+		return nil, fmt.Errorf("net.cidr_contains: expected 2 arguments, got %d", len(args))
+	}
+	prefix, err := parsePrefix("cidr_contains", args[0])
+	if err != nil {
+		return nil, err
+	}
+	addr, err := parseAddr("cidr_contains", args[1])
+	if err != nil {
+		return nil, err
+	}
+	return object.NewBool(prefix.Contains(addr)), nil
+}
+
+// IPRange returns the addresses in a CIDR range, or from a first to a last
+// address inclusive, as a list of strings. Ranges of more than MaxRangeSize
+// addresses are an error.
+func IPRange(ctx context.Context, args ...object.Object) (object.Object, error) {
+	var first, last netip.Addr
+	switch len(args) {
+	case 1:
+		prefix, err := parsePrefix("ip_range", args[0])
+		if err != nil {
+			return nil, err
+		}
+		if hostBits := prefix.Addr().BitLen() - prefix.Bits(); hostBits > 62 || 1<<hostBits > MaxRangeSize {
+			return nil, fmt.Errorf("net.ip_range: %s has more than %d addresses", prefix, MaxRangeSize)
+		}
+		first = prefix.Addr()
+		last = lastAddr(prefix)
+	case 2:
+		var err error
+		if first, err = parseAddr("ip_range", args[0]); err != nil {
+			return nil, err
+		}
+		if last, err = parseAddr("ip_range", args[1]); err != nil {
+			return nil, err
+		}
+		if first.BitLen() != last.BitLen() {
+			return nil, fmt.Errorf("net.ip_range: %s and %s are different IP versions", first, last)
+		}
+		if last.Less(first) {
+			return nil, fmt.Errorf("net.ip_range: %s is after %s", first, last)
+		}
+	default:
+		return nil, fmt.Errorf("net.ip_range: expected 1-2 arguments, got %d", len(args))
+	}
+	var items []object.Object
+	for addr := first; ; addr = addr.Next() {
+		if len(items) == MaxRangeSize {
+			return nil, fmt.Errorf("net.ip_range: %s to %s has more than %d addresses", first, last, MaxRangeSize)
+		}
+		items = append(items, object.NewString(addr.String()))
+		if addr == last {
+			break
+		}
+	}
+	return object.NewList(items), nil
+}
+
+// lastAddr returns the last address in a masked prefix.
+func lastAddr(prefix netip.Prefix) netip.Addr {
+	b := prefix.Addr().AsSlice()
+	for i := prefix.Bits(); i < len(b)*8; i++ {
+		b[i/8] |= 1 << (7 - i%8)
+	}
+	addr, _ := netip.AddrFromSlice(b)
+	return addr
+}
+
+// Module returns the net module, which opens connections according to
+// policy.
+func Module(policy Policy) *object.Module {
+	return object.NewBuiltinsModule("net", map[string]object.Object{
+		"parse_ip":      object.NewBuiltin("parse_ip", ParseIP),
+		"cidr_contains": object.NewBuiltin("cidr_contains", CIDRContains),
+		"ip_range":      object.NewBuiltin("ip_range", IPRange),
+		"dial":          object.NewBuiltin("dial", policy.Dial),
+	})
+}
@@ -0,0 +1,90 @@
+package net
+
+import (
+	"context"
+	"testing"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+func TestParseIP(t *testing.T) {
+	ctx := context.Background()
+	result, err := ParseIP(ctx, object.NewString("10.1.2.3"))
+	assert.Nil(t, err)
+	m := result.(*object.Map)
+	assert.Equal(t, m.Get("ip"), object.NewString("10.1.2.3"))
+	assert.Equal(t, m.Get("version"), object.NewInt(4))
+	assert.Equal(t, m.Get("is_private"), object.True)
+	assert.Equal(t, m.Get("is_loopback"), object.False)
+
+	result, err = ParseIP(ctx, object.NewString("::1"))
+	assert.Nil(t, err)
+	m = result.(*object.Map)
+	assert.Equal(t, m.Get("version"), object.NewInt(6))
+	assert.Equal(t, m.Get("is_loopback"), object.True)
+
+	// IPv4-mapped IPv6 addresses are read as IPv4
+	result, err = ParseIP(ctx, object.NewString("::ffff:192.168.0.1"))
+	assert.Nil(t, err)
+	assert.Equal(t, result.(*object.Map).Get("ip"), object.NewString("192.168.0.1"))
+
+	_, err = ParseIP(ctx, object.NewString("10.0.0.256"))
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), `net.parse_ip: invalid IP address "10.0.0.256"`)
+}
+
+func TestCIDRContains(t *testing.T) {
+	ctx := context.Background()
+	tests := []struct {
+		cidr string
+		ip   string
+		want object.Object
+	}{
+		{"10.0.0.0/8", "10.200.3.4", object.True},
+		{"10.0.0.0/8", "11.0.0.1", object.False},
+		{"192.168.1.77/24", "192.168.1.1", object.True},
+		{"2001:db8::/32", "2001:db8:1::1", object.True},
+		{"2001:db8::/32", "10.0.0.1", object.False},
+	}
+	for _, tt := range tests {
+		result, err := CIDRContains(ctx, object.NewString(tt.cidr), object.NewString(tt.ip))
+		assert.Nil(t, err)
+		assert.Equal(t, result, tt.want, "cidr_contains(%q, %q)", tt.cidr, tt.ip)
+	}
+
+	_, err := CIDRContains(ctx, object.NewString("10.0.0.0"), object.NewString("10.0.0.1"))
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), `net.cidr_contains: invalid CIDR "10.0.0.0"`)
+}
+
+func TestIPRange(t *testing.T) {
+	ctx := context.Background()
+	result, err := IPRange(ctx, object.NewString("192.168.1.5/30"))
+	assert.Nil(t, err)
+	assert.Equal(t, result, object.NewStringList([]string{
+		"192.168.1.4", "192.168.1.5", "192.168.1.6", "192.168.1.7",
+	}))
+
+	result, err = IPRange(ctx, object.NewString("10.0.0.254"), object.NewString("10.0.1.1"))
+	assert.Nil(t, err)
+	assert.Equal(t, result, object.NewStringList([]string{
+		"10.0.0.254", "10.0.0.255", "10.0.1.0", "10.0.1.1",
+	}))
+
+	result, err = IPRange(ctx, object.NewString("2001:db8::/127"))
+	assert.Nil(t, err)
+	assert.Equal(t, result, object.NewStringList([]string{"2001:db8::", "2001:db8::1"}))
+
+	_, err = IPRange(ctx, object.NewString("10.0.0.0/8"))
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), "net.ip_range: 10.0.0.0/8 has more than 65536 addresses")
+
+	_, err = IPRange(ctx, object.NewString("10.0.0.0"), object.NewString("10.2.0.0"))
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), "net.ip_range: 10.0.0.0 to 10.2.0.0 has more than 65536 addresses")
+
+	_, err = IPRange(ctx, object.NewString("10.0.0.2"), object.NewString("10.0.0.1"))
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), "net.ip_range: 10.0.0.2 is after 10.0.0.1")
+}
@@ -0,0 +1,17 @@
+package os
+
+import "github.com/deepnoodle-ai/risor/v2/pkg/object"
+
+// Docs returns documentation for the os module.
+func Docs() []object.FuncSpec {
+	return osDocs
+}
+
+// ModuleDoc returns the module-level documentation.
+func ModuleDoc() string {
+	return "File access within a directory chosen by the host, and script arguments"
+}
+
+var osDocs = []object.FuncSpec{
+	{Name: "open", Doc: "Open a file for reading or writing", Args: []string{"path", "mode?"}, Returns: "file"},
+}
@@ -0,0 +1,333 @@
+package os
+
+import (
+	"bufio"
+	"context"
+	"errors"
+	"fmt"
+	"io"
+	"os"
+	"strings"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+	"github.com/deepnoodle-ai/risor/v2/pkg/op"
+)
+
+const FILE object.Type = "file"
+
+var fileAttrs = object.NewAttrRegistry[*File]("file")
+
+func init() {
+	fileAttrs.Define("name").
+		Doc("The path the file was opened with").
+		Returns("string").
+		Getter(func(f *File) object.Object {
+			return object.NewString(f.name)
+		})
+
+	fileAttrs.Define("closed").
+		Doc("Whether the file has been closed").
+		Returns("bool").
+		Getter(func(f *File) object.Object {
+			return object.NewBool(f.resource.Closed())
+		})
+
+	fileAttrs.Define("read").
+		Doc("Read up to n bytes, or the rest of the file").
+		OptionalArg("n").
+		Returns("string").
+		Impl(func(f *File, ctx context.Context, args ...object.Object) (object.Object, error) {
+			n := int64(-1)
+			if len(args) == 1 {
+				var err error
+				if n, err = object.AsInt(args[0]); err != nil {
+					return nil, err
+				}
+				if n < 0 {
+					return nil, object.ValueErrorf("file.read: n must not be negative (got %d)", n)
+				}
+			}
+			data, err := f.Read(n)
+			if err != nil {
+				return nil, err
+			}
+			return object.NewString(string(data)), nil
+		})
+
+	fileAttrs.Define("read_line").
+		Doc("Read the next line without its line ending, or null at the end of the file").
+		Returns("string").
+		Impl(func(f *File, ctx context.Context, args ...object.Object) (object.Object, error) {
+			line, ok, err := f.ReadLine()
+			if err != nil {
+				return nil, err
+			}
+			if !ok {
+				return object.Nil, nil
+			}
+			return object.NewString(line), nil
+		})
+
+	fileAttrs.Define("write").
+		Doc("Write a string or bytes and return the number of bytes written").
+		Arg("data").
+		Returns("int").
+		Impl(func(f *File, ctx context.Context, args ...object.Object) (object.Object, error) {
+			var data []byte
+			switch arg := args[0].(type) {
+			case *object.String:
+				data = []byte(arg.Value())
+			case *object.Bytes:
+				data = arg.Value()
+			default:
+				return nil, object.TypeErrorf("file.write: expected a string or bytes (%s given)", args[0].Type())
+			}
+			n, err := f.Write(data)
+			if err != nil {
+				return nil, err
+			}
+			return object.NewInt(int64(n)), nil
+		})
+
+	fileAttrs.Define("seek").
+		Doc("Move to offset from whence (0 start, 1 current position, 2 end) and return the new position").
+		Arg("offset").
+		OptionalArg("whence").
+		Returns("int").
+		Impl(func(f *File, ctx context.Context, args ...object.Object) (object.Object, error) {
+			offset, err := object.AsInt(args[0])
+			if err != nil {
+				return nil, err
+			}
+			whence := int64(io.SeekStart)
+			if len(args) == 2 {
+				if whence, err = object.AsInt(args[1]); err != nil {
+					return nil, err
+				}
+				if whence < 0 || whence > 2 {
+					return nil, object.ValueErrorf("file.seek: whence must be 0, 1, or 2 (got %d)", whence)
+				}
+			}
+			pos, err := f.Seek(offset, int(whence))
+			if err != nil {
+				return nil, err
+			}
+			return object.NewInt(pos), nil
+		})
+
+	fileAttrs.Define("flush").
+		Doc("Write any buffered data to the file").
+		Returns("null").
+		Impl(func(f *File, ctx context.Context, args ...object.Object) (object.Object, error) {
+			if err := f.Flush(); err != nil {
+				return nil, err
+			}
+			return object.Nil, nil
+		})
+
+	fileAttrs.Define("close").
+		Doc("Flush and close the file. Closing it again does nothing").
+		Returns("null").
+		Impl(func(f *File, ctx context.Context, args ...object.Object) (object.Object, error) {
+			if err := f.Close(); err != nil {
+				return nil, err
+			}
+			return object.Nil, nil
+		})
+}
+
+// File is an open file with buffered reads and writes. Files that scripts
+// don't close are closed once they are garbage collected, as with
+// object.Resource. Enumerating a file yields its remaining lines.
+type File struct {
+	name     string
+	file     *os.File
+	reader   *bufio.Reader
+	writer   *bufio.Writer
+	resource *object.Resource
+}
+
+// NewFile returns a File that reads and writes f. The name is reported by
+// the file's name attribute.
+func NewFile(name string, f *os.File) *File {
+	writer := bufio.NewWriter(f)
+	// The release function must not refer to the File, which would keep the
+	// resource reachable and stop it from ever being released
+	release := func() error {
+		err := writer.Flush()
+		if closeErr := f.Close(); err == nil {
+			err = closeErr
+		}
+		return err
+	}
+	return &File{
+		name:     name,
+		file:     f,
+		reader:   bufio.NewReader(f),
+		writer:   writer,
+		resource: object.NewResource("file", f, release),
+	}
+}
+
+func (f *File) checkOpen() error {
+	if f.resource.Closed() {
+		return object.ValueErrorf("file %q is closed", f.name)
+	}
+	return nil
+}
+
+// startRead flushes pending writes so that reads see them.
+func (f *File) startRead() error {
+	if err := f.checkOpen(); err != nil {
+		return err
+	}
+	return f.writer.Flush()
+}
+
+// startWrite discards data read ahead of the current position, so that
+// writes go where the script expects them.
+func (f *File) startWrite() error {
+	if err := f.checkOpen(); err != nil {
+		return err
+	}
+	if buffered := f.reader.Buffered(); buffered > 0 {
+		if _, err := f.file.Seek(-int64(buffered), io.SeekCurrent); err != nil {
+			return err
+		}
+		f.reader.Reset(f.file)
+	}
+	return nil
+}
+
+// Read reads up to n bytes, or the rest of the file if n is negative. It
+// returns fewer than n bytes only at the end of the file.
+func (f *File) Read(n int64) ([]byte, error) {
+	if err := f.startRead(); err != nil {
+		return nil, err
+	}
+	if n < 0 {
+		return io.ReadAll(f.reader)
+	}
+	return io.ReadAll(io.LimitReader(f.reader, n))
+}
+
+// ReadLine reads the next line, without its "\n" or "\r\n" ending. It
+// returns false at the end of the file.
+func (f *File) ReadLine() (string, bool, error) {
+	if err := f.startRead(); err != nil {
+		return "", false, err
+	}
+	line, err := f.reader.ReadString('\n')
+	if err != nil && !errors.Is(err, io.EOF) {
+		return "", false, err
+	}
+	if line == "" && err != nil {
+		return "", false, nil
+	}
+	line = strings.TrimSuffix(line, "\n")
+	line = strings.TrimSuffix(line, "\r")
+	return line, true, nil
+}
+
+// Write writes data to the file's buffer.
+func (f *File) Write(data []byte) (int, error) {
+	if err := f.startWrite(); err != nil {
+		return 0, err
+	}
+	return f.writer.Write(data)
+}
+
+// Seek sets the position for the next read or write, as by os.File.Seek.
+func (f *File) Seek(offset int64, whence int) (int64, error) {
+	if err := f.startRead(); err != nil {
+		return 0, err
+	}
+	if whence == io.SeekCurrent {
+		// The file is ahead of the script by whatever has been buffered
+		offset -= int64(f.reader.Buffered())
+	}
+	pos, err := f.file.Seek(offset, whence)
+	if err != nil {
+		return 0, err
+	}
+	f.reader.Reset(f.file)
+	return pos, nil
+}
+
+// Flush writes any buffered data to the file.
+func (f *File) Flush() error {
+	if err := f.checkOpen(); err != nil {
+		return err
+	}
+	return f.writer.Flush()
+}
+
+// Close flushes and closes the file. Closing a closed file does nothing.
+func (f *File) Close() error {
+	return f.resource.Close()
+}
+
+// Enumerate calls fn with each remaining line of the file, numbered from
+// zero. It stops at the end of the file or at the first error.
+func (f *File) Enumerate(ctx context.Context, fn func(key, value object.Object) bool) {
+	for i := int64(0); ctx.Err() == nil; i++ {
+		line, ok, err := f.ReadLine()
+		if err != nil || !ok {
+			return
+		}
+		if !fn(object.NewInt(i), object.NewString(line)) {
+			return
+		}
+	}
+}
+
+func (f *File) Type() object.Type {
+	return FILE
+}
+
+func (f *File) Inspect() string {
+	if f.resource.Closed() {
+		return fmt.Sprintf("file(%q, closed)", f.name)
+	}
+	return fmt.Sprintf("file(%q)", f.name)
+}
+
+func (f *File) String() string {
+	return f.Inspect()
+}
+
+func (f *File) Interface() interface{} {
+	return f.file
+}
+
+func (f *File) Equals(other object.Object) bool {
+	return f == other
+}
+
+func (f *File) IsTruthy() bool {
+	return true
+}
+
+func (f *File) Attrs() []object.AttrSpec {
+	return fileAttrs.Specs()
+}
+
+func (f *File) GetAttr(name string) (object.Object, bool) {
+	return fileAttrs.GetAttr(f, name)
+}
+
+func (f *File) CallMethod(ctx context.Context, name string, args []object.Object) (object.Object, bool, error) {
+	return fileAttrs.CallMethod(ctx, f, name, args)
+}
+
+func (f *File) SetAttr(name string, value object.Object) error {
+	return object.TypeErrorf("cannot set attribute %q on file object", name)
+}
+
+func (f *File) RunOperation(opType op.BinaryOpType, right object.Object) (object.Object, error) {
+	return nil, object.TypeErrorf("unsupported operation for file: %v", opType)
+}
+
+func (f *File) MarshalJSON() ([]byte, error) {
+	return nil, errors.New("marshal error: unable to marshal file")
+}
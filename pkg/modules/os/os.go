@@ -0,0 +1,85 @@
+// Package os provides file access for scripts, limited by a Policy.
+//
+// Paths resolve inside the Policy's Root, which confines scripts to one
+// directory that the host chooses. risor.Builtins() has no such directory,
+// so a host that wants file access opens one:
+//
+//	root, err := os.OpenRoot("data")
+//	...
+//	env := risor.Builtins()
+//	env["os"] = modos.Module(modos.Policy{Root: root})
+package os
+
+import (
+	"context"
+	"fmt"
+	"os"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+)
+
+// Policy controls which files scripts can open.
+type Policy struct {
+	// Root is the directory that scripts can access. Paths are resolved
+	// relative to it and can't refer to anything outside it, including
+	// through symlinks. If Root is nil, scripts can't open any files.
+	Root *os.Root
+
+	// ReadOnly prevents scripts from opening files for writing.
+	ReadOnly bool
+
+	// Args is the list scripts see as os.args. Like os.Args in Go, it
+	// usually holds the script path followed by the script's arguments.
+	Args []string
+}
+
+// openFlags maps the modes accepted by open() to os.OpenFile flags.
+var openFlags = map[string]int{
+	"r":  os.O_RDONLY,
+	"w":  os.O_WRONLY | os.O_CREATE | os.O_TRUNC,
+	"a":  os.O_WRONLY | os.O_CREATE | os.O_APPEND,
+	"r+": os.O_RDWR,
+	"w+": os.O_RDWR | os.O_CREATE | os.O_TRUNC,
+	"a+": os.O_RDWR | os.O_CREATE | os.O_APPEND,
+}
+
+// Open opens the file at the given path with the given mode, which defaults
+// to "r", and returns a File object.
+func (p Policy) Open(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) < 1 || len(args) > 2 {
+		return nil, fmt.Errorf("os.open: expected 1-2 arguments, got %d", len(args))
+	}
+	path, err := object.AsString(args[0])
+	if err != nil {
+		return nil, err
+	}
+	mode := "r"
+	if len(args) == 2 {
+		if mode, err = object.AsString(args[1]); err != nil {
+			return nil, err
+		}
+	}
+	flags, ok := openFlags[mode]
+	if !ok {
+		return nil, object.ValueErrorf("os.open: invalid mode %q (expected r, w, a, r+, w+, or a+)", mode)
+	}
+	if p.Root == nil {
+		return nil, fmt.Errorf("os.open: file access is not allowed")
+	}
+	if p.ReadOnly && mode != "r" {
+		return nil, fmt.Errorf("os.open: opening files for writing is not allowed")
+	}
+	f, err := p.Root.OpenFile(path, flags, 0o644)
+	if err != nil {
+		return nil, err
+	}
+	return NewFile(path, f), nil
+}
+
+// Module returns the os module, which opens files according to policy.
+func Module(policy Policy) *object.Module {
+	return object.NewBuiltinsModule("os", map[string]object.Object{
+		"open": object.NewBuiltin("open", policy.Open),
+		"args": object.NewStringList(policy.Args),
+	})
+}
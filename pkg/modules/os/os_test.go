@@ -0,0 +1,137 @@
+package os
+
+import (
+	"context"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+func testPolicy(t *testing.T) (Policy, string) {
+	t.Helper()
+	dir := t.TempDir()
+	root, err := os.OpenRoot(dir)
+	assert.Nil(t, err)
+	t.Cleanup(func() { root.Close() })
+	return Policy{Root: root}, dir
+}
+
+func call(t *testing.T, f *File, name string, args ...object.Object) object.Object {
+	t.Helper()
+	result, ok, err := f.CallMethod(context.Background(), name, args)
+	assert.True(t, ok)
+	assert.Nil(t, err)
+	return result
+}
+
+func open(t *testing.T, policy Policy, path, mode string) *File {
+	t.Helper()
+	f, err := policy.Open(context.Background(), object.NewString(path), object.NewString(mode))
+	assert.Nil(t, err)
+	return f.(*File)
+}
+
+func TestFileWriteAndRead(t *testing.T) {
+	policy, dir := testPolicy(t)
+
+	f := open(t, policy, "data.txt", "w")
+	assert.Equal(t, call(t, f, "write", object.NewString("one\r\ntwo\n")), object.NewInt(9))
+	assert.Equal(t, call(t, f, "write", object.NewBytes([]byte("three"))), object.NewInt(5))
+	call(t, f, "close")
+	closed, _ := f.GetAttr("closed")
+	assert.Equal(t, closed, object.True)
+
+	data, err := os.ReadFile(filepath.Join(dir, "data.txt"))
+	assert.Nil(t, err)
+	assert.Equal(t, string(data), "one\r\ntwo\nthree")
+
+	f = open(t, policy, "data.txt", "r")
+	assert.Equal(t, call(t, f, "read", object.NewInt(2)), object.NewString("on"))
+	assert.Equal(t, call(t, f, "read_line"), object.NewString("e"))
+	assert.Equal(t, call(t, f, "read_line"), object.NewString("two"))
+	assert.Equal(t, call(t, f, "read_line"), object.NewString("three"))
+	assert.Equal(t, call(t, f, "read_line"), object.Nil)
+	assert.Equal(t, call(t, f, "read"), object.NewString(""))
+
+	assert.Equal(t, call(t, f, "seek", object.NewInt(5)), object.NewInt(5))
+	assert.Equal(t, call(t, f, "read", object.NewInt(3)), object.NewString("two"))
+	assert.Equal(t, call(t, f, "seek", object.NewInt(-5), object.NewInt(2)), object.NewInt(9))
+	assert.Equal(t, call(t, f, "read"), object.NewString("three"))
+	call(t, f, "close")
+
+	_, _, err = f.CallMethod(context.Background(), "read", nil)
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "is closed")
+}
+
+func TestFileReadWrite(t *testing.T) {
+	policy, dir := testPolicy(t)
+	assert.Nil(t, os.WriteFile(filepath.Join(dir, "data.txt"), []byte("abcdef"), 0o644))
+
+	// Writes land at the position the script has read to, even though the
+	// reader has buffered the whole file
+	f := open(t, policy, "data.txt", "r+")
+	assert.Equal(t, call(t, f, "read", object.NewInt(2)), object.NewString("ab"))
+	assert.Equal(t, call(t, f, "seek", object.NewInt(0), object.NewInt(1)), object.NewInt(2))
+	call(t, f, "write", object.NewString("XY"))
+	assert.Equal(t, call(t, f, "read"), object.NewString("ef"))
+	assert.Nil(t, f.Close())
+
+	data, err := os.ReadFile(filepath.Join(dir, "data.txt"))
+	assert.Nil(t, err)
+	assert.Equal(t, string(data), "abXYef")
+}
+
+func TestFileLines(t *testing.T) {
+	policy, dir := testPolicy(t)
+	assert.Nil(t, os.WriteFile(filepath.Join(dir, "lines.txt"), []byte("a\nb\nc\n"), 0o644))
+
+	f := open(t, policy, "lines.txt", "r")
+	defer f.Close()
+	var lines []string
+	f.Enumerate(context.Background(), func(key, value object.Object) bool {
+		lines = append(lines, value.(*object.String).Value())
+		return true
+	})
+	assert.Equal(t, lines, []string{"a", "b", "c"})
+}
+
+func TestModuleArgs(t *testing.T) {
+	m := Module(Policy{Args: []string{"deploy.risor", "--dry-run"}})
+	args, ok := m.GetAttr("args")
+	assert.True(t, ok)
+	assert.Equal(t, args.Inspect(), `["deploy.risor", "--dry-run"]`)
+
+	args, ok = Module(Policy{}).GetAttr("args")
+	assert.True(t, ok)
+	assert.Equal(t, args.Inspect(), `[]`)
+}
+
+func TestOpenPolicy(t *testing.T) {
+	ctx := context.Background()
+	policy, dir := testPolicy(t)
+	assert.Nil(t, os.WriteFile(filepath.Join(dir, "a.txt"), []byte("a"), 0o644))
+
+	// Paths can't escape the root
+	_, err := policy.Open(ctx, object.NewString("../outside.txt"), object.NewString("w"))
+	assert.NotNil(t, err)
+
+	_, err = policy.Open(ctx, object.NewString("a.txt"), object.NewString("x"))
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "invalid mode")
+
+	readOnly := Policy{Root: policy.Root, ReadOnly: true}
+	f, err := readOnly.Open(ctx, object.NewString("a.txt"))
+	assert.Nil(t, err)
+	assert.Nil(t, f.(*File).Close())
+	_, err = readOnly.Open(ctx, object.NewString("a.txt"), object.NewString("a"))
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "not allowed")
+
+	_, err = Policy{}.Open(ctx, object.NewString("a.txt"))
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "file access is not allowed")
+}
@@ -0,0 +1,157 @@
+package redis
+
+import (
+	"bufio"
+	"errors"
+	"fmt"
+	"io"
+	"net"
+	"strconv"
+	"sync"
+	"time"
+)
+
+// MaxBulkSize is the largest bulk string a Client reads, which is the
+// largest value Redis stores.
+const MaxBulkSize = 512 << 20
+
+// Error is an error reply from the server, such as "ERR value is not an
+// integer or out of range".
+type Error string
+
+func (e Error) Error() string {
+	return string(e)
+}
+
+// Client sends commands to a Redis server over a connection, speaking the
+// RESP2 protocol. Commands are sent one at a time, and a Client is safe for
+// concurrent use.
+//
+// A command that fails for any reason other than an error reply, such as a
+// timeout or a malformed reply, may leave part of a command or reply on the
+// connection. Every later command then fails too, since its reply couldn't be
+// told apart from the remains of the earlier one.
+type Client struct {
+	mu     sync.Mutex
+	conn   net.Conn
+	r      *bufio.Reader
+	w      *bufio.Writer
+	broken error // The failure that made the connection unusable
+}
+
+// NewClient returns a Client that uses conn.
+func NewClient(conn net.Conn) *Client {
+	return &Client{conn: conn, r: bufio.NewReader(conn), w: bufio.NewWriter(conn)}
+}
+
+// Do sends a command and returns its reply, which is nil, a string for a
+// status or bulk reply, an int64, or a []any for an array. An error reply is
+// returned as an Error.
+func (c *Client) Do(args ...string) (any, error) {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	if c.broken != nil {
+		return nil, fmt.Errorf("connection is unusable after an earlier error: %w", c.broken)
+	}
+	reply, err := c.do(args)
+	var replyErr Error
+	if err != nil && !errors.As(err, &replyErr) {
+		c.broken = err
+	}
+	return reply, err
+}
+
+func (c *Client) do(args []string) (any, error) {
+	fmt.Fprintf(c.w, "*%d\r\n", len(args))
+	for _, arg := range args {
+		fmt.Fprintf(c.w, "$%d\r\n%s\r\n", len(arg), arg)
+	}
+	if err := c.w.Flush(); err != nil {
+		return nil, err
+	}
+	return c.readReply()
+}
+
+func (c *Client) readReply() (any, error) {
+	line, err := c.readLine()
+	if err != nil {
+		return nil, err
+	}
+	if line == "" {
+		return nil, errors.New("invalid reply from server")
+	}
+	switch line[0] {
+	case '+':
+		return line[1:], nil
+	case '-':
+		return nil, Error(line[1:])
+	case ':':
+		n, err := strconv.ParseInt(line[1:], 10, 64)
+		if err != nil {
+			return nil, fmt.Errorf("invalid integer reply %q", line[1:])
+		}
+		return n, nil
+	case '$':
+		size, err := strconv.Atoi(line[1:])
+		if err != nil || size < -1 || size > MaxBulkSize {
+			return nil, fmt.Errorf("invalid bulk reply size %q", line[1:])
+		}
+		if size == -1 {
+			return nil, nil
+		}
+		data := make([]byte, size+2)
+		if _, err := io.ReadFull(c.r, data); err != nil {
+			return nil, err
+		}
+		return string(data[:size]), nil
+	case '*':
+		count, err := strconv.Atoi(line[1:])
+		if err != nil || count < -1 {
+			return nil, fmt.Errorf("invalid array reply size %q", line[1:])
+		}
+		if count == -1 {
+			return nil, nil
+		}
+		items := make([]any, 0, min(count, 1024))
+		for i := 0; i < count; i++ {
+			item, err := c.readReply()
+			if err != nil {
+				// Error replies inside an array are values, not failures
+				var replyErr Error
+				if !errors.As(err, &replyErr) {
+					return nil, err
+				}
+				item = replyErr
+			}
+			items = append(items, item)
+		}
+		return items, nil
+	default:
+		return nil, fmt.Errorf("invalid reply type %q", line[0])
+	}
+}
+
+// readLine reads a line without its trailing CRLF.
+func (c *Client) readLine() (string, error) {
+	line, err := c.r.ReadString('\n')
+	if err != nil {
+		if err == io.EOF && line != "" {
+			err = io.ErrUnexpectedEOF
+		}
+		return "", err
+	}
+	if len(line) < 2 || line[len(line)-2] != '\r' {
+		return "", errors.New("invalid reply from server")
+	}
+	return line[:len(line)-2], nil
+}
+
+// SetDeadline sets the deadline for reads and writes on the connection.
+func (c *Client) SetDeadline(t time.Time) error {
+	return c.conn.SetDeadline(t)
+}
+
+// Close closes the connection.
+func (c *Client) Close() error {
+	return c.conn.Close()
+}
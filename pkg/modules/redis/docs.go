@@ -0,0 +1,17 @@
+package redis
+
+import "github.com/deepnoodle-ai/risor/v2/pkg/object"
+
+// Docs returns documentation for the redis module.
+func Docs() []object.FuncSpec {
+	return redisDocs
+}
+
+// ModuleDoc returns the module-level documentation.
+func ModuleDoc() string {
+	return "Redis client for hosts that allow network access"
+}
+
+var redisDocs = []object.FuncSpec{
+	{Name: "connect", Doc: "Open a connection to a Redis server", Args: []string{"url", "options?"}, Returns: "resource"},
+}
@@ -0,0 +1,413 @@
+// Package redis provides a Redis client for scripts that cache values or
+// pass messages through Redis.
+//
+// The module only reaches the servers its Policy allows, and with no Dialer
+// it reaches none, which is why risor.Builtins() doesn't include it. To let
+// scripts use a local server:
+//
+//	env := risor.Builtins()
+//	env["redis"] = modredis.Module(modredis.Policy{
+//		Dialer: &net.Dialer{},
+//		Allow:  func(u *url.URL) bool { return u.Hostname() == "localhost" },
+//	})
+package redis
+
+import (
+	"context"
+	"crypto/tls"
+	"fmt"
+	"net"
+	"net/url"
+	"strconv"
+	"strings"
+	"time"
+
+	"github.com/deepnoodle-ai/risor/v2/internal/netpolicy"
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+)
+
+// Dialer opens network connections. *net.Dialer implements it.
+type Dialer = netpolicy.Dialer
+
+// Policy controls the connections scripts can open with redis.connect. The
+// zero Policy allows none.
+type Policy struct {
+	// Dialer opens connections to servers. If it is nil, scripts can't
+	// open any.
+	Dialer Dialer
+
+	// Allow reports whether scripts may connect to a URL. If it is nil,
+	// all URLs are allowed.
+	Allow func(u *url.URL) bool
+
+	// TLSConfig is used for rediss URLs. If it is nil, the default
+	// configuration is used.
+	TLSConfig *tls.Config
+}
+
+// Connect opens a connection to a redis or rediss URL, such as
+// "redis://:password@localhost:6379/0", authenticating and selecting the
+// database the URL gives. The optional options map may set a timeout, which
+// limits the time taken to connect and then each command.
+func (p Policy) Connect(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) < 1 || len(args) > 2 {
+		return nil, fmt.Errorf("redis.connect: expected 1-2 arguments, got %d", len(args))
+	}
+	rawURL, err := object.AsString(args[0])
+	if err != nil {
+		return nil, err
+	}
+	var timeout time.Duration
+	if len(args) == 2 {
+		options, err := object.AsMap(args[1])
+		if err != nil {
+			return nil, err
+		}
+		if timeout, err = parseOptions(options); err != nil {
+			return nil, err
+		}
+	}
+	u, err := url.Parse(rawURL)
+	if err != nil || (u.Scheme != "redis" && u.Scheme != "rediss") || u.Host == "" {
+		// The URL may hold a password, so it isn't repeated in the error
+		return nil, object.ValueErrorf("redis.connect: invalid Redis URL")
+	}
+	db, err := database(u)
+	if err != nil {
+		return nil, err
+	}
+	allowed := p.Allow == nil || p.Allow(u)
+	if err := netpolicy.Check("redis.connect", p.Dialer, allowed, u.Redacted()); err != nil {
+		return nil, err
+	}
+	if timeout > 0 {
+		var cancel context.CancelFunc
+		ctx, cancel = context.WithTimeout(ctx, timeout)
+		defer cancel()
+	}
+	client, err := p.dial(ctx, u, db)
+	if err != nil {
+		return nil, fmt.Errorf("redis.connect: %w", err)
+	}
+	return newResource(client, timeout), nil
+}
+
+func parseOptions(options *object.Map) (time.Duration, error) {
+	var timeout time.Duration
+	for key, value := range options.Value() {
+		switch key {
+		case "timeout":
+			d, err := object.AsDuration(value)
+			if err != nil {
+				return 0, err
+			}
+			if d < 0 {
+				return 0, object.ValueErrorf("redis.connect: timeout must not be negative")
+			}
+			timeout = d
+		default:
+			return 0, object.ValueErrorf("redis.connect: unknown option %q", key)
+		}
+	}
+	return timeout, nil
+}
+
+// database returns the database number given by the URL's path, or -1 if
+// it gives none.
+func database(u *url.URL) (int, error) {
+	path := strings.TrimPrefix(u.Path, "/")
+	if path == "" {
+		return -1, nil
+	}
+	db, err := strconv.Atoi(path)
+	if err != nil || db < 0 {
+		return 0, object.ValueErrorf("redis.connect: invalid database %q", path)
+	}
+	return db, nil
+}
+
+// dial connects to the server, then authenticates and selects a database,
+// all bounded by ctx.
+func (p Policy) dial(ctx context.Context, u *url.URL, db int) (*Client, error) {
+	address := u.Host
+	if u.Port() == "" {
+		address = net.JoinHostPort(u.Hostname(), "6379")
+	}
+	conn, err := p.Dialer.DialContext(ctx, "tcp", address)
+	if err != nil {
+		return nil, err
+	}
+	if u.Scheme == "rediss" {
+		config := &tls.Config{}
+		if p.TLSConfig != nil {
+			config = p.TLSConfig.Clone()
+		}
+		if config.ServerName == "" {
+			config.ServerName = u.Hostname()
+		}
+		tlsConn := tls.Client(conn, config)
+		if err := tlsConn.HandshakeContext(ctx); err != nil {
+			conn.Close()
+			return nil, err
+		}
+		conn = tlsConn
+	}
+	if deadline, ok := ctx.Deadline(); ok {
+		_ = conn.SetDeadline(deadline)
+	}
+	client := NewClient(conn)
+	var setup [][]string
+	if password, ok := u.User.Password(); ok {
+		if user := u.User.Username(); user != "" {
+			setup = append(setup, []string{"AUTH", user, password})
+		} else {
+			setup = append(setup, []string{"AUTH", password})
+		}
+	}
+	if db >= 0 {
+		setup = append(setup, []string{"SELECT", strconv.Itoa(db)})
+	}
+	for _, cmd := range setup {
+		if _, err := client.Do(cmd...); err != nil {
+			client.Close()
+			return nil, err
+		}
+	}
+	_ = conn.SetDeadline(time.Time{})
+	return client, nil
+}
+
+// session holds a client and the timeout for each command.
+type session struct {
+	client  *Client
+	timeout *netpolicy.Timeout
+}
+
+// newResource returns a resource named "redis" for scripts to use client.
+func newResource(client *Client, timeout time.Duration) *object.Resource {
+	s := &session{client: client, timeout: netpolicy.NewTimeout(timeout)}
+	return object.NewResource("redis", client, client.Close).
+		WithMethod("get", s.get).
+		WithMethod("set", s.set).
+		WithMethod("incr", s.incr).
+		WithMethod("expire", s.expire).
+		WithMethod("publish", s.publish).
+		WithMethod("set_timeout", s.setTimeout)
+}
+
+// do sends a command with a deadline from the timeout and the context, and
+// interrupts it if the context is canceled. Error replies are prefixed with
+// the method name.
+func (s *session) do(ctx context.Context, method string, args ...string) (any, error) {
+	var reply any
+	err := s.timeout.Do(ctx, s.client, func() error {
+		var err error
+		reply, err = s.client.Do(args...)
+		return err
+	})
+	if err != nil {
+		if _, ok := err.(Error); ok {
+			return nil, fmt.Errorf("redis.%s: %w", method, err)
+		}
+		return nil, err
+	}
+	return reply, nil
+}
+
+// value converts a script value to a command argument.
+func value(method string, obj object.Object) (string, error) {
+	switch obj := obj.(type) {
+	case *object.String:
+		return obj.Value(), nil
+	case *object.Bytes:
+		return string(obj.Value()), nil
+	case *object.Int:
+		return strconv.FormatInt(obj.Value(), 10), nil
+	case *object.Float:
+		return strconv.FormatFloat(obj.Value(), 'f', -1, 64), nil
+	default:
+		return "", object.TypeErrorf("redis.%s: expected a string, bytes, or number (%s given)",
+			method, obj.Type())
+	}
+}
+
+// milliseconds converts a duration argument to whole milliseconds, which
+// must be positive.
+func milliseconds(method string, obj object.Object) (string, error) {
+	d, err := object.AsDuration(obj)
+	if err != nil {
+		return "", err
+	}
+	if d < time.Millisecond {
+		return "", object.ValueErrorf("redis.%s: expiry must be at least 1ms", method)
+	}
+	return strconv.FormatInt(d.Milliseconds(), 10), nil
+}
+
+func (s *session) get(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) != 1 {
+		return nil, fmt.Errorf("redis.get: expected 1 argument, got %d", len(args))
+	}
+	key, err := object.AsString(args[0])
+	if err != nil {
+		return nil, err
+	}
+	reply, err := s.do(ctx, "get", "GET", key)
+	if err != nil {
+		return nil, err
+	}
+	switch reply := reply.(type) {
+	case nil:
+		return object.Nil, nil
+	case string:
+		return object.NewString(reply), nil
+	default:
+		return nil, fmt.Errorf("redis.get: unexpected reply %v", reply)
+	}
+}
+
+// set implements set(key, value, options?). The options are ex, an expiry
+// duration, and nx or xx, to set the key only if it doesn't or does exist.
+// It returns whether the key was set.
+func (s *session) set(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) < 2 || len(args) > 3 {
+		return nil, fmt.Errorf("redis.set: expected 2-3 arguments, got %d", len(args))
+	}
+	key, err := object.AsString(args[0])
+	if err != nil {
+		return nil, err
+	}
+	v, err := value("set", args[1])
+	if err != nil {
+		return nil, err
+	}
+	cmd := []string{"SET", key, v}
+	if len(args) == 3 {
+		options, err := object.AsMap(args[2])
+		if err != nil {
+			return nil, err
+		}
+		for _, name := range options.SortedKeys() {
+			option := options.Value()[name]
+			switch name {
+			case "ex":
+				ms, err := milliseconds("set", option)
+				if err != nil {
+					return nil, err
+				}
+				cmd = append(cmd, "PX", ms)
+			case "nx", "xx":
+				enabled, err := object.AsBool(option)
+				if err != nil {
+					return nil, err
+				}
+				if enabled {
+					cmd = append(cmd, strings.ToUpper(name))
+				}
+			default:
+				return nil, object.ValueErrorf("redis.set: unknown option %q", name)
+			}
+		}
+	}
+	reply, err := s.do(ctx, "set", cmd...)
+	if err != nil {
+		return nil, err
+	}
+	return object.NewBool(reply != nil), nil
+}
+
+// incr implements incr(key, by?), returning the new value.
+func (s *session) incr(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) < 1 || len(args) > 2 {
+		return nil, fmt.Errorf("redis.incr: expected 1-2 arguments, got %d", len(args))
+	}
+	key, err := object.AsString(args[0])
+	if err != nil {
+		return nil, err
+	}
+	cmd := []string{"INCR", key}
+	if len(args) == 2 {
+		by, err := object.AsInt(args[1])
+		if err != nil {
+			return nil, err
+		}
+		cmd = []string{"INCRBY", key, strconv.FormatInt(by, 10)}
+	}
+	reply, err := s.do(ctx, "incr", cmd...)
+	if err != nil {
+		return nil, err
+	}
+	n, ok := reply.(int64)
+	if !ok {
+		return nil, fmt.Errorf("redis.incr: unexpected reply %v", reply)
+	}
+	return object.NewInt(n), nil
+}
+
+// expire implements expire(key, ttl), returning whether the key exists.
+func (s *session) expire(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) != 2 {
+		return nil, fmt.Errorf("redis.expire: expected 2 arguments, got %d", len(args))
+	}
+	key, err := object.AsString(args[0])
+	if err != nil {
+		return nil, err
+	}
+	ms, err := milliseconds("expire", args[1])
+	if err != nil {
+		return nil, err
+	}
+	reply, err := s.do(ctx, "expire", "PEXPIRE", key, ms)
+	if err != nil {
+		return nil, err
+	}
+	return object.NewBool(reply == int64(1)), nil
+}
+
+// publish implements publish(channel, message), returning the number of
+// subscribers that received the message.
+func (s *session) publish(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) != 2 {
+		return nil, fmt.Errorf("redis.publish: expected 2 arguments, got %d", len(args))
+	}
+	channel, err := object.AsString(args[0])
+	if err != nil {
+		return nil, err
+	}
+	message, err := value("publish", args[1])
+	if err != nil {
+		return nil, err
+	}
+	reply, err := s.do(ctx, "publish", "PUBLISH", channel, message)
+	if err != nil {
+		return nil, err
+	}
+	n, ok := reply.(int64)
+	if !ok {
+		return nil, fmt.Errorf("redis.publish: unexpected reply %v", reply)
+	}
+	return object.NewInt(n), nil
+}
+
+func (s *session) setTimeout(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) != 1 {
+		return nil, fmt.Errorf("redis.set_timeout: expected 1 argument, got %d", len(args))
+	}
+	timeout, err := object.AsDuration(args[0])
+	if err != nil {
+		return nil, err
+	}
+	if timeout < 0 {
+		return nil, object.ValueErrorf("redis.set_timeout: timeout must not be negative")
+	}
+	s.timeout.Set(timeout)
+	return object.Nil, nil
+}
+
+// Module returns the redis module, which opens connections according to
+// policy.
+func Module(policy Policy) *object.Module {
+	return object.NewBuiltinsModule("redis", map[string]object.Object{
+		"connect": object.NewBuiltin("connect", policy.Connect),
+	})
+}
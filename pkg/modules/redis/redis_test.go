@@ -0,0 +1,252 @@
+package redis
+
+import (
+	"bufio"
+	"context"
+	"errors"
+	"io"
+	"net"
+	"net/url"
+	"os"
+	"strconv"
+	"strings"
+	"sync"
+	"testing"
+	"time"
+
+	"github.com/deepnoodle-ai/risor/v2/internal/objtest"
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+// testServer is a Redis server that supports the commands the module sends.
+type testServer struct {
+	mu       sync.Mutex
+	values   map[string]string
+	commands []string
+}
+
+func startServer(t *testing.T) (*testServer, string) {
+	t.Helper()
+	listener, err := net.Listen("tcp", "127.0.0.1:0")
+	assert.Nil(t, err)
+	t.Cleanup(func() { listener.Close() })
+	s := &testServer{values: map[string]string{}}
+	go func() {
+		for {
+			conn, err := listener.Accept()
+			if err != nil {
+				return
+			}
+			go s.serve(conn)
+		}
+	}()
+	return s, listener.Addr().String()
+}
+
+func (s *testServer) serve(conn net.Conn) {
+	defer conn.Close()
+	r := bufio.NewReader(conn)
+	for {
+		cmd, err := readCommand(r)
+		if err != nil {
+			return
+		}
+		s.mu.Lock()
+		s.commands = append(s.commands, strings.Join(cmd, " "))
+		reply := s.handle(cmd)
+		s.mu.Unlock()
+		if _, err := io.WriteString(conn, reply); err != nil {
+			return
+		}
+	}
+}
+
+func (s *testServer) handle(cmd []string) string {
+	switch cmd[0] {
+	case "AUTH", "SELECT":
+		return "+OK\r\n"
+	case "GET":
+		v, ok := s.values[cmd[1]]
+		if !ok {
+			return "$-1\r\n"
+		}
+		return "$" + strconv.Itoa(len(v)) + "\r\n" + v + "\r\n"
+	case "SET":
+		if _, ok := s.values[cmd[1]]; ok && len(cmd) > 3 && cmd[len(cmd)-1] == "NX" {
+			return "$-1\r\n"
+		}
+		s.values[cmd[1]] = cmd[2]
+		return "+OK\r\n"
+	case "INCR", "INCRBY":
+		by := int64(1)
+		if len(cmd) == 3 {
+			by, _ = strconv.ParseInt(cmd[2], 10, 64)
+		}
+		n, err := strconv.ParseInt(s.values[cmd[1]], 10, 64)
+		if err != nil && s.values[cmd[1]] != "" {
+			return "-ERR value is not an integer or out of range\r\n"
+		}
+		s.values[cmd[1]] = strconv.FormatInt(n+by, 10)
+		return ":" + s.values[cmd[1]] + "\r\n"
+	case "PEXPIRE":
+		if _, ok := s.values[cmd[1]]; ok {
+			return ":1\r\n"
+		}
+		return ":0\r\n"
+	case "PUBLISH":
+		return ":2\r\n"
+	default:
+		return "-ERR unknown command\r\n"
+	}
+}
+
+func readCommand(r *bufio.Reader) ([]string, error) {
+	line, err := r.ReadString('\n')
+	if err != nil {
+		return nil, err
+	}
+	count, _ := strconv.Atoi(strings.TrimSpace(line[1:]))
+	cmd := make([]string, count)
+	for i := range cmd {
+		line, err := r.ReadString('\n')
+		if err != nil {
+			return nil, err
+		}
+		size, _ := strconv.Atoi(strings.TrimSpace(line[1:]))
+		data := make([]byte, size+2)
+		if _, err := io.ReadFull(r, data); err != nil {
+			return nil, err
+		}
+		cmd[i] = string(data[:size])
+	}
+	return cmd, nil
+}
+
+func (s *testServer) log() []string {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	return append([]string(nil), s.commands...)
+}
+
+func TestCommands(t *testing.T) {
+	server, address := startServer(t)
+	ctx := context.Background()
+	conn, err := Policy{Dialer: &net.Dialer{}}.Connect(ctx,
+		object.NewString("redis://app:secret@"+address+"/2"))
+	assert.Nil(t, err)
+	defer conn.(*object.Resource).Close()
+
+	result, err := objtest.Call(t, conn, "get", object.NewString("greeting"))
+	assert.Nil(t, err)
+	assert.Equal(t, result, object.Object(object.Nil))
+
+	result, err = objtest.Call(t, conn, "set", object.NewString("greeting"), object.NewString("hi"),
+		object.NewMap(map[string]object.Object{"ex": object.NewString("1m"), "nx": object.True}))
+	assert.Nil(t, err)
+	assert.Equal(t, result, object.Object(object.True))
+
+	result, err = objtest.Call(t, conn, "set", object.NewString("greeting"), object.NewString("hey"),
+		object.NewMap(map[string]object.Object{"nx": object.True}))
+	assert.Nil(t, err)
+	assert.Equal(t, result, object.Object(object.False))
+
+	result, err = objtest.Call(t, conn, "get", object.NewString("greeting"))
+	assert.Nil(t, err)
+	assert.Equal(t, result, object.Object(object.NewString("hi")))
+
+	result, err = objtest.Call(t, conn, "incr", object.NewString("hits"))
+	assert.Nil(t, err)
+	assert.Equal(t, result, object.Object(object.NewInt(1)))
+	result, err = objtest.Call(t, conn, "incr", object.NewString("hits"), object.NewInt(5))
+	assert.Nil(t, err)
+	assert.Equal(t, result, object.Object(object.NewInt(6)))
+
+	_, err = objtest.Call(t, conn, "incr", object.NewString("greeting"))
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), "redis.incr: ERR value is not an integer or out of range")
+
+	result, err = objtest.Call(t, conn, "expire", object.NewString("hits"), object.NewInt(30))
+	assert.Nil(t, err)
+	assert.Equal(t, result, object.Object(object.True))
+
+	result, err = objtest.Call(t, conn, "publish", object.NewString("events"), object.NewInt(7))
+	assert.Nil(t, err)
+	assert.Equal(t, result, object.Object(object.NewInt(2)))
+
+	assert.Equal(t, server.log(), []string{
+		"AUTH app secret",
+		"SELECT 2",
+		"GET greeting",
+		"SET greeting hi PX 60000 NX",
+		"SET greeting hey NX",
+		"GET greeting",
+		"INCR hits",
+		"INCRBY hits 5",
+		"INCR greeting",
+		"PEXPIRE hits 30000",
+		"PUBLISH events 7",
+	})
+}
+
+func TestClientUnusableAfterFailure(t *testing.T) {
+	client, server := net.Pipe()
+	defer server.Close()
+	c := NewClient(client)
+	defer c.Close()
+	go func() {
+		r := bufio.NewReader(server)
+		if _, err := readCommand(r); err != nil {
+			return
+		}
+		// Reply with an error, then with part of a bulk string
+		_, _ = io.WriteString(server, "-ERR wrong\r\n")
+		if _, err := readCommand(r); err != nil {
+			return
+		}
+		_, _ = io.WriteString(server, "$5\r\nab")
+	}()
+
+	// An error reply leaves the connection usable
+	_, err := c.Do("GET", "a")
+	assert.Equal(t, err, error(Error("ERR wrong")))
+
+	assert.Nil(t, c.SetDeadline(time.Now().Add(50*time.Millisecond)))
+	_, err = c.Do("GET", "b")
+	assert.True(t, errors.Is(err, os.ErrDeadlineExceeded))
+
+	// The rest of the reply could arrive later, so the connection is done
+	_, err = c.Do("GET", "c")
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "connection is unusable after an earlier error")
+}
+
+func TestConnectErrors(t *testing.T) {
+	ctx := context.Background()
+	redisURL := object.NewString("redis://:secret@cache.example.com")
+
+	_, err := Policy{}.Connect(ctx, redisURL)
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), "redis.connect: network access is not allowed")
+
+	policy := Policy{
+		Dialer: &net.Dialer{},
+		Allow:  func(u *url.URL) bool { return u.Hostname() == "localhost" },
+	}
+	_, err = policy.Connect(ctx, redisURL)
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), "redis.connect: connecting to redis://:xxxxx@cache.example.com is not allowed")
+
+	_, err = policy.Connect(ctx, object.NewString("http://localhost"))
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), "value error: redis.connect: invalid Redis URL")
+
+	_, err = policy.Connect(ctx, object.NewString("redis://localhost/cache"))
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), `value error: redis.connect: invalid database "cache"`)
+
+	_, err = policy.Connect(ctx, object.NewString("redis://localhost"),
+		object.NewMap(map[string]object.Object{"retries": object.NewInt(3)}))
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), `value error: redis.connect: unknown option "retries"`)
+}
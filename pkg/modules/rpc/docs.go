@@ -0,0 +1,17 @@
+package rpc
+
+import "github.com/deepnoodle-ai/risor/v2/pkg/object"
+
+// Docs returns documentation for the rpc module.
+func Docs() []object.FuncSpec {
+	return rpcDocs
+}
+
+// ModuleDoc returns the module-level documentation.
+func ModuleDoc() string {
+	return "Remote procedure calls through a caller provided by the host"
+}
+
+var rpcDocs = []object.FuncSpec{
+	{Name: "call", Doc: "Call a remote method with a request map", Args: []string{"service", "method", "payload?"}, Returns: "map"},
+}
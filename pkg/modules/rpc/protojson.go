@@ -0,0 +1,162 @@
+package rpc
+
+import (
+	"bytes"
+	"encoding/base64"
+	"encoding/json"
+	"fmt"
+	"math"
+	"time"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+)
+
+// MarshalProtoJSON converts a value to JSON following the protobuf JSON
+// mapping, so protojson can read it into a message:
+//
+//   - bytes are base64 strings, as for bytes fields
+//   - times are RFC 3339 strings in UTC, as for google.protobuf.Timestamp
+//   - NaN and infinite floats are "NaN", "Infinity", and "-Infinity"
+//   - decimals are strings, which protojson reads into numeric fields
+//
+// Null, bools, ints, strings, lists, and maps are written as usual. Other
+// values, such as functions, and containers that hold themselves are an
+// error.
+func MarshalProtoJSON(obj object.Object) ([]byte, error) {
+	e := &protoJSONEncoder{active: map[object.Object]bool{}}
+	v, err := e.value(obj)
+	if err != nil {
+		return nil, err
+	}
+	return json.Marshal(v)
+}
+
+type protoJSONEncoder struct {
+	active map[object.Object]bool
+}
+
+func (e *protoJSONEncoder) value(obj object.Object) (any, error) {
+	switch obj := obj.(type) {
+	case *object.NilType:
+		return nil, nil
+	case *object.Bool:
+		return obj.Value(), nil
+	case *object.Int:
+		return obj.Value(), nil
+	case *object.Byte:
+		return int64(obj.Value()), nil
+	case *object.Float:
+		f := obj.Value()
+		switch {
+		case math.IsNaN(f):
+			return "NaN", nil
+		case math.IsInf(f, 1):
+			return "Infinity", nil
+		case math.IsInf(f, -1):
+			return "-Infinity", nil
+		}
+		return f, nil
+	case *object.Decimal:
+		return obj.String(), nil
+	case *object.String:
+		return obj.Value(), nil
+	case *object.Bytes:
+		return base64.StdEncoding.EncodeToString(obj.Value()), nil
+	case *object.Time:
+		return obj.Value().UTC().Format(time.RFC3339Nano), nil
+	case *object.List:
+		return e.array(obj, obj.Value())
+	case *object.Tuple:
+		return e.array(obj, obj.Value())
+	case *object.Map:
+		if err := e.enter(obj); err != nil {
+			return nil, err
+		}
+		defer delete(e.active, obj)
+		result := make(map[string]any, obj.Size())
+		for key, item := range obj.Value() {
+			v, err := e.value(item)
+			if err != nil {
+				return nil, fmt.Errorf("field %q: %w", key, err)
+			}
+			result[key] = v
+		}
+		return result, nil
+	default:
+		return nil, fmt.Errorf("cannot convert a %s to protobuf JSON", obj.Type())
+	}
+}
+
+func (e *protoJSONEncoder) array(obj object.Object, items []object.Object) (any, error) {
+	if err := e.enter(obj); err != nil {
+		return nil, err
+	}
+	defer delete(e.active, obj)
+	result := make([]any, len(items))
+	for i, item := range items {
+		v, err := e.value(item)
+		if err != nil {
+			return nil, err
+		}
+		result[i] = v
+	}
+	return result, nil
+}
+
+// enter marks a container as being encoded, failing if it already is.
+func (e *protoJSONEncoder) enter(obj object.Object) error {
+	if e.active[obj] {
+		return fmt.Errorf("cannot convert a %s that contains itself to protobuf JSON", obj.Type())
+	}
+	e.active[obj] = true
+	return nil
+}
+
+// UnmarshalProtoJSON converts JSON written by protojson to a Risor value.
+// Integral numbers become ints and other numbers floats. The mapping writes
+// 64-bit integers, bytes, and timestamps as strings, and without the
+// message's schema they stay strings; scripts convert them with int(),
+// decode(s, "base64"), and time.parse() as needed.
+func UnmarshalProtoJSON(data []byte) (object.Object, error) {
+	decoder := json.NewDecoder(bytes.NewReader(data))
+	decoder.UseNumber()
+	var v any
+	if err := decoder.Decode(&v); err != nil {
+		return nil, err
+	}
+	if decoder.More() {
+		return nil, fmt.Errorf("unexpected data after JSON value")
+	}
+	return fromJSONValue(v), nil
+}
+
+func fromJSONValue(v any) object.Object {
+	switch v := v.(type) {
+	case nil:
+		return object.Nil
+	case bool:
+		return object.NewBool(v)
+	case json.Number:
+		if i, err := v.Int64(); err == nil {
+			return object.NewInt(i)
+		}
+		f, _ := v.Float64()
+		return object.NewFloat(f)
+	case string:
+		return object.NewString(v)
+	case []any:
+		items := make([]object.Object, len(v))
+		for i, item := range v {
+			items[i] = fromJSONValue(item)
+		}
+		return object.NewList(items)
+	case map[string]any:
+		items := make(map[string]object.Object, len(v))
+		for key, item := range v {
+			items[key] = fromJSONValue(item)
+		}
+		return object.NewMap(items)
+	default:
+		return object.Nil
+	}
+}
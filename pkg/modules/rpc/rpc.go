@@ -0,0 +1,82 @@
+// Package rpc lets scripts call remote methods through a Caller that the
+// host provides, such as a gRPC client that builds messages dynamically.
+//
+// The Caller decides which services exist and how requests reach them, so
+// there is no useful default and risor.Builtins() omits the module. Pass the
+// host's Caller to Module:
+//
+//	env := risor.Builtins()
+//	env["rpc"] = modrpc.Module(caller)
+package rpc
+
+import (
+	"context"
+	"fmt"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+)
+
+// Caller invokes a remote method for scripts. The request and response are
+// JSON in the protobuf JSON mapping, which protojson can read into and write
+// from a dynamicpb message, so a gRPC host can implement Caller from
+// descriptors it loads at runtime without generated code.
+type Caller interface {
+	Call(ctx context.Context, service, method string, request []byte) ([]byte, error)
+}
+
+// CallerFunc adapts a function to a Caller.
+type CallerFunc func(ctx context.Context, service, method string, request []byte) ([]byte, error)
+
+// Call calls f.
+func (f CallerFunc) Call(ctx context.Context, service, method string, request []byte) ([]byte, error) {
+	return f(ctx, service, method, request)
+}
+
+// Module returns the rpc module, which makes calls with caller.
+func Module(caller Caller) *object.Module {
+	call := func(ctx context.Context, args ...object.Object) (object.Object, error) {
+		return Call(ctx, caller, args...)
+	}
+	return object.NewBuiltinsModule("rpc", map[string]object.Object{
+		"call": object.NewBuiltin("call", call),
+	})
+}
+
+// Call implements rpc.call(service, method, payload?). The payload map, which
+// defaults to empty, is sent as the request, and the response is returned as
+// a map.
+func Call(ctx context.Context, caller Caller, args ...object.Object) (object.Object, error) {
+	if len(args) < 2 || len(args) > 3 {
+		return nil, fmt.Errorf("rpc.call: expected 2-3 arguments, got %d", len(args))
+	}
+	service, err := object.AsString(args[0])
+	if err != nil {
+		return nil, err
+	}
+	method, err := object.AsString(args[1])
+	if err != nil {
+		return nil, err
+	}
+	payload := object.NewMap(map[string]object.Object{})
+	if len(args) == 3 {
+		if payload, err = object.AsMap(args[2]); err != nil {
+			return nil, err
+		}
+	}
+	if caller == nil {
+		return nil, fmt.Errorf("rpc.call: remote calls are not allowed")
+	}
+	request, err := MarshalProtoJSON(payload)
+	if err != nil {
+		return nil, fmt.Errorf("rpc.call: %w", err)
+	}
+	response, err := caller.Call(ctx, service, method, request)
+	if err != nil {
+		return nil, fmt.Errorf("rpc.call %s/%s: %w", service, method, err)
+	}
+	result, err := UnmarshalProtoJSON(response)
+	if err != nil {
+		return nil, fmt.Errorf("rpc.call %s/%s: invalid response: %w", service, method, err)
+	}
+	return result, nil
+}
@@ -0,0 +1,94 @@
+package rpc
+
+import (
+	"context"
+	"errors"
+	"math"
+	"testing"
+	"time"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+func TestCall(t *testing.T) {
+	var gotService, gotMethod, gotRequest string
+	caller := CallerFunc(func(ctx context.Context, service, method string, request []byte) ([]byte, error) {
+		gotService, gotMethod, gotRequest = service, method, string(request)
+		return []byte(`{"name": "Ada", "age": 36, "score": 9.5, "tags": ["a"], "id": "123", "manager": null}`), nil
+	})
+	payload := object.NewMap(map[string]object.Object{"id": object.NewString("u-42")})
+	result, err := Call(context.Background(), caller,
+		object.NewString("users.v1.UserService"), object.NewString("GetUser"), payload)
+	assert.Nil(t, err)
+	assert.Equal(t, gotService, "users.v1.UserService")
+	assert.Equal(t, gotMethod, "GetUser")
+	assert.Equal(t, gotRequest, `{"id":"u-42"}`)
+	assert.Equal(t, result.Inspect(),
+		`{"age": 36, "id": "123", "manager": null, "name": "Ada", "score": 9.5, "tags": ["a"]}`)
+
+	_, err = Call(context.Background(), caller, object.NewString("svc"), object.NewString("Ping"))
+	assert.Nil(t, err)
+	assert.Equal(t, gotRequest, `{}`)
+}
+
+func TestCallErrors(t *testing.T) {
+	ctx := context.Background()
+	svc, method := object.NewString("svc"), object.NewString("Ping")
+
+	_, err := Call(ctx, nil, svc, method)
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), "rpc.call: remote calls are not allowed")
+
+	_, err = Call(ctx, nil, svc)
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), "rpc.call: expected 2-3 arguments, got 1")
+
+	failing := CallerFunc(func(ctx context.Context, service, method string, request []byte) ([]byte, error) {
+		return nil, errors.New("unavailable")
+	})
+	_, err = Call(ctx, failing, svc, method)
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), "rpc.call svc/Ping: unavailable")
+
+	invalid := CallerFunc(func(ctx context.Context, service, method string, request []byte) ([]byte, error) {
+		return []byte(`{"a": 1} {}`), nil
+	})
+	_, err = Call(ctx, invalid, svc, method)
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), "rpc.call svc/Ping: invalid response: unexpected data after JSON value")
+
+	payload := object.NewMap(map[string]object.Object{"f": object.NewBuiltin("f", nil)})
+	_, err = Call(ctx, failing, svc, method, payload)
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), `rpc.call: field "f": cannot convert a builtin to protobuf JSON`)
+}
+
+func TestMarshalProtoJSON(t *testing.T) {
+	value := object.NewMap(map[string]object.Object{
+		"data":  object.NewBytes([]byte("hi")),
+		"at":    object.NewTime(time.Date(2024, 1, 2, 3, 4, 5, 0, time.FixedZone("", 3600))),
+		"nan":   object.NewFloat(math.NaN()),
+		"inf":   object.NewFloat(math.Inf(-1)),
+		"items": object.NewList([]object.Object{object.NewInt(1), object.True, object.Nil}),
+	})
+	data, err := MarshalProtoJSON(value)
+	assert.Nil(t, err)
+	assert.Equal(t, string(data),
+		`{"at":"2024-01-02T02:04:05Z","data":"aGk=","inf":"-Infinity","items":[1,true,null],"nan":"NaN"}`)
+
+	list := object.NewList(nil)
+	list.Append(list)
+	_, err = MarshalProtoJSON(list)
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), "cannot convert a list that contains itself to protobuf JSON")
+}
+
+func TestUnmarshalProtoJSON(t *testing.T) {
+	result, err := UnmarshalProtoJSON([]byte(`[1, 1.5, 1e3, 9223372036854775807, true, "x"]`))
+	assert.Nil(t, err)
+	assert.Equal(t, result.Inspect(), `[1, 1.5, 1000, 9223372036854775807, true, "x"]`)
+
+	_, err = UnmarshalProtoJSON([]byte(`{`))
+	assert.NotNil(t, err)
+}
@@ -0,0 +1,17 @@
+package schema
+
+import "github.com/deepnoodle-ai/risor/v2/pkg/object"
+
+// Docs returns documentation for the schema module.
+func Docs() []object.FuncSpec {
+	return schemaDocs
+}
+
+// ModuleDoc returns the module-level documentation.
+func ModuleDoc() string {
+	return "Validate values against JSON Schema-style schemas"
+}
+
+var schemaDocs = []object.FuncSpec{
+	{Name: "validate", Doc: "Check a value against a schema and list the errors", Args: []string{"value", "schema"}, Returns: "list"},
+}
@@ -0,0 +1,356 @@
+// Package schema validates values against declarative schemas. Schemas are
+// maps that use a subset of JSON Schema: types, enums, numeric ranges, string
+// lengths and patterns, list items, and map properties. This lets scripts
+// check inbound payloads without writing the checks by hand.
+package schema
+
+import (
+	"context"
+	"fmt"
+	"math"
+	"regexp"
+	"strings"
+	"unicode/utf8"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+)
+
+// keywords lists the schema keys that are understood. Annotations such as
+// title and description are accepted and ignored.
+var keywords = map[string]bool{
+	"type":                 true,
+	"enum":                 true,
+	"minimum":              true,
+	"maximum":              true,
+	"exclusiveMinimum":     true,
+	"exclusiveMaximum":     true,
+	"minLength":            true,
+	"maxLength":            true,
+	"pattern":              true,
+	"minItems":             true,
+	"maxItems":             true,
+	"items":                true,
+	"properties":           true,
+	"required":             true,
+	"additionalProperties": true,
+	"title":                true,
+	"description":          true,
+	"default":              true,
+}
+
+// maxDepth limits how deeply validation descends into a value, so a schema
+// that refers to itself can't follow a value that contains itself forever.
+const maxDepth = 1000
+
+// typeNames maps the JSON Schema type names to Risor types. Any other name
+// matches values whose Risor type has that name. A float with an integral
+// value, as JSON decoding produces for every number, is also an integer.
+var typeNames = map[string][]object.Type{
+	"boolean": {object.BOOL},
+	"integer": {object.INT},
+	"number":  {object.INT, object.FLOAT},
+	"array":   {object.LIST},
+	"object":  {object.MAP},
+}
+
+// validator collects the errors found in a value. Patterns are compiled once
+// per validation.
+type validator struct {
+	errors   []string
+	patterns map[string]*regexp.Regexp
+	depth    int
+}
+
+func (v *validator) fail(path, format string, args ...any) {
+	v.errors = append(v.errors, path+": "+fmt.Sprintf(format, args...))
+}
+
+// validate checks value against schema and records any errors. The returned
+// error is for a malformed schema, not for an invalid value.
+func (v *validator) validate(value object.Object, schema *object.Map, path string) error {
+	if v.depth >= maxDepth {
+		return fmt.Errorf("schema.validate: value is nested more than %d levels deep", maxDepth)
+	}
+	v.depth++
+	defer func() { v.depth-- }()
+	for _, key := range schema.SortedKeys() {
+		if !keywords[key] {
+			return fmt.Errorf("schema.validate: unknown schema keyword %q", key)
+		}
+	}
+	items := schema.Value()
+	if t, ok := items["type"]; ok {
+		matched, expected, err := matchType(value, t)
+		if err != nil {
+			return err
+		}
+		if !matched {
+			v.fail(path, "expected %s, got %s", expected, value.Type())
+			return nil
+		}
+	}
+	if e, ok := items["enum"]; ok {
+		options, err := object.AsList(e)
+		if err != nil {
+			return keywordError("enum", err)
+		}
+		if !containsValue(options, value) {
+			v.fail(path, "must be one of %s", options.Inspect())
+		}
+	}
+	switch value := value.(type) {
+	case *object.Int, *object.Float:
+		n, _ := object.AsFloat(value)
+		return v.validateNumber(n, items, path)
+	case *object.String:
+		return v.validateString(value.Value(), items, path)
+	case *object.List:
+		return v.validateList(value, items, path)
+	case *object.Map:
+		return v.validateMap(value, items, path)
+	}
+	return nil
+}
+
+func (v *validator) validateNumber(n float64, schema map[string]object.Object, path string) error {
+	checks := []struct {
+		key     string
+		fails   func(n, limit float64) bool
+		message string
+	}{
+		{"minimum", func(n, limit float64) bool { return n < limit }, "must be at least %s"},
+		{"maximum", func(n, limit float64) bool { return n > limit }, "must be at most %s"},
+		{"exclusiveMinimum", func(n, limit float64) bool { return n <= limit }, "must be greater than %s"},
+		{"exclusiveMaximum", func(n, limit float64) bool { return n >= limit }, "must be less than %s"},
+	}
+	for _, check := range checks {
+		obj, ok := schema[check.key]
+		if !ok {
+			continue
+		}
+		limit, err := object.AsFloat(obj)
+		if err != nil {
+			return keywordError(check.key, err)
+		}
+		if check.fails(n, limit) {
+			v.fail(path, check.message, obj.Inspect())
+		}
+	}
+	return nil
+}
+
+func (v *validator) validateString(s string, schema map[string]object.Object, path string) error {
+	length := int64(utf8.RuneCountInString(s))
+	if err := v.checkLength(length, schema, "minLength", "maxLength", "character", path); err != nil {
+		return err
+	}
+	if obj, ok := schema["pattern"]; ok {
+		pattern, err := object.AsString(obj)
+		if err != nil {
+			return keywordError("pattern", err)
+		}
+		re, ok := v.patterns[pattern]
+		if !ok {
+			re, err = regexp.Compile(pattern)
+			if err != nil {
+				return fmt.Errorf("schema.validate: invalid pattern %q: %w", pattern, err)
+			}
+			v.patterns[pattern] = re
+		}
+		if !re.MatchString(s) {
+			v.fail(path, "must match pattern %q", pattern)
+		}
+	}
+	return nil
+}
+
+func (v *validator) validateList(list *object.List, schema map[string]object.Object, path string) error {
+	elems := list.Value()
+	if err := v.checkLength(int64(len(elems)), schema, "minItems", "maxItems", "item", path); err != nil {
+		return err
+	}
+	if obj, ok := schema["items"]; ok {
+		itemSchema, err := object.AsMap(obj)
+		if err != nil {
+			return keywordError("items", err)
+		}
+		for i, elem := range elems {
+			if err := v.validate(elem, itemSchema, fmt.Sprintf("%s[%d]", path, i)); err != nil {
+				return err
+			}
+		}
+	}
+	return nil
+}
+
+func (v *validator) validateMap(m *object.Map, schema map[string]object.Object, path string) error {
+	values := m.Value()
+	if obj, ok := schema["required"]; ok {
+		required, err := object.AsStringSlice(obj)
+		if err != nil {
+			return keywordError("required", err)
+		}
+		for _, key := range required {
+			if _, ok := values[key]; !ok {
+				v.fail(propertyPath(path, key), "is required")
+			}
+		}
+	}
+	properties := map[string]object.Object{}
+	if obj, ok := schema["properties"]; ok {
+		props, err := object.AsMap(obj)
+		if err != nil {
+			return keywordError("properties", err)
+		}
+		properties = props.Value()
+	}
+	var additional *object.Map
+	allowAdditional := true
+	if obj, ok := schema["additionalProperties"]; ok {
+		switch obj := obj.(type) {
+		case *object.Bool:
+			allowAdditional = obj.Value()
+		case *object.Map:
+			additional = obj
+		default:
+			return fmt.Errorf("schema.validate: %q must be a bool or a map (%s given)",
+				"additionalProperties", obj.Type())
+		}
+	}
+	for _, key := range m.SortedKeys() {
+		propSchema := additional
+		if obj, ok := properties[key]; ok {
+			s, err := object.AsMap(obj)
+			if err != nil {
+				return keywordError("properties", err)
+			}
+			propSchema = s
+		} else if !allowAdditional {
+			v.fail(propertyPath(path, key), "is not allowed")
+			continue
+		}
+		if propSchema != nil {
+			if err := v.validate(values[key], propSchema, propertyPath(path, key)); err != nil {
+				return err
+			}
+		}
+	}
+	return nil
+}
+
+// checkLength checks a length against the given minimum and maximum keywords.
+func (v *validator) checkLength(length int64, schema map[string]object.Object, minKey, maxKey, unit, path string) error {
+	if obj, ok := schema[minKey]; ok {
+		limit, err := object.AsInt(obj)
+		if err != nil {
+			return keywordError(minKey, err)
+		}
+		if length < limit {
+			v.fail(path, "must have at least %s", plural(limit, unit))
+		}
+	}
+	if obj, ok := schema[maxKey]; ok {
+		limit, err := object.AsInt(obj)
+		if err != nil {
+			return keywordError(maxKey, err)
+		}
+		if length > limit {
+			v.fail(path, "must have at most %s", plural(limit, unit))
+		}
+	}
+	return nil
+}
+
+func plural(n int64, unit string) string {
+	if n == 1 {
+		return "1 " + unit
+	}
+	return fmt.Sprintf("%d %ss", n, unit)
+}
+
+// matchType reports whether value has one of the types named by t, which is
+// a type name or a list of them. It also returns the names joined for use in
+// an error message.
+func matchType(value object.Object, t object.Object) (bool, string, error) {
+	var names []string
+	switch t := t.(type) {
+	case *object.String:
+		names = []string{t.Value()}
+	case *object.List:
+		var err error
+		if names, err = object.AsStringSlice(t); err != nil {
+			return false, "", keywordError("type", err)
+		}
+	default:
+		return false, "", fmt.Errorf("schema.validate: %q must be a string or a list (%s given)",
+			"type", t.Type())
+	}
+	for _, name := range names {
+		types, ok := typeNames[name]
+		if !ok {
+			types = []object.Type{object.Type(name)}
+		}
+		for _, typ := range types {
+			if value.Type() == typ {
+				return true, strings.Join(names, " or "), nil
+			}
+		}
+		if name == "integer" && isIntegral(value) {
+			return true, strings.Join(names, " or "), nil
+		}
+	}
+	return false, strings.Join(names, " or "), nil
+}
+
+// isIntegral reports whether value is a float with an integral value.
+func isIntegral(value object.Object) bool {
+	f, ok := value.(*object.Float)
+	return ok && !math.IsInf(f.Value(), 0) && f.Value() == math.Trunc(f.Value())
+}
+
+func containsValue(list *object.List, value object.Object) bool {
+	for _, item := range list.Value() {
+		if item.Equals(value) {
+			return true
+		}
+	}
+	return false
+}
+
+func keywordError(key string, err error) error {
+	return fmt.Errorf("schema.validate: invalid %q: %w", key, err)
+}
+
+// propertyPath returns the path of a map property. Keys that aren't simple
+// names are written in brackets.
+func propertyPath(path, key string) string {
+	if object.IsIdentifier(key) {
+		return path + "." + key
+	}
+	return fmt.Sprintf("%s[%q]", path, key)
+}
+
+// Validate checks a value against a schema map. It returns a list of error
+// messages, each prefixed with the path of the offending value, which is
+// empty if the value is valid. A malformed schema raises an error.
+func Validate(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) != 2 {
+		return nil, fmt.Errorf("schema.validate: expected 2 arguments, got %d", len(args))
+	}
+	schema, err := object.AsMap(args[1])
+	if err != nil {
+		return nil, err
+	}
+	v := &validator{patterns: map[string]*regexp.Regexp{}}
+	if err := v.validate(args[0], schema, "$"); err != nil {
+		return nil, err
+	}
+	return object.NewStringList(v.errors), nil
+}
+
+// Module returns the schema module.
+func Module() *object.Module {
+	return object.NewBuiltinsModule("schema", map[string]object.Object{
+		"validate": object.NewBuiltin("validate", Validate),
+	})
+}
@@ -0,0 +1,128 @@
+package schema
+
+import (
+	"context"
+	"encoding/json"
+	"testing"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+func validate(t *testing.T, value any, schema map[string]any) []string {
+	t.Helper()
+	result, err := Validate(context.Background(), object.FromGoType(value), object.FromGoType(schema))
+	assert.Nil(t, err)
+	errs, err := object.AsStringSlice(result)
+	assert.Nil(t, err)
+	return errs
+}
+
+func TestValidateTypes(t *testing.T) {
+	assert.Len(t, validate(t, "a", map[string]any{"type": "string"}), 0)
+	assert.Len(t, validate(t, int64(1), map[string]any{"type": "number"}), 0)
+	assert.Len(t, validate(t, 1.5, map[string]any{"type": "number"}), 0)
+	assert.Len(t, validate(t, nil, map[string]any{"type": []any{"string", "null"}}), 0)
+	assert.Equal(t, validate(t, 1.5, map[string]any{"type": "integer"}),
+		[]string{"$: expected integer, got float"})
+	assert.Equal(t, validate(t, true, map[string]any{"type": []any{"string", "null"}}),
+		[]string{"$: expected string or null, got bool"})
+}
+
+func TestValidateDecodedJSON(t *testing.T) {
+	// JSON numbers decode as floats, so integral floats are integers
+	var payload any
+	assert.Nil(t, json.Unmarshal([]byte(`{"id": 42, "qty": 2.5, "if": 1}`), &payload))
+	schema := map[string]any{
+		"properties": map[string]any{
+			"id":  map[string]any{"type": "integer"},
+			"qty": map[string]any{"type": "integer"},
+			"if":  map[string]any{"type": "string"},
+		},
+	}
+	assert.Equal(t, validate(t, payload, schema), []string{
+		`$["if"]: expected string, got float`,
+		"$.qty: expected integer, got float",
+	})
+}
+
+func TestValidateCyclic(t *testing.T) {
+	schema := object.NewMap(map[string]object.Object{"type": object.NewString("object")})
+	schema.Set("additionalProperties", schema)
+	value := object.NewMap(map[string]object.Object{})
+	value.Set("self", value)
+	_, err := Validate(context.Background(), value, schema)
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "nested more than 1000 levels deep")
+}
+
+func TestValidateValues(t *testing.T) {
+	assert.Equal(t, validate(t, int64(5), map[string]any{"minimum": int64(0), "maximum": int64(3)}),
+		[]string{"$: must be at most 3"})
+	assert.Equal(t, validate(t, 0.0, map[string]any{"exclusiveMinimum": int64(0)}),
+		[]string{"$: must be greater than 0"})
+	assert.Equal(t, validate(t, "héllo", map[string]any{"maxLength": int64(4)}),
+		[]string{"$: must have at most 4 characters"})
+	assert.Equal(t, validate(t, "abc", map[string]any{"pattern": `^\d+$`}),
+		[]string{`$: must match pattern "^\\d+$"`})
+	assert.Equal(t, validate(t, "c", map[string]any{"enum": []any{"a", "b"}}),
+		[]string{`$: must be one of ["a", "b"]`})
+	assert.Equal(t, validate(t, []any{}, map[string]any{"minItems": int64(1)}),
+		[]string{"$: must have at least 1 item"})
+}
+
+func TestValidateNested(t *testing.T) {
+	schema := map[string]any{
+		"type":     "object",
+		"required": []any{"id", "items"},
+		"properties": map[string]any{
+			"id": map[string]any{"type": "integer"},
+			"items": map[string]any{
+				"type":  "array",
+				"items": map[string]any{"type": "object", "required": []any{"sku"}},
+			},
+		},
+		"additionalProperties": false,
+	}
+	assert.Len(t, validate(t, map[string]any{
+		"id":    int64(1),
+		"items": []any{map[string]any{"sku": "A1"}},
+	}, schema), 0)
+	assert.Equal(t, validate(t, map[string]any{
+		"items":   []any{map[string]any{"sku": "A1"}, map[string]any{}},
+		"note":    "x",
+		"x-trace": "y",
+	}, schema), []string{
+		"$.id: is required",
+		"$.items[1].sku: is required",
+		"$.note: is not allowed",
+		`$["x-trace"]: is not allowed`,
+	})
+
+	// Extra keys can be checked against a schema
+	extra := map[string]any{"additionalProperties": map[string]any{"type": "int"}}
+	assert.Equal(t, validate(t, map[string]any{"a": int64(1), "b": "2"}, extra),
+		[]string{"$.b: expected int, got string"})
+}
+
+func TestValidateSchemaErrors(t *testing.T) {
+	ctx := context.Background()
+	tests := []struct {
+		schema map[string]any
+		err    string
+	}{
+		{map[string]any{"minimum": int64(1), "maxLenght": int64(2)}, `schema.validate: unknown schema keyword "maxLenght"`},
+		{map[string]any{"minimum": "1"}, `schema.validate: invalid "minimum": type error: expected a number (string given)`},
+		{map[string]any{"pattern": "("}, "schema.validate: invalid pattern \"(\": error parsing regexp: missing closing ): `(`"},
+		{map[string]any{"type": int64(1)}, `schema.validate: "type" must be a string or a list (int given)`},
+	}
+	for _, tt := range tests {
+		_, err := Validate(ctx, object.NewString("a"), object.FromGoType(tt.schema))
+		assert.NotNil(t, err)
+		assert.Equal(t, err.Error(), tt.err)
+	}
+
+	_, err := Validate(ctx, object.NewString("a"))
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), "schema.validate: expected 2 arguments, got 1")
+}
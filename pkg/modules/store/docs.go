@@ -0,0 +1,20 @@
+package store
+
+import "github.com/deepnoodle-ai/risor/v2/pkg/object"
+
+// Docs returns documentation for the store module.
+func Docs() []object.FuncSpec {
+	return storeDocs
+}
+
+// ModuleDoc returns the module-level documentation.
+func ModuleDoc() string {
+	return "Key-value state that persists across runs"
+}
+
+var storeDocs = []object.FuncSpec{
+	{Name: "delete", Doc: "Remove a key", Args: []string{"key"}, Returns: "null"},
+	{Name: "get", Doc: "Get the value of a key", Args: []string{"key", "default?"}, Returns: "any"},
+	{Name: "list", Doc: "List keys, optionally with a prefix", Args: []string{"prefix?"}, Returns: "list"},
+	{Name: "set", Doc: "Set the value of a key", Args: []string{"key", "value"}, Returns: "null"},
+}
@@ -0,0 +1,245 @@
+// Package store gives scripts a key-value store for small amounts of state
+// that outlive a single run, such as the last item an automation processed.
+//
+// Where state lives and how long it lasts is up to the host, which passes
+// its Store to Module:
+//
+//	env := risor.Builtins()
+//	env["store"] = modstore.Module(states)
+//
+// where states is a Store kept by the host between runs, such as one from
+// NewMemoryStore.
+package store
+
+import (
+	"bytes"
+	"context"
+	"encoding/json"
+	"fmt"
+	"sort"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+)
+
+// Store holds the values that scripts save. Values are JSON documents, so a
+// Store can keep them in a file, a database, or a cache as they are.
+// Implementations must be safe for concurrent use.
+type Store interface {
+	// Get returns the value for key, and false if there isn't one.
+	Get(ctx context.Context, key string) ([]byte, bool, error)
+
+	// Set sets the value for key.
+	Set(ctx context.Context, key string, value []byte) error
+
+	// Delete removes key. Deleting a missing key is not an error.
+	Delete(ctx context.Context, key string) error
+
+	// List returns the keys that start with prefix, in any order.
+	List(ctx context.Context, prefix string) ([]string, error)
+}
+
+// Module returns the store module, which keeps values in s.
+func Module(s Store) *object.Module {
+	m := &module{store: s}
+	return object.NewBuiltinsModule("store", map[string]object.Object{
+		"delete": object.NewBuiltin("delete", m.Delete),
+		"get":    object.NewBuiltin("get", m.Get),
+		"list":   object.NewBuiltin("list", m.List),
+		"set":    object.NewBuiltin("set", m.Set),
+	})
+}
+
+type module struct {
+	store Store
+}
+
+func (m *module) check(name string) error {
+	if m.store == nil {
+		return fmt.Errorf("store.%s: no store is configured", name)
+	}
+	return nil
+}
+
+// Get implements store.get(key, default?), returning default, or null, if
+// the key has no value.
+func (m *module) Get(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) < 1 || len(args) > 2 {
+		return nil, fmt.Errorf("store.get: expected 1-2 arguments, got %d", len(args))
+	}
+	key, err := object.AsString(args[0])
+	if err != nil {
+		return nil, err
+	}
+	if err := m.check("get"); err != nil {
+		return nil, err
+	}
+	data, ok, err := m.store.Get(ctx, key)
+	if err != nil {
+		return nil, fmt.Errorf("store.get: %w", err)
+	}
+	if !ok {
+		if len(args) == 2 {
+			return args[1], nil
+		}
+		return object.Nil, nil
+	}
+	value, err := decode(data)
+	if err != nil {
+		return nil, fmt.Errorf("store.get: invalid value for %q: %w", key, err)
+	}
+	return value, nil
+}
+
+// Set implements store.set(key, value). The value is saved as JSON, so it
+// must be null, a bool, number, string, or a list or map of them. Bytes and
+// times are saved as strings.
+func (m *module) Set(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) != 2 {
+		return nil, fmt.Errorf("store.set: expected 2 arguments, got %d", len(args))
+	}
+	key, err := object.AsString(args[0])
+	if err != nil {
+		return nil, err
+	}
+	if err := m.check("set"); err != nil {
+		return nil, err
+	}
+	data, err := encode(args[1])
+	if err != nil {
+		return nil, object.TypeErrorf("store.set: cannot store a %s", args[1].Type())
+	}
+	if err := m.store.Set(ctx, key, data); err != nil {
+		return nil, fmt.Errorf("store.set: %w", err)
+	}
+	return object.Nil, nil
+}
+
+// Delete implements store.delete(key).
+func (m *module) Delete(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) != 1 {
+		return nil, fmt.Errorf("store.delete: expected 1 argument, got %d", len(args))
+	}
+	key, err := object.AsString(args[0])
+	if err != nil {
+		return nil, err
+	}
+	if err := m.check("delete"); err != nil {
+		return nil, err
+	}
+	if err := m.store.Delete(ctx, key); err != nil {
+		return nil, fmt.Errorf("store.delete: %w", err)
+	}
+	return object.Nil, nil
+}
+
+// List implements store.list(prefix?), returning the keys that start with
+// prefix in sorted order.
+func (m *module) List(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) > 1 {
+		return nil, fmt.Errorf("store.list: expected 0-1 arguments, got %d", len(args))
+	}
+	var prefix string
+	if len(args) == 1 {
+		var err error
+		if prefix, err = object.AsString(args[0]); err != nil {
+			return nil, err
+		}
+	}
+	if err := m.check("list"); err != nil {
+		return nil, err
+	}
+	keys, err := m.store.List(ctx, prefix)
+	if err != nil {
+		return nil, fmt.Errorf("store.list: %w", err)
+	}
+	sort.Strings(keys)
+	return object.NewStringList(keys), nil
+}
+
+// encode converts a value to JSON for saving. Floats are always written with
+// a fraction or exponent, so decode can tell 2.0 from 2.
+func encode(obj object.Object) ([]byte, error) {
+	v, err := toJSON(obj)
+	if err != nil {
+		return nil, err
+	}
+	return json.Marshal(v)
+}
+
+func toJSON(obj object.Object) (any, error) {
+	switch obj := obj.(type) {
+	case *object.Float:
+		data, err := json.Marshal(obj.Value())
+		if err != nil {
+			return nil, err
+		}
+		if !bytes.ContainsAny(data, ".eE") {
+			data = append(data, ".0"...)
+		}
+		return json.Number(data), nil
+	case *object.List:
+		items := make([]any, len(obj.Value()))
+		for i, item := range obj.Value() {
+			v, err := toJSON(item)
+			if err != nil {
+				return nil, err
+			}
+			items[i] = v
+		}
+		return items, nil
+	case *object.Map:
+		items := make(map[string]any, obj.Size())
+		for key, item := range obj.Value() {
+			v, err := toJSON(item)
+			if err != nil {
+				return nil, err
+			}
+			items[key] = v
+		}
+		return items, nil
+	default:
+		return obj, nil
+	}
+}
+
+// decode converts a saved JSON value to a Risor value. Numbers without a
+// fraction or exponent are read as ints, and others as floats, so both
+// round-trip.
+func decode(data []byte) (object.Object, error) {
+	decoder := json.NewDecoder(bytes.NewReader(data))
+	decoder.UseNumber()
+	var v any
+	if err := decoder.Decode(&v); err != nil {
+		return nil, err
+	}
+	return fromJSON(v), nil
+}
+
+func fromJSON(v any) object.Object {
+	switch v := v.(type) {
+	case bool:
+		return object.NewBool(v)
+	case json.Number:
+		if i, err := v.Int64(); err == nil {
+			return object.NewInt(i)
+		}
+		f, _ := v.Float64()
+		return object.NewFloat(f)
+	case string:
+		return object.NewString(v)
+	case []any:
+		items := make([]object.Object, len(v))
+		for i, item := range v {
+			items[i] = fromJSON(item)
+		}
+		return object.NewList(items)
+	case map[string]any:
+		items := make(map[string]object.Object, len(v))
+		for key, item := range v {
+			items[key] = fromJSON(item)
+		}
+		return object.NewMap(items)
+	default:
+		return object.Nil
+	}
+}
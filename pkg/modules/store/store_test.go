@@ -0,0 +1,107 @@
+package store
+
+import (
+	"context"
+	"errors"
+	"testing"
+
+	"github.com/deepnoodle-ai/risor/v2/internal/objtest"
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+func TestStore(t *testing.T) {
+	states := NewMemoryStore()
+	m := Module(states)
+
+	result, err := objtest.Call(t, m, "get", object.NewString("cursor"))
+	assert.Nil(t, err)
+	assert.Equal(t, result, object.Object(object.Nil))
+
+	result, err = objtest.Call(t, m, "get", object.NewString("cursor"), object.NewInt(0))
+	assert.Nil(t, err)
+	assert.Equal(t, result, object.Object(object.NewInt(0)))
+
+	cursor := object.NewMap(map[string]object.Object{
+		"page":  object.NewInt(3),
+		"ratio": object.NewFloat(0.5),
+		"ids":   object.NewList([]object.Object{object.NewString("a"), object.Nil}),
+	})
+	_, err = objtest.Call(t, m, "set", object.NewString("feed:news"), cursor)
+	assert.Nil(t, err)
+	_, err = objtest.Call(t, m, "set", object.NewString("feed:weather"), object.True)
+	assert.Nil(t, err)
+	_, err = objtest.Call(t, m, "set", object.NewString("other"), object.NewInt(1))
+	assert.Nil(t, err)
+
+	// A second module on the same store sees the values, as a later run would
+	m = Module(states)
+	result, err = objtest.Call(t, m, "get", object.NewString("feed:news"))
+	assert.Nil(t, err)
+	assert.Equal(t, result.Inspect(), `{"ids": ["a", null], "page": 3, "ratio": 0.5}`)
+
+	result, err = objtest.Call(t, m, "list", object.NewString("feed:"))
+	assert.Nil(t, err)
+	assert.Equal(t, result.Inspect(), `["feed:news", "feed:weather"]`)
+
+	_, err = objtest.Call(t, m, "delete", object.NewString("feed:news"))
+	assert.Nil(t, err)
+	_, err = objtest.Call(t, m, "delete", object.NewString("missing"))
+	assert.Nil(t, err)
+	result, err = objtest.Call(t, m, "list")
+	assert.Nil(t, err)
+	assert.Equal(t, result.Inspect(), `["feed:weather", "other"]`)
+}
+
+func TestStoreNumbers(t *testing.T) {
+	m := Module(NewMemoryStore())
+	value := object.NewList([]object.Object{
+		object.NewInt(2),
+		object.NewFloat(2),
+		object.NewFloat(1e21),
+		object.NewMap(map[string]object.Object{"x": object.NewFloat(-3)}),
+	})
+	_, err := objtest.Call(t, m, "set", object.NewString("numbers"), value)
+	assert.Nil(t, err)
+	result, err := objtest.Call(t, m, "get", object.NewString("numbers"))
+	assert.Nil(t, err)
+	items := result.(*object.List).Value()
+	assert.Len(t, items, 4)
+	assert.Equal(t, items[0], object.Object(object.NewInt(2)))
+	assert.Equal(t, items[1], object.Object(object.NewFloat(2)))
+	assert.Equal(t, items[2], object.Object(object.NewFloat(1e21)))
+	assert.Equal(t, items[3].(*object.Map).Get("x"), object.Object(object.NewFloat(-3)))
+}
+
+type failingStore struct {
+	MemoryStore
+}
+
+func (*failingStore) Set(ctx context.Context, key string, value []byte) error {
+	return errors.New("disk full")
+}
+
+func TestStoreErrors(t *testing.T) {
+	_, err := objtest.Call(t, Module(nil), "get", object.NewString("x"))
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), "store.get: no store is configured")
+
+	m := Module(&failingStore{})
+	_, err = objtest.Call(t, m, "set", object.NewString("x"), object.NewInt(1))
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), "store.set: disk full")
+
+	_, err = objtest.Call(t, m, "set", object.NewString("x"), object.NewBuiltin("f", nil))
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), "type error: store.set: cannot store a builtin")
+
+	_, err = objtest.Call(t, m, "set", object.NewString("x"))
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), "store.set: expected 2 arguments, got 1")
+
+	states := NewMemoryStore()
+	assert.Nil(t, states.Set(context.Background(), "bad", []byte("{")))
+	_, err = objtest.Call(t, Module(states), "get", object.NewString("bad"))
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), `store.get: invalid value for "bad": unexpected EOF`)
+}
@@ -0,0 +1,19 @@
+package time
+
+import "github.com/deepnoodle-ai/risor/v2/pkg/object"
+
+// Docs returns documentation for the time module.
+func Docs() []object.FuncSpec {
+	return timeDocs
+}
+
+// ModuleDoc returns the module-level documentation.
+func ModuleDoc() string {
+	return "Create times from the clock, strings, and Unix timestamps"
+}
+
+var timeDocs = []object.FuncSpec{
+	{Name: "now", Doc: "Current time", Args: []string{"tz?"}, Returns: "time"},
+	{Name: "parse", Doc: "Parse a time, as ISO-8601 by default", Args: []string{"value", "layout?", "tz?"}, Returns: "time"},
+	{Name: "unix", Doc: "Time from a Unix timestamp in seconds", Args: []string{"seconds", "tz?"}, Returns: "time"},
+}
@@ -0,0 +1,108 @@
+// Package time creates time values for scripts: the current time, times
+// parsed from strings, and times from Unix timestamps. Methods on the time
+// values themselves handle arithmetic, truncation, timezones, and formatting.
+//
+// Timezones are loaded from the system's timezone database. Build with the
+// risor_tzdata tag to embed a copy in the binary for hosts without one.
+package time
+
+import (
+	"context"
+	"fmt"
+	"math"
+	"time"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+)
+
+// isoLayouts are the ISO-8601 forms accepted by parse when no layout is
+// given. Fractional seconds are accepted by all layouts with seconds.
+var isoLayouts = []string{
+	time.RFC3339,
+	"2006-01-02T15:04:05",
+	"2006-01-02T15:04",
+	"2006-01-02",
+}
+
+// location returns the timezone given by args[i], or UTC if there isn't one.
+func location(args []object.Object, i int) (*time.Location, error) {
+	if len(args) <= i || args[i] == object.Nil {
+		return time.UTC, nil
+	}
+	return object.AsLocation(args[i])
+}
+
+// Now returns the current time, in UTC or the given timezone.
+func Now(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) > 1 {
+		return nil, fmt.Errorf("time.now: expected 0-1 arguments, got %d", len(args))
+	}
+	loc, err := location(args, 0)
+	if err != nil {
+		return nil, err
+	}
+	return object.NewTime(time.Now().In(loc)), nil
+}
+
+// Parse parses a time from a string. Without a layout, or with a null one,
+// the string is read as ISO-8601. Times without a UTC offset are read in the
+// given timezone, which defaults to UTC.
+func Parse(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) < 1 || len(args) > 3 {
+		return nil, fmt.Errorf("time.parse: expected 1-3 arguments, got %d", len(args))
+	}
+	value, err := object.AsString(args[0])
+	if err != nil {
+		return nil, err
+	}
+	layouts := isoLayouts
+	if len(args) > 1 && args[1] != object.Nil {
+		layout, err := object.AsString(args[1])
+		if err != nil {
+			return nil, err
+		}
+		layouts = []string{layout}
+	}
+	loc, err := location(args, 2)
+	if err != nil {
+		return nil, err
+	}
+	for _, layout := range layouts {
+		if t, err := time.ParseInLocation(layout, value, loc); err == nil {
+			return object.NewTime(t), nil
+		}
+	}
+	return nil, fmt.Errorf("time.parse: cannot parse %q as a time", value)
+}
+
+// Unix returns the time for a Unix timestamp in seconds, which may be
+// fractional, in UTC or the given timezone.
+func Unix(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) < 1 || len(args) > 2 {
+		return nil, fmt.Errorf("time.unix: expected 1-2 arguments, got %d", len(args))
+	}
+	var t time.Time
+	switch arg := args[0].(type) {
+	case *object.Int:
+		t = time.Unix(arg.Value(), 0)
+	case *object.Float:
+		sec, frac := math.Modf(arg.Value())
+		t = time.Unix(int64(sec), int64(frac*1e9))
+	default:
+		return nil, object.TypeErrorf("time.unix: expected number, got %s", args[0].Type())
+	}
+	loc, err := location(args, 1)
+	if err != nil {
+		return nil, err
+	}
+	return object.NewTime(t.In(loc)), nil
+}
+
+// Module returns the time module.
+func Module() *object.Module {
+	return object.NewBuiltinsModule("time", map[string]object.Object{
+		"now":   object.NewBuiltin("now", Now),
+		"parse": object.NewBuiltin("parse", Parse),
+		"unix":  object.NewBuiltin("unix", Unix),
+	})
+}
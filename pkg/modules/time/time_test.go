@@ -0,0 +1,80 @@
+package time
+
+import (
+	"context"
+	"testing"
+	"time"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+func TestParse(t *testing.T) {
+	ctx := context.Background()
+	newYork, err := time.LoadLocation("America/New_York")
+	assert.Nil(t, err)
+	tests := []struct {
+		args []object.Object
+		want time.Time
+	}{
+		{
+			[]object.Object{object.NewString("2024-03-10T12:30:00Z")},
+			time.Date(2024, 3, 10, 12, 30, 0, 0, time.UTC),
+		},
+		{
+			[]object.Object{object.NewString("2024-03-10T12:30:00.25+02:00")},
+			time.Date(2024, 3, 10, 10, 30, 0, 250000000, time.UTC),
+		},
+		{
+			[]object.Object{object.NewString("2024-03-10")},
+			time.Date(2024, 3, 10, 0, 0, 0, 0, time.UTC),
+		},
+		{
+			[]object.Object{object.NewString("2024-03-10T08:00"), object.Nil, object.NewString("America/New_York")},
+			time.Date(2024, 3, 10, 8, 0, 0, 0, newYork),
+		},
+		{
+			[]object.Object{object.NewString("10/03/2024"), object.NewString("02/01/2006")},
+			time.Date(2024, 3, 10, 0, 0, 0, 0, time.UTC),
+		},
+	}
+	for _, tt := range tests {
+		result, err := Parse(ctx, tt.args...)
+		assert.Nil(t, err)
+		got, err := object.AsTime(result)
+		assert.Nil(t, err)
+		assert.True(t, got.Equal(tt.want), "got %s, want %s", got, tt.want)
+	}
+
+	_, err = Parse(ctx, object.NewString("yesterday"))
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), `time.parse: cannot parse "yesterday" as a time`)
+
+	_, err = Parse(ctx, object.NewString("2024-03-10"), object.Nil, object.NewString("Mars/Olympus"))
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), `value error: unknown time zone "Mars/Olympus"`)
+}
+
+func TestUnix(t *testing.T) {
+	ctx := context.Background()
+	result, err := Unix(ctx, object.NewInt(1700000000))
+	assert.Nil(t, err)
+	assert.Equal(t, result.(*object.Time).Value(), time.Date(2023, 11, 14, 22, 13, 20, 0, time.UTC))
+
+	result, err = Unix(ctx, object.NewFloat(1.5), object.NewString("Asia/Tokyo"))
+	assert.Nil(t, err)
+	got := result.(*object.Time).Value()
+	assert.Equal(t, got.UnixMilli(), int64(1500))
+	assert.Equal(t, got.Location().String(), "Asia/Tokyo")
+
+	_, err = Unix(ctx, object.NewString("1"))
+	assert.NotNil(t, err)
+}
+
+func TestNow(t *testing.T) {
+	result, err := Now(context.Background(), object.NewString("Europe/Paris"))
+	assert.Nil(t, err)
+	got := result.(*object.Time).Value()
+	assert.Equal(t, got.Location().String(), "Europe/Paris")
+	assert.True(t, time.Since(got) < time.Minute)
+}
@@ -0,0 +1,21 @@
+package unicode
+
+import "github.com/deepnoodle-ai/risor/v2/pkg/object"
+
+// Docs returns documentation for the unicode module.
+func Docs() []object.FuncSpec {
+	return unicodeDocs
+}
+
+// ModuleDoc returns the module-level documentation.
+func ModuleDoc() string {
+	return "Unicode case folding and normalization"
+}
+
+var unicodeDocs = []object.FuncSpec{
+	{Name: "casefold", Doc: "Apply full Unicode case folding", Args: []string{"s"}, Returns: "string"},
+	{Name: "eq_ignore_case", Doc: "Compare strings ignoring case and normalization", Args: []string{"a", "b"}, Returns: "bool"},
+	{Name: "normalize", Doc: "Convert to a normalization form (default NFC)", Args: []string{"s", "form?"}, Returns: "string"},
+	{Name: "is_normalized", Doc: "Check if string is in a normalization form", Args: []string{"s", "form?"}, Returns: "bool"},
+	{Name: "compare", Doc: "Compare strings after NFC normalization", Args: []string{"a", "b"}, Returns: "int"},
+}
@@ -0,0 +1,144 @@
+// Package unicode provides Unicode-aware case folding and normalization.
+//
+// It depends on golang.org/x/text, so it lives in its own Go module to keep
+// that dependency out of programs that don't need it. Programs that do
+// import this package and register the module:
+//
+//	env := risor.Builtins()
+//	env["unicode"] = unicode.Module()
+package unicode
+
+import (
+	"context"
+	"fmt"
+	"strings"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+	"golang.org/x/text/cases"
+	"golang.org/x/text/unicode/norm"
+)
+
+// parseForm returns the normalization form with the given name.
+func parseForm(name string) (norm.Form, error) {
+	switch strings.ToUpper(name) {
+	case "NFC":
+		return norm.NFC, nil
+	case "NFD":
+		return norm.NFD, nil
+	case "NFKC":
+		return norm.NFKC, nil
+	case "NFKD":
+		return norm.NFKD, nil
+	default:
+		return 0, object.ValueErrorf("unknown normalization form %q (expected NFC, NFD, NFKC, or NFKD)", name)
+	}
+}
+
+// formArgs parses the string and optional form arguments shared by
+// normalize and is_normalized. The form defaults to NFC.
+func formArgs(name string, args []object.Object) (string, norm.Form, error) {
+	if len(args) < 1 || len(args) > 2 {
+		return "", 0, fmt.Errorf("unicode.%s: expected 1-2 arguments, got %d", name, len(args))
+	}
+	s, err := object.AsString(args[0])
+	if err != nil {
+		return "", 0, err
+	}
+	form := norm.NFC
+	if len(args) == 2 {
+		formName, err := object.AsString(args[1])
+		if err != nil {
+			return "", 0, err
+		}
+		if form, err = parseForm(formName); err != nil {
+			return "", 0, err
+		}
+	}
+	return s, form, nil
+}
+
+// foldKey returns the canonical caseless form of s, so that two strings are
+// equal ignoring case exactly when their keys are equal. This follows the
+// Unicode definition of canonical caseless matching: NFD, full case folding,
+// then NFC.
+func foldKey(s string) string {
+	return norm.NFC.String(cases.Fold().String(norm.NFD.String(s)))
+}
+
+// Casefold returns s with full Unicode case folding applied. Unlike
+// to_lower, folding maps characters such as "ß" to "ss", and does not depend
+// on the host locale.
+func Casefold(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) != 1 {
+		return nil, fmt.Errorf("unicode.casefold: expected 1 argument, got %d", len(args))
+	}
+	s, err := object.AsString(args[0])
+	if err != nil {
+		return nil, err
+	}
+	return object.NewString(cases.Fold().String(s)), nil
+}
+
+// EqIgnoreCase reports whether two strings are equal after normalization and
+// case folding.
+func EqIgnoreCase(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) != 2 {
+		return nil, fmt.Errorf("unicode.eq_ignore_case: expected 2 arguments, got %d", len(args))
+	}
+	a, err := object.AsString(args[0])
+	if err != nil {
+		return nil, err
+	}
+	b, err := object.AsString(args[1])
+	if err != nil {
+		return nil, err
+	}
+	return object.NewBool(foldKey(a) == foldKey(b)), nil
+}
+
+// Normalize returns s converted to the given normalization form.
+func Normalize(ctx context.Context, args ...object.Object) (object.Object, error) {
+	s, form, err := formArgs("normalize", args)
+	if err != nil {
+		return nil, err
+	}
+	return object.NewString(form.String(s)), nil
+}
+
+// IsNormalized reports whether s is already in the given normalization form.
+func IsNormalized(ctx context.Context, args ...object.Object) (object.Object, error) {
+	s, form, err := formArgs("is_normalized", args)
+	if err != nil {
+		return nil, err
+	}
+	return object.NewBool(form.IsNormalString(s)), nil
+}
+
+// Compare compares two strings after NFC normalization, returning -1, 0, or
+// 1. Strings that differ only in how accented characters are encoded, such
+// as a precomposed "é" and "e" followed by a combining accent, compare equal.
+func Compare(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) != 2 {
+		return nil, fmt.Errorf("unicode.compare: expected 2 arguments, got %d", len(args))
+	}
+	a, err := object.AsString(args[0])
+	if err != nil {
+		return nil, err
+	}
+	b, err := object.AsString(args[1])
+	if err != nil {
+		return nil, err
+	}
+	return object.NewInt(int64(strings.Compare(norm.NFC.String(a), norm.NFC.String(b)))), nil
+}
+
+// Module returns the unicode module.
+func Module() *object.Module {
+	return object.NewBuiltinsModule("unicode", map[string]object.Object{
+		"casefold":       object.NewBuiltin("casefold", Casefold),
+		"compare":        object.NewBuiltin("compare", Compare),
+		"eq_ignore_case": object.NewBuiltin("eq_ignore_case", EqIgnoreCase),
+		"is_normalized":  object.NewBuiltin("is_normalized", IsNormalized),
+		"normalize":      object.NewBuiltin("normalize", Normalize),
+	})
+}
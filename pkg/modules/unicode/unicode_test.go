@@ -0,0 +1,112 @@
+package unicode
+
+import (
+	"context"
+	"testing"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+func TestCasefold(t *testing.T) {
+	ctx := context.Background()
+	tests := []struct {
+		input    string
+		expected string
+	}{
+		{"Hello", "hello"},
+		{"Straße", "strasse"},
+		{"ΣΑΣ", "σασ"},
+		{"\u0130", "i\u0307"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.input, func(t *testing.T) {
+			result, err := Casefold(ctx, object.NewString(tt.input))
+			assert.Nil(t, err)
+			assert.Equal(t, result, object.NewString(tt.expected))
+		})
+	}
+}
+
+func TestEqIgnoreCase(t *testing.T) {
+	ctx := context.Background()
+	tests := []struct {
+		a, b     string
+		expected bool
+	}{
+		{"hello", "HELLO", true},
+		{"STRASSE", "straße", true},
+		{"Caf\u00e9", "CAFE\u0301", true},
+		{"caf\u00e9", "cafe", false},
+		{"", "", true},
+	}
+	for _, tt := range tests {
+		t.Run(tt.a+"/"+tt.b, func(t *testing.T) {
+			result, err := EqIgnoreCase(ctx, object.NewString(tt.a), object.NewString(tt.b))
+			assert.Nil(t, err)
+			assert.Equal(t, result, object.NewBool(tt.expected))
+		})
+	}
+}
+
+func TestNormalize(t *testing.T) {
+	ctx := context.Background()
+	result, err := Normalize(ctx, object.NewString("e\u0301"))
+	assert.Nil(t, err)
+	assert.Equal(t, result, object.NewString("\u00e9"))
+
+	result, err = Normalize(ctx, object.NewString("\u00e9"), object.NewString("NFD"))
+	assert.Nil(t, err)
+	assert.Equal(t, result, object.NewString("e\u0301"))
+
+	result, err = Normalize(ctx, object.NewString("ﬁ"), object.NewString("nfkc"))
+	assert.Nil(t, err)
+	assert.Equal(t, result, object.NewString("fi"))
+
+	result, err = IsNormalized(ctx, object.NewString("e\u0301"))
+	assert.Nil(t, err)
+	assert.Equal(t, result, object.False)
+
+	result, err = IsNormalized(ctx, object.NewString("e\u0301"), object.NewString("NFD"))
+	assert.Nil(t, err)
+	assert.Equal(t, result, object.True)
+}
+
+func TestCompare(t *testing.T) {
+	ctx := context.Background()
+	result, err := Compare(ctx, object.NewString("\u00e9"), object.NewString("e\u0301"))
+	assert.Nil(t, err)
+	assert.Equal(t, result, object.NewInt(0))
+
+	result, err = Compare(ctx, object.NewString("a"), object.NewString("b"))
+	assert.Nil(t, err)
+	assert.Equal(t, result, object.NewInt(-1))
+}
+
+func TestErrors(t *testing.T) {
+	ctx := context.Background()
+	_, err := Casefold(ctx)
+	assert.NotNil(t, err)
+
+	_, err = Casefold(ctx, object.NewInt(1))
+	assert.NotNil(t, err)
+
+	_, err = EqIgnoreCase(ctx, object.NewString("a"))
+	assert.NotNil(t, err)
+
+	_, err = Normalize(ctx, object.NewString("a"), object.NewString("NFX"))
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "unknown normalization form")
+
+	_, err = Compare(ctx, object.NewString("a"), object.NewInt(1))
+	assert.NotNil(t, err)
+}
+
+func TestModule(t *testing.T) {
+	m := Module()
+	assert.Equal(t, m.Name().Value(), "unicode")
+	for _, spec := range Docs() {
+		_, ok := m.GetAttr(spec.Name)
+		assert.True(t, ok, spec.Name)
+	}
+}
@@ -0,0 +1,301 @@
+package ws
+
+import (
+	"bufio"
+	"crypto/rand"
+	"crypto/sha1"
+	"encoding/base64"
+	"encoding/binary"
+	"errors"
+	"fmt"
+	"io"
+	"net"
+	"net/http"
+	"net/url"
+	"strings"
+	"sync"
+	"sync/atomic"
+	"time"
+	"unicode/utf8"
+)
+
+// MessageType is the type of a WebSocket data message.
+type MessageType int
+
+const (
+	TextMessage   MessageType = 1
+	BinaryMessage MessageType = 2
+)
+
+// Frame opcodes (RFC 6455, section 5.2)
+const (
+	opContinuation = 0x0
+	opText         = 0x1
+	opBinary       = 0x2
+	opClose        = 0x8
+	opPing         = 0x9
+	opPong         = 0xa
+)
+
+// Close status codes (RFC 6455, section 7.4.1)
+const (
+	CloseNormal    = 1000
+	CloseGoingAway = 1001
+)
+
+// acceptGUID is combined with the handshake key to compute the key the
+// server must send back (RFC 6455, section 1.3).
+const acceptGUID = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11"
+
+// ErrProtocol is wrapped by errors for frames that break the protocol.
+var ErrProtocol = errors.New("websocket protocol error")
+
+func protocolErrorf(format string, args ...any) error {
+	return fmt.Errorf("%w: %s", ErrProtocol, fmt.Sprintf(format, args...))
+}
+
+// Conn is the client end of a WebSocket connection. Reads must not be made
+// concurrently with each other, but writes may be made at any time.
+type Conn struct {
+	conn           net.Conn
+	reader         *bufio.Reader
+	maxMessageSize int64
+
+	writeMu       sync.Mutex
+	closeSent     atomic.Bool
+	closeReceived atomic.Bool
+
+	// readErr is the error that ended the last failed read. A read can fail
+	// partway through a frame, after which the stream can't be trusted.
+	readErr error
+}
+
+// Handshake performs the client side of the opening handshake for the ws or
+// wss URL u over conn, sending the given extra headers. Messages larger than
+// maxMessageSize bytes are rejected when read.
+func Handshake(conn net.Conn, u *url.URL, header http.Header, maxMessageSize int64) (*Conn, error) {
+	var nonce [16]byte
+	if _, err := rand.Read(nonce[:]); err != nil {
+		return nil, err
+	}
+	key := base64.StdEncoding.EncodeToString(nonce[:])
+
+	req := &http.Request{
+		Method:     http.MethodGet,
+		URL:        &url.URL{Path: u.Path, RawPath: u.RawPath, RawQuery: u.RawQuery},
+		Proto:      "HTTP/1.1",
+		ProtoMajor: 1,
+		ProtoMinor: 1,
+		Host:       u.Host,
+		Header:     header.Clone(),
+	}
+	if req.URL.Path == "" {
+		req.URL.Path = "/"
+	}
+	if req.Header == nil {
+		req.Header = http.Header{}
+	}
+	req.Header.Set("Upgrade", "websocket")
+	req.Header.Set("Connection", "Upgrade")
+	req.Header.Set("Sec-WebSocket-Key", key)
+	req.Header.Set("Sec-WebSocket-Version", "13")
+	if err := req.Write(conn); err != nil {
+		return nil, err
+	}
+
+	reader := bufio.NewReader(conn)
+	resp, err := http.ReadResponse(reader, req)
+	if err != nil {
+		return nil, err
+	}
+	resp.Body.Close()
+	if resp.StatusCode != http.StatusSwitchingProtocols {
+		return nil, fmt.Errorf("handshake failed: server responded %s", resp.Status)
+	}
+	if !strings.EqualFold(resp.Header.Get("Upgrade"), "websocket") ||
+		!headerContains(resp.Header, "Connection", "upgrade") {
+		return nil, protocolErrorf("server did not upgrade the connection")
+	}
+	sum := sha1.Sum([]byte(key + acceptGUID))
+	if resp.Header.Get("Sec-WebSocket-Accept") != base64.StdEncoding.EncodeToString(sum[:]) {
+		return nil, protocolErrorf("invalid Sec-WebSocket-Accept header")
+	}
+	return &Conn{conn: conn, reader: reader, maxMessageSize: maxMessageSize}, nil
+}
+
+// headerContains reports whether a comma-separated header contains token,
+// ignoring case.
+func headerContains(header http.Header, name, token string) bool {
+	for _, value := range header.Values(name) {
+		for _, part := range strings.Split(value, ",") {
+			if strings.EqualFold(strings.TrimSpace(part), token) {
+				return true
+			}
+		}
+	}
+	return false
+}
+
+// SetDeadline sets the deadline for reads and writes on the connection.
+func (c *Conn) SetDeadline(t time.Time) error {
+	return c.conn.SetDeadline(t)
+}
+
+// ReadMessage reads the next data message, answering pings while it waits.
+// It returns io.EOF once the server has closed the connection. Once a read
+// fails, every later read fails too.
+func (c *Conn) ReadMessage() (MessageType, []byte, error) {
+	if c.closeReceived.Load() {
+		return 0, nil, io.EOF
+	}
+	if c.readErr != nil {
+		return 0, nil, fmt.Errorf("connection is unusable after an earlier error: %w", c.readErr)
+	}
+	msgType, data, err := c.readMessage()
+	if err != nil && err != io.EOF {
+		c.readErr = err
+	}
+	return msgType, data, err
+}
+
+func (c *Conn) readMessage() (MessageType, []byte, error) {
+	var msgType MessageType
+	var data []byte
+	for {
+		fin, op, payload, err := c.readFrame()
+		if err != nil {
+			return 0, nil, err
+		}
+		switch op {
+		case opPing:
+			if err := c.writeFrame(opPong, payload); err != nil {
+				return 0, nil, err
+			}
+			continue
+		case opPong:
+			continue
+		case opClose:
+			c.closeReceived.Store(true)
+			// Echo the status code, as the protocol requires
+			if len(payload) > 2 {
+				payload = payload[:2]
+			}
+			if c.closeSent.CompareAndSwap(false, true) {
+				_ = c.writeFrame(opClose, payload)
+			}
+			return 0, nil, io.EOF
+		case opText, opBinary:
+			if msgType != 0 {
+				return 0, nil, protocolErrorf("expected a continuation frame")
+			}
+			msgType = MessageType(op)
+		case opContinuation:
+			if msgType == 0 {
+				return 0, nil, protocolErrorf("unexpected continuation frame")
+			}
+		default:
+			return 0, nil, protocolErrorf("unknown opcode %d", op)
+		}
+		if int64(len(data)+len(payload)) > c.maxMessageSize {
+			return 0, nil, fmt.Errorf("message exceeds %d bytes", c.maxMessageSize)
+		}
+		data = append(data, payload...)
+		if fin {
+			if msgType == TextMessage && !utf8.Valid(data) {
+				return 0, nil, protocolErrorf("text message is not valid UTF-8")
+			}
+			return msgType, data, nil
+		}
+	}
+}
+
+func (c *Conn) readFrame() (fin bool, op byte, payload []byte, err error) {
+	var head [2]byte
+	if _, err = io.ReadFull(c.reader, head[:]); err != nil {
+		return false, 0, nil, err
+	}
+	fin = head[0]&0x80 != 0
+	op = head[0] & 0x0f
+	if head[0]&0x70 != 0 {
+		return false, 0, nil, protocolErrorf("reserved bits are set")
+	}
+	if head[1]&0x80 != 0 {
+		return false, 0, nil, protocolErrorf("server frames must not be masked")
+	}
+	size := uint64(head[1] & 0x7f)
+	switch size {
+	case 126:
+		var ext [2]byte
+		if _, err = io.ReadFull(c.reader, ext[:]); err != nil {
+			return false, 0, nil, err
+		}
+		size = uint64(binary.BigEndian.Uint16(ext[:]))
+	case 127:
+		var ext [8]byte
+		if _, err = io.ReadFull(c.reader, ext[:]); err != nil {
+			return false, 0, nil, err
+		}
+		size = binary.BigEndian.Uint64(ext[:])
+	}
+	if op >= opClose && (size > 125 || !fin) {
+		return false, 0, nil, protocolErrorf("invalid control frame")
+	}
+	if size > uint64(c.maxMessageSize) {
+		return false, 0, nil, fmt.Errorf("message exceeds %d bytes", c.maxMessageSize)
+	}
+	payload = make([]byte, size)
+	if _, err = io.ReadFull(c.reader, payload); err != nil {
+		return false, 0, nil, err
+	}
+	return fin, op, payload, nil
+}
+
+// WriteMessage sends a data message in a single frame.
+func (c *Conn) WriteMessage(msgType MessageType, data []byte) error {
+	if c.closeSent.Load() {
+		return net.ErrClosed
+	}
+	return c.writeFrame(byte(msgType), data)
+}
+
+// writeFrame writes a final frame. Client frames are always masked.
+func (c *Conn) writeFrame(op byte, payload []byte) error {
+	buf := make([]byte, 0, 14+len(payload))
+	buf = append(buf, 0x80|op)
+	switch n := len(payload); {
+	case n <= 125:
+		buf = append(buf, 0x80|byte(n))
+	case n <= 0xffff:
+		buf = append(buf, 0x80|126)
+		buf = binary.BigEndian.AppendUint16(buf, uint16(n))
+	default:
+		buf = append(buf, 0x80|127)
+		buf = binary.BigEndian.AppendUint64(buf, uint64(n))
+	}
+	var mask [4]byte
+	if _, err := rand.Read(mask[:]); err != nil {
+		return err
+	}
+	buf = append(buf, mask[:]...)
+	for i, b := range payload {
+		buf = append(buf, b^mask[i%4])
+	}
+	c.writeMu.Lock()
+	defer c.writeMu.Unlock()
+	_, err := c.conn.Write(buf)
+	return err
+}
+
+// Close sends a close frame with the given status code and reason, if one
+// hasn't been sent, and closes the connection.
+func (c *Conn) Close(code int, reason string) error {
+	if c.closeSent.CompareAndSwap(false, true) {
+		payload := binary.BigEndian.AppendUint16(nil, uint16(code))
+		payload = append(payload, reason...)
+		// The connection is closed whether or not the server hears about
+		// it, so don't wait long on a server that isn't reading
+		_ = c.conn.SetWriteDeadline(time.Now().Add(time.Second))
+		_ = c.writeFrame(opClose, payload)
+	}
+	return c.conn.Close()
+}
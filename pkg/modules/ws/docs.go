@@ -0,0 +1,17 @@
+package ws
+
+import "github.com/deepnoodle-ai/risor/v2/pkg/object"
+
+// Docs returns documentation for the ws module.
+func Docs() []object.FuncSpec {
+	return wsDocs
+}
+
+// ModuleDoc returns the module-level documentation.
+func ModuleDoc() string {
+	return "WebSocket client for hosts that allow network access"
+}
+
+var wsDocs = []object.FuncSpec{
+	{Name: "connect", Doc: "Open a WebSocket connection", Args: []string{"url", "options?"}, Returns: "resource"},
+}
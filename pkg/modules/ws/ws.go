@@ -0,0 +1,256 @@
+// Package ws provides a WebSocket client for scripts that drive real-time
+// APIs.
+//
+// Connections run over the Dialer in the Policy passed to Module, and the
+// Policy can also limit which URLs scripts reach, so risor.Builtins() leaves
+// the module out. A host that trusts its scripts with any URL can add it as:
+//
+//	env := risor.Builtins()
+//	env["ws"] = modws.Module(modws.Policy{Dialer: &net.Dialer{}})
+package ws
+
+import (
+	"context"
+	"crypto/tls"
+	"errors"
+	"fmt"
+	"io"
+	"net"
+	"net/http"
+	"net/url"
+	"time"
+
+	"github.com/deepnoodle-ai/risor/v2/internal/netpolicy"
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+)
+
+// DefaultMaxMessageSize is the largest message scripts can receive when the
+// Policy doesn't set a limit.
+const DefaultMaxMessageSize = 16 << 20
+
+// Dialer opens network connections. *net.Dialer implements it.
+type Dialer = netpolicy.Dialer
+
+// Policy controls the connections scripts can open with ws.connect. The
+// zero Policy allows none.
+type Policy struct {
+	// Dialer opens the TCP connections that WebSockets run over. If it is
+	// nil, scripts can't open any.
+	Dialer Dialer
+
+	// Allow reports whether scripts may connect to a URL. If it is nil,
+	// all URLs are allowed.
+	Allow func(u *url.URL) bool
+
+	// TLSConfig is used for wss URLs. If it is nil, the default
+	// configuration is used.
+	TLSConfig *tls.Config
+
+	// MaxMessageSize limits the size of messages scripts receive. If it is
+	// zero, DefaultMaxMessageSize is used.
+	MaxMessageSize int64
+}
+
+// Connect opens a WebSocket connection to a ws or wss URL. The optional
+// options map may set a timeout, which limits the time taken to connect and
+// then each send and receive, and headers to send with the handshake.
+func (p Policy) Connect(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) < 1 || len(args) > 2 {
+		return nil, fmt.Errorf("ws.connect: expected 1-2 arguments, got %d", len(args))
+	}
+	rawURL, err := object.AsString(args[0])
+	if err != nil {
+		return nil, err
+	}
+	var timeout time.Duration
+	header := http.Header{}
+	if len(args) == 2 {
+		options, err := object.AsMap(args[1])
+		if err != nil {
+			return nil, err
+		}
+		if timeout, header, err = parseOptions(options); err != nil {
+			return nil, err
+		}
+	}
+	u, err := url.Parse(rawURL)
+	if err != nil || (u.Scheme != "ws" && u.Scheme != "wss") || u.Host == "" {
+		return nil, object.ValueErrorf("ws.connect: invalid WebSocket URL %q", rawURL)
+	}
+	if err := netpolicy.Check("ws.connect", p.Dialer, p.Allow == nil || p.Allow(u), rawURL); err != nil {
+		return nil, err
+	}
+	if timeout > 0 {
+		var cancel context.CancelFunc
+		ctx, cancel = context.WithTimeout(ctx, timeout)
+		defer cancel()
+	}
+	conn, err := p.dial(ctx, u, header)
+	if err != nil {
+		return nil, fmt.Errorf("ws.connect: %w", err)
+	}
+	return newResource(conn, timeout), nil
+}
+
+func parseOptions(options *object.Map) (time.Duration, http.Header, error) {
+	var timeout time.Duration
+	header := http.Header{}
+	for key, value := range options.Value() {
+		switch key {
+		case "timeout":
+			d, err := object.AsDuration(value)
+			if err != nil {
+				return 0, nil, err
+			}
+			if d < 0 {
+				return 0, nil, object.ValueErrorf("ws.connect: timeout must not be negative")
+			}
+			timeout = d
+		case "headers":
+			m, err := object.AsMap(value)
+			if err != nil {
+				return 0, nil, err
+			}
+			for name, v := range m.Value() {
+				s, err := object.AsString(v)
+				if err != nil {
+					return 0, nil, err
+				}
+				header.Set(name, s)
+			}
+		default:
+			return 0, nil, object.ValueErrorf("ws.connect: unknown option %q", key)
+		}
+	}
+	return timeout, header, nil
+}
+
+// dial connects to the server and performs the opening handshake, all
+// bounded by ctx.
+func (p Policy) dial(ctx context.Context, u *url.URL, header http.Header) (*Conn, error) {
+	address := u.Host
+	if u.Port() == "" {
+		if u.Scheme == "wss" {
+			address = net.JoinHostPort(u.Hostname(), "443")
+		} else {
+			address = net.JoinHostPort(u.Hostname(), "80")
+		}
+	}
+	conn, err := p.Dialer.DialContext(ctx, "tcp", address)
+	if err != nil {
+		return nil, err
+	}
+	if u.Scheme == "wss" {
+		config := &tls.Config{}
+		if p.TLSConfig != nil {
+			config = p.TLSConfig.Clone()
+		}
+		if config.ServerName == "" {
+			config.ServerName = u.Hostname()
+		}
+		tlsConn := tls.Client(conn, config)
+		if err := tlsConn.HandshakeContext(ctx); err != nil {
+			conn.Close()
+			return nil, err
+		}
+		conn = tlsConn
+	}
+	if deadline, ok := ctx.Deadline(); ok {
+		_ = conn.SetDeadline(deadline)
+	}
+	maxSize := p.MaxMessageSize
+	if maxSize <= 0 {
+		maxSize = DefaultMaxMessageSize
+	}
+	ws, err := Handshake(conn, u, header, maxSize)
+	if err != nil {
+		conn.Close()
+		return nil, err
+	}
+	_ = conn.SetDeadline(time.Time{})
+	return ws, nil
+}
+
+// socket holds a connection and the timeout for each send and receive.
+type socket struct {
+	conn    *Conn
+	timeout *netpolicy.Timeout
+}
+
+// newResource returns a resource named "websocket" for scripts to use conn.
+func newResource(conn *Conn, timeout time.Duration) *object.Resource {
+	s := &socket{conn: conn, timeout: netpolicy.NewTimeout(timeout)}
+	release := func() error {
+		return conn.Close(CloseNormal, "")
+	}
+	return object.NewResource("websocket", conn, release).
+		WithMethod("send", s.send).
+		WithMethod("recv", s.recv).
+		WithMethod("set_timeout", s.setTimeout)
+}
+
+func (s *socket) send(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) != 1 {
+		return nil, fmt.Errorf("websocket.send: expected 1 argument, got %d", len(args))
+	}
+	var msgType MessageType
+	var data []byte
+	switch arg := args[0].(type) {
+	case *object.String:
+		msgType, data = TextMessage, []byte(arg.Value())
+	case *object.Bytes:
+		msgType, data = BinaryMessage, arg.Value()
+	default:
+		return nil, object.TypeErrorf("websocket.send: expected a string or bytes (%s given)", args[0].Type())
+	}
+	err := s.timeout.Do(ctx, s.conn, func() error { return s.conn.WriteMessage(msgType, data) })
+	if err != nil {
+		return nil, err
+	}
+	return object.Nil, nil
+}
+
+func (s *socket) recv(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) != 0 {
+		return nil, fmt.Errorf("websocket.recv: expected 0 arguments, got %d", len(args))
+	}
+	var msgType MessageType
+	var data []byte
+	err := s.timeout.Do(ctx, s.conn, func() error {
+		var err error
+		msgType, data, err = s.conn.ReadMessage()
+		return err
+	})
+	if errors.Is(err, io.EOF) {
+		return object.Nil, nil
+	}
+	if err != nil {
+		return nil, err
+	}
+	if msgType == BinaryMessage {
+		return object.NewBytes(data), nil
+	}
+	return object.NewString(string(data)), nil
+}
+
+func (s *socket) setTimeout(ctx context.Context, args ...object.Object) (object.Object, error) {
+	if len(args) != 1 {
+		return nil, fmt.Errorf("websocket.set_timeout: expected 1 argument, got %d", len(args))
+	}
+	timeout, err := object.AsDuration(args[0])
+	if err != nil {
+		return nil, err
+	}
+	if timeout < 0 {
+		return nil, object.ValueErrorf("websocket.set_timeout: timeout must not be negative")
+	}
+	s.timeout.Set(timeout)
+	return object.Nil, nil
+}
+
+// Module returns the ws module, which opens connections according to policy.
+func Module(policy Policy) *object.Module {
+	return object.NewBuiltinsModule("ws", map[string]object.Object{
+		"connect": object.NewBuiltin("connect", policy.Connect),
+	})
+}
@@ -0,0 +1,218 @@
+package ws
+
+import (
+	"bufio"
+	"context"
+	"crypto/sha1"
+	"encoding/base64"
+	"encoding/binary"
+	"errors"
+	"io"
+	"net"
+	"net/http"
+	"net/http/httptest"
+	"net/url"
+	"os"
+	"strings"
+	"testing"
+
+	"github.com/deepnoodle-ai/risor/v2/internal/objtest"
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+// testServer upgrades each request to a WebSocket and runs handle on it.
+func testServer(t *testing.T, handle func(r *bufio.Reader, w net.Conn, req *http.Request)) string {
+	t.Helper()
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		sum := sha1.Sum([]byte(r.Header.Get("Sec-WebSocket-Key") + acceptGUID))
+		conn, rw, err := w.(http.Hijacker).Hijack()
+		if err != nil {
+			return
+		}
+		defer conn.Close()
+		_, _ = conn.Write([]byte("HTTP/1.1 101 Switching Protocols\r\n" +
+			"Upgrade: websocket\r\nConnection: Upgrade\r\n" +
+			"Sec-WebSocket-Accept: " + base64.StdEncoding.EncodeToString(sum[:]) + "\r\n\r\n"))
+		handle(rw.Reader, conn, r)
+	}))
+	t.Cleanup(server.Close)
+	return "ws" + strings.TrimPrefix(server.URL, "http")
+}
+
+// readFrame reads a masked client frame.
+func readFrame(r *bufio.Reader) (byte, []byte, error) {
+	var head [2]byte
+	if _, err := io.ReadFull(r, head[:]); err != nil {
+		return 0, nil, err
+	}
+	size := int(head[1] & 0x7f)
+	if size == 126 {
+		var ext [2]byte
+		if _, err := io.ReadFull(r, ext[:]); err != nil {
+			return 0, nil, err
+		}
+		size = int(binary.BigEndian.Uint16(ext[:]))
+	}
+	var mask [4]byte
+	if _, err := io.ReadFull(r, mask[:]); err != nil {
+		return 0, nil, err
+	}
+	payload := make([]byte, size)
+	if _, err := io.ReadFull(r, payload); err != nil {
+		return 0, nil, err
+	}
+	for i := range payload {
+		payload[i] ^= mask[i%4]
+	}
+	return head[0] & 0x0f, payload, nil
+}
+
+// writeFrame writes an unmasked server frame.
+func writeFrame(w io.Writer, fin bool, op byte, payload []byte) {
+	head := op
+	if fin {
+		head |= 0x80
+	}
+	_, _ = w.Write(append([]byte{head, byte(len(payload))}, payload...))
+}
+
+func TestSendRecv(t *testing.T) {
+	pong := make(chan string, 1)
+	closeCode := make(chan uint16, 1)
+	wsURL := testServer(t, func(r *bufio.Reader, w net.Conn, req *http.Request) {
+		// A text message is answered after a ping, in two fragments
+		if op, payload, err := readFrame(r); err != nil || op != opText || string(payload) != "hello" {
+			return
+		}
+		writeFrame(w, true, opPing, []byte("p"))
+		if op, payload, err := readFrame(r); err == nil && op == opPong {
+			pong <- string(payload)
+		}
+		writeFrame(w, false, opText, []byte("hel"))
+		writeFrame(w, true, opContinuation, []byte("lo"))
+
+		// A binary message is echoed
+		if op, payload, err := readFrame(r); err == nil && op == opBinary {
+			writeFrame(w, true, opBinary, payload)
+		}
+
+		writeFrame(w, true, opClose, []byte{0x03, 0xe8})
+		if op, payload, err := readFrame(r); err == nil && op == opClose && len(payload) == 2 {
+			closeCode <- binary.BigEndian.Uint16(payload)
+		}
+	})
+
+	policy := Policy{Dialer: &net.Dialer{}}
+	conn, err := policy.Connect(context.Background(), object.NewString(wsURL+"/chat"),
+		object.NewMap(map[string]object.Object{"timeout": object.NewInt(5)}))
+	assert.Nil(t, err)
+	defer conn.(*object.Resource).Close()
+
+	_, err = objtest.Call(t, conn, "send", object.NewString("hello"))
+	assert.Nil(t, err)
+	result, err := objtest.Call(t, conn, "recv")
+	assert.Nil(t, err)
+	assert.Equal(t, result, object.NewString("hello"))
+	assert.Equal(t, <-pong, "p")
+
+	_, err = objtest.Call(t, conn, "send", object.NewBytes([]byte{0, 1, 2}))
+	assert.Nil(t, err)
+	result, err = objtest.Call(t, conn, "recv")
+	assert.Nil(t, err)
+	assert.Equal(t, result, object.NewBytes([]byte{0, 1, 2}))
+
+	// The server closes the connection
+	result, err = objtest.Call(t, conn, "recv")
+	assert.Nil(t, err)
+	assert.Equal(t, result, object.Nil)
+	assert.Equal(t, <-closeCode, uint16(CloseNormal))
+}
+
+func TestTimeoutAndHeaders(t *testing.T) {
+	auth := make(chan string, 1)
+	done := make(chan struct{})
+	wsURL := testServer(t, func(r *bufio.Reader, w net.Conn, req *http.Request) {
+		auth <- req.Header.Get("Authorization")
+		<-done
+	})
+	defer close(done)
+
+	policy := Policy{Dialer: &net.Dialer{}}
+	conn, err := policy.Connect(context.Background(), object.NewString(wsURL),
+		object.NewMap(map[string]object.Object{
+			"headers": object.NewMap(map[string]object.Object{
+				"Authorization": object.NewString("Bearer abc"),
+			}),
+		}))
+	assert.Nil(t, err)
+	defer conn.(*object.Resource).Close()
+	assert.Equal(t, <-auth, "Bearer abc")
+
+	_, err = objtest.Call(t, conn, "set_timeout", object.NewString("20ms"))
+	assert.Nil(t, err)
+	_, err = objtest.Call(t, conn, "recv")
+	assert.True(t, errors.Is(err, os.ErrDeadlineExceeded))
+}
+
+func TestRecvAfterPartialFrame(t *testing.T) {
+	resume := make(chan struct{})
+	wsURL := testServer(t, func(r *bufio.Reader, w net.Conn, req *http.Request) {
+		// Stall partway through a frame, then finish it and send another
+		_, _ = w.Write([]byte{0x80 | opText, 5, 'h', 'e'})
+		<-resume
+		_, _ = w.Write([]byte("llo"))
+		writeFrame(w, true, opText, []byte("next"))
+		_, _ = readFrame(r)
+	})
+
+	policy := Policy{Dialer: &net.Dialer{}}
+	conn, err := policy.Connect(context.Background(), object.NewString(wsURL),
+		object.NewMap(map[string]object.Object{"timeout": object.NewString("20ms")}))
+	assert.Nil(t, err)
+	defer conn.(*object.Resource).Close()
+
+	_, err = objtest.Call(t, conn, "recv")
+	assert.True(t, errors.Is(err, os.ErrDeadlineExceeded))
+	close(resume)
+
+	// The rest of the first frame must not be read as a new frame
+	_, err = objtest.Call(t, conn, "recv")
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "connection is unusable after an earlier error")
+	assert.True(t, errors.Is(err, os.ErrDeadlineExceeded))
+}
+
+func TestConnectErrors(t *testing.T) {
+	ctx := context.Background()
+	wsURL := object.NewString("ws://example.com/feed")
+
+	_, err := Policy{}.Connect(ctx, wsURL)
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), "ws.connect: network access is not allowed")
+
+	policy := Policy{
+		Dialer: &net.Dialer{},
+		Allow:  func(u *url.URL) bool { return u.Hostname() == "localhost" },
+	}
+	_, err = policy.Connect(ctx, wsURL)
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), "ws.connect: connecting to ws://example.com/feed is not allowed")
+
+	_, err = policy.Connect(ctx, object.NewString("http://localhost/feed"))
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), `value error: ws.connect: invalid WebSocket URL "http://localhost/feed"`)
+
+	_, err = policy.Connect(ctx, object.NewString("ws://localhost/feed"),
+		object.NewMap(map[string]object.Object{"retries": object.NewInt(3)}))
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), `value error: ws.connect: unknown option "retries"`)
+
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		http.Error(w, "no", http.StatusForbidden)
+	}))
+	defer server.Close()
+	_, err = Policy{Dialer: &net.Dialer{}}.Connect(ctx, object.NewString("ws"+strings.TrimPrefix(server.URL, "http")))
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), "ws.connect: handshake failed: server responded 403 Forbidden")
+}
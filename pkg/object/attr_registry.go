@@ -16,6 +16,12 @@ type AttrDef[T any] struct {
 	MethodImpl func(self T, ctx context.Context, args ...Object) (Object, error)
 	// For properties:
 	PropertyImpl func(self T) Object
+
+	// fullName is the qualified method name, e.g. "list.append"
+	fullName string
+	// bound validates the argument count and calls MethodImpl with the
+	// receiver, so GetAttr can bind a method without allocating a closure
+	bound func(recv any, ctx context.Context, args ...Object) (Object, error)
 }
 
 // AttrRegistry holds all attributes (properties and methods) for a given object type.
@@ -71,19 +77,24 @@ func (r *AttrRegistry[T]) GetAttr(self T, name string) (Object, bool) {
 		return attr.PropertyImpl(self), true
 	}
 
-	// Method: wrap in Builtin with argument validation
-	minArgs := attr.MinArgs
+	// Method: a Builtin bound to self, with argument validation
+	return &Builtin{name: attr.fullName, method: attr.bound, recv: self}, true
+}
+
+// CallMethod calls the named method on self directly, validating the argument
+// count as the Builtin returned by GetAttr would. It reports false if there is
+// no method with the given name.
+func (r *AttrRegistry[T]) CallMethod(ctx context.Context, self T, name string, args []Object) (Object, bool, error) {
+	attr, ok := r.attrs[name]
+	if !ok || attr.IsProperty {
+		return nil, false, nil
+	}
 	maxArgs := len(attr.Spec.Args)
-	fullName := r.typeName + "." + name
-	return &Builtin{
-		name: fullName,
-		fn: func(ctx context.Context, args ...Object) (Object, error) {
-			if len(args) < minArgs || len(args) > maxArgs {
-				return nil, argsRangeError(fullName, minArgs, maxArgs, len(args))
-			}
-			return attr.MethodImpl(self, ctx, args...)
-		},
-	}, true
+	if len(args) < attr.MinArgs || len(args) > maxArgs {
+		return nil, true, argsRangeError(attr.fullName, attr.MinArgs, maxArgs, len(args))
+	}
+	result, err := attr.MethodImpl(self, ctx, args...)
+	return result, true, err
 }
 
 // Doc sets the attribute's documentation string.
@@ -150,7 +161,21 @@ func (b *AttrBuilder[T]) Impl(fn func(T, context.Context, ...Object) (Object, er
 	if b.optionalIdx > 0 {
 		minArgs = b.optionalIdx - 1 // -1 because optionalIdx is 1-indexed
 	}
-	r.attrs[b.name] = AttrDef[T]{Spec: spec, MinArgs: minArgs, MethodImpl: fn}
+	fullName := r.typeName + "." + b.name
+	maxArgs := len(b.args)
+	bound := func(recv any, ctx context.Context, args ...Object) (Object, error) {
+		if len(args) < minArgs || len(args) > maxArgs {
+			return nil, argsRangeError(fullName, minArgs, maxArgs, len(args))
+		}
+		return fn(recv.(T), ctx, args...)
+	}
+	r.attrs[b.name] = AttrDef[T]{
+		Spec:       spec,
+		MinArgs:    minArgs,
+		MethodImpl: fn,
+		fullName:   fullName,
+		bound:      bound,
+	}
 	r.specs = append(r.specs, spec)
 }
 
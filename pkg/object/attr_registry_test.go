@@ -56,6 +56,38 @@ func TestAttrRegistryBasics(t *testing.T) {
 	assert.False(t, ok)
 }
 
+// TestAttrRegistryBoundMethod tests calling methods bound by GetAttr and
+// calling them directly with CallMethod.
+func TestAttrRegistryBoundMethod(t *testing.T) {
+	ctx := context.Background()
+	list := NewList([]Object{NewInt(1)})
+
+	// Binding a method allocates only the Builtin itself
+	var bound Object
+	allocs := testing.AllocsPerRun(100, func() {
+		bound, _ = listMethods.GetAttr(list, "append")
+	})
+	assert.Equal(t, allocs, float64(1))
+	assert.NotNil(t, bound)
+
+	method, ok := list.GetAttr("append")
+	assert.True(t, ok)
+	builtin := method.(*Builtin)
+	assert.Equal(t, builtin.Name(), "list.append")
+	_, err := builtin.Value()(ctx, NewInt(2))
+	assert.Nil(t, err)
+	_, err = builtin.Call(ctx)
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), "list.append: expected 1 argument, got 0")
+
+	result, found, err := list.CallMethod(ctx, "count", []Object{NewInt(2)})
+	assert.True(t, found)
+	assert.Nil(t, err)
+	assert.Equal(t, result.(*Int).Value(), int64(1))
+	_, found, _ = list.CallMethod(ctx, "missing", nil)
+	assert.False(t, found)
+}
+
 // TestAttrRegistrySpecs tests the Specs() method.
 func TestAttrRegistrySpecs(t *testing.T) {
 	type testObj struct{}
@@ -360,10 +392,17 @@ func TestModuleAttrs(t *testing.T) {
 	assert.True(t, ok)
 	assert.Equal(t, foo.(*String).Value(), "bar")
 
-	// Test Attrs() returns registry specs
+	// Test Attrs() returns registry specs followed by members
 	attrs := m.Attrs()
-	assert.Equal(t, len(attrs), 1)
+	assert.Equal(t, len(attrs), 2)
 	assert.Equal(t, attrs[0].Name, "__name__")
+	assert.Equal(t, attrs[1].Name, "foo")
+
+	// Members are described by attached documentation
+	m.WithDocs("", []FuncSpec{{Name: "foo", Doc: "A foo", Returns: "string"}})
+	attrs = m.Attrs()
+	assert.Equal(t, attrs[1].Doc, "A foo")
+	assert.Equal(t, attrs[1].Returns, "string")
 }
 
 // TestBuiltinAttrs tests Builtin attributes.
@@ -382,9 +421,18 @@ func TestBuiltinAttrs(t *testing.T) {
 	assert.True(t, ok)
 	assert.Equal(t, module, Nil)
 
+	// Test __doc__ property (nil until documentation is attached)
+	doc, ok := b.GetAttr("__doc__")
+	assert.True(t, ok)
+	assert.Equal(t, doc, Nil)
+	b.WithSpec(FuncSpec{Name: "mybuiltin", Doc: "Does things"})
+	doc, ok = b.GetAttr("__doc__")
+	assert.True(t, ok)
+	assert.Equal(t, doc.(*String).Value(), "Does things")
+
 	// Test Attrs() returns registry specs
 	attrs := b.Attrs()
-	assert.Equal(t, len(attrs), 2)
+	assert.Equal(t, len(attrs), 3)
 
 	names := make(map[string]bool)
 	for _, attr := range attrs {
@@ -392,6 +440,7 @@ func TestBuiltinAttrs(t *testing.T) {
 	}
 	assert.True(t, names["__name__"])
 	assert.True(t, names["__module__"])
+	assert.True(t, names["__doc__"])
 }
 
 // TestRangeAttrs tests Range.Attrs() returns property specs.
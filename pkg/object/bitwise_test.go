@@ -0,0 +1,72 @@
+package object
+
+import (
+	"testing"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/op"
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+func TestBitwiseOperations(t *testing.T) {
+	tests := []struct {
+		name     string
+		left     Object
+		opType   op.BinaryOpType
+		right    Object
+		expected Object
+	}{
+		{"int and", NewInt(12), op.BitwiseAnd, NewInt(10), NewInt(8)},
+		{"int or", NewInt(12), op.BitwiseOr, NewInt(10), NewInt(14)},
+		{"int xor", NewInt(12), op.Xor, NewInt(10), NewInt(6)},
+		{"int lshift", NewInt(1), op.LShift, NewInt(4), NewInt(16)},
+		{"int rshift", NewInt(16), op.RShift, NewInt(4), NewInt(1)},
+		{"int rshift is arithmetic", NewInt(-16), op.RShift, NewInt(2), NewInt(-4)},
+		{"int lshift past width", NewInt(1), op.LShift, NewInt(64), NewInt(0)},
+		{"negative int rshift past width", NewInt(-1), op.RShift, NewInt(100), NewInt(-1)},
+		{"int with byte", NewInt(1), op.LShift, NewByte(3), NewInt(8)},
+		{"byte and", NewByte(0xF0), op.BitwiseAnd, NewByte(0x3C), NewByte(0x30)},
+		{"byte xor", NewByte(0xFF), op.Xor, NewByte(0x0F), NewByte(0xF0)},
+		{"byte lshift truncates", NewByte(0x81), op.LShift, NewByte(1), NewByte(0x02)},
+		{"byte rshift is logical", NewByte(0x80), op.RShift, NewByte(7), NewByte(1)},
+		{"byte with int widens", NewByte(0x81), op.LShift, NewInt(1), NewInt(0x102)},
+		{"byte or int", NewByte(1), op.BitwiseOr, NewInt(256), NewInt(257)},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			result, err := tt.left.RunOperation(tt.opType, tt.right)
+			assert.Nil(t, err)
+			assert.Equal(t, result.Type(), tt.expected.Type())
+			assert.True(t, result.Equals(tt.expected), "got %s, want %s", result.Inspect(), tt.expected.Inspect())
+		})
+	}
+}
+
+func TestNegativeShiftCount(t *testing.T) {
+	tests := []struct {
+		name   string
+		left   Object
+		opType op.BinaryOpType
+		right  Object
+	}{
+		{"int lshift", NewInt(1), op.LShift, NewInt(-1)},
+		{"int rshift", NewInt(1), op.RShift, NewInt(-1)},
+		{"byte lshift", NewByte(1), op.LShift, NewInt(-1)},
+		{"byte rshift", NewByte(1), op.RShift, NewInt(-1)},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			_, err := tt.left.RunOperation(tt.opType, tt.right)
+			assert.NotNil(t, err)
+			assert.Contains(t, err.Error(), "negative shift count: -1")
+		})
+	}
+}
+
+func TestBitwiseFloatUnsupported(t *testing.T) {
+	for _, opType := range []op.BinaryOpType{op.BitwiseAnd, op.BitwiseOr, op.Xor, op.LShift, op.RShift} {
+		_, err := NewInt(1).RunOperation(opType, NewFloat(1))
+		assert.NotNil(t, err)
+		_, err = NewFloat(1).RunOperation(opType, NewInt(1))
+		assert.NotNil(t, err)
+	}
+}
@@ -57,6 +57,10 @@ func (b *Bool) Compare(other Object) (int, error) {
 	return -1, nil
 }
 
+func (b *Bool) HashKey() HashKey {
+	return HashKey{Type: BOOL, Value: b.value}
+}
+
 func (b *Bool) Equals(other Object) bool {
 	otherBool, ok := other.(*Bool)
 	if !ok {
@@ -19,6 +19,16 @@ func init() {
 			return NewString(b.Key())
 		})
 
+	builtinAttrs.Define("__doc__").
+		Doc("The documentation of the builtin function, or nil").
+		Returns("string").
+		Getter(func(b *Builtin) Object {
+			if b.spec != nil && b.spec.Doc != "" {
+				return NewString(b.spec.Doc)
+			}
+			return Nil
+		})
+
 	builtinAttrs.Define("__module__").
 		Doc("The module this builtin belongs to, or nil").
 		Returns("module").
@@ -41,6 +51,11 @@ type Builtin struct {
 	// The name of the function.
 	name string
 
+	// For a method bound to a receiver, the method and the receiver. Call
+	// passes the receiver explicitly, so binding a method needs no closure.
+	method func(recv any, ctx context.Context, args ...Object) (Object, error)
+	recv   any
+
 	// The module the function originates from (optional). Used by GetAttr to
 	// return the actual module object for the __module__ attribute.
 	module *Module
@@ -50,6 +65,9 @@ type Builtin struct {
 	// priority over module.Name() when set, allowing standalone builtins to
 	// report a module name without having an actual module reference.
 	moduleName string
+
+	// Documentation for the function (optional), shown by help().
+	spec *FuncSpec
 }
 
 func (b *Builtin) Attrs() []AttrSpec {
@@ -73,6 +91,9 @@ func (b *Builtin) Type() Type {
 }
 
 func (b *Builtin) Value() BuiltinFunction {
+	if b.method != nil {
+		return b.Call
+	}
 	return b.fn
 }
 
@@ -81,6 +102,9 @@ func (b *Builtin) Interface() interface{} {
 }
 
 func (b *Builtin) Call(ctx context.Context, args ...Object) (Object, error) {
+	if b.method != nil {
+		return b.method(b.recv, ctx, args...)
+	}
 	return b.fn(ctx, args...)
 }
 
@@ -159,3 +183,17 @@ func (b *Builtin) WithModule(module *Module) *Builtin {
 	}
 	return b
 }
+
+// WithSpec attaches documentation to this builtin, which help() shows.
+func (b *Builtin) WithSpec(spec FuncSpec) *Builtin {
+	b.spec = &spec
+	return b
+}
+
+// Spec returns the documentation attached to this builtin, if any.
+func (b *Builtin) Spec() (FuncSpec, bool) {
+	if b.spec == nil {
+		return FuncSpec{}, false
+	}
+	return *b.spec, true
+}
@@ -1,6 +1,7 @@
 package object
 
 import (
+	"cmp"
 	"fmt"
 	"math"
 
@@ -47,14 +48,7 @@ func (b *Byte) String() string {
 func (b *Byte) Compare(other Object) (int, error) {
 	switch other := other.(type) {
 	case *Float:
-		thisFloat := float64(b.value)
-		if thisFloat == other.value {
-			return 0, nil
-		}
-		if thisFloat > other.value {
-			return 1, nil
-		}
-		return -1, nil
+		return cmp.Compare(float64(b.value), other.value), nil
 	case *Int:
 		thisInt := int64(b.value)
 		if thisInt == other.value {
@@ -72,11 +66,18 @@ func (b *Byte) Compare(other Object) (int, error) {
 			return 1, nil
 		}
 		return -1, nil
+	case *Decimal:
+		result, err := other.Compare(b)
+		return -result, err
 	default:
 		return 0, TypeErrorf("unable to compare byte and %s", other.Type())
 	}
 }
 
+func (b *Byte) HashKey() HashKey {
+	return HashKey{Type: INT, Value: int64(b.value)}
+}
+
 func (b *Byte) Equals(other Object) bool {
 	switch other := other.(type) {
 	case *Byte:
@@ -85,6 +86,8 @@ func (b *Byte) Equals(other Object) bool {
 		return int64(b.value) == other.value
 	case *Float:
 		return float64(b.value) == other.value
+	case *Decimal:
+		return other.Equals(b)
 	}
 	return false
 }
@@ -101,6 +104,8 @@ func (b *Byte) RunOperation(opType op.BinaryOpType, right Object) (Object, error
 		return b.runOperationInt(opType, right.value)
 	case *Float:
 		return b.runOperationFloat(opType, right.value)
+	case *Decimal:
+		return NewDecimalFromInt(int64(b.value)).RunOperation(opType, right)
 	default:
 		return nil, newTypeErrorf("unsupported operation for byte: %v on type %s", opType, right.Type())
 	}
@@ -164,9 +169,15 @@ func (b *Byte) runOperationInt(opType op.BinaryOpType, right int64) (Object, err
 	case op.Power:
 		return NewInt(int64(math.Pow(float64(b.value), float64(right)))), nil
 	case op.LShift:
-		return NewInt(int64(b.value) << right), nil
+		if right < 0 {
+			return nil, newValueErrorf("negative shift count: %d", right)
+		}
+		return NewInt(int64(b.value) << uint64(right)), nil
 	case op.RShift:
-		return NewInt(int64(b.value) >> right), nil
+		if right < 0 {
+			return nil, newValueErrorf("negative shift count: %d", right)
+		}
+		return NewInt(int64(b.value) >> uint64(right)), nil
 	case op.BitwiseAnd:
 		return NewInt(int64(b.value) & right), nil
 	case op.BitwiseOr:
@@ -144,6 +144,10 @@ func (b *Bytes) GetAttr(name string) (Object, bool) {
 	return bytesMethods.GetAttr(b, name)
 }
 
+func (b *Bytes) CallMethod(ctx context.Context, name string, args []Object) (Object, bool, error) {
+	return bytesMethods.CallMethod(ctx, b, name, args)
+}
+
 func (b *Bytes) SetAttr(name string, value Object) error {
 	return TypeErrorf("bytes has no attribute %q", name)
 }
@@ -242,10 +246,13 @@ func (b *Bytes) GetItem(key Object) (Object, *Error) {
 }
 
 func (b *Bytes) GetSlice(slice Slice) (Object, *Error) {
-	start, stop, err := ResolveIntSlice(slice, int64(len(b.value)))
+	start, stop, step, err := ResolveSlice(slice, int64(len(b.value)))
 	if err != nil {
 		return nil, NewError(err)
 	}
+	if step != 1 {
+		return NewBytes(stepSlice(b.value, start, stop, step)), nil
+	}
 	return NewBytes(b.value[start:stop]), nil
 }
 
@@ -269,6 +276,10 @@ func (b *Bytes) SetItem(key, value Object) *Error {
 	return nil
 }
 
+func (b *Bytes) SetSlice(s Slice, value Object) *Error {
+	return TypeErrorf("cannot assign to a slice of bytes")
+}
+
 func (b *Bytes) DelItem(key Object) *Error {
 	return TypeErrorf("cannot delete from bytes")
 }
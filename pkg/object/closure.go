@@ -18,6 +18,7 @@ type Closure struct {
 	defaults      []Object           // Pre-converted default values
 	defaultsCount int                // Number of non-nil defaults
 	freeVars      []*Cell            // Captured variables (closure state)
+	env           any                // Globals the closure was created with, set by the VM
 }
 
 func (f *Closure) SetAttr(name string, value Object) error {
@@ -217,5 +218,19 @@ func CloneWithCaptures(c *Closure, freeVars []*Cell) *Closure {
 		defaults:      c.defaults,
 		defaultsCount: c.defaultsCount,
 		freeVars:      vars,
+		env:           c.env,
 	}
 }
+
+// Env returns the global environment the closure was created in, as set by
+// BindEnv, or nil. The environment is opaque to this package; the VM uses it
+// to run the closure against the globals of the code that defined it.
+func (f *Closure) Env() any {
+	return f.env
+}
+
+// BindEnv sets the global environment of a closure the VM has just created.
+// Closures cloned from it with CloneWithCaptures share the environment.
+func (f *Closure) BindEnv(env any) {
+	f.env = env
+}
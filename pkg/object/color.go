@@ -38,6 +38,10 @@ func (c *Color) GetAttr(name string) (Object, bool) {
 	return colorMethods.GetAttr(c, name)
 }
 
+func (c *Color) CallMethod(ctx context.Context, name string, args []Object) (Object, bool, error) {
+	return colorMethods.CallMethod(ctx, c, name, args)
+}
+
 func (c *Color) SetAttr(name string, value Object) error {
 	return TypeErrorf("color object has no attribute %q", name)
 }
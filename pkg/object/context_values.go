@@ -2,6 +2,7 @@ package object
 
 import (
 	"context"
+	"log/slog"
 )
 
 type contextKey string
@@ -36,3 +37,87 @@ func GetCallFunc(ctx context.Context) (CallFunc, bool) {
 	}
 	return nil, false
 }
+
+////////////////////////////////////////////////////////////////////////////////
+
+// LogRecord is a structured log record emitted by a script through the log
+// module.
+type LogRecord struct {
+	Level   slog.Level
+	Message string
+	Fields  map[string]any
+}
+
+// LogHandler receives log records emitted by scripts. The context is the one
+// the script is running with, so handlers can read values such as trace
+// spans that the host attached to it. Returning an error raises it in the
+// script.
+type LogHandler func(ctx context.Context, record LogRecord) error
+
+const logHandlerKey = contextKey("risor:log")
+
+// WithLogHandler stores a LogHandler in the context. Called by the VM when a
+// handler is configured with vm.WithLogHandler.
+func WithLogHandler(ctx context.Context, fn LogHandler) context.Context {
+	return context.WithValue(ctx, logHandlerKey, fn)
+}
+
+// GetLogHandler retrieves the LogHandler from the context.
+func GetLogHandler(ctx context.Context) (LogHandler, bool) {
+	if fn, ok := ctx.Value(logHandlerKey).(LogHandler); ok {
+		if fn != nil {
+			return fn, ok
+		}
+	}
+	return nil, false
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+// EvalFunc compiles and runs source code with the given globals, returning
+// its result. The VM provides its implementation via WithEvalFunc, and the
+// eval() builtin retrieves it.
+type EvalFunc func(ctx context.Context, source string, globals map[string]Object) (Object, error)
+
+const evalFuncKey = contextKey("risor:eval")
+
+// WithEvalFunc stores an EvalFunc in the context. Called by the VM during
+// initialization.
+func WithEvalFunc(ctx context.Context, fn EvalFunc) context.Context {
+	return context.WithValue(ctx, evalFuncKey, fn)
+}
+
+// GetEvalFunc retrieves the EvalFunc from the context.
+func GetEvalFunc(ctx context.Context) (EvalFunc, bool) {
+	if fn, ok := ctx.Value(evalFuncKey).(EvalFunc); ok {
+		if fn != nil {
+			return fn, ok
+		}
+	}
+	return nil, false
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+// EventRegisterFunc registers a handler for a named event. The VM provides
+// its implementation via WithEventRegisterFunc, and the on() builtin
+// retrieves it to subscribe script functions to events emitted by the host.
+type EventRegisterFunc func(event string, handler Callable)
+
+const eventRegisterKey = contextKey("risor:on")
+
+// WithEventRegisterFunc stores an EventRegisterFunc in the context. Called by
+// the VM during initialization.
+func WithEventRegisterFunc(ctx context.Context, fn EventRegisterFunc) context.Context {
+	return context.WithValue(ctx, eventRegisterKey, fn)
+}
+
+// GetEventRegisterFunc retrieves the EventRegisterFunc from the context.
+func GetEventRegisterFunc(ctx context.Context) (EventRegisterFunc, bool) {
+	if fn, ok := ctx.Value(eventRegisterKey).(EventRegisterFunc); ok {
+		if fn != nil {
+			return fn, ok
+		}
+	}
+	return nil, false
+}
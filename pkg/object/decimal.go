@@ -0,0 +1,442 @@
+package object
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"math"
+	"math/big"
+	"strconv"
+	"strings"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/op"
+)
+
+// DecimalDivisionScale is the number of digits kept after the decimal point
+// when a division doesn't terminate, e.g. decimal("1") / decimal("3").
+const DecimalDivisionScale = 28
+
+// maxDecimalExponent limits the exponent accepted by ParseDecimal, so that a
+// short string can't describe a number with millions of digits.
+const maxDecimalExponent = 10000
+
+// maxDecimalBits limits the size of the coefficient of a product or power,
+// about 78,000 decimal digits, so repeated squaring can't exhaust memory.
+// The scale of a product or power is limited to maxDecimalExponent.
+const maxDecimalBits = 1 << 18
+
+var decimalMethods = NewMethodRegistry[*Decimal]("decimal")
+
+func init() {
+	decimalMethods.Define("round").
+		Doc("Round to a number of decimal places, rounding half to even").
+		OptionalArg("places").
+		Returns("decimal").
+		Impl((*Decimal).roundMethod)
+
+	decimalMethods.Define("scale").
+		Doc("Get the number of digits after the decimal point").
+		Returns("int").
+		Impl(func(d *Decimal, ctx context.Context, args ...Object) (Object, error) {
+			return NewInt(int64(d.scale)), nil
+		})
+}
+
+// Decimal is an exact base 10 number of arbitrary precision, for uses such as
+// money where float rounding is unacceptable. Its value is coef / 10^scale.
+// The scale is kept through arithmetic, so decimal("1.10") prints as "1.10".
+type Decimal struct {
+	coef  *big.Int
+	scale int32
+}
+
+func (d *Decimal) Attrs() []AttrSpec {
+	return decimalMethods.Specs()
+}
+
+func (d *Decimal) GetAttr(name string) (Object, bool) {
+	return decimalMethods.GetAttr(d, name)
+}
+
+func (d *Decimal) CallMethod(ctx context.Context, name string, args []Object) (Object, bool, error) {
+	return decimalMethods.CallMethod(ctx, d, name, args)
+}
+
+func (d *Decimal) SetAttr(name string, value Object) error {
+	return TypeErrorf("decimal has no attribute %q", name)
+}
+
+func (d *Decimal) Inspect() string {
+	return fmt.Sprintf("decimal(%q)", d.String())
+}
+
+func (d *Decimal) Type() Type {
+	return DECIMAL
+}
+
+// Interface returns the decimal as a json.Number, which holds its exact
+// digits and encodes as a JSON number.
+func (d *Decimal) Interface() interface{} {
+	return json.Number(d.String())
+}
+
+// String returns the decimal in plain notation with all of its digits, e.g.
+// "-12.50".
+func (d *Decimal) String() string {
+	digits := new(big.Int).Abs(d.coef).String()
+	if d.scale > 0 {
+		if pad := int(d.scale) + 1 - len(digits); pad > 0 {
+			digits = strings.Repeat("0", pad) + digits
+		}
+		point := len(digits) - int(d.scale)
+		digits = digits[:point] + "." + digits[point:]
+	}
+	if d.coef.Sign() < 0 {
+		return "-" + digits
+	}
+	return digits
+}
+
+// Scale returns the number of digits after the decimal point.
+func (d *Decimal) Scale() int32 {
+	return d.scale
+}
+
+// Rat returns the exact value of the decimal as a rational number.
+func (d *Decimal) Rat() *big.Rat {
+	return new(big.Rat).SetFrac(d.coef, pow10(d.scale))
+}
+
+// Float64 returns the float nearest to the decimal.
+func (d *Decimal) Float64() float64 {
+	f, _ := d.Rat().Float64()
+	return f
+}
+
+// Int64 returns the integer part of the decimal, truncating toward zero. It
+// returns false if that doesn't fit in an int64.
+func (d *Decimal) Int64() (int64, bool) {
+	i := new(big.Int).Quo(d.coef, pow10(d.scale))
+	return i.Int64(), i.IsInt64()
+}
+
+// Neg returns the decimal with its sign flipped.
+func (d *Decimal) Neg() *Decimal {
+	return &Decimal{coef: new(big.Int).Neg(d.coef), scale: d.scale}
+}
+
+// Round returns the decimal rounded to the given number of places after the
+// decimal point, with ties rounded to the even neighbor. Decimals that
+// already have no more places are returned unchanged.
+func (d *Decimal) Round(places int32) *Decimal {
+	if places < 0 {
+		places = 0
+	}
+	if d.scale <= places {
+		return d
+	}
+	divisor := pow10(d.scale - places)
+	q, r := new(big.Int).QuoRem(d.coef, divisor, new(big.Int))
+	roundHalfEven(q, r, divisor, d.coef.Sign())
+	return &Decimal{coef: q, scale: places}
+}
+
+func (d *Decimal) roundMethod(ctx context.Context, args ...Object) (Object, error) {
+	var places int64
+	if len(args) > 0 {
+		var err error
+		if places, err = AsInt(args[0]); err != nil {
+			return nil, err
+		}
+		if places < 0 || places > maxDecimalExponent {
+			return nil, ValueErrorf("decimal.round: places must be between 0 and %d (got %d)",
+				maxDecimalExponent, places)
+		}
+	}
+	return d.Round(int32(places)), nil
+}
+
+// Compare compares the decimal to another number. Floats are compared by
+// their exact value; NaN is ordered before all other numbers, as in
+// Float.Compare.
+func (d *Decimal) Compare(other Object) (int, error) {
+	switch other := other.(type) {
+	case *Decimal:
+		a, b := alignDecimals(d, other)
+		return a.Cmp(b), nil
+	case *Int:
+		return d.Compare(NewDecimalFromInt(other.value))
+	case *Byte:
+		return d.Compare(NewDecimalFromInt(int64(other.value)))
+	case *Float:
+		switch {
+		case math.IsNaN(other.value), math.IsInf(other.value, -1):
+			return 1, nil
+		case math.IsInf(other.value, 1):
+			return -1, nil
+		}
+		return d.Rat().Cmp(new(big.Rat).SetFloat64(other.value)), nil
+	default:
+		return 0, TypeErrorf("unable to compare decimal and %s", other.Type())
+	}
+}
+
+// HashKey returns the key shared with equal ints and floats, so that
+// decimal("2.0"), 2, and 2.0 are the same key. Other values hash by their
+// digits, ignoring trailing zeros.
+func (d *Decimal) HashKey() HashKey {
+	trimmed := d.trim(0)
+	if trimmed.scale == 0 && trimmed.coef.IsInt64() {
+		return HashKey{Type: INT, Value: trimmed.coef.Int64()}
+	}
+	if f, exact := d.Rat().Float64(); exact {
+		return numericHashKey(f)
+	}
+	return HashKey{Type: DECIMAL, Value: trimmed.String()}
+}
+
+func (d *Decimal) Equals(other Object) bool {
+	switch other.(type) {
+	case *Decimal, *Int, *Byte, *Float:
+		result, err := d.Compare(other)
+		return err == nil && result == 0
+	}
+	return false
+}
+
+func (d *Decimal) IsTruthy() bool {
+	return d.coef.Sign() != 0
+}
+
+// RunOperation performs arithmetic with another decimal or an int or byte,
+// which is converted exactly. Arithmetic with floats is a type error, since
+// the float may already have been rounded; convert with decimal() or float()
+// to choose which rounding applies.
+func (d *Decimal) RunOperation(opType op.BinaryOpType, right Object) (Object, error) {
+	switch right := right.(type) {
+	case *Decimal:
+		return d.runOperationDecimal(opType, right)
+	case *Int:
+		return d.runOperationDecimal(opType, NewDecimalFromInt(right.value))
+	case *Byte:
+		return d.runOperationDecimal(opType, NewDecimalFromInt(int64(right.value)))
+	case *Float:
+		return nil, newTypeErrorf("unsupported operation for decimal: %v on type float "+
+			"(convert with decimal() or float() first)", opType)
+	default:
+		return nil, newTypeErrorf("unsupported operation for decimal: %v on type %s", opType, right.Type())
+	}
+}
+
+func (d *Decimal) runOperationDecimal(opType op.BinaryOpType, right *Decimal) (Object, error) {
+	switch opType {
+	case op.Add:
+		a, b := alignDecimals(d, right)
+		return &Decimal{coef: a.Add(a, b), scale: max(d.scale, right.scale)}, nil
+	case op.Subtract:
+		a, b := alignDecimals(d, right)
+		return &Decimal{coef: a.Sub(a, b), scale: max(d.scale, right.scale)}, nil
+	case op.Multiply:
+		scale := int64(d.scale) + int64(right.scale)
+		bits := int64(d.coef.BitLen()) + int64(right.coef.BitLen())
+		if err := checkDecimalSize(scale, bits); err != nil {
+			return nil, err
+		}
+		return &Decimal{coef: new(big.Int).Mul(d.coef, right.coef), scale: int32(scale)}, nil
+	case op.Divide:
+		if right.coef.Sign() == 0 {
+			return nil, newValueErrorf("division by zero")
+		}
+		return divideDecimals(d, right), nil
+	case op.Modulo:
+		if right.coef.Sign() == 0 {
+			return nil, newValueErrorf("division by zero")
+		}
+		a, b := alignDecimals(d, right)
+		return &Decimal{coef: a.Rem(a, b), scale: max(d.scale, right.scale)}, nil
+	case op.Power:
+		return d.pow(right)
+	default:
+		return nil, newTypeErrorf("unsupported operation for decimal: %v on type decimal", opType)
+	}
+}
+
+// pow raises the decimal to an integer power.
+func (d *Decimal) pow(exponent *Decimal) (Object, error) {
+	n, ok := exponent.Int64()
+	if cmp, _ := exponent.Compare(NewInt(n)); !ok || cmp != 0 {
+		return nil, newValueErrorf("decimal exponent must be an integer (got %s)", exponent)
+	}
+	if n > maxDecimalExponent || n < -maxDecimalExponent {
+		return nil, newValueErrorf("decimal exponent out of range (got %d)", n)
+	}
+	if n < 0 {
+		if d.coef.Sign() == 0 {
+			return nil, newValueErrorf("division by zero")
+		}
+		result, err := d.powInt(-n)
+		if err != nil {
+			return nil, err
+		}
+		return divideDecimals(NewDecimalFromInt(1), result), nil
+	}
+	return d.powInt(n)
+}
+
+// powInt raises the decimal to a power between 0 and maxDecimalExponent.
+func (d *Decimal) powInt(n int64) (*Decimal, error) {
+	if err := checkDecimalSize(int64(d.scale)*n, int64(d.coef.BitLen())*n); err != nil {
+		return nil, err
+	}
+	coef := new(big.Int).Exp(d.coef, big.NewInt(n), nil)
+	return &Decimal{coef: coef, scale: d.scale * int32(n)}, nil
+}
+
+// checkDecimalSize returns an error if a product or power with the given
+// scale and coefficient size in bits would exceed the decimal limits. The
+// size is an upper bound computed from the operands.
+func checkDecimalSize(scale, bits int64) error {
+	if scale > maxDecimalExponent {
+		return newValueErrorf("decimal result has too many digits after the decimal point (limit %d)",
+			maxDecimalExponent)
+	}
+	if bits > maxDecimalBits {
+		return newValueErrorf("decimal result is too large")
+	}
+	return nil
+}
+
+// trim removes trailing zeros after the decimal point, keeping at least
+// minScale digits after it.
+func (d *Decimal) trim(minScale int32) *Decimal {
+	coef, scale := new(big.Int).Set(d.coef), d.scale
+	ten := big.NewInt(10)
+	q, r := new(big.Int), new(big.Int)
+	for scale > minScale {
+		q.QuoRem(coef, ten, r)
+		if r.Sign() != 0 {
+			break
+		}
+		coef, q = q, coef
+		scale--
+	}
+	return &Decimal{coef: coef, scale: scale}
+}
+
+func (d *Decimal) MarshalJSON() ([]byte, error) {
+	return []byte(d.String()), nil
+}
+
+// NewDecimal returns the decimal coef / 10^scale.
+func NewDecimal(coef *big.Int, scale int32) *Decimal {
+	if scale < 0 {
+		return &Decimal{coef: new(big.Int).Mul(coef, pow10(-scale))}
+	}
+	return &Decimal{coef: new(big.Int).Set(coef), scale: scale}
+}
+
+// NewDecimalFromInt returns a decimal with the value of an integer.
+func NewDecimalFromInt(value int64) *Decimal {
+	return &Decimal{coef: big.NewInt(value)}
+}
+
+// NewDecimalFromFloat returns the decimal with the fewest digits that
+// converts back to the given float, so 0.1 becomes decimal("0.1") rather than
+// the float's exact binary value. NaN and infinities are rejected.
+func NewDecimalFromFloat(value float64) (*Decimal, error) {
+	if math.IsNaN(value) || math.IsInf(value, 0) {
+		return nil, fmt.Errorf("cannot convert %s to decimal", FormatFloat(value))
+	}
+	return ParseDecimal(strconv.FormatFloat(value, 'f', -1, 64))
+}
+
+// ParseDecimal parses a decimal from a string such as "12.50", "-3", or
+// "1.5e-3". The scale is the number of digits written after the decimal
+// point, adjusted by any exponent.
+func ParseDecimal(s string) (*Decimal, error) {
+	mantissa, exponent := s, int64(0)
+	if i := strings.IndexAny(s, "eE"); i >= 0 {
+		var err error
+		mantissa = s[:i]
+		exponent, err = strconv.ParseInt(s[i+1:], 10, 64)
+		if err != nil || exponent > maxDecimalExponent || exponent < -maxDecimalExponent {
+			return nil, fmt.Errorf("invalid decimal: %q", s)
+		}
+	}
+	sign := ""
+	if len(mantissa) > 0 && (mantissa[0] == '-' || mantissa[0] == '+') {
+		sign, mantissa = mantissa[:1], mantissa[1:]
+	}
+	whole, frac, _ := strings.Cut(mantissa, ".")
+	if (whole == "" && frac == "") || !isDigits(whole) || !isDigits(frac) {
+		return nil, fmt.Errorf("invalid decimal: %q", s)
+	}
+	coef, ok := new(big.Int).SetString(sign+whole+frac, 10)
+	if !ok {
+		return nil, fmt.Errorf("invalid decimal: %q", s)
+	}
+	scale := int64(len(frac)) - exponent
+	if scale > math.MaxInt32 {
+		return nil, fmt.Errorf("invalid decimal: %q", s)
+	}
+	return NewDecimal(coef, int32(scale)), nil
+}
+
+// isDigits reports whether s contains only ASCII digits.
+func isDigits(s string) bool {
+	for i := 0; i < len(s); i++ {
+		if s[i] < '0' || s[i] > '9' {
+			return false
+		}
+	}
+	return true
+}
+
+// pow10 returns 10^n.
+func pow10(n int32) *big.Int {
+	return new(big.Int).Exp(big.NewInt(10), big.NewInt(int64(n)), nil)
+}
+
+// alignDecimals returns the coefficients of a and b scaled to the larger of
+// their two scales. The results are new values that may be modified.
+func alignDecimals(a, b *Decimal) (*big.Int, *big.Int) {
+	x, y := new(big.Int).Set(a.coef), new(big.Int).Set(b.coef)
+	if a.scale < b.scale {
+		x.Mul(x, pow10(b.scale-a.scale))
+	} else if b.scale < a.scale {
+		y.Mul(y, pow10(a.scale-b.scale))
+	}
+	return x, y
+}
+
+// divideDecimals returns a / b rounded half to even at DecimalDivisionScale
+// places, or at the larger scale of a and b if that is greater. Trailing
+// zeros are removed down to the larger scale of a and b, so exact quotients
+// such as 10.00 / 4 = 2.50 aren't padded.
+func divideDecimals(a, b *Decimal) *Decimal {
+	natural := max(a.scale, b.scale)
+	scale := max(natural, DecimalDivisionScale)
+	num := new(big.Int).Mul(a.coef, pow10(scale-a.scale+b.scale))
+	q, r := new(big.Int).QuoRem(num, b.coef, new(big.Int))
+	roundHalfEven(q, r, b.coef, num.Sign()*b.coef.Sign())
+	return (&Decimal{coef: q, scale: scale}).trim(natural)
+}
+
+// roundHalfEven adjusts the truncated quotient q, given the remainder r of
+// dividing by divisor, to the nearest integer, with ties going to the even
+// neighbor. The sign is that of the exact quotient.
+func roundHalfEven(q, r, divisor *big.Int, sign int) {
+	if r.Sign() == 0 {
+		return
+	}
+	twice := new(big.Int).Abs(r)
+	twice.Lsh(twice, 1)
+	c := twice.Cmp(new(big.Int).Abs(divisor))
+	if c > 0 || (c == 0 && q.Bit(0) == 1) {
+		if sign < 0 {
+			q.Sub(q, big.NewInt(1))
+		} else {
+			q.Add(q, big.NewInt(1))
+		}
+	}
+}
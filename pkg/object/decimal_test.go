@@ -0,0 +1,228 @@
+package object
+
+import (
+	"context"
+	"encoding/json"
+	"math"
+	"testing"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/op"
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+func mustDecimal(t *testing.T, s string) *Decimal {
+	t.Helper()
+	d, err := ParseDecimal(s)
+	assert.Nil(t, err)
+	return d
+}
+
+func TestDecimalBasics(t *testing.T) {
+	d := mustDecimal(t, "1.10")
+	assert.Equal(t, d.Type(), DECIMAL)
+	assert.Equal(t, d.String(), "1.10")
+	assert.Equal(t, d.Inspect(), `decimal("1.10")`)
+	assert.Equal(t, d.Interface(), json.Number("1.10"))
+	assert.Equal(t, d.Scale(), int32(2))
+	assert.Equal(t, d.Float64(), 1.1)
+	assert.True(t, d.IsTruthy())
+	assert.False(t, mustDecimal(t, "0.00").IsTruthy())
+
+	data, err := json.Marshal(NewList([]Object{d}))
+	assert.Nil(t, err)
+	assert.Equal(t, string(data), "[1.10]")
+}
+
+func TestParseDecimal(t *testing.T) {
+	tests := []struct {
+		input    string
+		expected string
+	}{
+		{"0", "0"},
+		{"-0", "0"},
+		{"1.10", "1.10"},
+		{"+3", "3"},
+		{"-0.001", "-0.001"},
+		{".5", "0.5"},
+		{"1.", "1"},
+		{"1.5e-3", "0.0015"},
+		{"1.5E3", "1500"},
+		{"12345678901234567890.123456789", "12345678901234567890.123456789"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.input, func(t *testing.T) {
+			assert.Equal(t, mustDecimal(t, tt.input).String(), tt.expected)
+		})
+	}
+	for _, input := range []string{"", ".", "+", "abc", "1.2.3", "--1", "1e", "1e99999", " 1", "1_000"} {
+		_, err := ParseDecimal(input)
+		assert.NotNil(t, err, input)
+	}
+}
+
+func TestDecimalFromFloat(t *testing.T) {
+	d, err := NewDecimalFromFloat(0.1)
+	assert.Nil(t, err)
+	assert.Equal(t, d.String(), "0.1")
+
+	_, err = NewDecimalFromFloat(math.NaN())
+	assert.NotNil(t, err)
+	_, err = NewDecimalFromFloat(math.Inf(1))
+	assert.NotNil(t, err)
+}
+
+func TestDecimalArithmetic(t *testing.T) {
+	tests := []struct {
+		left     string
+		opType   op.BinaryOpType
+		right    Object
+		expected string
+	}{
+		{"0.1", op.Add, NewInt(0), "0.1"},
+		{"1.10", op.Add, NewInt(2), "3.10"},
+		{"5", op.Subtract, NewByte(1), "4"},
+		{"1.5", op.Multiply, NewInt(3), "4.5"},
+		{"19.99", op.Multiply, NewInt(3), "59.97"},
+		{"1", op.Divide, NewInt(3), "0.3333333333333333333333333333"},
+		{"2", op.Divide, NewInt(3), "0.6666666666666666666666666667"},
+		{"-2", op.Divide, NewInt(3), "-0.6666666666666666666666666667"},
+		{"1", op.Divide, NewInt(8), "0.125"},
+		{"10.00", op.Divide, NewInt(4), "2.50"},
+		{"7", op.Modulo, NewInt(2), "1"},
+		{"-7", op.Modulo, NewInt(2), "-1"},
+		{"1.1", op.Power, NewInt(2), "1.21"},
+		{"2", op.Power, NewInt(-2), "0.25"},
+	}
+	for _, tt := range tests {
+		result, err := mustDecimal(t, tt.left).RunOperation(tt.opType, tt.right)
+		assert.Nil(t, err)
+		assert.Equal(t, result.(*Decimal).String(), tt.expected, "%s %s %s", tt.left, tt.opType, tt.right.Inspect())
+	}
+
+	sum, err := mustDecimal(t, "0.1").RunOperation(op.Add, mustDecimal(t, "0.2"))
+	assert.Nil(t, err)
+	assert.True(t, sum.Equals(mustDecimal(t, "0.3")))
+
+	rem, err := mustDecimal(t, "7").RunOperation(op.Modulo, mustDecimal(t, "2.5"))
+	assert.Nil(t, err)
+	assert.Equal(t, rem.(*Decimal).String(), "2.0")
+
+	// Ints and bytes on the left produce decimals too
+	result, err := NewInt(2).RunOperation(op.Subtract, mustDecimal(t, "0.5"))
+	assert.Nil(t, err)
+	assert.Equal(t, result.(*Decimal).String(), "1.5")
+	result, err = NewByte(2).RunOperation(op.Multiply, mustDecimal(t, "0.5"))
+	assert.Nil(t, err)
+	assert.Equal(t, result.(*Decimal).String(), "1.0")
+}
+
+func TestDecimalArithmeticErrors(t *testing.T) {
+	d := mustDecimal(t, "1.5")
+	_, err := d.RunOperation(op.Add, NewFloat(1))
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "convert with decimal() or float() first")
+	_, err = NewFloat(1).RunOperation(op.Add, d)
+	assert.NotNil(t, err)
+
+	_, err = d.RunOperation(op.Divide, NewInt(0))
+	assert.Equal(t, err.Error(), "value error: division by zero")
+	_, err = d.RunOperation(op.Modulo, mustDecimal(t, "0.0"))
+	assert.NotNil(t, err)
+	_, err = d.RunOperation(op.Power, d)
+	assert.NotNil(t, err)
+	_, err = d.RunOperation(op.LShift, NewInt(1))
+	assert.NotNil(t, err)
+
+	// Results are limited in size before they are computed
+	_, err = mustDecimal(t, "1.5").RunOperation(op.Power, NewInt(10000))
+	assert.Nil(t, err)
+	_, err = mustDecimal(t, "1.05").RunOperation(op.Power, NewInt(10000))
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "too many digits after the decimal point")
+	_, err = mustDecimal(t, "1.05").RunOperation(op.Power, NewInt(-10000))
+	assert.NotNil(t, err)
+	_, err = mustDecimal(t, "1e10000").RunOperation(op.Power, NewInt(100))
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "decimal result is too large")
+	small := mustDecimal(t, "1e-6000")
+	_, err = small.RunOperation(op.Multiply, small)
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "too many digits after the decimal point")
+	var square Object = mustDecimal(t, "1e9000")
+	for range 4 {
+		if square, err = square.(*Decimal).RunOperation(op.Multiply, square); err != nil {
+			break
+		}
+	}
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "decimal result is too large")
+}
+
+func TestDecimalRound(t *testing.T) {
+	tests := []struct {
+		input    string
+		places   int32
+		expected string
+	}{
+		{"2.345", 2, "2.34"},
+		{"2.355", 2, "2.36"},
+		{"2.3451", 2, "2.35"},
+		{"-2.5", 0, "-2"},
+		{"-3.5", 0, "-4"},
+		{"1.5", 3, "1.5"},
+	}
+	for _, tt := range tests {
+		assert.Equal(t, mustDecimal(t, tt.input).Round(tt.places).String(), tt.expected)
+	}
+
+	ctx := context.Background()
+	result, ok, err := mustDecimal(t, "9.995").CallMethod(ctx, "round", []Object{NewInt(2)})
+	assert.True(t, ok)
+	assert.Nil(t, err)
+	assert.Equal(t, result.(*Decimal).String(), "10.00")
+	_, _, err = mustDecimal(t, "1").CallMethod(ctx, "round", []Object{NewInt(-1)})
+	assert.NotNil(t, err)
+}
+
+func TestDecimalCompare(t *testing.T) {
+	half := mustDecimal(t, "0.50")
+	assert.True(t, half.Equals(NewFloat(0.5)))
+	assert.True(t, NewFloat(0.5).Equals(half))
+	assert.True(t, half.Equals(mustDecimal(t, "0.5")))
+	assert.False(t, mustDecimal(t, "0.1").Equals(NewFloat(0.1)))
+	assert.True(t, mustDecimal(t, "2.00").Equals(NewInt(2)))
+	assert.True(t, NewInt(2).Equals(mustDecimal(t, "2.00")))
+	assert.True(t, NewByte(2).Equals(mustDecimal(t, "2")))
+	assert.False(t, half.Equals(NewString("0.5")))
+
+	for _, tt := range []struct {
+		left, right Object
+		expected    int
+	}{
+		{half, NewInt(1), -1},
+		{NewInt(1), half, 1},
+		{NewByte(0), half, -1},
+		{NewFloat(0.75), half, 1},
+		{half, NewFloat(math.Inf(1)), -1},
+		{half, NewFloat(math.NaN()), 1},
+	} {
+		result, err := tt.left.(Comparable).Compare(tt.right)
+		assert.Nil(t, err)
+		assert.Equal(t, result, tt.expected)
+	}
+	_, err := half.Compare(NewString("x"))
+	assert.NotNil(t, err)
+}
+
+func TestDecimalHash(t *testing.T) {
+	hash := func(obj Object) HashKey {
+		t.Helper()
+		key, err := Hash(obj)
+		assert.Nil(t, err)
+		return key
+	}
+	assert.Equal(t, hash(mustDecimal(t, "2.00")), hash(NewInt(2)))
+	assert.Equal(t, hash(mustDecimal(t, "2.5")), hash(NewFloat(2.5)))
+	assert.Equal(t, hash(mustDecimal(t, "0.10")), hash(mustDecimal(t, "0.1")))
+	assert.True(t, hash(mustDecimal(t, "0.1")) != hash(NewFloat(0.1)))
+}
@@ -0,0 +1,86 @@
+package object
+
+import (
+	"context"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/op"
+)
+
+// Names of the methods a map may define to overload operators. Each method
+// receives the map itself as its first argument, followed by the other
+// operand, since Risor functions have no implicit receiver:
+//
+//	let money = {amount: 5, __add__: (self, other) => ...}
+const (
+	MethodAdd    = "__add__"
+	MethodSub    = "__sub__"
+	MethodMul    = "__mul__"
+	MethodDiv    = "__div__"
+	MethodMod    = "__mod__"
+	MethodPow    = "__pow__"
+	MethodXor    = "__xor__"
+	MethodLShift = "__lshift__"
+	MethodRShift = "__rshift__"
+	MethodAnd    = "__and__"
+	MethodOr     = "__or__"
+	MethodEq     = "__eq__"
+	MethodLt     = "__lt__"
+	MethodStr    = "__str__"
+	MethodCall   = "__call__"
+)
+
+var binaryOpMethods = map[op.BinaryOpType]string{
+	op.Add:        MethodAdd,
+	op.Subtract:   MethodSub,
+	op.Multiply:   MethodMul,
+	op.Divide:     MethodDiv,
+	op.Modulo:     MethodMod,
+	op.Power:      MethodPow,
+	op.Xor:        MethodXor,
+	op.LShift:     MethodLShift,
+	op.RShift:     MethodRShift,
+	op.BitwiseAnd: MethodAnd,
+	op.BitwiseOr:  MethodOr,
+}
+
+// BinaryOpMethod returns the name of the method that overloads the given
+// binary operator. The logical operators && and || cannot be overloaded.
+func BinaryOpMethod(opType op.BinaryOpType) (string, bool) {
+	name, ok := binaryOpMethods[opType]
+	return name, ok
+}
+
+// LookupMethod returns the callable stored under the given method name when
+// obj is a map that defines it. Map methods such as keys() are not consulted,
+// so only values stored in the map can overload operators.
+func LookupMethod(obj Object, name string) (Callable, bool) {
+	m, ok := obj.(*Map)
+	if !ok {
+		return nil, false
+	}
+	value, found := m.items[name]
+	if !found {
+		return nil, false
+	}
+	callable, ok := value.(Callable)
+	return callable, ok
+}
+
+// CallStr converts obj to a string using its __str__ method. The second
+// return value is false if obj does not define __str__. The method must
+// return a string.
+func CallStr(ctx context.Context, obj Object) (string, bool, error) {
+	method, ok := LookupMethod(obj, MethodStr)
+	if !ok {
+		return "", false, nil
+	}
+	result, err := method.Call(ctx, obj)
+	if err != nil {
+		return "", true, err
+	}
+	s, ok := result.(*String)
+	if !ok {
+		return "", true, TypeErrorf("%s must return a string (got %s)", MethodStr, result.Type())
+	}
+	return s.value, true, nil
+}
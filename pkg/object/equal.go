@@ -0,0 +1,157 @@
+package object
+
+// equalCycleDepth is the nesting depth at which equality checks start
+// tracking the pairs of containers they visit. Shallower comparisons can't be
+// caught in a cycle, so they skip the bookkeeping.
+const equalCycleDepth = 64
+
+// DeepEqual reports whether a and b are equal, as by the == operator. Lists
+// and tuples are equal if their items are equal in order, and maps if they
+// have the same keys with equal values. Numbers of different types are equal
+// if they have the same value, so [1] equals [1.0]. Other objects are compared
+// with their Equals method. Lists and maps that contain themselves are
+// handled.
+func DeepEqual(a, b Object) bool {
+	c := equalityCheck{}
+	return c.equal(a, b, 0)
+}
+
+// StrictEqual is like DeepEqual, but objects must also have the same type at
+// every level, so 1 and 1.0 are not strictly equal, and neither are [1] and
+// [1.0].
+func StrictEqual(a, b Object) bool {
+	c := equalityCheck{strict: true}
+	return c.equal(a, b, 0)
+}
+
+type equalPair struct {
+	a, b Object
+}
+
+type equalityCheck struct {
+	strict bool
+	seen   map[equalPair]bool
+}
+
+func (c *equalityCheck) equal(a, b Object, depth int) bool {
+	if c.strict && a.Type() != b.Type() {
+		return false
+	}
+	switch a := a.(type) {
+	case *List:
+		other, ok := b.(*List)
+		if !ok {
+			return false
+		}
+		if a == other || c.revisit(a, other, depth) {
+			return true
+		}
+		return c.equalItems(a.items, other.items, depth)
+	case *Tuple:
+		other, ok := b.(*Tuple)
+		if !ok {
+			return false
+		}
+		return c.equalItems(a.items, other.items, depth)
+	case *Map:
+		other, ok := b.(*Map)
+		if !ok {
+			return false
+		}
+		if len(a.items) != len(other.items) {
+			return false
+		}
+		if a == other || c.revisit(a, other, depth) {
+			return true
+		}
+		for k, v := range a.items {
+			otherValue, found := other.items[k]
+			if !found || !c.equal(v, otherValue, depth+1) {
+				return false
+			}
+		}
+		return true
+	}
+	return a.Equals(b)
+}
+
+func (c *equalityCheck) equalItems(a, b []Object, depth int) bool {
+	if len(a) != len(b) {
+		return false
+	}
+	for i, v := range a {
+		if !c.equal(v, b[i], depth+1) {
+			return false
+		}
+	}
+	return true
+}
+
+// revisit reports whether the containers a and b are already being compared
+// further up, in which case they are assumed to be equal unless something
+// else differs. Pairs are only recorded once the comparison is deep enough
+// that it may be in a cycle.
+func (c *equalityCheck) revisit(a, b Object, depth int) bool {
+	if depth < equalCycleDepth {
+		return false
+	}
+	pair := equalPair{a, b}
+	if c.seen[pair] {
+		return true
+	}
+	if c.seen == nil {
+		c.seen = map[equalPair]bool{}
+	}
+	c.seen[pair] = true
+	return false
+}
+
+// DeepCopy returns a copy of obj in which lists, maps, tuples, and bytes are
+// copied at every level, so that no mutable value is shared with the
+// original. Other objects, which are immutable or can't be copied, such as
+// functions and modules, are shared. Lists and maps that contain themselves
+// are copied with the same structure.
+func DeepCopy(obj Object) Object {
+	return deepCopy(obj, map[Object]Object{})
+}
+
+func deepCopy(obj Object, copies map[Object]Object) Object {
+	switch obj := obj.(type) {
+	case *List:
+		if c, ok := copies[obj]; ok {
+			return c
+		}
+		items := make([]Object, len(obj.items))
+		result := NewList(items)
+		copies[obj] = result
+		for i, item := range obj.items {
+			items[i] = deepCopy(item, copies)
+		}
+		return result
+	case *Tuple:
+		if c, ok := copies[obj]; ok {
+			return c
+		}
+		items := make([]Object, len(obj.items))
+		result := NewTuple(items)
+		copies[obj] = result
+		for i, item := range obj.items {
+			items[i] = deepCopy(item, copies)
+		}
+		return result
+	case *Map:
+		if c, ok := copies[obj]; ok {
+			return c
+		}
+		items := make(map[string]Object, len(obj.items))
+		result := NewMap(items)
+		copies[obj] = result
+		for k, v := range obj.items {
+			items[k] = deepCopy(v, copies)
+		}
+		return result
+	case *Bytes:
+		return obj.Clone()
+	}
+	return obj
+}
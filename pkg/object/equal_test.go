@@ -0,0 +1,83 @@
+package object
+
+import (
+	"testing"
+
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+func TestDeepEqual(t *testing.T) {
+	nested := func(value Object) Object {
+		return NewMap(map[string]Object{
+			"items": NewList([]Object{NewInt(1), NewTuple([]Object{value})}),
+		})
+	}
+	assert.True(t, DeepEqual(nested(NewInt(2)), nested(NewInt(2))))
+	assert.True(t, DeepEqual(nested(NewInt(2)), nested(NewFloat(2))))
+	assert.False(t, DeepEqual(nested(NewInt(2)), nested(NewInt(3))))
+	assert.False(t, DeepEqual(NewList([]Object{NewInt(1)}), NewTuple([]Object{NewInt(1)})))
+	assert.False(t, DeepEqual(NewMap(map[string]Object{"a": Nil}), NewMap(map[string]Object{"b": Nil})))
+
+	// The == operator uses the same rules
+	assert.True(t, nested(NewInt(2)).Equals(nested(NewByte(2))))
+}
+
+func TestStrictEqual(t *testing.T) {
+	list := func(items ...Object) *List { return NewList(items) }
+	assert.True(t, StrictEqual(list(NewInt(1), NewString("a")), list(NewInt(1), NewString("a"))))
+	assert.False(t, StrictEqual(NewInt(1), NewFloat(1)))
+	assert.False(t, StrictEqual(list(NewInt(1)), list(NewFloat(1))))
+	assert.False(t, StrictEqual(NewBytes([]byte("a")), NewString("a")))
+	assert.True(t, StrictEqual(Nil, Nil))
+}
+
+func TestDeepEqualCycles(t *testing.T) {
+	a := NewList([]Object{NewInt(1)})
+	a.Append(a)
+	b := NewList([]Object{NewInt(1)})
+	b.Append(b)
+	assert.True(t, DeepEqual(a, b))
+	assert.True(t, a.Equals(b))
+
+	c := NewList([]Object{NewInt(2)})
+	c.Append(c)
+	assert.False(t, DeepEqual(a, c))
+
+	m1 := NewMap(nil)
+	m1.Set("self", m1)
+	m2 := NewMap(nil)
+	m2.Set("self", m2)
+	assert.True(t, StrictEqual(m1, m2))
+}
+
+func TestDeepCopy(t *testing.T) {
+	inner := NewList([]Object{NewInt(1)})
+	data := NewBytes([]byte("x"))
+	original := NewMap(map[string]Object{
+		"list":  inner,
+		"tuple": NewTuple([]Object{inner}),
+		"bytes": data,
+		"name":  NewString("a"),
+	})
+	copied := DeepCopy(original).(*Map)
+	assert.True(t, StrictEqual(original, copied))
+
+	// Mutating the original doesn't affect the copy
+	inner.Append(NewInt(2))
+	data.Value()[0] = 'y'
+	assert.Equal(t, copied.Get("list").(*List).Value(), []Object{NewInt(1)})
+	assert.Equal(t, copied.Get("bytes").(*Bytes).Value(), []byte("x"))
+
+	// Values shared within the original are shared within the copy
+	copiedInner := copied.Get("list")
+	assert.True(t, copied.Get("tuple").(*Tuple).Value()[0] == copiedInner)
+	assert.True(t, copied.Get("name") == original.Get("name"))
+}
+
+func TestDeepCopyCycles(t *testing.T) {
+	list := NewList(nil)
+	list.Append(list)
+	copied := DeepCopy(list).(*List)
+	assert.True(t, copied != list)
+	assert.True(t, copied.Value()[0] == copied)
+}
@@ -119,6 +119,10 @@ func (e *Error) GetAttr(name string) (Object, bool) {
 	return errorMethods.GetAttr(e, name)
 }
 
+func (e *Error) CallMethod(ctx context.Context, name string, args []Object) (Object, bool, error) {
+	return errorMethods.CallMethod(ctx, e, name, args)
+}
+
 func (e *Error) SetAttr(name string, value Object) error {
 	return TypeErrorf("error has no attribute %q", name)
 }
@@ -21,12 +21,13 @@ type (
 
 // Re-export error kind constants
 const (
-	ErrSyntax  = errors.ErrSyntax
-	ErrType    = errors.ErrType
-	ErrName    = errors.ErrName
-	ErrValue   = errors.ErrValue
-	ErrRuntime = errors.ErrRuntime
-	ErrImport  = errors.ErrImport
+	ErrSyntax    = errors.ErrSyntax
+	ErrType      = errors.ErrType
+	ErrName      = errors.ErrName
+	ErrValue     = errors.ErrValue
+	ErrRuntime   = errors.ErrRuntime
+	ErrImport    = errors.ErrImport
+	ErrAssertion = errors.ErrAssertion
 )
 
 // Re-export functions for convenience
@@ -39,6 +40,8 @@ var (
 	NewIndexError       = errors.NewIndexError
 	NewStructuredError  = errors.NewStructuredError
 	NewStructuredErrorf = errors.NewStructuredErrorf
+	SuggestSimilar      = errors.SuggestSimilar
+	FormatSuggestions   = errors.FormatSuggestions
 )
 
 // Internal functions used by the wrapper functions in object.go
@@ -1,6 +1,7 @@
 package object
 
 import (
+	"cmp"
 	"encoding/json"
 	"math"
 	"strconv"
@@ -26,7 +27,7 @@ func (f *Float) SetAttr(name string, value Object) error {
 }
 
 func (f *Float) Inspect() string {
-	return strconv.FormatFloat(f.value, 'f', -1, 64)
+	return FormatFloat(f.value)
 }
 
 func (f *Float) Type() Type {
@@ -45,39 +46,29 @@ func (f *Float) String() string {
 	return f.Inspect()
 }
 
+// Compare orders floats for sorting. NaN is ordered before all other numbers
+// and equal to itself, so that sorting is well defined. The comparison
+// operators treat NaN differently; see the Compare function.
 func (f *Float) Compare(other Object) (int, error) {
 	switch other := other.(type) {
 	case *Float:
-		if f.value == other.value {
-			return 0, nil
-		}
-		if f.value > other.value {
-			return 1, nil
-		}
-		return -1, nil
+		return cmp.Compare(f.value, other.value), nil
 	case *Int:
-		otherFloat := float64(other.value)
-		if f.value == otherFloat {
-			return 0, nil
-		}
-		if f.value > otherFloat {
-			return 1, nil
-		}
-		return -1, nil
+		return cmp.Compare(f.value, float64(other.value)), nil
 	case *Byte:
-		otherFloat := float64(other.value)
-		if f.value == otherFloat {
-			return 0, nil
-		}
-		if f.value > otherFloat {
-			return 1, nil
-		}
-		return -1, nil
+		return cmp.Compare(f.value, float64(other.value)), nil
+	case *Decimal:
+		result, err := other.Compare(f)
+		return -result, err
 	default:
 		return 0, TypeErrorf("unable to compare float and %s", other.Type())
 	}
 }
 
+func (f *Float) HashKey() HashKey {
+	return numericHashKey(f.value)
+}
+
 func (f *Float) Equals(other Object) bool {
 	switch other := other.(type) {
 	case *Int:
@@ -86,6 +77,8 @@ func (f *Float) Equals(other Object) bool {
 		return f.value == other.value
 	case *Byte:
 		return f.value == float64(other.value)
+	case *Decimal:
+		return other.Equals(f)
 	}
 	return false
 }
@@ -103,6 +96,9 @@ func (f *Float) RunOperation(opType op.BinaryOpType, right Object) (Object, erro
 	case *Byte:
 		rightFloat := float64(right.value)
 		return f.runOperationFloat(opType, rightFloat)
+	case *Decimal:
+		return nil, newTypeErrorf("unsupported operation for float: %v on type decimal "+
+			"(convert with decimal() or float() first)", opType)
 	default:
 		return nil, newTypeErrorf("unsupported operation for float: %v on type %s", opType, right.Type())
 	}
@@ -132,3 +128,27 @@ func (f *Float) MarshalJSON() ([]byte, error) {
 func NewFloat(value float64) *Float {
 	return &Float{value: value}
 }
+
+// FormatFloat returns the string form of a float, as used by Inspect, string(),
+// and templates. It uses the fewest digits that parse back to the same value.
+// Numbers from 1e-6 up to, but not including, 1e21 are written in decimal
+// notation, and others in scientific notation, matching JSON encoding:
+//
+//	1.0    -> "1"
+//	0.1    -> "0.1"
+//	1e21   -> "1e+21"
+//	1.5e-7 -> "1.5e-7"
+//
+// NaN and infinities are written as "NaN", "+Inf", and "-Inf".
+func FormatFloat(v float64) string {
+	abs := math.Abs(v)
+	if abs == 0 || math.IsInf(v, 0) || math.IsNaN(v) || (abs >= 1e-6 && abs < 1e21) {
+		return strconv.FormatFloat(v, 'f', -1, 64)
+	}
+	s := strconv.FormatFloat(v, 'e', -1, 64)
+	// Drop the leading zero of a two digit exponent, e.g. 1e-07 to 1e-7
+	if n := len(s); n >= 4 && s[n-4] == 'e' && s[n-2] == '0' {
+		s = s[:n-2] + s[n-1:]
+	}
+	return s
+}
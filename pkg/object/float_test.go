@@ -1,6 +1,8 @@
 package object
 
 import (
+	"math"
+	"strconv"
 	"testing"
 
 	"github.com/deepnoodle-ai/wonton/assert"
@@ -14,3 +16,40 @@ func TestFloatBasics(t *testing.T) {
 	assert.Equal(t, value.Inspect(), "-2")
 	assert.Equal(t, value.Interface(), float64(-2))
 }
+
+func TestFormatFloat(t *testing.T) {
+	tests := []struct {
+		value    float64
+		expected string
+	}{
+		{0, "0"},
+		{1, "1"},
+		{-2.5, "-2.5"},
+		{0.1, "0.1"},
+		{0.1 + 0.2, "0.30000000000000004"},
+		{1.0 / 3, "0.3333333333333333"},
+		{123456789012, "123456789012"},
+		{1e20, "100000000000000000000"},
+		{1e21, "1e+21"},
+		{1.5e300, "1.5e+300"},
+		{0.000001, "0.000001"},
+		{1.5e-7, "1.5e-7"},
+		{-1e-10, "-1e-10"},
+		{5e-324, "5e-324"},
+		{math.Inf(1), "+Inf"},
+		{math.Inf(-1), "-Inf"},
+		{math.NaN(), "NaN"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.expected, func(t *testing.T) {
+			assert.Equal(t, FormatFloat(tt.value), tt.expected)
+			assert.Equal(t, NewFloat(tt.value).Inspect(), tt.expected)
+			// Finite values round-trip through the float() parsing rules
+			if !math.IsInf(tt.value, 0) && !math.IsNaN(tt.value) {
+				parsed, err := strconv.ParseFloat(tt.expected, 64)
+				assert.Nil(t, err)
+				assert.Equal(t, parsed, tt.value)
+			}
+		})
+	}
+}
@@ -0,0 +1,35 @@
+package object
+
+// Freeze makes lists and maps read-only, along with the lists and maps nested
+// within them, including inside tuples, and returns obj. Risor code that
+// tries to modify a frozen list or map gets a type error; copies made with
+// copy(), list.copy(), map.copy(), or slicing are not frozen. Hosts can use
+// Freeze to pass configuration into a script without a defensive copy.
+//
+// Freezing is permanent and happens in place, so other references to the
+// same lists and maps see them frozen too. It only restricts Risor code: Go
+// code can still modify frozen values, and objects other than lists and maps
+// are unaffected.
+func Freeze(obj Object) Object {
+	switch obj := obj.(type) {
+	case *List:
+		if !obj.frozen {
+			obj.frozen = true
+			for _, item := range obj.items {
+				Freeze(item)
+			}
+		}
+	case *Map:
+		if !obj.frozen {
+			obj.frozen = true
+			for _, value := range obj.items {
+				Freeze(value)
+			}
+		}
+	case *Tuple:
+		for _, item := range obj.items {
+			Freeze(item)
+		}
+	}
+	return obj
+}
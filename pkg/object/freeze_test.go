@@ -0,0 +1,70 @@
+package object
+
+import (
+	"context"
+	"testing"
+
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+func TestFreeze(t *testing.T) {
+	ctx := context.Background()
+	inner := NewList([]Object{NewInt(1)})
+	nested := NewMap(map[string]Object{"x": NewInt(1)})
+	config := NewMap(map[string]Object{
+		"list":  inner,
+		"tuple": NewTuple([]Object{nested}),
+	})
+	assert.True(t, Freeze(config) == config)
+	assert.True(t, config.IsFrozen())
+	assert.True(t, inner.IsFrozen())
+	assert.True(t, nested.IsFrozen())
+
+	// Every way Risor code modifies a list or map fails
+	for _, method := range []struct {
+		obj  MethodCaller
+		name string
+		args []Object
+	}{
+		{inner, "append", []Object{NewInt(2)}},
+		{inner, "clear", nil},
+		{inner, "extend", []Object{NewList(nil)}},
+		{inner, "insert", []Object{NewInt(0), NewInt(2)}},
+		{inner, "pop", []Object{NewInt(0)}},
+		{inner, "remove", []Object{NewInt(1)}},
+		{inner, "reverse", nil},
+		{inner, "sort", nil},
+		{config, "pop", []Object{NewString("list")}},
+		{config, "setdefault", []Object{NewString("y"), NewInt(2)}},
+		{config, "update", []Object{NewMap(nil)}},
+		{config, "clear", nil},
+	} {
+		_, ok, err := method.obj.CallMethod(ctx, method.name, method.args)
+		assert.True(t, ok)
+		assert.NotNil(t, err, method.name)
+	}
+	err := inner.SetItem(NewInt(0), NewInt(2))
+	assert.Equal(t, err.Error(), "type error: cannot modify a frozen list")
+	assert.NotNil(t, inner.DelItem(NewInt(0)))
+	err = nested.SetItem(NewString("x"), NewInt(2))
+	assert.Equal(t, err.Error(), "type error: cannot modify a frozen map")
+	assert.NotNil(t, nested.DelItem(NewString("x")))
+	assert.NotNil(t, nested.SetAttr("x", NewInt(2)))
+
+	assert.Equal(t, inner.Value(), []Object{NewInt(1)})
+	assert.Equal(t, nested.Get("x"), NewInt(1))
+
+	// Copies are mutable
+	listCopy := inner.Copy()
+	assert.False(t, listCopy.IsFrozen())
+	listCopy.Append(NewInt(2))
+	assert.Equal(t, inner.Value(), []Object{NewInt(1)})
+	assert.False(t, DeepCopy(config).(*Map).IsFrozen())
+}
+
+func TestFreezeCycle(t *testing.T) {
+	list := NewList(nil)
+	list.Append(list)
+	Freeze(list)
+	assert.True(t, list.IsFrozen())
+}
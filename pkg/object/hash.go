@@ -0,0 +1,64 @@
+package object
+
+import (
+	"fmt"
+	"math"
+	"strings"
+)
+
+// HashKey identifies an object by value so it can be used as a Go map key.
+// Objects that are equal according to Equals produce the same HashKey. In
+// particular, numbers hash by numeric value, so 1, 1.0, and byte(1) share a
+// key.
+type HashKey struct {
+	Type  Type
+	Value any
+}
+
+// Hashable is implemented by immutable objects that can be used as hash keys.
+// Mutable containers such as lists and maps are not hashable, since their
+// key would change whenever their contents change. Tuples are hashable when
+// all of their items are; use Hash to handle them.
+type Hashable interface {
+	HashKey() HashKey
+}
+
+// Hash returns the HashKey for the given object, or a type error if the
+// object is not hashable. NaN floats are rejected with a value error.
+func Hash(obj Object) (HashKey, error) {
+	// NaN isn't equal to itself, so it could never be found again by key
+	if isNaN(obj) {
+		return HashKey{}, ValueErrorf("NaN is not hashable")
+	}
+	if t, ok := obj.(*Tuple); ok {
+		return tupleHashKey(t.items)
+	}
+	h, ok := obj.(Hashable)
+	if !ok {
+		return HashKey{}, TypeErrorf("unhashable type: %s", obj.Type())
+	}
+	return h.HashKey(), nil
+}
+
+// numericHashKey returns the shared key used by int, float, and byte values.
+// Floats with an integral value hash as ints so that they match equal ints.
+func numericHashKey(f float64) HashKey {
+	if f == math.Trunc(f) && f >= math.MinInt64 && f < math.MaxInt64 {
+		return HashKey{Type: INT, Value: int64(f)}
+	}
+	return HashKey{Type: FLOAT, Value: f}
+}
+
+// tupleHashKey combines the keys of the given items into a single key. The
+// encoding quotes string values so that distinct tuples never collide.
+func tupleHashKey(items []Object) (HashKey, error) {
+	var sb strings.Builder
+	for _, item := range items {
+		key, err := Hash(item)
+		if err != nil {
+			return HashKey{}, err
+		}
+		fmt.Fprintf(&sb, "%s(%#v)", key.Type, key.Value)
+	}
+	return HashKey{Type: TUPLE, Value: sb.String()}, nil
+}
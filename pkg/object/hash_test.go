@@ -0,0 +1,74 @@
+package object
+
+import (
+	"math"
+	"testing"
+
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+func TestHashNumbers(t *testing.T) {
+	intKey, err := Hash(NewInt(3))
+	assert.Nil(t, err)
+	floatKey, err := Hash(NewFloat(3.0))
+	assert.Nil(t, err)
+	byteKey, err := Hash(NewByte(3))
+	assert.Nil(t, err)
+	assert.Equal(t, intKey, floatKey)
+	assert.Equal(t, intKey, byteKey)
+
+	fracKey, err := Hash(NewFloat(3.5))
+	assert.Nil(t, err)
+	assert.NotEqual(t, intKey, fracKey)
+}
+
+func TestHashDistinguishesTypes(t *testing.T) {
+	keys := map[HashKey]bool{}
+	for _, obj := range []Object{
+		NewInt(1),
+		NewString("1"),
+		True,
+		Nil,
+		NewTuple([]Object{NewInt(1)}),
+	} {
+		key, err := Hash(obj)
+		assert.Nil(t, err)
+		keys[key] = true
+	}
+	assert.Equal(t, len(keys), 5)
+}
+
+func TestHashTuple(t *testing.T) {
+	a, err := Hash(NewTuple([]Object{NewString("a,b"), NewString("c")}))
+	assert.Nil(t, err)
+	b, err := Hash(NewTuple([]Object{NewString("a"), NewString("b,c")}))
+	assert.Nil(t, err)
+	c, err := Hash(NewTuple([]Object{NewString("a,b"), NewString("c")}))
+	assert.Nil(t, err)
+	assert.NotEqual(t, a, b)
+	assert.Equal(t, a, c)
+
+	_, err = Hash(NewTuple([]Object{NewInt(1), NewList(nil)}))
+	assert.NotNil(t, err)
+}
+
+func TestHashUnhashable(t *testing.T) {
+	_, err := Hash(NewList(nil))
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), "type error: unhashable type: list")
+
+	_, err = Hash(NewMap(nil))
+	assert.NotNil(t, err)
+}
+
+func TestHashNaN(t *testing.T) {
+	_, err := Hash(NewFloat(math.NaN()))
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), "value error: NaN is not hashable")
+
+	_, err = Hash(NewTuple([]Object{NewInt(1), NewFloat(math.NaN())}))
+	assert.NotNil(t, err)
+
+	_, err = Hash(NewFloat(math.Inf(1)))
+	assert.Nil(t, err)
+}
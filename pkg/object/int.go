@@ -1,6 +1,7 @@
 package object
 
 import (
+	"cmp"
 	"encoding/json"
 	"fmt"
 	"math"
@@ -49,14 +50,7 @@ func (i *Int) String() string {
 func (i *Int) Compare(other Object) (int, error) {
 	switch other := other.(type) {
 	case *Float:
-		thisFloat := float64(i.value)
-		if thisFloat == other.value {
-			return 0, nil
-		}
-		if thisFloat > other.value {
-			return 1, nil
-		}
-		return -1, nil
+		return cmp.Compare(float64(i.value), other.value), nil
 	case *Int:
 		if i.value == other.value {
 			return 0, nil
@@ -73,11 +67,18 @@ func (i *Int) Compare(other Object) (int, error) {
 			return 1, nil
 		}
 		return -1, nil
+	case *Decimal:
+		result, err := other.Compare(i)
+		return -result, err
 	default:
 		return 0, TypeErrorf("unable to compare int and %s", other.Type())
 	}
 }
 
+func (i *Int) HashKey() HashKey {
+	return HashKey{Type: INT, Value: i.value}
+}
+
 func (i *Int) Equals(other Object) bool {
 	switch other := other.(type) {
 	case *Int:
@@ -86,6 +87,8 @@ func (i *Int) Equals(other Object) bool {
 		return float64(i.value) == other.value
 	case *Byte:
 		return i.value == int64(other.value)
+	case *Decimal:
+		return other.Equals(i)
 	}
 	return false
 }
@@ -103,6 +106,8 @@ func (i *Int) RunOperation(opType op.BinaryOpType, right Object) (Object, error)
 	case *Byte:
 		rightInt := int64(right.value)
 		return i.runOperationInt(opType, rightInt)
+	case *Decimal:
+		return NewDecimalFromInt(i.value).RunOperation(opType, right)
 	default:
 		return nil, newTypeErrorf("unsupported operation for int: %v on type %s", opType, right.Type())
 	}
@@ -131,9 +136,16 @@ func (i *Int) runOperationInt(opType op.BinaryOpType, right int64) (Object, erro
 	case op.Power:
 		return NewInt(int64(math.Pow(float64(i.value), float64(right)))), nil
 	case op.LShift:
-		return NewInt(i.value << uint(right)), nil
+		if right < 0 {
+			return nil, newValueErrorf("negative shift count: %d", right)
+		}
+		return NewInt(i.value << uint64(right)), nil
 	case op.RShift:
-		return NewInt(i.value >> uint(right)), nil
+		// Arithmetic shift: the sign bit is preserved
+		if right < 0 {
+			return nil, newValueErrorf("negative shift count: %d", right)
+		}
+		return NewInt(i.value >> uint64(right)), nil
 	case op.BitwiseAnd:
 		return NewInt(i.value & right), nil
 	case op.BitwiseOr:
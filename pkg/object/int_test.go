@@ -62,3 +62,13 @@ func TestIntBasics(t *testing.T) {
 	assert.Equal(t, value.Inspect(), "-3")
 	assert.Equal(t, value.Interface(), int64(-3))
 }
+
+func TestIntCache(t *testing.T) {
+	// Small ints are shared, and others are allocated
+	assert.True(t, NewInt(0) == NewInt(0))
+	assert.True(t, NewInt(255) == NewInt(255))
+	assert.True(t, NewInt(-10) == NewInt(-10))
+	assert.True(t, NewInt(256) != NewInt(256))
+	assert.True(t, NewInt(-11) != NewInt(-11))
+	assert.Equal(t, NewInt(-10).Value(), int64(-10))
+}
@@ -3,6 +3,7 @@ package object
 import (
 	"context"
 	"fmt"
+	"iter"
 
 	"github.com/deepnoodle-ai/risor/v2/pkg/op"
 )
@@ -10,6 +11,30 @@ import (
 // ITER type constant
 const ITER Type = "iter"
 
+var iterAttrs = NewAttrRegistry[*Iter]("iter")
+
+func init() {
+	iterAttrs.Define("each").
+		Doc("Call function for each value as it is produced").
+		Arg("fn").
+		Returns("null").
+		Impl(func(it *Iter, ctx context.Context, args ...Object) (Object, error) {
+			return it.Each(ctx, args[0])
+		})
+
+	iterAttrs.Define("take").
+		Doc("Return a list of up to n values, stopping the iterator after them").
+		Arg("n").
+		Returns("list").
+		Impl(func(it *Iter, ctx context.Context, args ...Object) (Object, error) {
+			n, err := AsInt(args[0])
+			if err != nil {
+				return nil, err
+			}
+			return it.Take(ctx, n)
+		})
+}
+
 // Iter is a lazy iterator that wraps a generator function.
 // It implements Enumerable so it can be used with spread, list(), etc.
 type Iter struct {
@@ -49,11 +74,15 @@ func (it *Iter) Equals(other Object) bool {
 }
 
 func (it *Iter) Attrs() []AttrSpec {
-	return nil
+	return iterAttrs.Specs()
 }
 
 func (it *Iter) GetAttr(name string) (Object, bool) {
-	return nil, false
+	return iterAttrs.GetAttr(it, name)
+}
+
+func (it *Iter) CallMethod(ctx context.Context, name string, args []Object) (Object, bool, error) {
+	return iterAttrs.CallMethod(ctx, it, name, args)
 }
 
 func (it *Iter) SetAttr(name string, value Object) error {
@@ -73,6 +102,49 @@ func (it *Iter) Enumerate(ctx context.Context, fn func(key, value Object) bool)
 	it.generator(ctx, fn)
 }
 
+// Each calls fn with each value as the generator produces it, so values
+// are never collected into a list.
+func (it *Iter) Each(ctx context.Context, fn Object) (Object, error) {
+	callable, ok := fn.(Callable)
+	if !ok {
+		return nil, newTypeErrorf("iter.each() expected a function (%s given)", fn.Type())
+	}
+	var callErr error
+	it.generator(ctx, func(key, value Object) bool {
+		if _, err := callable.Call(ctx, value); err != nil {
+			callErr = err
+			return false
+		}
+		return true
+	})
+	if callErr != nil {
+		return nil, callErr
+	}
+	if err := ctx.Err(); err != nil {
+		return nil, err
+	}
+	return Nil, nil
+}
+
+// Take returns a list of up to n values, stopping the generator once it
+// has produced them.
+func (it *Iter) Take(ctx context.Context, n int64) (Object, error) {
+	if n < 0 {
+		return nil, newValueErrorf("iter.take() count must not be negative")
+	}
+	items := []Object{}
+	if n > 0 {
+		it.generator(ctx, func(key, value Object) bool {
+			items = append(items, value)
+			return int64(len(items)) < n
+		})
+	}
+	if err := ctx.Err(); err != nil {
+		return nil, err
+	}
+	return NewList(items), nil
+}
+
 // NewIter creates a new iterator with a description and generator function.
 func NewIter(desc string, gen func(ctx context.Context, fn func(key, value Object) bool)) *Iter {
 	return &Iter{
@@ -81,6 +153,23 @@ func NewIter(desc string, gen func(ctx context.Context, fn func(key, value Objec
 	}
 }
 
+// NewSeqIter returns an iterator over the values of a Go sequence, keyed by
+// their position. Hosts can use it to pass a stream, such as events read
+// from a queue, to scripts, which consume it lazily with each, take, or
+// spread. The sequence is run again each time the iterator is enumerated,
+// and iteration stops if the script's context is done.
+func NewSeqIter(desc string, seq iter.Seq[Object]) *Iter {
+	return NewIter(desc, func(ctx context.Context, fn func(key, value Object) bool) {
+		var i int64
+		for value := range seq {
+			if ctx.Err() != nil || !fn(NewInt(i), value) {
+				return
+			}
+			i++
+		}
+	})
+}
+
 // NewMapKeyIter creates an iterator over map keys.
 func NewMapKeyIter(m *Map) *Iter {
 	return NewIter("map.keys", func(ctx context.Context, fn func(key, value Object) bool) {
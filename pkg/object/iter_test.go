@@ -2,6 +2,7 @@ package object
 
 import (
 	"context"
+	"fmt"
 	"testing"
 
 	"github.com/deepnoodle-ai/wonton/assert"
@@ -85,13 +86,73 @@ func TestIterIsTruthy(t *testing.T) {
 
 func TestIterAttrs(t *testing.T) {
 	it := NewIter("test", func(ctx context.Context, fn func(key, value Object) bool) {})
-	assert.Nil(t, it.Attrs())
+	var names []string
+	for _, spec := range it.Attrs() {
+		names = append(names, spec.Name)
+	}
+	assert.Equal(t, names, []string{"each", "take"})
 }
 
 func TestIterGetAttr(t *testing.T) {
 	it := NewIter("test", func(ctx context.Context, fn func(key, value Object) bool) {})
 	_, ok := it.GetAttr("anything")
 	assert.False(t, ok)
+	_, ok = it.GetAttr("each")
+	assert.True(t, ok)
+}
+
+func TestSeqIter(t *testing.T) {
+	ctx := context.Background()
+	var produced int
+	events := NewSeqIter("events", func(yield func(Object) bool) {
+		for i := 0; ; i++ {
+			produced++
+			if !yield(NewString(fmt.Sprintf("event-%d", i))) {
+				return
+			}
+		}
+	})
+	assert.Equal(t, events.Inspect(), "iter(events)")
+
+	// take stops the unbounded stream after n values
+	result, err := events.Take(ctx, 2)
+	assert.Nil(t, err)
+	assert.Equal(t, result.Inspect(), `["event-0", "event-1"]`)
+	assert.Equal(t, produced, 2)
+
+	_, err = events.Take(ctx, -1)
+	assert.NotNil(t, err)
+}
+
+func TestIterEach(t *testing.T) {
+	ctx := context.Background()
+	it := NewSeqIter("test", func(yield func(Object) bool) {
+		for i := int64(1); i <= 3; i++ {
+			if !yield(NewInt(i)) {
+				return
+			}
+		}
+	})
+	var seen []int64
+	collect := NewBuiltin("collect", func(ctx context.Context, args ...Object) (Object, error) {
+		seen = append(seen, args[0].(*Int).Value())
+		if len(seen) == 2 {
+			return nil, fmt.Errorf("stop")
+		}
+		return Nil, nil
+	})
+	_, err := it.Each(ctx, collect)
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), "stop")
+	assert.Equal(t, seen, []int64{1, 2})
+
+	_, err = it.Each(ctx, NewInt(1))
+	assert.NotNil(t, err)
+
+	canceled, cancel := context.WithCancel(ctx)
+	cancel()
+	_, err = it.Each(canceled, collect)
+	assert.Equal(t, err, context.Canceled)
 }
 
 func TestIterSetAttr(t *testing.T) {
@@ -4,8 +4,12 @@ import (
 	"bytes"
 	"context"
 	"encoding/json"
+	"errors"
 	"fmt"
+	"math"
+	"slices"
 	"strings"
+	"sync/atomic"
 
 	"github.com/deepnoodle-ai/risor/v2/pkg/op"
 )
@@ -18,6 +22,9 @@ func init() {
 		Arg("item").
 		Returns("list").
 		Impl(func(ls *List, ctx context.Context, args ...Object) (Object, error) {
+			if err := ls.checkMutable(); err != nil {
+				return nil, err
+			}
 			ls.Append(args[0])
 			return ls, nil
 		})
@@ -26,6 +33,9 @@ func init() {
 		Doc("Remove all items").
 		Returns("list").
 		Impl(func(ls *List, ctx context.Context, args ...Object) (Object, error) {
+			if err := ls.checkMutable(); err != nil {
+				return nil, err
+			}
 			ls.Clear()
 			return ls, nil
 		})
@@ -58,6 +68,9 @@ func init() {
 		Arg("items").
 		Returns("list").
 		Impl(func(ls *List, ctx context.Context, args ...Object) (Object, error) {
+			if err := ls.checkMutable(); err != nil {
+				return nil, err
+			}
 			other, err := AsList(args[0])
 			if err != nil {
 				return nil, err
@@ -87,6 +100,9 @@ func init() {
 		Args("index", "item").
 		Returns("list").
 		Impl(func(ls *List, ctx context.Context, args ...Object) (Object, error) {
+			if err := ls.checkMutable(); err != nil {
+				return nil, err
+			}
 			index, err := AsInt(args[0])
 			if err != nil {
 				return nil, err
@@ -108,6 +124,9 @@ func init() {
 		Arg("index").
 		Returns("any").
 		Impl(func(ls *List, ctx context.Context, args ...Object) (Object, error) {
+			if err := ls.checkMutable(); err != nil {
+				return nil, err
+			}
 			index, err := AsInt(args[0])
 			if err != nil {
 				return nil, err
@@ -128,6 +147,9 @@ func init() {
 		Arg("item").
 		Returns("null").
 		Impl(func(ls *List, ctx context.Context, args ...Object) (Object, error) {
+			if err := ls.checkMutable(); err != nil {
+				return nil, err
+			}
 			ls.Remove(args[0])
 			return ls, nil
 		})
@@ -136,15 +158,59 @@ func init() {
 		Doc("Reverse list in place").
 		Returns("list").
 		Impl(func(ls *List, ctx context.Context, args ...Object) (Object, error) {
+			if err := ls.checkMutable(); err != nil {
+				return nil, err
+			}
 			ls.Reverse()
 			return ls, nil
 		})
 
 	listMethods.Define("sort").
-		Doc("Sort list in place").
+		Doc("Sort list in place, optionally with a less-than function").
+		OptionalArg("fn").
 		Returns("list").
 		Impl(func(ls *List, ctx context.Context, args ...Object) (Object, error) {
-			if err := Sort(ls.items); err != nil {
+			if err := ls.checkMutable(); err != nil {
+				return nil, err
+			}
+			sortFn := func(items []Object) error {
+				if err := Sort(items); err != nil {
+					return err
+				}
+				return nil
+			}
+			if len(args) > 0 {
+				less, ok := args[0].(Callable)
+				if !ok {
+					return nil, TypeErrorf("list.sort() expected a function (%s given)", args[0].Type())
+				}
+				sortFn = func(items []Object) error {
+					return SortFunc(ctx, items, less)
+				}
+			}
+			err := ls.sortWith(sortFn)
+			if err != nil {
+				return nil, err
+			}
+			return ls, nil
+		})
+
+	listMethods.Define("sort_by").
+		Doc("Sort list in place by the key fn returns for each item").
+		Arg("fn").
+		Returns("list").
+		Impl(func(ls *List, ctx context.Context, args ...Object) (Object, error) {
+			if err := ls.checkMutable(); err != nil {
+				return nil, err
+			}
+			key, ok := args[0].(Callable)
+			if !ok {
+				return nil, TypeErrorf("list.sort_by() expected a function (%s given)", args[0].Type())
+			}
+			err := ls.sortWith(func(items []Object) error {
+				return SortByKey(ctx, items, key)
+			})
+			if err != nil {
 				return nil, err
 			}
 			return ls, nil
@@ -156,9 +222,20 @@ type List struct {
 	// items holds the list of objects
 	items []Object
 
-	// Used to avoid the possibility of infinite recursion when inspecting.
-	// Similar to the usage of Py_ReprEnter in CPython.
-	inspectActive bool
+	// shared is set when items may also be held by another list, in which
+	// case they are copied before this list is modified. It is atomic since
+	// copying a list sets it, and frozen lists may be copied concurrently.
+	shared atomic.Bool
+
+	// frozen is set by Freeze to make the list read-only for Risor code
+	frozen bool
+
+	// Used to avoid the possibility of infinite recursion when inspecting,
+	// converting, or marshaling a list that contains itself. Similar to the
+	// usage of Py_ReprEnter in CPython.
+	inspectActive   bool
+	interfaceActive bool
+	marshalActive   bool
 }
 
 func (ls *List) Attrs() []AttrSpec {
@@ -169,6 +246,10 @@ func (ls *List) GetAttr(name string) (Object, bool) {
 	return listMethods.GetAttr(ls, name)
 }
 
+func (ls *List) CallMethod(ctx context.Context, name string, args []Object) (Object, bool, error) {
+	return listMethods.CallMethod(ctx, ls, name, args)
+}
+
 func (ls *List) SetAttr(name string, value Object) error {
 	return TypeErrorf("list has no attribute %q", name)
 }
@@ -177,6 +258,8 @@ func (ls *List) Type() Type {
 	return LIST
 }
 
+// Value returns the items in the list. The slice may be shared with other
+// lists and must not be modified.
 func (ls *List) Value() []Object {
 	return ls.items
 }
@@ -201,6 +284,19 @@ func (ls *List) Inspect() string {
 	return out.String()
 }
 
+// sortWith sorts a copy of the items and stores the result if the sort
+// succeeds, so a failed sort leaves the list unchanged. Functions called by
+// the sort may also read or modify the list without disturbing it.
+func (ls *List) sortWith(sortFn func(items []Object) error) error {
+	items := slices.Clone(ls.items)
+	if err := sortFn(items); err != nil {
+		return err
+	}
+	ls.items = items
+	ls.shared.Store(false)
+	return nil
+}
+
 func (ls *List) Map(ctx context.Context, fn Object) (Object, error) {
 	callable, ok := fn.(Callable)
 	if !ok {
@@ -279,21 +375,54 @@ func (ls *List) Reduce(ctx context.Context, initial Object, fn Object) (Object,
 	return accumulator, nil
 }
 
+// own gives the list its own copy of its items if they are shared with
+// another list. It must be called before the items are modified in place.
+func (ls *List) own() {
+	if ls.shared.Load() {
+		ls.items = slices.Clone(ls.items)
+		ls.shared.Store(false)
+	}
+}
+
+// IsFrozen reports whether the list has been made read-only by Freeze.
+func (ls *List) IsFrozen() bool {
+	return ls.frozen
+}
+
+// checkMutable returns an error if the list is frozen.
+func (ls *List) checkMutable() *Error {
+	if ls.frozen {
+		return TypeErrorf("cannot modify a frozen list")
+	}
+	return nil
+}
+
 // Append adds an item at the end of the list.
 func (ls *List) Append(obj Object) {
+	ls.own()
 	ls.items = append(ls.items, obj)
 }
 
 // Clear removes all the items from the list.
 func (ls *List) Clear() {
 	ls.items = []Object{}
+	ls.shared.Store(false)
 }
 
-// Copy returns a shallow copy of the list.
+// Copy returns a shallow copy of the list. The copy shares its items with the
+// original until either list is modified, so copying is cheap even for large
+// lists.
 func (ls *List) Copy() *List {
-	result := &List{items: make([]Object, len(ls.items))}
-	copy(result.items, ls.items)
-	return result
+	return ls.share(ls.items)
+}
+
+// share returns a list holding items, which are held by this list too. Both
+// lists copy the items before modifying them.
+func (ls *List) share(items []Object) *List {
+	ls.shared.Store(true)
+	other := &List{items: items}
+	other.shared.Store(true)
+	return other
 }
 
 // Count returns the number of items with the specified value.
@@ -309,6 +438,7 @@ func (ls *List) Count(obj Object) int64 {
 
 // Extend adds the items of a list to the end of the current list.
 func (ls *List) Extend(other *List) {
+	ls.own()
 	ls.items = append(ls.items, other.items...)
 }
 
@@ -331,6 +461,7 @@ func (ls *List) Insert(index int64, obj Object) {
 			index = 0
 		}
 	}
+	ls.own()
 	if index == 0 {
 		ls.items = append([]Object{obj}, ls.items...)
 		return
@@ -350,6 +481,7 @@ func (ls *List) Pop(index int64) (Object, error) {
 	if err != nil {
 		return nil, err
 	}
+	ls.own()
 	result := ls.items[idx]
 	ls.items = append(ls.items[:idx], ls.items[idx+1:]...)
 	return result, nil
@@ -361,17 +493,27 @@ func (ls *List) Remove(obj Object) {
 	if index == -1 {
 		return
 	}
+	ls.own()
 	ls.items = append(ls.items[:index], ls.items[index+1:]...)
 }
 
 // Reverse reverses the order of the list.
 func (ls *List) Reverse() {
+	ls.own()
 	for i, j := 0, len(ls.items)-1; i < j; i, j = i+1, j-1 {
 		ls.items[i], ls.items[j] = ls.items[j], ls.items[i]
 	}
 }
 
 func (ls *List) Interface() interface{} {
+	// A list that contains itself is converted to a placeholder where it
+	// repeats, as when it is inspected
+	if ls.interfaceActive {
+		return "[...]"
+	}
+	ls.interfaceActive = true
+	defer func() { ls.interfaceActive = false }()
+
 	items := make([]interface{}, 0, len(ls.items))
 	for _, item := range ls.items {
 		items = append(items, item.Interface())
@@ -409,21 +551,9 @@ func (ls *List) Compare(other Object) (int, error) {
 	return 0, nil
 }
 
+// Equals reports whether other is a list with equal items; see DeepEqual.
 func (ls *List) Equals(other Object) bool {
-	otherList, ok := other.(*List)
-	if !ok {
-		return false
-	}
-	if len(ls.items) != len(otherList.items) {
-		return false
-	}
-	for i, v := range ls.items {
-		otherV := otherList.items[i]
-		if !Equals(v, otherV) {
-			return false
-		}
-	}
-	return true
+	return DeepEqual(ls, other)
 }
 
 func (ls *List) IsTruthy() bool {
@@ -459,20 +589,24 @@ func (ls *List) GetItem(key Object) (Object, *Error) {
 	return ls.items[idx], nil
 }
 
-// GetSlice implements the [start:stop] operator for a container type.
+// GetSlice implements the [start:stop] operator for a container type. Like
+// Copy, the result shares items with the list until either is modified.
 func (ls *List) GetSlice(s Slice) (Object, *Error) {
-	start, stop, err := ResolveIntSlice(s, int64(len(ls.items)))
+	start, stop, step, err := ResolveSlice(s, int64(len(ls.items)))
 	if err != nil {
 		return nil, NewError(err)
 	}
-	items := ls.items[start:stop]
-	itemsCopy := make([]Object, len(items))
-	copy(itemsCopy, items)
-	return NewList(itemsCopy), nil
+	if step != 1 {
+		return NewList(stepSlice(ls.items, start, stop, step)), nil
+	}
+	return ls.share(ls.items[start:stop]), nil
 }
 
 // SetItem implements the [key] = value operator for a container type.
 func (ls *List) SetItem(key, value Object) *Error {
+	if err := ls.checkMutable(); err != nil {
+		return err
+	}
 	indexObj, ok := key.(*Int)
 	if !ok {
 		return TypeErrorf("list index must be an int (got %s)", key.Type())
@@ -481,12 +615,52 @@ func (ls *List) SetItem(key, value Object) *Error {
 	if err != nil {
 		return Errorf(err.Error())
 	}
+	ls.own()
 	ls.items[idx] = value
 	return nil
 }
 
+// SetSlice implements the [start:stop] = value operator for a container type.
+// The items of a list or tuple value replace the items in the slice, which
+// may change the length of the list. With a step other than 1, the value must
+// have as many items as the slice.
+func (ls *List) SetSlice(s Slice, value Object) *Error {
+	if err := ls.checkMutable(); err != nil {
+		return err
+	}
+	var items []Object
+	switch value := value.(type) {
+	case *List:
+		items = slices.Clone(value.items)
+	case *Tuple:
+		items = value.items
+	default:
+		return TypeErrorf("can only assign a list or tuple to a list slice (got %s)", value.Type())
+	}
+	start, stop, step, err := ResolveSlice(s, int64(len(ls.items)))
+	if err != nil {
+		return NewError(err)
+	}
+	ls.own()
+	if step == 1 {
+		ls.items = slices.Replace(ls.items, int(start), int(stop), items...)
+		return nil
+	}
+	if count := stepCount(start, stop, step); int64(len(items)) != count {
+		return ValueErrorf("cannot assign %d items to a slice of %d items with a step of %d",
+			len(items), count, step)
+	}
+	for i, item := range items {
+		ls.items[start+int64(i)*step] = item
+	}
+	return nil
+}
+
 // DelItem implements the del [key] operator for a container type.
 func (ls *List) DelItem(key Object) *Error {
+	if err := ls.checkMutable(); err != nil {
+		return err
+	}
 	indexObj, ok := key.(*Int)
 	if !ok {
 		return TypeErrorf("list index must be an int (got %s)", key.Type())
@@ -495,6 +669,7 @@ func (ls *List) DelItem(key Object) *Error {
 	if err != nil {
 		return Errorf(err.Error())
 	}
+	ls.own()
 	ls.items = append(ls.items[:idx], ls.items[idx+1:]...)
 	return nil
 }
@@ -526,14 +701,40 @@ func (ls *List) Enumerate(ctx context.Context, fn func(key, value Object) bool)
 	}
 }
 
+// RunOperation implements the binary operators for lists. Adding two lists
+// concatenates them, and multiplying a list by an int n repeats its items n
+// times. Both return a new list.
 func (ls *List) RunOperation(opType op.BinaryOpType, right Object) (Object, error) {
 	switch right := right.(type) {
 	case *List:
 		return ls.runOperationList(opType, right)
-	default:
-		return nil, newTypeErrorf("unsupported operation for list: %v on type %s",
-			opType, right.Type())
+	case *Int:
+		if opType == op.Multiply {
+			return ls.repeat(right.value)
+		}
+	}
+	return nil, newTypeErrorf("unsupported operation for list: %v on type %s",
+		opType, right.Type())
+}
+
+// repeat returns a new list with the items of the list repeated count times.
+func (ls *List) repeat(count int64) (Object, error) {
+	if count < 0 {
+		return nil, newValueErrorf("negative repeat count")
+	}
+	size := int64(len(ls.items))
+	if size == 0 || count == 0 {
+		// Skip the loop below, which would run count times for nothing
+		return NewList([]Object{}), nil
+	}
+	if count > math.MaxInt32/size {
+		return nil, newValueErrorf("repeat count is too large")
+	}
+	items := make([]Object, 0, size*count)
+	for range count {
+		items = append(items, ls.items...)
 	}
+	return NewList(items), nil
 }
 
 func (ls *List) runOperationList(opType op.BinaryOpType, right *List) (Object, error) {
@@ -550,6 +751,11 @@ func (ls *List) runOperationList(opType op.BinaryOpType, right *List) (Object, e
 }
 
 func (ls *List) MarshalJSON() ([]byte, error) {
+	if ls.marshalActive {
+		return nil, errors.New("marshal error: unable to marshal a list that contains itself")
+	}
+	ls.marshalActive = true
+	defer func() { ls.marshalActive = false }()
 	return json.Marshal(ls.items)
 }
 
@@ -586,7 +792,8 @@ func ResolveIndex(idx int64, size int64) (int64, error) {
 
 // ResolveIntSlice checks that the slice start and stop indices are inbounds and
 // transforms negative indices into the corresponding positive indices. If the
-// slice is out of bounds, an error is returned.
+// slice is out of bounds, an error is returned. The start may equal the size,
+// giving an empty slice at the end, so x[len(x):] = items appends to x.
 func ResolveIntSlice(slice Slice, size int64) (start int64, stop int64, err error) {
 	if slice.Start != nil {
 		startObj, ok := slice.Start.(*Int)
@@ -624,13 +831,89 @@ func ResolveIntSlice(slice Slice, size int64) (start int64, stop int64, err erro
 		err = fmt.Errorf("slice error: start index is greater than stop index")
 		return
 	}
-	if start > size-1 {
-		err = fmt.Errorf("slice error: start index is out of range")
-		return
-	}
 	if stop > size {
 		err = fmt.Errorf("slice error: stop index is out of range")
 		return
 	}
 	return start, stop, nil
 }
+
+// ResolveSlice resolves the indices of a slice that may have a step. With a
+// positive step, start and stop are resolved as by ResolveIntSlice. With a
+// negative step, the slice walks backward from start, which defaults to the
+// last index, down to but not including stop, which defaults to -1 to mean
+// before the first index. The step defaults to 1 and can't be zero.
+func ResolveSlice(slice Slice, size int64) (start, stop, step int64, err error) {
+	step = 1
+	if slice.Step != nil {
+		stepObj, ok := slice.Step.(*Int)
+		if !ok {
+			err = TypeErrorf("slice step must be an int (got %s)", slice.Step.Type())
+			return
+		}
+		if step = stepObj.value; step == 0 {
+			err = ValueErrorf("slice step cannot be zero")
+			return
+		}
+	}
+	if step > 0 {
+		start, stop, err = ResolveIntSlice(slice, size)
+		return
+	}
+	start, stop = size-1, -1
+	if slice.Start != nil {
+		if start, err = resolveSliceIndex(slice.Start, "start", size); err != nil {
+			return
+		}
+	}
+	if slice.Stop != nil {
+		if stop, err = resolveSliceIndex(slice.Stop, "stop", size); err != nil {
+			return
+		}
+	}
+	if start < stop {
+		err = fmt.Errorf("slice error: start index is less than stop index with a negative step")
+	}
+	return
+}
+
+// resolveSliceIndex resolves a start or stop index of a slice with a negative
+// step, which must refer to an item in the container.
+func resolveSliceIndex(obj Object, name string, size int64) (int64, error) {
+	indexObj, ok := obj.(*Int)
+	if !ok {
+		return 0, TypeErrorf("slice %s index must be an int (got %s)", name, obj.Type())
+	}
+	index := indexObj.value
+	if index < 0 {
+		index += size
+	}
+	if index < 0 || index >= size {
+		return 0, fmt.Errorf("slice error: %s index is out of range", name)
+	}
+	return index, nil
+}
+
+// stepSlice returns a new slice of the items selected by a slice resolved by
+// ResolveSlice.
+func stepSlice[T any](items []T, start, stop, step int64) []T {
+	if step == -1 {
+		result := slices.Clone(items[stop+1 : start+1])
+		slices.Reverse(result)
+		return result
+	}
+	result := make([]T, 0, stepCount(start, stop, step))
+	for i := start; (step > 0 && i < stop) || (step < 0 && i > stop); i += step {
+		result = append(result, items[i])
+	}
+	return result
+}
+
+// stepCount returns the number of items selected by a slice resolved by
+// ResolveSlice.
+func stepCount(start, stop, step int64) int64 {
+	if step > 0 {
+		return max((stop-start+step-1)/step, 0)
+	}
+	return max((start-stop-step-1)/-step, 0)
+}
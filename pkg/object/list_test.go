@@ -2,6 +2,10 @@ package object
 
 import (
 	"context"
+	"encoding/json"
+	"math"
+	"slices"
+	"sync"
 	"testing"
 
 	"github.com/deepnoodle-ai/risor/v2/pkg/op"
@@ -37,6 +41,24 @@ func TestListInspectSelfReference(t *testing.T) {
 	assert.Equal(t, inspect, "[[...]]")
 }
 
+func TestListSelfReferenceConversion(t *testing.T) {
+	list := NewList([]Object{NewInt(1)})
+	list.Append(NewTuple([]Object{list}))
+	assert.Equal(t, list.Interface(), []any{int64(1), []any{"[...]"}})
+	assert.Equal(t, PrintableValue(list), "[1, ([...],)]")
+
+	_, err := json.Marshal(list)
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "contains itself")
+
+	// The list can still be converted once it no longer contains itself
+	_, err = list.Pop(1)
+	assert.Nil(t, err)
+	data, err := json.Marshal(list)
+	assert.Nil(t, err)
+	assert.Equal(t, string(data), "[1]")
+}
+
 func TestListString(t *testing.T) {
 	list := NewList([]Object{NewInt(1)})
 	assert.Equal(t, list.String(), "[1]")
@@ -111,6 +133,52 @@ func TestListCopy(t *testing.T) {
 	assert.Equal(t, copyList.Len().Value(), int64(2))
 }
 
+func TestListCopyOnWrite(t *testing.T) {
+	list := NewList([]Object{NewInt(1), NewInt(2), NewInt(3)})
+	copyList := list.Copy()
+
+	// Modifying the copy leaves the original alone
+	copyList.SetItem(NewInt(0), NewInt(10))
+	copyList.Reverse()
+	assert.Equal(t, list.Inspect(), "[1, 2, 3]")
+	assert.Equal(t, copyList.Inspect(), "[3, 2, 10]")
+
+	// And modifying the original leaves the copy alone
+	other := list.Copy()
+	list.Append(NewInt(4))
+	list.Insert(0, NewInt(0))
+	assert.Equal(t, list.Inspect(), "[0, 1, 2, 3, 4]")
+	assert.Equal(t, other.Inspect(), "[1, 2, 3]")
+
+	// Slices share items the same way
+	result, err := list.GetSlice(Slice{Start: NewInt(1), Stop: NewInt(3)})
+	assert.Nil(t, err)
+	slice := result.(*List)
+	slice.Append(NewInt(99))
+	slice.SetItem(NewInt(0), NewInt(-1))
+	assert.Equal(t, slice.Inspect(), "[-1, 2, 99]")
+	assert.Equal(t, list.Inspect(), "[0, 1, 2, 3, 4]")
+}
+
+func TestListCopyConcurrent(t *testing.T) {
+	// Scripts running in parallel may copy and slice the same frozen list
+	list := Freeze(NewList([]Object{NewInt(1), NewInt(2), NewInt(3)})).(*List)
+	var wg sync.WaitGroup
+	for range 8 {
+		wg.Add(1)
+		go func() {
+			defer wg.Done()
+			copyList := list.Copy()
+			copyList.Append(NewInt(4))
+			result, err := list.GetSlice(Slice{Start: NewInt(1)})
+			assert.Nil(t, err)
+			result.(*List).SetItem(NewInt(0), NewInt(0))
+		}()
+	}
+	wg.Wait()
+	assert.Equal(t, list.Inspect(), "[1, 2, 3]")
+}
+
 func TestListCount(t *testing.T) {
 	list := NewList([]Object{NewInt(1), NewInt(2), NewInt(1), NewInt(1)})
 	assert.Equal(t, list.Count(NewInt(1)), int64(3))
@@ -301,6 +369,62 @@ func TestListDelItem(t *testing.T) {
 	assert.NotNil(t, err)
 }
 
+func TestListSetSlice(t *testing.T) {
+	ints := func(values ...int64) []Object {
+		items := make([]Object, len(values))
+		for i, v := range values {
+			items[i] = NewInt(v)
+		}
+		return items
+	}
+	list := NewList(ints(0, 1, 2, 3, 4))
+
+	// Replace with more items
+	err := list.SetSlice(Slice{Start: NewInt(1), Stop: NewInt(3)}, NewList(ints(7, 8, 9)))
+	assert.Nil(t, err)
+	assert.Equal(t, list.Value(), ints(0, 7, 8, 9, 3, 4))
+
+	// Replace with fewer items
+	err = list.SetSlice(Slice{Start: NewInt(1), Stop: NewInt(4)}, NewTuple(ints(1)))
+	assert.Nil(t, err)
+	assert.Equal(t, list.Value(), ints(0, 1, 3, 4))
+
+	// Assign to every other item
+	err = list.SetSlice(Slice{Step: NewInt(-2)}, NewList(ints(5, 6)))
+	assert.Nil(t, err)
+	assert.Equal(t, list.Value(), ints(0, 6, 3, 5))
+
+	// Assign the list to a slice of itself
+	err = list.SetSlice(Slice{Start: NewInt(0), Stop: NewInt(1)}, list)
+	assert.Nil(t, err)
+	assert.Equal(t, list.Value(), ints(0, 6, 3, 5, 6, 3, 5))
+
+	// The value doesn't share items with the list
+	other := NewList(ints(1, 2))
+	err = list.SetSlice(Slice{Start: NewInt(0), Stop: NewInt(7)}, other)
+	assert.Nil(t, err)
+	assert.Nil(t, other.SetItem(NewInt(0), NewInt(9)))
+	assert.Equal(t, list.Value(), ints(1, 2))
+
+	// Assign to the empty slice at the end, or to all of an empty list
+	end := NewList(ints(1, 2))
+	err = end.SetSlice(Slice{Start: NewInt(2)}, NewList(ints(4)))
+	assert.Nil(t, err)
+	assert.Equal(t, end.Value(), ints(1, 2, 4))
+	empty := NewList(nil)
+	err = empty.SetSlice(Slice{}, NewList(ints(1)))
+	assert.Nil(t, err)
+	assert.Equal(t, empty.Value(), ints(1))
+
+	// Wrong number of items with a step
+	err = list.SetSlice(Slice{Step: NewInt(2)}, NewList(ints(1, 2)))
+	assert.NotNil(t, err)
+
+	// Wrong value type
+	err = list.SetSlice(Slice{Start: NewInt(0), Stop: NewInt(1)}, NewInt(1))
+	assert.NotNil(t, err)
+}
+
 func TestListContains(t *testing.T) {
 	list := NewList([]Object{NewInt(1), NewInt(2), NewInt(3)})
 	assert.Equal(t, list.Contains(NewInt(2)), True)
@@ -363,6 +487,26 @@ func TestListRunOperation(t *testing.T) {
 	// Unsupported type
 	_, err = list1.RunOperation(op.Add, NewInt(1))
 	assert.NotNil(t, err)
+
+	// Repeat
+	result, err = list1.RunOperation(op.Multiply, NewInt(3))
+	assert.Nil(t, err)
+	assert.Equal(t, result.Inspect(), "[1, 2, 1, 2, 1, 2]")
+	result, err = list1.RunOperation(op.Multiply, NewInt(0))
+	assert.Nil(t, err)
+	assert.Equal(t, result.Inspect(), "[]")
+	assert.Equal(t, list1.Inspect(), "[1, 2]")
+
+	// Negative and oversized repeat counts
+	_, err = list1.RunOperation(op.Multiply, NewInt(-1))
+	assert.NotNil(t, err)
+	_, err = list1.RunOperation(op.Multiply, NewInt(math.MaxInt64))
+	assert.NotNil(t, err)
+
+	// An empty list repeats to an empty list at once, however large the count
+	result, err = NewList(nil).RunOperation(op.Multiply, NewInt(math.MaxInt64))
+	assert.Nil(t, err)
+	assert.Equal(t, result.Inspect(), "[]")
 }
 
 func TestListMarshalJSON(t *testing.T) {
@@ -627,6 +771,64 @@ func TestListGetAttrSortError(t *testing.T) {
 	assert.NotNil(t, err)
 }
 
+func TestListSortFunc(t *testing.T) {
+	ctx := context.Background()
+	list := NewList([]Object{NewInt(1), NewInt(3), NewInt(2)})
+	descending := NewBuiltin("desc", func(ctx context.Context, args ...Object) (Object, error) {
+		return Compare(op.GreaterThan, args[0], args[1])
+	})
+
+	result, ok, err := list.CallMethod(ctx, "sort", []Object{descending})
+	assert.True(t, ok)
+	assert.Nil(t, err)
+	assert.Equal(t, result, list)
+	assert.Equal(t, list.Value(), []Object{NewInt(3), NewInt(2), NewInt(1)})
+
+	_, _, err = list.CallMethod(ctx, "sort", []Object{NewInt(1)})
+	assert.Equal(t, err.Error(), "type error: list.sort() expected a function (int given)")
+}
+
+func TestListSortBy(t *testing.T) {
+	ctx := context.Background()
+	// Sorting by length keeps items with the same length in their original
+	// order, and calls the key function once per item
+	list := NewList([]Object{NewString("ccc"), NewString("a"), NewString("bb"), NewString("b")})
+	calls := 0
+	length := NewBuiltin("length", func(ctx context.Context, args ...Object) (Object, error) {
+		calls++
+		return NewInt(int64(len(args[0].(*String).Value()))), nil
+	})
+	_, ok, err := list.CallMethod(ctx, "sort_by", []Object{length})
+	assert.True(t, ok)
+	assert.Nil(t, err)
+	assert.Equal(t, calls, 4)
+	assert.Equal(t, list.Value(), []Object{NewString("a"), NewString("b"), NewString("bb"), NewString("ccc")})
+
+	// Keys that can't be compared are an error, and the list is unchanged
+	list = NewList([]Object{NewInt(1), NewString("x")})
+	identity := NewBuiltin("identity", func(ctx context.Context, args ...Object) (Object, error) {
+		return args[0], nil
+	})
+	_, _, err = list.CallMethod(ctx, "sort_by", []Object{identity})
+	assert.NotNil(t, err)
+	assert.Equal(t, list.Value(), []Object{NewInt(1), NewString("x")})
+}
+
+func TestListSortIncomparable(t *testing.T) {
+	ctx := context.Background()
+	for _, items := range [][]Object{
+		{NewInt(2), NewString("a"), NewInt(1)},
+		{NewString("a"), NewInt(1)},
+		{NewInt(1), NewMap(nil)},
+		{NewMap(nil), NewMap(nil)},
+	} {
+		list := NewList(slices.Clone(items))
+		_, _, err := list.CallMethod(ctx, "sort", nil)
+		assert.NotNil(t, err)
+		assert.Equal(t, list.Value(), items)
+	}
+}
+
 func TestListGetAttrInvalid(t *testing.T) {
 	list := NewList(nil)
 	_, ok := list.GetAttr("invalid_method")
@@ -708,6 +910,59 @@ func TestResolveIntSlice(t *testing.T) {
 	assert.NotNil(t, err)
 }
 
+func TestResolveSlice(t *testing.T) {
+	// No step
+	start, stop, step, err := ResolveSlice(Slice{Start: NewInt(1), Stop: NewInt(3)}, 5)
+	assert.Nil(t, err)
+	assert.Equal(t, []int64{start, stop, step}, []int64{1, 3, 1})
+
+	// Positive step with default bounds
+	start, stop, step, err = ResolveSlice(Slice{Step: NewInt(2)}, 5)
+	assert.Nil(t, err)
+	assert.Equal(t, []int64{start, stop, step}, []int64{0, 5, 2})
+
+	// Negative step with default bounds
+	start, stop, step, err = ResolveSlice(Slice{Step: NewInt(-1)}, 5)
+	assert.Nil(t, err)
+	assert.Equal(t, []int64{start, stop, step}, []int64{4, -1, -1})
+
+	// Negative step with negative bounds
+	start, stop, step, err = ResolveSlice(Slice{Start: NewInt(-1), Stop: NewInt(-4), Step: NewInt(-2)}, 5)
+	assert.Nil(t, err)
+	assert.Equal(t, []int64{start, stop, step}, []int64{4, 1, -2})
+
+	// Zero step
+	_, _, _, err = ResolveSlice(Slice{Step: NewInt(0)}, 5)
+	assert.NotNil(t, err)
+
+	// Wrong step type
+	_, _, _, err = ResolveSlice(Slice{Step: NewString("x")}, 5)
+	assert.NotNil(t, err)
+
+	// Start < stop with a negative step
+	_, _, _, err = ResolveSlice(Slice{Start: NewInt(1), Stop: NewInt(3), Step: NewInt(-1)}, 5)
+	assert.NotNil(t, err)
+
+	// Start out of range with a negative step
+	_, _, _, err = ResolveSlice(Slice{Start: NewInt(5), Step: NewInt(-1)}, 5)
+	assert.NotNil(t, err)
+}
+
+func TestGetSliceStep(t *testing.T) {
+	list := NewList([]Object{NewInt(0), NewInt(1), NewInt(2), NewInt(3), NewInt(4)})
+	result, err := list.GetSlice(Slice{Start: NewInt(4), Stop: NewInt(0), Step: NewInt(-3)})
+	assert.Nil(t, err)
+	assert.Equal(t, result, NewList([]Object{NewInt(4), NewInt(1)}))
+
+	result, err = NewString("abcdef").GetSlice(Slice{Step: NewInt(-2)})
+	assert.Nil(t, err)
+	assert.Equal(t, result, NewString("fdb"))
+
+	result, err = NewBytes([]byte("abc")).GetSlice(Slice{Step: NewInt(-1)})
+	assert.Nil(t, err)
+	assert.Equal(t, result, NewBytes([]byte("cba")))
+}
+
 // mockCallFunc creates a context with a CallFunc that invokes the closure.
 // This simulates what the VM does at runtime.
 func mockCallFunc(ctx context.Context) context.Context {
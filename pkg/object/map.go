@@ -4,9 +4,12 @@ import (
 	"bytes"
 	"context"
 	"encoding/json"
+	"errors"
 	"fmt"
+	"maps"
 	"sort"
 	"strings"
+	"sync/atomic"
 
 	"github.com/deepnoodle-ai/risor/v2/pkg/op"
 )
@@ -82,12 +85,16 @@ func init() {
 		OptionalArg("default").
 		Returns("any").
 		Impl(func(m *Map, ctx context.Context, args ...Object) (Object, error) {
+			if err := m.checkMutable(); err != nil {
+				return nil, err
+			}
 			key, err := Arg[*String](args, 0, "map.pop")
 			if err != nil {
 				return nil, err
 			}
 			value, found := m.items[key.value]
 			if found {
+				m.own()
 				delete(m.items, key.value)
 				return value, nil
 			}
@@ -103,11 +110,15 @@ func init() {
 		Args("key", "value").
 		Returns("any").
 		Impl(func(m *Map, ctx context.Context, args ...Object) (Object, error) {
+			if err := m.checkMutable(); err != nil {
+				return nil, err
+			}
 			key, err := Arg[*String](args, 0, "map.setdefault")
 			if err != nil {
 				return nil, err
 			}
 			if _, found := m.items[key.value]; !found {
+				m.own()
 				m.items[key.value] = args[1]
 			}
 			return m.items[key.value], nil
@@ -119,6 +130,9 @@ func init() {
 		Arg("other").
 		Returns("null").
 		Impl(func(m *Map, ctx context.Context, args ...Object) (Object, error) {
+			if err := m.checkMutable(); err != nil {
+				return nil, err
+			}
 			other, ok := args[0].(*Map)
 			if !ok {
 				return nil, newTypeErrorf("map.update() expected a map (%s given)", args[0].Type())
@@ -127,9 +141,7 @@ func init() {
 			if other == m {
 				return Nil, nil
 			}
-			for k, v := range other.items {
-				m.items[k] = v
-			}
+			m.Update(other)
 			return Nil, nil
 		})
 
@@ -138,7 +150,10 @@ func init() {
 		Doc("Remove all items").
 		Returns("null").
 		Impl(func(m *Map, ctx context.Context, args ...Object) (Object, error) {
-			m.items = map[string]Object{}
+			if err := m.checkMutable(); err != nil {
+				return nil, err
+			}
+			m.Clear()
 			return Nil, nil
 		})
 
@@ -154,9 +169,20 @@ func init() {
 type Map struct {
 	items map[string]Object
 
-	// Used to avoid the possibility of infinite recursion when inspecting.
-	// Similar to the usage of Py_ReprEnter in CPython.
-	inspectActive bool
+	// shared is set when items may also be held by another map, in which
+	// case they are copied before this map is modified. It is atomic since
+	// copying a map sets it, and frozen maps may be copied concurrently.
+	shared atomic.Bool
+
+	// frozen is set by Freeze to make the map read-only for Risor code
+	frozen bool
+
+	// Used to avoid the possibility of infinite recursion when inspecting,
+	// converting, or marshaling a map that contains itself. Similar to the
+	// usage of Py_ReprEnter in CPython.
+	inspectActive   bool
+	interfaceActive bool
+	marshalActive   bool
 }
 
 func (m *Map) Type() Type {
@@ -188,11 +214,16 @@ func (m *Map) String() string {
 	return m.Inspect()
 }
 
+// Value returns the items in the map. The map may be shared with other maps
+// and must not be modified.
 func (m *Map) Value() map[string]Object {
 	return m.items
 }
 
 func (m *Map) SetAttr(name string, value Object) error {
+	if err := m.checkMutable(); err != nil {
+		return err
+	}
 	// Dot syntax only updates existing keys. Use bracket syntax to add new keys.
 	if _, exists := m.items[name]; !exists {
 		return fmt.Errorf("key error: %q does not exist (use m[%q] = value to add new keys)", name, name)
@@ -216,6 +247,10 @@ func (m *Map) GetAttr(name string) (Object, bool) {
 	return o, ok
 }
 
+func (m *Map) CallMethod(ctx context.Context, name string, args []Object) (Object, bool, error) {
+	return mapMethods.CallMethod(ctx, m, name, args)
+}
+
 func (m *Map) ListItems() *List {
 	items := make([]Object, 0, len(m.items))
 	for _, k := range m.SortedKeys() {
@@ -224,21 +259,47 @@ func (m *Map) ListItems() *List {
 	return NewList(items)
 }
 
+// own gives the map its own copy of its items if they are shared with
+// another map. It must be called before the items are modified.
+func (m *Map) own() {
+	if m.shared.Load() {
+		m.items = maps.Clone(m.items)
+		m.shared.Store(false)
+	}
+}
+
+// IsFrozen reports whether the map has been made read-only by Freeze.
+func (m *Map) IsFrozen() bool {
+	return m.frozen
+}
+
+// checkMutable returns an error if the map is frozen.
+func (m *Map) checkMutable() *Error {
+	if m.frozen {
+		return TypeErrorf("cannot modify a frozen map")
+	}
+	return nil
+}
+
 func (m *Map) Clear() {
 	m.items = map[string]Object{}
+	m.shared.Store(false)
 }
 
+// Copy returns a shallow copy of the map. The copy shares its items with the
+// original until either map is modified, so copying is cheap even for large
+// maps.
 func (m *Map) Copy() *Map {
-	items := make(map[string]Object, len(m.items))
-	for k, v := range m.items {
-		items[k] = v
-	}
-	return &Map{items: items}
+	m.shared.Store(true)
+	other := &Map{items: m.items}
+	other.shared.Store(true)
+	return other
 }
 
 func (m *Map) Pop(key string, def Object) Object {
 	value, found := m.items[key]
 	if found {
+		m.own()
 		delete(m.items, key)
 		return value
 	}
@@ -250,12 +311,14 @@ func (m *Map) Pop(key string, def Object) Object {
 
 func (m *Map) SetDefault(key string, value Object) Object {
 	if _, found := m.items[key]; !found {
+		m.own()
 		m.items[key] = value
 	}
 	return m.items[key]
 }
 
 func (m *Map) Update(other *Map) {
+	m.own()
 	for k, v := range other.items {
 		m.items[k] = v
 	}
@@ -311,11 +374,13 @@ func (m *Map) GetWithDefault(key string, defaultValue Object) Object {
 }
 
 func (m *Map) Delete(key string) Object {
+	m.own()
 	delete(m.items, key)
 	return Nil
 }
 
 func (m *Map) Set(key string, value Object) {
+	m.own()
 	m.items[key] = value
 }
 
@@ -324,6 +389,14 @@ func (m *Map) Size() int {
 }
 
 func (m *Map) Interface() interface{} {
+	// A map that contains itself is converted to a placeholder where it
+	// repeats, as when it is inspected
+	if m.interfaceActive {
+		return "{...}"
+	}
+	m.interfaceActive = true
+	defer func() { m.interfaceActive = false }()
+
 	result := make(map[string]any, len(m.items))
 	for k, v := range m.items {
 		result[k] = v.Interface()
@@ -331,28 +404,24 @@ func (m *Map) Interface() interface{} {
 	return result
 }
 
+// Equals reports whether other is a map with the same keys and equal values;
+// see DeepEqual.
 func (m *Map) Equals(other Object) bool {
-	otherMap, ok := other.(*Map)
-	if !ok {
-		return false
-	}
-	if len(m.items) != len(otherMap.items) {
-		return false
-	}
-	for k, v := range m.items {
-		otherValue, found := otherMap.items[k]
-		if !found {
-			return false
-		}
-		if !v.Equals(otherValue) {
-			return false
-		}
-	}
-	return true
+	return DeepEqual(m, other)
 }
 
+// RunOperation implements the binary operators for maps. Adding two maps
+// merges them into a new map, where the right map's value wins for a key in
+// both.
 func (m *Map) RunOperation(opType op.BinaryOpType, right Object) (Object, error) {
-	return nil, newTypeErrorf("unsupported operation for map: %v", opType)
+	other, ok := right.(*Map)
+	if !ok || opType != op.Add {
+		return nil, newTypeErrorf("unsupported operation for map: %v on type %s", opType, right.Type())
+	}
+	items := make(map[string]Object, len(m.items)+len(other.items))
+	maps.Copy(items, m.items)
+	maps.Copy(items, other.items)
+	return NewMap(items), nil
 }
 
 func (m *Map) GetItem(key Object) (Object, *Error) {
@@ -374,20 +443,33 @@ func (m *Map) GetSlice(s Slice) (Object, *Error) {
 
 // SetItem assigns a value to the given key in the map.
 func (m *Map) SetItem(key, value Object) *Error {
+	if err := m.checkMutable(); err != nil {
+		return err
+	}
 	strObj, ok := key.(*String)
 	if !ok {
 		return TypeErrorf("map key must be a string (got %s)", key.Type())
 	}
+	m.own()
 	m.items[strObj.value] = value
 	return nil
 }
 
+// SetSlice implements the [start:stop] = value operator for a container type.
+func (m *Map) SetSlice(s Slice, value Object) *Error {
+	return TypeErrorf("map does not support slice operations")
+}
+
 // DelItem deletes the item with the given key from the map.
 func (m *Map) DelItem(key Object) *Error {
+	if err := m.checkMutable(); err != nil {
+		return err
+	}
 	strObj, ok := key.(*String)
 	if !ok {
 		return TypeErrorf("map key must be a string (got %s)", key.Type())
 	}
+	m.own()
 	delete(m.items, strObj.value)
 	return nil
 }
@@ -428,6 +510,11 @@ func (m *Map) StringKeys() []string {
 }
 
 func (m *Map) MarshalJSON() ([]byte, error) {
+	if m.marshalActive {
+		return nil, errors.New("marshal error: unable to marshal a map that contains itself")
+	}
+	m.marshalActive = true
+	defer func() { m.marshalActive = false }()
 	return json.Marshal(m.items)
 }
 
@@ -2,6 +2,8 @@ package object
 
 import (
 	"context"
+	"encoding/json"
+	"sync"
 	"testing"
 
 	"github.com/deepnoodle-ai/risor/v2/pkg/op"
@@ -36,6 +38,16 @@ func TestMapInspectSelfReference(t *testing.T) {
 	assert.Equal(t, inspect, `{"self": {...}}`)
 }
 
+func TestMapSelfReferenceConversion(t *testing.T) {
+	m := NewMap(map[string]Object{"a": NewInt(1)})
+	m.Set("self", m)
+	assert.Equal(t, m.Interface(), map[string]any{"a": int64(1), "self": "{...}"})
+
+	_, err := json.Marshal(m)
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "contains itself")
+}
+
 func TestMapString(t *testing.T) {
 	m := NewMap(map[string]Object{"x": NewInt(42)})
 	assert.Equal(t, m.String(), `{"x": 42}`)
@@ -146,6 +158,40 @@ func TestMapCopy(t *testing.T) {
 	assert.Equal(t, copyMap.Get("key").(*Int).Value(), int64(42))
 }
 
+func TestMapCopyOnWrite(t *testing.T) {
+	m := NewMap(map[string]Object{"a": NewInt(1), "b": NewInt(2)})
+	copyMap := m.Copy()
+
+	// Modifying the copy leaves the original alone
+	copyMap.Delete("a")
+	copyMap.SetItem(NewString("c"), NewInt(3))
+	assert.Equal(t, m.Inspect(), `{"a": 1, "b": 2}`)
+	assert.Equal(t, copyMap.Inspect(), `{"b": 2, "c": 3}`)
+
+	// And modifying the original leaves the copy alone
+	other := m.Copy()
+	m.Update(NewMap(map[string]Object{"b": NewInt(20)}))
+	m.Pop("a", nil)
+	assert.Equal(t, m.Inspect(), `{"b": 20}`)
+	assert.Equal(t, other.Inspect(), `{"a": 1, "b": 2}`)
+}
+
+func TestMapCopyConcurrent(t *testing.T) {
+	// Scripts running in parallel may copy the same frozen map
+	m := Freeze(NewMap(map[string]Object{"a": NewInt(1)})).(*Map)
+	var wg sync.WaitGroup
+	for range 8 {
+		wg.Add(1)
+		go func() {
+			defer wg.Done()
+			copyMap := m.Copy()
+			copyMap.Set("b", NewInt(2))
+		}()
+	}
+	wg.Wait()
+	assert.Equal(t, m.Inspect(), `{"a": 1}`)
+}
+
 func TestMapPop(t *testing.T) {
 	m := NewMap(map[string]Object{"key": NewInt(42)})
 
@@ -270,6 +316,18 @@ func TestMapRunOperation(t *testing.T) {
 	m := NewMap(nil)
 	_, err := m.RunOperation(op.Add, NewInt(1))
 	assert.NotNil(t, err)
+
+	// Adding maps merges them, preferring the right map's values
+	a := NewMap(map[string]Object{"x": NewInt(1), "y": NewInt(2)})
+	b := NewMap(map[string]Object{"y": NewInt(3), "z": NewInt(4)})
+	result, err := a.RunOperation(op.Add, b)
+	assert.Nil(t, err)
+	assert.Equal(t, result.Inspect(), `{"x": 1, "y": 3, "z": 4}`)
+	assert.Equal(t, a.Inspect(), `{"x": 1, "y": 2}`)
+	assert.Equal(t, b.Inspect(), `{"y": 3, "z": 4}`)
+
+	_, err = a.RunOperation(op.Subtract, b)
+	assert.NotNil(t, err)
 }
 
 func TestMapGetItem(t *testing.T) {
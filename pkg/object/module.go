@@ -3,6 +3,7 @@ package object
 import (
 	"context"
 	"fmt"
+	"slices"
 
 	"github.com/deepnoodle-ai/risor/v2/pkg/bytecode"
 	"github.com/deepnoodle-ai/risor/v2/pkg/op"
@@ -26,12 +27,35 @@ type Module struct {
 	globals      []Object
 	globalsIndex map[string]int
 	callable     BuiltinFunction
+	doc          string
+	specs        []FuncSpec
 }
 
+// Attrs returns the module's registry attributes, such as __name__, followed
+// by its builtins and globals in sorted order. Members are described by the
+// documentation attached with WithDocs, if any.
 func (m *Module) Attrs() []AttrSpec {
-	// Module has both registry attrs and dynamic attrs from builtins/globals
-	// For introspection, we only return the registry specs
-	return moduleAttrs.Specs()
+	specs := moduleAttrs.Specs()
+	names := make([]string, 0, len(m.builtins)+len(m.globalsIndex))
+	for name := range m.builtins {
+		names = append(names, name)
+	}
+	for name := range m.globalsIndex {
+		if _, found := m.builtins[name]; !found {
+			names = append(names, name)
+		}
+	}
+	slices.Sort(names)
+	for _, name := range names {
+		spec := AttrSpec{Name: name}
+		if i := slices.IndexFunc(m.specs, func(fs FuncSpec) bool { return fs.Name == name }); i >= 0 {
+			spec.Doc = m.specs[i].Doc
+			spec.Args = m.specs[i].Args
+			spec.Returns = m.specs[i].Returns
+		}
+		specs = append(specs, spec)
+	}
+	return specs
 }
 
 func (m *Module) GetAttr(name string) (Object, bool) {
@@ -189,9 +213,35 @@ func NewBuiltinsModule(name string, contents map[string]Object, callableOption .
 		globalsIndex: map[string]int{},
 	}
 	for _, v := range builtins {
-		if builtin, ok := v.(*Builtin); ok {
+		// Builtins that already belong to a module, e.g. ones re-exported by
+		// a script module, keep their original module.
+		if builtin, ok := v.(*Builtin); ok && builtin.module == nil {
 			builtin.module = m
 		}
 	}
 	return m
 }
+
+// WithDocs attaches documentation to the module: a summary of the module and
+// specs for its members, which help() shows. Builtins in the module that
+// match a spec by name and have no documentation of their own get the spec.
+func (m *Module) WithDocs(doc string, specs []FuncSpec) *Module {
+	m.doc = doc
+	m.specs = specs
+	for _, spec := range specs {
+		if builtin, ok := m.builtins[spec.Name].(*Builtin); ok && builtin.spec == nil {
+			builtin.WithSpec(spec)
+		}
+	}
+	return m
+}
+
+// Doc returns the module's summary, if it has one.
+func (m *Module) Doc() string {
+	return m.doc
+}
+
+// Specs returns the documentation of the module's members.
+func (m *Module) Specs() []FuncSpec {
+	return m.specs
+}
@@ -41,6 +41,10 @@ func (n *NilType) Compare(other Object) (int, error) {
 	return 0, TypeErrorf("unable to compare null and %s", other.Type())
 }
 
+func (n *NilType) HashKey() HashKey {
+	return HashKey{Type: NIL}
+}
+
 func (n *NilType) Equals(other Object) bool {
 	_, ok := other.(*NilType)
 	return ok
@@ -30,30 +30,35 @@ type Type string
 
 // Type constants
 const (
-	BOOL          Type = "bool"
-	BUILTIN       Type = "builtin"
-	BYTE          Type = "byte"
-	BYTES         Type = "bytes"
-	CELL          Type = "cell"
-	COLOR         Type = "color"
-	COMPLEX       Type = "complex"
-	COMPLEX_SLICE Type = "complex_slice"
-	DYNAMIC_ATTR  Type = "dynamic_attr"
-	ERROR         Type = "error"
-	FLOAT         Type = "float"
-	FUNCTION      Type = "function"
-	INT           Type = "int"
-	LIST          Type = "list"
-	MAP           Type = "map"
-	MODULE        Type = "module"
-	NIL           Type = "null"
-	PARTIAL       Type = "partial"
-	RANGE         Type = "range"
-	RESULT        Type = "result"
-	STRING        Type = "string"
-	TIME          Type = "time"
-	GOFUNC        Type = "go_func"
-	GOSTRUCT      Type = "go_struct"
+	BOOL           Type = "bool"
+	BUILTIN        Type = "builtin"
+	BYTE           Type = "byte"
+	BYTES          Type = "bytes"
+	CELL           Type = "cell"
+	COLOR          Type = "color"
+	COMPLEX        Type = "complex"
+	COMPLEX_SLICE  Type = "complex_slice"
+	DECIMAL        Type = "decimal"
+	DYNAMIC_ATTR   Type = "dynamic_attr"
+	ERROR          Type = "error"
+	FLOAT          Type = "float"
+	FUNCTION       Type = "function"
+	INT            Type = "int"
+	LIST           Type = "list"
+	MAP            Type = "map"
+	MODULE         Type = "module"
+	NIL            Type = "null"
+	PARTIAL        Type = "partial"
+	QUOTE          Type = "quote"
+	RANGE          Type = "range"
+	RESOURCE       Type = "resource"
+	RESULT         Type = "result"
+	STRING         Type = "string"
+	STRING_BUILDER Type = "stringbuilder"
+	TIME           Type = "time"
+	TUPLE          Type = "tuple"
+	GOFUNC         Type = "go_func"
+	GOSTRUCT       Type = "go_struct"
 )
 
 var (
@@ -99,6 +104,7 @@ type Object interface {
 type Slice struct {
 	Start Object
 	Stop  Object
+	Step  Object // nil for a step of 1
 }
 
 // Enumerable is an interface for types that can be iterated with a callback.
@@ -119,6 +125,10 @@ type Container interface {
 	// SetItem implements the [key] = value operator for a container type.
 	SetItem(key, value Object) *Error
 
+	// SetSlice implements the [start:stop] = value operator for a container
+	// type.
+	SetSlice(s Slice, value Object) *Error
+
 	// DelItem implements the del [key] operator for a container type.
 	DelItem(key Object) *Error
 
@@ -165,6 +175,14 @@ func CompareTypes(a, b Object) int {
 	return 0
 }
 
+// MethodCaller is implemented by objects that can call their methods directly,
+// without first creating a bound method object. The VM uses it for method call
+// expressions like items.map(fn). CallMethod reports false if the object has
+// no method with the given name.
+type MethodCaller interface {
+	CallMethod(ctx context.Context, name string, args []Object) (Object, bool, error)
+}
+
 // AttrResolver is an interface used to resolve dynamic attributes on an object.
 type AttrResolver interface {
 	ResolveAttr(ctx context.Context, name string) (Object, error)
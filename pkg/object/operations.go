@@ -1,6 +1,8 @@
 package object
 
 import (
+	"math"
+
 	"github.com/deepnoodle-ai/risor/v2/pkg/op"
 )
 
@@ -23,20 +25,30 @@ func Compare(opType op.CompareOpType, a, b Object) (Object, error) {
 		return nil, err
 	}
 
+	// As in IEEE 754, NaN is unordered: every ordering comparison with NaN
+	// is false, even though sorting puts NaN first
+	ordered := !isNaN(a) && !isNaN(b)
+
 	switch opType {
 	case op.LessThan:
-		return NewBool(value < 0), nil
+		return NewBool(ordered && value < 0), nil
 	case op.LessThanOrEqual:
-		return NewBool(value <= 0), nil
+		return NewBool(ordered && value <= 0), nil
 	case op.GreaterThan:
-		return NewBool(value > 0), nil
+		return NewBool(ordered && value > 0), nil
 	case op.GreaterThanOrEqual:
-		return NewBool(value >= 0), nil
+		return NewBool(ordered && value >= 0), nil
 	default:
 		return nil, EvalErrorf("eval error: unknown object comparison operator: %d", opType)
 	}
 }
 
+// isNaN reports whether obj is a float with the value NaN.
+func isNaN(obj Object) bool {
+	f, ok := obj.(*Float)
+	return ok && math.IsNaN(f.value)
+}
+
 // BinaryOp performs a binary operation on two objects, given an operator.
 func BinaryOp(opType op.BinaryOpType, a, b Object) (Object, error) {
 	switch opType {
@@ -1,6 +1,7 @@
 package object
 
 import (
+	"math"
 	"testing"
 
 	"github.com/deepnoodle-ai/risor/v2/pkg/op"
@@ -72,3 +73,39 @@ func TestOrOperator(t *testing.T) {
 		assert.Equal(t, result, tc.want)
 	}
 }
+
+func TestCompareNaN(t *testing.T) {
+	nan := NewFloat(math.NaN())
+	for _, other := range []Object{nan, NewFloat(1), NewInt(1), NewByte(1), NewFloat(math.Inf(-1))} {
+		for _, opType := range []op.CompareOpType{
+			op.LessThan, op.LessThanOrEqual, op.GreaterThan, op.GreaterThanOrEqual, op.Equal,
+		} {
+			result, err := Compare(opType, nan, other)
+			assert.Nil(t, err)
+			assert.Equal(t, result, False, "nan %s %s", opType, other.Inspect())
+
+			result, err = Compare(opType, other, nan)
+			assert.Nil(t, err)
+			assert.Equal(t, result, False, "%s %s nan", other.Inspect(), opType)
+		}
+		result, err := Compare(op.NotEqual, nan, other)
+		assert.Nil(t, err)
+		assert.Equal(t, result, True)
+	}
+}
+
+func TestSortNaN(t *testing.T) {
+	// NaN sorts before all other numbers, and the sort is stable
+	nanA := NewFloat(math.NaN())
+	nanB := NewFloat(math.NaN())
+	two := NewFloat(2)
+	items := []Object{NewInt(3), nanA, NewInt(1), nanB, two, NewInt(2), NewFloat(math.Inf(-1))}
+	assert.Nil(t, Sort(items))
+	assert.True(t, items[0] == nanA)
+	assert.True(t, items[1] == nanB)
+	assert.Equal(t, items[2], NewFloat(math.Inf(-1)))
+	assert.Equal(t, items[3], NewInt(1))
+	assert.True(t, items[4] == two)
+	assert.Equal(t, items[5], NewInt(2))
+	assert.Equal(t, items[6], NewInt(3))
+}
@@ -0,0 +1,91 @@
+package object
+
+import (
+	"encoding/json"
+	"fmt"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/op"
+)
+
+var quoteAttrs = NewAttrRegistry[*Quote]("quote")
+
+func init() {
+	quoteAttrs.Define("source").
+		Doc("The quoted source code").
+		Returns("string").
+		Getter(func(q *Quote) Object {
+			return NewString(q.source)
+		})
+}
+
+// Quote is a block of code held as a value. Quotes are created by quote
+// expressions and the compile builtin, and are run with the run builtin.
+type Quote struct {
+	source string
+}
+
+// NewQuote returns a quote of the given source code. The source isn't
+// checked here; it's parsed when the quote is run.
+func NewQuote(source string) *Quote {
+	return &Quote{source: source}
+}
+
+// Source returns the quoted source code.
+func (q *Quote) Source() string {
+	return q.source
+}
+
+func (q *Quote) Attrs() []AttrSpec {
+	return quoteAttrs.Specs()
+}
+
+func (q *Quote) GetAttr(name string) (Object, bool) {
+	return quoteAttrs.GetAttr(q, name)
+}
+
+func (q *Quote) SetAttr(name string, value Object) error {
+	return TypeErrorf("quote has no attribute %q", name)
+}
+
+func (q *Quote) Type() Type {
+	return QUOTE
+}
+
+func (q *Quote) Inspect() string {
+	return fmt.Sprintf("quote { %s }", q.source)
+}
+
+func (q *Quote) String() string {
+	return q.source
+}
+
+func (q *Quote) Interface() interface{} {
+	return q.source
+}
+
+func (q *Quote) IsTruthy() bool {
+	return true
+}
+
+func (q *Quote) Equals(other Object) bool {
+	otherQuote, ok := other.(*Quote)
+	return ok && q.source == otherQuote.source
+}
+
+func (q *Quote) RunOperation(opType op.BinaryOpType, right Object) (Object, error) {
+	return nil, newTypeErrorf("unsupported operation for quote: %v", opType)
+}
+
+func (q *Quote) MarshalJSON() ([]byte, error) {
+	return json.Marshal(q.source)
+}
+
+// UnquoteSource returns the source code that unquote splices into a quote
+// for the given value. A quote is spliced as its own source, and other
+// values as the code ToSource writes for them.
+func UnquoteSource(obj Object) (string, error) {
+	if q, ok := obj.(*Quote); ok {
+		return q.source, nil
+	}
+	return ToSource(obj, "")
+}
@@ -80,6 +80,10 @@ func (r *Range) GetAttr(name string) (Object, bool) {
 	return rangeAttrs.GetAttr(r, name)
 }
 
+func (r *Range) CallMethod(ctx context.Context, name string, args []Object) (Object, bool, error) {
+	return rangeAttrs.CallMethod(ctx, r, name, args)
+}
+
 func (r *Range) SetAttr(name string, value Object) error {
 	return fmt.Errorf("attribute error: range object does not support attribute assignment")
 }
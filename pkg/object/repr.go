@@ -0,0 +1,79 @@
+package object
+
+import (
+	"fmt"
+	"strconv"
+	"strings"
+)
+
+// Repr returns a debugging representation of obj that shows its type as
+// well as its value. Unlike the string() conversion, strings are quoted with
+// control characters escaped, floats always have a decimal point or exponent
+// so they can't be mistaken for ints, and bytes are shown as byte(n). Items
+// of lists, tuples, and maps are shown the same way. Other objects use their
+// Inspect method.
+func Repr(obj Object) string {
+	var sb strings.Builder
+	writeRepr(&sb, obj, map[Object]bool{})
+	return sb.String()
+}
+
+func writeRepr(sb *strings.Builder, obj Object, active map[Object]bool) {
+	switch obj := obj.(type) {
+	case *String:
+		sb.WriteString(strconv.Quote(obj.value))
+	case *Float:
+		s := FormatFloat(obj.value)
+		if !strings.ContainsAny(s, ".eIN") {
+			s += ".0"
+		}
+		sb.WriteString(s)
+	case *Byte:
+		fmt.Fprintf(sb, "byte(%d)", obj.value)
+	case *List:
+		if active[obj] {
+			sb.WriteString("[...]")
+			return
+		}
+		active[obj] = true
+		defer delete(active, obj)
+		sb.WriteString("[")
+		writeReprItems(sb, obj.items, active)
+		sb.WriteString("]")
+	case *Tuple:
+		sb.WriteString("(")
+		writeReprItems(sb, obj.items, active)
+		if len(obj.items) == 1 {
+			sb.WriteString(",")
+		}
+		sb.WriteString(")")
+	case *Map:
+		if active[obj] {
+			sb.WriteString("{...}")
+			return
+		}
+		active[obj] = true
+		defer delete(active, obj)
+		sb.WriteString("{")
+		for i, k := range obj.SortedKeys() {
+			if i > 0 {
+				sb.WriteString(", ")
+			}
+			sb.WriteString(strconv.Quote(k))
+			sb.WriteString(": ")
+			writeRepr(sb, obj.items[k], active)
+		}
+		sb.WriteString("}")
+	default:
+		sb.WriteString(obj.Inspect())
+	}
+}
+
+func writeReprItems(sb *strings.Builder, items []Object, active map[Object]bool) {
+	for i, item := range items {
+		if i > 0 {
+			sb.WriteString(", ")
+		}
+		writeRepr(sb, item, active)
+	}
+}
@@ -0,0 +1,194 @@
+package object
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"runtime"
+	"sort"
+	"sync"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/op"
+)
+
+var resourceAttrs = NewAttrRegistry[*Resource]("resource")
+
+func init() {
+	resourceAttrs.Define("close").
+		Doc("Release the resource. Closing it again does nothing").
+		Returns("null").
+		Impl(func(r *Resource, ctx context.Context, args ...Object) (Object, error) {
+			if err := r.Close(); err != nil {
+				return nil, err
+			}
+			return Nil, nil
+		})
+
+	resourceAttrs.Define("closed").
+		Doc("Whether the resource has been closed").
+		Returns("bool").
+		Getter(func(r *Resource) Object {
+			return NewBool(r.Closed())
+		})
+}
+
+// Resource wraps a host resource, such as a file or a network connection,
+// that must be released once scripts are done with it. Scripts release it by
+// calling close(), and the host can call Close. If neither happens, the
+// release function runs after the garbage collector finds the resource
+// unreachable, so a script that forgets to close a resource doesn't leak it.
+// That may happen much later, or not before the program exits, so scripts
+// should still close resources explicitly.
+//
+// The release function is called at most once, and may be called from
+// another goroutine. Methods added with WithMethod fail once the resource is
+// closed.
+type Resource struct {
+	name    string
+	value   any
+	methods map[string]*Builtin
+	state   *resourceState
+	cleanup runtime.Cleanup
+}
+
+// resourceState is kept apart from the Resource so that the cleanup
+// registered for the Resource doesn't keep it reachable.
+type resourceState struct {
+	mu      sync.Mutex
+	closed  bool
+	release func() error
+}
+
+func (s *resourceState) close() error {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	if s.closed {
+		return nil
+	}
+	s.closed = true
+	if s.release == nil {
+		return nil
+	}
+	return s.release()
+}
+
+// NewResource returns a Resource that wraps value, which Interface returns.
+// The name describes the kind of resource, such as "file", and is used in
+// error messages. The release function, which may be nil, is called when the
+// resource is closed or garbage collected.
+func NewResource(name string, value any, release func() error) *Resource {
+	r := &Resource{
+		name:    name,
+		value:   value,
+		methods: map[string]*Builtin{},
+		state:   &resourceState{release: release},
+	}
+	r.cleanup = runtime.AddCleanup(r, func(s *resourceState) {
+		// There is no one to report the error to at this point
+		_ = s.close()
+	}, r.state)
+	return r
+}
+
+// WithMethod adds a method that scripts can call on the resource. Calling it
+// after the resource is closed is a value error. The close method and closed
+// attribute can't be replaced.
+//
+// The method holds the resource for the duration of each call, so a method
+// that a script detached from the resource, as in let read = f.read, keeps
+// the resource from being released by the garbage collector while it runs.
+func (r *Resource) WithMethod(name string, fn BuiltinFunction) *Resource {
+	r.methods[name] = NewBuiltin(name, func(ctx context.Context, args ...Object) (Object, error) {
+		defer runtime.KeepAlive(r)
+		if r.Closed() {
+			return nil, ValueErrorf("%s is closed", r.name)
+		}
+		return fn(ctx, args...)
+	}).InModule(r.name)
+	return r
+}
+
+// Close releases the resource, returning the error from the release
+// function. Closing a resource that is already closed does nothing.
+func (r *Resource) Close() error {
+	r.cleanup.Stop()
+	return r.state.close()
+}
+
+// Closed reports whether the resource has been closed.
+func (r *Resource) Closed() bool {
+	r.state.mu.Lock()
+	defer r.state.mu.Unlock()
+	return r.state.closed
+}
+
+// Name returns the kind of resource, as given to NewResource.
+func (r *Resource) Name() string {
+	return r.name
+}
+
+func (r *Resource) Type() Type {
+	return RESOURCE
+}
+
+func (r *Resource) Inspect() string {
+	if r.Closed() {
+		return fmt.Sprintf("resource(%s, closed)", r.name)
+	}
+	return fmt.Sprintf("resource(%s)", r.name)
+}
+
+func (r *Resource) String() string {
+	return r.Inspect()
+}
+
+func (r *Resource) Interface() any {
+	return r.value
+}
+
+func (r *Resource) Equals(other Object) bool {
+	return r == other
+}
+
+func (r *Resource) IsTruthy() bool {
+	return true
+}
+
+func (r *Resource) Attrs() []AttrSpec {
+	specs := resourceAttrs.Specs()
+	names := make([]string, 0, len(r.methods))
+	for name := range r.methods {
+		names = append(names, name)
+	}
+	sort.Strings(names)
+	for _, name := range names {
+		specs = append(specs, AttrSpec{Name: name})
+	}
+	return specs
+}
+
+func (r *Resource) GetAttr(name string) (Object, bool) {
+	if attr, ok := resourceAttrs.GetAttr(r, name); ok {
+		return attr, true
+	}
+	if method, ok := r.methods[name]; ok {
+		return method, true
+	}
+	return nil, false
+}
+
+func (r *Resource) CallMethod(ctx context.Context, name string, args []Object) (Object, bool, error) {
+	return resourceAttrs.CallMethod(ctx, r, name, args)
+}
+
+func (r *Resource) SetAttr(name string, value Object) error {
+	return TypeErrorf("cannot set attributes on a %s resource", r.name)
+}
+
+func (r *Resource) RunOperation(opType op.BinaryOpType, right Object) (Object, error) {
+	return nil, newTypeErrorf("unsupported operation for resource: %v", opType)
+}
+
+func (r *Resource) MarshalJSON() ([]byte, error) {
+	return nil, errors.New("marshal error: unable to marshal resource")
+}
@@ -0,0 +1,102 @@
+package object
+
+import (
+	"context"
+	"errors"
+	"runtime"
+	"sync/atomic"
+	"testing"
+	"time"
+
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+func TestResourceClose(t *testing.T) {
+	ctx := context.Background()
+	releases := 0
+	r := NewResource("file", "data.txt", func() error {
+		releases++
+		return nil
+	}).WithMethod("name", func(ctx context.Context, args ...Object) (Object, error) {
+		return NewString("data.txt"), nil
+	})
+	assert.Equal(t, r.Type(), RESOURCE)
+	assert.Equal(t, r.Inspect(), "resource(file)")
+	assert.Equal(t, r.Interface(), "data.txt")
+
+	name, ok := r.GetAttr("name")
+	assert.True(t, ok)
+	result, err := name.(*Builtin).Call(ctx)
+	assert.Nil(t, err)
+	assert.Equal(t, result, NewString("data.txt"))
+
+	closed, ok := r.GetAttr("closed")
+	assert.True(t, ok)
+	assert.Equal(t, closed, False)
+	_, ok, err = r.CallMethod(ctx, "close", nil)
+	assert.True(t, ok)
+	assert.Nil(t, err)
+	assert.True(t, r.Closed())
+	assert.Equal(t, r.Inspect(), "resource(file, closed)")
+
+	// Closing again doesn't release the resource again
+	assert.Nil(t, r.Close())
+	assert.Equal(t, releases, 1)
+
+	_, err = name.(*Builtin).Call(ctx)
+	assert.Equal(t, err.Error(), "value error: file is closed")
+}
+
+func TestResourceCloseError(t *testing.T) {
+	r := NewResource("socket", nil, func() error {
+		return errors.New("connection reset")
+	})
+	_, _, err := r.CallMethod(context.Background(), "close", nil)
+	assert.Equal(t, err.Error(), "connection reset")
+	assert.True(t, r.Closed())
+	assert.Nil(t, r.Close())
+}
+
+func TestResourceHeldDuringDetachedCall(t *testing.T) {
+	var released atomic.Bool
+	var method Object
+	func() {
+		r := NewResource("file", nil, func() error {
+			released.Store(true)
+			return nil
+		})
+		r.WithMethod("read", func(ctx context.Context, args ...Object) (Object, error) {
+			// Nothing else refers to the resource while the method runs
+			for range 5 {
+				runtime.GC()
+				time.Sleep(10 * time.Millisecond)
+			}
+			return NewBool(released.Load()), nil
+		})
+		method, _ = r.GetAttr("read")
+	}()
+	result, err := method.(*Builtin).Call(context.Background())
+	assert.Nil(t, err)
+	assert.Equal(t, result, False)
+}
+
+func TestResourceReleasedWhenUnreachable(t *testing.T) {
+	released := make(chan struct{})
+	func() {
+		NewResource("file", nil, func() error {
+			close(released)
+			return nil
+		})
+	}()
+	deadline := time.After(5 * time.Second)
+	for {
+		runtime.GC()
+		select {
+		case <-released:
+			return
+		case <-deadline:
+			t.Fatal("resource was not released after it became unreachable")
+		case <-time.After(10 * time.Millisecond):
+		}
+	}
+}
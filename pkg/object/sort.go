@@ -1,31 +1,91 @@
 package object
 
 import (
+	"context"
 	"sort"
 )
 
-// Sort a list in place. If the list contains a non-comparable object, an error
-// is returned.
+// Sort a list in place. The sort is stable. If the list contains a
+// non-comparable object, or two objects that can't be compared with each
+// other, such as an int and a string, an error is returned and the order of
+// the items is unspecified.
 func Sort(items []Object) *Error {
 	var sortErr *Error
 	sort.SliceStable(items, func(a, b int) bool {
-		itemA := items[a]
-		itemB := items[b]
-		compA, ok := itemA.(Comparable)
-		if !ok {
-			sortErr = TypeErrorf("sorted() encountered a non-comparable item (%s)", itemA.Type())
+		if sortErr != nil {
 			return false
 		}
-		if _, ok := itemB.(Comparable); !ok {
-			sortErr = TypeErrorf("sorted() encountered a non-comparable item (%s)", itemB.Type())
+		var result int
+		result, sortErr = compareForSort(items[a], items[b])
+		return result == -1
+	})
+	return sortErr
+}
+
+// SortFunc sorts items in place using less, which is called with two items
+// and returns a truthy value if the first belongs before the second. The sort
+// is stable. The first error returned by less stops the sort.
+func SortFunc(ctx context.Context, items []Object, less Callable) error {
+	var sortErr error
+	sort.SliceStable(items, func(a, b int) bool {
+		if sortErr != nil {
 			return false
 		}
-		result, err := compA.Compare(itemB)
+		result, err := less.Call(ctx, items[a], items[b])
 		if err != nil {
-			sortErr = NewError(err)
+			sortErr = err
 			return false
 		}
-		return result == -1
+		return result.IsTruthy()
 	})
 	return sortErr
 }
+
+// SortByKey sorts items in place by the keys that key returns for them, which
+// are ordered as by Sort. Each key is computed once. The sort is stable.
+func SortByKey(ctx context.Context, items []Object, key Callable) error {
+	keys := make([]Object, len(items))
+	order := make([]int, len(items))
+	for i, item := range items {
+		k, err := key.Call(ctx, item)
+		if err != nil {
+			return err
+		}
+		keys[i] = k
+		order[i] = i
+	}
+	var sortErr *Error
+	sort.SliceStable(order, func(a, b int) bool {
+		if sortErr != nil {
+			return false
+		}
+		var result int
+		result, sortErr = compareForSort(keys[order[a]], keys[order[b]])
+		return result == -1
+	})
+	if sortErr != nil {
+		return sortErr
+	}
+	sorted := make([]Object, len(items))
+	for i, index := range order {
+		sorted[i] = items[index]
+	}
+	copy(items, sorted)
+	return nil
+}
+
+// compareForSort compares two items being sorted.
+func compareForSort(a, b Object) (int, *Error) {
+	compA, ok := a.(Comparable)
+	if !ok {
+		return 0, TypeErrorf("sorted() encountered a non-comparable item (%s)", a.Type())
+	}
+	if _, ok := b.(Comparable); !ok {
+		return 0, TypeErrorf("sorted() encountered a non-comparable item (%s)", b.Type())
+	}
+	result, err := compA.Compare(b)
+	if err != nil {
+		return 0, NewError(err)
+	}
+	return result, nil
+}
@@ -0,0 +1,158 @@
+package object
+
+import (
+	"fmt"
+	"math"
+	"strconv"
+	"strings"
+	"unicode/utf8"
+
+	"github.com/deepnoodle-ai/risor/v2/internal/token"
+)
+
+// ToSource returns Risor code that evaluates to a value equal to obj. It
+// supports null, bools, numbers, bytes, decimals, strings, quotes, and lists
+// and maps of them, and returns an error for other values. Maps are written
+// with sorted keys, and keys that are valid identifiers are left unquoted.
+//
+// If indent is empty the code is written on one line. Otherwise each list
+// item and map entry is written on its own line, indented by indent per
+// level of nesting, and followed by a comma.
+func ToSource(obj Object, indent string) (string, error) {
+	w := &sourceWriter{indent: indent, active: map[Object]bool{}}
+	if err := w.write(obj, 0); err != nil {
+		return "", err
+	}
+	return w.sb.String(), nil
+}
+
+type sourceWriter struct {
+	sb     strings.Builder
+	indent string
+	active map[Object]bool
+}
+
+func (w *sourceWriter) write(obj Object, depth int) error {
+	switch obj := obj.(type) {
+	case *NilType:
+		w.sb.WriteString("null")
+	case *Bool:
+		w.sb.WriteString(strconv.FormatBool(obj.value))
+	case *Int:
+		if obj.value == math.MinInt64 {
+			// The literal for its absolute value would overflow
+			w.sb.WriteString("(-9223372036854775807 - 1)")
+		} else {
+			w.sb.WriteString(strconv.FormatInt(obj.value, 10))
+		}
+	case *Float:
+		if math.IsInf(obj.value, 0) || math.IsNaN(obj.value) {
+			return TypeErrorf("cannot write %s as source", obj.Inspect())
+		}
+		s := FormatFloat(obj.value)
+		if !strings.ContainsAny(s, ".e") {
+			s += ".0"
+		}
+		w.sb.WriteString(s)
+	case *Byte:
+		fmt.Fprintf(&w.sb, "byte(%d)", obj.value)
+	case *Bytes:
+		w.sb.WriteString("bytes(")
+		w.sb.WriteString(sourceString(string(obj.value)))
+		w.sb.WriteString(")")
+	case *Decimal:
+		fmt.Fprintf(&w.sb, "decimal(%q)", obj.String())
+	case *String:
+		w.sb.WriteString(sourceString(obj.value))
+	case *Quote:
+		fmt.Fprintf(&w.sb, "quote { %s }", obj.source)
+	case *List:
+		return w.writeItems(obj, "[", "]", len(obj.items), depth, func(i int) error {
+			return w.write(obj.items[i], depth+1)
+		})
+	case *Map:
+		keys := obj.SortedKeys()
+		return w.writeItems(obj, "{", "}", len(keys), depth, func(i int) error {
+			if IsIdentifier(keys[i]) {
+				w.sb.WriteString(keys[i])
+			} else {
+				w.sb.WriteString(sourceString(keys[i]))
+			}
+			w.sb.WriteString(": ")
+			return w.write(obj.items[keys[i]], depth+1)
+		})
+	default:
+		return TypeErrorf("cannot write a value of type %s as source", obj.Type())
+	}
+	return nil
+}
+
+// writeItems writes the items of a list or map between the given brackets,
+// failing if the container is one of its own items.
+func (w *sourceWriter) writeItems(obj Object, start, end string, n, depth int, item func(int) error) error {
+	if w.active[obj] {
+		return TypeErrorf("cannot write a %s that contains itself as source", obj.Type())
+	}
+	w.active[obj] = true
+	defer delete(w.active, obj)
+
+	w.sb.WriteString(start)
+	for i := 0; i < n; i++ {
+		if w.indent != "" {
+			w.sb.WriteString("\n")
+			w.sb.WriteString(strings.Repeat(w.indent, depth+1))
+		} else if i > 0 {
+			w.sb.WriteString(", ")
+		}
+		if err := item(i); err != nil {
+			return err
+		}
+		if w.indent != "" {
+			w.sb.WriteString(",")
+		}
+	}
+	if w.indent != "" && n > 0 {
+		w.sb.WriteString("\n")
+		w.sb.WriteString(strings.Repeat(w.indent, depth))
+	}
+	w.sb.WriteString(end)
+	return nil
+}
+
+// sourceString returns s as a double-quoted string literal. Bytes that
+// aren't valid UTF-8 are written as octal escapes, since Risor reads a \x
+// escape as a rune rather than a byte.
+func sourceString(s string) string {
+	var sb strings.Builder
+	sb.WriteByte('"')
+	for i := 0; i < len(s); {
+		r, size := utf8.DecodeRuneInString(s[i:])
+		if r == utf8.RuneError && size == 1 {
+			fmt.Fprintf(&sb, `\%03o`, s[i])
+		} else {
+			quoted := strconv.Quote(s[i : i+size])
+			sb.WriteString(quoted[1 : len(quoted)-1])
+		}
+		i += size
+	}
+	sb.WriteByte('"')
+	return sb.String()
+}
+
+// IsIdentifier returns true if s can be written as a bare map key, or
+// accessed as an attribute, in Risor source.
+func IsIdentifier(s string) bool {
+	if s == "" || token.LookupIdentifier(s) != token.IDENT {
+		return false
+	}
+	for i, r := range s {
+		if r != '_' && !isLetter(r) && (i == 0 || r < '0' || r > '9') {
+			return false
+		}
+	}
+	return true
+}
+
+func isLetter(r rune) bool {
+	return (r >= 'a' && r <= 'z') || (r >= 'A' && r <= 'Z')
+}
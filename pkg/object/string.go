@@ -5,6 +5,8 @@ import (
 	"encoding/json"
 	"fmt"
 	"strings"
+	"sync/atomic"
+	"unicode/utf8"
 
 	"github.com/deepnoodle-ai/risor/v2/pkg/op"
 )
@@ -159,8 +161,15 @@ func init() {
 
 type String struct {
 	value string
+	flags atomic.Uint32 // What scanning value found; see scan
 }
 
+const (
+	stringScanned uint32 = 1 << iota
+	stringASCII          // value contains only ASCII characters
+	stringValid          // value is valid UTF-8
+)
+
 func (s *String) Attrs() []AttrSpec {
 	return stringMethods.Specs()
 }
@@ -169,6 +178,10 @@ func (s *String) GetAttr(name string) (Object, bool) {
 	return stringMethods.GetAttr(s, name)
 }
 
+func (s *String) CallMethod(ctx context.Context, name string, args []Object) (Object, bool, error) {
+	return stringMethods.CallMethod(ctx, s, name, args)
+}
+
 func (s *String) SetAttr(name string, value Object) error {
 	return TypeErrorf("string has no attribute %q", name)
 }
@@ -215,6 +228,10 @@ func (s *String) Compare(other Object) (int, error) {
 	return -1, nil
 }
 
+func (s *String) HashKey() HashKey {
+	return HashKey{Type: STRING, Value: s.value}
+}
+
 func (s *String) Equals(other Object) bool {
 	otherString, ok := other.(*String)
 	if !ok {
@@ -258,28 +275,95 @@ func (s *String) GetItem(key Object) (Object, *Error) {
 	if !ok {
 		return nil, TypeErrorf("string index must be an int (got %s)", key.Type())
 	}
-	runes := []rune(s.value)
-	index, err := ResolveIndex(indexObj.value, int64(len(runes)))
+	size, valid := s.runeCount()
+	index, err := ResolveIndex(indexObj.value, size)
 	if err != nil {
 		return nil, NewError(err)
 	}
-	return NewString(string(runes[index])), nil
+	if !valid {
+		return NewString(string([]rune(s.value)[index])), nil
+	}
+	if ascii, _ := s.scan(); ascii {
+		return NewString(s.value[index : index+1]), nil
+	}
+	offset := s.byteOffset(0, index)
+	r, _ := utf8.DecodeRuneInString(s.value[offset:])
+	return NewString(string(r)), nil
 }
 
 func (s *String) GetSlice(slice Slice) (Object, *Error) {
-	runes := []rune(s.value)
-	start, stop, err := ResolveIntSlice(slice, int64(len(runes)))
+	size, valid := s.runeCount()
+	start, stop, step, err := ResolveSlice(slice, size)
 	if err != nil {
 		return nil, NewError(err)
 	}
-	resultRunes := runes[start:stop]
-	return NewString(string(resultRunes)), nil
+	if step != 1 || !valid {
+		runes := []rune(s.value)
+		return NewString(string(stepSlice(runes, start, stop, step))), nil
+	}
+	if stop <= start {
+		return NewString(""), nil
+	}
+	if ascii, _ := s.scan(); ascii {
+		return NewString(s.value[start:stop]), nil
+	}
+	startOffset := s.byteOffset(0, start)
+	stopOffset := s.byteOffset(startOffset, stop-start)
+	return NewString(s.value[startOffset:stopOffset]), nil
+}
+
+// runeCount returns the number of runes in the string and whether the string
+// is valid UTF-8. Indexing and slicing work on byte offsets when it is, which
+// avoids converting the whole string to runes. For ASCII strings the count is
+// the length, so indexing them takes constant time.
+func (s *String) runeCount() (int64, bool) {
+	ascii, valid := s.scan()
+	if ascii {
+		return int64(len(s.value)), true
+	}
+	return int64(utf8.RuneCountInString(s.value)), valid
+}
+
+// scan reports whether the string contains only ASCII characters and whether
+// it is valid UTF-8. The string is scanned the first time it is indexed,
+// sliced, or measured, rather than when it is created, since most strings
+// never are.
+func (s *String) scan() (ascii, valid bool) {
+	flags := s.flags.Load()
+	if flags&stringScanned == 0 {
+		flags = stringScanned
+		if isASCII(s.value) {
+			flags |= stringASCII | stringValid
+		} else if utf8.ValidString(s.value) {
+			flags |= stringValid
+		}
+		s.flags.Store(flags)
+	}
+	return flags&stringASCII != 0, flags&stringValid != 0
+}
+
+// byteOffset returns the byte offset of the rune that is count runes past the
+// given byte offset.
+func (s *String) byteOffset(offset int, count int64) int {
+	for ; count > 0 && offset < len(s.value); count-- {
+		if s.value[offset] < utf8.RuneSelf {
+			offset++
+			continue
+		}
+		_, size := utf8.DecodeRuneInString(s.value[offset:])
+		offset += size
+	}
+	return offset
 }
 
 func (s *String) SetItem(key, value Object) *Error {
 	return TypeErrorf("set item is unsupported for string")
 }
 
+func (s *String) SetSlice(slice Slice, value Object) *Error {
+	return TypeErrorf("set slice is unsupported for string")
+}
+
 func (s *String) DelItem(key Object) *Error {
 	return TypeErrorf("del item is unsupported for string")
 }
@@ -420,7 +504,8 @@ func (s *String) Repeat(obj Object) (Object, error) {
 }
 
 func (s *String) Len() *Int {
-	return NewInt(int64(len([]rune(s.value))))
+	size, _ := s.runeCount()
+	return NewInt(size)
 }
 
 func (s *String) Enumerate(ctx context.Context, fn func(key, value Object) bool) {
@@ -447,3 +532,12 @@ func (s *String) MarshalJSON() ([]byte, error) {
 func NewString(s string) *String {
 	return &String{value: s}
 }
+
+func isASCII(s string) bool {
+	for i := 0; i < len(s); i++ {
+		if s[i] >= utf8.RuneSelf {
+			return false
+		}
+	}
+	return true
+}
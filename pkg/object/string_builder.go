@@ -0,0 +1,125 @@
+package object
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"strings"
+	"unicode/utf8"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/op"
+)
+
+var stringBuilderAttrs = NewAttrRegistry[*StringBuilder]("stringbuilder")
+
+func init() {
+	stringBuilderAttrs.Define("write").
+		Doc("Append a string, returning the builder").
+		Arg("s").
+		Returns("stringbuilder").
+		Impl(func(b *StringBuilder, ctx context.Context, args ...Object) (Object, error) {
+			s, err := AsString(args[0])
+			if err != nil {
+				return nil, err
+			}
+			b.WriteString(s)
+			return b, nil
+		})
+
+	stringBuilderAttrs.Define("to_string").
+		Doc("Return the string built so far").
+		Returns("string").
+		Impl(func(b *StringBuilder, ctx context.Context, args ...Object) (Object, error) {
+			return NewString(b.String()), nil
+		})
+
+	stringBuilderAttrs.Define("len").
+		Doc("Return the number of characters written").
+		Returns("int").
+		Impl(func(b *StringBuilder, ctx context.Context, args ...Object) (Object, error) {
+			return NewInt(int64(utf8.RuneCountInString(b.String()))), nil
+		})
+
+	stringBuilderAttrs.Define("reset").
+		Doc("Discard everything written, returning the builder").
+		Returns("stringbuilder").
+		Impl(func(b *StringBuilder, ctx context.Context, args ...Object) (Object, error) {
+			b.Reset()
+			return b, nil
+		})
+}
+
+// StringBuilder builds a string from pieces written one at a time. Writing
+// appends to a growing buffer, so building a string from n pieces takes time
+// proportional to its length, where repeated + copies the whole string on
+// every step.
+type StringBuilder struct {
+	sb strings.Builder
+}
+
+// NewStringBuilder returns a builder that starts with the given string.
+func NewStringBuilder(s string) *StringBuilder {
+	b := &StringBuilder{}
+	b.sb.WriteString(s)
+	return b
+}
+
+// WriteString appends s to the builder.
+func (b *StringBuilder) WriteString(s string) {
+	b.sb.WriteString(s)
+}
+
+// Reset discards everything written to the builder.
+func (b *StringBuilder) Reset() {
+	b.sb.Reset()
+}
+
+func (b *StringBuilder) Attrs() []AttrSpec {
+	return stringBuilderAttrs.Specs()
+}
+
+func (b *StringBuilder) GetAttr(name string) (Object, bool) {
+	return stringBuilderAttrs.GetAttr(b, name)
+}
+
+func (b *StringBuilder) CallMethod(ctx context.Context, name string, args []Object) (Object, bool, error) {
+	return stringBuilderAttrs.CallMethod(ctx, b, name, args)
+}
+
+func (b *StringBuilder) SetAttr(name string, value Object) error {
+	return TypeErrorf("stringbuilder has no attribute %q", name)
+}
+
+func (b *StringBuilder) Type() Type {
+	return STRING_BUILDER
+}
+
+func (b *StringBuilder) Inspect() string {
+	return fmt.Sprintf("stringbuilder(%s)", NewString(b.sb.String()).Inspect())
+}
+
+// String returns the string built so far, which is also what string()
+// returns for a builder.
+func (b *StringBuilder) String() string {
+	return b.sb.String()
+}
+
+func (b *StringBuilder) Interface() interface{} {
+	return b.sb.String()
+}
+
+func (b *StringBuilder) IsTruthy() bool {
+	return b.sb.Len() > 0
+}
+
+func (b *StringBuilder) Equals(other Object) bool {
+	return b == other
+}
+
+func (b *StringBuilder) RunOperation(opType op.BinaryOpType, right Object) (Object, error) {
+	return nil, newTypeErrorf("unsupported operation for stringbuilder: %v", opType)
+}
+
+func (b *StringBuilder) MarshalJSON() ([]byte, error) {
+	return json.Marshal(b.sb.String())
+}
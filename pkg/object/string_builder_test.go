@@ -0,0 +1,40 @@
+package object
+
+import (
+	"context"
+	"testing"
+
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+func TestStringBuilder(t *testing.T) {
+	ctx := context.Background()
+	b := NewStringBuilder("a")
+	assert.Equal(t, b.Type(), STRING_BUILDER)
+	assert.True(t, b.IsTruthy())
+
+	result, ok, err := b.CallMethod(ctx, "write", []Object{NewString("é")})
+	assert.True(t, ok)
+	assert.Nil(t, err)
+	assert.Equal(t, result, Object(b))
+	b.WriteString("c")
+	assert.Equal(t, b.String(), "aéc")
+	assert.Equal(t, b.Inspect(), `stringbuilder("aéc")`)
+
+	result, _, err = b.CallMethod(ctx, "len", nil)
+	assert.Nil(t, err)
+	assert.Equal(t, result, NewInt(3))
+
+	result, _, err = b.CallMethod(ctx, "to_string", nil)
+	assert.Nil(t, err)
+	assert.Equal(t, result, NewString("aéc"))
+
+	_, _, err = b.CallMethod(ctx, "write", []Object{NewInt(1)})
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "expected a string (int given)")
+
+	b.Reset()
+	assert.Equal(t, b.String(), "")
+	assert.False(t, b.IsTruthy())
+	assert.False(t, b.Equals(NewStringBuilder("")))
+}
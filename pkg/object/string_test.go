@@ -70,6 +70,12 @@ func TestStringGetItem(t *testing.T) {
 		{"012345", 5, "5", ""},
 		{"012345", -1, "5", ""},
 		{"012345", -2, "4", ""},
+		{"héllo", 1, "é", ""},
+		{"héllo", 2, "l", ""},
+		{"héllo", -4, "é", ""},
+		{"日本語", 2, "語", ""},
+		{"日本語", 3, "", "index error: index out of range: 3"},
+		{"a\xffb", 1, "\uFFFD", ""},
 	}
 	for _, tc := range tests {
 		msg := fmt.Sprintf("%v[%d]", tc.s, tc.index)
@@ -84,3 +90,29 @@ func TestStringGetItem(t *testing.T) {
 		}
 	}
 }
+
+func TestStringGetSlice(t *testing.T) {
+	tests := []struct {
+		s        string
+		start    Object
+		stop     Object
+		expected string
+	}{
+		{"012345", NewInt(1), NewInt(4), "123"},
+		{"012345", NewInt(-2), nil, "45"},
+		{"012345", nil, NewInt(6), "012345"},
+		{"012345", NewInt(6), nil, ""},
+		{"", nil, nil, ""},
+		{"héllo", NewInt(1), NewInt(3), "él"},
+		{"héllo", NewInt(-4), NewInt(-1), "éll"},
+		{"日本語です", NewInt(2), nil, "語です"},
+		{"日本語です", nil, NewInt(-2), "日本語"},
+		{"a\xffb", NewInt(1), nil, "\uFFFDb"},
+	}
+	for _, tc := range tests {
+		msg := fmt.Sprintf("%v[%v:%v]", tc.s, tc.start, tc.stop)
+		result, err := NewString(tc.s).GetSlice(Slice{Start: tc.start, Stop: tc.stop})
+		assert.Nil(t, err, msg)
+		assert.Equal(t, result.(*String).Value(), tc.expected, msg)
+	}
+}
@@ -12,6 +12,12 @@ import (
 var timeMethods = NewMethodRegistry[*Time]("time")
 
 func init() {
+	timeMethods.Define("add").
+		Doc("Add a duration").
+		Arg("duration").
+		Returns("time").
+		Impl((*Time).Add)
+
 	timeMethods.Define("add_date").
 		Doc("Add years, months, and days").
 		Args("years", "months", "days").
@@ -30,12 +36,41 @@ func init() {
 		Returns("bool").
 		Impl((*Time).Before)
 
+	timeMethods.Define("diff").
+		Doc("Get the seconds elapsed since another time").
+		Arg("other").
+		Returns("float").
+		Impl((*Time).Diff)
+
 	timeMethods.Define("format").
 		Doc("Format time using layout string").
 		Arg("layout").
 		Returns("string").
 		Impl((*Time).Format)
 
+	timeMethods.Define("in").
+		Doc("Convert to a timezone").
+		Arg("tz").
+		Returns("time").
+		Impl((*Time).In)
+
+	timeMethods.Define("iso").
+		Doc("Format as an ISO-8601 string").
+		Returns("string").
+		Impl((*Time).ISO)
+
+	timeMethods.Define("sub").
+		Doc("Subtract a duration").
+		Arg("duration").
+		Returns("time").
+		Impl((*Time).Sub)
+
+	timeMethods.Define("truncate").
+		Doc("Round down to a calendar unit or a multiple of a duration").
+		Arg("unit").
+		Returns("time").
+		Impl((*Time).Truncate)
+
 	timeMethods.Define("unix").
 		Doc("Get Unix timestamp (seconds)").
 		Returns("int").
@@ -59,6 +94,10 @@ func (t *Time) GetAttr(name string) (Object, bool) {
 	return timeMethods.GetAttr(t, name)
 }
 
+func (t *Time) CallMethod(ctx context.Context, name string, args []Object) (Object, bool, error) {
+	return timeMethods.CallMethod(ctx, t, name, args)
+}
+
 func (t *Time) SetAttr(name string, value Object) error {
 	return TypeErrorf("time has no attribute %q", name)
 }
@@ -113,6 +152,85 @@ func NewTime(t time.Time) *Time {
 	return &Time{value: t}
 }
 
+func (t *Time) Add(ctx context.Context, args ...Object) (Object, error) {
+	d, err := AsDuration(args[0])
+	if err != nil {
+		return nil, err
+	}
+	return NewTime(t.value.Add(d)), nil
+}
+
+func (t *Time) Sub(ctx context.Context, args ...Object) (Object, error) {
+	d, err := AsDuration(args[0])
+	if err != nil {
+		return nil, err
+	}
+	return NewTime(t.value.Add(-d)), nil
+}
+
+// Diff returns the number of seconds from another time to this one, which is
+// negative if the other time is later.
+func (t *Time) Diff(ctx context.Context, args ...Object) (Object, error) {
+	other, err := AsTime(args[0])
+	if err != nil {
+		return nil, err
+	}
+	return NewFloat(t.value.Sub(other).Seconds()), nil
+}
+
+func (t *Time) In(ctx context.Context, args ...Object) (Object, error) {
+	loc, err := AsLocation(args[0])
+	if err != nil {
+		return nil, err
+	}
+	return NewTime(t.value.In(loc)), nil
+}
+
+func (t *Time) ISO(ctx context.Context, args ...Object) (Object, error) {
+	return NewString(t.value.Format(time.RFC3339Nano)), nil
+}
+
+// Truncate rounds the time down. The units "year", "month", "week" (which
+// starts on Monday), "day", "hour", "minute", and "second" are truncated in
+// the time's own timezone. Any other unit is read as a duration, and the time
+// is rounded down to a multiple of it since the zero time.
+func (t *Time) Truncate(ctx context.Context, args ...Object) (Object, error) {
+	v := t.value
+	if unit, ok := args[0].(*String); ok {
+		y, mo, d := v.Date()
+		_, mi, s := v.Clock()
+		loc := v.Location()
+		ns := time.Duration(v.Nanosecond())
+		switch unit.value {
+		case "year":
+			return NewTime(time.Date(y, 1, 1, 0, 0, 0, 0, loc)), nil
+		case "month":
+			return NewTime(time.Date(y, mo, 1, 0, 0, 0, 0, loc)), nil
+		case "week":
+			daysSinceMonday := (int(v.Weekday()) + 6) % 7
+			return NewTime(time.Date(y, mo, d-daysSinceMonday, 0, 0, 0, 0, loc)), nil
+		case "day":
+			return NewTime(time.Date(y, mo, d, 0, 0, 0, 0, loc)), nil
+		case "hour":
+			// Subtract rather than rebuild the time, which is ambiguous
+			// when clocks go back
+			return NewTime(v.Add(-time.Duration(mi)*time.Minute - time.Duration(s)*time.Second - ns)), nil
+		case "minute":
+			return NewTime(v.Add(-time.Duration(s)*time.Second - ns)), nil
+		case "second":
+			return NewTime(v.Add(-ns)), nil
+		}
+	}
+	d, err := AsDuration(args[0])
+	if err != nil {
+		return nil, err
+	}
+	if d <= 0 {
+		return nil, newValueErrorf("truncate: duration must be positive")
+	}
+	return NewTime(v.Truncate(d)), nil
+}
+
 func (t *Time) AddDate(ctx context.Context, args ...Object) (Object, error) {
 	years, err := AsInt(args[0])
 	if err != nil {
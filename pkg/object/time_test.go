@@ -40,3 +40,78 @@ func TestTimeAddDate(t *testing.T) {
 		})
 	}
 }
+
+func TestTimeArithmetic(t *testing.T) {
+	ctx := context.Background()
+	base := NewTime(time.Date(2024, 3, 9, 22, 45, 30, 0, time.UTC))
+
+	result, err := base.Add(ctx, NewString("1h30m"))
+	assert.Nil(t, err)
+	assert.Equal(t, result.(*Time).Value(), time.Date(2024, 3, 10, 0, 15, 30, 0, time.UTC))
+
+	result, err = base.Sub(ctx, NewFloat(0.5))
+	assert.Nil(t, err)
+	assert.Equal(t, result.(*Time).Value(), time.Date(2024, 3, 9, 22, 45, 29, 500000000, time.UTC))
+
+	later := NewTime(time.Date(2024, 3, 10, 0, 0, 0, 0, time.UTC))
+	result, err = later.Diff(ctx, base)
+	assert.Nil(t, err)
+	assert.Equal(t, result, NewFloat(4470))
+
+	_, err = base.Add(ctx, NewString("1 day"))
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), `value error: invalid duration "1 day"`)
+	_, err = base.Add(ctx, True)
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), "type error: expected a duration (bool given)")
+}
+
+func TestTimeTruncate(t *testing.T) {
+	ctx := context.Background()
+	newYork, err := time.LoadLocation("America/New_York")
+	assert.Nil(t, err)
+	// A Thursday, in daylight saving time
+	base := NewTime(time.Date(2024, 8, 15, 13, 47, 12, 345, newYork))
+
+	tests := []struct {
+		unit Object
+		want time.Time
+	}{
+		{NewString("year"), time.Date(2024, 1, 1, 0, 0, 0, 0, newYork)},
+		{NewString("month"), time.Date(2024, 8, 1, 0, 0, 0, 0, newYork)},
+		{NewString("week"), time.Date(2024, 8, 12, 0, 0, 0, 0, newYork)},
+		{NewString("day"), time.Date(2024, 8, 15, 0, 0, 0, 0, newYork)},
+		{NewString("hour"), time.Date(2024, 8, 15, 13, 0, 0, 0, newYork)},
+		{NewString("minute"), time.Date(2024, 8, 15, 13, 47, 0, 0, newYork)},
+		{NewString("second"), time.Date(2024, 8, 15, 13, 47, 12, 0, newYork)},
+		{NewString("15m"), time.Date(2024, 8, 15, 13, 45, 0, 0, newYork)},
+		{NewInt(3600), time.Date(2024, 8, 15, 13, 0, 0, 0, newYork)},
+	}
+	for _, tt := range tests {
+		result, err := base.Truncate(ctx, tt.unit)
+		assert.Nil(t, err)
+		got := result.(*Time).Value()
+		assert.True(t, got.Equal(tt.want), "truncate(%s): got %s, want %s", tt.unit.Inspect(), got, tt.want)
+	}
+
+	_, err = base.Truncate(ctx, NewInt(0))
+	assert.NotNil(t, err)
+}
+
+func TestTimeZones(t *testing.T) {
+	ctx := context.Background()
+	base := NewTime(time.Date(2024, 1, 15, 12, 0, 0, 0, time.UTC))
+
+	result, err := base.In(ctx, NewString("Asia/Kolkata"))
+	assert.Nil(t, err)
+	inKolkata := result.(*Time)
+	assert.True(t, inKolkata.Equals(base))
+
+	iso, err := inKolkata.ISO(ctx)
+	assert.Nil(t, err)
+	assert.Equal(t, iso, NewString("2024-01-15T17:30:00+05:30"))
+
+	_, err = base.In(ctx, NewString("Nowhere/Special"))
+	assert.NotNil(t, err)
+	assert.Equal(t, err.Error(), `value error: unknown time zone "Nowhere/Special"`)
+}
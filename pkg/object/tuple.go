@@ -0,0 +1,154 @@
+package object
+
+import (
+	"bytes"
+	"context"
+	"encoding/json"
+	"strings"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/op"
+)
+
+// Tuple is an immutable, fixed-length sequence of objects. Tuples are created
+// by functions that return multiple values (return a, b) and are unpacked by
+// multi-variable assignments (let x, y = f()). Unlike lists, unpacking a tuple
+// requires the number of variables to match the number of values exactly.
+type Tuple struct {
+	items []Object
+}
+
+func (t *Tuple) Type() Type {
+	return TUPLE
+}
+
+// Value returns the items in the tuple. The returned slice must not be modified.
+func (t *Tuple) Value() []Object {
+	return t.items
+}
+
+func (t *Tuple) Inspect() string {
+	var out bytes.Buffer
+	items := make([]string, 0, len(t.items))
+	for _, e := range t.items {
+		items = append(items, e.Inspect())
+	}
+	out.WriteString("(")
+	out.WriteString(strings.Join(items, ", "))
+	if len(t.items) == 1 {
+		out.WriteString(",")
+	}
+	out.WriteString(")")
+	return out.String()
+}
+
+func (t *Tuple) String() string {
+	return t.Inspect()
+}
+
+func (t *Tuple) Interface() interface{} {
+	items := make([]interface{}, 0, len(t.items))
+	for _, item := range t.items {
+		items = append(items, item.Interface())
+	}
+	return items
+}
+
+// Equals reports whether other is a tuple with equal items; see DeepEqual.
+func (t *Tuple) Equals(other Object) bool {
+	return DeepEqual(t, other)
+}
+
+func (t *Tuple) Attrs() []AttrSpec {
+	return nil
+}
+
+func (t *Tuple) GetAttr(name string) (Object, bool) {
+	return nil, false
+}
+
+func (t *Tuple) SetAttr(name string, value Object) error {
+	return TypeErrorf("tuple has no attribute %q", name)
+}
+
+func (t *Tuple) IsTruthy() bool {
+	return len(t.items) > 0
+}
+
+func (t *Tuple) RunOperation(opType op.BinaryOpType, right Object) (Object, error) {
+	return nil, newTypeErrorf("unsupported operation for tuple: %v on type %s",
+		opType, right.Type())
+}
+
+func (t *Tuple) GetItem(key Object) (Object, *Error) {
+	indexObj, ok := key.(*Int)
+	if !ok {
+		return nil, TypeErrorf("tuple index must be an int (got %s)", key.Type())
+	}
+	idx, err := ResolveIndex(indexObj.value, int64(len(t.items)))
+	if err != nil {
+		return nil, NewError(err)
+	}
+	return t.items[idx], nil
+}
+
+// GetSlice implements the [start:stop] operator for a container type.
+func (t *Tuple) GetSlice(s Slice) (Object, *Error) {
+	start, stop, step, err := ResolveSlice(s, int64(len(t.items)))
+	if err != nil {
+		return nil, NewError(err)
+	}
+	if step != 1 {
+		return NewTuple(stepSlice(t.items, start, stop, step)), nil
+	}
+	items := make([]Object, stop-start)
+	copy(items, t.items[start:stop])
+	return NewTuple(items), nil
+}
+
+// SetItem always fails since tuples are immutable.
+func (t *Tuple) SetItem(key, value Object) *Error {
+	return TypeErrorf("tuple does not support item assignment")
+}
+
+// SetSlice always fails since tuples are immutable.
+func (t *Tuple) SetSlice(s Slice, value Object) *Error {
+	return TypeErrorf("tuple does not support slice assignment")
+}
+
+// DelItem always fails since tuples are immutable.
+func (t *Tuple) DelItem(key Object) *Error {
+	return TypeErrorf("tuple does not support item deletion")
+}
+
+// Contains returns true if the given item is found in this container.
+func (t *Tuple) Contains(item Object) *Bool {
+	for _, v := range t.items {
+		if Equals(v, item) {
+			return True
+		}
+	}
+	return False
+}
+
+// Len returns the number of items in this container.
+func (t *Tuple) Len() *Int {
+	return NewInt(int64(len(t.items)))
+}
+
+func (t *Tuple) Enumerate(ctx context.Context, fn func(key, value Object) bool) {
+	for i, item := range t.items {
+		if !fn(NewInt(int64(i)), item) {
+			return
+		}
+	}
+}
+
+func (t *Tuple) MarshalJSON() ([]byte, error) {
+	return json.Marshal(t.items)
+}
+
+// NewTuple returns a tuple containing the given items. The tuple takes
+// ownership of the slice.
+func NewTuple(items []Object) *Tuple {
+	return &Tuple{items: items}
+}
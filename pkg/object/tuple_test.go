@@ -0,0 +1,68 @@
+package object
+
+import (
+	"context"
+	"testing"
+
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+func TestTupleType(t *testing.T) {
+	tuple := NewTuple([]Object{NewInt(1), NewInt(2)})
+	assert.Equal(t, tuple.Type(), TUPLE)
+}
+
+func TestTupleInspect(t *testing.T) {
+	tuple := NewTuple([]Object{NewInt(1), NewString("hello")})
+	assert.Equal(t, tuple.Inspect(), `(1, "hello")`)
+	assert.Equal(t, NewTuple([]Object{NewInt(1)}).Inspect(), "(1,)")
+}
+
+func TestTupleEquals(t *testing.T) {
+	a := NewTuple([]Object{NewInt(1), NewInt(2)})
+	b := NewTuple([]Object{NewInt(1), NewInt(2)})
+	c := NewTuple([]Object{NewInt(1), NewInt(3)})
+	assert.True(t, a.Equals(b))
+	assert.False(t, a.Equals(c))
+	assert.False(t, a.Equals(NewList([]Object{NewInt(1), NewInt(2)})))
+}
+
+func TestTupleGetItem(t *testing.T) {
+	tuple := NewTuple([]Object{NewInt(1), NewInt(2)})
+	item, err := tuple.GetItem(NewInt(-1))
+	assert.Nil(t, err)
+	assert.Equal(t, item, NewInt(2))
+
+	_, err = tuple.GetItem(NewInt(2))
+	assert.NotNil(t, err)
+
+	_, err = tuple.GetItem(NewString("a"))
+	assert.NotNil(t, err)
+}
+
+func TestTupleGetSlice(t *testing.T) {
+	tuple := NewTuple([]Object{NewInt(1), NewInt(2), NewInt(3)})
+	result, err := tuple.GetSlice(Slice{Start: NewInt(1)})
+	assert.Nil(t, err)
+	assert.Equal(t, result, NewTuple([]Object{NewInt(2), NewInt(3)}))
+}
+
+func TestTupleImmutable(t *testing.T) {
+	tuple := NewTuple([]Object{NewInt(1), NewInt(2)})
+	assert.NotNil(t, tuple.SetItem(NewInt(0), NewInt(5)))
+	assert.NotNil(t, tuple.DelItem(NewInt(0)))
+	assert.Equal(t, tuple.Value()[0], NewInt(1))
+}
+
+func TestTupleEnumerate(t *testing.T) {
+	tuple := NewTuple([]Object{NewString("a"), NewString("b")})
+	var values []Object
+	tuple.Enumerate(context.Background(), func(key, value Object) bool {
+		values = append(values, value)
+		return true
+	})
+	assert.Equal(t, values, []Object{NewString("a"), NewString("b")})
+	assert.Equal(t, tuple.Len(), NewInt(2))
+	assert.Equal(t, tuple.Contains(NewString("b")), True)
+	assert.Equal(t, tuple.Interface(), []interface{}{"a", "b"})
+}
@@ -10,6 +10,10 @@ func init() {
 		return NewString("").Attrs()
 	})
 
+	RegisterType(STRING_BUILDER, "Mutable buffer for building a string piece by piece", func() []AttrSpec {
+		return stringBuilderAttrs.Specs()
+	})
+
 	RegisterType(LIST, "Mutable ordered collection of values", func() []AttrSpec {
 		return NewList(nil).Attrs()
 	})
@@ -22,6 +26,10 @@ func init() {
 
 	RegisterType(FLOAT, "64-bit floating point number", nil)
 
+	RegisterType(DECIMAL, "Exact decimal number of arbitrary precision", func() []AttrSpec {
+		return NewDecimalFromInt(0).Attrs()
+	})
+
 	RegisterType(BOOL, "Boolean value (true or false)", nil)
 
 	RegisterType(BYTE, "Single byte value (0-255)", nil)
@@ -49,7 +57,19 @@ func init() {
 	// Module attrs are dynamic based on contents, just register __name__
 	RegisterType(MODULE, "Collection of related functions and values", nil)
 
+	RegisterType(RESOURCE, "Host resource such as a file that is released by close()", func() []AttrSpec {
+		return resourceAttrs.Specs()
+	})
+
 	RegisterType(RANGE, "Lazy sequence of integers", func() []AttrSpec {
 		return NewRange(0, 0, 1).Attrs()
 	})
+
+	RegisterType(ITER, "Lazy sequence of values, such as map keys or a host stream", func() []AttrSpec {
+		return iterAttrs.Specs()
+	})
+
+	RegisterType(QUOTE, "Block of code held as a value, run with run()", func() []AttrSpec {
+		return quoteAttrs.Specs()
+	})
 }
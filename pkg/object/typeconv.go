@@ -7,7 +7,9 @@ import (
 	"errors"
 	"fmt"
 	"io"
+	"math"
 	"reflect"
+	"strings"
 	"time"
 	"unicode/utf8"
 
@@ -122,6 +124,46 @@ func AsTime(obj Object) (time.Time, error) {
 	return t.value, nil
 }
 
+// AsDuration returns the duration given by obj, which is a number of seconds
+// or a duration string such as "1h30m".
+func AsDuration(obj Object) (time.Duration, error) {
+	switch obj := obj.(type) {
+	case *Int:
+		if obj.value > math.MaxInt64/int64(time.Second) || obj.value < math.MinInt64/int64(time.Second) {
+			return 0, newValueErrorf("duration out of range: %d seconds", obj.value)
+		}
+		return time.Duration(obj.value) * time.Second, nil
+	case *Float:
+		d := obj.value * float64(time.Second)
+		if math.IsNaN(d) || d >= math.MaxInt64 || d < math.MinInt64 {
+			return 0, newValueErrorf("duration out of range: %s seconds", obj.Inspect())
+		}
+		return time.Duration(d), nil
+	case *String:
+		d, err := time.ParseDuration(obj.value)
+		if err != nil {
+			return 0, newValueErrorf("invalid duration %q", obj.value)
+		}
+		return d, nil
+	default:
+		return 0, newTypeErrorf("expected a duration (%s given)", obj.Type())
+	}
+}
+
+// AsLocation returns the time zone named by obj, which is an IANA name such
+// as "America/New_York", "UTC", or "Local".
+func AsLocation(obj Object) (*time.Location, error) {
+	name, err := AsString(obj)
+	if err != nil {
+		return nil, err
+	}
+	loc, err := time.LoadLocation(name)
+	if err != nil {
+		return nil, newValueErrorf("unknown time zone %q", name)
+	}
+	return loc, nil
+}
+
 func AsBytes(obj Object) ([]byte, error) {
 	switch obj := obj.(type) {
 	case *Bytes:
@@ -219,6 +261,15 @@ func (r *TypeRegistry) FromGo(v any) (Object, error) {
 		return obj, nil
 	}
 
+	// Host callbacks written with the builtin signature receive Risor values
+	// as they are, rather than through reflection
+	switch fn := v.(type) {
+	case BuiltinFunction:
+		return builtinCallback(fn), nil
+	case func(context.Context, ...Object) (Object, error):
+		return builtinCallback(fn), nil
+	}
+
 	typ := reflect.TypeOf(v)
 
 	// Check for exact type match
@@ -230,6 +281,14 @@ func (r *TypeRegistry) FromGo(v any) (Object, error) {
 	return r.fromGoByKind(v, typ)
 }
 
+// builtinCallback wraps a host callback as a builtin named "callback".
+func builtinCallback(fn BuiltinFunction) Object {
+	if fn == nil {
+		return Nil
+	}
+	return NewBuiltin("callback", fn)
+}
+
 func (r *TypeRegistry) fromGoByKind(v any, typ reflect.Type) (Object, error) {
 	rv := reflect.ValueOf(v)
 
@@ -362,6 +421,8 @@ func (r *TypeRegistry) toGoByKind(obj Object, target reflect.Type) (any, error)
 		return r.toGoMap(obj, target)
 	case reflect.Ptr:
 		return r.toGoPointer(obj, target)
+	case reflect.Struct:
+		return r.toGoStruct(obj, target)
 	case reflect.Interface:
 		if target.NumMethod() == 0 {
 			// any / interface{}
@@ -478,6 +539,120 @@ func (r *TypeRegistry) toGoPointer(obj Object, target reflect.Type) (any, error)
 	return ptr.Interface(), nil
 }
 
+// ErrMissingField is wrapped by the FieldError returned when a map has no
+// entry for a required struct field.
+var ErrMissingField = errors.New("missing field")
+
+// FieldError reports a map entry that couldn't be converted to the struct
+// field it was matched to.
+type FieldError struct {
+	Path string // path to the field, such as "server.port"
+	Err  error
+}
+
+func (e *FieldError) Error() string {
+	if errors.Is(e.Err, ErrMissingField) {
+		return fmt.Sprintf("missing field %q", e.Path)
+	}
+	return fmt.Sprintf("field %q: %v", e.Path, e.Err)
+}
+
+func (e *FieldError) Unwrap() error {
+	return e.Err
+}
+
+// toGoStruct converts a map to a struct. Each exported field is set from the
+// map entry named by its risor or json tag, or else by the field name, and
+// a key that differs only in case is accepted too. A field whose tag name is
+// "-" is skipped. Fields are required unless they are pointers or tagged
+// omitempty, and map entries that match no field are ignored.
+func (r *TypeRegistry) toGoStruct(obj Object, target reflect.Type) (any, error) {
+	if g, ok := obj.(*GoStruct); ok && g.structType == target {
+		return g.value.Elem().Interface(), nil
+	}
+	m, ok := obj.(*Map)
+	if !ok {
+		return nil, newTypeErrorf("expected a map, got %s", obj.Type())
+	}
+	result := reflect.New(target).Elem()
+	for i := 0; i < target.NumField(); i++ {
+		field := target.Field(i)
+		if !field.IsExported() {
+			continue
+		}
+		key, optional, skip := structFieldKey(field)
+		if skip {
+			continue
+		}
+		value, ok := lookupField(m, key)
+		if !ok {
+			if optional {
+				continue
+			}
+			return nil, &FieldError{Path: key, Err: ErrMissingField}
+		}
+		goValue, err := r.ToGo(value, field.Type)
+		if err != nil {
+			if fieldErr, ok := err.(*FieldError); ok {
+				// A field of a nested struct
+				return nil, &FieldError{Path: key + "." + fieldErr.Path, Err: fieldErr.Err}
+			}
+			return nil, &FieldError{Path: key, Err: err}
+		}
+		if goValue != nil {
+			v := reflect.ValueOf(goValue)
+			if v.Type() != field.Type && v.Type().ConvertibleTo(field.Type) {
+				// Numbers are converted to their kind, as int for a named
+				// int type such as time.Duration
+				v = v.Convert(field.Type)
+			}
+			result.Field(i).Set(v)
+		}
+	}
+	return result.Interface(), nil
+}
+
+// structFieldKey returns the map key for a struct field and whether the
+// field is optional or skipped.
+func structFieldKey(field reflect.StructField) (key string, optional, skip bool) {
+	key = field.Name
+	optional = field.Type.Kind() == reflect.Ptr
+	tag, ok := field.Tag.Lookup("risor")
+	if !ok {
+		tag, ok = field.Tag.Lookup("json")
+	}
+	if !ok {
+		return key, optional, false
+	}
+	name, opts, _ := strings.Cut(tag, ",")
+	if name == "-" && opts == "" {
+		return "", false, true
+	}
+	if name != "" {
+		key = name
+	}
+	for _, opt := range strings.Split(opts, ",") {
+		if opt == "omitempty" || opt == "omitzero" {
+			optional = true
+		}
+	}
+	return key, optional, false
+}
+
+// lookupField returns the map entry for key, or else the first entry, in
+// sorted order, whose key matches it ignoring case.
+func lookupField(m *Map, key string) (Object, bool) {
+	if value, ok := m.items[key]; ok {
+		return value, true
+	}
+	for _, k := range m.SortedKeys() {
+		if strings.EqualFold(k, key) {
+			return m.items[k], true
+		}
+	}
+	return nil, false
+}
+
 // toNumeric handles all numeric conversions.
 func toNumeric(obj Object, target reflect.Type) (any, error) {
 	var intVal int64
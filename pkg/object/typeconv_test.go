@@ -1,7 +1,9 @@
 package object
 
 import (
+	"context"
 	"encoding/json"
+	"errors"
 	"reflect"
 	"testing"
 	"time"
@@ -800,6 +802,31 @@ func TestTypeRegistryObjectPassthrough(t *testing.T) {
 	})
 }
 
+func TestTypeRegistryBuiltinCallback(t *testing.T) {
+	registry := DefaultRegistry()
+	first := func(ctx context.Context, args ...Object) (Object, error) {
+		return args[0], nil
+	}
+
+	for _, fn := range []any{first, BuiltinFunction(first)} {
+		result, err := registry.FromGo(fn)
+		assert.Nil(t, err)
+		builtin, ok := result.(*Builtin)
+		assert.True(t, ok)
+		assert.Equal(t, builtin.Name(), "callback")
+
+		// Arguments are passed through without conversion
+		list := NewList([]Object{NewInt(1)})
+		out, err := builtin.Call(context.Background(), list)
+		assert.Nil(t, err)
+		assert.True(t, out == Object(list))
+	}
+
+	result, err := registry.FromGo(BuiltinFunction(nil))
+	assert.Nil(t, err)
+	assert.Equal(t, result, Object(Nil))
+}
+
 func TestTypeRegistryNestedStructures(t *testing.T) {
 	registry := DefaultRegistry()
 
@@ -839,6 +866,57 @@ func TestTypeRegistryNestedStructures(t *testing.T) {
 	})
 }
 
+func TestTypeRegistryStruct(t *testing.T) {
+	type server struct {
+		Host string
+		Port int `json:"port"`
+	}
+	type config struct {
+		Name    string        `risor:"name"`
+		Timeout time.Duration `risor:"timeout"`
+		Server  server        `risor:"server"`
+		Tags    []string      `risor:"tags,omitempty"`
+		Limit   *int          `risor:"limit"`
+		Skipped string        `risor:"-"`
+	}
+	registry := DefaultRegistry()
+	target := reflect.TypeOf(config{})
+
+	result, err := registry.ToGo(NewMap(map[string]Object{
+		"name":    NewString("svc"),
+		"timeout": NewInt(5),
+		"server":  NewMap(map[string]Object{"host": NewString("localhost"), "port": NewInt(80)}),
+		"Skipped": NewString("x"),
+		"extra":   NewInt(1),
+	}), target)
+	assert.Nil(t, err)
+	assert.Equal(t, result, config{
+		Name:    "svc",
+		Timeout: 5,
+		Server:  server{Host: "localhost", Port: 80},
+	})
+
+	// Missing and mistyped fields are named in the error
+	_, err = registry.ToGo(NewMap(map[string]Object{
+		"name":   NewString("svc"),
+		"server": NewMap(map[string]Object{"host": NewString("localhost")}),
+	}), target)
+	assert.Equal(t, err.Error(), `missing field "timeout"`)
+	assert.True(t, errors.Is(err, ErrMissingField))
+
+	_, err = registry.ToGo(NewMap(map[string]Object{
+		"name":    NewString("svc"),
+		"timeout": NewInt(5),
+		"server":  NewMap(map[string]Object{"host": NewString("localhost"), "port": NewString("80")}),
+	}), target)
+	var fieldErr *FieldError
+	assert.True(t, errors.As(err, &fieldErr))
+	assert.Equal(t, fieldErr.Path, "server.port")
+
+	_, err = registry.ToGo(NewString("svc"), target)
+	assert.NotNil(t, err)
+}
+
 func TestAsObjectsWithRegistry(t *testing.T) {
 	type Custom struct {
 		Value int
@@ -15,6 +15,7 @@ const (
 	// Defer (removed in v2)    Code = 5
 	// Go (removed in v2)       Code = 6
 	CallSpread Code = 7 // Call with args from list on stack
+	CallMethod Code = 8 // Call a method of an object: operand1=name index, operand2=argc
 
 	// Jump
 	JumpBackward           Code = 10
@@ -31,6 +32,8 @@ const (
 	LoadGlobal    Code = 23
 	LoadConst     Code = 24
 	LoadAttrOrNil Code = 25 // Like LoadAttr but returns nil instead of error for missing attrs
+	LoadName      Code = 26 // Load a global by name at runtime: operand1=name index
+	LoadNameOrNil Code = 27 // Like LoadName but pushes nil if the global is undefined
 
 	// Store
 	StoreAttr   Code = 30
@@ -43,23 +46,34 @@ const (
 	CompareOp     Code = 41
 	UnaryNegative Code = 42
 	UnaryNot      Code = 43
+	UnaryInvert   Code = 44
+
+	// Specialized operations. The VM rewrites BinaryOp and CompareOp
+	// instructions into these at runtime once they are executed with two ints,
+	// and back again if other types turn up. The compiler never emits them.
+	BinaryOpInt  Code = 45
+	CompareOpInt Code = 46
 
 	// Build
 	BuildList   Code = 50
 	BuildMap    Code = 51
+	BuildTuple  Code = 52
 	BuildString Code = 53
 	ListAppend  Code = 54 // Append TOS to list at TOS-1
 	ListExtend  Code = 55 // Extend list at TOS-1 with iterable at TOS
 	MapMerge    Code = 56 // Merge map at TOS into map at TOS-1
 	MapSet      Code = 57 // Set key (TOS-1) to value (TOS) in map at TOS-2
+	BuildQuote  Code = 58 // Build a quote from source fragments alternating with unquoted values: operand1=number of values
 
 	// Containers
 	BinarySubscr Code = 60
 	StoreSubscr  Code = 61
 	ContainsOp   Code = 62
 	Length       Code = 63
-	Slice        Code = 64
+	Slice        Code = 64 // Slice a container: operand1=number of slice arguments (2, or 3 with a step)
 	Unpack       Code = 65
+	StoreSlice   Code = 66 // Assign a value to a slice of a container: operand1=number of slice arguments
+	DeleteSubscr Code = 67 // Delete the item at index TOS from the container at TOS-1
 
 	// Stack
 	Swap   Code = 70
@@ -82,7 +96,8 @@ const (
 
 	// Closures
 	LoadClosure Code = 120
-	MakeCell    Code = 121
+	MakeCell    Code = 121 // Push a cell for a local of the active frame: operand1=local index
+	LoadCell    Code = 122 // Push the active closure's free variable cell
 
 	// Partials
 	Partial Code = 130
@@ -92,6 +107,10 @@ const (
 	PopExcept  Code = 141 // Pop exception handler (normal try completion)
 	Throw      Code = 142 // Throw TOS as exception
 	EndFinally Code = 143 // End finally block, re-raise pending exception if any
+	AssertFail Code = 144 // Raise an assertion error: operand1=source constant, operand2=comparison operator or 0
+
+	// Modules
+	Import Code = 150 // Push the module for an import path: operand1=path constant
 )
 
 // BinaryOpType describes a type of binary operation, as in an operation that
@@ -200,15 +219,21 @@ func init() {
 	}
 	ops := []opInfo{
 		{BinaryOp, "BINARY_OP", 1},
+		{BinaryOpInt, "BINARY_OP_INT", 1},
 		{BinarySubscr, "BINARY_SUBSCR", 0},
 		{BuildList, "BUILD_LIST", 1},
 		{BuildMap, "BUILD_MAP", 1},
+		{BuildQuote, "BUILD_QUOTE", 1},
 		{BuildString, "BUILD_STRING", 1},
+		{BuildTuple, "BUILD_TUPLE", 1},
 		{Call, "CALL", 1},
 		{CallSpread, "CALL_SPREAD", 0},
+		{CallMethod, "CALL_METHOD", 2},
 		{CompareOp, "COMPARE_OP", 1},
+		{CompareOpInt, "COMPARE_OP_INT", 1},
 		{ContainsOp, "CONTAINS_OP", 1},
 		{Copy, "COPY", 1},
+		{DeleteSubscr, "DELETE_SUBSCR", 0},
 		{False, "FALSE", 0},
 		{Halt, "HALT", 0},
 		{JumpBackward, "JUMP_BACKWARD", 1},
@@ -220,12 +245,15 @@ func init() {
 		{MapSet, "MAP_SET", 0},
 		{LoadAttr, "LOAD_ATTR", 1},
 		{LoadAttrOrNil, "LOAD_ATTR_OR_NIL", 1},
+		{LoadCell, "LOAD_CELL", 1},
 		{LoadClosure, "LOAD_CLOSURE", 2},
 		{LoadConst, "LOAD_CONST", 1},
 		{LoadFast, "LOAD_FAST", 1},
 		{LoadFree, "LOAD_FREE", 1},
 		{LoadGlobal, "LOAD_GLOBAL", 1},
-		{MakeCell, "MAKE_CELL", 2},
+		{LoadName, "LOAD_NAME", 1},
+		{LoadNameOrNil, "LOAD_NAME_OR_NIL", 1},
+		{MakeCell, "MAKE_CELL", 1},
 		{Nil, "NIL", 0},
 		{Nop, "NOP", 0},
 		{Partial, "PARTIAL", 1},
@@ -235,21 +263,25 @@ func init() {
 		{PopJumpForwardIfTrue, "POP_JUMP_FORWARD_IF_TRUE", 1},
 		{PopTop, "POP_TOP", 0},
 		{ReturnValue, "RETURN_VALUE", 0},
-		{Slice, "SLICE", 0},
+		{Slice, "SLICE", 1},
 		{StoreAttr, "STORE_ATTR", 1},
 		{StoreFast, "STORE_FAST", 1},
 		{StoreFree, "STORE_FREE", 1},
 		{StoreGlobal, "STORE_GLOBAL", 1},
+		{StoreSlice, "STORE_SLICE", 1},
 		{StoreSubscr, "STORE_SUBSCR", 0},
 		{Swap, "SWAP", 1},
 		{True, "TRUE", 0},
 		{UnaryNegative, "UNARY_NEGATIVE", 0},
 		{UnaryNot, "UNARY_NOT", 0},
+		{UnaryInvert, "UNARY_INVERT", 0},
 		{Unpack, "UNPACK", 1},
 		{PushExcept, "PUSH_EXCEPT", 2},
 		{PopExcept, "POP_EXCEPT", 0},
 		{Throw, "THROW", 0},
 		{EndFinally, "END_FINALLY", 0},
+		{AssertFail, "ASSERT_FAIL", 2},
+		{Import, "IMPORT", 1},
 	}
 	for _, o := range ops {
 		infos[o.op] = Info{
@@ -24,6 +24,7 @@ func TestGetInfoAllOpcodes(t *testing.T) {
 		{Call, "CALL", 1},
 		{ReturnValue, "RETURN_VALUE", 0},
 		{CallSpread, "CALL_SPREAD", 0},
+		{CallMethod, "CALL_METHOD", 2},
 		{JumpBackward, "JUMP_BACKWARD", 1},
 		{JumpForward, "JUMP_FORWARD", 1},
 		{PopJumpForwardIfFalse, "POP_JUMP_FORWARD_IF_FALSE", 1},
@@ -36,6 +37,8 @@ func TestGetInfoAllOpcodes(t *testing.T) {
 		{LoadGlobal, "LOAD_GLOBAL", 1},
 		{LoadConst, "LOAD_CONST", 1},
 		{LoadAttrOrNil, "LOAD_ATTR_OR_NIL", 1},
+		{LoadName, "LOAD_NAME", 1},
+		{LoadNameOrNil, "LOAD_NAME_OR_NIL", 1},
 		{StoreAttr, "STORE_ATTR", 1},
 		{StoreFast, "STORE_FAST", 1},
 		{StoreFree, "STORE_FREE", 1},
@@ -44,19 +47,26 @@ func TestGetInfoAllOpcodes(t *testing.T) {
 		{CompareOp, "COMPARE_OP", 1},
 		{UnaryNegative, "UNARY_NEGATIVE", 0},
 		{UnaryNot, "UNARY_NOT", 0},
+		{UnaryInvert, "UNARY_INVERT", 0},
+		{BinaryOpInt, "BINARY_OP_INT", 1},
+		{CompareOpInt, "COMPARE_OP_INT", 1},
 		{BuildList, "BUILD_LIST", 1},
 		{BuildMap, "BUILD_MAP", 1},
+		{BuildTuple, "BUILD_TUPLE", 1},
 		{BuildString, "BUILD_STRING", 1},
 		{ListAppend, "LIST_APPEND", 0},
 		{ListExtend, "LIST_EXTEND", 0},
 		{MapMerge, "MAP_MERGE", 0},
 		{MapSet, "MAP_SET", 0},
+		{BuildQuote, "BUILD_QUOTE", 1},
 		{BinarySubscr, "BINARY_SUBSCR", 0},
 		{StoreSubscr, "STORE_SUBSCR", 0},
 		{ContainsOp, "CONTAINS_OP", 1},
 		{Length, "LENGTH", 0},
-		{Slice, "SLICE", 0},
+		{Slice, "SLICE", 1},
 		{Unpack, "UNPACK", 1},
+		{StoreSlice, "STORE_SLICE", 1},
+		{DeleteSubscr, "DELETE_SUBSCR", 0},
 		{Swap, "SWAP", 1},
 		{Copy, "COPY", 1},
 		{PopTop, "POP_TOP", 0},
@@ -64,12 +74,15 @@ func TestGetInfoAllOpcodes(t *testing.T) {
 		{False, "FALSE", 0},
 		{True, "TRUE", 0},
 		{LoadClosure, "LOAD_CLOSURE", 2},
-		{MakeCell, "MAKE_CELL", 2},
+		{MakeCell, "MAKE_CELL", 1},
+		{LoadCell, "LOAD_CELL", 1},
 		{Partial, "PARTIAL", 1},
 		{PushExcept, "PUSH_EXCEPT", 2},
 		{PopExcept, "POP_EXCEPT", 0},
 		{Throw, "THROW", 0},
 		{EndFinally, "END_FINALLY", 0},
+		{AssertFail, "ASSERT_FAIL", 2},
+		{Import, "IMPORT", 1},
 	}
 	for _, tt := range tests {
 		t.Run(tt.name, func(t *testing.T) {
@@ -159,6 +172,9 @@ func TestOpcodeConstants(t *testing.T) {
 	assert.Equal(t, LoadAttr, Code(20))
 	assert.Equal(t, StoreAttr, Code(30))
 	assert.Equal(t, BinaryOp, Code(40))
+	assert.Equal(t, UnaryInvert, Code(44))
+	assert.Equal(t, BinaryOpInt, Code(45))
+	assert.Equal(t, CompareOpInt, Code(46))
 	assert.Equal(t, BuildList, Code(50))
 	assert.Equal(t, BinarySubscr, Code(60))
 	assert.Equal(t, Swap, Code(70))
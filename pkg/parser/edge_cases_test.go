@@ -135,22 +135,23 @@ func TestOperatorPrecedenceNotInOperator(t *testing.T) {
 	assert.Equal(t, "+", infix.Op)
 }
 
-func TestAssignmentChainingNotSupported(t *testing.T) {
-	// Risor's Assign is a statement, not an expression
-	// So assignment chaining like "x = y = z = 1" parses as separate statements
-	// First statement: "x = y"
-	// Then: "=" is invalid since = requires an identifier on left
-	program, err := Parse(context.Background(), "x = y", nil)
+func TestAssignmentChaining(t *testing.T) {
+	// "x = y = z = 1" assigns 1 to all three variables
+	program, err := Parse(context.Background(), "x = y = z = 1", nil)
 	assert.Nil(t, err)
 
-	assign, ok := program.First().(*ast.Assign)
-	assert.True(t, ok, "expected Assign")
-	assert.Equal(t, "x", assign.Name.Name)
+	assign, ok := program.First().(*ast.MultiAssign)
+	assert.True(t, ok, "expected MultiAssign, got %T", program.First())
+	assert.False(t, assign.Unpack)
+	assert.Len(t, assign.Names, 3)
+	assert.Equal(t, "x = y = z = 1", assign.String())
 
-	// The value should be identifier "y"
-	ident, ok := assign.Value.(*ast.Ident)
-	assert.True(t, ok, "expected Ident as value")
-	assert.Equal(t, "y", ident.Name)
+	// A single assignment is still an Assign
+	program, err = Parse(context.Background(), "x = y", nil)
+	assert.Nil(t, err)
+	single, ok := program.First().(*ast.Assign)
+	assert.True(t, ok, "expected Assign")
+	assert.Equal(t, "x", single.Name.Name)
 }
 
 // =============================================================================
@@ -496,6 +497,11 @@ func TestSliceExpressions(t *testing.T) {
 		{"a[1:]", "a[1:]"},
 		{"a[:2]", "a[:2]"},
 		{"a[1:2]", "a[1:2]"},
+		{"a[::-1]", "a[::(-1)]"},
+		{"a[1:5:2]", "a[1:5:2]"},
+		{"a[:5:2]", "a[:5:2]"},
+		{"a[1::2]", "a[1::2]"},
+		{"a[::]", "a[:]"},
 	}
 
 	for _, tt := range tests {
@@ -1233,6 +1239,34 @@ func TestMapWithExpressionKeys(t *testing.T) {
 	assert.Len(t, m.Items, 2)
 }
 
+func TestMapComputedKeys(t *testing.T) {
+	program, err := Parse(context.Background(), `{[k]: 1, ["a" + "b"]: 2, c: 3}`, nil)
+	assert.Nil(t, err)
+
+	m, ok := program.First().(*ast.Map)
+	assert.True(t, ok)
+	assert.Len(t, m.Items, 3)
+
+	assert.True(t, m.Items[0].Computed)
+	key, ok := m.Items[0].Key.(*ast.Ident)
+	assert.True(t, ok)
+	assert.Equal(t, "k", key.Name)
+
+	assert.True(t, m.Items[1].Computed)
+	_, ok = m.Items[1].Key.(*ast.Infix)
+	assert.True(t, ok)
+
+	assert.False(t, m.Items[2].Computed)
+	assert.Equal(t, `{[k]:1, [("a" + "b")]:2, c:3}`, m.String())
+}
+
+func TestMapComputedKeyErrors(t *testing.T) {
+	for _, input := range []string{`{[k: 1}`, `{[k] 1}`, `{[]: 1}`} {
+		_, err := Parse(context.Background(), input, nil)
+		assert.NotNil(t, err, input)
+	}
+}
+
 func TestMapTrailingComma(t *testing.T) {
 	program, err := Parse(context.Background(), "{a: 1, b: 2,}", nil)
 	assert.Nil(t, err)
@@ -63,6 +63,19 @@ func (p *Parser) parsePrefixExpr() (ast.Node, bool) {
 	return &ast.Prefix{OpPos: opPos, Op: op, X: right}, true
 }
 
+// parsePrefixIncrement reports an error for "++x" and "--x". Risor only has
+// the postfix forms, which are statements.
+func (p *Parser) parsePrefixIncrement() (ast.Node, bool) {
+	op := p.curToken.Literal
+	if p.peekTokenIs(token.IDENT) {
+		p.setTokenError(p.curToken, "prefix %q is not supported; use %q as a statement instead",
+			op, p.peekToken.Literal+op)
+	} else {
+		p.setTokenError(p.curToken, "prefix %q is not supported; use a postfix %q statement instead", op, op)
+	}
+	return nil, false
+}
+
 func (p *Parser) parseInfixExpr(leftNode ast.Node) (ast.Node, bool) {
 	left, ok := leftNode.(ast.Expr)
 	if !ok {
@@ -208,6 +221,10 @@ func (p *Parser) convertMapToDestructureParam(m *ast.Map) *ast.ObjectDestructure
 			p.setTokenError(p.curToken, "spread not allowed in destructuring parameter")
 			return nil
 		}
+		if item.Computed {
+			p.setTokenError(p.curToken, "computed key not allowed in destructuring parameter")
+			return nil
+		}
 
 		// Get key name - can be Ident (explicit key:value) or String (shorthand)
 		var keyName string
@@ -236,6 +253,20 @@ func (p *Parser) convertMapToDestructureParam(m *ast.Map) *ast.ObjectDestructure
 			case *ast.DefaultValue:
 				// Shorthand with default: {a = expr}
 				binding.Default = v.Default
+			case *ast.Map:
+				// Nested pattern: {a: {b}}
+				pattern := p.convertMapToDestructureParam(v)
+				if pattern == nil {
+					return nil
+				}
+				binding.Pattern = pattern
+			case *ast.List:
+				// Nested pattern: {a: [b, c]}
+				pattern := p.convertListToDestructureParam(v)
+				if pattern == nil {
+					return nil
+				}
+				binding.Pattern = pattern
 			default:
 				p.setTokenError(p.curToken, "expected identifier or default in destructuring pattern")
 				return nil
@@ -264,6 +295,20 @@ func (p *Parser) convertListToDestructureParam(l *ast.List) *ast.ArrayDestructur
 				Name:    v.Name,
 				Default: v.Default,
 			})
+		case *ast.Map:
+			// Nested pattern: [a, {b}]
+			pattern := p.convertMapToDestructureParam(v)
+			if pattern == nil {
+				return nil
+			}
+			elements = append(elements, ast.ArrayDestructureElement{Pattern: pattern})
+		case *ast.List:
+			// Nested pattern: [a, [b, c]]
+			pattern := p.convertListToDestructureParam(v)
+			if pattern == nil {
+				return nil
+			}
+			elements = append(elements, ast.ArrayDestructureElement{Pattern: pattern})
 		default:
 			p.setTokenError(p.curToken, "expected identifier in array destructuring pattern")
 			return nil
@@ -415,7 +460,7 @@ func (p *Parser) parseIndex(leftNode ast.Node) (ast.Node, bool) {
 		return nil, false
 	}
 	lbrack := p.curToken.StartPosition
-	var firstIndex, secondIndex ast.Expr
+	var firstIndex, secondIndex, step ast.Expr
 	if !p.peekTokenIs(token.COLON) {
 		p.nextToken() // move to the first index
 		firstIndex = p.parseExpression(LOWEST)
@@ -430,22 +475,29 @@ func (p *Parser) parseIndex(leftNode ast.Node) (ast.Node, bool) {
 	}
 	if p.peekTokenIs(token.COLON) {
 		p.nextToken() // move to the ":"
-		if p.peekTokenIs(token.RBRACKET) {
-			p.nextToken() // move to the "]"
-			rbrack := p.curToken.StartPosition
-			return &ast.Slice{X: left, Lbrack: lbrack, Low: firstIndex, High: nil, Rbrack: rbrack}, true
+		if !p.peekTokenIs(token.RBRACKET) && !p.peekTokenIs(token.COLON) {
+			p.nextToken() // move to the second index
+			secondIndex = p.parseExpression(LOWEST)
+			if secondIndex == nil {
+				return nil, false
+			}
 		}
-		p.nextToken() // move to the second index
-		secondIndex = p.parseExpression(LOWEST)
-		if secondIndex == nil {
-			return nil, false
+		if p.peekTokenIs(token.COLON) {
+			p.nextToken() // move to the second ":"
+			if !p.peekTokenIs(token.RBRACKET) {
+				p.nextToken() // move to the step
+				step = p.parseExpression(LOWEST)
+				if step == nil {
+					return nil, false
+				}
+			}
 		}
 	}
 	if !p.expectPeek("an index expression", token.RBRACKET) {
 		return nil, false
 	}
 	rbrack := p.curToken.StartPosition
-	return &ast.Slice{X: left, Lbrack: lbrack, Low: firstIndex, High: secondIndex, Rbrack: rbrack}, true
+	return &ast.Slice{X: left, Lbrack: lbrack, Low: firstIndex, High: secondIndex, Step: step, Rbrack: rbrack}, true
 }
 
 func (p *Parser) parseCall(functionNode ast.Node) (ast.Node, bool) {
@@ -607,8 +659,21 @@ func (p *Parser) parseOptionalChain(objNode ast.Node) (ast.Node, bool) {
 	period := p.curToken.StartPosition
 	p.nextToken()
 	p.eatNewlines()
+	if p.curTokenIs(token.LBRACKET) {
+		node, ok := p.parseIndex(obj)
+		if !ok {
+			return nil, false
+		}
+		index, ok := node.(*ast.Index)
+		if !ok {
+			p.setTokenError(p.curToken, "slicing is not supported after %q", "?.")
+			return nil, false
+		}
+		index.Optional = true
+		return index, true
+	}
 	if !p.curTokenIs(token.IDENT) {
-		p.setTokenError(p.curToken, "expected an identifier after %q", "?.")
+		p.setTokenError(p.curToken, "expected an identifier or \"[\" after %q", "?.")
 		return nil, false
 	}
 	name := p.newIdent(p.curToken)
@@ -802,3 +867,17 @@ func (p *Parser) parsePattern() ast.Pattern {
 
 	return &ast.LiteralPattern{Value: expr}
 }
+
+// parseQuote parses a quote expression: quote { ... }. The block is parsed
+// like any other, so syntax errors in quoted code are reported up front.
+func (p *Parser) parseQuote() (ast.Node, bool) {
+	quotePos := p.curToken.StartPosition
+	if !p.expectPeek("quote expression", token.LBRACE) {
+		return nil, false
+	}
+	body := p.parseBlock()
+	if body == nil {
+		return nil, false
+	}
+	return &ast.Quote{Quote: quotePos, Body: body}, true
+}
@@ -54,6 +54,7 @@ func TestPrefix(t *testing.T) {
 		{"-15;", "-", 15},
 		{"!true;", "!", true},
 		{"!false", "!", false},
+		{"~7", "~", 7},
 	}
 	for _, tt := range tests {
 		t.Run(tt.input, func(t *testing.T) {
@@ -336,6 +337,22 @@ func TestSliceAST(t *testing.T) {
 	assert.Equal(t, int64(5), high.Value)
 }
 
+func TestSliceStep(t *testing.T) {
+	program, err := Parse(context.Background(), "arr[5:1:-2]", nil)
+	assert.Nil(t, err)
+
+	slice, ok := program.First().(*ast.Slice)
+	assert.True(t, ok)
+	testLiteralExpression(t, slice.Low, 5)
+	testLiteralExpression(t, slice.High, 1)
+	prefix, ok := slice.Step.(*ast.Prefix)
+	assert.True(t, ok)
+	assert.Equal(t, prefix.Op, "-")
+
+	_, err = Parse(context.Background(), "arr[1:2:3:4]", nil)
+	assert.NotNil(t, err)
+}
+
 func TestCall(t *testing.T) {
 	program, err := Parse(context.Background(), "add(1, 2*3, 4+5)", nil)
 	assert.Nil(t, err)
@@ -632,6 +649,9 @@ func TestOptionalChaining(t *testing.T) {
 		{`obj?.method(1, 2)`, `obj?.method(1, 2)`},
 		{`obj.a?.b`, `obj.a?.b`},
 		{`obj?.a.b`, `obj?.a.b`},
+		{`items?.[0]`, `items?.[0]`},
+		{`obj?.items?.[i + 1]`, `obj?.items?.[(i + 1)]`},
+		{`items?.[0].name`, `items?.[0].name`},
 	}
 	for _, tt := range tests {
 		t.Run(tt.input, func(t *testing.T) {
@@ -659,6 +679,31 @@ func TestOptionalChainingAST(t *testing.T) {
 	assert.Equal(t, "obj", obj.Name)
 }
 
+func TestOptionalIndexAST(t *testing.T) {
+	program, err := Parse(context.Background(), "items?.[0]", nil)
+	assert.Nil(t, err)
+	index, ok := program.First().(*ast.Index)
+	assert.True(t, ok, "expected Index node")
+	assert.True(t, index.Optional)
+}
+
+func TestOptionalChainingErrors(t *testing.T) {
+	tests := []string{
+		`items?.[0] = 1`,
+		`items?.[0] += 1`,
+		`items?.[0]++`,
+		`obj?.count++`,
+		`items?.[1:2]`,
+		`obj?.1`,
+	}
+	for _, input := range tests {
+		t.Run(input, func(t *testing.T) {
+			_, err := Parse(context.Background(), input, nil)
+			assert.NotNil(t, err)
+		})
+	}
+}
+
 func TestMethodChaining(t *testing.T) {
 	tests := []struct {
 		input    string
@@ -1409,3 +1454,23 @@ func TestMatchGuards(t *testing.T) {
 		assert.Nil(t, matchExpr.Default.Guard)
 	})
 }
+
+func TestQuoteExpression(t *testing.T) {
+	program, err := Parse(context.Background(), `let q = quote { unquote(a) + quote { unquote(b) } }`, nil)
+	assert.Nil(t, err)
+	assert.Len(t, program.Stmts, 1)
+
+	v, ok := program.First().(*ast.Var)
+	assert.True(t, ok)
+	quote, ok := v.Value.(*ast.Quote)
+	assert.True(t, ok)
+	assert.Len(t, quote.Body.Stmts, 1)
+
+	// The unquote in the nested quote belongs to that quote
+	unquotes := quote.Unquotes()
+	assert.Len(t, unquotes, 1)
+	assert.Equal(t, unquotes[0].String(), "unquote(a)")
+
+	_, err = Parse(context.Background(), `quote x`, nil)
+	assert.NotNil(t, err)
+}
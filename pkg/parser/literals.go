@@ -1,6 +1,8 @@
 package parser
 
 import (
+	"context"
+	"errors"
 	"fmt"
 	"strconv"
 	"strings"
@@ -74,7 +76,7 @@ func (p *Parser) parseBoolean() (ast.Node, bool) {
 }
 
 func (p *Parser) parseNil() (ast.Node, bool) {
-	return &ast.Nil{NilPos: p.curToken.StartPosition}, true
+	return &ast.Nil{NilPos: p.curToken.StartPosition, Literal: p.curToken.Literal}, true
 }
 
 func (p *Parser) parseString() (ast.Node, bool) {
@@ -83,6 +85,7 @@ func (p *Parser) parseString() (ast.Node, bool) {
 	if strToken.Type == token.STRING {
 		return &ast.String{
 			ValuePos: strToken.StartPosition,
+			ValueEnd: strToken.EndPosition.Advance(1),
 			Literal:  strToken.Literal,
 			Value:    strToken.Literal,
 		}, true
@@ -91,6 +94,7 @@ func (p *Parser) parseString() (ast.Node, bool) {
 	if !strings.Contains(strToken.Literal, "${") {
 		return &ast.String{
 			ValuePos: strToken.StartPosition,
+			ValueEnd: strToken.EndPosition.Advance(1),
 			Literal:  strToken.Literal,
 			Value:    strToken.Literal,
 		}, true
@@ -101,39 +105,47 @@ func (p *Parser) parseString() (ast.Node, bool) {
 		p.setTokenError(strToken, "%s", err.Error())
 		return nil, false
 	}
+	exprs, err := parseTemplateExprs(p.ctx, tmpl, p.l.Filename())
+	if err != nil {
+		p.setTokenError(strToken, "%s", err.Error())
+		return nil, false
+	}
+	return &ast.String{
+		ValuePos: strToken.StartPosition,
+		ValueEnd: strToken.EndPosition.Advance(1),
+		Literal:  strToken.Literal,
+		Value:    strToken.Literal,
+		Template: tmpl,
+		Exprs:    exprs,
+	}, true
+}
+
+// parseTemplateExprs parses the expression in each ${...} hole of a template.
+// An empty hole gives a nil expression.
+func parseTemplateExprs(ctx context.Context, t *tmpl.Template, filename string) ([]ast.Expr, error) {
 	var exprs []ast.Expr
-	for _, e := range tmpl.Fragments() {
+	for _, e := range t.Fragments() {
 		if !e.IsVariable() {
 			continue
 		}
-		tmplAst, err := Parse(p.ctx, e.Value(), &Config{Filename: p.l.Filename()})
+		tmplAst, err := Parse(ctx, e.Value(), &Config{Filename: filename})
 		if err != nil {
-			p.setTokenError(strToken, "in template interpolation: %s", err.Error())
-			return nil, false
+			return nil, fmt.Errorf("in template interpolation: %s", err.Error())
 		}
 		statements := tmplAst.Stmts
 		if len(statements) == 0 {
 			exprs = append(exprs, nil)
 		} else if len(statements) > 1 {
-			p.setTokenError(strToken, "template contains more than one expression")
-			return nil, false
+			return nil, errors.New("template contains more than one expression")
 		} else {
-			stmt := statements[0]
-			expr, ok := stmt.(ast.Expr)
+			expr, ok := statements[0].(ast.Expr)
 			if !ok {
-				p.setTokenError(strToken, "template contains an unexpected statement type")
-				return nil, false
+				return nil, errors.New("template contains an unexpected statement type")
 			}
 			exprs = append(exprs, expr)
 		}
 	}
-	return &ast.String{
-		ValuePos: strToken.StartPosition,
-		Literal:  strToken.Literal,
-		Value:    strToken.Literal,
-		Template: tmpl,
-		Exprs:    exprs,
-	}, true
+	return exprs, nil
 }
 
 func (p *Parser) parseList() (ast.Node, bool) {
@@ -310,7 +322,7 @@ func (p *Parser) parseMap() (ast.Node, bool) {
 	return &ast.Map{Lbrace: lbrace, Items: items, Rbrace: rbrace}, true
 }
 
-// parseMapItem parses a single map item: spread (...obj), shorthand (a), shorthand with default (a = expr), or key-value (a: b).
+// parseMapItem parses a single map item: spread (...obj), shorthand (a), shorthand with default (a = expr), key-value (a: b), or computed key-value ([expr]: b).
 func (p *Parser) parseMapItem() *ast.MapItem {
 	// Check for spread expression
 	if p.curTokenIs(token.SPREAD) {
@@ -363,6 +375,27 @@ func (p *Parser) parseMapItem() *ast.MapItem {
 		}
 	}
 
+	// Computed key: {[expr]: value}
+	if p.curTokenIs(token.LBRACKET) {
+		p.nextToken() // move past '['
+		key := p.parseExpression(LOWEST)
+		if key == nil {
+			return nil
+		}
+		if !p.expectPeek("map", token.RBRACKET) {
+			return nil
+		}
+		if !p.expectPeek("map", token.COLON) {
+			return nil
+		}
+		p.nextToken() // move to the value
+		value := p.parseExpression(LOWEST)
+		if value == nil {
+			return nil
+		}
+		return &ast.MapItem{Key: key, Value: value, Computed: true}
+	}
+
 	// Regular key-value pair
 	key := p.parseExpression(LOWEST)
 	if key == nil {
@@ -548,15 +581,23 @@ func (p *Parser) parseObjectDestructureParam() *ast.ObjectDestructureParam {
 		binding := ast.DestructureBinding{Key: p.curToken.Literal}
 		p.nextToken()
 
-		// Check for alias: {key: alias}
+		// Check for alias: {key: alias} or nested pattern: {key: {a}} / {key: [a]}
 		if p.curTokenIs(token.COLON) {
 			p.nextToken() // move past ':'
-			if !p.curTokenIs(token.IDENT) {
+			switch {
+			case p.curTokenIs(token.LBRACE) || p.curTokenIs(token.LBRACKET):
+				pattern := p.parseDestructureParamPattern()
+				if pattern == nil {
+					return nil
+				}
+				binding.Pattern = pattern
+			case p.curTokenIs(token.IDENT):
+				binding.Alias = p.curToken.Literal
+				p.nextToken()
+			default:
 				p.setTokenError(p.curToken, "expected identifier after ':' in destructuring pattern")
 				return nil
 			}
-			binding.Alias = p.curToken.Literal
-			p.nextToken()
 		}
 
 		// Check for default value: {key = value} or {key: alias = value}
@@ -612,14 +653,23 @@ func (p *Parser) parseArrayDestructureParam() *ast.ArrayDestructureParam {
 			break
 		}
 
-		if !p.curTokenIs(token.IDENT) {
+		var elem ast.ArrayDestructureElement
+		switch {
+		case p.curTokenIs(token.LBRACE) || p.curTokenIs(token.LBRACKET):
+			// Nested pattern: [a, [b, c]] or [a, {b}]
+			pattern := p.parseDestructureParamPattern()
+			if pattern == nil {
+				return nil
+			}
+			elem.Pattern = pattern
+		case p.curTokenIs(token.IDENT):
+			elem.Name = p.newIdent(p.curToken)
+			p.nextToken()
+		default:
 			p.setTokenError(p.curToken, "expected identifier in array destructuring pattern")
 			return nil
 		}
 
-		elem := ast.ArrayDestructureElement{Name: p.newIdent(p.curToken)}
-		p.nextToken()
-
 		// Check for default value: [a = value]
 		if p.curTokenIs(token.ASSIGN) {
 			p.nextToken() // move past '='
@@ -654,6 +704,22 @@ func (p *Parser) parseArrayDestructureParam() *ast.ArrayDestructureParam {
 	}
 }
 
+// parseDestructureParamPattern parses a nested object or array destructuring
+// pattern. The current token must be '{' or '['. On return, the current token
+// is the one following the closing brace or bracket.
+func (p *Parser) parseDestructureParamPattern() ast.FuncParam {
+	if p.curTokenIs(token.LBRACE) {
+		if param := p.parseObjectDestructureParam(); param != nil {
+			return param
+		}
+		return nil
+	}
+	if param := p.parseArrayDestructureParam(); param != nil {
+		return param
+	}
+	return nil
+}
+
 func (p *Parser) parseSpread() (ast.Node, bool) {
 	ellipsis := p.curToken.StartPosition
 	if err := p.nextToken(); err != nil {
@@ -1061,6 +1061,44 @@ func TestDestructureParamString(t *testing.T) {
 	})
 }
 
+func TestNestedDestructureParams(t *testing.T) {
+	tests := []struct {
+		input    string
+		expected string
+		names    []string
+	}{
+		{`function foo({a: {b, c}}) { b }`, "{a: {b, c}}", []string{"b", "c"}},
+		{`function foo({a: [b, c = 1]}) { b }`, "{a: [b, c = 1]}", []string{"b", "c"}},
+		{`function foo([a, [b, c]]) { b }`, "[a, [b, c]]", []string{"a", "b", "c"}},
+		{`function foo([{x, y: z}, b]) { b }`, "[{x, y: z}, b]", []string{"x", "z", "b"}},
+		{`function foo({a: {b} = {}}) { b }`, "{a: {b} = {}}", []string{"b"}},
+		{`function foo([a, [b, [c]]]) { c }`, "[a, [b, [c]]]", []string{"a", "b", "c"}},
+	}
+	for _, tt := range tests {
+		t.Run(tt.input, func(t *testing.T) {
+			program, err := Parse(context.Background(), tt.input, nil)
+			assert.Nil(t, err)
+
+			fn, ok := program.First().(*ast.Func)
+			assert.True(t, ok)
+			assert.Len(t, fn.Params, 1)
+			assert.Equal(t, tt.expected, fn.Params[0].String())
+			assert.Equal(t, tt.names, fn.Params[0].ParamNames())
+		})
+	}
+}
+
+func TestNestedArrowDestructureParams(t *testing.T) {
+	program, err := Parse(context.Background(), `({a: {b}, c: [d, e]}) => b + d`, nil)
+	assert.Nil(t, err)
+
+	fn, ok := program.First().(*ast.Func)
+	assert.True(t, ok)
+	assert.Len(t, fn.Params, 1)
+	assert.Equal(t, "{a: {b}, c: [d, e]}", fn.Params[0].String())
+	assert.Equal(t, []string{"b", "d", "e"}, fn.Params[0].ParamNames())
+}
+
 func TestDestructureParamInFuncString(t *testing.T) {
 	// Test that Func.String() correctly formats destructure params
 	program, err := Parse(context.Background(), `function foo({a, b}, [c, d]) { a }`, nil)
@@ -1166,3 +1204,22 @@ func TestSpreadAST(t *testing.T) {
 	assert.Equal(t, "items", ident.Name)
 	assert.Equal(t, "...items", spread.String())
 }
+
+func TestLiteralSpans(t *testing.T) {
+	tests := []string{
+		`"hello"`,
+		`"a\tb"`,
+		`"世界"`,
+		`nil`,
+		`null`,
+		`"é" + x`,
+	}
+	for _, input := range tests {
+		t.Run(input, func(t *testing.T) {
+			program, err := Parse(context.Background(), input, nil)
+			assert.Nil(t, err)
+			assert.Len(t, program.Stmts, 1)
+			assert.Equal(t, ast.SpanOf(program.First()).Text(input), input)
+		})
+	}
+}
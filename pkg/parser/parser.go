@@ -9,6 +9,7 @@ import (
 	"fmt"
 
 	"github.com/deepnoodle-ai/risor/v2/internal/lexer"
+	"github.com/deepnoodle-ai/risor/v2/internal/tmpl"
 	"github.com/deepnoodle-ai/risor/v2/internal/token"
 	"github.com/deepnoodle-ai/risor/v2/pkg/ast"
 )
@@ -47,13 +48,22 @@ type Config struct {
 	// This prevents stack overflow on deeply nested input.
 	// If 0, DefaultMaxDepth (500) is used.
 	MaxDepth int
+
+	// Identifiers lists keywords to read as identifiers, for code written for
+	// a language version that didn't reserve them. See
+	// compiler.UnreservedKeywords.
+	Identifiers []string
 }
 
 // Parse the provided input as Risor source code and return the AST. This is
 // shorthand way to create a Lexer and Parser and then call Parse on that.
 // Pass nil for cfg to use default settings.
 func Parse(ctx context.Context, input string, cfg *Config) (*ast.Program, error) {
-	l := lexer.New(input)
+	var opts []lexer.Option
+	if cfg != nil && len(cfg.Identifiers) > 0 {
+		opts = append(opts, lexer.WithIdentifiers(cfg.Identifiers...))
+	}
+	l := lexer.New(input, opts...)
 	if cfg != nil && cfg.Filename != "" {
 		l.SetFilename(cfg.Filename)
 	}
@@ -62,6 +72,31 @@ func Parse(ctx context.Context, input string, cfg *Config) (*ast.Program, error)
 	return p.Parse(ctx)
 }
 
+// ParseTemplate parses text as a template, in which each ${expr} hole holds
+// an expression and all other text is kept as is. The returned program holds
+// the equivalent template string, so running it renders the text. Pass nil
+// for cfg to use default settings.
+func ParseTemplate(ctx context.Context, text string, cfg *Config) (*ast.Program, error) {
+	t, err := tmpl.Parse(text)
+	if err != nil {
+		return nil, err
+	}
+	var filename string
+	if cfg != nil {
+		filename = cfg.Filename
+	}
+	exprs, err := parseTemplateExprs(ctx, t, filename)
+	if err != nil {
+		return nil, err
+	}
+	return &ast.Program{Stmts: []ast.Node{&ast.String{
+		Literal:  text,
+		Value:    text,
+		Template: t,
+		Exprs:    exprs,
+	}}}, nil
+}
+
 // DefaultMaxDepth is the default maximum nesting depth for parsing.
 const DefaultMaxDepth = 500
 
@@ -133,6 +168,7 @@ func New(l *lexer.Lexer, cfg *Config) *Parser {
 
 	// Register prefix-functions
 	p.registerPrefix(token.TEMPLATE, p.parseString)
+	p.registerPrefix(token.TILDE, p.parsePrefixExpr)
 	p.registerPrefix(token.BANG, p.parsePrefixExpr)
 	p.registerPrefix(token.EOF, p.illegalToken)
 	p.registerPrefix(token.FALSE, p.parseBoolean)
@@ -146,13 +182,17 @@ func New(l *lexer.Lexer, cfg *Config) *Parser {
 	p.registerPrefix(token.LBRACKET, p.parseList)
 	p.registerPrefix(token.LPAREN, p.parseGroupedExpr)
 	p.registerPrefix(token.MINUS, p.parsePrefixExpr)
+	p.registerPrefix(token.MINUS_MINUS, p.parsePrefixIncrement)
 	p.registerPrefix(token.NEWLINE, p.parseNewline)
 	p.registerPrefix(token.NIL, p.parseNil)
+	p.registerPrefix(token.PLUS_PLUS, p.parsePrefixIncrement)
+	p.registerPrefix(token.QUOTE, p.parseQuote)
 	p.registerPrefix(token.STRING, p.parseString)
 	p.registerPrefix(token.MATCH, p.parseMatch)
 	p.registerPrefix(token.TRUE, p.parseBoolean)
 	p.registerPrefix(token.SPREAD, p.parseSpread)
 	p.registerPrefix(token.TRY, p.parseTry)
+	p.registerPrefix(token.WITH, p.parseWith)
 
 	// Register infix functions
 	p.registerInfix(token.AND, p.parseInfixExpr)
@@ -306,7 +346,7 @@ func (p *Parser) synchronize() {
 		// Stop at statement-starting keywords
 		switch p.curToken.Type {
 		case token.LET, token.CONST, token.RETURN, token.IF,
-			token.FUNCTION, token.TRY, token.THROW:
+			token.FUNCTION, token.TRY, token.THROW, token.ASSERT, token.DEL, token.EXPORT, token.IMPORT:
 			return
 		}
 		prevPos := p.curToken.StartPosition
@@ -404,8 +444,32 @@ func (p *Parser) parseStatement() ast.Node {
 		if s := p.parseThrow(); s != nil {
 			stmt = s
 		}
+	case token.ASSERT:
+		if s := p.parseAssert(); s != nil {
+			stmt = s
+		}
+	case token.DEL:
+		if s := p.parseDel(); s != nil {
+			stmt = s
+		}
+	case token.EXPORT:
+		if s := p.parseExport(); s != nil {
+			stmt = s
+		}
+	case token.IMPORT:
+		if s := p.parseImport(); s != nil {
+			stmt = s
+		}
 	case token.NEWLINE:
 		stmt = nil
+	case token.IDENT:
+		if p.peekTokenIs(token.COMMA) {
+			if s := p.parseMultiAssign(); s != nil {
+				stmt = s
+			}
+		} else {
+			stmt = p.parseExpressionStatement()
+		}
 	default:
 		stmt = p.parseExpressionStatement()
 	}
@@ -494,6 +558,12 @@ func (p *Parser) parseExpression(precedence int) ast.Expr {
 	if expr, ok := node.(ast.Expr); ok {
 		return expr
 	}
+	if postfix, ok := node.(*ast.Postfix); ok {
+		// The operator is still the current token
+		p.setTokenError(p.curToken, "%s%s is a statement and has no value; increment on a separate line",
+			postfix.X, postfix.Op)
+		return nil
+	}
 	p.setTokenError(p.prevToken, "expected expression")
 	return nil
 }
@@ -1,6 +1,8 @@
 package parser
 
 import (
+	"unicode"
+
 	"github.com/deepnoodle-ai/risor/v2/internal/token"
 	"github.com/deepnoodle-ai/risor/v2/pkg/ast"
 )
@@ -13,6 +15,7 @@ import (
 // - Assignment statements
 // - Postfix operators (x++, x--)
 // - Try/catch/finally
+// - Assert, export, and import
 
 func (p *Parser) parseLet() ast.Node {
 	letPos := p.curToken.StartPosition
@@ -42,13 +45,17 @@ func (p *Parser) parseLet() ast.Node {
 		return nil
 	}
 	p.nextToken()
+	if len(idents) > 1 {
+		value := p.parseAssignmentValues()
+		if value == nil {
+			return nil
+		}
+		return &ast.MultiVar{Let: letPos, Names: idents, Value: value}
+	}
 	value := p.parseAssignmentValue()
 	if value == nil {
 		return nil
 	}
-	if len(idents) > 1 {
-		return &ast.MultiVar{Let: letPos, Names: idents, Value: value}
-	}
 	return &ast.Var{Let: letPos, Name: idents[0], Value: value}
 }
 
@@ -269,6 +276,68 @@ func (p *Parser) parseAssignmentValue() ast.Expr {
 	return result
 }
 
+// parseAssignmentValues parses the value assigned to several variables, which
+// may be a list of values separated by commas, as in "let a, b = 1, 2". The
+// values are collected into a tuple.
+func (p *Parser) parseAssignmentValues() ast.Expr {
+	value := p.parseAssignmentValue()
+	if value == nil || !p.peekTokenIs(token.COMMA) {
+		return value
+	}
+	items := []ast.Expr{value}
+	for p.peekTokenIs(token.COMMA) {
+		p.nextToken() // move to ','
+		p.nextToken() // move to the next value
+		item := p.parseExpression(LOWEST)
+		if item == nil {
+			return nil
+		}
+		items = append(items, item)
+	}
+	return &ast.Tuple{Items: items}
+}
+
+// parseMultiAssign parses an assignment to several variables, such as
+// "a, b = b, a", where the current token is the first name.
+func (p *Parser) parseMultiAssign() ast.Node {
+	names := []*ast.Ident{p.newIdent(p.curToken)}
+	for p.peekTokenIs(token.COMMA) {
+		p.nextToken()
+		if !p.expectPeek("assignment", token.IDENT) {
+			return nil
+		}
+		names = append(names, p.newIdent(p.curToken))
+	}
+	if !p.expectPeek("assignment", token.ASSIGN) {
+		return nil
+	}
+	opPos := p.curToken.StartPosition
+	p.nextToken()
+	value := p.parseAssignmentValues()
+	if value == nil {
+		return nil
+	}
+	return &ast.MultiAssign{Names: names, OpPos: opPos, Value: value, Unpack: true}
+}
+
+// parseChainAssign parses the rest of a chained assignment such as
+// "x = y = 0", where the current token is the second name.
+func (p *Parser) parseChainAssign(first *ast.Ident, opPos token.Position) (ast.Node, bool) {
+	names := []*ast.Ident{first}
+	for p.curTokenIs(token.IDENT) && p.peekTokenIs(token.ASSIGN) {
+		names = append(names, p.newIdent(p.curToken))
+		p.nextToken() // move to '='
+		p.nextToken() // move to the next name or the value
+		p.eatNewlines()
+	}
+	value := p.parseExpression(LOWEST)
+	if value == nil {
+		p.setTokenError(p.curToken, "invalid assignment statement value")
+		return nil, false
+	}
+	return &ast.MultiAssign{Names: names, OpPos: opPos, Value: value}, true
+}
+
 func (p *Parser) parseReturn() *ast.Return {
 	returnPos := p.curToken.StartPosition
 	if p.peekTokenIs(token.SEMICOLON) ||
@@ -282,6 +351,20 @@ func (p *Parser) parseReturn() *ast.Return {
 	if value == nil {
 		return nil
 	}
+	// Multiple return values: return a, b
+	if p.peekTokenIs(token.COMMA) {
+		items := []ast.Expr{value}
+		for p.peekTokenIs(token.COMMA) {
+			p.nextToken() // move to ','
+			p.nextToken() // move to the next value
+			item := p.parseExpression(LOWEST)
+			if item == nil {
+				return nil
+			}
+			items = append(items, item)
+		}
+		value = &ast.Tuple{Items: items}
+	}
 	return &ast.Return{Return: returnPos, Value: value}
 }
 
@@ -302,17 +385,31 @@ func (p *Parser) parseAssign(name ast.Node) (ast.Node, bool) {
 	op := p.curToken.Literal
 	var ident *ast.Ident
 	var index *ast.Index
+	var slice *ast.Slice
 	switch node := name.(type) {
 	case *ast.Ident:
 		ident = node
 	case *ast.Index:
+		if node.Optional {
+			p.setTokenError(p.curToken, "cannot assign to an optional chain")
+			return nil, false
+		}
 		index = node
+	case *ast.Slice:
+		if op != "=" {
+			p.setTokenError(p.curToken, "compound assignment to a slice is not supported")
+			return nil, false
+		}
+		slice = node
 	default:
 		p.setTokenError(p.curToken, "unexpected token for assignment: %s", name.String())
 		return nil, false
 	}
 	p.nextToken() // move to the RHS value
 	p.eatNewlines()
+	if ident != nil && op == "=" && p.curTokenIs(token.IDENT) && p.peekTokenIs(token.ASSIGN) {
+		return p.parseChainAssign(ident, opPos)
+	}
 	right := p.parseExpression(LOWEST)
 	if right == nil {
 		p.setTokenError(p.curToken, "invalid assignment statement value")
@@ -321,6 +418,9 @@ func (p *Parser) parseAssign(name ast.Node) (ast.Node, bool) {
 	if index != nil {
 		return &ast.Assign{Name: nil, Index: index, OpPos: opPos, Op: op, Value: right}, true
 	}
+	if slice != nil {
+		return &ast.Assign{Slice: slice, OpPos: opPos, Op: op, Value: right}, true
+	}
 	return &ast.Assign{Name: ident, Index: nil, OpPos: opPos, Op: op, Value: right}, true
 }
 
@@ -331,13 +431,22 @@ func (p *Parser) parsePostfix(leftNode ast.Node) ast.Node {
 		p.setTokenError(p.curToken, "invalid operand for postfix operator")
 		return nil
 	}
-	switch expr.(type) {
-	case *ast.Ident, *ast.Index, *ast.GetAttr:
+	optional := false
+	switch expr := expr.(type) {
+	case *ast.Ident:
 		// Valid assignable expressions
+	case *ast.Index:
+		optional = expr.Optional
+	case *ast.GetAttr:
+		optional = expr.Optional
 	default:
 		p.setTokenError(p.curToken, "cannot apply postfix operator to this expression")
 		return nil
 	}
+	if optional {
+		p.setTokenError(p.curToken, "cannot apply postfix operator to an optional chain")
+		return nil
+	}
 	return &ast.Postfix{
 		X:     expr,
 		OpPos: p.curToken.StartPosition,
@@ -429,6 +538,43 @@ func (p *Parser) parseTry() (ast.Node, bool) {
 	}, true
 }
 
+func (p *Parser) parseWith() (ast.Node, bool) {
+	withPos := p.curToken.StartPosition
+
+	p.nextToken() // move to the resource expression
+	value := p.parseExpression(LOWEST)
+	if value == nil {
+		return nil, false
+	}
+
+	// The "as" keyword is lexed as an identifier
+	if !p.peekTokenIs(token.IDENT) || p.peekToken.Literal != "as" {
+		p.setTokenError(p.peekToken, "with statement requires a variable name (use: with value as name { ... })")
+		return nil, false
+	}
+	p.nextToken() // move to "as"
+	if !p.expectPeek("with statement", token.IDENT) {
+		return nil, false
+	}
+	name := p.newIdent(p.curToken)
+
+	// Expect opening brace for with block
+	if !p.expectPeek("with block", token.LBRACE) {
+		return nil, false
+	}
+	body := p.parseBlock()
+	if body == nil {
+		return nil, false
+	}
+
+	return &ast.With{
+		With:  withPos,
+		Value: value,
+		Name:  name,
+		Body:  body,
+	}, true
+}
+
 func (p *Parser) parseThrow() ast.Node {
 	throwPos := p.curToken.StartPosition
 
@@ -449,3 +595,195 @@ func (p *Parser) parseThrow() ast.Node {
 
 	return &ast.Throw{Throw: throwPos, Value: value}
 }
+
+func (p *Parser) parseAssert() ast.Node {
+	assertPos := p.curToken.StartPosition
+
+	if p.peekTokenIs(token.SEMICOLON) ||
+		p.peekTokenIs(token.NEWLINE) ||
+		p.peekTokenIs(token.RBRACE) ||
+		p.peekTokenIs(token.EOF) {
+		p.setTokenError(p.curToken, "assert statement requires a condition")
+		return nil
+	}
+
+	// Support the call-like form assert(cond, message)
+	if p.peekTokenIs(token.LPAREN) {
+		if cond, message, ok := p.parseParenthesizedAssert(); ok {
+			return &ast.Assert{Assert: assertPos, Cond: cond, Message: message}
+		}
+	}
+
+	p.nextToken()
+	cond := p.parseExpression(LOWEST)
+	if cond == nil {
+		return nil
+	}
+	var message ast.Expr
+	if p.peekTokenIs(token.COMMA) {
+		p.nextToken() // move to ','
+		p.nextToken() // move past ','
+		if message = p.parseExpression(LOWEST); message == nil {
+			return nil
+		}
+	}
+	return &ast.Assert{Assert: assertPos, Cond: cond, Message: message}
+}
+
+// parseDel parses a del statement such as "del m["key"]", which removes an
+// item from a container.
+func (p *Parser) parseDel() ast.Node {
+	delToken := p.curToken
+	if p.peekTokenIs(token.SEMICOLON) ||
+		p.peekTokenIs(token.NEWLINE) ||
+		p.peekTokenIs(token.RBRACE) ||
+		p.peekTokenIs(token.EOF) {
+		p.setTokenError(delToken, "del statement requires an item such as x[key]")
+		return nil
+	}
+	p.nextToken()
+	target := p.parseExpression(LOWEST)
+	if target == nil {
+		return nil
+	}
+	index, ok := target.(*ast.Index)
+	if !ok || index.Optional {
+		p.setTokenError(delToken, "del statement requires an item such as x[key] (got %s)", target.String())
+		return nil
+	}
+	return &ast.Del{Del: delToken.StartPosition, Index: index}
+}
+
+// parseParenthesizedAssert tries to parse "(cond, message)" following the
+// assert keyword. A parenthesized condition without a message, as in
+// "assert (a) == b", is left to the regular expression parser, so if the
+// tokens don't form exactly this shape the parser state is restored and ok is
+// false.
+func (p *Parser) parseParenthesizedAssert() (cond, message ast.Expr, ok bool) {
+	savedPrev := p.prevToken
+	savedCur := p.curToken
+	savedPeek := p.peekToken
+	savedLexer := p.l.SaveState()
+	savedErrors := len(p.errors)
+	defer func() {
+		if !ok {
+			p.prevToken = savedPrev
+			p.curToken = savedCur
+			p.peekToken = savedPeek
+			p.l.RestoreState(savedLexer)
+			p.errors = p.errors[:savedErrors]
+		}
+	}()
+
+	p.nextToken() // move to '('
+	p.nextToken() // move past '('
+	p.eatNewlines()
+	if cond = p.parseExpression(LOWEST); cond == nil {
+		return nil, nil, false
+	}
+	if !p.peekTokenIs(token.COMMA) {
+		return nil, nil, false
+	}
+	p.nextToken() // move to ','
+	p.nextToken() // move past ','
+	p.eatNewlines()
+	if message = p.parseExpression(LOWEST); message == nil {
+		return nil, nil, false
+	}
+	if !p.skipNewlinesAndPeek(token.RPAREN) {
+		return nil, nil, false
+	}
+	p.nextToken() // move to ')'
+	if !p.peekTokenIs(token.SEMICOLON) &&
+		!p.peekTokenIs(token.NEWLINE) &&
+		!p.peekTokenIs(token.RBRACE) &&
+		!p.peekTokenIs(token.EOF) {
+		return nil, nil, false
+	}
+	return cond, message, true
+}
+
+func (p *Parser) parseExport() ast.Node {
+	exportPos := p.curToken.StartPosition
+
+	var decl ast.Node
+	switch p.peekToken.Type {
+	case token.LET:
+		p.nextToken()
+		decl = p.parseLet()
+	case token.CONST:
+		p.nextToken()
+		decl = p.parseConst()
+	case token.FUNCTION:
+		p.nextToken()
+		if !p.peekTokenIs(token.IDENT) {
+			p.setTokenError(p.curToken, "exported function must have a name")
+			return nil
+		}
+		decl, _ = p.parseFunc()
+	default:
+		p.setTokenError(p.peekToken, "export must be followed by let, const, or a function declaration")
+		return nil
+	}
+	if decl == nil {
+		return nil
+	}
+	switch decl.(type) {
+	case *ast.Var, *ast.MultiVar, *ast.Const, *ast.Func:
+	default:
+		p.setTokenError(p.curToken, "destructuring declarations cannot be exported")
+		return nil
+	}
+	return &ast.Export{Export: exportPos, Decl: decl.(ast.Stmt)}
+}
+
+func (p *Parser) parseImport() ast.Node {
+	importPos := p.curToken.StartPosition
+
+	if !p.peekTokenIs(token.STRING) {
+		p.setTokenError(p.peekToken, "import must be followed by a module path string")
+		return nil
+	}
+	p.nextToken()
+	path := &ast.String{
+		ValuePos: p.curToken.StartPosition,
+		ValueEnd: p.curToken.EndPosition.Advance(1),
+		Literal:  p.curToken.Literal,
+		Value:    p.curToken.Literal,
+	}
+	if path.Value == "" {
+		p.setTokenError(p.curToken, "import path cannot be empty")
+		return nil
+	}
+	node := &ast.Import{Import: importPos, Path: path}
+
+	// The "as" keyword is lexed as an identifier
+	if p.peekTokenIs(token.IDENT) && p.peekToken.Literal == "as" {
+		p.nextToken()
+		if !p.expectPeek("import alias", token.IDENT) {
+			return nil
+		}
+		node.Alias = p.newIdent(p.curToken)
+	} else if !isImportName(node.Name()) {
+		p.setTokenError(p.curToken,
+			"cannot derive a variable name from import path %q (use: import %q as name)",
+			path.Value, path.Value)
+		return nil
+	}
+	return node
+}
+
+// isImportName reports whether name can be used as the variable name for an
+// import without an alias.
+func isImportName(name string) bool {
+	if name == "" || token.LookupIdentifier(name) != token.IDENT {
+		return false
+	}
+	for i, ch := range name {
+		if ch == '_' || unicode.IsLetter(ch) || (i > 0 && unicode.IsDigit(ch)) {
+			continue
+		}
+		return false
+	}
+	return true
+}
@@ -226,6 +226,19 @@ func TestReturnAST(t *testing.T) {
 	assert.Equal(t, "+", infix.Op)
 }
 
+func TestReturnMultipleValues(t *testing.T) {
+	program, err := Parse(context.Background(), "return x, y + 1, \"z\"", nil)
+	assert.Nil(t, err)
+
+	ret, ok := program.First().(*ast.Return)
+	assert.True(t, ok)
+
+	tuple, ok := ret.Value.(*ast.Tuple)
+	assert.True(t, ok)
+	assert.Len(t, tuple.Items, 3)
+	assert.Equal(t, `return x, (y + 1), "z"`, ret.String())
+}
+
 func TestNakedReturns(t *testing.T) {
 	tests := []struct {
 		input    string
@@ -733,6 +746,74 @@ func TestPostfixErrors(t *testing.T) {
 	}
 }
 
+func TestMultiAssign(t *testing.T) {
+	tests := []struct {
+		input    string
+		expected string
+		unpack   bool
+	}{
+		{"a, b = b, a", "a, b = b, a", true},
+		{"a, b, c = 1, 2, 3", "a, b, c = 1, 2, 3", true},
+		{"a, b = pair", "a, b = pair", true},
+		{"_, b = f()", "_, b = f()", true},
+		{"x = y = 0", "x = y = 0", false},
+		{"x = y =\n  z + 1", "x = y = (z + 1)", false},
+		{"let a, b = 1, 2", "let a, b = 1, 2", false},
+	}
+	for _, tt := range tests {
+		t.Run(tt.input, func(t *testing.T) {
+			program, err := Parse(context.Background(), tt.input, nil)
+			assert.Nil(t, err)
+			assert.Len(t, program.Stmts, 1)
+			assert.Equal(t, tt.expected, program.Stmts[0].String())
+			if assign, ok := program.First().(*ast.MultiAssign); ok {
+				assert.Equal(t, tt.unpack, assign.Unpack)
+			}
+		})
+	}
+}
+
+func TestMultiAssignErrors(t *testing.T) {
+	tests := []struct {
+		input string
+		err   string
+	}{
+		{"a, b", "unexpected end of file while parsing assignment (expected =)"},
+		{"a, b.c = 1, 2", "while parsing assignment (expected =)"},
+		{"a, 1 = 1, 2", "while parsing assignment (expected identifier)"},
+		{"a, b += 1, 2", "while parsing assignment (expected =)"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.input, func(t *testing.T) {
+			_, err := Parse(context.Background(), tt.input, nil)
+			assert.NotNil(t, err)
+			assert.Contains(t, err.Error(), tt.err)
+		})
+	}
+}
+
+func TestIncrementErrors(t *testing.T) {
+	tests := []struct {
+		input string
+		err   string
+	}{
+		{"++x", `prefix "++" is not supported; use "x++" as a statement instead`},
+		{"--count", `prefix "--" is not supported; use "count--" as a statement instead`},
+		{"++arr[0]", `prefix "++" is not supported; use a postfix "++" statement instead`},
+		{"let y = x++", "x++ is a statement and has no value; increment on a separate line"},
+		{"print(obj.n--)", "obj.n-- is a statement and has no value; increment on a separate line"},
+		{"let f = () => x++", "x++ is a statement and has no value; increment on a separate line"},
+		{"return items[0]++", "items[0]++ is a statement and has no value; increment on a separate line"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.input, func(t *testing.T) {
+			_, err := Parse(context.Background(), tt.input, nil)
+			assert.NotNil(t, err)
+			assert.Contains(t, err.Error(), tt.err)
+		})
+	}
+}
+
 func TestTryCatchFinally(t *testing.T) {
 	validInputs := []string{
 		`try { throw "err" } catch e { e }`,
@@ -843,6 +924,35 @@ func TestTryWithEmptyParensCatchErrors(t *testing.T) {
 	assert.NotNil(t, err)
 }
 
+func TestWith(t *testing.T) {
+	program, err := Parse(context.Background(), `with open("a.txt") as f { f.read() }`, nil)
+	assert.Nil(t, err)
+
+	withStmt, ok := program.First().(*ast.With)
+	assert.True(t, ok)
+	assert.Equal(t, withStmt.Value.String(), `open("a.txt")`)
+	assert.Equal(t, withStmt.Name.Name, "f")
+	assert.Len(t, withStmt.Body.Stmts, 1)
+	assert.Equal(t, withStmt.String(), `with open("a.txt") as f { f.read() }`)
+
+	errorCases := []string{
+		`with open("a.txt") { f.read() }`,
+		`with open("a.txt") as { f.read() }`,
+		`with open("a.txt") as f`,
+		`with as f { 1 }`,
+	}
+	for _, input := range errorCases {
+		t.Run(input, func(t *testing.T) {
+			_, err := Parse(context.Background(), input, nil)
+			assert.NotNil(t, err, "expected error for: %s", input)
+		})
+	}
+
+	// "with" remains usable as an attribute name
+	_, err = Parse(context.Background(), `obj.with(1)`, nil)
+	assert.Nil(t, err)
+}
+
 func TestThrow(t *testing.T) {
 	program, err := Parse(context.Background(), `throw "error"`, nil)
 	assert.Nil(t, err)
@@ -873,6 +983,194 @@ func TestThrowError(t *testing.T) {
 	assert.Contains(t, err.Error(), "throw statement requires a value")
 }
 
+func TestAssert(t *testing.T) {
+	tests := []struct {
+		input   string
+		cond    string
+		message string
+	}{
+		{`assert x`, "x", ""},
+		{`assert x == 1`, "(x == 1)", ""},
+		{`assert x == 1, "bad x"`, "(x == 1)", "bad x"},
+		{`assert(x)`, "x", ""},
+		{`assert(x == 1, "bad x")`, "(x == 1)", "bad x"},
+		{`assert (x) == 1`, "(x == 1)", ""},
+		{`assert (x), "bad x"`, "x", "bad x"},
+		{"assert(\n  x == 1,\n  \"bad x\"\n)", "(x == 1)", "bad x"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.input, func(t *testing.T) {
+			program, err := Parse(context.Background(), tt.input, nil)
+			assert.Nil(t, err)
+			assert.Len(t, program.Stmts, 1)
+
+			assertStmt, ok := program.First().(*ast.Assert)
+			assert.True(t, ok)
+			assert.Equal(t, assertStmt.Cond.String(), tt.cond)
+			if tt.message == "" {
+				assert.Nil(t, assertStmt.Message)
+			} else {
+				msg, ok := assertStmt.Message.(*ast.String)
+				assert.True(t, ok)
+				assert.Equal(t, msg.Value, tt.message)
+			}
+		})
+	}
+}
+
+func TestAssertError(t *testing.T) {
+	_, err := Parse(context.Background(), `assert`, nil)
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "assert statement requires a condition")
+
+	_, err = Parse(context.Background(), `assert(x, "a", "b")`, nil)
+	assert.NotNil(t, err)
+}
+
+func TestAssertAttribute(t *testing.T) {
+	program, err := Parse(context.Background(), `t.assert(x, "bad x")`, nil)
+	assert.Nil(t, err)
+
+	call, ok := program.First().(*ast.ObjectCall)
+	assert.True(t, ok)
+	assert.Equal(t, call.Call.Fun.String(), "assert")
+}
+
+func TestDel(t *testing.T) {
+	program, err := Parse(context.Background(), `del m["a"]`, nil)
+	assert.Nil(t, err)
+
+	stmt, ok := program.First().(*ast.Del)
+	assert.True(t, ok)
+	testIdentifier(t, stmt.Index.X, "m")
+	key, ok := stmt.Index.Index.(*ast.String)
+	assert.True(t, ok)
+	assert.Equal(t, key.Value, "a")
+	assert.Equal(t, stmt.String(), `del m["a"]`)
+}
+
+func TestDelErrors(t *testing.T) {
+	tests := []struct {
+		input string
+		err   string
+	}{
+		{`del`, "del statement requires an item such as x[key]"},
+		{`del x`, "del statement requires an item such as x[key] (got x)"},
+		{`del x.y`, "del statement requires an item such as x[key] (got x.y)"},
+		{`del x?.[0]`, "del statement requires an item such as x[key] (got x?.[0])"},
+		{`del x[1:2]`, "del statement requires an item such as x[key] (got x[1:2])"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.input, func(t *testing.T) {
+			_, err := Parse(context.Background(), tt.input, nil)
+			assert.NotNil(t, err)
+			assert.Contains(t, err.Error(), tt.err)
+		})
+	}
+}
+
+func TestSliceAssign(t *testing.T) {
+	program, err := Parse(context.Background(), `x[1:3] = [4, 5]`, nil)
+	assert.Nil(t, err)
+
+	stmt, ok := program.First().(*ast.Assign)
+	assert.True(t, ok)
+	assert.Nil(t, stmt.Name)
+	assert.Nil(t, stmt.Index)
+	assert.Equal(t, stmt.Slice.String(), "x[1:3]")
+	assert.Equal(t, stmt.String(), "x[1:3] = [4, 5]")
+
+	_, err = Parse(context.Background(), `x[1:3] += [4, 5]`, nil)
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "compound assignment to a slice is not supported")
+}
+
+func TestExport(t *testing.T) {
+	tests := []struct {
+		input string
+		names []string
+	}{
+		{`export let x = 1`, []string{"x"}},
+		{`export let x, y = [1, 2]`, []string{"x", "y"}},
+		{`export const LIMIT = 10`, []string{"LIMIT"}},
+		{`export function handle(req) { req }`, []string{"handle"}},
+	}
+	for _, tt := range tests {
+		t.Run(tt.input, func(t *testing.T) {
+			program, err := Parse(context.Background(), tt.input, nil)
+			assert.Nil(t, err)
+			assert.Len(t, program.Stmts, 1)
+
+			export, ok := program.First().(*ast.Export)
+			assert.True(t, ok)
+			assert.Equal(t, export.Names(), tt.names)
+			assert.Equal(t, export.String(), tt.input)
+		})
+	}
+}
+
+func TestExportError(t *testing.T) {
+	tests := []struct {
+		input string
+		err   string
+	}{
+		{`export x`, "export must be followed by let, const, or a function declaration"},
+		{`export function() {}`, "exported function must have a name"},
+		{`export let {a} = m`, "destructuring declarations cannot be exported"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.input, func(t *testing.T) {
+			_, err := Parse(context.Background(), tt.input, nil)
+			assert.NotNil(t, err)
+			assert.Contains(t, err.Error(), tt.err)
+		})
+	}
+}
+
+func TestImport(t *testing.T) {
+	tests := []struct {
+		input string
+		name  string
+	}{
+		{`import "strings_ext"`, "strings_ext"},
+		{`import "./lib/helpers"`, "helpers"},
+		{`import "../shared/util.risor"`, "util"},
+		{`import "./lib/my-helpers" as helpers`, "helpers"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.input, func(t *testing.T) {
+			program, err := Parse(context.Background(), tt.input, nil)
+			assert.Nil(t, err)
+			assert.Len(t, program.Stmts, 1)
+
+			node, ok := program.First().(*ast.Import)
+			assert.True(t, ok)
+			assert.Equal(t, node.Name(), tt.name)
+			assert.Equal(t, node.String(), tt.input)
+		})
+	}
+}
+
+func TestImportError(t *testing.T) {
+	tests := []struct {
+		input string
+		err   string
+	}{
+		{`import helpers`, "import must be followed by a module path string"},
+		{`import ""`, "import path cannot be empty"},
+		{`import "./lib/my-helpers"`, `cannot derive a variable name from import path "./lib/my-helpers"`},
+		{`import "./lib/if"`, `cannot derive a variable name from import path "./lib/if"`},
+		{`import "./lib/helpers" as`, "import alias"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.input, func(t *testing.T) {
+			_, err := Parse(context.Background(), tt.input, nil)
+			assert.NotNil(t, err)
+			assert.Contains(t, err.Error(), tt.err)
+		})
+	}
+}
+
 func TestContinueBreak(t *testing.T) {
 	tests := []struct {
 		input   string
@@ -0,0 +1,41 @@
+// Package stdlib contains standard library modules written in Risor.
+//
+// The module sources are embedded in the Go binary and are compiled only
+// when a script first imports them, so the library can grow without adding
+// to the startup cost of scripts that don't use it. The modules rely on the
+// default builtins, so the importer that loads them should be configured
+// with risor.Builtins() in its environment.
+//
+//	import "itertools"
+//	itertools.window([1, 2, 3, 4], 2) // [[1, 2], [2, 3], [3, 4]]
+package stdlib
+
+import (
+	"embed"
+	"io/fs"
+	"slices"
+	"strings"
+)
+
+//go:embed *.risor
+var files embed.FS
+
+// FS returns the file system holding the module sources. Each module is a
+// file named after the module, with the ".risor" extension, at the root.
+func FS() fs.FS {
+	return files
+}
+
+// Names returns the sorted names of the standard library modules.
+func Names() []string {
+	entries, err := files.ReadDir(".")
+	if err != nil {
+		return nil
+	}
+	var names []string
+	for _, entry := range entries {
+		names = append(names, strings.TrimSuffix(entry.Name(), ".risor"))
+	}
+	slices.Sort(names)
+	return names
+}
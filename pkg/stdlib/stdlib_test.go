@@ -0,0 +1,92 @@
+package stdlib_test
+
+import (
+	"context"
+	"testing"
+
+	"github.com/deepnoodle-ai/risor/v2"
+	"github.com/deepnoodle-ai/risor/v2/pkg/stdlib"
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+func eval(t *testing.T, source string) any {
+	t.Helper()
+	env := risor.Builtins()
+	imp := risor.NewImporter(risor.StdlibLoader(), risor.WithEnv(env))
+	result, err := risor.Eval(context.Background(), source,
+		risor.WithEnv(env), risor.WithImporter(imp))
+	assert.Nil(t, err)
+	return result
+}
+
+func TestNames(t *testing.T) {
+	assert.Equal(t, stdlib.Names(), []string{"functools", "itertools", "strings_extra"})
+}
+
+func TestItertools(t *testing.T) {
+	tests := []struct {
+		source   string
+		expected any
+	}{
+		{`itertools.enumerate(["a", "b"])`, []any{[]any{int64(0), "a"}, []any{int64(1), "b"}}},
+		{`itertools.zip([1, 2, 3], "ab")`, []any{[]any{int64(1), "a"}, []any{int64(2), "b"}}},
+		{`itertools.flatten([[1], [], [2, 3]])`, []any{int64(1), int64(2), int64(3)}},
+		{`itertools.take(range(5), 2)`, []any{int64(0), int64(1)}},
+		{`itertools.take([1], 5)`, []any{int64(1)}},
+		{`itertools.drop([1, 2, 3], 2)`, []any{int64(3)}},
+		{`itertools.drop([1, 2, 3], 5)`, []any{}},
+		{`itertools.window([1, 2, 3], 2)`, []any{[]any{int64(1), int64(2)}, []any{int64(2), int64(3)}}},
+		{`itertools.window([1], 2)`, []any{}},
+		{`itertools.group_by([1, 2, 3, 4], x => x % 2 == 0)`, map[string]any{
+			"false": []any{int64(1), int64(3)},
+			"true":  []any{int64(2), int64(4)},
+		}},
+		{`itertools.partition([1, 2, 3], x => x > 1)`, []any{[]any{int64(2), int64(3)}, []any{int64(1)}}},
+		{`itertools.unique([1, 2, 1, 3, 2])`, []any{int64(1), int64(2), int64(3)}},
+	}
+	for _, tt := range tests {
+		t.Run(tt.source, func(t *testing.T) {
+			assert.Equal(t, eval(t, `import "itertools"; `+tt.source), tt.expected)
+		})
+	}
+}
+
+func TestFunctools(t *testing.T) {
+	tests := []struct {
+		source   string
+		expected any
+	}{
+		{`functools.identity(5)`, int64(5)},
+		{`functools.compose(x => x + 1, x => x * 2)(5)`, int64(11)},
+		{`functools.pipe(x => x + 1, x => x * 2)(5)`, int64(12)},
+		{`functools.partial((a, b, c) => [a, b, c], 1, 2)(3)`, []any{int64(1), int64(2), int64(3)}},
+		{`functools.flip((a, b) => a - b)(1, 10)`, int64(9)},
+		{`let n = 0; let f = functools.once(function() { n++; return n }); [f(), f(), n]`, []any{int64(1), int64(1), int64(1)}},
+	}
+	for _, tt := range tests {
+		t.Run(tt.source, func(t *testing.T) {
+			assert.Equal(t, eval(t, `import "functools"; `+tt.source), tt.expected)
+		})
+	}
+}
+
+func TestStringsExtra(t *testing.T) {
+	tests := []struct {
+		source   string
+		expected any
+	}{
+		{`strings_extra.pad_left("7", 3, "0")`, "007"},
+		{`strings_extra.pad_right("ab", 4)`, "ab  "},
+		{`strings_extra.pad_left("long", 2)`, "long"},
+		{`strings_extra.center("ab", 5, "*")`, "*ab**"},
+		{`strings_extra.truncate("hello world", 8)`, "hello..."},
+		{`strings_extra.truncate("hi", 8)`, "hi"},
+		{`strings_extra.capitalize("risor")`, "Risor"},
+		{`strings_extra.words(" a  b ")`, []any{"a", "b"}},
+	}
+	for _, tt := range tests {
+		t.Run(tt.source, func(t *testing.T) {
+			assert.Equal(t, eval(t, `import "strings_extra"; `+tt.source), tt.expected)
+		})
+	}
+}
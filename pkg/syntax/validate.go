@@ -27,7 +27,7 @@ func (v *SyntaxValidator) Validate(program *ast.Program) []ValidationError {
 
 func (v *SyntaxValidator) checkNode(node ast.Node) *ValidationError {
 	switch n := node.(type) {
-	case *ast.Var, *ast.Const, *ast.MultiVar:
+	case *ast.Var, *ast.Const, *ast.MultiVar, *ast.With:
 		if v.config.DisallowVariableDecl {
 			return &ValidationError{
 				Message:  "variable declarations are not allowed",
@@ -63,7 +63,7 @@ func (v *SyntaxValidator) checkNode(node ast.Node) *ValidationError {
 			}
 		}
 
-	case *ast.Assign, *ast.SetAttr, *ast.Postfix:
+	case *ast.Assign, *ast.MultiAssign, *ast.SetAttr, *ast.Postfix, *ast.Del:
 		if v.config.DisallowAssignment {
 			return &ValidationError{
 				Message:  "assignment is not allowed",
@@ -0,0 +1,116 @@
+package vm
+
+import (
+	"context"
+	"fmt"
+	"strings"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+	"github.com/deepnoodle-ai/risor/v2/pkg/op"
+)
+
+// maxAssertDiffs limits how many differing map keys are listed in a failed
+// assertion message.
+const maxAssertDiffs = 3
+
+// assertionMessage builds the message for a failed assert statement. The
+// source is the text of the condition. When the condition was a comparison,
+// cmp is its operator and left and right are the operand values.
+func assertionMessage(ctx context.Context, source string, message object.Object, cmp op.CompareOpType, left, right object.Object) (string, error) {
+	var sb strings.Builder
+	if message != object.Nil {
+		text, err := templateString(ctx, message)
+		if err != nil {
+			return "", err
+		}
+		sb.WriteString(text)
+		sb.WriteString(": ")
+	}
+	sb.WriteString(source)
+	if cmp != 0 {
+		fmt.Fprintf(&sb, " (left: %s, right: %s", left.Inspect(), right.Inspect())
+		if cmp == op.Equal {
+			if diff := describeDiff(left, right); diff != "" {
+				sb.WriteString("; ")
+				sb.WriteString(diff)
+			}
+		}
+		sb.WriteString(")")
+	}
+	return sb.String(), nil
+}
+
+// describeDiff explains where two unequal strings, lists, or maps differ.
+// It returns an empty string for other types.
+func describeDiff(left, right object.Object) string {
+	switch a := left.(type) {
+	case *object.String:
+		b, ok := right.(*object.String)
+		if !ok {
+			return ""
+		}
+		return describeStringDiff([]rune(a.Value()), []rune(b.Value()))
+	case *object.List:
+		b, ok := right.(*object.List)
+		if !ok {
+			return ""
+		}
+		return describeListDiff(a.Value(), b.Value())
+	case *object.Map:
+		b, ok := right.(*object.Map)
+		if !ok {
+			return ""
+		}
+		return describeMapDiff(a, b)
+	}
+	return ""
+}
+
+func describeStringDiff(a, b []rune) string {
+	n := min(len(a), len(b))
+	for i := 0; i < n; i++ {
+		if a[i] != b[i] {
+			return fmt.Sprintf("first difference at index %d", i)
+		}
+	}
+	if len(a) != len(b) {
+		return fmt.Sprintf("first difference at index %d (lengths %d and %d)", n, len(a), len(b))
+	}
+	return ""
+}
+
+func describeListDiff(a, b []object.Object) string {
+	n := min(len(a), len(b))
+	for i := 0; i < n; i++ {
+		if !a[i].Equals(b[i]) {
+			return fmt.Sprintf("first difference at index %d: %s != %s",
+				i, a[i].Inspect(), b[i].Inspect())
+		}
+	}
+	if len(a) != len(b) {
+		return fmt.Sprintf("lengths differ: %d != %d", len(a), len(b))
+	}
+	return ""
+}
+
+func describeMapDiff(a, b *object.Map) string {
+	var diffs []string
+	for _, key := range a.SortedKeys() {
+		av := a.Get(key)
+		if bv := b.GetWithDefault(key, nil); bv == nil {
+			diffs = append(diffs, fmt.Sprintf("key %q missing on right", key))
+		} else if !av.Equals(bv) {
+			diffs = append(diffs, fmt.Sprintf("key %q: %s != %s", key, av.Inspect(), bv.Inspect()))
+		}
+	}
+	for _, key := range b.SortedKeys() {
+		if a.GetWithDefault(key, nil) == nil {
+			diffs = append(diffs, fmt.Sprintf("key %q missing on left", key))
+		}
+	}
+	if len(diffs) > maxAssertDiffs {
+		more := len(diffs) - maxAssertDiffs
+		diffs = append(diffs[:maxAssertDiffs], fmt.Sprintf("and %d more", more))
+	}
+	return strings.Join(diffs, "; ")
+}
@@ -0,0 +1,345 @@
+package vm
+
+import (
+	"context"
+	"testing"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+// TestClosureSharedState verifies that a captured variable is a single
+// binding: writes made by the enclosing function and by any closure that
+// captured it are visible everywhere the variable is read.
+func TestClosureSharedState(t *testing.T) {
+	tests := []struct {
+		name     string
+		input    string
+		expected object.Object
+	}{
+		{
+			name: "outer writes after closure is created",
+			input: `
+			function f() {
+				let x = 1
+				let get = function() { x }
+				x = 2
+				return get()
+			}
+			f()
+			`,
+			expected: object.NewInt(2),
+		},
+		{
+			name: "closure writes are visible to outer",
+			input: `
+			function f() {
+				let x = 1
+				let set = function(v) { x = v }
+				set(5)
+				return x
+			}
+			f()
+			`,
+			expected: object.NewInt(5),
+		},
+		{
+			name: "two closures share one variable",
+			input: `
+			function counter() {
+				let n = 0
+				let inc = function() { n++ }
+				let get = function() { n }
+				return [inc, get]
+			}
+			let fns = counter()
+			fns[0]()
+			fns[0]()
+			fns[0]()
+			fns[1]()
+			`,
+			expected: object.NewInt(3),
+		},
+		{
+			name: "each call gets its own binding",
+			input: `
+			function counter() {
+				let n = 0
+				return function() { n++; n }
+			}
+			let a = counter()
+			let b = counter()
+			a()
+			a()
+			b()
+			let results = [a(), b()]
+			results
+			`,
+			expected: object.NewList([]object.Object{object.NewInt(3), object.NewInt(2)}),
+		},
+		{
+			name: "closure captures a parameter",
+			input: `
+			function adder(total) {
+				return function(v) { total += v; total }
+			}
+			let add = adder(10)
+			add(1)
+			add(2)
+			`,
+			expected: object.NewInt(13),
+		},
+		{
+			name: "closures created in a loop callback",
+			input: `
+			function f() {
+				let total = 0
+				let fns = []
+				list(range(4)).each(function(i) {
+					fns.append(function() { total += i })
+				})
+				fns.each(function(fn) { fn() })
+				return total
+			}
+			f()
+			`,
+			expected: object.NewInt(6),
+		},
+		{
+			name: "each loop callback has its own locals",
+			input: `
+			let fns = []
+			list(range(3)).each(function(i) {
+				let doubled = i * 2
+				fns.append(function() { doubled })
+			})
+			fns.map(function(fn) { fn() })
+			`,
+			expected: object.NewList([]object.Object{
+				object.NewInt(0), object.NewInt(2), object.NewInt(4),
+			}),
+		},
+		{
+			name: "outer variable updated by loop callback",
+			input: `
+			function f() {
+				let sum = 0
+				list(range(5)).each(function(i) { sum += i })
+				return sum
+			}
+			f()
+			`,
+			expected: object.NewInt(10),
+		},
+		{
+			name: "function with many locals",
+			input: `
+			function f() {
+				let a = 1; let b = 2; let c = 3; let d = 4; let e = 5
+				let g = 6; let h = 7; let i = 8; let j = 9; let k = 10
+				let get = function() { k }
+				k = 100
+				let set = function() { k = 1000 }
+				set()
+				return [get(), k]
+			}
+			f()
+			`,
+			expected: object.NewList([]object.Object{object.NewInt(1000), object.NewInt(1000)}),
+		},
+		{
+			name: "recursive calls each capture their own frame",
+			input: `
+			function collect(n, acc) {
+				let v = n * 10
+				acc.append(function() { v })
+				if (n > 0) {
+					collect(n - 1, acc)
+				}
+				v = v + 1
+				return acc
+			}
+			collect(2, []).map(function(fn) { fn() })
+			`,
+			expected: object.NewList([]object.Object{
+				object.NewInt(21), object.NewInt(11), object.NewInt(1),
+			}),
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			result, err := run(context.Background(), tt.input)
+			assert.Nil(t, err, "unexpected error: %v", err)
+			assert.Equal(t, result, tt.expected)
+		})
+	}
+}
+
+// TestNestedClosures verifies captures that pass through one or more
+// intermediate functions. The intermediate functions are frequently called
+// after the function that owns the variable has returned, so the capture
+// must not depend on which frames are on the call stack at that time.
+func TestNestedClosures(t *testing.T) {
+	tests := []struct {
+		name     string
+		input    string
+		expected object.Object
+	}{
+		{
+			name: "two levels called immediately",
+			input: `
+			function outer() {
+				let x = 1
+				function middle() {
+					return function() { x }
+				}
+				return middle()()
+			}
+			outer()
+			`,
+			expected: object.NewInt(1),
+		},
+		{
+			name: "middle called after outer returns",
+			input: `
+			function outer() {
+				let x = "outer"
+				return function() {
+					return function() { x }
+				}
+			}
+			let middle = outer()
+			let inner = middle()
+			inner()
+			`,
+			expected: object.NewString("outer"),
+		},
+		{
+			name: "middle called from a deeper call stack",
+			input: `
+			function outer() {
+				let x = 42
+				return function() {
+					return function() { x }
+				}
+			}
+			let middle = outer()
+			function wrap1() { return wrap2() }
+			function wrap2() { return middle() }
+			wrap1()()
+			`,
+			expected: object.NewInt(42),
+		},
+		{
+			name: "innermost write visible to outer",
+			input: `
+			function outer() {
+				let x = 0
+				let middle = function() {
+					return function() { x = x + 1 }
+				}
+				let bump = middle()
+				bump()
+				bump()
+				return x
+			}
+			outer()
+			`,
+			expected: object.NewInt(2),
+		},
+		{
+			name: "innermost closures from separate middle calls share state",
+			input: `
+			function outer() {
+				let n = 0
+				return function() {
+					return function() { n++; n }
+				}
+			}
+			let middle = outer()
+			let a = middle()
+			let b = middle()
+			a()
+			b()
+			a()
+			`,
+			expected: object.NewInt(3),
+		},
+		{
+			name: "three levels deep",
+			input: `
+			function l1() {
+				let x = 1
+				return function() {
+					return function() {
+						return function() { x = x * 10; x }
+					}
+				}
+			}
+			let l2 = l1()
+			let l3 = l2()
+			let l4 = l3()
+			l4()
+			l2()()()
+			`,
+			expected: object.NewInt(100),
+		},
+		{
+			name: "captures from several enclosing levels",
+			input: `
+			function l1() {
+				let a = "a"
+				return function() {
+					let b = "b"
+					return function() {
+						let c = "c"
+						return function() { a + b + c }
+					}
+				}
+			}
+			l1()()()()
+			`,
+			expected: object.NewString("abc"),
+		},
+		{
+			name: "middle also uses the captured variable",
+			input: `
+			function outer() {
+				let x = 1
+				return function() {
+					x = x + 1
+					let inner = function() { x = x * 3 }
+					inner()
+					return x
+				}
+			}
+			let middle = outer()
+			middle()
+			middle()
+			`,
+			expected: object.NewInt(21),
+		},
+		{
+			name: "nested arrow functions",
+			input: `
+			let make = (start) => () => () => {
+				start += 1
+				return start
+			}
+			let m = make(5)
+			let f = m()
+			let g = m()
+			f()
+			g()
+			`,
+			expected: object.NewInt(7),
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			result, err := run(context.Background(), tt.input)
+			assert.Nil(t, err, "unexpected error: %v", err)
+			assert.Equal(t, result, tt.expected)
+		})
+	}
+}
@@ -9,23 +9,32 @@ import (
 )
 
 // loadedCode wraps bytecode.Code with VM-specific runtime data.
-// It caches converted constants and stores the mutable globals array.
+// It caches converted constants and holds a copy of the instructions, with
+// global variable indices linked to the VM's global slots.
 type loadedCode struct {
 	*bytecode.Code
 	Instructions      []op.Code
 	Constants         []object.Object
-	Globals           []object.Object
 	Names             []string
 	Locations         []object.SourceLocation
 	ExceptionHandlers []bytecode.ExceptionHandler
 
 	// Optimization metadata from compiler
 	MaxCallArgs int // Maximum argument count from any Call opcode
+
+	// The globals this code is linked against
+	env *globalEnv
+
+	// Whether env belongs to another VM, so access to it must be locked
+	shared bool
 }
 
-func wrapCode(bc *bytecode.Code) *loadedCode {
-	// Note that this does NOT set the Globals field.
+// wrapCode prepares code to run against the globals in env. Functions defined
+// by the code are bound to env. Note that this does NOT link the global
+// variable indices; see globalEnv.link.
+func wrapCode(bc *bytecode.Code, env *globalEnv) *loadedCode {
 	c := &loadedCode{
+		env:          env,
 		Code:         bc,
 		Instructions: make([]op.Code, bc.InstructionCount()),
 		Constants:    make([]object.Object, bc.ConstantCount()),
@@ -78,7 +87,9 @@ func wrapCode(bc *bytecode.Code) *loadedCode {
 		case bool:
 			c.Constants[i] = object.NewBool(constant)
 		case *bytecode.Function:
-			c.Constants[i] = object.NewClosure(constant)
+			fn := object.NewClosure(constant)
+			fn.BindEnv(env)
+			c.Constants[i] = fn
 		case nil:
 			c.Constants[i] = object.Nil
 		default:
@@ -88,10 +99,6 @@ func wrapCode(bc *bytecode.Code) *loadedCode {
 	return c
 }
 
-func (c *loadedCode) GlobalsCount() int {
-	return len(c.Globals)
-}
-
 // LocalsCount returns the number of local variables in this code.
 func (c *loadedCode) LocalsCount() int {
 	return c.Code.LocalCount()
@@ -109,22 +116,3 @@ func (c *loadedCode) LocationAt(ip int) object.SourceLocation {
 	}
 	return c.Locations[ip]
 }
-
-func loadChildCode(root *loadedCode, bc *bytecode.Code) *loadedCode {
-	c := wrapCode(bc)
-	c.Globals = root.Globals
-	return c
-}
-
-func loadRootCode(bc *bytecode.Code, globals map[string]object.Object) *loadedCode {
-	c := wrapCode(bc)
-	globalCount := bc.GlobalCount()
-	c.Globals = make([]object.Object, globalCount)
-	for i := 0; i < globalCount; i++ {
-		name := bc.GlobalNameAt(i)
-		if value, found := globals[name]; found {
-			c.Globals[i] = value
-		}
-	}
-	return c
-}
@@ -0,0 +1,92 @@
+package vm
+
+import (
+	"context"
+	"maps"
+	"slices"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/builtins"
+	"github.com/deepnoodle-ai/risor/v2/pkg/compiler"
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+	"github.com/deepnoodle-ai/risor/v2/pkg/parser"
+)
+
+// MaxEvalDepth limits how deeply calls to eval() can nest.
+const MaxEvalDepth = 16
+
+// WithEvalOptions sets options for the child VMs that run code passed to the
+// eval() and run() builtins. They are applied after the limits the child
+// inherits from this VM, so they can tighten them, as with WithMaxSteps or
+// WithTimeout.
+func WithEvalOptions(options ...Option) Option {
+	return func(vm *VirtualMachine) {
+		vm.evalOptions = append(vm.evalOptions, options...)
+	}
+}
+
+// evalSource implements object.EvalFunc for eval() and run(). The source
+// runs in a child VM whose globals are the builtins and the given globals, so
+// it can't see or change this VM's globals, and imports are disabled. The
+// child inherits this VM's stack limits and remaining step budget.
+//
+// Errors raised by the code are returned as error values. If the context is
+// cancelled or the step budget runs out, the error is returned as is, so
+// that this VM stops too.
+func (vm *VirtualMachine) evalSource(ctx context.Context, source string, globals map[string]object.Object) (object.Object, error) {
+	if vm.evalDepth >= MaxEvalDepth {
+		return object.Errorf("eval: maximum nesting depth of %d exceeded", MaxEvalDepth), nil
+	}
+	env := map[string]any{}
+	for name, fn := range builtins.Builtins() {
+		env[name] = fn
+	}
+	for name, value := range globals {
+		env[name] = value
+	}
+	program, err := parser.Parse(ctx, source, nil)
+	if err != nil {
+		return object.NewError(err), nil
+	}
+	code, err := compiler.Compile(program, &compiler.Config{
+		GlobalNames: slices.Sorted(maps.Keys(env)),
+		Source:      source,
+	})
+	if err != nil {
+		return object.NewError(err), nil
+	}
+
+	options := []Option{
+		WithGlobals(env),
+		WithContextCheckInterval(vm.contextCheckInterval),
+		WithMaxValueStackDepth(vm.maxValueStackDepth),
+		WithMaxFrameDepth(vm.maxFrameDepth),
+	}
+	if vm.typeRegistry != nil {
+		options = append(options, WithTypeRegistry(vm.typeRegistry))
+	}
+	if vm.logHandler != nil {
+		options = append(options, WithLogHandler(vm.logHandler))
+	}
+	if vm.maxSteps > 0 {
+		options = append(options, WithMaxSteps(max(vm.maxSteps-vm.stepCount, 1)))
+	}
+	child, err := New(code, append(options, vm.evalOptions...)...)
+	if err != nil {
+		return nil, err
+	}
+	child.evalOptions = vm.evalOptions
+	child.evalDepth = vm.evalDepth + 1
+
+	err = child.Run(ctx)
+	vm.stepCount += child.stepCount
+	if err != nil {
+		if ctx.Err() != nil || (vm.maxSteps > 0 && vm.stepCount > vm.maxSteps) {
+			return nil, err
+		}
+		return object.NewError(err), nil
+	}
+	if result, exists := child.TOS(); exists {
+		return result, nil
+	}
+	return object.Nil, nil
+}
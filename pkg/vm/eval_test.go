@@ -0,0 +1,65 @@
+package vm
+
+import (
+	"context"
+	"errors"
+	"testing"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+func TestEval(t *testing.T) {
+	ctx := context.Background()
+	result, err := run(ctx, `
+	let secret = "s3cret"
+	let vars = {price: 2, qty: 3}
+	[
+		eval("price * qty + len([1])", vars),
+		type(eval("secret")),
+		eval("throw error('bad rule')").message().contains("bad rule"),
+		eval("price = 10; price", vars),
+		vars.price,
+		eval("eval('1 + 1')"),
+	]
+	`)
+	assert.Nil(t, err)
+	assert.Equal(t, result, object.NewList([]object.Object{
+		object.NewInt(7),
+		object.NewString("error"),
+		object.True,
+		object.NewInt(10),
+		object.NewInt(2),
+		object.NewInt(2),
+	}))
+}
+
+func TestEvalLimits(t *testing.T) {
+	ctx := context.Background()
+
+	// The child's own limits produce error values
+	machine, err := newVM(ctx, `
+	let r = eval("function f(n) { return f(n + 1) }; f(0)")
+	type(r)
+	`)
+	assert.Nil(t, err)
+	assert.Nil(t, machine.Run(ctx))
+	result, _ := machine.TOS()
+	assert.Equal(t, result, object.NewString("error"))
+
+	// Options for eval apply to the child only
+	source := `eval("list(range(100000)).each(x => x)")`
+	machine, err = newVM(ctx, source)
+	assert.Nil(t, err)
+	assert.Nil(t, machine.applyOptions([]Option{WithEvalOptions(WithMaxSteps(1000))}))
+	assert.Nil(t, machine.Run(ctx))
+	result, _ = machine.TOS()
+	assert.True(t, object.IsError(result))
+
+	// The step budget is shared with the parent
+	machine, err = newVM(ctx, source)
+	assert.Nil(t, err)
+	assert.Nil(t, machine.applyOptions([]Option{WithMaxSteps(10000)}))
+	err = machine.Run(ctx)
+	assert.True(t, errors.Is(err, ErrStepLimitExceeded))
+}
@@ -0,0 +1,115 @@
+package vm
+
+import (
+	"context"
+	"fmt"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+)
+
+// event is a host-emitted event waiting to be dispatched to script handlers.
+type event struct {
+	name string
+	data object.Object
+}
+
+// EventError is returned by DispatchEvents when a handler fails. It carries
+// the event the handler was called with, so the host can log or retry it.
+// Handlers registered after the failing one have not seen the event.
+type EventError struct {
+	Name string
+	Data object.Object
+	Err  error
+}
+
+func (e *EventError) Error() string {
+	return fmt.Sprintf("event %q: %s", e.Name, e.Err)
+}
+
+func (e *EventError) Unwrap() error {
+	return e.Err
+}
+
+// registerEventHandler subscribes a handler to the named event. Scripts reach
+// this through the on() builtin.
+func (vm *VirtualMachine) registerEventHandler(name string, handler object.Callable) {
+	vm.eventMu.Lock()
+	defer vm.eventMu.Unlock()
+	if vm.eventHandlers == nil {
+		vm.eventHandlers = map[string][]object.Callable{}
+	}
+	vm.eventHandlers[name] = append(vm.eventHandlers[name], handler)
+}
+
+// Emit queues an event for delivery to the handlers a script registered with
+// on(). The data is converted to a Risor object using the VM's type registry
+// and is passed to each handler as its only argument; nil data is passed as
+// nil. Emit is safe to call from any goroutine, including while the VM is
+// running. Queued events are delivered by DispatchEvents.
+func (vm *VirtualMachine) Emit(name string, data any) error {
+	obj, err := vm.TypeRegistry().FromGo(data)
+	if err != nil {
+		return err
+	}
+	vm.eventMu.Lock()
+	defer vm.eventMu.Unlock()
+	vm.eventQueue = append(vm.eventQueue, event{name: name, data: obj})
+	return nil
+}
+
+// PendingEvents returns the number of events waiting to be dispatched.
+func (vm *VirtualMachine) PendingEvents() int {
+	vm.eventMu.Lock()
+	defer vm.eventMu.Unlock()
+	return len(vm.eventQueue)
+}
+
+// DispatchEvents delivers queued events to their handlers, in the order the
+// events were emitted. Each event is passed to its handlers in the order they
+// were registered. Events emitted while dispatching, for example by a host
+// function a handler calls, are delivered before DispatchEvents returns.
+// Events with no registered handlers are dropped.
+//
+// Dispatch stops at the first handler that returns an error. The error is
+// returned as an *EventError holding the event being delivered, and any
+// events still queued remain queued. If the VM is already running, an error
+// is returned.
+func (vm *VirtualMachine) DispatchEvents(ctx context.Context) (err error) {
+	if err := vm.start(ctx); err != nil {
+		return err
+	}
+	defer func() {
+		if r := recover(); r != nil {
+			err = vm.panicToError(r)
+		}
+		vm.stop()
+	}()
+	ctx = vm.initContext(ctx)
+	for {
+		ev, handlers, ok := vm.nextEvent()
+		if !ok {
+			return nil
+		}
+		for _, handler := range handlers {
+			if _, err := handler.Call(ctx, ev.data); err != nil {
+				return &EventError{Name: ev.name, Data: ev.data, Err: err}
+			}
+		}
+	}
+}
+
+// nextEvent removes the oldest queued event and returns it together with a
+// snapshot of its handlers. The snapshot means handlers registered while the
+// event is being delivered only see later events.
+func (vm *VirtualMachine) nextEvent() (event, []object.Callable, bool) {
+	vm.eventMu.Lock()
+	defer vm.eventMu.Unlock()
+	if len(vm.eventQueue) == 0 {
+		return event{}, nil, false
+	}
+	ev := vm.eventQueue[0]
+	vm.eventQueue[0] = event{}
+	vm.eventQueue = vm.eventQueue[1:]
+	handlers := vm.eventHandlers[ev.name]
+	return ev, handlers[:len(handlers):len(handlers)], true
+}
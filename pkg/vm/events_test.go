@@ -0,0 +1,85 @@
+package vm
+
+import (
+	"context"
+	"errors"
+	"testing"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+func TestDispatchEvents(t *testing.T) {
+	ctx := context.Background()
+	source := `
+	let seen = []
+	on("order", o => seen.append("first " + o.id))
+	on("order", o => seen.append("second " + o.id))
+	on("refund", amount => seen.append(string(amount)))
+	`
+	vm, err := newVM(ctx, source)
+	assert.Nil(t, err)
+	assert.Nil(t, vm.Run(ctx))
+
+	assert.Nil(t, vm.Emit("order", map[string]any{"id": "a"}))
+	assert.Nil(t, vm.Emit("refund", 5))
+	assert.Nil(t, vm.Emit("unhandled", nil))
+	assert.Nil(t, vm.Emit("order", map[string]any{"id": "b"}))
+	assert.Equal(t, vm.PendingEvents(), 4)
+
+	assert.Nil(t, vm.DispatchEvents(ctx))
+	assert.Equal(t, vm.PendingEvents(), 0)
+
+	seen, err := vm.Get("seen")
+	assert.Nil(t, err)
+	assert.Equal(t, seen, object.NewList([]object.Object{
+		object.NewString("first a"),
+		object.NewString("second a"),
+		object.NewString("5"),
+		object.NewString("first b"),
+		object.NewString("second b"),
+	}))
+}
+
+func TestDispatchEventsError(t *testing.T) {
+	ctx := context.Background()
+	source := `
+	let count = 0
+	on("tick", n => {
+		if (n == 2) { throw error("bad tick") }
+		count += 1
+	})
+	`
+	vm, err := newVM(ctx, source)
+	assert.Nil(t, err)
+	assert.Nil(t, vm.Run(ctx))
+
+	for i := 1; i <= 3; i++ {
+		assert.Nil(t, vm.Emit("tick", i))
+	}
+	err = vm.DispatchEvents(ctx)
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "bad tick")
+	// The failing event is returned with the error
+	var eventErr *EventError
+	assert.True(t, errors.As(err, &eventErr))
+	assert.Equal(t, eventErr.Name, "tick")
+	assert.Equal(t, eventErr.Data, object.NewInt(2))
+	// The remaining event stays queued.
+	assert.Equal(t, vm.PendingEvents(), 1)
+
+	count, err := vm.Get("count")
+	assert.Nil(t, err)
+	assert.Equal(t, count, object.NewInt(1))
+
+	assert.Nil(t, vm.DispatchEvents(ctx))
+	count, err = vm.Get("count")
+	assert.Nil(t, err)
+	assert.Equal(t, count, object.NewInt(2))
+}
+
+func TestOnRequiresCallable(t *testing.T) {
+	_, err := run(context.Background(), `on("tick", 1)`)
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), `on() argument "fn" must be a callable (int given)`)
+}
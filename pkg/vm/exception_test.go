@@ -414,6 +414,102 @@ func TestFinallyBehavior(t *testing.T) {
 	}
 }
 
+func TestWithStatement(t *testing.T) {
+	const open = `
+	let log = []
+	function open(name) {
+		return {name: name, close: () => { log.append("close " + name) }}
+	}
+	`
+	str := func(items ...string) object.Object {
+		list := make([]object.Object, len(items))
+		for i, item := range items {
+			list[i] = object.NewString(item)
+		}
+		return object.NewList(list)
+	}
+	tests := []struct {
+		name     string
+		input    string
+		expected object.Object
+	}{
+		{
+			name: "closes on normal completion",
+			input: open + `
+			with open("a") as f {
+				log.append("use " + f.name)
+			}
+			log
+			`,
+			expected: str("use a", "close a"),
+		},
+		{
+			name: "evaluates to the block value",
+			input: open + `
+			let name = with open("a") as f { f.name + "!" }
+			log.append(name)
+			log
+			`,
+			expected: str("close a", "a!"),
+		},
+		{
+			name: "closes when the block throws",
+			input: open + `
+			try {
+				with open("a") as f {
+					throw "boom"
+				}
+			} catch (e) {
+				log.append("caught " + string(e))
+			}
+			log
+			`,
+			expected: str("close a", "caught boom"),
+		},
+		{
+			name: "closes when the block returns",
+			input: open + `
+			function read() {
+				with open("a") as f {
+					return f.name
+				}
+			}
+			log.append(read())
+			log
+			`,
+			expected: str("close a", "a"),
+		},
+		{
+			name: "nested resources close in reverse order",
+			input: open + `
+			with open("a") as a {
+				with open("b") as b {
+					log.append(a.name + b.name)
+				}
+			}
+			log
+			`,
+			expected: str("ab", "close b", "close a"),
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			result, err := run(context.Background(), tt.input)
+			assert.Nil(t, err, "unexpected error: %v", err)
+			assert.Equal(t, result, tt.expected)
+		})
+	}
+
+	// The variable is scoped to the block
+	_, err := run(context.Background(), open+`with open("a") as f { 1 }; f`)
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "undefined variable")
+
+	// A value without a close method is an error
+	_, err = run(context.Background(), `with 1 as f { 1 }`)
+	assert.NotNil(t, err)
+}
+
 // TestReturnInTryCatch tests return statements within try/catch/finally blocks.
 func TestReturnInTryCatch(t *testing.T) {
 	tests := []struct {
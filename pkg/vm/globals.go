@@ -0,0 +1,179 @@
+package vm
+
+import (
+	"fmt"
+	"math"
+	"sync"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/bytecode"
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+	"github.com/deepnoodle-ai/risor/v2/pkg/op"
+)
+
+// globalEnv is a set of global variables. Each VM has its own, shared by all
+// code run on that VM. Functions remember the environment they were created
+// in, so a function exported by a module keeps reading and writing the
+// module's globals when a script that imported it calls it from another VM.
+type globalEnv struct {
+	values     []object.Object // Global values, indexed by slot
+	names      []string        // Name of the global in each slot
+	slots      map[string]int  // Slot of each named global
+	blankSlots []int           // Slots for unnamed globals, by compiled index
+
+	// mu guards the environment against VMs that call functions from it. Such
+	// VMs add slots when they link a function and read and write values while
+	// running it, possibly at the same time as each other. The VM that owns
+	// the environment accesses it without locking.
+	mu sync.RWMutex
+}
+
+func newGlobalEnv() *globalEnv {
+	return &globalEnv{slots: map[string]int{}}
+}
+
+// get returns the value of the named global.
+func (env *globalEnv) get(name string) (object.Object, bool) {
+	slot, ok := env.slots[name]
+	if !ok {
+		return nil, false
+	}
+	return env.values[slot], true
+}
+
+// sharedGet is get for code run by a VM that doesn't own the environment.
+func (env *globalEnv) sharedGet(name string) (object.Object, bool) {
+	env.mu.RLock()
+	defer env.mu.RUnlock()
+	return env.get(name)
+}
+
+// sharedLoad returns the value in a slot, for code run by a VM that doesn't
+// own the environment.
+func (env *globalEnv) sharedLoad(slot op.Code) object.Object {
+	env.mu.RLock()
+	defer env.mu.RUnlock()
+	return env.values[slot]
+}
+
+// sharedStore sets the value in a slot, for code run by a VM that doesn't own
+// the environment.
+func (env *globalEnv) sharedStore(slot op.Code, value object.Object) {
+	env.mu.Lock()
+	defer env.mu.Unlock()
+	env.values[slot] = value
+}
+
+// link rewrites the operands of the global load and store instructions in c
+// from the indices assigned by the compiler to the environment's slots. Each
+// compilation numbers its globals independently, so globals are matched by
+// name. This lets code that is compiled and run later on the same VM,
+// including functions defined by earlier code, share the same globals.
+func (env *globalEnv) link(c *loadedCode) error {
+	env.mu.Lock()
+	defer env.mu.Unlock()
+	root := c.Code.Root()
+	slots := make([]int, root.GlobalCount())
+	for i := range slots {
+		slots[i] = env.slot(root, i)
+	}
+	for ip := 0; ip < len(c.Instructions); ip += op.GetInfo(c.Instructions[ip]).OperandCount + 1 {
+		switch c.Instructions[ip] {
+		case op.LoadGlobal, op.StoreGlobal:
+			slot := slots[c.Instructions[ip+1]]
+			if slot > math.MaxUint16 {
+				return fmt.Errorf("too many globals: slot %d exceeds the limit of %d",
+					slot, math.MaxUint16+1)
+			}
+			c.Instructions[ip+1] = op.Code(slot)
+		}
+	}
+	return nil
+}
+
+// slot returns the slot for the global at the given compiled index in root,
+// adding a slot if the global is new to the environment. Unnamed globals,
+// which hold compiler temporaries, are matched by index instead of name.
+func (env *globalEnv) slot(root *bytecode.Code, index int) int {
+	name := root.GlobalNameAt(index)
+	if name != "" && name != "_" {
+		return env.namedSlot(name)
+	}
+	for len(env.blankSlots) <= index {
+		env.blankSlots = append(env.blankSlots, -1)
+	}
+	if env.blankSlots[index] < 0 {
+		env.blankSlots[index] = env.addSlot("")
+	}
+	return env.blankSlots[index]
+}
+
+// namedSlot returns the slot for the named global, adding a slot if the global
+// is new to the environment.
+func (env *globalEnv) namedSlot(name string) int {
+	if slot, ok := env.slots[name]; ok {
+		return slot
+	}
+	slot := env.addSlot(name)
+	env.slots[name] = slot
+	return slot
+}
+
+func (env *globalEnv) addSlot(name string) int {
+	env.values = append(env.values, nil)
+	env.names = append(env.names, name)
+	return len(env.values) - 1
+}
+
+// loadGlobal returns the value in a slot of the globals c is linked against.
+func (c *loadedCode) loadGlobal(slot op.Code) object.Object {
+	if c.shared {
+		return c.env.sharedLoad(slot)
+	}
+	return c.env.values[slot]
+}
+
+// storeGlobal sets the value in a slot of the globals c is linked against.
+func (c *loadedCode) storeGlobal(slot op.Code, value object.Object) {
+	if c.shared {
+		c.env.sharedStore(slot, value)
+		return
+	}
+	c.env.values[slot] = value
+}
+
+// getGlobal returns the value of a named global c is linked against.
+func (c *loadedCode) getGlobal(name string) (object.Object, bool) {
+	if c.shared {
+		return c.env.sharedGet(name)
+	}
+	return c.env.get(name)
+}
+
+// foreignCodeKey identifies code linked against another VM's globals.
+type foreignCodeKey struct {
+	env  *globalEnv
+	code *bytecode.Code
+}
+
+// loadFunctionCode returns the code for a function, linked against the
+// globals of the environment the function was created in.
+func (vm *VirtualMachine) loadFunctionCode(fn *object.Closure) (*loadedCode, error) {
+	env, _ := fn.Env().(*globalEnv)
+	if env == nil || env == vm.env {
+		return vm.loadCode(fn.Code())
+	}
+	key := foreignCodeKey{env: env, code: fn.Code()}
+	if lc, ok := vm.foreignCode[key]; ok {
+		return lc, nil
+	}
+	c := wrapCode(fn.Code(), env)
+	c.shared = true
+	if err := env.link(c); err != nil {
+		return nil, err
+	}
+	if vm.foreignCode == nil {
+		vm.foreignCode = map[foreignCodeKey]*loadedCode{}
+	}
+	vm.foreignCode[key] = c
+	return c, nil
+}
@@ -0,0 +1,39 @@
+package vm
+
+import (
+	"context"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+)
+
+// Importer loads the modules named by import statements. Imports are
+// disabled unless an Importer is configured with WithImporter, so a script
+// can only reach the modules its host chooses to provide.
+type Importer interface {
+	// Import returns the module for an import path. The from argument is the
+	// filename of the code containing the import statement, or "" if that
+	// code has no filename, and is used to resolve relative paths.
+	Import(ctx context.Context, path, from string) (*object.Module, error)
+}
+
+// ImporterFunc adapts a function to the Importer interface.
+type ImporterFunc func(ctx context.Context, path, from string) (*object.Module, error)
+
+// Import calls f(ctx, path, from).
+func (f ImporterFunc) Import(ctx context.Context, path, from string) (*object.Module, error) {
+	return f(ctx, path, from)
+}
+
+// importModule loads the module for an import statement in the active code.
+func (vm *VirtualMachine) importModule(ctx context.Context, path string) (*object.Module, error) {
+	if vm.importer == nil {
+		return nil, vm.runtimeError(object.ErrImport, "cannot import %q: imports are not enabled", path)
+	}
+	module, err := vm.importer.Import(ctx, path, vm.activeCode.Filename())
+	if err != nil {
+		importErr := vm.runtimeError(object.ErrImport, "cannot import %q: %v", path, err)
+		importErr.Cause = err
+		return nil, importErr
+	}
+	return module, nil
+}
@@ -63,6 +63,23 @@ func WithTypeRegistry(registry *object.TypeRegistry) Option {
 	}
 }
 
+// WithLogHandler sets the handler that receives records emitted by scripts
+// through the log module, as in log.info("saved", {id: 42}). If no handler is
+// set, records are discarded.
+func WithLogHandler(handler object.LogHandler) Option {
+	return func(vm *VirtualMachine) {
+		vm.logHandler = handler
+	}
+}
+
+// WithImporter sets the importer that loads the modules named by import
+// statements. If no importer is set, import statements raise an import error.
+func WithImporter(importer Importer) Option {
+	return func(vm *VirtualMachine) {
+		vm.importer = importer
+	}
+}
+
 // WithMaxSteps sets the maximum number of instructions the VM will execute.
 // If the limit is exceeded, the VM will return ErrStepLimitExceeded.
 // A value of 0 (default) means unlimited.
@@ -122,3 +139,16 @@ func WithTimeout(d time.Duration) Option {
 		vm.timeout = d
 	}
 }
+
+// WithFinallyOnCancel runs pending finally blocks when the context is
+// cancelled or the timeout expires, before the VM returns the context's error.
+// Catch blocks don't see the cancellation. This lets scripts clean up when a
+// host interrupts them, as the CLI does on Ctrl-C.
+//
+// A finally block can keep running for as long as it likes once cancellation
+// begins, so don't use this for untrusted scripts that must stop on time.
+func WithFinallyOnCancel() Option {
+	return func(vm *VirtualMachine) {
+		vm.finallyOnCancel = true
+	}
+}
@@ -0,0 +1,193 @@
+package vm
+
+import (
+	"context"
+	"strings"
+	"testing"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+// TestReentrantCalls tests script functions called from builtins that in
+// turn call builtins, as happens with callbacks passed to map and each.
+func TestReentrantCalls(t *testing.T) {
+	tests := []struct {
+		name     string
+		input    string
+		expected object.Object
+	}{
+		{
+			name: "deep recursion through map",
+			input: `
+			function nest(n) {
+				if (n == 0) { return [0] }
+				return [n].map(x => nest(x - 1)[0] + 1)
+			}
+			nest(200)[0]
+			`,
+			expected: object.NewInt(200),
+		},
+		{
+			name: "nested map and filter callbacks",
+			input: `
+			let rows = [[1, 2], [3, 4], [5, 6]]
+			rows.map(row => row.filter(x => [x].map(y => y % 2 == 0)[0]).map(x => x * 10)).map(row => row[0])
+			`,
+			expected: object.NewList([]object.Object{
+				object.NewInt(20), object.NewInt(40), object.NewInt(60),
+			}),
+		},
+		{
+			name: "error propagates through nested callbacks",
+			input: `
+			function walk(n) {
+				if (n == 0) { throw "deep" }
+				return [n].map(x => walk(x - 1))
+			}
+			let result = try { walk(100) } catch e { string(e) }
+			[result, [1, 2, 3].map(x => x * 2)]
+			`,
+			expected: object.NewList([]object.Object{
+				object.NewString("deep"),
+				object.NewList([]object.Object{
+					object.NewInt(2), object.NewInt(4), object.NewInt(6),
+				}),
+			}),
+		},
+		{
+			name: "error caught inside a callback",
+			input: `
+			function risky(x) {
+				if (x % 2 == 1) { throw "odd" }
+				return x
+			}
+			[1, 2, 3, 4].map(x => {
+				try {
+					return 1 + risky(x)
+				} catch e {
+					return -1
+				}
+			})
+			`,
+			expected: object.NewList([]object.Object{
+				object.NewInt(-1), object.NewInt(3), object.NewInt(-1), object.NewInt(5),
+			}),
+		},
+		{
+			name: "return from try in deep recursion",
+			input: `
+			function depth(n) {
+				if (n == 0) { return 0 }
+				try {
+					return depth(n - 1) + 1
+				} catch e {
+					return -1
+				}
+			}
+			depth(50)
+			`,
+			expected: object.NewInt(50),
+		},
+		{
+			name: "handler from a returned call is not reused",
+			input: `
+			function check(fail) {
+				if (fail) { throw "boom" }
+				try { return "ok" } catch e { return "stale" }
+			}
+			[1, 2, 3, 4, 5, 6, 7, 8, 9, 10].each(x => check(false))
+			try { check(true) } catch e { string(e) }
+			`,
+			expected: object.NewString("boom"),
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			result, err := run(context.Background(), tt.input)
+			assert.Nil(t, err, "unexpected error: %v", err)
+			assert.Equal(t, result, tt.expected)
+		})
+	}
+}
+
+// TestReentrantStackOverflow tests that running out of frames deep inside
+// builtin callbacks raises a catchable error and leaves the VM usable.
+func TestReentrantStackOverflow(t *testing.T) {
+	result, err := run(context.Background(), `
+	function forever(n) {
+		return [n].map(x => forever(x + 1))
+	}
+	let result = try { forever(0) } catch e { string(e) }
+	[result, [1, 2].map(x => x + 1)]
+	`)
+	assert.Nil(t, err)
+	items := result.(*object.List).Value()
+	assert.Contains(t, items[0].(*object.String).Value(), "stack overflow")
+	assert.Equal(t, items[1], object.NewList([]object.Object{
+		object.NewInt(2), object.NewInt(3),
+	}))
+}
+
+// TestCaughtCallErrorsDoNotGrowStack tests that values left on the stack by
+// a failed call are discarded when the error is caught.
+func TestCaughtCallErrorsDoNotGrowStack(t *testing.T) {
+	ctx := context.Background()
+	stackSize := func(repeat int) int {
+		source := `
+		function fail() { throw "boom" }
+		function add() { return 1 + fail() }
+		let caught = 0
+		` + strings.Repeat(`
+		try { add() } catch e { caught = caught + 1 }
+		try { [1].map(x => add()) } catch e { caught = caught + 1 }
+		`, repeat) + `
+		caught
+		`
+		vm, err := newVM(ctx, source)
+		assert.Nil(t, err)
+		assert.Nil(t, vm.Run(ctx))
+		result, ok := vm.TOS()
+		assert.True(t, ok)
+		assert.Equal(t, result, object.NewInt(int64(2*repeat)))
+		return vm.sp
+	}
+	assert.Equal(t, stackSize(20), stackSize(1))
+}
+
+// TestCallAfterFailedCall tests that a closure called from Go can be called
+// again after a call fails, without the failure leaking into later calls.
+func TestCallAfterFailedCall(t *testing.T) {
+	ctx := context.Background()
+	vm, err := newVM(ctx, `
+	function check() {
+		try { return 1 } catch e { return "stale" }
+	}
+	function process(fail) {
+		let values = [1, 2].map(x => check())
+		if (fail) { throw "boom" }
+		return values
+	}
+	`)
+	assert.Nil(t, err)
+	assert.Nil(t, vm.Run(ctx))
+	obj, err := vm.Get("process")
+	assert.Nil(t, err)
+	fn := obj.(*object.Closure)
+
+	sp := vm.sp
+	for i := 0; i < 20; i++ {
+		_, err := vm.Call(ctx, fn, []object.Object{object.True})
+		assert.NotNil(t, err)
+		assert.Contains(t, err.Error(), "boom")
+		assert.Equal(t, vm.sp, sp)
+		assert.Equal(t, vm.excStackSize, 0)
+	}
+	result, err := vm.Call(ctx, fn, []object.Object{object.False})
+	assert.Nil(t, err)
+	assert.Equal(t, result, object.NewList([]object.Object{
+		object.NewInt(1), object.NewInt(1),
+	}))
+	assert.Equal(t, vm.excStackSize, 0)
+}
@@ -20,7 +20,7 @@ func checkCallArgs(fn *object.Closure, argc int) error {
 			if name := fn.Name(); name != "" {
 				msg = fmt.Sprintf("%s %q", msg, name)
 			}
-			msg = fmt.Sprintf("%s requires at least %d argument(s) (%d given)", msg, requiredArgsCount, argc)
+			msg = fmt.Sprintf("%s takes at least %s (%d given)", msg, pluralArgs(requiredArgsCount), argc)
 			return object.ArgsErrorf("%s", msg)
 		}
 		return nil
@@ -32,15 +32,23 @@ func checkCallArgs(fn *object.Closure, argc int) error {
 		if name := fn.Name(); name != "" {
 			msg = fmt.Sprintf("%s %q", msg, name)
 		}
-		switch paramsCount {
-		case 0:
-			msg = fmt.Sprintf("%s takes 0 arguments (%d given)", msg, argc)
-		case 1:
-			msg = fmt.Sprintf("%s takes 1 argument (%d given)", msg, argc)
+		switch {
+		case requiredArgsCount < paramsCount && argc < requiredArgsCount:
+			// Some parameters have defaults, so report the bound that was violated
+			msg = fmt.Sprintf("%s takes at least %s (%d given)", msg, pluralArgs(requiredArgsCount), argc)
+		case requiredArgsCount < paramsCount:
+			msg = fmt.Sprintf("%s takes at most %s (%d given)", msg, pluralArgs(paramsCount), argc)
 		default:
-			msg = fmt.Sprintf("%s takes %d arguments (%d given)", msg, paramsCount, argc)
+			msg = fmt.Sprintf("%s takes %s (%d given)", msg, pluralArgs(paramsCount), argc)
 		}
 		return object.ArgsErrorf("%s", msg)
 	}
 	return nil
 }
+
+func pluralArgs(count int) string {
+	if count == 1 {
+		return "1 argument"
+	}
+	return fmt.Sprintf("%d arguments", count)
+}
@@ -5,6 +5,8 @@ import (
 	"context"
 	"errors"
 	"fmt"
+	"maps"
+	"slices"
 	"strings"
 	"sync"
 	"sync/atomic"
@@ -47,8 +49,9 @@ type VirtualMachine struct {
 	activeCode   *loadedCode
 	main         *bytecode.Code
 	inputGlobals map[string]any
-	globals      map[string]object.Object
+	env          *globalEnv // Globals of code run on this VM
 	loadedCode   map[*bytecode.Code]*loadedCode
+	foreignCode  map[foreignCodeKey]*loadedCode // Functions from other VMs
 	running      bool
 	runMutex     sync.Mutex
 	tmp          [MaxArgs]object.Object
@@ -90,6 +93,26 @@ type VirtualMachine struct {
 	// If nil, object.DefaultRegistry() is used.
 	typeRegistry *object.TypeRegistry
 
+	// logHandler receives records emitted by the log module.
+	// If nil, records are discarded.
+	logHandler object.LogHandler
+
+	// importer loads the modules named by import statements.
+	// If nil, imports are disabled.
+	importer Importer
+
+	// evalOptions are applied to the child VMs that run code for eval(), and
+	// evalDepth is how deeply this VM is nested in calls to eval().
+	evalOptions []Option
+	evalDepth   int
+
+	// Event state. Scripts register handlers with on(), the host queues
+	// events with Emit, and DispatchEvents delivers them. Guarded by eventMu
+	// since Emit may be called from other goroutines.
+	eventMu       sync.Mutex
+	eventHandlers map[string][]object.Callable
+	eventQueue    []event
+
 	// Resource limits
 	maxSteps int64 // Maximum instructions. 0 = unlimited.
 	// maxValueStackDepth limits the value stack depth (vm.sp).
@@ -100,6 +123,12 @@ type VirtualMachine struct {
 	maxFrameDepth int
 	timeout       time.Duration // Execution timeout. 0 = no timeout.
 
+	// finallyOnCancel runs pending finally blocks when the context is
+	// cancelled. cancelling is set once the cancellation has been raised, so
+	// that it is raised only once and can't be caught.
+	finallyOnCancel bool
+	cancelling      bool
+
 	// Step counting state for resource limits. These fields are stored on the
 	// VM (rather than as local variables in eval) so that step counting persists
 	// across recursive eval calls. This is important because methods like
@@ -138,15 +167,15 @@ func New(main *bytecode.Code, options ...Option) (*VirtualMachine, error) {
 // NewEmpty creates a new Virtual Machine without initial main code.
 // Code can be provided later using RunCode, or functions can be called
 // directly using Call.
-func NewEmpty() (*VirtualMachine, error) {
-	return createVM(nil)
+func NewEmpty(options ...Option) (*VirtualMachine, error) {
+	return createVM(options)
 }
 
 func createVM(options []Option) (*VirtualMachine, error) {
 	vm := &VirtualMachine{
 		sp:                   -1,
 		inputGlobals:         map[string]any{},
-		globals:              map[string]object.Object{},
+		env:                  newGlobalEnv(),
 		loadedCode:           map[*bytecode.Code]*loadedCode{},
 		contextCheckInterval: DefaultContextCheckInterval,
 		frames:               make([]frame, InitialFrameCapacity),
@@ -174,12 +203,18 @@ func (vm *VirtualMachine) applyOptions(options []Option) error {
 	// Configure observer if present
 	vm.configureObserver()
 
-	// Convert globals to Risor objects using the type registry
-	var err error
-	vm.globals, err = object.AsObjectsWithRegistry(vm.inputGlobals, vm.TypeRegistry())
+	// Convert globals to Risor objects using the type registry. Provided
+	// globals replace any values left by previous runs.
+	globals, err := object.AsObjectsWithRegistry(vm.inputGlobals, vm.TypeRegistry())
 	if err != nil {
 		return fmt.Errorf("invalid global provided: %v", err)
 	}
+	vm.env.mu.Lock()
+	defer vm.env.mu.Unlock()
+	for _, name := range slices.Sorted(maps.Keys(globals)) {
+		vm.env.values[vm.env.namedSlot(name)] = globals[name]
+	}
+	vm.inputGlobals = map[string]any{}
 	return nil
 }
 
@@ -282,6 +317,7 @@ func (vm *VirtualMachine) start(ctx context.Context) error {
 	vm.startCount++
 	// Halt execution when the context is cancelled
 	vm.halt = 0
+	vm.cancelling = false
 	if doneChan := ctx.Done(); doneChan != nil {
 		go func() {
 			<-doneChan
@@ -315,6 +351,9 @@ func (vm *VirtualMachine) Run(ctx context.Context) (err error) {
 
 // RunCode runs the given compiled code object on the VM. This allows running
 // multiple different code objects on the same VM instance sequentially.
+// The globals of the code are linked by name to those already defined on the
+// VM, so code compiled with Config.GlobalNames set to GlobalNames() can use
+// the globals and functions defined by code run before it.
 // The VM must not be currently running when this method is called.
 func (vm *VirtualMachine) RunCode(ctx context.Context, codeToRun *bytecode.Code, opts ...Option) (err error) {
 	if err := vm.applyOptions(opts); err != nil {
@@ -364,20 +403,25 @@ func (vm *VirtualMachine) runCodeInternal(ctx context.Context, codeToRun *byteco
 		if !resetState {
 			// For Run(), we need to preserve globals from previous runs (REPL behavior)
 			// Use reloadCode to get fresh code with preserved globals
-			codeObj = vm.reloadCode(codeToRun)
+			codeObj, err = vm.reloadCode(codeToRun)
 		} else {
 			// Reuse the existing code object as-is
 			codeObj = existingCode
 		}
 	} else {
 		// Load this code for the first time
-		codeObj = vm.loadCode(codeToRun)
+		codeObj, err = vm.loadCode(codeToRun)
+	}
+	if err != nil {
+		return err
 	}
 
 	// Load function constants
 	for i := 0; i < codeToRun.ConstantCount(); i++ {
 		if fn, ok := codeToRun.ConstantAt(i).(*bytecode.Function); ok {
-			vm.loadCode(fn.Code())
+			if _, err := vm.loadCode(fn.Code()); err != nil {
+				return err
+			}
 		}
 	}
 
@@ -401,27 +445,9 @@ func (vm *VirtualMachine) runCodeInternal(ctx context.Context, codeToRun *byteco
 	return vm.eval(vm.initContext(ctx))
 }
 
-// resetForNewCode resets the VM state for running a new code object
-// while preserving any globals that were defined during previous runs.
-// Globals provided via WithGlobals take precedence over preserved values.
+// resetForNewCode resets the VM state for running a new code object.
+// Globals are kept, since they are stored in the VM rather than the code.
 func (vm *VirtualMachine) resetForNewCode() {
-	// Preserve globals from the current main code before resetting.
-	// Only set globals that don't already exist in vm.globals, so that
-	// values provided via WithGlobals take precedence.
-	if vm.activeCode != nil {
-		for i := 0; i < vm.activeCode.GlobalCount(); i++ {
-			name := vm.activeCode.GlobalNameAt(i)
-			if value := vm.activeCode.Globals[i]; value != nil {
-				if vm.globals == nil {
-					vm.globals = make(map[string]object.Object)
-				}
-				if _, exists := vm.globals[name]; !exists {
-					vm.globals[name] = value
-				}
-			}
-		}
-	}
-
 	vm.sp = -1
 	vm.ip = 0
 	vm.fp = 0
@@ -429,6 +455,7 @@ func (vm *VirtualMachine) resetForNewCode() {
 	vm.activeFrame = nil
 	vm.activeCode = nil
 	vm.loadedCode = map[*bytecode.Code]*loadedCode{}
+	vm.foreignCode = nil
 	vm.excStackSize = 0
 
 	// Clear stack (only used portion would be cleaner but this ensures GC)
@@ -445,31 +472,57 @@ func (vm *VirtualMachine) resetForNewCode() {
 	}
 }
 
-// Get a global variable by name as a Risor Object.
+// Get a global variable by name as a Risor Object. Globals defined by any
+// code previously run on the VM are available.
 func (vm *VirtualMachine) Get(name string) (object.Object, error) {
-	code := vm.activeCode
-	if code == nil {
-		return nil, errors.New("no active code")
-	}
-	globalCount := code.GlobalCount()
-	for i := 0; i < globalCount; i++ {
-		if code.GlobalNameAt(i) == name {
-			return code.Globals[i], nil
+	value, ok := vm.env.get(name)
+	if !ok {
+		return nil, fmt.Errorf("%w: %q", ErrGlobalNotFound, name)
+	}
+	return value, nil
+}
+
+// Set the value of a global variable by name. The variable must be defined by
+// code loaded on the VM or provided with WithGlobals; new globals cannot be
+// added this way. Set must not be called while the VM is running.
+func (vm *VirtualMachine) Set(name string, value object.Object) error {
+	slot, ok := vm.env.slots[name]
+	if !ok {
+		return fmt.Errorf("%w: %q", ErrGlobalNotFound, name)
+	}
+	vm.env.values[slot] = value
+	return nil
+}
+
+// Exports returns the current values of the globals the main code declared
+// with the export statement, keyed by name. Call it after Run to retrieve a
+// script's public surface. A script without export statements returns an
+// empty map.
+func (vm *VirtualMachine) Exports() (map[string]object.Object, error) {
+	if vm.main == nil {
+		return nil, errors.New("no main code available")
+	}
+	exports := map[string]object.Object{}
+	for _, name := range vm.main.Exports() {
+		value, err := vm.Get(name)
+		if err != nil {
+			return nil, err
 		}
+		exports[name] = value
 	}
-	return nil, fmt.Errorf("%w: %q", ErrGlobalNotFound, name)
+	return exports, nil
 }
 
-// GlobalNames returns the names of all global variables in the active code.
+// GlobalNames returns the names of all global variables defined on the VM,
+// in the order they were defined. This includes globals from every code
+// object run on the VM, so it can be passed to the compiler as
+// Config.GlobalNames to compile further code that uses them.
 func (vm *VirtualMachine) GlobalNames() []string {
-	code := vm.activeCode
-	if code == nil {
-		return nil
-	}
-	count := code.GlobalCount()
-	names := make([]string, 0, count)
-	for i := 0; i < count; i++ {
-		names = append(names, code.GlobalNameAt(i))
+	names := make([]string, 0, len(vm.env.slots))
+	for _, name := range vm.env.names {
+		if name != "" {
+			names = append(names, name)
+		}
 	}
 	return names
 }
@@ -498,8 +551,11 @@ func (vm *VirtualMachine) eval(ctx context.Context) error {
 evalLoop:
 	for vm.ip < len(vm.activeCode.Instructions) {
 
-		if atomic.LoadInt32(&vm.halt) == 1 {
-			return ctx.Err()
+		if atomic.LoadInt32(&vm.halt) == 1 && !vm.cancelling {
+			if err := vm.cancel(ctx); err != nil {
+				return err
+			}
+			continue
 		}
 
 		// Periodic checks (context, steps, stack) every N instructions.
@@ -512,11 +568,14 @@ evalLoop:
 				vm.stepCheckCounter = 0
 
 				// Context cancellation check
-				if doneChan != nil {
+				if doneChan != nil && !vm.cancelling {
 					select {
 					case <-doneChan:
 						atomic.StoreInt32(&vm.halt, 1)
-						return ctx.Err()
+						if err := vm.cancel(ctx); err != nil {
+							return err
+						}
+						continue evalLoop
 					default:
 					}
 				}
@@ -559,8 +618,7 @@ evalLoop:
 			name := vm.activeCode.Names[vm.fetch()]
 			value, found := obj.GetAttr(name)
 			if !found {
-				if herr := vm.tryHandleError(vm.typeError("attribute %q not found on %s object",
-					name, obj.Type())); herr != nil {
+				if herr := vm.tryHandleError(vm.attrNotFoundError(obj, name)); herr != nil {
 					return herr
 				}
 				continue
@@ -603,7 +661,26 @@ evalLoop:
 		case op.LoadFast:
 			vm.push(vm.activeFrame.Locals()[vm.fetch()])
 		case op.LoadGlobal:
-			vm.push(vm.activeCode.Globals[vm.fetch()])
+			vm.push(vm.activeCode.loadGlobal(vm.fetch()))
+		case op.LoadName:
+			name := vm.activeCode.Names[vm.fetch()]
+			value, ok := vm.activeCode.getGlobal(name)
+			if !ok || value == nil {
+				if herr := vm.tryHandleError(vm.runtimeError(object.ErrName,
+					"undefined variable %q", name)); herr != nil {
+					return herr
+				}
+				continue
+			}
+			vm.push(value)
+		case op.LoadNameOrNil:
+			name := vm.activeCode.Names[vm.fetch()]
+			value, ok := vm.activeCode.getGlobal(name)
+			if !ok || value == nil {
+				vm.push(object.Nil)
+			} else {
+				vm.push(value)
+			}
 		case op.LoadFree:
 			idx := vm.fetch()
 			obj := vm.activeFrame.fn.FreeVar(int(idx)).Value()
@@ -613,7 +690,8 @@ evalLoop:
 			obj := vm.pop()
 			vm.activeFrame.Locals()[idx] = obj
 		case op.StoreGlobal:
-			vm.activeCode.Globals[vm.fetch()] = vm.pop()
+			idx := vm.fetch()
+			vm.activeCode.storeGlobal(idx, vm.pop())
 		case op.StoreFree:
 			idx := vm.fetch()
 			obj := vm.pop()
@@ -649,17 +727,11 @@ evalLoop:
 			vm.push(object.CloneWithCaptures(fn, free))
 		case op.MakeCell:
 			symbolIndex := vm.fetch()
-			framesBack := int(vm.fetch())
-			frameIndex := vm.fp - framesBack
-			if frameIndex < 0 {
-				if herr := vm.tryHandleError(vm.evalError("no frame at depth %d", framesBack)); herr != nil {
-					return herr
-				}
-				continue
-			}
-			frame := &vm.frames[frameIndex]
-			locals := frame.CaptureLocals()
+			locals := vm.activeFrame.CaptureLocals()
 			vm.push(object.NewCell(&locals[symbolIndex]))
+		case op.LoadCell:
+			idx := vm.fetch()
+			vm.push(vm.activeFrame.fn.FreeVar(int(idx)))
 		case op.Nil:
 			vm.push(object.Nil)
 		case op.True:
@@ -670,11 +742,15 @@ evalLoop:
 			opType := op.CompareOpType(vm.fetch())
 			b := vm.pop()
 			a := vm.pop()
-			result, err := object.Compare(opType, a, b)
+			if result, ok := intCompareObjects(opType, a, b); ok {
+				// Specialize the instruction for ints
+				vm.activeCode.Instructions[vm.ip-2] = op.CompareOpInt
+				vm.push(result)
+				continue
+			}
+			result, err := vm.compare(ctx, opType, a, b)
 			if err != nil {
-				// Wrap the error with location info if it's a simple type error
-				wrappedErr := vm.wrapError(err)
-				if herr := vm.tryHandleError(wrappedErr); herr != nil {
+				if herr := vm.tryHandleError(err); herr != nil {
 					return herr
 				}
 				continue
@@ -684,11 +760,55 @@ evalLoop:
 			opType := op.BinaryOpType(vm.fetch())
 			b := vm.pop()
 			a := vm.pop()
-			result, err := object.BinaryOp(opType, a, b)
+			if result, ok := intBinaryOpObjects(opType, a, b); ok {
+				// Specialize the instruction for ints
+				vm.activeCode.Instructions[vm.ip-2] = op.BinaryOpInt
+				vm.push(result)
+				continue
+			}
+			result, err := vm.binaryOp(ctx, opType, a, b)
+			if err != nil {
+				if herr := vm.tryHandleError(err); herr != nil {
+					return herr
+				}
+				continue
+			}
+			vm.push(result)
+		case op.BinaryOpInt:
+			// Operate on the stack in place while the operands are ints
+			opType := op.BinaryOpType(vm.fetch())
+			if result, ok := intBinaryOpObjects(opType, vm.stack[vm.sp-1], vm.stack[vm.sp]); ok {
+				vm.stack[vm.sp] = nil
+				vm.sp--
+				vm.stack[vm.sp] = result
+				continue
+			}
+			// Other types turned up, so return to the generic instruction
+			vm.activeCode.Instructions[vm.ip-2] = op.BinaryOp
+			b := vm.pop()
+			a := vm.pop()
+			result, err := vm.binaryOp(ctx, opType, a, b)
 			if err != nil {
-				// Wrap the error with location info if it's a simple type error
-				wrappedErr := vm.wrapError(err)
-				if herr := vm.tryHandleError(wrappedErr); herr != nil {
+				if herr := vm.tryHandleError(err); herr != nil {
+					return herr
+				}
+				continue
+			}
+			vm.push(result)
+		case op.CompareOpInt:
+			opType := op.CompareOpType(vm.fetch())
+			if result, ok := intCompareObjects(opType, vm.stack[vm.sp-1], vm.stack[vm.sp]); ok {
+				vm.stack[vm.sp] = nil
+				vm.sp--
+				vm.stack[vm.sp] = result
+				continue
+			}
+			vm.activeCode.Instructions[vm.ip-2] = op.CompareOp
+			b := vm.pop()
+			a := vm.pop()
+			result, err := vm.compare(ctx, opType, a, b)
+			if err != nil {
+				if herr := vm.tryHandleError(err); herr != nil {
 					return herr
 				}
 				continue
@@ -714,6 +834,24 @@ evalLoop:
 				}
 				continue
 			}
+		case op.CallMethod:
+			name := vm.activeCode.Names[vm.fetch()]
+			argc := int(vm.fetch())
+			if argc > MaxArgs {
+				if herr := vm.tryHandleError(vm.evalError("max args limit of %d exceeded (got %d)",
+					MaxArgs, argc)); herr != nil {
+					return herr
+				}
+				continue
+			}
+			args := vm.popN(argc)
+			obj := vm.pop()
+			if err := vm.callMethod(ctx, obj, name, args); err != nil {
+				if herr := vm.tryHandleError(err); herr != nil {
+					return herr
+				}
+				continue
+			}
 		case op.Partial:
 			argc := int(vm.fetch())
 			args := make([]object.Object, argc)
@@ -826,18 +964,35 @@ evalLoop:
 			vm.ip = base - delta
 		case op.BuildList:
 			count := vm.fetch()
-			items := make([]object.Object, count)
-			for i := uint16(0); i < count; i++ {
-				items[count-1-i] = vm.pop()
-			}
-			vm.push(object.NewList(items))
-		case op.BuildMap:
+			vm.push(object.NewList(vm.popN(int(count))))
+		case op.BuildTuple:
 			count := vm.fetch()
+			vm.push(object.NewTuple(vm.popN(int(count))))
+		case op.BuildMap:
+			count := int(vm.fetch())
 			items := make(map[string]object.Object, count)
-			for i := uint16(0); i < count; i++ {
-				v := vm.pop()
-				k := vm.pop()
-				items[k.(*object.String).Value()] = v
+			base := vm.sp - 2*count + 1
+			var keyErr error
+			// Visit the pairs from the top of the stack down, so the first
+			// occurrence of a duplicate key wins
+			for i := count - 1; i >= 0; i-- {
+				k, v := vm.stack[base+2*i], vm.stack[base+2*i+1]
+				key, ok := k.(*object.String)
+				if !ok {
+					if keyErr == nil {
+						keyErr = vm.typeError("map key must be a string (got %s)", k.Type())
+					}
+					continue
+				}
+				items[key.Value()] = v
+			}
+			clear(vm.stack[base : vm.sp+1])
+			vm.sp = base - 1
+			if keyErr != nil {
+				if herr := vm.tryHandleError(keyErr); herr != nil {
+					return herr
+				}
+				continue
 			}
 			vm.push(object.NewMap(items))
 		case op.ListAppend:
@@ -929,7 +1084,7 @@ evalLoop:
 			}
 			key, ok := keyObj.(*object.String)
 			if !ok {
-				if herr := vm.tryHandleError(vm.typeError("map key must be string (got %s)", keyObj.Type())); herr != nil {
+				if herr := vm.tryHandleError(vm.typeError("map key must be a string (got %s)", keyObj.Type())); herr != nil {
 					return herr
 				}
 				continue
@@ -976,6 +1131,22 @@ evalLoop:
 				}
 				continue
 			}
+		case op.DeleteSubscr:
+			idx := vm.pop()
+			lhs := vm.pop()
+			container, ok := lhs.(object.Container)
+			if !ok {
+				if herr := vm.tryHandleError(vm.typeError("object is not a container (got %s)", lhs.Type())); herr != nil {
+					return herr
+				}
+				continue
+			}
+			if err := container.DelItem(idx); err != nil {
+				if herr := vm.handleException(err); herr != nil {
+					return herr
+				}
+				continue
+			}
 		case op.UnaryNegative:
 			obj := vm.pop()
 			switch obj := obj.(type) {
@@ -983,12 +1154,27 @@ evalLoop:
 				vm.push(object.NewInt(-obj.Value()))
 			case *object.Float:
 				vm.push(object.NewFloat(-obj.Value()))
+			case *object.Decimal:
+				vm.push(obj.Neg())
 			default:
 				if herr := vm.tryHandleError(vm.typeError("object is not a number (got %s)", obj.Type())); herr != nil {
 					return herr
 				}
 				continue
 			}
+		case op.UnaryInvert:
+			obj := vm.pop()
+			switch obj := obj.(type) {
+			case *object.Int:
+				vm.push(object.NewInt(^obj.Value()))
+			case *object.Byte:
+				vm.push(object.NewByte(^obj.Value()))
+			default:
+				if herr := vm.tryHandleError(vm.typeError("bitwise not requires an int or byte (got %s)", obj.Type())); herr != nil {
+					return herr
+				}
+				continue
+			}
 		case op.UnaryNot:
 			obj := vm.pop()
 			if obj.IsTruthy() {
@@ -1018,24 +1204,45 @@ evalLoop:
 		case op.BuildString:
 			count := vm.fetch()
 			items := make([]string, count)
+			var strErr error
 			for i := uint16(0); i < count; i++ {
 				dst := count - 1 - i
 				obj := vm.pop()
-				switch obj := obj.(type) {
-				case *object.Error:
-					// Errors are values - stringify them in templates
-					items[dst] = obj.String()
-				case *object.String:
-					items[dst] = obj.Value()
-				default:
-					items[dst] = obj.Inspect()
+				if strErr != nil {
+					continue
+				}
+				items[dst], strErr = templateString(ctx, obj)
+			}
+			if strErr != nil {
+				if herr := vm.tryHandleError(strErr); herr != nil {
+					return herr
 				}
+				continue
 			}
 			vm.push(object.NewString(strings.Join(items, "")))
+		case op.BuildQuote:
+			// Source fragments alternate with unquoted values, starting and
+			// ending with a fragment
+			count := 2*int(vm.fetch()) + 1
+			parts := make([]string, count)
+			var quoteErr error
+			for i := count - 1; i >= 0; i-- {
+				obj := vm.pop()
+				if i%2 == 0 {
+					parts[i] = obj.(*object.String).Value()
+				} else if quoteErr == nil {
+					parts[i], quoteErr = object.UnquoteSource(obj)
+				}
+			}
+			if quoteErr != nil {
+				if herr := vm.tryHandleError(quoteErr); herr != nil {
+					return herr
+				}
+				continue
+			}
+			vm.push(object.NewQuote(strings.Join(parts, "")))
 		case op.Slice:
-			start := vm.pop()
-			stop := vm.pop()
-			containerObj := vm.pop()
+			containerObj, slice := vm.popSlice(vm.fetch())
 			container, ok := containerObj.(object.Container)
 			if !ok {
 				if herr := vm.tryHandleError(vm.typeError("object is not a container (got %s)",
@@ -1044,7 +1251,6 @@ evalLoop:
 				}
 				continue
 			}
-			slice := object.Slice{Start: start, Stop: stop}
 			result, err := container.GetSlice(slice)
 			if err != nil {
 				if herr := vm.handleException(err); herr != nil {
@@ -1053,6 +1259,23 @@ evalLoop:
 				continue
 			}
 			vm.push(result)
+		case op.StoreSlice:
+			containerObj, slice := vm.popSlice(vm.fetch())
+			value := vm.pop()
+			container, ok := containerObj.(object.Container)
+			if !ok {
+				if herr := vm.tryHandleError(vm.typeError("object is not a container (got %s)",
+					containerObj.Type())); herr != nil {
+					return herr
+				}
+				continue
+			}
+			if err := container.SetSlice(slice, value); err != nil {
+				if herr := vm.handleException(err); herr != nil {
+					return herr
+				}
+				continue
+			}
 		case op.Length:
 			containerObj := vm.pop()
 			container, ok := containerObj.(object.Container)
@@ -1081,6 +1304,15 @@ evalLoop:
 				continue
 			}
 			containerSize := container.Len().Value()
+			// Tuples hold a fixed number of return values, so they must be
+			// unpacked into exactly that many variables
+			if _, ok := containerObj.(*object.Tuple); ok && containerSize != nameCount {
+				if herr := vm.tryHandleError(vm.runtimeError(object.ErrValue, "unpack count mismatch: expected %d values, got %d",
+					nameCount, containerSize)); herr != nil {
+					return herr
+				}
+				continue
+			}
 			// Allow fewer elements than expected (for destructuring with defaults)
 			if containerSize > nameCount {
 				if herr := vm.tryHandleError(fmt.Errorf("unpack count mismatch: %d > %d", containerSize, nameCount)); herr != nil {
@@ -1136,6 +1368,9 @@ evalLoop:
 				}
 			}
 
+			if vm.excStackSize == len(vm.excStack) {
+				vm.excStack = append(vm.excStack, exceptionFrame{})
+			}
 			vm.excStack[vm.excStackSize] = exceptionFrame{
 				handler: handler,
 				code:    vm.activeCode,
@@ -1166,6 +1401,37 @@ evalLoop:
 			if err := vm.handleException(errObj); err != nil {
 				return err
 			}
+		case op.AssertFail:
+			// Stack: [left, right,] message. The operands are only present
+			// when the condition was a comparison.
+			source := vm.activeCode.Constants[vm.fetch()].(*object.String).Value()
+			cmp := op.CompareOpType(vm.fetch())
+			message := vm.pop()
+			var left, right object.Object
+			if cmp != 0 {
+				right = vm.pop()
+				left = vm.pop()
+			}
+			text, err := assertionMessage(ctx, source, message, cmp, left, right)
+			if err != nil {
+				if herr := vm.tryHandleError(err); herr != nil {
+					return herr
+				}
+				continue
+			}
+			if herr := vm.tryHandleError(vm.runtimeError(object.ErrAssertion, "%s", text)); herr != nil {
+				return herr
+			}
+		case op.Import:
+			path := vm.activeCode.Constants[vm.fetch()].(*object.String).Value()
+			module, err := vm.importModule(ctx, path)
+			if err != nil {
+				if herr := vm.tryHandleError(err); herr != nil {
+					return herr
+				}
+				continue
+			}
+			vm.push(module)
 		case op.EndFinally:
 			// End of finally block - check for pending return or exception
 			if vm.excStackSize > 0 {
@@ -1279,6 +1545,38 @@ func (vm *VirtualMachine) pop() object.Object {
 	return obj
 }
 
+// popSlice removes the arguments of a slice and the container below them from
+// the stack. With a step, omitted bounds are pushed as nil so the container
+// can choose their defaults.
+func (vm *VirtualMachine) popSlice(count uint16) (object.Object, object.Slice) {
+	var slice object.Slice
+	if count == 3 {
+		slice.Step = vm.pop()
+	}
+	slice.Start = vm.pop()
+	slice.Stop = vm.pop()
+	if slice.Step != nil {
+		if slice.Start == object.Nil {
+			slice.Start = nil
+		}
+		if slice.Stop == object.Nil {
+			slice.Stop = nil
+		}
+	}
+	return vm.pop(), slice
+}
+
+// popN removes the top count objects from the stack and returns them in the
+// order they were pushed.
+func (vm *VirtualMachine) popN(count int) []object.Object {
+	start := vm.sp - count + 1
+	items := make([]object.Object, count)
+	copy(items, vm.stack[start:vm.sp+1])
+	clear(vm.stack[start : vm.sp+1])
+	vm.sp -= count
+	return items
+}
+
 func (vm *VirtualMachine) push(obj object.Object) {
 	vm.sp++
 	vm.stack[vm.sp] = obj
@@ -1348,8 +1646,11 @@ func (vm *VirtualMachine) callFunction(
 	baseFP := vm.fp
 	baseIP := vm.ip
 	baseSP := vm.sp
+	baseExc := vm.excStackSize
 
-	// Restore the previous frame when done.
+	// Restore the previous frame when done. Any exception handlers the call
+	// left behind belong to frames that no longer exist, so they're dropped.
+	// If the call failed, whatever it left on the stack is dropped too.
 	// If panicking, capture the stack trace before restoring the frame.
 	defer func() {
 		if r := recover(); r != nil {
@@ -1357,10 +1658,15 @@ func (vm *VirtualMachine) callFunction(
 			if vm.panicStack == nil {
 				vm.panicStack = vm.captureStack()
 			}
-			vm.resumeFrame(baseFP, baseIP, baseSP)
+			vm.unwindFrame(baseFP, baseIP, baseSP, baseExc)
 			panic(r) // Re-panic to continue unwinding
 		}
+		if resultErr != nil {
+			vm.unwindFrame(baseFP, baseIP, baseSP, baseExc)
+			return
+		}
 		vm.resumeFrame(baseFP, baseIP, baseSP)
+		vm.excStackSize = min(vm.excStackSize, baseExc)
 	}()
 
 	// Assemble frame local variables in vm.tmp. The local variable order is:
@@ -1386,10 +1692,12 @@ func (vm *VirtualMachine) callFunction(
 			}
 		}
 
-		// Collect remaining args into rest param list
+		// Collect remaining args into rest param list. The args are copied
+		// since the slice may be owned by the caller (e.g. a spread list).
 		var restArgs []object.Object
 		if argc > paramsCount {
-			restArgs = args[paramsCount:]
+			restArgs = make([]object.Object, argc-paramsCount)
+			copy(restArgs, args[paramsCount:])
 		} else {
 			restArgs = []object.Object{}
 		}
@@ -1478,11 +1786,234 @@ func (vm *VirtualMachine) callObject(
 		copy(newArgs[argc:], fn.Args())
 		// Recursive call with the wrapped function and the combined args
 		return vm.callObject(ctx, fn.Function(), newArgs)
+	case *object.Map:
+		// A map with a __call__ member is called with itself as the first argument
+		method, ok := object.LookupMethod(fn, object.MethodCall)
+		if !ok {
+			return vm.typeError("object is not callable (got %s)", fn.Type())
+		}
+		if len(args)+1 > MaxArgs {
+			return vm.evalError("max arguments limit of %d exceeded (got %d)",
+				MaxArgs, len(args)+1)
+		}
+		newArgs := make([]object.Object, 0, len(args)+1)
+		newArgs = append(newArgs, fn)
+		newArgs = append(newArgs, args...)
+		return vm.callObject(ctx, method.(object.Object), newArgs)
 	default:
 		return vm.typeError("object is not callable (got %s)", fn.Type())
 	}
 }
 
+// callMethod calls the named method of obj. Objects that implement
+// object.MethodCaller are called directly. Otherwise the method is looked up
+// as an attribute and called, as LoadAttr followed by Call would do.
+func (vm *VirtualMachine) callMethod(
+	ctx context.Context,
+	obj object.Object,
+	name string,
+	args []object.Object,
+) error {
+	if caller, ok := obj.(object.MethodCaller); ok {
+		result, found, err := caller.CallMethod(ctx, name, args)
+		if found {
+			if err != nil {
+				return err
+			}
+			vm.push(result)
+			return nil
+		}
+	}
+	value, found := obj.GetAttr(name)
+	if !found {
+		return vm.attrNotFoundError(obj, name)
+	}
+	if resolver, ok := value.(object.AttrResolver); ok {
+		attr, err := resolver.ResolveAttr(ctx, name)
+		if err != nil {
+			return err
+		}
+		value = attr
+	}
+	return vm.callObject(ctx, value, args)
+}
+
+// binaryOp applies a binary operator. If the left operand is a map that
+// overloads the operator (e.g. defines __add__), the method is called with
+// both operands instead.
+func (vm *VirtualMachine) binaryOp(
+	ctx context.Context,
+	opType op.BinaryOpType,
+	a, b object.Object,
+) (object.Object, error) {
+	if name, ok := object.BinaryOpMethod(opType); ok {
+		if method, ok := object.LookupMethod(a, name); ok {
+			return method.Call(ctx, a, b)
+		}
+	}
+	result, err := object.BinaryOp(opType, a, b)
+	if err != nil {
+		// Wrap the error with location info if it's a simple type error
+		return nil, vm.wrapError(err)
+	}
+	return result, nil
+}
+
+// compare applies a comparison operator, consulting __eq__ and __lt__ on map
+// operands. Equality uses whichever operand defines __eq__, preferring the
+// left. Orderings are derived from __lt__ alone: a > b is b < a, a <= b is
+// !(b < a), and a >= b is !(a < b).
+func (vm *VirtualMachine) compare(
+	ctx context.Context,
+	opType op.CompareOpType,
+	a, b object.Object,
+) (object.Object, error) {
+	var result bool
+	var found bool
+	var err error
+	switch opType {
+	case op.Equal, op.NotEqual:
+		result, found, err = dunderEqual(ctx, a, b)
+		result = result != (opType == op.NotEqual)
+	case op.LessThan, op.GreaterThanOrEqual:
+		result, found, err = dunderLess(ctx, a, b)
+		result = result != (opType == op.GreaterThanOrEqual)
+	case op.GreaterThan, op.LessThanOrEqual:
+		result, found, err = dunderLess(ctx, b, a)
+		result = result != (opType == op.LessThanOrEqual)
+	}
+	if err != nil {
+		return nil, err
+	}
+	if !found {
+		result, err := object.Compare(opType, a, b)
+		if err != nil {
+			// Wrap the error with location info if it's a simple type error
+			return nil, vm.wrapError(err)
+		}
+		return result, nil
+	}
+	return object.NewBool(result), nil
+}
+
+// dunderEqual reports whether a == b using the __eq__ method of either
+// operand, preferring a. found is false if neither defines __eq__.
+func dunderEqual(ctx context.Context, a, b object.Object) (result, found bool, err error) {
+	if m, ok := object.LookupMethod(a, object.MethodEq); ok {
+		return callDunder(ctx, m, a, b)
+	}
+	if m, ok := object.LookupMethod(b, object.MethodEq); ok {
+		return callDunder(ctx, m, b, a)
+	}
+	return false, false, nil
+}
+
+// dunderLess reports whether a < b using the __lt__ method of either
+// operand, preferring a. When only b defines __lt__, a < b holds if b < a
+// doesn't and the operands aren't equal. found is false if neither defines
+// __lt__.
+func dunderLess(ctx context.Context, a, b object.Object) (result, found bool, err error) {
+	if m, ok := object.LookupMethod(a, object.MethodLt); ok {
+		return callDunder(ctx, m, a, b)
+	}
+	m, ok := object.LookupMethod(b, object.MethodLt)
+	if !ok {
+		return false, false, nil
+	}
+	greater, _, err := callDunder(ctx, m, b, a)
+	if err != nil || greater {
+		return false, true, err
+	}
+	equal, found, err := dunderEqual(ctx, a, b)
+	if err != nil {
+		return false, true, err
+	}
+	if !found {
+		equal = a.Equals(b)
+	}
+	return !equal, true, nil
+}
+
+// callDunder calls a dunder method with the operand that defines it first.
+func callDunder(ctx context.Context, method object.Callable, self, other object.Object) (bool, bool, error) {
+	result, err := method.Call(ctx, self, other)
+	if err != nil {
+		return false, true, err
+	}
+	return result.IsTruthy(), true, nil
+}
+
+// intBinaryOpObjects is the fast path for int arithmetic, which is common in
+// loops. It handles the operators that can't fail when both operands are
+// ints, and reports false otherwise so that the operation goes through
+// object.BinaryOp.
+func intBinaryOpObjects(opType op.BinaryOpType, left, right object.Object) (object.Object, bool) {
+	x, ok := left.(*object.Int)
+	if !ok {
+		return nil, false
+	}
+	y, ok := right.(*object.Int)
+	if !ok {
+		return nil, false
+	}
+	a, b := x.Value(), y.Value()
+	switch opType {
+	case op.Add:
+		return object.NewInt(a + b), true
+	case op.Subtract:
+		return object.NewInt(a - b), true
+	case op.Multiply:
+		return object.NewInt(a * b), true
+	}
+	return nil, false
+}
+
+// intCompareObjects is the fast path for comparing two ints. It reports false
+// if either operand isn't an int, or for unknown operators, which are left to
+// object.Compare to reject.
+func intCompareObjects(opType op.CompareOpType, left, right object.Object) (object.Object, bool) {
+	x, ok := left.(*object.Int)
+	if !ok {
+		return nil, false
+	}
+	y, ok := right.(*object.Int)
+	if !ok {
+		return nil, false
+	}
+	a, b := x.Value(), y.Value()
+	switch opType {
+	case op.LessThan:
+		return object.NewBool(a < b), true
+	case op.LessThanOrEqual:
+		return object.NewBool(a <= b), true
+	case op.Equal:
+		return object.NewBool(a == b), true
+	case op.NotEqual:
+		return object.NewBool(a != b), true
+	case op.GreaterThan:
+		return object.NewBool(a > b), true
+	case op.GreaterThanOrEqual:
+		return object.NewBool(a >= b), true
+	}
+	return nil, false
+}
+
+// templateString returns the text a value contributes to a template string.
+func templateString(ctx context.Context, obj object.Object) (string, error) {
+	if str, ok, err := object.CallStr(ctx, obj); ok {
+		return str, err
+	}
+	switch obj := obj.(type) {
+	case *object.Error:
+		// Errors are values - stringify them in templates
+		return obj.String(), nil
+	case *object.String:
+		return obj.Value(), nil
+	default:
+		return obj.Inspect(), nil
+	}
+}
+
 // Resume the frame at the given frame pointer, restoring the given IP and SP.
 func (vm *VirtualMachine) resumeFrame(fp, ip, sp int) *frame {
 	// The return value of the previous frame is on the top of the stack
@@ -1507,6 +2038,21 @@ func (vm *VirtualMachine) resumeFrame(fp, ip, sp int) *frame {
 	return vm.activeFrame
 }
 
+// unwindFrame resumes the frame at fp after a call out of it failed. Unlike
+// resumeFrame, nothing on the stack above sp is kept, since a failed call
+// has no result. Exception handlers above excSize are discarded.
+func (vm *VirtualMachine) unwindFrame(fp, ip, sp, excSize int) {
+	for i := vm.sp; i > sp; i-- {
+		vm.stack[i] = nil
+	}
+	vm.sp = sp
+	vm.excStackSize = min(vm.excStackSize, excSize)
+	vm.fp = fp
+	vm.ip = ip
+	vm.activeFrame = &vm.frames[fp]
+	vm.activeCode = vm.activeFrame.code
+}
+
 // ensureFrameCapacity grows the frames slice if needed to accommodate the given frame index.
 // Returns an error if the frame index exceeds the configured limit or MaxFrameDepth.
 func (vm *VirtualMachine) ensureFrameCapacity(fp int) error {
@@ -1552,7 +2098,10 @@ func (vm *VirtualMachine) activateFunction(fp, ip int, fn *object.Closure, local
 	if err := vm.ensureFrameCapacity(fp); err != nil {
 		return nil, err
 	}
-	code := vm.loadCode(fn.Code())
+	code, err := vm.loadFunctionCode(fn)
+	if err != nil {
+		return nil, err
+	}
 	returnAddr := vm.ip
 	returnSp := vm.sp
 	vm.fp = fp
@@ -1563,39 +2112,37 @@ func (vm *VirtualMachine) activateFunction(fp, ip int, fn *object.Closure, local
 	return vm.activeFrame, nil
 }
 
-// Wrap the *bytecode.Code in a *loadedCode object to make it usable by the VM.
-func (vm *VirtualMachine) loadCode(bc *bytecode.Code) *loadedCode {
+// Wrap the *bytecode.Code in a *loadedCode object to make it usable by the VM,
+// linked against the VM's globals.
+func (vm *VirtualMachine) loadCode(bc *bytecode.Code) (*loadedCode, error) {
 	if lc, ok := vm.loadedCode[bc]; ok {
-		return lc
-	}
-	// Loading is slightly different if this is the "root" (entrypoint) code
-	// vs. a child of that. The root code owns the globals array, while the
-	// children will reuse the globals from the root.
-	var c *loadedCode
-	if vm.main == bc {
-		c = loadRootCode(bc, vm.globals)
-	} else {
-		c = loadChildCode(vm.loadedCode[vm.main], bc)
+		return lc, nil
+	}
+	c := wrapCode(bc, vm.env)
+	if err := vm.env.link(c); err != nil {
+		return nil, err
 	}
 	vm.loadedCode[bc] = c
-	return c
+	return c, nil
 }
 
-// Reloads the main code while preserving global variables. This happens as
-// part of a typical REPL workflow, where the main code is appended to with
-// each new input.
-func (vm *VirtualMachine) reloadCode(main *bytecode.Code) *loadedCode {
-	oldWrappedMain, ok := vm.loadedCode[main]
-	if !ok {
+// Reloads the main code, discarding the previously loaded copy. This happens
+// as part of a typical REPL workflow, where the main code is appended to with
+// each new input. Globals are stored in the VM, so they are preserved.
+func (vm *VirtualMachine) reloadCode(main *bytecode.Code) (*loadedCode, error) {
+	if _, ok := vm.loadedCode[main]; !ok {
 		panic("main code not loaded")
 	}
 	delete(vm.loadedCode, main)
-	newWrappedMain := vm.loadCode(main)
-	copy(newWrappedMain.Globals, oldWrappedMain.Globals)
-	return newWrappedMain
+	return vm.loadCode(main)
 }
 
 func (vm *VirtualMachine) initContext(ctx context.Context) context.Context {
+	if vm.logHandler != nil {
+		ctx = object.WithLogHandler(ctx, vm.logHandler)
+	}
+	ctx = object.WithEventRegisterFunc(ctx, vm.registerEventHandler)
+	ctx = object.WithEvalFunc(ctx, vm.evalSource)
 	return object.WithCallFunc(ctx, vm.callFunction)
 }
 
@@ -1675,6 +2222,15 @@ func (vm *VirtualMachine) typeError(format string, args ...any) *object.Structur
 	return vm.runtimeError(object.ErrType, format, args...)
 }
 
+// attrNotFoundError creates a type error for a missing attribute, with a hint
+// naming similar attributes that the object does have.
+func (vm *VirtualMachine) attrNotFoundError(obj object.Object, name string) *object.StructuredError {
+	err := vm.typeError("attribute %q not found on %s object", name, obj.Type())
+	suggestions := object.SuggestSimilar(name, object.AttrNames(obj.Attrs()))
+	err.Hint = object.FormatSuggestions(suggestions)
+	return err
+}
+
 // evalError creates an evaluation error with location and stack trace.
 func (vm *VirtualMachine) evalError(format string, args ...any) *object.StructuredError {
 	return vm.runtimeError(object.ErrRuntime, format, args...)
@@ -1776,8 +2332,9 @@ func (vm *VirtualMachine) handleException(errObj *object.Error) error {
 		handler := excFrame.handler
 
 		// If we have a catch block and we're not already in it, enter catch
-		// When catch completes normally, exception is considered handled
-		if handler.CatchStart > 0 && handler.CatchStart != handler.FinallyStart && !excFrame.inCatch {
+		// When catch completes normally, exception is considered handled.
+		// Cancellation can't be caught, so only finally blocks run for it.
+		if handler.CatchStart > 0 && handler.CatchStart != handler.FinallyStart && !excFrame.inCatch && !vm.cancelling {
 			// If there's a finally block, keep the frame on the stack so that
 			// return statements in catch can trigger the finally block.
 			// EndFinally will pop the frame after finally completes.
@@ -1824,6 +2381,18 @@ func (vm *VirtualMachine) handleException(errObj *object.Error) error {
 	return errObj.Value()
 }
 
+// cancel stops execution after the context is cancelled. By default the
+// context's error is returned right away. With WithFinallyOnCancel, it is
+// raised like an exception instead, so that pending finally blocks run before
+// it propagates. Returns nil if execution continues in a finally block.
+func (vm *VirtualMachine) cancel(ctx context.Context) error {
+	if !vm.finallyOnCancel {
+		return ctx.Err()
+	}
+	vm.cancelling = true
+	return vm.tryHandleError(ctx.Err())
+}
+
 // tryHandleError attempts to handle an error via exception handling.
 // If a handler is found and jumped to, returns nil (exception was handled).
 // If no handler is found, returns the error to propagate up.
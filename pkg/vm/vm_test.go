@@ -2,12 +2,17 @@ package vm
 
 import (
 	"context"
+	"errors"
+	"fmt"
+	"math"
+	"slices"
 	"testing"
 	"time"
 
 	"github.com/deepnoodle-ai/risor/v2/pkg/bytecode"
 	"github.com/deepnoodle-ai/risor/v2/pkg/compiler"
 	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+	"github.com/deepnoodle-ai/risor/v2/pkg/op"
 	"github.com/deepnoodle-ai/risor/v2/pkg/parser"
 	"github.com/deepnoodle-ai/wonton/assert"
 )
@@ -105,6 +110,138 @@ func TestIndexing(t *testing.T) {
 	runTests(t, tests)
 }
 
+func TestSliceStep(t *testing.T) {
+	tests := []struct {
+		input    string
+		expected string
+	}{
+		{`[1, 2, 3, 4][::-1]`, `[4, 3, 2, 1]`},
+		{`[0, 1, 2, 3, 4, 5][1:5:2]`, `[1, 3]`},
+		{`[0, 1, 2, 3, 4, 5][::2]`, `[0, 2, 4]`},
+		{`[0, 1, 2, 3, 4, 5][1::3]`, `[1, 4]`},
+		{`[0, 1, 2, 3, 4, 5][4:1:-1]`, `[4, 3, 2]`},
+		{`[0, 1, 2, 3, 4, 5][-2::-2]`, `[4, 2, 0]`},
+		{`[0, 1, 2, 3, 4, 5][:2:-1]`, `[5, 4, 3]`},
+		{`[0, 1, 2][1:1:-1]`, `[]`},
+		{`[][::-1]`, `[]`},
+		{`[1, 2, 3][::]`, `[1, 2, 3]`},
+		{`let x = [1, 2, 3]; let y = x[::-1]; y[0] = 9; x`, `[1, 2, 3]`},
+		{`"hello"[::-1]`, `"olleh"`},
+		{`"héllo"[1::2]`, `"él"`},
+		{`let s = -1; "abc"[null:null:s]`, `"cba"`},
+	}
+	for _, tt := range tests {
+		t.Run(tt.input, func(t *testing.T) {
+			result, err := run(context.Background(), tt.input)
+			assert.Nil(t, err)
+			assert.Equal(t, result.Inspect(), tt.expected)
+		})
+	}
+}
+
+func TestSliceStepErrors(t *testing.T) {
+	tests := []struct {
+		input string
+		err   string
+	}{
+		{`[1, 2, 3][::0]`, "slice step cannot be zero"},
+		{`[1, 2, 3][::"a"]`, "slice step must be an int (got string)"},
+		{`[1, 2, 3][0:2:-1]`, "start index is less than stop index with a negative step"},
+		{`[1, 2, 3][5::-1]`, "start index is out of range"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.input, func(t *testing.T) {
+			_, err := run(context.Background(), tt.input)
+			assert.NotNil(t, err)
+			assert.Contains(t, err.Error(), tt.err)
+		})
+	}
+}
+
+func TestSliceAssignmentAndDel(t *testing.T) {
+	tests := []struct {
+		input    string
+		expected string
+	}{
+		{`let x = [0, 1, 2, 3]; x[1:3] = ["a", "b", "c"]; x`, `[0, "a", "b", "c", 3]`},
+		{`let x = [0, 1, 2, 3]; x[1:] = []; x`, `[0]`},
+		{`let x = [0, 1, 2, 3]; x[:] = [9]; x`, `[9]`},
+		{`let x = [1, 2, 3]; x[len(x):] = [4]; x`, `[1, 2, 3, 4]`},
+		{`let x = []; x[:] = [1]; [x, x[1:]]`, `[[1], []]`},
+		{`let x = [0, 1, 2, 3]; x[::2] = [8, 9]; x`, `[8, 1, 9, 3]`},
+		{`let x = [0, 1, 2, 3]; x[::-1] = x; x`, `[3, 2, 1, 0]`},
+		{`let x = [1, 2]; let y = x; x[:1] = [5, 6]; y`, `[5, 6, 2]`},
+		{`let x = [1, 2, 3]; let y = x[:]; y[0:1] = []; [x, y]`, `[[1, 2, 3], [2, 3]]`},
+		{`let x = [0, 1, 2]; del x[0]; x`, `[1, 2]`},
+		{`let x = [0, 1, 2]; del x[-1]; x`, `[0, 1]`},
+		{`let m = {a: 1, b: 2}; del m["a"]; m`, `{"b": 2}`},
+		{`let m = {a: 1}; del m["missing"]; m`, `{"a": 1}`},
+		{`let m = {a: [1, 2]}; del m.a[0]; m`, `{"a": [2]}`},
+	}
+	for _, tt := range tests {
+		t.Run(tt.input, func(t *testing.T) {
+			result, err := run(context.Background(), tt.input)
+			assert.Nil(t, err)
+			assert.Equal(t, result.Inspect(), tt.expected)
+		})
+	}
+}
+
+func TestSliceAssignmentAndDelErrors(t *testing.T) {
+	tests := []struct {
+		input string
+		err   string
+	}{
+		{`let x = [1, 2, 3]; x[::2] = [1]`, "cannot assign 1 items to a slice of 2 items with a step of 2"},
+		{`let x = [1, 2, 3]; x[0:1] = 5`, "can only assign a list or tuple to a list slice (got int)"},
+		{`let s = "abc"; s[0:1] = ["x"]`, "set slice is unsupported for string"},
+		{`let x = [1, 2, 3]; del x[5]`, "out of range"},
+		{`let s = "abc"; del s[0]`, "del item is unsupported for string"},
+		{`let n = 1; del n[0]`, "object is not a container (got int)"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.input, func(t *testing.T) {
+			_, err := run(context.Background(), tt.input)
+			assert.NotNil(t, err)
+			assert.Contains(t, err.Error(), tt.err)
+		})
+	}
+}
+
+func TestCollectionOperators(t *testing.T) {
+	tests := []struct {
+		input    string
+		expected string
+	}{
+		{`let m = {a: 1, b: 2} + {b: 3, c: 4}; m`, `{"a": 1, "b": 3, "c": 4}`},
+		{`let m = {a: 1}; let n = m + {}; n.a = 2; m`, `{"a": 1}`},
+		{`let m = {a: 1}; m += {b: 2}; m`, `{"a": 1, "b": 2}`},
+		{`let m = {a: 1, __add__: (x, y) => "added"}; m + {b: 2}`, `"added"`},
+		{`[1, 2] * 3`, `[1, 2, 1, 2, 1, 2]`},
+		{`[1, 2] * 0`, `[]`},
+		{`let x = [[0]] * 2; x[0].append(1); x`, `[[0, 1], [0, 1]]`},
+		{`let x = [1]; x *= 2; x`, `[1, 1]`},
+	}
+	for _, tt := range tests {
+		t.Run(tt.input, func(t *testing.T) {
+			result, err := run(context.Background(), tt.input)
+			assert.Nil(t, err)
+			assert.Equal(t, result.Inspect(), tt.expected)
+		})
+	}
+
+	for input, msg := range map[string]string{
+		`[1] * -1`:           "negative repeat count",
+		`3 * [1]`:            "unsupported operation for int: * on type list",
+		`let m = {} - {}`:    "unsupported operation for map: - on type map",
+		`let m = {a: 1} + 1`: "unsupported operation for map: + on type int",
+	} {
+		_, err := run(context.Background(), input)
+		assert.NotNil(t, err, input)
+		assert.Contains(t, err.Error(), msg)
+	}
+}
+
 func TestStackBehavior3(t *testing.T) {
 	result, err := run(context.Background(), `
 	let x = 77
@@ -489,6 +626,47 @@ func TestMap(t *testing.T) {
 		}))
 }
 
+func TestMapComputedKeys(t *testing.T) {
+	result, err := run(context.Background(), `
+	let k = "x"
+	let m = {[k]: 1, [k + "y"]: 2, ["z"]: 3, ...{w: 4}, [string(5)]: 5}
+	m
+	`)
+	assert.Nil(t, err)
+	assert.Equal(t, result, object.NewMap(map[string]object.Object{
+		"x":  object.NewInt(1),
+		"xy": object.NewInt(2),
+		"z":  object.NewInt(3),
+		"w":  object.NewInt(4),
+		"5":  object.NewInt(5),
+	}))
+}
+
+func TestMapInvalidKeys(t *testing.T) {
+	tests := []struct {
+		input    string
+		expected string
+	}{
+		{`{[1]: "a"}`, "type error: map key must be a string (got int)"},
+		{`{[[1, 2]]: "a"}`, "type error: map key must be a string (got list)"},
+		{`{...{}, [{}]: "a"}`, "type error: map key must be a string (got map)"},
+		{`{[function() {}]: "a"}`, "type error: map key must be a string (got function)"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.input, func(t *testing.T) {
+			_, err := run(context.Background(), tt.input)
+			assert.NotNil(t, err)
+			assert.Contains(t, err.Error(), tt.expected)
+		})
+	}
+}
+
+func TestMapLiteralIntKey(t *testing.T) {
+	_, err := run(context.Background(), `{1: "a"}`)
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), `map key must be a string (got 1); use a string key like "1" or a computed key [1]`)
+}
+
 func TestNonLocal(t *testing.T) {
 	result, err := run(context.Background(), `
 	let y = 3
@@ -650,6 +828,68 @@ func TestRecursiveExample2(t *testing.T) {
 	assert.Equal(t, result, object.NewInt(16))
 }
 
+func TestNamedFunctionExpression(t *testing.T) {
+	tests := []testCase{
+		{`
+		let f = function fact(n) {
+			if (n <= 1) { return 1 }
+			return n * fact(n - 1)
+		}
+		f(5)
+		`, object.NewInt(120)},
+		{`
+		let fact = "outer"
+		let f = function fact(n) {
+			if (n <= 1) { return 1 }
+			return n * fact(n - 1)
+		}
+		let results = [f(4), fact]
+		results
+		`, object.NewList([]object.Object{object.NewInt(24), object.NewString("outer")})},
+		{`
+		function make() {
+			let f = function loop(n, acc) {
+				if (n == 0) { return acc }
+				return loop(n - 1, acc + n)
+			}
+			return f
+		}
+		let g = make()
+		g(4, 0)
+		`, object.NewInt(10)},
+		{`
+		[3, 4].map(function fib(n) {
+			if (n < 2) { return n }
+			return fib(n - 1) + fib(n - 2)
+		})
+		`, object.NewList([]object.Object{object.NewInt(2), object.NewInt(3)})},
+		{`
+		let f = function count({n}) {
+			if (n == 0) { return 0 }
+			return 1 + count({n: n - 1})
+		}
+		f({n: 3})
+		`, object.NewInt(3)},
+		{`
+		function count([n], ...rest) {
+			if (n == 0) { return len(rest) }
+			return count([n - 1], ...rest)
+		}
+		count([3], "a", "b")
+		`, object.NewInt(2)},
+	}
+	runTests(t, tests)
+}
+
+func TestNamedFunctionExpressionScope(t *testing.T) {
+	_, err := run(context.Background(), `
+	let f = function fact(n) { n }
+	fact(1)
+	`)
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "undefined variable \"fact\"")
+}
+
 func TestConstant(t *testing.T) {
 	_, err := run(context.Background(), `const x = 1; x = 2`)
 	assert.NotNil(t, err)
@@ -748,6 +988,37 @@ func TestArithmetic(t *testing.T) {
 	runTests(t, tests)
 }
 
+func TestSpecializedIntOperations(t *testing.T) {
+	// The same instructions see ints and then other types
+	fns := `
+	function add(a, b) { return a + b }
+	function lt(a, b) { return a < b }
+	`
+	tests := []testCase{
+		{fns + `[add(1, 2), add(1.5, 2), add("a", "b"), add(3, 4)]`, object.NewList([]object.Object{
+			object.NewInt(3), object.NewFloat(3.5), object.NewString("ab"), object.NewInt(7),
+		})},
+		{fns + `[lt(1, 2), lt("b", "a"), lt(3, 2), lt(1.5, 2)]`, object.NewList([]object.Object{
+			object.True, object.False, object.False, object.True,
+		})},
+		{fns + `add(1, 2); try { add(1, null) } catch (e) { "caught" }`, object.NewString("caught")},
+		{fns + `add(1, 2); add({__add__: (a, b) => "overloaded"}, 1)`, object.NewString("overloaded")},
+	}
+	runTests(t, tests)
+
+	// Executing an operation on ints specializes the instruction
+	ast, err := parser.Parse(context.Background(), `let x = 1; let y = x + 2; y < 10`, nil)
+	assert.Nil(t, err)
+	main, err := compiler.Compile(ast, nil)
+	assert.Nil(t, err)
+	machine, err := New(main)
+	assert.Nil(t, err)
+	assert.Nil(t, machine.Run(context.Background()))
+	instructions := machine.loadedCode[main].Instructions
+	assert.True(t, slices.Contains(instructions, op.BinaryOpInt))
+	assert.True(t, slices.Contains(instructions, op.CompareOpInt))
+}
+
 func TestNumericComparisons(t *testing.T) {
 	tests := []testCase{
 		// Integers
@@ -793,6 +1064,29 @@ func TestNumericComparisons(t *testing.T) {
 	runTests(t, tests)
 }
 
+func TestDecimalArithmetic(t *testing.T) {
+	tests := []testCase{
+		{`string(decimal("0.1") + decimal("0.2"))`, object.NewString("0.3")},
+		{`string(decimal("19.99") * 3)`, object.NewString("59.97")},
+		{`string(1 - decimal("0.25"))`, object.NewString("0.75")},
+		{`string(-decimal("1.50"))`, object.NewString("-1.50")},
+		{`string(decimal("10") / 4)`, object.NewString("2.5")},
+		{`string(decimal("2.675").round(2))`, object.NewString("2.68")},
+		{`decimal("0.5") == 0.5`, object.True},
+		{`decimal("2.00") == 2`, object.True},
+		{`decimal("0.1") == 0.1`, object.False},
+		{`decimal("1.5") < 2`, object.True},
+		{`type(decimal("1"))`, object.NewString("decimal")},
+		{`let total = decimal("0"); [decimal("1.10"), decimal("2.20")].each(x => { total = total + x }); string(total)`,
+			object.NewString("3.30")},
+	}
+	runTests(t, tests)
+
+	_, err := run(context.Background(), `decimal("1.5") + 1.5`)
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "unsupported operation for decimal")
+}
+
 func TestBooleans(t *testing.T) {
 	tests := []testCase{
 		{`true`, object.True},
@@ -834,6 +1128,51 @@ func TestTruthiness(t *testing.T) {
 	runTests(t, tests)
 }
 
+// TestTruthinessAgrees checks that every way of testing a value's truthiness
+// gives the same answer.
+func TestTruthinessAgrees(t *testing.T) {
+	tests := []struct {
+		value  string
+		truthy bool
+	}{
+		{`null`, false},
+		{`false`, false},
+		{`0`, false},
+		{`0.0`, false},
+		{`byte(0)`, false},
+		{`decimal("0.00")`, false},
+		{`""`, false},
+		{`bytes("")`, false},
+		{`[]`, false},
+		{`{}`, false},
+		{`range(0)`, false},
+		{`true`, true},
+		{`-1`, true},
+		{`0.5`, true},
+		{`float("nan")`, true},
+		{`decimal("0.01")`, true},
+		{`"0"`, true},
+		{`[null]`, true},
+		{`{a: null}`, true},
+		{`range(3)`, true},
+		{`len`, true},
+		{`(x => x)`, true},
+		{`error("failed")`, true},
+	}
+	ctx := context.Background()
+	for _, tt := range tests {
+		t.Run(tt.value, func(t *testing.T) {
+			result, err := run(ctx, fmt.Sprintf(`
+let v = %s;
+[bool(v), !!v, if (v) { true } else { false }, match 1 { 1 if v => true, _ => false }, any([v]), all([v])]
+`, tt.value))
+			assert.Nil(t, err)
+			want := object.NewBool(tt.truthy)
+			assert.Equal(t, result, object.NewList([]object.Object{want, want, want, want, want, want}))
+		})
+	}
+}
+
 func TestControlFlow(t *testing.T) {
 	tests := []testCase{
 		{`if (false) { 3 }`, object.Nil},
@@ -1011,6 +1350,87 @@ func TestMultiVarAssignment(t *testing.T) {
 	runTests(t, tests)
 }
 
+func TestMultiAssign(t *testing.T) {
+	tests := []testCase{
+		{`let a = 1; let b = 2; a, b = b, a; [a, b]`, object.NewList([]object.Object{object.NewInt(2), object.NewInt(1)})},
+		{`let a, b, c = 1, 2, 3; a, b, c = c, a, b; [a, b, c]`,
+			object.NewList([]object.Object{object.NewInt(3), object.NewInt(1), object.NewInt(2)})},
+		{`let a, b = 0, 0; a, b = [5, 6]; a + b`, object.NewInt(11)},
+		{`let a, b = 0, 0; _, b = "xy"; [a, b]`, object.NewList([]object.Object{object.NewInt(0), object.NewString("y")})},
+		{`let x = 1; let y = 1; x = y = 5; [x, y]`, object.NewList([]object.Object{object.NewInt(5), object.NewInt(5)})},
+		{`let x = 0; let y = 0; let z = 0; x = y = z = [1]; x.append(2); z`,
+			object.NewList([]object.Object{object.NewInt(1), object.NewInt(2)})},
+		{`let a, b = 1, 2; function swap() { a, b = b, a }; swap(); [a, b]`,
+			object.NewList([]object.Object{object.NewInt(2), object.NewInt(1)})},
+		{`function f() { let lo, hi = 1, 9; lo, hi = hi, lo; return hi - lo }; f()`, object.NewInt(-8)},
+	}
+	runTests(t, tests)
+}
+
+func TestMultiAssignErrors(t *testing.T) {
+	tests := []struct {
+		input string
+		err   string
+	}{
+		{`let a = 1; a, b = 1, 2`, `undefined variable "b"`},
+		{`const a = 1; let b = 2; a, b = b, a`, `cannot assign to constant "a"`},
+		{`let x = 1; const y = 2; x = y = 3`, `cannot assign to constant "y"`},
+		{`let a, b = 1, 2; a, b = 1, 2, 3`, "unpack count mismatch"},
+		{`function f() { return 1, 2 }; let a, b, c = 0, 0, 0; a, b, c = f()`,
+			`function "f" returns 2 values but 3 variables were given`},
+	}
+	for _, tt := range tests {
+		t.Run(tt.input, func(t *testing.T) {
+			_, err := run(context.Background(), tt.input)
+			assert.NotNil(t, err)
+			assert.Contains(t, err.Error(), tt.err)
+		})
+	}
+}
+
+func TestMultipleReturnValues(t *testing.T) {
+	tests := []testCase{
+		{`function f() { return 1, 2 }; let a, b = f(); a`, object.NewInt(1)},
+		{`function f() { return 1, 2 }; let a, b = f(); b`, object.NewInt(2)},
+		{`function f(x) { if (x) { return "y", 1 }; return "n", 0 }; let s, n = f(false); s`, object.NewString("n")},
+		{`let f = function() { return 1, 2, 3 }; let a, b, c = f(); a + b + c`, object.NewInt(6)},
+		{`function f() { return 1, 2 }; let _, b = f(); b`, object.NewInt(2)},
+		{`function f() { return 1, 2 }; f()`, object.NewTuple([]object.Object{object.NewInt(1), object.NewInt(2)})},
+		{`function f() { return 1, 2 }; f()[1]`, object.NewInt(2)},
+		{`function f() { return 1, 2 }; type(f())`, object.NewString("tuple")},
+		{`function f() { return 1, 2 }; len(f())`, object.NewInt(2)},
+		{`function f() { return 1, 2 }; [...f()]`, object.NewList([]object.Object{object.NewInt(1), object.NewInt(2)})},
+	}
+	runTests(t, tests)
+}
+
+func TestMultipleReturnValuesArityMismatch(t *testing.T) {
+	// Known statically: compile error
+	_, err := run(context.Background(), `
+	function f() { return 1, 2 }
+	let a, b, c = f()
+	`)
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), `function "f" returns 2 values but 3 variables were given`)
+
+	// Only known at runtime: value error
+	_, err = run(context.Background(), `
+	let f = function() { return 1, 2 }
+	let a, b, c = f()
+	`)
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "unpack count mismatch: expected 3 values, got 2")
+
+	// Tuples are immutable
+	_, err = run(context.Background(), `
+	function f() { return 1, 2 }
+	let t = f()
+	t[0] = 5
+	`)
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "tuple does not support item assignment")
+}
+
 func TestFunctions(t *testing.T) {
 	tests := []testCase{
 		{`function add(x, y) { x + y }; add(3, 4)`, object.NewInt(7)},
@@ -1152,6 +1572,15 @@ func TestNullishCoalescing(t *testing.T) {
 		// Comparison with OR (different behavior)
 		{`0 || 42`, object.NewInt(42)}, // OR uses truthiness
 		{`0 ?? 42`, object.NewInt(0)},  // ?? only checks nil
+		// The right side is only evaluated when needed
+		{`let calls = 0; let f = () => { calls++; return 1 }; 5 ?? f(); calls`, object.NewInt(0)},
+		{`let calls = 0; let f = () => { calls++; return 1 }; nil ?? f(); calls`, object.NewInt(1)},
+		// Precedence relative to other operators
+		{`nil ?? 1 + 2`, object.NewInt(3)},
+		{`(nil ?? false) || "x"`, object.NewString("x")},
+		// Stack stays balanced when used repeatedly
+		{`let total = 0; [nil, 1, nil, 2].each(x => { total += x ?? 10 }); total`, object.NewInt(23)},
+		{`[nil ?? 1, 2 ?? 3, nil ?? nil]`, object.NewList([]object.Object{object.NewInt(1), object.NewInt(2), object.Nil})},
 	}
 	runTests(t, tests)
 }
@@ -1176,10 +1605,32 @@ func TestSpreadOperator(t *testing.T) {
 		},
 		// Spread with string concatenation
 		{`function join(a, b) { return a + b }; let items = ["a", "b"]; join(...items)`, object.NewString("ab")},
+		// Non-spread list arguments are passed intact
+		{
+			`function f(a, b, c) { return [a, b, c] }; let x = [1, 2]; let y = [3, 4]; f(x, ...y)`,
+			object.NewList([]object.Object{
+				object.NewList([]object.Object{object.NewInt(1), object.NewInt(2)}),
+				object.NewInt(3), object.NewInt(4),
+			}),
+		},
+		{`function f(a, ...rest) { return len(a) + len(rest) }; let x = [1, 2, 3]; f(x, ...x)`, object.NewInt(6)},
+		// Method call spread
+		{`let parts = ["-"]; "a-b-c".split(...parts)`, object.NewList([]object.Object{
+			object.NewString("a"), object.NewString("b"), object.NewString("c"),
+		})},
+		{`let m = {f: function(a, b) { return [a, b] }}; let x = [1]; m.f(x, ...x)`, object.NewList([]object.Object{
+			object.NewList([]object.Object{object.NewInt(1)}), object.NewInt(1),
+		})},
 	}
 	runTests(t, tests)
 }
 
+func TestSpreadOutsideCall(t *testing.T) {
+	_, err := run(context.Background(), `let a = [1]; let b = ...a`)
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "spread is only allowed in list literals, map literals, and call arguments")
+}
+
 func TestRestParameter(t *testing.T) {
 	tests := []testCase{
 		// Rest with regular params
@@ -1194,6 +1645,28 @@ func TestRestParameter(t *testing.T) {
 		{`function test(...args) { return args }; test()`, object.NewList([]object.Object{})},
 		// Rest collects all remaining
 		{`function test(a, b, ...rest) { return len(rest) }; test(1, 2, 3, 4, 5)`, object.NewInt(3)},
+		// Defaults are filled before the rest param
+		{
+			`function foo(a, b=10, ...rest) { return [a, b, rest] }; foo(1)`,
+			object.NewList([]object.Object{
+				object.NewInt(1),
+				object.NewInt(10),
+				object.NewList([]object.Object{}),
+			}),
+		},
+		{
+			`function foo(a, b=10, ...rest) { return [a, b, rest] }; foo(1, 2, 3)`,
+			object.NewList([]object.Object{
+				object.NewInt(1),
+				object.NewInt(2),
+				object.NewList([]object.Object{object.NewInt(3)}),
+			}),
+		},
+		// The rest list does not alias a spread argument list
+		{
+			`let xs = [1, 2, 3]; function f(a, ...rest) { rest[0] = 99 }; f(...xs); xs`,
+			object.NewList([]object.Object{object.NewInt(1), object.NewInt(2), object.NewInt(3)}),
+		},
 	}
 	runTests(t, tests)
 }
@@ -1336,10 +1809,40 @@ func TestOptionalChaining(t *testing.T) {
 		// With nullish coalescing
 		{`let obj = nil; obj?.name ?? "default"`, object.NewString("default")},
 		{`let obj = { name: "test" }; obj?.name ?? "default"`, object.NewString("test")},
+		// Missing attribute on a non-nil value
+		{`let obj = {}; obj?.name`, object.Nil},
+		// Optional indexing
+		{`let items = [1, 2]; items?.[1]`, object.NewInt(2)},
+		{`let items = nil; items?.[0]`, object.Nil},
+		{`let obj = { items: [1, 2] }; obj?.items?.[0]`, object.NewInt(1)},
+		{`let obj = nil; obj?.items?.[0]`, object.Nil},
+		{`let m = nil; m?.["a"] ?? "none"`, object.NewString("none")},
+		// The index is not evaluated when the value is nil
+		{`let calls = 0; let f = () => { calls++; return 0 }; let items = nil; items?.[f()]; calls`, object.NewInt(0)},
+		// Chains through method calls
+		{`let s = nil; s?.to_upper()?.to_lower() ?? "none"`, object.NewString("none")},
+		{`let s = "Hi"; s?.to_upper()?.to_lower() ?? "none"`, object.NewString("hi")},
+		{`nil?.a?.b?.c ?? "x"`, object.NewString("x")},
+		// Results are usable in larger expressions
+		{`let obj = nil; [obj?.a ?? 1, obj?.[0] ?? 2]`, object.NewList([]object.Object{object.NewInt(1), object.NewInt(2)})},
 	}
 	runTests(t, tests)
 }
 
+func TestOptionalChainingErrors(t *testing.T) {
+	ctx := context.Background()
+	// Only a nil value short-circuits; other failures still raise errors
+	for _, source := range []string{
+		`let items = [1]; items?.[5]`,
+		`let n = 1; n?.[0]`,
+		`let s = "hi"; s?.missing()`,
+		`let obj = nil; obj?.a.b`,
+	} {
+		_, err := run(ctx, source)
+		assert.NotNil(t, err, source)
+	}
+}
+
 func TestManyLocals(t *testing.T) {
 	result, err := run(context.Background(), `
 	function example(x) {
@@ -1376,6 +1879,10 @@ func TestIncorrectArgCount(t *testing.T) {
 		{`function ex(x, y) { 1 }; ex(0)`, "args error: function \"ex\" takes 2 arguments (1 given)"},
 		{`function ex(x, y) { 1 }; ex(1, 2, 3)`, "args error: function \"ex\" takes 2 arguments (3 given)"},
 		{`function ex() { 1 }; [1, 2].filter(ex)`, "args error: function \"ex\" takes 0 arguments (1 given)"},
+		{`function ex(x, y=1) { 1 }; ex()`, "args error: function \"ex\" takes at least 1 argument (0 given)"},
+		{`function ex(x, y=1, z=2) { 1 }; ex(1, 2, 3, 4)`, "args error: function \"ex\" takes at most 3 arguments (4 given)"},
+		{`function ex(a, b, ...rest) { 1 }; ex(1)`, "args error: function \"ex\" takes at least 2 arguments (1 given)"},
+		{`let f = function(x) { x }; f()`, "args error: function takes 1 argument (0 given)"},
 		{`function ex() { 1 }; "foo" |> ex`, "args error: function \"ex\" takes 0 arguments (1 given)"},
 		{`"foo" |> "bar"`, "type error: object is not callable (got string)"},
 	}
@@ -1463,6 +1970,246 @@ func TestGetGlobal(t *testing.T) {
 	assert.Equal(t, fn.Name(), "inc")
 }
 
+func TestUndefinedNames(t *testing.T) {
+	ctx := context.Background()
+	compile := func(source string, mode compiler.UndefinedMode) *bytecode.Code {
+		program, err := parser.Parse(ctx, source, nil)
+		assert.Nil(t, err)
+		code, err := compiler.Compile(program, &compiler.Config{UndefinedNames: mode})
+		assert.Nil(t, err)
+		return code
+	}
+	globals := basicBuiltins()
+	globals["values"] = []any{1, 2, 3}
+	globals["bonus"] = 10
+
+	result, err := Run(ctx, compile(`
+	function total() { return len(values) + bonus }
+	let caught = try { missing } catch e { e.kind() }
+	[total(), caught]
+	`, compiler.UndefinedLateBound), WithGlobals(globals))
+	assert.Nil(t, err)
+	assert.Equal(t, result, object.NewList([]object.Object{
+		object.NewInt(13), object.NewString("name error"),
+	}))
+
+	_, err = Run(ctx, compile(`missing + 1`, compiler.UndefinedLateBound), WithGlobals(globals))
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), `undefined variable "missing"`)
+
+	result, err = Run(ctx, compile(`[missing, bonus]`, compiler.UndefinedNil), WithGlobals(globals))
+	assert.Nil(t, err)
+	assert.Equal(t, result, object.NewList([]object.Object{object.Nil, object.NewInt(10)}))
+}
+
+func TestSetGlobal(t *testing.T) {
+	ctx := context.Background()
+	source := `let count = 1; function get() { count }`
+	vm, err := newVM(ctx, source)
+	assert.Nil(t, err)
+	assert.Nil(t, vm.Run(ctx))
+
+	assert.Nil(t, vm.Set("count", object.NewInt(5)))
+	obj, err := vm.Get("get")
+	assert.Nil(t, err)
+	result, err := vm.Call(ctx, obj.(*object.Closure), nil)
+	assert.Nil(t, err)
+	assert.Equal(t, result, object.NewInt(5))
+
+	err = vm.Set("missing", object.NewInt(1))
+	assert.True(t, errors.Is(err, ErrGlobalNotFound))
+}
+
+func TestFunctionFromAnotherVM(t *testing.T) {
+	ctx := context.Background()
+	lib, err := newVM(ctx, `let n = 10; function get() { n }; function set(v) { n = v }`)
+	assert.Nil(t, err)
+	assert.Nil(t, lib.Run(ctx))
+	get, err := lib.Get("get")
+	assert.Nil(t, err)
+	set, err := lib.Get("set")
+	assert.Nil(t, err)
+
+	// The functions use the globals of the VM that defined them, even where
+	// the calling VM has a global of the same name
+	result, err := run(ctx, `let n = 1; set(5); [n, get()]`, runOpts{
+		Globals: map[string]any{"get": get, "set": set},
+	})
+	assert.Nil(t, err)
+	assert.Equal(t, result, object.NewList([]object.Object{object.NewInt(1), object.NewInt(5)}))
+	n, err := lib.Get("n")
+	assert.Nil(t, err)
+	assert.Equal(t, n, object.NewInt(5))
+}
+
+func TestTooManyGlobals(t *testing.T) {
+	ctx := context.Background()
+	program, err := parser.Parse(ctx, `let extra = 1; extra`, nil)
+	assert.Nil(t, err)
+	code, err := compiler.Compile(program, nil)
+	assert.Nil(t, err)
+	globals := map[string]any{}
+	for i := range math.MaxUint16 + 1 {
+		globals[fmt.Sprintf("g%d", i)] = i
+	}
+	_, err = Run(ctx, code, WithGlobals(globals))
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "too many globals")
+}
+
+func TestExports(t *testing.T) {
+	ctx := context.Background()
+	source := `
+	export let count = 1
+	let hidden = 2
+	export function inc() { count++ }
+	inc()
+	`
+	vm, err := newVM(ctx, source)
+	assert.Nil(t, err)
+	assert.Nil(t, vm.Run(ctx))
+
+	exports, err := vm.Exports()
+	assert.Nil(t, err)
+	assert.Len(t, exports, 2)
+	assert.Equal(t, exports["count"], object.NewInt(2))
+	_, ok := exports["inc"].(*object.Closure)
+	assert.True(t, ok)
+}
+
+func TestImport(t *testing.T) {
+	ctx := context.Background()
+	source := `
+	import "./lib/helpers"
+	import "shared/math" as m
+	helpers.greet("x") + string(m.double(2))
+	`
+	var imported []string
+	importer := ImporterFunc(func(ctx context.Context, path, from string) (*object.Module, error) {
+		imported = append(imported, path)
+		switch path {
+		case "./lib/helpers":
+			return object.NewBuiltinsModule("helpers", map[string]object.Object{
+				"greet": object.NewBuiltin("greet", func(ctx context.Context, args ...object.Object) (object.Object, error) {
+					return object.NewString("hi " + args[0].(*object.String).Value()), nil
+				}),
+			}), nil
+		case "shared/math":
+			return object.NewBuiltinsModule("math", map[string]object.Object{
+				"double": object.NewBuiltin("double", func(ctx context.Context, args ...object.Object) (object.Object, error) {
+					return object.NewInt(args[0].(*object.Int).Value() * 2), nil
+				}),
+			}), nil
+		}
+		return nil, fmt.Errorf("module not found")
+	})
+	vm, err := newVM(ctx, source)
+	assert.Nil(t, err)
+	WithImporter(importer)(vm)
+	assert.Nil(t, vm.Run(ctx))
+	result, exists := vm.TOS()
+	assert.True(t, exists)
+	assert.Equal(t, result, object.NewString("hi x4"))
+	assert.Equal(t, imported, []string{"./lib/helpers", "shared/math"})
+}
+
+func TestImportErrors(t *testing.T) {
+	ctx := context.Background()
+
+	// Imports are disabled by default
+	_, err := run(ctx, `import "./lib/helpers"`)
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), `cannot import "./lib/helpers": imports are not enabled`)
+
+	// Importer errors are raised as import errors
+	vm, err := newVM(ctx, `import "missing"`)
+	assert.Nil(t, err)
+	WithImporter(ImporterFunc(func(ctx context.Context, path, from string) (*object.Module, error) {
+		return nil, fmt.Errorf("module not found")
+	}))(vm)
+	err = vm.Run(ctx)
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), `cannot import "missing": module not found`)
+}
+
+func TestCallMethod(t *testing.T) {
+	tests := []testCase{
+		{`[3, 1, 2].sort().map(x => x * 2)`, object.NewList([]object.Object{
+			object.NewInt(2), object.NewInt(4), object.NewInt(6),
+		})},
+		{`{add: x => x + 1}.add(1)`, object.NewInt(2)},
+		{`{keys: () => "shadowed"}.keys()`, object.NewList([]object.Object{object.NewString("keys")})},
+		{`let m = {get: 1}; m.get("get")`, object.NewInt(1)},
+		{`let x = nil; x?.upper()`, object.Nil},
+		{`let args = [","]; "a,b".split(...args)`, object.NewList([]object.Object{
+			object.NewString("a"), object.NewString("b"),
+		})},
+	}
+	runTests(t, tests)
+}
+
+func TestListSorting(t *testing.T) {
+	tests := []testCase{
+		{`[1, 3, 2].sort((a, b) => a > b)`, object.NewList([]object.Object{
+			object.NewInt(3), object.NewInt(2), object.NewInt(1),
+		})},
+		{`let people = [{name: "b", age: 30}, {name: "a", age: 25}, {name: "c", age: 30}]
+		  people.sort_by(p => p.age).map(p => p.name)`, object.NewList([]object.Object{
+			object.NewString("a"), object.NewString("b"), object.NewString("c"),
+		})},
+		{`try { [2, "a", 1].sort() } catch (e) { "caught" }`, object.NewString("caught")},
+		{`try { [2, 1].sort_by(x => [x, "a"][x - 1]) } catch (e) { "caught" }`, object.NewString("caught")},
+	}
+	runTests(t, tests)
+}
+
+func TestFreeze(t *testing.T) {
+	tests := []testCase{
+		{`let c = freeze({a: [1]}); try { c.a.append(2) } catch (e) { "caught" }`, object.NewString("caught")},
+		{`let c = freeze({a: 1}); try { c.a = 2 } catch (e) { "caught" }`, object.NewString("caught")},
+		{`let c = freeze({a: 1}); try { c["b"] = 2 } catch (e) { "caught" }`, object.NewString("caught")},
+		{`let c = freeze([1, 2]); try { c[0] = 2 } catch (e) { "caught" }`, object.NewString("caught")},
+		{`let c = freeze({a: [1]}); let d = copy(c); d.a.append(2); [len(c.a), len(d.a)]`,
+			object.NewList([]object.Object{object.NewInt(1), object.NewInt(2)})},
+	}
+	runTests(t, tests)
+
+	_, err := run(context.Background(), `let c = freeze([[1]]); c[0].pop(0)`)
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "cannot modify a frozen list")
+}
+
+func TestCallMethodErrors(t *testing.T) {
+	ctx := context.Background()
+	_, err := run(ctx, `[].append()`)
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "list.append: expected 1 argument, got 0")
+
+	_, err = run(ctx, `range(3).start()`)
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "object is not callable (got int)")
+
+	_, err = run(ctx, `let n = 1; n.missing()`)
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), `attribute "missing" not found on int object`)
+}
+
+func TestAttrNotFoundHint(t *testing.T) {
+	ctx := context.Background()
+	for _, source := range []string{`[1].apend(2)`, `let f = [1].apend`} {
+		_, err := run(ctx, source)
+		var structured *object.StructuredError
+		assert.True(t, errors.As(err, &structured))
+		assert.Contains(t, structured.Hint, "'append'")
+		assert.Contains(t, structured.FriendlyErrorMessage(), "hint: Did you mean")
+	}
+
+	_, err := run(ctx, `let n = 1; n.missing()`)
+	var structured *object.StructuredError
+	assert.True(t, errors.As(err, &structured))
+	assert.Equal(t, structured.Hint, "")
+}
+
 func TestCall(t *testing.T) {
 	ctx := context.Background()
 	source := `function inc(a, b) { a + b }`
@@ -2299,6 +3046,78 @@ func TestNewEmpty(t *testing.T) {
 	assert.Equal(t, int64(30), intResult.Value())
 }
 
+func TestRunCodeLinksGlobalsByName(t *testing.T) {
+	ctx := context.Background()
+	compile := func(source string, globalNames []string) *bytecode.Code {
+		ast, err := parser.Parse(ctx, source, nil)
+		assert.NoError(t, err)
+		code, err := compiler.Compile(ast, &compiler.Config{GlobalNames: globalNames})
+		assert.NoError(t, err)
+		return code
+	}
+
+	vm, err := NewEmpty()
+	assert.NoError(t, err)
+	assert.NoError(t, vm.RunCode(ctx, compile(`
+		let counter = 0
+		let a = 1
+		function bump(n) { counter += n; return counter }
+	`, nil)))
+
+	// The compiler sorts the names it's given, so the globals of the first
+	// script have different indices here. Calling bump must still update the
+	// same counter.
+	assert.NoError(t, vm.RunCode(ctx, compile(`
+		let total = bump(5)
+		counter = counter + a
+		total
+	`, vm.GlobalNames())))
+	result, ok := vm.TOS()
+	assert.True(t, ok)
+	assert.Equal(t, result, object.NewInt(5))
+	counter, err := vm.Get("counter")
+	assert.NoError(t, err)
+	assert.Equal(t, counter, object.NewInt(6))
+
+	// A third script sees the values written by both earlier scripts
+	assert.NoError(t, vm.RunCode(ctx, compile(`bump(10) + total`, vm.GlobalNames())))
+	result, ok = vm.TOS()
+	assert.True(t, ok)
+	assert.Equal(t, result, object.NewInt(21))
+	assert.Equal(t, slices.Sorted(slices.Values(vm.GlobalNames())),
+		[]string{"a", "bump", "counter", "total"})
+
+	// Globals from earlier scripts can be set by name
+	assert.NoError(t, vm.Set("a", object.NewInt(100)))
+	assert.NoError(t, vm.RunCode(ctx, compile(`a + counter`, vm.GlobalNames())))
+	result, ok = vm.TOS()
+	assert.True(t, ok)
+	assert.Equal(t, result, object.NewInt(116))
+}
+
+func TestRunCodeWithGlobalsOverride(t *testing.T) {
+	ctx := context.Background()
+	vm, err := newVM(ctx, `x = x + 1`, runOpts{Globals: map[string]any{"x": 1}})
+	assert.NoError(t, err)
+	assert.NoError(t, vm.Run(ctx))
+	x, err := vm.Get("x")
+	assert.NoError(t, err)
+	assert.Equal(t, x, object.NewInt(2))
+
+	// Values changed by a script are kept unless new values are provided
+	ast, err := parser.Parse(ctx, `x * 10`, nil)
+	assert.NoError(t, err)
+	code, err := compiler.Compile(ast, &compiler.Config{GlobalNames: vm.GlobalNames()})
+	assert.NoError(t, err)
+	assert.NoError(t, vm.RunCode(ctx, code))
+	result, _ := vm.TOS()
+	assert.Equal(t, result, object.NewInt(20))
+
+	assert.NoError(t, vm.RunCode(ctx, code, WithGlobals(map[string]any{"x": 7})))
+	result, _ = vm.TOS()
+	assert.Equal(t, result, object.NewInt(70))
+}
+
 func TestArrowFunctions(t *testing.T) {
 	tests := []struct {
 		name     string
@@ -2660,3 +3479,268 @@ func TestTimeout(t *testing.T) {
 	assert.NotNil(t, err)
 	assert.Equal(t, err, context.DeadlineExceeded)
 }
+
+func TestFinallyOnCancel(t *testing.T) {
+	ctx := context.Background()
+	source := `
+	let sum = 0
+	try {
+		try {
+			list(range(1000000)).each(function(i) { sum = sum + i })
+		} catch (e) {
+			record("catch")
+		}
+	} finally {
+		record("finally")
+	}
+	`
+	ast, err := parser.Parse(ctx, source, nil)
+	assert.Nil(t, err)
+
+	var calls []string
+	globals := basicBuiltins()
+	globals["record"] = object.NewBuiltin("record", func(ctx context.Context, args ...object.Object) (object.Object, error) {
+		calls = append(calls, args[0].(*object.String).Value())
+		return object.Nil, nil
+	})
+	var globalNames []string
+	for k := range globals {
+		globalNames = append(globalNames, k)
+	}
+	main, err := compiler.Compile(ast, &compiler.Config{GlobalNames: globalNames})
+	assert.Nil(t, err)
+
+	// By default, cancellation stops the script right away
+	vm, err := New(main, WithGlobals(globals), WithTimeout(5*time.Millisecond))
+	assert.Nil(t, err)
+	err = vm.Run(ctx)
+	assert.Equal(t, err, context.DeadlineExceeded)
+	assert.Len(t, calls, 0)
+
+	// Finally blocks run, but catch blocks don't see the cancellation
+	vm, err = New(main, WithGlobals(globals), WithTimeout(5*time.Millisecond), WithFinallyOnCancel())
+	assert.Nil(t, err)
+	err = vm.Run(ctx)
+	assert.Equal(t, err, context.DeadlineExceeded)
+	assert.Equal(t, calls, []string{"finally"})
+}
+
+func TestOperatorOverloading(t *testing.T) {
+	vec := `
+	function vec(x, y) {
+		return {
+			x: x,
+			y: y,
+			__add__: (a, b) => vec(a.x + b.x, a.y + b.y),
+			__mul__: (a, k) => vec(a.x * k, a.y * k),
+			__eq__: (a, b) => type(b) == "map" && a.x == b.x && a.y == b.y,
+			__lt__: (a, b) => a.x * a.x + a.y * a.y < b.x * b.x + b.y * b.y,
+			__str__: (a) => "vec(" + string(a.x) + ", " + string(a.y) + ")",
+		}
+	}
+	`
+	tests := []testCase{
+		{vec + `let v = vec(1, 2) + vec(3, 4); [v.x, v.y]`,
+			object.NewList([]object.Object{object.NewInt(4), object.NewInt(6)})},
+		{vec + `let v = vec(1, 2) * 3; [v.x, v.y]`,
+			object.NewList([]object.Object{object.NewInt(3), object.NewInt(6)})},
+		{vec + `let v = vec(1, 1); v += vec(1, 1); v.x`, object.NewInt(2)},
+		{vec + `vec(1, 2) == vec(1, 2)`, object.True},
+		{vec + `vec(1, 2) != vec(1, 2)`, object.False},
+		{vec + `vec(1, 2) == 3`, object.False},
+		{vec + `3 == vec(1, 2)`, object.False},
+		{vec + `[vec(1, 1) < vec(2, 2), vec(1, 1) > vec(2, 2)]`,
+			object.NewList([]object.Object{object.True, object.False})},
+		{vec + `[vec(1, 1) <= vec(1, 1), vec(1, 1) >= vec(2, 2)]`,
+			object.NewList([]object.Object{object.True, object.False})},
+		{vec + `string(vec(1, 2))`, object.NewString("vec(1, 2)")},
+		{vec + "`v = ${vec(1, 2)}`", object.NewString("v = vec(1, 2)")},
+		// Maps without dunder members keep their default behavior
+		{`let m = {a: 1}; m == {a: 1}`, object.True},
+	}
+	runTests(t, tests)
+}
+
+func TestOperatorOverloadingErrors(t *testing.T) {
+	ctx := context.Background()
+
+	_, err := run(ctx, "let x = {__str__: (a) => 42}; `${x}`")
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "__str__ must return a string (got int)")
+
+	_, err = run(ctx, `let x = {__add__: function(a, b) { throw "no" }}; x + 1`)
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "no")
+
+	// Only the left operand is consulted for arithmetic operators
+	_, err = run(ctx, `let x = {__add__: (a, b) => 1}; 1 + x`)
+	assert.NotNil(t, err)
+
+	// Orderings require __lt__ on one of the operands
+	_, err = run(ctx, `let x = {a: 1}; x > 1`)
+	assert.NotNil(t, err)
+}
+
+func TestOperatorOverloadingOrderingEitherSide(t *testing.T) {
+	num := `
+	function value(x) {
+		if (type(x) == "map") {
+			return x.n
+		}
+		return x
+	}
+	function num(n) {
+		return {
+			n: n,
+			__eq__: (a, b) => a.n == value(b),
+			__lt__: (a, b) => a.n < value(b),
+		}
+	}
+	`
+	bools := func(values ...bool) object.Object {
+		items := make([]object.Object, len(values))
+		for i, v := range values {
+			items[i] = object.NewBool(v)
+		}
+		return object.NewList(items)
+	}
+	tests := []testCase{
+		// The dunder-defining operand on the left
+		{num + `[num(1) < 2, num(1) > 2, num(1) <= 2, num(1) >= 2]`, bools(true, false, true, false)},
+		{num + `[num(3) < 2, num(3) > 2, num(3) <= 2, num(3) >= 2]`, bools(false, true, false, true)},
+		{num + `[num(2) < 2, num(2) > 2, num(2) <= 2, num(2) >= 2]`, bools(false, false, true, true)},
+		// The dunder-defining operand on the right
+		{num + `[2 < num(3), 2 > num(3), 2 <= num(3), 2 >= num(3)]`, bools(true, false, true, false)},
+		{num + `[2 < num(1), 2 > num(1), 2 <= num(1), 2 >= num(1)]`, bools(false, true, false, true)},
+		{num + `[2 < num(2), 2 > num(2), 2 <= num(2), 2 >= num(2)]`, bools(false, false, true, true)},
+	}
+	runTests(t, tests)
+}
+
+func TestCallableMap(t *testing.T) {
+	tests := []testCase{
+		{`
+		let counter = {count: 0, __call__: function(self, n = 1) {
+			self.count += n
+			return self.count
+		}}
+		counter()
+		counter(5)
+		`, object.NewInt(6)},
+		{`
+		let greeter = {greeting: "hi", __call__: (self, name) => self.greeting + " " + name}
+		let args = ["bob"]
+		greeter(...args)
+		`, object.NewString("hi bob")},
+		{`
+		let inc = {__call__: (self, x) => x + 1}
+		41 |> inc
+		`, object.NewInt(42)},
+	}
+	runTests(t, tests)
+}
+
+func TestCallableMapErrors(t *testing.T) {
+	ctx := context.Background()
+
+	_, err := run(ctx, `let m = {a: 1}; m()`)
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "object is not callable (got map)")
+
+	_, err = run(ctx, `let m = {__call__: 1}; m()`)
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "object is not callable (got map)")
+}
+
+func TestBitwiseNot(t *testing.T) {
+	tests := []testCase{
+		{`~0`, object.NewInt(-1)},
+		{`~5`, object.NewInt(-6)},
+		{`~-1`, object.NewInt(0)},
+		{`~~42`, object.NewInt(42)},
+		{`~byte(0x0F)`, object.NewByte(0xF0)},
+		{`let x = 12; x & ~4`, object.NewInt(8)},
+		{`-~3`, object.NewInt(4)},
+	}
+	runTests(t, tests)
+}
+
+func TestBitwiseErrors(t *testing.T) {
+	ctx := context.Background()
+
+	_, err := run(ctx, `~1.5`)
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "bitwise not requires an int or byte (got float)")
+
+	_, err = run(ctx, `let n = -2; 1 << n`)
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "negative shift count: -2")
+
+	_, err = run(ctx, `1 >> -1`)
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "negative shift count")
+}
+
+func TestAssertStatement(t *testing.T) {
+	tests := []testCase{
+		{`assert true; 1`, object.NewInt(1)},
+		{`let x = 5; assert x == 5; x`, object.NewInt(5)},
+		{`let x = 5; assert x > 1, "x too small"; x`, object.NewInt(5)},
+		{`assert([1, 2] == [1, 2], "lists differ"); 2`, object.NewInt(2)},
+		{`assert "non-empty"; 3`, object.NewInt(3)},
+		// Comparison operands are evaluated once
+		{`let n = 0
+		function f() { n = n + 1; return n }
+		assert f() == 1
+		n`, object.NewInt(1)},
+		// Assert is a statement, so a block ending with it evaluates to nil
+		{`if true { assert true }`, object.Nil},
+	}
+	runTests(t, tests)
+}
+
+func TestKeywordsAsKeysAndAttributes(t *testing.T) {
+	tests := []testCase{
+		{`let m = {assert: 1, quote: 2, with: 3}; [m.assert, m.quote, m.with]`,
+			object.NewList([]object.Object{object.NewInt(1), object.NewInt(2), object.NewInt(3)})},
+		{`let m = {with: "w"}; m?.with`, object.NewString("w")},
+		{`let {assert: a, with: w} = {assert: 1, with: 2}; a + w`, object.NewInt(3)},
+		// The keywords still work as statements and expressions
+		{`assert true; let q = quote { x }; type(q)`, object.NewString("quote")},
+	}
+	runTests(t, tests)
+}
+
+func TestAssertFailure(t *testing.T) {
+	ctx := context.Background()
+	tests := []struct {
+		input    string
+		expected string
+	}{
+		{`assert false`, "assertion error: false"},
+		{`let x = 4; assert x == 5`, "assertion error: x == 5 (left: 4, right: 5)"},
+		{`let x = 4; assert x > 5, "x too small"`, "assertion error: x too small: x > 5 (left: 4, right: 5)"},
+		{`assert "abc" == "abd"`, `(left: "abc", right: "abd"; first difference at index 2)`},
+		{`assert "ab" == "abc"`, "first difference at index 2 (lengths 2 and 3)"},
+		{`assert [1, 2, 4] == [1, 2, 3]`, "first difference at index 2: 4 != 3"},
+		{`assert [1, 2] == [1, 2, 3]`, "lengths differ: 2 != 3"},
+		{`let m = {a: 1, b: 2}; assert m == {a: 1, b: 3, c: 4}`, `key "b": 2 != 3; key "c" missing on left`},
+		{`assert [1] != [1]`, "assertion error: [1] != [1] (left: [1], right: [1])"},
+		{`assert len([]) > 0, error("empty")`, "assertion error: empty: len([]) > 0"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.input, func(t *testing.T) {
+			_, err := run(ctx, tt.input)
+			assert.NotNil(t, err)
+			assert.Contains(t, err.Error(), tt.expected)
+		})
+	}
+}
+
+func TestAssertCatch(t *testing.T) {
+	tests := []testCase{
+		{`try { assert 1 == 2 } catch (e) { e.kind() }`, object.NewString("assertion error")},
+		{`let x = try { assert 1 == 2; 0 } catch (e) { 1 }; x + 1`, object.NewInt(2)},
+	}
+	runTests(t, tests)
+}
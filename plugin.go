@@ -0,0 +1,191 @@
+package risor
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"maps"
+	"slices"
+	"sync"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+	"github.com/deepnoodle-ai/risor/v2/pkg/vm"
+)
+
+// ErrFunctionNotFound is returned when a plugin is asked to call a function
+// it does not define.
+var ErrFunctionNotFound = errors.New("function not found")
+
+// Plugin is a script that is loaded once and then driven by the host through
+// the functions it defines. This suits scripts that act as hooks, such as a
+// request filter or an event transformer:
+//
+//	plugin, err := risor.LoadPlugin(ctx, `
+//	    let seen = 0
+//	    function on_request(req) {
+//	        seen++
+//	        return req.path != "/admin"
+//	    }
+//	`, risor.WithEnv(risor.Builtins()))
+//
+//	allowed, err := plugin.Call(ctx, "on_request", map[string]any{"path": "/"})
+//
+// Loading compiles the source and runs its top level once. The functions it
+// defines at the top level are then available to Call, and all calls share
+// the script's global state. A script that uses the export statement limits
+// this to the functions it exports, keeping its helpers private. Calls are
+// serialized, so a Plugin may be used from multiple goroutines.
+//
+// Reload replaces the script with a new version while keeping the values of
+// its global variables, so a plugin can be updated without losing state.
+type Plugin struct {
+	mu      sync.Mutex
+	opts    []Option
+	o       *options
+	machine *vm.VirtualMachine
+	funcs   map[string]*object.Closure
+}
+
+// LoadPlugin compiles the source, runs its top level once, and returns a
+// Plugin exposing the functions it defines. The options are used for both
+// compilation and execution, and are retained for later calls to Reload.
+func LoadPlugin(ctx context.Context, source string, opts ...Option) (*Plugin, error) {
+	p := &Plugin{opts: opts, o: collectOptions(opts...)}
+	machine, funcs, err := p.load(ctx, source)
+	if err != nil {
+		return nil, err
+	}
+	p.machine = machine
+	p.funcs = funcs
+	return p, nil
+}
+
+// load compiles and runs the source on a new VM and collects the functions
+// it exports, or all of its top-level functions if it has no exports.
+func (p *Plugin) load(ctx context.Context, source string) (*vm.VirtualMachine, map[string]*object.Closure, error) {
+	code, err := Compile(ctx, source, p.opts...)
+	if err != nil {
+		return nil, nil, err
+	}
+	machine, err := vm.NewEmpty(p.o.vmOpts()...)
+	if err != nil {
+		return nil, nil, err
+	}
+	if err := p.o.prelude.runOn(ctx, machine); err != nil {
+		return nil, nil, err
+	}
+	if err := machine.RunCode(ctx, code); err != nil {
+		return nil, nil, err
+	}
+	names := code.Exports()
+	if names == nil {
+		names = p.scriptGlobals(machine)
+	}
+	funcs := map[string]*object.Closure{}
+	for _, name := range names {
+		value, err := machine.Get(name)
+		if err != nil {
+			return nil, nil, err
+		}
+		if fn, ok := value.(*object.Closure); ok {
+			funcs[name] = fn
+		}
+	}
+	return machine, funcs, nil
+}
+
+// scriptGlobals returns the names of the globals defined by the script
+// itself, excluding those provided through WithEnv or defined by a prelude.
+func (p *Plugin) scriptGlobals(machine *vm.VirtualMachine) []string {
+	var names []string
+	for _, name := range machine.GlobalNames() {
+		if _, ok := p.o.env[name]; !ok && !p.o.prelude.defines(name) {
+			names = append(names, name)
+		}
+	}
+	return names
+}
+
+// Functions returns the sorted names of the functions the plugin defines.
+func (p *Plugin) Functions() []string {
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	return slices.Sorted(maps.Keys(p.funcs))
+}
+
+// Has reports whether the plugin defines a function with the given name.
+// Hosts can use this to treat hooks as optional.
+func (p *Plugin) Has(name string) bool {
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	_, ok := p.funcs[name]
+	return ok
+}
+
+// Call invokes the named plugin function. Arguments are converted to Risor
+// objects using the configured type registry, and the result is converted
+// back to a Go value following the same rules as Run. If the plugin does not
+// define the function, an error wrapping ErrFunctionNotFound is returned.
+func (p *Plugin) Call(ctx context.Context, name string, args ...any) (any, error) {
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	fn, ok := p.funcs[name]
+	if !ok {
+		return nil, fmt.Errorf("%w: %q", ErrFunctionNotFound, name)
+	}
+	registry := p.machine.TypeRegistry()
+	objArgs := make([]object.Object, len(args))
+	for i, arg := range args {
+		obj, err := registry.FromGo(arg)
+		if err != nil {
+			return nil, fmt.Errorf("%s: argument %d: %w", name, i+1, err)
+		}
+		objArgs[i] = obj
+	}
+	if p.o.timeout > 0 {
+		var cancel context.CancelFunc
+		ctx, cancel = context.WithTimeout(ctx, p.o.timeout)
+		defer cancel()
+	}
+	result, err := p.machine.Call(ctx, fn, objArgs)
+	if err != nil {
+		return nil, err
+	}
+	return p.o.convertResult(result), nil
+}
+
+// Reload replaces the plugin with a new version of its source. The new source
+// is compiled and its top level is run, then each global variable that also
+// existed in the previous version is restored to its previous value, as long
+// as its type is unchanged. Functions are always taken from the new version.
+// This lets a script keep counters, caches, and other state across reloads.
+//
+// If the new source fails to compile or run, the plugin is left unchanged
+// and the error is returned.
+func (p *Plugin) Reload(ctx context.Context, source string) error {
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	machine, funcs, err := p.load(ctx, source)
+	if err != nil {
+		return err
+	}
+	for _, name := range p.scriptGlobals(machine) {
+		oldValue, err := p.machine.Get(name)
+		if err != nil || oldValue == nil {
+			continue
+		}
+		if _, ok := oldValue.(object.Callable); ok {
+			continue
+		}
+		newValue, err := machine.Get(name)
+		if err != nil || newValue == nil || newValue.Type() != oldValue.Type() {
+			continue
+		}
+		if err := machine.Set(name, oldValue); err != nil {
+			return err
+		}
+	}
+	p.machine = machine
+	p.funcs = funcs
+	return nil
+}
@@ -0,0 +1,119 @@
+package risor
+
+import (
+	"context"
+	"errors"
+	"testing"
+
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+func TestLoadPlugin(t *testing.T) {
+	ctx := context.Background()
+	plugin, err := LoadPlugin(ctx, `
+	let calls = 0
+	let prefix = "hello"
+	function greet(name) {
+		calls++
+		return prefix + " " + name
+	}
+	function count() { calls }
+	`, WithEnv(Builtins()))
+	assert.Nil(t, err)
+	assert.Equal(t, plugin.Functions(), []string{"count", "greet"})
+	assert.True(t, plugin.Has("greet"))
+	assert.False(t, plugin.Has("prefix"))
+	assert.False(t, plugin.Has("len"))
+
+	result, err := plugin.Call(ctx, "greet", "world")
+	assert.Nil(t, err)
+	assert.Equal(t, result, "hello world")
+	_, err = plugin.Call(ctx, "greet", "again")
+	assert.Nil(t, err)
+
+	result, err = plugin.Call(ctx, "count")
+	assert.Nil(t, err)
+	assert.Equal(t, result, int64(2))
+
+	_, err = plugin.Call(ctx, "missing")
+	assert.True(t, errors.Is(err, ErrFunctionNotFound))
+
+	// Builtins from the environment are not plugin functions
+	_, err = plugin.Call(ctx, "len", "abc")
+	assert.True(t, errors.Is(err, ErrFunctionNotFound))
+}
+
+func TestLoadPluginExports(t *testing.T) {
+	ctx := context.Background()
+	plugin, err := LoadPlugin(ctx, `
+	function normalize(s) { s.to_lower() }
+	export function handle(s) { normalize(s) + "!" }
+	`)
+	assert.Nil(t, err)
+	assert.Equal(t, plugin.Functions(), []string{"handle"})
+
+	result, err := plugin.Call(ctx, "handle", "HEY")
+	assert.Nil(t, err)
+	assert.Equal(t, result, "hey!")
+
+	// Functions that are not exported are private to the script
+	_, err = plugin.Call(ctx, "normalize", "HEY")
+	assert.True(t, errors.Is(err, ErrFunctionNotFound))
+}
+
+func TestLoadPluginErrors(t *testing.T) {
+	ctx := context.Background()
+	_, err := LoadPlugin(ctx, `function f( {`)
+	assert.NotNil(t, err)
+
+	_, err = LoadPlugin(ctx, `throw "init failed"`)
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "init failed")
+
+	plugin, err := LoadPlugin(ctx, `function fail() { throw "hook failed" }`)
+	assert.Nil(t, err)
+	_, err = plugin.Call(ctx, "fail")
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "hook failed")
+}
+
+func TestPluginReload(t *testing.T) {
+	ctx := context.Background()
+	plugin, err := LoadPlugin(ctx, `
+	let calls = 0
+	let mode = "v1"
+	function handle() {
+		calls++
+		return mode + ":" + string(calls)
+	}
+	`, WithEnv(Builtins()))
+	assert.Nil(t, err)
+	for i := 0; i < 2; i++ {
+		_, err = plugin.Call(ctx, "handle")
+		assert.Nil(t, err)
+	}
+
+	// calls keeps its value, mode changes type and so takes its new value,
+	// and handle is replaced by the new definition
+	err = plugin.Reload(ctx, `
+	let calls = 0
+	let mode = ["v2"]
+	function handle() {
+		calls++
+		return mode[0] + "/" + string(calls)
+	}
+	function extra() { true }
+	`)
+	assert.Nil(t, err)
+	assert.Equal(t, plugin.Functions(), []string{"extra", "handle"})
+	result, err := plugin.Call(ctx, "handle")
+	assert.Nil(t, err)
+	assert.Equal(t, result, "v2/3")
+
+	// A failed reload leaves the plugin unchanged
+	err = plugin.Reload(ctx, `function handle( {`)
+	assert.NotNil(t, err)
+	result, err = plugin.Call(ctx, "handle")
+	assert.Nil(t, err)
+	assert.Equal(t, result, "v2/4")
+}
@@ -0,0 +1,104 @@
+package risor
+
+import (
+	"context"
+	"fmt"
+	"slices"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/bytecode"
+	"github.com/deepnoodle-ai/risor/v2/pkg/vm"
+)
+
+// Prelude is a script of helper definitions that is compiled once and then
+// run ahead of every script that is compiled and run with WithPrelude. The
+// globals and functions it defines are available to those scripts as if they
+// were part of the environment, so a platform can ship a library of helpers
+// without concatenating it to each user script:
+//
+//	prelude, err := risor.CompilePrelude(ctx, helpers, risor.WithEnv(risor.Builtins()))
+//	if err != nil {
+//	    return err
+//	}
+//	result, err := risor.Eval(ctx, userScript,
+//	    risor.WithEnv(risor.Builtins()),
+//	    risor.WithPrelude(prelude))
+//
+// The prelude runs on the same VM as the script, so every run starts from a
+// fresh copy of the prelude's state. A Prelude is immutable and safe for
+// concurrent use.
+type Prelude struct {
+	codes []*bytecode.Code
+	names []string // Sorted names of the globals the prelude defines
+}
+
+// CompilePrelude compiles the source of a prelude. The options are used as
+// they are by Compile, and the environment given with WithEnv must also be
+// given wherever the prelude is used. If WithPrelude is given, the new
+// prelude extends that one: its source can use the definitions of the
+// earlier prelude, and both are run in order.
+func CompilePrelude(ctx context.Context, source string, opts ...Option) (*Prelude, error) {
+	code, err := Compile(ctx, source, opts...)
+	if err != nil {
+		return nil, err
+	}
+	p := &Prelude{}
+	if base := collectOptions(opts...).prelude; base != nil {
+		p.codes = slices.Clone(base.codes)
+		p.names = slices.Clone(base.names)
+	}
+	p.codes = append(p.codes, code)
+	envKeys := code.EnvKeys()
+	for _, name := range code.GlobalNames() {
+		if name != "_" && !slices.Contains(envKeys, name) {
+			p.names = append(p.names, name)
+		}
+	}
+	slices.Sort(p.names)
+	p.names = slices.Compact(p.names)
+	return p, nil
+}
+
+// WithPrelude runs the prelude before the script, and makes the globals it
+// defines available to the script when it is compiled. The same prelude must
+// be given when compiling and when running a script.
+func WithPrelude(p *Prelude) Option {
+	return func(o *options) {
+		o.prelude = p
+	}
+}
+
+// defines reports whether the prelude defines the named global.
+func (p *Prelude) defines(name string) bool {
+	if p == nil {
+		return false
+	}
+	_, found := slices.BinarySearch(p.names, name)
+	return found
+}
+
+// validate checks that env provides the globals the prelude was compiled
+// with.
+func (p *Prelude) validate(env map[string]any) error {
+	if p == nil {
+		return nil
+	}
+	for _, code := range p.codes {
+		if err := validateGlobals(code, env, p); err != nil {
+			return fmt.Errorf("prelude: %w", err)
+		}
+	}
+	return nil
+}
+
+// runOn runs the prelude on the VM, defining its globals there.
+func (p *Prelude) runOn(ctx context.Context, machine *vm.VirtualMachine) error {
+	if p == nil {
+		return nil
+	}
+	for _, code := range p.codes {
+		if err := machine.RunCode(ctx, code); err != nil {
+			return fmt.Errorf("prelude: %w", err)
+		}
+	}
+	return nil
+}
@@ -0,0 +1,101 @@
+package risor
+
+import (
+	"context"
+	"strings"
+	"testing"
+
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+func TestPrelude(t *testing.T) {
+	ctx := context.Background()
+	prelude, err := CompilePrelude(ctx, `
+	let calls = 0
+	function shout(s) {
+		calls++
+		return s.to_upper() + "!"
+	}
+	`, WithEnv(Builtins()))
+	assert.Nil(t, err)
+
+	opts := []Option{WithEnv(Builtins()), WithPrelude(prelude)}
+	result, err := Eval(ctx, `shout("hi") + " " + string(calls)`, opts...)
+	assert.Nil(t, err)
+	assert.Equal(t, result, "HI! 1")
+
+	// Each run starts from fresh prelude state
+	result, err = Eval(ctx, `shout("a"); calls`, opts...)
+	assert.Nil(t, err)
+	assert.Equal(t, result, int64(1))
+
+	// Compiled code can be run repeatedly with the prelude
+	code, err := Compile(ctx, `shout(name)`, append(opts, WithEnv(map[string]any{"name": "x"}))...)
+	assert.Nil(t, err)
+	result, err = Run(ctx, code, append(opts, WithEnv(map[string]any{"name": "y"}))...)
+	assert.Nil(t, err)
+	assert.Equal(t, result, "Y!")
+
+	// Code that uses the prelude can't run without it
+	_, err = Run(ctx, code, WithEnv(Builtins()), WithEnv(map[string]any{"name": "y"}))
+	assert.NotNil(t, err)
+	assert.True(t, strings.Contains(err.Error(), "missing required globals"))
+}
+
+func TestPreludeExtends(t *testing.T) {
+	ctx := context.Background()
+	base, err := CompilePrelude(ctx, `function double(x) { x * 2 }`)
+	assert.Nil(t, err)
+	prelude, err := CompilePrelude(ctx, `function quadruple(x) { double(double(x)) }`, WithPrelude(base))
+	assert.Nil(t, err)
+
+	result, err := Eval(ctx, `quadruple(3) + double(1)`, WithPrelude(prelude))
+	assert.Nil(t, err)
+	assert.Equal(t, result, int64(14))
+}
+
+func TestPreludeErrors(t *testing.T) {
+	ctx := context.Background()
+	_, err := CompilePrelude(ctx, `function broken( {`)
+	assert.NotNil(t, err)
+
+	prelude, err := CompilePrelude(ctx, `throw error("setup failed")`)
+	assert.Nil(t, err)
+	_, err = Eval(ctx, `1`, WithPrelude(prelude))
+	assert.NotNil(t, err)
+	assert.True(t, strings.HasPrefix(err.Error(), "prelude: "))
+
+	// The prelude's environment must be provided when it is used
+	prelude, err = CompilePrelude(ctx, `let y = x + 1`, WithEnv(map[string]any{"x": 1}))
+	assert.Nil(t, err)
+	_, err = Eval(ctx, `y`, WithPrelude(prelude))
+	assert.NotNil(t, err)
+	assert.True(t, strings.Contains(err.Error(), "missing required globals"))
+}
+
+func TestPreludeSessionAndPlugin(t *testing.T) {
+	ctx := context.Background()
+	prelude, err := CompilePrelude(ctx, `
+	let seen = []
+	function record(v) { seen.append(v); return len(seen) }
+	`, WithEnv(Builtins()))
+	assert.Nil(t, err)
+	opts := []Option{WithEnv(Builtins()), WithPrelude(prelude)}
+
+	// A session runs the prelude once and shares its state across scripts
+	session, err := NewSession(ctx, opts...)
+	assert.Nil(t, err)
+	_, err = session.Eval(ctx, `record("a")`)
+	assert.Nil(t, err)
+	result, err := session.Eval(ctx, `record("b")`)
+	assert.Nil(t, err)
+	assert.Equal(t, result, int64(2))
+
+	// Prelude functions are not plugin functions
+	plugin, err := LoadPlugin(ctx, `function hook(v) { record(v) }`, opts...)
+	assert.Nil(t, err)
+	assert.Equal(t, plugin.Functions(), []string{"hook"})
+	result, err = plugin.Call(ctx, "hook", "x")
+	assert.Nil(t, err)
+	assert.Equal(t, result, int64(1))
+}
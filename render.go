@@ -0,0 +1,53 @@
+package risor
+
+import (
+	"context"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/bytecode"
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+	"github.com/deepnoodle-ai/risor/v2/pkg/parser"
+)
+
+// CompileTemplate compiles text as a template. Each ${expr} hole in the text
+// holds an expression, and all other text is kept as is, as in a template
+// string. Running the returned Code with Run produces the rendered string.
+// The options are used as they are by Compile, except that templates are not
+// cached.
+func CompileTemplate(ctx context.Context, text string, opts ...Option) (*bytecode.Code, error) {
+	o := collectOptions(opts...)
+	var parserCfg *parser.Config
+	if o.filename != "" {
+		parserCfg = &parser.Config{Filename: o.filename}
+	}
+	program, err := parser.ParseTemplate(ctx, text, parserCfg)
+	if err != nil {
+		return nil, err
+	}
+	return o.compileProgram(program, text)
+}
+
+// Render evaluates text as a template against the environment and returns
+// the rendered string. Values in holes are converted to text as they are in
+// template strings: strings are inserted as is, and other values as they
+// print. This suits generating configuration files and messages:
+//
+//	out, err := risor.Render(ctx, "host: ${host}\nport: ${port + 1}\n",
+//	    risor.WithEnv(map[string]any{"host": "db", "port": 5432}))
+//
+// With WithUndefinedNames(UndefinedNil), holes may refer to names that are
+// missing from the environment; write ${name ?? ""} to render them as empty.
+func Render(ctx context.Context, text string, opts ...Option) (string, error) {
+	code, err := CompileTemplate(ctx, text, opts...)
+	if err != nil {
+		return "", err
+	}
+	result, err := Run(ctx, code, append(opts, WithRawResult())...)
+	if err != nil {
+		return "", err
+	}
+	s, ok := result.(*object.String)
+	if !ok {
+		return "", object.TypeErrorf("template rendered a %s, not a string", result.Type())
+	}
+	return s.Value(), nil
+}
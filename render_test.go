@@ -0,0 +1,67 @@
+package risor
+
+import (
+	"context"
+	"testing"
+
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+func TestRender(t *testing.T) {
+	ctx := context.Background()
+	env := WithEnv(map[string]any{
+		"host":  "db",
+		"port":  5432,
+		"tags":  []string{"a", "b"},
+		"debug": false,
+	})
+	tests := []struct {
+		text     string
+		expected string
+	}{
+		{"", ""},
+		{"plain text", "plain text"},
+		{"host: ${host}\nport: ${port + 1}\n", "host: db\nport: 5433\n"},
+		{"${tags} ${debug} ${len(tags)}", `["a", "b"] false 2`},
+		{"`quoted` and 'single' ${host}", "`quoted` and 'single' db"},
+		{"empty ${} hole", "empty  hole"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.text, func(t *testing.T) {
+			out, err := Render(ctx, tt.text, env, WithEnv(Builtins()))
+			assert.Nil(t, err)
+			assert.Equal(t, out, tt.expected)
+		})
+	}
+}
+
+func TestRenderErrors(t *testing.T) {
+	ctx := context.Background()
+
+	_, err := Render(ctx, "hello ${name}")
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), `undefined variable "name"`)
+
+	_, err = Render(ctx, "hello ${name")
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "missing '}'")
+
+	_, err = Render(ctx, "${[1][2]}")
+	assert.NotNil(t, err)
+
+	out, err := Render(ctx, `hello ${name ?? "there"}`, WithUndefinedNames(UndefinedNil))
+	assert.Nil(t, err)
+	assert.Equal(t, out, "hello there")
+}
+
+func TestCompileTemplate(t *testing.T) {
+	ctx := context.Background()
+	code, err := CompileTemplate(ctx, "${greeting}, ${name}!",
+		WithEnv(map[string]any{"greeting": "", "name": ""}))
+	assert.Nil(t, err)
+	for _, name := range []string{"Ada", "Grace"} {
+		result, err := Run(ctx, code, WithEnv(map[string]any{"greeting": "Hi", "name": name}))
+		assert.Nil(t, err)
+		assert.Equal(t, result, "Hi, "+name+"!")
+	}
+}
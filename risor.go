@@ -5,15 +5,23 @@ import (
 	"errors"
 	"fmt"
 	"maps"
+	"reflect"
 	"slices"
+	"sync"
 	"time"
 
+	"github.com/deepnoodle-ai/risor/v2/pkg/ast"
 	"github.com/deepnoodle-ai/risor/v2/pkg/builtins"
 	"github.com/deepnoodle-ai/risor/v2/pkg/bytecode"
 	"github.com/deepnoodle-ai/risor/v2/pkg/compiler"
+	modCron "github.com/deepnoodle-ai/risor/v2/pkg/modules/cron"
+	modLog "github.com/deepnoodle-ai/risor/v2/pkg/modules/log"
 	modMath "github.com/deepnoodle-ai/risor/v2/pkg/modules/math"
+	modNet "github.com/deepnoodle-ai/risor/v2/pkg/modules/net"
 	modRand "github.com/deepnoodle-ai/risor/v2/pkg/modules/rand"
 	modRegexp "github.com/deepnoodle-ai/risor/v2/pkg/modules/regexp"
+	modSchema "github.com/deepnoodle-ai/risor/v2/pkg/modules/schema"
+	modTime "github.com/deepnoodle-ai/risor/v2/pkg/modules/time"
 	"github.com/deepnoodle-ai/risor/v2/pkg/object"
 	"github.com/deepnoodle-ai/risor/v2/pkg/parser"
 	"github.com/deepnoodle-ai/risor/v2/pkg/syntax"
@@ -47,19 +55,38 @@ var (
 	FullLanguage   = syntax.FullLanguage
 )
 
+// UndefinedMode selects how undefined names are handled. See WithUndefinedNames.
+type UndefinedMode = compiler.UndefinedMode
+
+// Re-export undefined name modes.
+const (
+	UndefinedError     = compiler.UndefinedError
+	UndefinedLateBound = compiler.UndefinedLateBound
+	UndefinedNil       = compiler.UndefinedNil
+)
+
 // Option configures a Risor compilation or execution.
 type Option func(*options)
 
 type options struct {
 	env          map[string]any
+	globalNames  []string // Globals defined by earlier scripts in a Session
+	prelude      *Prelude
 	filename     string
+	undefined    UndefinedMode
+	langVersion  string
 	observer     vm.Observer
 	typeRegistry *object.TypeRegistry
+	logHandler   object.LogHandler
+	importer     vm.Importer
+	compileCache *CompileCache
 	rawResult    bool
 	// Resource limits
-	maxSteps      int64
-	maxStackDepth int
-	timeout       time.Duration
+	maxSteps        int64
+	maxStackDepth   int
+	timeout         time.Duration
+	finallyOnCancel bool
+	evalOptions     []vm.Option
 	// AST validation and transformation
 	syntaxConfig *syntax.SyntaxConfig
 	validators   []syntax.Validator
@@ -78,12 +105,20 @@ func collectOptions(opts ...Option) *options {
 
 func (o *options) compilerConfig() *compiler.Config {
 	cfg := &compiler.Config{}
-	if len(o.env) > 0 {
-		cfg.GlobalNames = slices.Sorted(maps.Keys(o.env))
+	if len(o.env) > 0 || len(o.globalNames) > 0 || o.prelude != nil {
+		names := slices.Collect(maps.Keys(o.env))
+		names = append(names, o.globalNames...)
+		if o.prelude != nil {
+			names = append(names, o.prelude.names...)
+		}
+		slices.Sort(names)
+		cfg.GlobalNames = slices.Compact(names)
 	}
 	if o.filename != "" {
 		cfg.Filename = o.filename
 	}
+	cfg.UndefinedNames = o.undefined
+	cfg.LanguageVersion = o.langVersion
 	return cfg
 }
 
@@ -98,6 +133,12 @@ func (o *options) vmOpts() []vm.Option {
 	if o.typeRegistry != nil {
 		opts = append(opts, vm.WithTypeRegistry(o.typeRegistry))
 	}
+	if o.logHandler != nil {
+		opts = append(opts, vm.WithLogHandler(o.logHandler))
+	}
+	if o.importer != nil {
+		opts = append(opts, vm.WithImporter(o.importer))
+	}
 	if o.maxSteps > 0 {
 		opts = append(opts, vm.WithMaxSteps(o.maxSteps))
 	}
@@ -107,6 +148,12 @@ func (o *options) vmOpts() []vm.Option {
 	if o.timeout > 0 {
 		opts = append(opts, vm.WithTimeout(o.timeout))
 	}
+	if o.finallyOnCancel {
+		opts = append(opts, vm.WithFinallyOnCancel())
+	}
+	if len(o.evalOptions) > 0 {
+		opts = append(opts, vm.WithEvalOptions(o.evalOptions...))
+	}
 	return opts
 }
 
@@ -149,6 +196,32 @@ func WithFilename(filename string) Option {
 	}
 }
 
+// WithUndefinedNames sets how the script's reads of names that aren't in the
+// environment at compile time are handled. By default (UndefinedError) they
+// fail compilation. With UndefinedLateBound they are looked up when the
+// script runs, and reading one that is still undefined raises a name error.
+// This suits hosts that compile a script once and run it against
+// environments whose names aren't known up front. UndefinedNil also looks
+// names up at runtime, but evaluates undefined ones to nil, as templates
+// usually want.
+func WithUndefinedNames(mode UndefinedMode) Option {
+	return func(o *options) {
+		o.undefined = mode
+	}
+}
+
+// WithLanguageVersion pins the language version, such as "2.1", that scripts
+// are written for. Syntax added in a later version fails compilation with an
+// error naming the version it requires, so platforms can keep stored user
+// scripts on a stable dialect as the language grows. Keywords added in a later
+// version, such as with and import, are ordinary names in the pinned version.
+// By default, scripts may use all syntax up to compiler.LatestLanguageVersion.
+func WithLanguageVersion(version string) Option {
+	return func(o *options) {
+		o.langVersion = version
+	}
+}
+
 // WithObserver sets an observer for VM execution events.
 // The observer receives callbacks for instruction steps, function calls,
 // and function returns. This enables profilers, debuggers, code coverage
@@ -174,6 +247,52 @@ func WithTypeRegistry(registry *object.TypeRegistry) Option {
 	}
 }
 
+// WithLogHandler sets the handler that receives structured records logged by
+// scripts with the log module. The handler is called with the context the
+// script runs with, so records can be forwarded to the application's logging
+// or tracing pipeline. Without a handler, log records are discarded.
+//
+// Example:
+//
+//	handler := func(ctx context.Context, r object.LogRecord) error {
+//	    slog.Log(ctx, r.Level, r.Message, "fields", r.Fields)
+//	    return nil
+//	}
+//	result, _ := risor.Eval(ctx, `log.info("hi", {n: 1})`,
+//	    risor.WithEnv(risor.Builtins()), risor.WithLogHandler(handler))
+func WithLogHandler(handler object.LogHandler) Option {
+	return func(o *options) {
+		o.logHandler = handler
+	}
+}
+
+// WithImporter enables import statements, using the given importer to load
+// the modules they name. Without an importer, import statements raise an
+// import error. Use NewFileImporter to import Risor source files from disk,
+// or NewImporter with a ModuleLoader to serve modules from elsewhere:
+//
+//	importer := risor.NewFileImporter([]string{"lib"}, risor.WithEnv(env))
+//	result, _ := risor.Eval(ctx, `import "./helpers"; helpers.run()`,
+//	    risor.WithEnv(env), risor.WithImporter(importer))
+func WithImporter(importer vm.Importer) Option {
+	return func(o *options) {
+		o.importer = importer
+	}
+}
+
+// WithCompileCache configures Compile and Eval to reuse bytecode from the
+// given cache when the same source has been compiled before with equivalent
+// options, and to store newly compiled bytecode in it:
+//
+//	dir, _ := risor.DefaultCompileCacheDir()
+//	cache := risor.NewCompileCache(dir)
+//	result, err := risor.Eval(ctx, source, risor.WithCompileCache(cache))
+func WithCompileCache(cache *CompileCache) Option {
+	return func(o *options) {
+		o.compileCache = cache
+	}
+}
+
 // WithRawResult configures Run and Eval to return the result as an
 // object.Object instead of converting it to a native Go type.
 //
@@ -254,6 +373,32 @@ func WithTimeout(d time.Duration) Option {
 	}
 }
 
+// WithFinallyOnCancel runs the script's pending finally blocks when the
+// context is cancelled or the timeout expires, before returning the context's
+// error. Catch blocks don't see the cancellation. Since finally blocks may run
+// for as long as they like, don't use this with untrusted scripts that must
+// stop on time.
+func WithFinallyOnCancel() Option {
+	return func(o *options) {
+		o.finallyOnCancel = true
+	}
+}
+
+// WithEvalOptions sets VM options for the code that scripts run with the
+// eval() and run() builtins. That code runs in a child VM that inherits the script's
+// step budget and stack limits, and these options are applied after them.
+//
+// Example:
+//
+//	result, err := risor.Eval(ctx, source,
+//	    risor.WithEnv(risor.Builtins()),
+//	    risor.WithEvalOptions(vm.WithMaxSteps(1000), vm.WithTimeout(time.Second)))
+func WithEvalOptions(opts ...vm.Option) Option {
+	return func(o *options) {
+		o.evalOptions = append(o.evalOptions, opts...)
+	}
+}
+
 // WithSyntax applies a syntax configuration that restricts allowed constructs.
 // The validator runs after parsing and before any transformers.
 //
@@ -351,7 +496,17 @@ func NewTypeRegistry() *object.RegistryBuilder {
 //	env["myvar"] = myValue           // add custom variable
 //	delete(env, "math")              // remove a module
 //	result, _ := risor.Eval(ctx, source, risor.WithEnv(env))
+//
+// The builtins and modules are created once and shared by every map this
+// function returns, so calling it for each evaluation is cheap. Scripts can't
+// modify them. A host that wants a customized module should create its own
+// rather than calling Module.Override on a shared one.
 func Builtins() map[string]any {
+	return maps.Clone(sharedBuiltins())
+}
+
+// sharedBuiltins builds the standard environment on first use.
+var sharedBuiltins = sync.OnceValue(func() map[string]any {
 	env := map[string]any{}
 	for k, v := range builtins.Builtins() {
 		env[k] = v
@@ -360,22 +515,28 @@ func Builtins() map[string]any {
 		env[k] = v
 	}
 	return env
-}
+})
 
 func defaultModules() map[string]object.Object {
 	return map[string]object.Object{
-		"math":   modMath.Module(),
-		"rand":   modRand.Module(),
-		"regexp": modRegexp.Module(),
+		"cron":   modCron.Module().WithDocs(modCron.ModuleDoc(), modCron.Docs()),
+		"log":    modLog.Module().WithDocs(modLog.ModuleDoc(), modLog.Docs()),
+		"math":   modMath.Module().WithDocs(modMath.ModuleDoc(), modMath.Docs()),
+		"net":    modNet.Module(modNet.Policy{}).WithDocs(modNet.ModuleDoc(), modNet.Docs()),
+		"rand":   modRand.Module().WithDocs(modRand.ModuleDoc(), modRand.Docs()),
+		"regexp": modRegexp.Module().WithDocs(modRegexp.ModuleDoc(), modRegexp.Docs()),
+		"schema": modSchema.Module().WithDocs(modSchema.ModuleDoc(), modSchema.Docs()),
+		"time":   modTime.Module().WithDocs(modTime.ModuleDoc(), modTime.Docs()),
 	}
 }
 
-// validateGlobals checks that the env keys match the globals expected by the
-// bytecode. Returns an error if there's a mismatch.
+// validateGlobals checks that the env keys, along with the globals defined by
+// the prelude, match the globals expected by the bytecode. Returns an error if
+// there's a mismatch.
 //
 // This uses EnvKeys() which tracks only the globals that were provided via
 // the environment at compile time (not globals defined within the script).
-func validateGlobals(code *bytecode.Code, env map[string]any) error {
+func validateGlobals(code *bytecode.Code, env map[string]any, prelude *Prelude) error {
 	// EnvKeys returns only the globals from compile-time env,
 	// not script-defined globals like functions or let bindings.
 	required := code.EnvKeys()
@@ -392,7 +553,7 @@ func validateGlobals(code *bytecode.Code, env map[string]any) error {
 	// Find any required keys that are missing
 	var missing []string
 	for _, name := range required {
-		if !provided[name] {
+		if !provided[name] && !prelude.defines(name) {
 			missing = append(missing, name)
 		}
 	}
@@ -437,14 +598,43 @@ func validateGlobals(code *bytecode.Code, env map[string]any) error {
 func Compile(ctx context.Context, source string, opts ...Option) (*bytecode.Code, error) {
 	o := collectOptions(opts...)
 
-	var parserCfg *parser.Config
-	if o.filename != "" {
-		parserCfg = &parser.Config{Filename: o.filename}
+	// Custom validators and transformers can't be part of a cache key
+	var cacheKey string
+	if o.compileCache != nil && len(o.validators) == 0 && len(o.transformers) == 0 {
+		cacheKey = o.compileCache.key(source, o)
+		if code, ok := o.compileCache.get(cacheKey); ok {
+			return code, nil
+		}
 	}
+
+	// Scripts pinned to an older language version may use later keywords
+	// as names
+	identifiers, err := compiler.UnreservedKeywords(o.langVersion)
+	if err != nil {
+		return nil, err
+	}
+	parserCfg := &parser.Config{Filename: o.filename, Identifiers: identifiers}
 	program, err := parser.Parse(ctx, source, parserCfg)
 	if err != nil {
 		return nil, err
 	}
+	if _, err := ParseMetadata(source); err != nil {
+		return nil, err
+	}
+	code, err := o.compileProgram(program, source)
+	if err != nil {
+		return nil, err
+	}
+	if cacheKey != "" {
+		// A cache that can't be written only costs the next run a compile
+		_ = o.compileCache.put(cacheKey, code)
+	}
+	return code, nil
+}
+
+// compileProgram validates, transforms, and compiles a parsed program.
+func (o *options) compileProgram(program *ast.Program, source string) (*bytecode.Code, error) {
+	var err error
 
 	// Validate syntax config (if specified)
 	if o.syntaxConfig != nil {
@@ -472,7 +662,6 @@ func Compile(ctx context.Context, source string, opts ...Option) (*bytecode.Code
 	// Pass the original source to the compiler for better error messages
 	cfg := o.compilerConfig()
 	cfg.Source = source
-
 	return compiler.Compile(program, cfg)
 }
 
@@ -486,6 +675,12 @@ func Compile(ctx context.Context, source string, opts ...Option) (*bytecode.Code
 // If there's a mismatch, Run returns an error explaining what's wrong.
 // This prevents subtle bugs where the wrong values are accessed at runtime.
 //
+// # Script Metadata
+//
+// If the script starts with a metadata block, Run checks that env provides
+// the globals it requires and applies the limits it declares when they are
+// tighter than those given as options. See Metadata.
+//
 // # Result Conversion
 //
 // By default, the result is converted to a native Go value using these rules:
@@ -513,18 +708,50 @@ func Run(ctx context.Context, code *bytecode.Code, opts ...Option) (any, error)
 	o := collectOptions(opts...)
 
 	// Validate that env keys match the globals expected by the bytecode
-	if err := validateGlobals(code, o.env); err != nil {
+	if err := validateGlobals(code, o.env, o.prelude); err != nil {
+		return nil, err
+	}
+	if err := o.prelude.validate(o.env); err != nil {
 		return nil, err
 	}
+	// Honor the requirements and limits declared in the script's metadata
+	if meta, err := ParseMetadata(code.Source()); err != nil {
+		return nil, err
+	} else if err := meta.apply(o); err != nil {
+		return nil, err
+	}
+
+	var result object.Object
+	var err error
+	if o.prelude != nil {
+		result, err = o.runWithPrelude(ctx, code)
+	} else {
+		result, err = vm.Run(ctx, code, o.vmOpts()...)
+	}
+	if err != nil {
+		return nil, err
+	}
+	return o.convertResult(result), nil
+}
 
-	result, err := vm.Run(ctx, code, o.vmOpts()...)
+// runWithPrelude runs the prelude and then the code on a new VM.
+func (o *options) runWithPrelude(ctx context.Context, code *bytecode.Code) (object.Object, error) {
+	machine, err := vm.NewEmpty(o.vmOpts()...)
 	if err != nil {
 		return nil, err
 	}
+	if err := o.prelude.runOn(ctx, machine); err != nil {
+		return nil, err
+	}
+	return vm.RunCodeOnVM(ctx, machine, code)
+}
 
+// convertResult converts a result object to a Go value following the rules
+// described on Run, unless WithRawResult was given.
+func (o *options) convertResult(result object.Object) any {
 	// Return raw object.Object if requested
 	if o.rawResult {
-		return result, nil
+		return result
 	}
 
 	// Convert to Go value
@@ -533,10 +760,10 @@ func Run(ctx context.Context, code *bytecode.Code, opts ...Option) (any, error)
 	// return their string representation
 	if interfaceVal == nil {
 		if _, isNil := result.(*object.NilType); !isNil {
-			return result.Inspect(), nil
+			return result.Inspect()
 		}
 	}
-	return interfaceVal, nil
+	return interfaceVal
 }
 
 // Eval is a convenience function that compiles and runs source code.
@@ -552,3 +779,40 @@ func Eval(ctx context.Context, source string, opts ...Option) (any, error) {
 	}
 	return Run(ctx, code, opts...)
 }
+
+// FromObject converts a result of Run or Eval to a T, such as a struct that
+// holds a script's configuration. The result may be an object.Object, as
+// returned with WithRawResult, or the Go value returned without it.
+//
+// A map is converted to a struct by matching each exported field to the map
+// entry named by its risor or json tag, or else by the field name. Fields
+// are required unless they are pointers or tagged omitempty. A missing or
+// mistyped field results in an *object.FieldError naming it.
+//
+// Example:
+//
+//	type Config struct {
+//	    Name    string   `risor:"name"`
+//	    Retries int      `risor:"retries"`
+//	    Tags    []string `risor:"tags,omitempty"`
+//	}
+//	result, err := risor.Eval(ctx, `{name: "svc", retries: 3}`)
+//	config, err := risor.FromObject[Config](result)
+func FromObject[T any](result any) (T, error) {
+	var zero T
+	registry := object.DefaultRegistry()
+	obj, err := registry.FromGo(result)
+	if err != nil {
+		return zero, err
+	}
+	target := reflect.TypeFor[T]()
+	value, err := registry.ToGo(obj, target)
+	if err != nil || value == nil {
+		return zero, err
+	}
+	if v := reflect.ValueOf(value); v.Type() != target && v.Type().ConvertibleTo(target) {
+		value = v.Convert(target).Interface()
+	}
+	typed, _ := value.(T)
+	return typed, nil
+}
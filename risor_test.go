@@ -3,6 +3,7 @@ package risor
 import (
 	"context"
 	"errors"
+	"log/slog"
 	"reflect"
 	"strings"
 	"sync"
@@ -97,6 +98,28 @@ func TestXorOperator(t *testing.T) {
 	}
 }
 
+func TestResource(t *testing.T) {
+	ctx := context.Background()
+	released := false
+	file := object.NewResource("file", nil, func() error {
+		released = true
+		return nil
+	}).WithMethod("read", func(ctx context.Context, args ...object.Object) (object.Object, error) {
+		return object.NewString("contents"), nil
+	})
+	env := Builtins()
+	env["file"] = file
+
+	result, err := Eval(ctx, `let data = file.read(); file.close(); [data, file.closed]`, WithEnv(env))
+	assert.Nil(t, err)
+	assert.Equal(t, result, []any{"contents", true})
+	assert.True(t, released)
+
+	_, err = Eval(ctx, `file.read()`, WithEnv(env))
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "file is closed")
+}
+
 func TestBytesBuiltin(t *testing.T) {
 	ctx := context.Background()
 	env := WithEnv(Builtins())
@@ -283,9 +306,14 @@ func TestCustomizeBuiltins(t *testing.T) {
 func TestBuiltinsFunc(t *testing.T) {
 	env := Builtins()
 	expectedNames := []string{
+		"cron",
+		"log",
 		"math",
+		"net",
 		"rand",
 		"regexp",
+		"schema",
+		"time",
 		"keys",
 		"len",
 		"string",
@@ -296,6 +324,22 @@ func TestBuiltinsFunc(t *testing.T) {
 	}
 }
 
+func TestBuiltinsShared(t *testing.T) {
+	env1 := Builtins()
+	env2 := Builtins()
+	// The values are built once and shared
+	assert.True(t, env1["len"] == env2["len"])
+	assert.True(t, env1["math"] == env2["math"])
+
+	// Each call returns a separate map
+	delete(env1, "math")
+	env1["custom"] = 1
+	_, ok := Builtins()["math"]
+	assert.True(t, ok)
+	_, ok = Builtins()["custom"]
+	assert.False(t, ok)
+}
+
 // Test the Compile/Run API
 func TestCompileRun(t *testing.T) {
 	ctx := context.Background()
@@ -321,6 +365,54 @@ func TestProgramReuse(t *testing.T) {
 	}
 }
 
+func TestUndefinedNames(t *testing.T) {
+	ctx := context.Background()
+	_, err := Compile(ctx, "len(x)")
+	assert.NotNil(t, err)
+
+	program, err := Compile(ctx, "len(x)", WithUndefinedNames(UndefinedLateBound))
+	assert.Nil(t, err)
+	result, err := Run(ctx, program, WithEnv(Builtins()), WithEnv(map[string]any{"x": "abc"}))
+	assert.Nil(t, err)
+	assert.Equal(t, result, int64(3))
+
+	_, err = Run(ctx, program, WithEnv(Builtins()))
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), `undefined variable "x"`)
+
+	program, err = Compile(ctx, `x ?? "none"`, WithUndefinedNames(UndefinedNil))
+	assert.Nil(t, err)
+	result, err = Run(ctx, program)
+	assert.Nil(t, err)
+	assert.Equal(t, result, "none")
+	result, err = Run(ctx, program, WithEnv(map[string]any{"x": "abc"}))
+	assert.Nil(t, err)
+	assert.Equal(t, result, "abc")
+}
+
+func TestLanguageVersion(t *testing.T) {
+	ctx := context.Background()
+	_, err := Eval(ctx, `[1, 2, 3][::2]`, WithLanguageVersion("2.1"))
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "slice steps require language version >= 2.2")
+
+	// Keywords added later are names in older versions
+	result, err := Eval(ctx, `let with = 1; let import = 2; function quote(del) { del }; quote(with + import)`,
+		WithLanguageVersion("2.0"))
+	assert.Nil(t, err)
+	assert.Equal(t, result, int64(3))
+	_, err = Eval(ctx, `let with = 1`)
+	assert.NotNil(t, err)
+
+	result, err = Eval(ctx, `try { throw "x" } catch (e) { "caught" }`, WithLanguageVersion("2.1"))
+	assert.Nil(t, err)
+	assert.Equal(t, result, "caught")
+
+	_, err = Eval(ctx, `1`, WithLanguageVersion("3.0"))
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), `invalid language version "3.0"`)
+}
+
 // Test concurrent execution of the same Program
 func TestConcurrentExecution(t *testing.T) {
 	ctx := context.Background()
@@ -431,6 +523,49 @@ func TestWithRawResultClosure(t *testing.T) {
 	assert.True(t, isClosure, "expected *object.Closure")
 }
 
+type traceIDKey struct{}
+
+func TestWithLogHandler(t *testing.T) {
+	var records []object.LogRecord
+	var traceIDs []any
+	handler := func(ctx context.Context, r object.LogRecord) error {
+		records = append(records, r)
+		traceIDs = append(traceIDs, ctx.Value(traceIDKey{}))
+		return nil
+	}
+	ctx := context.WithValue(context.Background(), traceIDKey{}, "abc123")
+	source := `
+	function save(id) {
+		log.info("saved", {id: id})
+	}
+	[1, 2].each(save)
+	log.warn("done")
+	`
+	_, err := Eval(ctx, source, WithEnv(Builtins()), WithLogHandler(handler))
+	assert.Nil(t, err)
+
+	assert.Len(t, records, 3)
+	assert.Equal(t, records[0].Message, "saved")
+	assert.Equal(t, records[0].Fields, map[string]any{"id": int64(1)})
+	assert.Equal(t, records[1].Fields, map[string]any{"id": int64(2)})
+	assert.Equal(t, records[2].Level, slog.LevelWarn)
+	assert.Equal(t, traceIDs, []any{"abc123", "abc123", "abc123"})
+
+	// Without a handler, records are discarded
+	_, err = Eval(context.Background(), `log.error("ignored")`, WithEnv(Builtins()))
+	assert.Nil(t, err)
+
+	// Handler errors are raised in the script
+	failing := func(ctx context.Context, r object.LogRecord) error {
+		return errors.New("log sink unavailable")
+	}
+	result, err := Eval(context.Background(),
+		`try { log.info("x") } catch (e) { e.message() }`,
+		WithEnv(Builtins()), WithLogHandler(failing))
+	assert.Nil(t, err)
+	assert.Contains(t, result.(string), "log sink unavailable")
+}
+
 // Test global name validation between compile and run
 func TestGlobalNameValidation(t *testing.T) {
 	ctx := context.Background()
@@ -593,6 +728,81 @@ func TestArrayDestructureParam(t *testing.T) {
 	})
 }
 
+func TestNestedDestructureParam(t *testing.T) {
+	ctx := context.Background()
+
+	t.Run("object within object", func(t *testing.T) {
+		result, err := Eval(ctx, `
+			function foo({user: {name, age}}) { return name + ":" + string(age) }
+			foo({user: {name: "ada", age: 36}})
+		`)
+		assert.Nil(t, err)
+		assert.Equal(t, "ada:36", result)
+	})
+
+	t.Run("array within array", func(t *testing.T) {
+		result, err := Eval(ctx, `
+			function foo([a, [b, c]]) { return a + b + c }
+			foo([1, [2, 3]])
+		`)
+		assert.Nil(t, err)
+		assert.Equal(t, int64(6), result)
+	})
+
+	t.Run("object within array", func(t *testing.T) {
+		result, err := Eval(ctx, `
+			function foo([{x}, {x: y}]) { return [x, y] }
+			foo([{x: 1}, {x: 2}])
+		`)
+		assert.Nil(t, err)
+		assert.Equal(t, []any{int64(1), int64(2)}, result)
+	})
+
+	t.Run("array within object with defaults", func(t *testing.T) {
+		result, err := Eval(ctx, `
+			function foo({point: [x, y = 0]}) { return x + y }
+			foo({point: [5]})
+		`)
+		assert.Nil(t, err)
+		assert.Equal(t, int64(5), result)
+	})
+
+	t.Run("nested pattern with default", func(t *testing.T) {
+		result, err := Eval(ctx, `
+			function foo({opts: {verbose = false} = {}}) { return verbose }
+			foo({})
+		`)
+		assert.Nil(t, err)
+		assert.Equal(t, false, result)
+	})
+
+	t.Run("nested arrow pattern", func(t *testing.T) {
+		result, err := Eval(ctx, `
+			let f = ({a: {b}, c: [d, e]}) => b + d + e
+			f({a: {b: 1}, c: [2, 3]})
+		`)
+		assert.Nil(t, err)
+		assert.Equal(t, int64(6), result)
+	})
+
+	t.Run("nested names visible to closures", func(t *testing.T) {
+		result, err := Eval(ctx, `
+			function foo({a: [x, y]}) { return () => x * y }
+			foo({a: [6, 7]})()
+		`)
+		assert.Nil(t, err)
+		assert.Equal(t, int64(42), result)
+	})
+
+	t.Run("duplicate nested name", func(t *testing.T) {
+		_, err := Eval(ctx, `
+			function foo({a: [x], b: {x}}) { return x }
+		`)
+		assert.NotNil(t, err)
+		assert.Contains(t, err.Error(), `variable "x" already exists`)
+	})
+}
+
 func TestArrowFunctionDestructureParam(t *testing.T) {
 	ctx := context.Background()
 
@@ -1255,6 +1465,25 @@ func TestGoFuncInEnv(t *testing.T) {
 		assert.Nil(t, err)
 		assert.Equal(t, result, "success")
 	})
+	t.Run("host callbacks as values", func(t *testing.T) {
+		env := map[string]any{
+			"multiplier": func(n int) func(int) int {
+				return func(x int) int { return x * n }
+			},
+			"greeter": func(greeting string) object.BuiltinFunction {
+				return func(ctx context.Context, args ...object.Object) (object.Object, error) {
+					return object.NewString(greeting + ", " + args[0].String()), nil
+				}
+			},
+		}
+		result, err := Eval(ctx, `
+			let handlers = {triple: multiplier(3), hello: greeter("hello")}
+			let results = [handlers.triple(5), handlers.hello("ada"), [1, 2].map(multiplier(10))]
+			results
+		`, WithEnv(env))
+		assert.Nil(t, err)
+		assert.Equal(t, result, []any{int64(15), "hello, ada", []any{int64(10), int64(20)}})
+	})
 }
 
 type TestTweet struct {
@@ -2030,3 +2259,176 @@ func TestBasicScriptingLoops(t *testing.T) {
 	assert.Nil(t, err)
 	assert.Equal(t, result, int64(6)) // 1 + 2 + 3
 }
+
+func TestQuote(t *testing.T) {
+	ctx := context.Background()
+	env := WithEnv(Builtins())
+
+	// Unquoted values are spliced in when the quote is built
+	result, err := Eval(ctx, `
+	let limit = 100
+	let cond = quote { amount > unquote(limit) }
+	let rule = quote {
+		if (unquote(cond)) { unquote("flag") } else { "ok" }
+	}
+	let nested = quote { quote { unquote(x) } }
+	[
+		type(cond),
+		cond.source,
+		rule.source,
+		run(rule, {amount: 500}),
+		run(rule, {amount: 5}),
+		nested.source,
+		run(nested, {x: [1, "a"]}).source,
+	]
+	`, env)
+	assert.Nil(t, err)
+	assert.Equal(t, result, []any{
+		"quote",
+		"amount > 100",
+		`if (amount > 100) { "flag" } else { "ok" }`,
+		"flag",
+		"ok",
+		"quote { unquote(x) }",
+		`[1, "a"]`,
+	})
+
+	result, err = Eval(ctx, `run(compile("price * qty"), {price: 2, qty: 3})`, env)
+	assert.Nil(t, err)
+	assert.Equal(t, result, int64(6))
+
+	_, err = Eval(ctx, `compile("price *")`, env)
+	assert.NotNil(t, err)
+
+	_, err = Eval(ctx, `quote { unquote(len) }`, env)
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "cannot unquote a value of type builtin")
+
+	_, err = Eval(ctx, `quote { unquote(1, 2) }`, env)
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), "unquote takes exactly one argument")
+}
+
+func TestStringBuilder(t *testing.T) {
+	result, err := Eval(context.Background(), `
+	let sb = stringbuilder("items:")
+	let items = [1, 2, 3]
+	items.each(x => { sb.write(" ").write(string(x)) })
+	let result = [type(sb), sb.to_string(), string(sb), sb.len(), sb.reset().to_string()]
+	result
+	`, WithEnv(Builtins()))
+	assert.Nil(t, err)
+	assert.Equal(t, result, []any{"stringbuilder", "items: 1 2 3", "items: 1 2 3", int64(12), ""})
+}
+
+func TestIterBuiltins(t *testing.T) {
+	result, err := Eval(context.Background(), `
+	let rows = range(1000000000)
+	let pairs = take(enumerate(skip(rows, 5), 1), 3)
+	let sums = []
+	window(pairs, 2).each(([a, b]) => { sums.append(a[1] + b[1]) })
+	let result = [list(pairs), sums, list(zip(["x", "y"], pairs))]
+	result
+	`, WithEnv(Builtins()))
+	assert.Nil(t, err)
+	assert.Equal(t, result, []any{
+		[]any{[]any{int64(1), int64(5)}, []any{int64(2), int64(6)}, []any{int64(3), int64(7)}},
+		[]any{int64(11), int64(13)},
+		[]any{[]any{"x", []any{int64(1), int64(5)}}, []any{"y", []any{int64(2), int64(6)}}},
+	})
+}
+
+func TestSortedOptions(t *testing.T) {
+	result, err := Eval(context.Background(), `
+	let users = [{name: "ann", age: 31}, {name: "bob", age: 25}, {name: "cy", age: 31}]
+	let oldest = sorted(users, {key: u => u.age, reverse: true}).map(u => u.name)
+	let result = [oldest, sorted(range(4), {reverse: true}), sorted(users.map(u => u.name), (a, b) => len(a) < len(b))]
+	result
+	`, WithEnv(Builtins()))
+	assert.Nil(t, err)
+	assert.Equal(t, result, []any{
+		[]any{"ann", "cy", "bob"},
+		[]any{int64(3), int64(2), int64(1), int64(0)},
+		[]any{"cy", "ann", "bob"},
+	})
+}
+
+func TestToSourceRoundTrip(t *testing.T) {
+	ctx := context.Background()
+	result, err := Eval(ctx, `
+	let config = {
+		name: "svc\t1",
+		"max-retries": 3,
+		ratio: 0.5,
+		limit: decimal("1.50"),
+		tags: ["a", null, [true, false]],
+		check: quote { x > 1 },
+	}
+	[
+		equal(eval(to_source(config)), config),
+		equal(eval(to_source(config, "\t")), config),
+	]
+	`, WithEnv(Builtins()))
+	assert.Nil(t, err)
+	assert.Equal(t, result, []any{true, true})
+}
+
+func TestFromObject(t *testing.T) {
+	type config struct {
+		Name    string   `risor:"name"`
+		Retries int      `risor:"retries"`
+		Tags    []string `risor:"tags,omitempty"`
+	}
+	ctx := context.Background()
+	source := `{name: "svc", retries: 1 + 2, tags: ["a", "b"]}`
+
+	result, err := Eval(ctx, source)
+	assert.Nil(t, err)
+	cfg, err := FromObject[config](result)
+	assert.Nil(t, err)
+	assert.Equal(t, cfg, config{Name: "svc", Retries: 3, Tags: []string{"a", "b"}})
+
+	raw, err := Eval(ctx, source, WithRawResult())
+	assert.Nil(t, err)
+	cfg, err = FromObject[config](raw)
+	assert.Nil(t, err)
+	assert.Equal(t, cfg.Retries, 3)
+
+	result, err = Eval(ctx, `{name: "svc", retries: "3"}`)
+	assert.Nil(t, err)
+	_, err = FromObject[config](result)
+	assert.NotNil(t, err)
+	assert.Contains(t, err.Error(), `field "retries"`)
+}
+
+func TestHelp(t *testing.T) {
+	ctx := context.Background()
+	env := WithEnv(Builtins())
+
+	result, err := Eval(ctx, `
+function greet(name, greeting="hello", ...rest) {
+    "Return a greeting for name"
+    return greeting + " " + name
+}
+help(greet)`, env)
+	assert.Nil(t, err)
+	assert.Equal(t, result, "greet(name, greeting=\"hello\", ...rest)\n\nReturn a greeting for name")
+
+	// A lone string is the function's result, not its docstring
+	result, err = Eval(ctx, `help(x => "not a doc")`, env)
+	assert.Nil(t, err)
+	assert.Equal(t, result, "function(x)")
+
+	result, err = Eval(ctx, `help(math.sqrt)`, env)
+	assert.Nil(t, err)
+	assert.Contains(t, result.(string), "math.sqrt(x) -> float")
+
+	result, err = Eval(ctx, `help(math)`, env)
+	assert.Nil(t, err)
+	assert.Contains(t, result.(string), "module math\n\nMathematical functions and constants")
+	assert.Contains(t, result.(string), "\npi: float\n")
+
+	result, err = Eval(ctx, `len.__doc__`, env)
+	assert.Nil(t, err)
+	assert.Equal(t, result, "Return length of container")
+}
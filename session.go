@@ -0,0 +1,111 @@
+package risor
+
+import (
+	"context"
+	"slices"
+	"sync"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/vm"
+)
+
+// Session runs a series of scripts on one VM, so that each script can use
+// the globals and functions defined by the scripts run before it. This lets
+// a host run a prelude of helpers ahead of a user's script without
+// concatenating their sources:
+//
+//	session, err := risor.NewSession(ctx, risor.WithEnv(risor.Builtins()))
+//	if err != nil {
+//	    return err
+//	}
+//	if _, err := session.Eval(ctx, prelude); err != nil {
+//	    return err
+//	}
+//	result, err := session.Eval(ctx, userScript)
+//
+// Every script is compiled and run with the options given to NewSession.
+// Values assigned by one script are seen by the next, including new values
+// for names provided with WithEnv. If a script fails, the globals it assigned
+// before the error keep their values. Scripts run one at a time, so a Session
+// may be used from multiple goroutines.
+type Session struct {
+	mu      sync.Mutex
+	opts    []Option
+	o       *options
+	machine *vm.VirtualMachine
+}
+
+// NewSession returns a Session whose globals are initially the environment
+// provided with WithEnv. If WithPrelude is given, the prelude is run once
+// here, and its definitions are shared by all the scripts in the session.
+func NewSession(ctx context.Context, opts ...Option) (*Session, error) {
+	o := collectOptions(opts...)
+	if err := o.prelude.validate(o.env); err != nil {
+		return nil, err
+	}
+	machine, err := vm.NewEmpty(o.vmOpts()...)
+	if err != nil {
+		return nil, err
+	}
+	if err := o.prelude.runOn(ctx, machine); err != nil {
+		return nil, err
+	}
+	return &Session{opts: opts, o: o, machine: machine}, nil
+}
+
+// Eval compiles the source so that it can refer to the globals defined so far
+// in the session, then runs it. Like Run, it honors the script's metadata
+// block, whose limits apply to this script only. The result is converted
+// following the rules described on Run.
+func (s *Session) Eval(ctx context.Context, source string) (any, error) {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	opts := append(slices.Clone(s.opts), withGlobalNames(s.machine.GlobalNames()))
+	code, err := Compile(ctx, source, opts...)
+	if err != nil {
+		return nil, err
+	}
+	// Honor the requirements and limits declared in the script's metadata,
+	// for this script only
+	o := *s.o
+	o.globalNames = s.machine.GlobalNames()
+	if meta, err := ParseMetadata(source); err != nil {
+		return nil, err
+	} else if err := meta.apply(&o); err != nil {
+		return nil, err
+	}
+	result, err := vm.RunCodeOnVM(ctx, s.machine, code,
+		vm.WithTimeout(o.timeout), vm.WithMaxSteps(o.maxSteps))
+	if err != nil {
+		return nil, err
+	}
+	return s.o.convertResult(result), nil
+}
+
+// Get returns the value of a global defined in the session, converted
+// following the same rules as results. If the global is not defined, an error
+// wrapping vm.ErrGlobalNotFound is returned.
+func (s *Session) Get(name string) (any, error) {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	value, err := s.machine.Get(name)
+	if err != nil || value == nil {
+		return nil, err
+	}
+	return s.o.convertResult(value), nil
+}
+
+// GlobalNames returns the names of the globals defined in the session,
+// including those provided with WithEnv.
+func (s *Session) GlobalNames() []string {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	return s.machine.GlobalNames()
+}
+
+// withGlobalNames declares globals that exist in addition to the environment
+// when compiling a script.
+func withGlobalNames(names []string) Option {
+	return func(o *options) {
+		o.globalNames = append(o.globalNames, names...)
+	}
+}
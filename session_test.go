@@ -0,0 +1,87 @@
+package risor
+
+import (
+	"context"
+	"errors"
+	"testing"
+
+	"github.com/deepnoodle-ai/risor/v2/pkg/vm"
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+func TestSession(t *testing.T) {
+	ctx := context.Background()
+	session, err := NewSession(ctx, WithEnv(Builtins()), WithEnv(map[string]any{"limit": 3}))
+	assert.Nil(t, err)
+
+	_, err = session.Eval(ctx, `
+	let calls = 0
+	function clamp(n) {
+		calls++
+		if (n > limit) {
+			return limit
+		}
+		return n
+	}
+	`)
+	assert.Nil(t, err)
+
+	result, err := session.Eval(ctx, `[clamp(1), clamp(10)]`)
+	assert.Nil(t, err)
+	assert.Equal(t, result, []any{int64(1), int64(3)})
+
+	// Values assigned by one script are seen by the next
+	_, err = session.Eval(ctx, `limit = 5`)
+	assert.Nil(t, err)
+	result, err = session.Eval(ctx, `clamp(10) + len("ab")`)
+	assert.Nil(t, err)
+	assert.Equal(t, result, int64(7))
+
+	calls, err := session.Get("calls")
+	assert.Nil(t, err)
+	assert.Equal(t, calls, int64(3))
+	assert.Contains(t, session.GlobalNames(), "clamp")
+
+	_, err = session.Get("missing")
+	assert.True(t, errors.Is(err, vm.ErrGlobalNotFound))
+}
+
+func TestSessionErrors(t *testing.T) {
+	ctx := context.Background()
+	session, err := NewSession(ctx, WithEnv(Builtins()))
+	assert.Nil(t, err)
+
+	_, err = session.Eval(ctx, `let x = 1; x = 2; throw error("failed")`)
+	assert.NotNil(t, err)
+
+	// Globals assigned before the error keep their values
+	result, err := session.Eval(ctx, `x + 1`)
+	assert.Nil(t, err)
+	assert.Equal(t, result, int64(3))
+
+	_, err = session.Eval(ctx, `undefined_name`)
+	assert.NotNil(t, err)
+}
+
+func TestSessionMetadata(t *testing.T) {
+	ctx := context.Background()
+	session, err := NewSession(ctx, WithEnv(Builtins()))
+	assert.Nil(t, err)
+
+	_, err = session.Eval(ctx, "/*---\nrequires: [api]\n---*/\napi")
+	assert.ErrorIs(t, err, ErrRequirementNotMet)
+
+	// Globals defined by earlier scripts satisfy requirements
+	_, err = session.Eval(ctx, "let api = 1")
+	assert.Nil(t, err)
+	result, err := session.Eval(ctx, "/*---\nrequires: [api]\n---*/\napi")
+	assert.Nil(t, err)
+	assert.Equal(t, result, int64(1))
+
+	// Limits apply to the script that declares them, not to later ones
+	loop := `let sum = 0; list(range(100000)).each(function(i) { sum = sum + i }); sum`
+	_, err = session.Eval(ctx, "/*---\nmax_steps: 5000\n---*/\n"+loop)
+	assert.ErrorIs(t, err, ErrStepLimitExceeded)
+	_, err = session.Eval(ctx, loop)
+	assert.Nil(t, err)
+}
@@ -2,9 +2,12 @@ package risor_test
 
 import (
 	"context"
+	"fmt"
 	"log"
+	"strings"
 	"testing"
 
+	"github.com/deepnoodle-ai/risor/v2"
 	"github.com/deepnoodle-ai/risor/v2/pkg/compiler"
 	"github.com/deepnoodle-ai/risor/v2/pkg/parser"
 	"github.com/deepnoodle-ai/risor/v2/pkg/vm"
@@ -44,3 +47,84 @@ func BenchmarkRisor_Fibonacci35(b *testing.B) {
 		}
 	}
 }
+
+// BenchmarkRisor_EvalWithBuiltins measures the per-evaluation overhead of a
+// small script run with the standard environment.
+func BenchmarkRisor_EvalWithBuiltins(b *testing.B) {
+	ctx := context.Background()
+	for i := 0; i < b.N; i++ {
+		result, err := risor.Eval(ctx, `len("hello") + math.abs(-1)`, risor.WithEnv(risor.Builtins()))
+		if err != nil {
+			b.Fatal(err)
+		}
+		if result != int64(6) {
+			b.Fatalf("unexpected result: %v", result)
+		}
+	}
+}
+
+// BenchmarkRisor_ParseLarge measures parse time and allocations for a large
+// script made of many small functions.
+func BenchmarkRisor_ParseLarge(b *testing.B) {
+	var sb strings.Builder
+	for i := 0; i < 1000; i++ {
+		fmt.Fprintf(&sb, `
+    function process_%d(items, threshold) {
+        let total = 0
+        let matches = items.filter(x => x.value >= threshold && x.name != "skip")
+        let names = matches.map(x => x.name.to_upper())
+        if (len(names) > 0) {
+            total = matches.reduce(0, (acc, x) => acc + x.value * 2.5)
+        }
+        return {names: names, total: total, label: "batch-%d"}
+    }
+    `, i, i)
+	}
+	script := sb.String()
+
+	ctx := context.Background()
+	b.ReportAllocs()
+	b.SetBytes(int64(len(script)))
+	b.ResetTimer()
+	for i := 0; i < b.N; i++ {
+		if _, err := parser.Parse(ctx, script, nil); err != nil {
+			b.Fatal(err)
+		}
+	}
+}
+
+// literalScript returns a script that builds a list of n map literals, each
+// holding further list and map literals.
+func literalScript(n int) string {
+	var sb strings.Builder
+	sb.WriteString("[\n")
+	for i := 0; i < n; i++ {
+		fmt.Fprintf(&sb, `    {id: %d, name: "row-%d", tags: ["a", "b", "c", "d"], pos: {x: %d, y: %d}, scores: [1, 2, 3, 4, 5, 6, 7, 8]},`+"\n", i, i, i, i*2)
+	}
+	sb.WriteString("]\n")
+	return sb.String()
+}
+
+// BenchmarkRisor_BuildLiterals measures the cost of building list and map
+// literals in the VM.
+func BenchmarkRisor_BuildLiterals(b *testing.B) {
+	ctx := context.Background()
+
+	ast, err := parser.Parse(ctx, literalScript(500), nil)
+	if err != nil {
+		log.Fatal(err)
+	}
+
+	code, err := compiler.Compile(ast, nil)
+	if err != nil {
+		log.Fatal(err)
+	}
+
+	b.ReportAllocs()
+	b.ResetTimer()
+	for i := 0; i < b.N; i++ {
+		if _, err := vm.Run(ctx, code); err != nil {
+			b.Fatal(err)
+		}
+	}
+}
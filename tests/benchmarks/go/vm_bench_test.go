@@ -0,0 +1,102 @@
+package risor_test
+
+import (
+	"context"
+	"testing"
+
+	"github.com/deepnoodle-ai/risor/v2"
+)
+
+// vmBenchmarks are small workloads that each stress one area of the VM. They
+// give performance work a baseline to compare against, e.g. with benchstat:
+//
+//	go test -bench=BenchmarkVM -benchmem -count=10 ./tests/benchmarks/go
+var vmBenchmarks = []struct {
+	name   string
+	script string
+	want   any
+}{
+	{
+		name:   "arithmetic",
+		script: `list(range(10000)).reduce(0, (acc, i) => acc + i * 3 % 7 - 1)`,
+		want:   int64(19999),
+	},
+	{
+		name: "int_recursion",
+		script: `
+		function fib(n) {
+			if (n < 2) {
+				return n
+			}
+			return fib(n - 1) + fib(n - 2)
+		}
+		fib(20)
+		`,
+		want: int64(6765),
+	},
+	{
+		name: "closure_calls",
+		script: `
+		function make_counter() {
+			let n = 0
+			return () => { n = n + 1; return n }
+		}
+		let counter = make_counter()
+		list(range(10000)).each(i => counter())
+		counter()
+		`,
+		want: int64(10001),
+	},
+	{
+		name: "string_building",
+		script: `
+		let parts = list(range(2000)).map(i => ` + "`item-${i}`" + `)
+		len(",".join(parts))
+		`,
+		want: int64(18889),
+	},
+	{
+		name: "map_operations",
+		script: `
+		let m = {}
+		list(range(5000)).each(i => {
+			let key = "k" + string(i % 100)
+			m[key] = m.get(key, 0) + i
+		})
+		len(m.keys())
+		`,
+		want: int64(100),
+	},
+	{
+		name: "exceptions",
+		script: `
+		let caught = list(range(2000)).map(i => try { throw error("boom") } catch (e) { i })
+		len(caught)
+		`,
+		want: int64(2000),
+	},
+}
+
+func BenchmarkVM(b *testing.B) {
+	ctx := context.Background()
+	env := risor.Builtins()
+	for _, bm := range vmBenchmarks {
+		b.Run(bm.name, func(b *testing.B) {
+			code, err := risor.Compile(ctx, bm.script, risor.WithEnv(env))
+			if err != nil {
+				b.Fatal(err)
+			}
+			b.ReportAllocs()
+			b.ResetTimer()
+			for i := 0; i < b.N; i++ {
+				result, err := risor.Run(ctx, code, risor.WithEnv(env))
+				if err != nil {
+					b.Fatal(err)
+				}
+				if result != bm.want {
+					b.Fatalf("unexpected result: %v", result)
+				}
+			}
+		})
+	}
+}
@@ -0,0 +1,90 @@
+package differential
+
+import (
+	"context"
+	"errors"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/deepnoodle-ai/risor/v2"
+	"github.com/deepnoodle-ai/risor/v2/pkg/interp"
+	"github.com/deepnoodle-ai/risor/v2/pkg/object"
+	"github.com/deepnoodle-ai/risor/v2/pkg/parser"
+	"github.com/deepnoodle-ai/wonton/assert"
+)
+
+// TestDifferential runs each script in testdata on the VM and compares the
+// result with the script's .golden file. Scripts that the interp package
+// supports must also give the same result there.
+//
+// To update golden files after an intended change in results:
+//
+//	UPDATE_GOLDEN=1 go test ./tests/differential/...
+func TestDifferential(t *testing.T) {
+	files, err := filepath.Glob("testdata/*.risor")
+	assert.Nil(t, err)
+	assert.Greater(t, len(files), 0)
+
+	for _, file := range files {
+		name := strings.TrimSuffix(filepath.Base(file), ".risor")
+		t.Run(name, func(t *testing.T) {
+			source, err := os.ReadFile(file)
+			assert.Nil(t, err)
+			actual := runVM(string(source))
+
+			goldenFile := strings.TrimSuffix(file, ".risor") + ".golden"
+			if os.Getenv("UPDATE_GOLDEN") == "1" {
+				assert.Nil(t, os.WriteFile(goldenFile, []byte(actual+"\n"), 0o644))
+				return
+			}
+			expected, err := os.ReadFile(goldenFile)
+			assert.Nil(t, err)
+			assert.Equal(t, actual, strings.TrimSpace(string(expected)))
+
+			reference, err := runReference(string(source))
+			if errors.Is(err, interp.ErrUnsupported) {
+				t.Skipf("checked against the golden value only: %v", err)
+			}
+			assert.Equal(t, reference, actual)
+		})
+	}
+}
+
+// outcome describes the result of a script. Errors are compared by
+// occurrence only, since the reference doesn't reproduce the VM's messages.
+func outcome(result object.Object, err error) string {
+	if err != nil {
+		return "error"
+	}
+	return result.Inspect()
+}
+
+func runVM(source string) string {
+	result, err := risor.Eval(context.Background(), source,
+		risor.WithEnv(risor.Builtins()), risor.WithRawResult())
+	if err != nil {
+		return outcome(nil, err)
+	}
+	return outcome(result.(object.Object), nil)
+}
+
+func runReference(source string) (string, error) {
+	ctx := context.Background()
+	program, err := parser.Parse(ctx, source, nil)
+	if err != nil {
+		return outcome(nil, err), nil
+	}
+	globals := map[string]object.Object{}
+	for name, value := range risor.Builtins() {
+		if obj, ok := value.(object.Object); ok {
+			globals[name] = obj
+		}
+	}
+	result, err := interp.Eval(ctx, program, globals)
+	if errors.Is(err, interp.ErrUnsupported) {
+		return "", err
+	}
+	return outcome(result, err), nil
+}